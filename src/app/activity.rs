@@ -0,0 +1,166 @@
+//! What each agent is doing right now.
+//!
+//! A ToolUse opens a call and the next ToolResult for the same tool closes it.
+//! The newest call still open is the agent's current activity, shown with a
+//! running timer in the agent list and the agent popup ("Bash cargo test … 34s").
+//! A call open for longer than the long-call limit (settings) is flagged once:
+//! hung commands otherwise go unnoticed until someone wonders why a run stalled.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::model::{ToolName, TranscriptEvent, TranscriptEventKind};
+use crate::view::components::format::format_elapsed;
+
+/// Open calls kept per agent; calls whose result never arrives drop off first.
+const MAX_OPEN_CALLS: usize = 16;
+
+/// Longest input summary shown in the activity line.
+const INPUT_MAX_CHARS: usize = 40;
+
+/// A tool call waiting for its result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunningTool {
+    pub tool_name: ToolName,
+    pub input_summary: String,
+    pub started_at: DateTime<Utc>,
+    /// Open for longer than the long-call limit
+    pub overdue: bool,
+}
+
+impl RunningTool {
+    /// Activity line as of `now`: `Bash cargo test … 34s`.
+    pub fn label(&self, now: DateTime<Utc>) -> String {
+        format!("{} {}", self.tool_name, self.detail(now))
+    }
+
+    /// The activity line without the tool name: `cargo test … 34s`.
+    pub fn detail(&self, now: DateTime<Utc>) -> String {
+        let input = self.input_summary.split_whitespace().collect::<Vec<_>>().join(" ");
+        let input = if input.chars().count() > INPUT_MAX_CHARS {
+            let kept: String = input.chars().take(INPUT_MAX_CHARS - 1).collect();
+            format!("{}…", kept.trim_end())
+        } else {
+            input
+        };
+        let secs = (now - self.started_at).num_seconds().max(0);
+        if input.is_empty() {
+            format!("… {}", format_elapsed(secs))
+        } else {
+            format!("{} … {}", input, format_elapsed(secs))
+        }
+    }
+}
+
+/// One agent's open tool calls, oldest first.
+#[derive(Debug, Clone, Default)]
+pub struct OpenCalls(Vec<RunningTool>);
+
+impl OpenCalls {
+    /// Open a call on ToolUse; close the newest open call of the same tool on
+    /// ToolResult (the newest call of any tool when the result's tool is unknown).
+    pub fn observe(&mut self, event: &TranscriptEvent) {
+        match &event.kind {
+            TranscriptEventKind::ToolUse { tool_name, input_summary } => {
+                if self.0.len() >= MAX_OPEN_CALLS {
+                    self.0.remove(0);
+                }
+                self.0.push(RunningTool {
+                    tool_name: tool_name.clone(),
+                    input_summary: input_summary.clone(),
+                    started_at: event.timestamp,
+                    overdue: false,
+                });
+            }
+            TranscriptEventKind::ToolResult { tool_name, .. } => {
+                let closed = self.0.iter().rposition(|call| call.tool_name == *tool_name).or_else(|| {
+                    if tool_name.as_str() == "unknown" { self.0.len().checked_sub(1) } else { None }
+                });
+                if let Some(idx) = closed {
+                    self.0.remove(idx);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The newest call still waiting for its result.
+    pub fn current(&self) -> Option<&RunningTool> {
+        self.0.last()
+    }
+
+    /// The open call started by the ToolUse `event`, if it has not been answered.
+    pub fn opened_by(&self, event: &TranscriptEvent) -> Option<&RunningTool> {
+        let TranscriptEventKind::ToolUse { tool_name, .. } = &event.kind else {
+            return None;
+        };
+        self.0.iter().find(|call| call.started_at == event.timestamp && call.tool_name == *tool_name)
+    }
+
+    /// Flag calls open for longer than `limit` as of `now`.
+    ///
+    /// # Returns
+    /// The calls flagged by this pass (each call is flagged once).
+    pub fn flag_overdue(&mut self, now: DateTime<Utc>, limit: Duration) -> Vec<RunningTool> {
+        let mut flagged = Vec::new();
+        for call in self.0.iter_mut().filter(|call| !call.overdue && now - call.started_at > limit) {
+            call.overdue = true;
+            flagged.push(call.clone());
+        }
+        flagged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(at: DateTime<Utc>, kind: TranscriptEventKind) -> TranscriptEvent {
+        TranscriptEvent::new(at, kind)
+    }
+
+    fn tool_use(tool: &str, input: &str) -> TranscriptEventKind {
+        TranscriptEventKind::ToolUse { tool_name: ToolName::new(tool), input_summary: input.into() }
+    }
+
+    fn tool_result(tool: &str) -> TranscriptEventKind {
+        TranscriptEventKind::ToolResult {
+            tool_name: ToolName::new(tool),
+            result_summary: String::new(),
+            duration_ms: None,
+            is_error: false,
+        }
+    }
+
+    #[test]
+    fn newest_open_call_is_current_until_its_result() {
+        let t0: DateTime<Utc> = "2026-03-18T10:00:00Z".parse().unwrap();
+        let mut calls = OpenCalls::default();
+        calls.observe(&event(t0, tool_use("Bash", "cargo   test")));
+        calls.observe(&event(t0 + Duration::seconds(2), tool_use("Read", "src/lib.rs")));
+        assert_eq!(calls.current().unwrap().tool_name.as_str(), "Read");
+
+        calls.observe(&event(t0 + Duration::seconds(3), tool_result("Read")));
+        let current = calls.current().unwrap();
+        assert_eq!(current.label(t0 + Duration::seconds(34)), "Bash cargo test … 34s");
+
+        calls.observe(&event(t0 + Duration::seconds(40), tool_result("unknown")));
+        assert!(calls.current().is_none());
+    }
+
+    #[test]
+    fn calls_past_the_limit_are_flagged_once() {
+        let t0: DateTime<Utc> = "2026-03-18T10:00:00Z".parse().unwrap();
+        let started = event(t0, tool_use("Bash", "sleep 600"));
+        let mut calls = OpenCalls::default();
+        calls.observe(&started);
+        calls.observe(&event(t0 + Duration::minutes(4), tool_use("Read", "src/lib.rs")));
+
+        let limit = Duration::minutes(5);
+        assert!(calls.flag_overdue(t0 + Duration::minutes(5), limit).is_empty());
+        let flagged = calls.flag_overdue(t0 + Duration::minutes(6), limit);
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].tool_name.as_str(), "Bash");
+        assert!(calls.flag_overdue(t0 + Duration::minutes(7), limit).is_empty());
+        assert!(calls.opened_by(&started).is_some_and(|call| call.overdue));
+    }
+}
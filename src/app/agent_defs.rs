@@ -0,0 +1,179 @@
+//! Agent definitions from `.claude/agents/`, shown next to the agents started from them.
+//! File format and reload behaviour: `docs/design.md`, "Agent definitions".
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::model::Agent;
+
+/// Directory under `.claude/` holding the definitions.
+pub const AGENTS_DIR: &str = "agents";
+
+/// Largest definition file read.
+const MAX_DEF_BYTES: u64 = 64 * 1024;
+
+/// One agent definition's front matter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentDefinition {
+    pub name: String,
+    pub description: Option<String>,
+    pub model: Option<String>,
+    /// Allowed tools (None: every tool)
+    pub tools: Option<Vec<String>>,
+    pub path: PathBuf,
+}
+
+impl AgentDefinition {
+    /// Pure function: the allowed tools as one line.
+    pub fn tools_label(&self) -> String {
+        match &self.tools {
+            None => "all tools".to_string(),
+            Some(tools) if tools.is_empty() => "no tools".to_string(),
+            Some(tools) => tools.join(", "),
+        }
+    }
+}
+
+/// Pure function: strip quotes from a front matter value.
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    ["\"", "'"]
+        .iter()
+        .find_map(|q| value.strip_prefix(q).and_then(|v| v.strip_suffix(q)))
+        .unwrap_or(value)
+}
+
+/// Pure function: a definition from a file's content. The name defaults to
+/// the file stem; a file without front matter is no definition.
+/// `tools` may be comma-separated, a `[a, b]` list or `- a` items.
+pub fn parse(path: &Path, content: &str) -> Option<AgentDefinition> {
+    let mut lines = content.lines();
+    if lines.next()?.trim() != "---" {
+        return None;
+    }
+    let stem = path.file_stem()?.to_string_lossy().into_owned();
+    let mut def = AgentDefinition { name: stem, description: None, model: None, tools: None, path: path.to_path_buf() };
+    let mut in_tools = false;
+    for line in lines.by_ref() {
+        if line.trim() == "---" {
+            return Some(def);
+        }
+        if in_tools {
+            if let Some(item) = line.trim().strip_prefix("- ") {
+                def.tools.get_or_insert_with(Vec::new).push(unquote(item).to_string());
+                continue;
+            }
+            in_tools = false;
+        }
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let value = unquote(value);
+        match key.trim() {
+            "name" if !value.is_empty() => def.name = value.to_string(),
+            "description" if !value.is_empty() => def.description = Some(value.to_string()),
+            "model" if !value.is_empty() => def.model = Some(value.to_string()),
+            // Items follow on the next lines
+            "tools" if value.is_empty() => in_tools = true,
+            "tools" => {
+                let list = value.trim_start_matches('[').trim_end_matches(']');
+                def.tools = Some(list.split(',').map(unquote).filter(|t| !t.is_empty()).map(str::to_string).collect());
+            }
+            _ => {}
+        }
+    }
+    // Unterminated front matter
+    None
+}
+
+/// The definition files under `claude_dir` and their modification times,
+/// sorted by path; compared between polls to spot changes.
+/// I/O: lists the agents directory.
+pub fn stamp(claude_dir: &Path) -> Vec<(PathBuf, SystemTime)> {
+    let Ok(entries) = std::fs::read_dir(claude_dir.join(AGENTS_DIR)) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(PathBuf, SystemTime)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+        .filter_map(|path| {
+            let modified = path.metadata().ok().filter(|m| m.is_file())?.modified().ok()?;
+            Some((path, modified))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Read and parse the stamped files; unreadable and malformed ones are skipped.
+/// I/O: reads each file.
+pub fn load(files: &[(PathBuf, SystemTime)]) -> Vec<AgentDefinition> {
+    use std::io::Read;
+    files
+        .iter()
+        .filter_map(|(path, _)| {
+            let mut bytes = Vec::new();
+            std::fs::File::open(path).ok()?.take(MAX_DEF_BYTES).read_to_end(&mut bytes).ok()?;
+            parse(path, &String::from_utf8_lossy(&bytes))
+        })
+        .collect()
+}
+
+/// Pure function: the definition an agent was started from, matched by its
+/// full type, then by the type's name.
+pub fn for_agent<'a>(definitions: &'a [AgentDefinition], agent: &Agent) -> Option<&'a AgentDefinition> {
+    let raw = agent.agent_type.as_deref()?.trim();
+    let name = agent.kind()?.name;
+    definitions
+        .iter()
+        .find(|def| def.name == raw)
+        .or_else(|| definitions.iter().find(|def| def.name == name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn front_matter_sets_model_tools_and_description() {
+        let path = Path::new(".claude/agents/reviewer.md");
+        let def = parse(
+            path,
+            "---\nname: code-reviewer\ndescription: \"Reviews diffs\"\nmodel: sonnet\ntools: Read, Grep , Glob\n---\nYou review.",
+        )
+        .unwrap();
+        assert_eq!(def.name, "code-reviewer");
+        assert_eq!(def.description.as_deref(), Some("Reviews diffs"));
+        assert_eq!(def.model.as_deref(), Some("sonnet"));
+        assert_eq!(def.tools_label(), "Read, Grep, Glob");
+
+        let listed = parse(path, "---\ntools:\n  - Bash\n  - 'Edit'\nmodel: opus\n---\n").unwrap();
+        assert_eq!((listed.name.as_str(), listed.tools_label()), ("reviewer", "Bash, Edit".to_string()));
+        assert_eq!(listed.model.as_deref(), Some("opus"));
+        assert_eq!(parse(path, "---\ntools: [Read]\n---").unwrap().tools, Some(vec!["Read".to_string()]));
+        assert_eq!(parse(path, "---\nmodel: haiku\n---").unwrap().tools_label(), "all tools");
+
+        assert_eq!(parse(path, "# No front matter"), None);
+        assert_eq!(parse(path, "---\nname: open"), None);
+    }
+
+    #[test]
+    fn agents_match_their_definition_by_type_or_name() {
+        let tmp = tempfile::tempdir().unwrap();
+        let agents_dir = tmp.path().join(AGENTS_DIR);
+        std::fs::create_dir_all(&agents_dir).unwrap();
+        std::fs::write(agents_dir.join("reviewer.md"), "---\nname: code-reviewer\nmodel: sonnet\n---\n").unwrap();
+        std::fs::write(agents_dir.join("notes.txt"), "---\nname: notes\n---\n").unwrap();
+        std::fs::write(agents_dir.join("broken.md"), "no front matter").unwrap();
+
+        let files = stamp(tmp.path());
+        assert_eq!(files.len(), 2);
+        let definitions = load(&files);
+        assert_eq!(definitions.len(), 1);
+
+        let mut agent = Agent::new("a01", Utc::now());
+        assert_eq!(for_agent(&definitions, &agent), None);
+        agent.agent_type = Some("loom:code-reviewer".into());
+        assert_eq!(for_agent(&definitions, &agent).and_then(|d| d.model.as_deref()), Some("sonnet"));
+    }
+}
@@ -0,0 +1,243 @@
+//! Agent list grouping and type filter.
+//!
+//! A session that spawns thirty reviewers and thirty implementers is hard to
+//! scan as one flat list. The agent list can be split into one collapsible
+//! section per agent type (`T`, `Enter` on a header folds it) and narrowed to a
+//! single type (`t` cycles through them). `ui.selected_agent_index` indexes the
+//! rows built here, section headers included.
+
+use std::collections::BTreeSet;
+
+use crate::app::cost_history::UNTYPED;
+use crate::app::AppState;
+use crate::model::{Agent, AgentId};
+
+/// Pure function: the type an agent is grouped and filtered under.
+pub fn agent_type(agent: &Agent) -> &str {
+    agent.agent_type.as_deref().unwrap_or(UNTYPED)
+}
+
+/// How the agent list is arranged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentGrouping {
+    /// One section per agent type
+    pub grouped: bool,
+    /// Types whose section is folded to its header
+    pub collapsed: BTreeSet<String>,
+    /// Only agents of this type are listed
+    pub type_filter: Option<String>,
+}
+
+impl AgentGrouping {
+    /// Pure function: the filter after this one: every type, then each of
+    /// `types` in order, then every type again.
+    pub fn next_filter(&self, types: &[String]) -> Option<String> {
+        match &self.type_filter {
+            None => types.first().cloned(),
+            Some(current) => types.iter().skip_while(|t| *t != current).nth(1).cloned(),
+        }
+    }
+
+    pub fn toggle_collapsed(&mut self, agent_type: &str) {
+        if !self.collapsed.remove(agent_type) {
+            self.collapsed.insert(agent_type.to_string());
+        }
+    }
+
+    /// Plain list of every agent, as without grouping.
+    pub fn is_flat(&self) -> bool {
+        !self.grouped && self.type_filter.is_none()
+    }
+}
+
+/// One line of the agent list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgentRow {
+    /// Section header with the number of agents in the section
+    Group { agent_type: String, count: usize, collapsed: bool },
+    Agent(AgentId),
+}
+
+impl AgentRow {
+    pub fn agent_id(&self) -> Option<&AgentId> {
+        match self {
+            Self::Agent(id) => Some(id),
+            Self::Group { .. } => None,
+        }
+    }
+
+    /// Same line across regrouping: the same agent, or the same section.
+    fn same_line(&self, other: &AgentRow) -> bool {
+        match (self, other) {
+            (Self::Agent(a), Self::Agent(b)) => a == b,
+            (Self::Group { agent_type: a, .. }, Self::Group { agent_type: b, .. }) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// Pure function: agent types present among the agents, sorted.
+pub fn agent_types(state: &AppState) -> Vec<String> {
+    let types: BTreeSet<&str> = state.domain.agents.values().map(agent_type).collect();
+    types.into_iter().map(str::to_string).collect()
+}
+
+/// Pure function: the agent list's rows. Agents keep their sorted order
+/// (active first, newest first); sections are ordered by type name.
+pub fn agent_rows(state: &AppState) -> Vec<AgentRow> {
+    let grouping = &state.ui.agent_grouping;
+    let agents: Vec<(&AgentId, &str)> = state
+        .sorted_agent_keys()
+        .iter()
+        .filter_map(|id| state.domain.agents.get(id).map(|agent| (id, agent_type(agent))))
+        .filter(|(_, t)| grouping.type_filter.as_deref().is_none_or(|filter| filter == *t))
+        .collect();
+
+    if !grouping.grouped {
+        return agents.into_iter().map(|(id, _)| AgentRow::Agent(id.clone())).collect();
+    }
+
+    let types: BTreeSet<&str> = agents.iter().map(|(_, t)| *t).collect();
+    let mut rows = Vec::new();
+    for section in types {
+        let members: Vec<&AgentId> = agents.iter().filter(|(_, t)| *t == section).map(|(id, _)| *id).collect();
+        let collapsed = grouping.collapsed.contains(section);
+        rows.push(AgentRow::Group { agent_type: section.to_string(), count: members.len(), collapsed });
+        if !collapsed {
+            rows.extend(members.into_iter().map(|id| AgentRow::Agent(id.clone())));
+        }
+    }
+    rows
+}
+
+/// The agent on the selected row; none when a section header is selected.
+pub fn selected_agent(state: &AppState) -> Option<&Agent> {
+    let idx = state.ui.selected_agent_index?;
+    let rows = agent_rows(state);
+    rows.get(idx).and_then(AgentRow::agent_id).and_then(|id| state.domain.agents.get(id))
+}
+
+/// Is a section header the selected row?
+pub fn header_selected(state: &AppState) -> bool {
+    let rows = agent_rows(state);
+    matches!(state.ui.selected_agent_index.and_then(|idx| rows.get(idx)), Some(AgentRow::Group { .. }))
+}
+
+/// Make `id` visible, dropping a type filter that hides it and unfolding its
+/// section, and return its row.
+pub fn reveal(state: &mut AppState, id: &AgentId) -> Option<usize> {
+    let section = agent_type(state.domain.agents.get(id)?).to_string();
+    let grouping = &mut state.ui.agent_grouping;
+    if grouping.type_filter.as_ref().is_some_and(|filter| *filter != section) {
+        grouping.type_filter = None;
+    }
+    grouping.collapsed.remove(&section);
+    agent_rows(state).iter().position(|row| row.agent_id() == Some(id))
+}
+
+/// Rearrange the list with `change`, keeping the selected agent (or its
+/// section's header, once folded away) selected.
+pub fn regroup(state: &mut AppState, change: impl FnOnce(&mut AgentGrouping)) {
+    let before = agent_rows(state);
+    let selected = state.ui.selected_agent_index.and_then(|idx| before.get(idx)).cloned();
+    change(&mut state.ui.agent_grouping);
+
+    let after = agent_rows(state);
+    let found = selected.as_ref().and_then(|row| {
+        after.iter().position(|other| row.same_line(other)).or_else(|| {
+            let id = row.agent_id()?;
+            let section = agent_type(state.domain.agents.get(id)?);
+            after.iter().position(|other| matches!(other, AgentRow::Group { agent_type, .. } if agent_type == section))
+        })
+    });
+    let idx = found.or(if after.is_empty() { None } else { Some(0) });
+    if idx.and_then(|i| after.get(i)) != selected.as_ref() {
+        state.ui.scroll_offsets.agent_events = 0;
+    }
+    state.ui.selected_agent_index = idx;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    /// Agents started one second apart, newest last in `types`.
+    fn state_with(types: &[Option<&str>]) -> AppState {
+        let mut state = AppState::new();
+        let start = Utc::now();
+        for (i, agent_type) in types.iter().enumerate() {
+            let id = format!("a{:02}", i);
+            let mut agent = Agent::new(id.as_str(), start + Duration::seconds(i as i64));
+            agent.agent_type = agent_type.map(str::to_string);
+            state.domain.agents.insert(AgentId::new(&id), agent);
+        }
+        state.recompute_sorted_keys();
+        state
+    }
+
+    fn ids(rows: &[AgentRow]) -> Vec<String> {
+        rows.iter()
+            .map(|row| match row {
+                AgentRow::Agent(id) => id.to_string(),
+                AgentRow::Group { agent_type, count, collapsed } => {
+                    format!("{}{} ({})", if *collapsed { "+" } else { "-" }, agent_type, count)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn grouped_rows_have_a_header_per_type_and_fold() {
+        let mut state = state_with(&[Some("reviewer"), Some("implementer"), Some("reviewer"), None]);
+        assert_eq!(ids(&agent_rows(&state)), ["a03", "a02", "a01", "a00"]);
+
+        state.ui.agent_grouping.grouped = true;
+        assert_eq!(
+            ids(&agent_rows(&state)),
+            ["-implementer (1)", "a01", "-reviewer (2)", "a02", "a00", "-untyped (1)", "a03"]
+        );
+
+        state.ui.agent_grouping.toggle_collapsed("reviewer");
+        assert_eq!(ids(&agent_rows(&state)), ["-implementer (1)", "a01", "+reviewer (2)", "-untyped (1)", "a03"]);
+
+        state.ui.agent_grouping.type_filter = Some("reviewer".into());
+        assert_eq!(ids(&agent_rows(&state)), ["+reviewer (2)"]);
+    }
+
+    #[test]
+    fn filter_cycles_through_types_then_back_to_all() {
+        let types = vec!["implementer".to_string(), "reviewer".to_string()];
+        let mut grouping = AgentGrouping::default();
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            grouping.type_filter = grouping.next_filter(&types);
+            seen.push(grouping.type_filter.clone());
+        }
+        assert_eq!(seen, [Some("implementer".to_string()), Some("reviewer".to_string()), None]);
+    }
+
+    #[test]
+    fn regroup_keeps_the_selection_on_the_same_agent_or_its_header() {
+        let mut state = state_with(&[Some("reviewer"), Some("implementer"), Some("reviewer")]);
+        // a00, the oldest reviewer, is last in the flat list
+        state.ui.selected_agent_index = Some(2);
+
+        regroup(&mut state, |g| g.grouped = true);
+        assert_eq!(selected_agent(&state).map(|a| a.id.as_str()), Some("a00"));
+
+        regroup(&mut state, |g| g.toggle_collapsed("reviewer"));
+        assert_eq!(agent_rows(&state)[state.ui.selected_agent_index.unwrap()], AgentRow::Group {
+            agent_type: "reviewer".into(),
+            count: 2,
+            collapsed: true,
+        });
+        assert!(selected_agent(&state).is_none());
+
+        // Jumping to a hidden agent unfolds its section and drops a filter that hides it
+        state.ui.agent_grouping.type_filter = Some("implementer".into());
+        let row = reveal(&mut state, &AgentId::new("a02")).unwrap();
+        assert_eq!(agent_rows(&state)[row], AgentRow::Agent(AgentId::new("a02")));
+        assert!(state.ui.agent_grouping.type_filter.is_none());
+    }
+}
@@ -0,0 +1,126 @@
+//! Agent kind icons and colors for the agent lists, stable across runs.
+//! Defaults and `agent_kinds` overrides: `docs/configuration.md`, "Agent kinds".
+
+use std::collections::BTreeMap;
+
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::app::severity;
+use crate::error::RuleError;
+use crate::model::{Agent, AgentKind, Theme};
+
+/// Icon of agents with no type.
+pub const UNTYPED_ICON: &str = "○";
+
+/// Icon of custom agent types.
+pub const CUSTOM_ICON: &str = "◆";
+
+/// Colors handed to custom types by a hash of their namespace or name.
+const CUSTOM_COLORS: [Color; 5] =
+    [Theme::ACCENT_WARM, Theme::SUCCESS, Theme::ACCENT_PURPLE, Theme::TOOL_WEBFETCH, Theme::WARNING];
+
+/// One `agent_kinds` entry; unset parts keep the default.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KindStyle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// A color name (see `app::severity`) or `#rrggbb`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+/// The configured overrides.
+#[derive(Debug, Clone, Default)]
+pub struct AgentKindStyles {
+    config: BTreeMap<String, KindStyle>,
+}
+
+impl AgentKindStyles {
+    /// Take the config's overrides. Unknown colors are reported and fall back
+    /// to the default.
+    pub fn from_config(config: &BTreeMap<String, KindStyle>) -> (Self, Vec<RuleError>) {
+        let errors = config
+            .iter()
+            .filter_map(|(key, style)| {
+                let color = style.color.as_deref()?;
+                severity::color(color).is_none().then(|| RuleError::UnknownStyle {
+                    rule: format!("agent kind {}", key),
+                    word: color.to_string(),
+                })
+            })
+            .collect();
+        (Self { config: config.clone() }, errors)
+    }
+
+    pub fn config(&self) -> &BTreeMap<String, KindStyle> {
+        &self.config
+    }
+
+    /// Pure function: icon and color of an agent's kind.
+    pub fn style(&self, agent: &Agent) -> (&str, Color) {
+        self.style_for(agent.agent_type.as_deref())
+    }
+
+    /// Pure function: icon and color of a raw agent type (None: untyped).
+    pub fn style_for(&self, agent_type: Option<&str>) -> (&str, Color) {
+        let Some(kind) = agent_type.map(AgentKind::parse).filter(|kind| !kind.name.is_empty()) else {
+            return (UNTYPED_ICON, Theme::MUTED_TEXT);
+        };
+        let (icon, color) = default_style(&kind);
+        let overrides: Vec<&KindStyle> = [agent_type.map(str::trim), Some(kind.name), kind.namespace]
+            .into_iter()
+            .flatten()
+            .filter_map(|key| self.config.get(key))
+            .collect();
+        let icon = overrides.iter().find_map(|s| s.icon.as_deref()).unwrap_or(icon);
+        let color = overrides
+            .iter()
+            .find_map(|s| s.color.as_deref().and_then(severity::color))
+            .unwrap_or(color);
+        (icon, color)
+    }
+}
+
+/// Pure function: the built-in style of a kind.
+fn default_style(kind: &AgentKind) -> (&'static str, Color) {
+    if kind.is_builtin() {
+        return match kind.name {
+            "Explore" => ("⌕", Theme::ACCENT),
+            "Plan" => ("◇", Theme::INFO),
+            _ => ("◈", Theme::TEXT),
+        };
+    }
+    let key = kind.namespace.unwrap_or(kind.name);
+    let hash = key.bytes().fold(0usize, |acc, b| acc.wrapping_mul(31).wrapping_add(b as usize));
+    (CUSTOM_ICON, CUSTOM_COLORS[hash % CUSTOM_COLORS.len()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_apply_by_type_then_name_then_namespace() {
+        let config: BTreeMap<String, KindStyle> = [
+            ("loom".to_string(), KindStyle { icon: Some("L".into()), color: Some("purple".into()) }),
+            ("code-reviewer".to_string(), KindStyle { icon: Some("✎".into()), color: None }),
+            ("Plan".to_string(), KindStyle { icon: None, color: Some("mauve".into()) }),
+        ]
+        .into_iter()
+        .collect();
+        let (styles, errors) = AgentKindStyles::from_config(&config);
+        assert!(matches!(&errors[..], [RuleError::UnknownStyle { word, .. }] if word == "mauve"));
+
+        assert_eq!(styles.style_for(Some("loom:code-reviewer")), ("✎", Theme::ACCENT_PURPLE));
+        assert_eq!(styles.style_for(Some("loom:implementer")), ("L", Theme::ACCENT_PURPLE));
+        assert_eq!(styles.style_for(Some("Plan")), ("◇", Theme::INFO));
+        assert_eq!(styles.style_for(Some("Explore")), ("⌕", Theme::ACCENT));
+        assert_eq!(styles.style_for(None), (UNTYPED_ICON, Theme::MUTED_TEXT));
+
+        // Custom types keep their color; a namespace colors all its agents alike
+        let plain = AgentKindStyles::default();
+        assert_eq!(plain.style_for(Some("backend/reviewer")), plain.style_for(Some("backend/implementer")));
+        assert_eq!(plain.style_for(Some("reviewer")).0, CUSTOM_ICON);
+    }
+}
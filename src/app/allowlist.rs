@@ -0,0 +1,84 @@
+//! Tool allowlist checks.
+//!
+//! An agent definition's `tools` (see `agent_defs`) lists what the agent may
+//! call. A call to anything else means the definition is not what was loaded
+//! or the agent was talked into it (prompt injection), so the event streams
+//! flag it. Entries match a tool by name, a `Bash(git *)` permission rule by
+//! its tool, and `mcp__server` every tool of that MCP server.
+
+use crate::app::agent_defs::{self, AgentDefinition};
+use crate::app::AppState;
+use crate::model::{TranscriptEvent, TranscriptEventKind};
+
+/// Pure function: does an allowlist entry cover `tool`?
+fn covers(entry: &str, tool: &str) -> bool {
+    let entry = entry.trim();
+    let name = entry.split_once('(').map_or(entry, |(name, _)| name.trim());
+    name == tool || (name.starts_with("mcp__") && tool.strip_prefix(name).is_some_and(|rest| rest.starts_with("__")))
+}
+
+/// Pure function: may an agent with these allowed tools call `tool`?
+pub fn allows(tools: &[String], tool: &str) -> bool {
+    tools.iter().any(|entry| covers(entry, tool))
+}
+
+/// Pure function: the definition a tool call breaks — set when the event is
+/// a call by an agent whose definition lists its tools, and this one is not
+/// among them.
+pub fn violation<'a>(state: &'a AppState, event: &TranscriptEvent) -> Option<&'a AgentDefinition> {
+    let TranscriptEventKind::ToolUse { tool_name, .. } = &event.kind else {
+        return None;
+    };
+    let agent = state.domain.agents.get(event.agent_id.as_ref()?)?;
+    let def = agent_defs::for_agent(&state.domain.agent_definitions, agent)?;
+    let tools = def.tools.as_ref()?;
+    (!allows(tools, tool_name.as_str())).then_some(def)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Agent, AgentId};
+    use chrono::Utc;
+
+    #[test]
+    fn entries_cover_tools_by_name_rule_or_mcp_server() {
+        let tools: Vec<String> = ["Read", "Bash(git diff:*)", "mcp__github"].iter().map(|s| s.to_string()).collect();
+        assert!(allows(&tools, "Read"));
+        assert!(allows(&tools, "Bash"));
+        assert!(allows(&tools, "mcp__github__create_issue"));
+        assert!(!allows(&tools, "mcp__githubx__create_issue"));
+        assert!(!allows(&tools, "Write"));
+    }
+
+    #[test]
+    fn only_calls_outside_a_declared_allowlist_are_violations() {
+        let mut state = AppState::new();
+        let mut agent = Agent::new("a01", Utc::now());
+        agent.agent_type = Some("code-reviewer".into());
+        state.domain.agents.insert(AgentId::new("a01"), agent);
+        let mut def = AgentDefinition {
+            name: "code-reviewer".into(),
+            description: None,
+            model: None,
+            tools: Some(vec!["Read".into(), "Grep".into()]),
+            path: ".claude/agents/code-reviewer.md".into(),
+        };
+        state.domain.agent_definitions = vec![def.clone()];
+        let call = |tool: &str, agent: &str| {
+            TranscriptEvent::new(
+                Utc::now(),
+                TranscriptEventKind::ToolUse { tool_name: tool.into(), input_summary: String::new() },
+            )
+            .with_agent(agent)
+        };
+
+        assert_eq!(violation(&state, &call("Bash", "a01")).map(|d| d.name.as_str()), Some("code-reviewer"));
+        assert_eq!(violation(&state, &call("Grep", "a01")), None);
+        assert_eq!(violation(&state, &call("Bash", "unknown")), None);
+
+        def.tools = None;
+        state.domain.agent_definitions = vec![def];
+        assert_eq!(violation(&state, &call("Bash", "a01")), None, "no list: every tool allowed");
+    }
+}
@@ -0,0 +1,377 @@
+//! Agent anomaly flagging against per-type history.
+//!
+//! Finished agents from archived sessions are folded into running moments per
+//! `agent_type` (duration, tool calls, tool failure rate). An agent whose value
+//! sits more than `Z_THRESHOLD` standard deviations above its type's mean is
+//! flagged in the agent lists, e.g. "3x slower than typical reviewer". Only the
+//! high side is flagged: a fast or quiet agent is not a problem worth a marker.
+//! Baselines are built off the UI thread at startup and extended in memory as
+//! sessions are archived.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::model::{Agent, AgentId, Clock, SessionArchive, TranscriptEvent, TranscriptEventKind};
+use crate::session;
+
+/// Finished agents of a type needed before its baseline is trusted.
+pub const MIN_SAMPLES: u32 = 5;
+
+/// Standard deviations above the mean that count as anomalous.
+pub const Z_THRESHOLD: f64 = 2.0;
+
+/// Tool results needed before an agent's failure rate is judged.
+pub const MIN_TOOL_RESULTS: u32 = 3;
+
+/// What an agent is compared on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Metric {
+    Duration,
+    ToolCount,
+    FailureRate,
+}
+
+impl Metric {
+    /// Smallest spread assumed, so a type whose history barely varies
+    /// (e.g. never fails a tool) doesn't flag every small deviation.
+    fn min_std_dev(self) -> f64 {
+        match self {
+            Self::Duration => 30.0,
+            Self::ToolCount => 2.0,
+            Self::FailureRate => 0.05,
+        }
+    }
+}
+
+/// Tool calls and results attributed to one agent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ToolStats {
+    pub uses: u32,
+    pub results: u32,
+    pub failures: u32,
+}
+
+impl ToolStats {
+    /// Count a ToolUse / ToolResult event; other events are ignored.
+    pub fn observe(&mut self, kind: &TranscriptEventKind) {
+        match kind {
+            TranscriptEventKind::ToolUse { .. } => self.uses += 1,
+            TranscriptEventKind::ToolResult { is_error, .. } => {
+                self.results += 1;
+                if *is_error {
+                    self.failures += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Share of tool results that failed, once there are enough to judge.
+    pub fn failure_rate(&self) -> Option<f64> {
+        (self.results >= MIN_TOOL_RESULTS).then(|| f64::from(self.failures) / f64::from(self.results))
+    }
+}
+
+/// Pure function: tool stats per agent in one pass over `events`.
+pub fn tool_stats<'a>(events: impl IntoIterator<Item = &'a TranscriptEvent>) -> HashMap<AgentId, ToolStats> {
+    let mut stats: HashMap<AgentId, ToolStats> = HashMap::new();
+    for event in events {
+        if let Some(ref agent_id) = event.agent_id {
+            if matches!(event.kind, TranscriptEventKind::ToolUse { .. } | TranscriptEventKind::ToolResult { .. }) {
+                stats.entry(agent_id.clone()).or_default().observe(&event.kind);
+            }
+        }
+    }
+    stats
+}
+
+/// Running count, sum and sum of squares of one metric.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Moments {
+    n: u32,
+    sum: f64,
+    sum_sq: f64,
+}
+
+impl Moments {
+    pub fn push(&mut self, x: f64) {
+        self.n += 1;
+        self.sum += x;
+        self.sum_sq += x * x;
+    }
+
+    /// Combine with moments gathered elsewhere.
+    pub fn merge(&mut self, other: &Moments) {
+        self.n += other.n;
+        self.sum += other.sum;
+        self.sum_sq += other.sum_sq;
+    }
+
+    pub fn count(&self) -> u32 {
+        self.n
+    }
+
+    pub fn mean(&self) -> f64 {
+        if self.n == 0 { 0.0 } else { self.sum / f64::from(self.n) }
+    }
+
+    /// Population standard deviation.
+    pub fn std_dev(&self) -> f64 {
+        if self.n == 0 {
+            return 0.0;
+        }
+        let mean = self.mean();
+        (self.sum_sq / f64::from(self.n) - mean * mean).max(0.0).sqrt()
+    }
+
+    /// z-score of `x` for `metric`, once there are `MIN_SAMPLES`.
+    fn z(&self, x: f64, metric: Metric) -> Option<f64> {
+        (self.n >= MIN_SAMPLES).then(|| (x - self.mean()) / self.std_dev().max(metric.min_std_dev()))
+    }
+}
+
+/// History of one agent type.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TypeBaseline {
+    pub duration_secs: Moments,
+    pub tool_count: Moments,
+    pub failure_rate: Moments,
+}
+
+impl TypeBaseline {
+    fn metric(&self, metric: Metric) -> &Moments {
+        match metric {
+            Metric::Duration => &self.duration_secs,
+            Metric::ToolCount => &self.tool_count,
+            Metric::FailureRate => &self.failure_rate,
+        }
+    }
+}
+
+/// Per-`agent_type` history of finished agents.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgentBaselines {
+    by_type: BTreeMap<String, TypeBaseline>,
+}
+
+impl AgentBaselines {
+    /// Pure function: baselines from the finished, typed agents of `archives`.
+    pub fn from_archives<'a>(archives: impl IntoIterator<Item = &'a SessionArchive>) -> Self {
+        let mut baselines = Self::default();
+        for archive in archives {
+            baselines.record_archive(archive);
+        }
+        baselines
+    }
+
+    /// Fold the finished, typed agents of `archive` into the history.
+    pub fn record_archive(&mut self, archive: &SessionArchive) {
+        let stats = tool_stats(&archive.events);
+        for agent in archive.agents.values() {
+            self.record(agent, stats.get(&agent.id).copied().unwrap_or_default());
+        }
+    }
+
+    /// Fold one agent into its type's history; running or untyped agents are skipped.
+    pub fn record(&mut self, agent: &Agent, tools: ToolStats) {
+        let (Some(agent_type), Some(finished)) = (agent.agent_type.as_ref(), agent.finished_at) else {
+            return;
+        };
+        let baseline = self.by_type.entry(agent_type.clone()).or_default();
+        baseline.duration_secs.push((finished - agent.started_at).num_seconds().max(0) as f64);
+        baseline.tool_count.push(f64::from(tools.uses));
+        if let Some(rate) = tools.failure_rate() {
+            baseline.failure_rate.push(rate);
+        }
+    }
+
+    /// Add history gathered elsewhere (the startup scan, while sessions were archived meanwhile).
+    pub fn merge(&mut self, other: &AgentBaselines) {
+        for (agent_type, theirs) in &other.by_type {
+            let ours = self.by_type.entry(agent_type.clone()).or_default();
+            ours.duration_secs.merge(&theirs.duration_secs);
+            ours.tool_count.merge(&theirs.tool_count);
+            ours.failure_rate.merge(&theirs.failure_rate);
+        }
+    }
+
+    pub fn get(&self, agent_type: &str) -> Option<&TypeBaseline> {
+        self.by_type.get(agent_type)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_type.is_empty()
+    }
+}
+
+/// Load every archive in `archive_dir` (one at a time) and build baselines.
+/// Unreadable archives are skipped; this only feeds a heuristic.
+pub fn load_baselines(archive_dir: &Path) -> AgentBaselines {
+    let mut baselines = AgentBaselines::default();
+    if let Ok((metas, _)) = session::list_session_metas(archive_dir) {
+        for (path, _) in metas {
+            if let Ok(archive) = session::load_session(&path) {
+                baselines.record_archive(&archive);
+            }
+        }
+    }
+    baselines
+}
+
+/// An agent well outside its type's history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomaly {
+    pub metric: Metric,
+    pub agent_type: String,
+    pub z: f64,
+    pub value: f64,
+    pub typical: f64,
+}
+
+impl Anomaly {
+    /// Short description: `3x slower than typical reviewer`.
+    pub fn label(&self) -> String {
+        match self.metric {
+            Metric::Duration => format!("{} slower than typical {}", ratio(self.value, self.typical), self.agent_type),
+            Metric::ToolCount => {
+                format!("{} more tool calls than typical {}", ratio(self.value, self.typical), self.agent_type)
+            }
+            Metric::FailureRate => format!(
+                "{:.0}% tool failures vs {:.0}% typical {}",
+                self.value * 100.0,
+                self.typical * 100.0,
+                self.agent_type
+            ),
+        }
+    }
+}
+
+/// `3x`, `2.5x`; `>10x` past ten (and when the typical value is zero).
+fn ratio(value: f64, typical: f64) -> String {
+    let r = if typical > 0.0 { value / typical } else { f64::INFINITY };
+    if r >= 10.0 {
+        ">10x".to_string()
+    } else if (r - r.round()).abs() < 0.05 {
+        format!("{:.0}x", r)
+    } else {
+        format!("{:.1}x", r)
+    }
+}
+
+/// Pure function: the most anomalous metric of `agent` (with `tools` attributed
+/// to it) against its type's history, as of now on `clock`. Running agents are judged on
+/// their time and tool calls so far.
+pub fn flag(agent: &Agent, tools: ToolStats, baselines: &AgentBaselines, clock: &dyn Clock) -> Option<Anomaly> {
+    let agent_type = agent.agent_type.as_deref()?;
+    let baseline = baselines.get(agent_type)?;
+    let values = [
+        (Metric::Duration, Some(agent.elapsed(clock).num_seconds().max(0) as f64)),
+        (Metric::ToolCount, Some(f64::from(tools.uses))),
+        (Metric::FailureRate, tools.failure_rate()),
+    ];
+    values
+        .into_iter()
+        .filter_map(|(metric, value)| {
+            let value = value?;
+            let moments = baseline.metric(metric);
+            let z = moments.z(value, metric)?;
+            (z > Z_THRESHOLD).then(|| Anomaly {
+                metric,
+                agent_type: agent_type.to_string(),
+                z,
+                value,
+                typical: moments.mean(),
+            })
+        })
+        .max_by(|a, b| a.z.total_cmp(&b.z))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{SessionMeta, SystemClock, ToolName};
+    use chrono::{DateTime, Duration, Utc};
+
+    fn reviewer(id: &str, secs: i64) -> Agent {
+        let start: DateTime<Utc> = "2026-03-18T10:00:00Z".parse().unwrap();
+        Agent::new(id, start).with_agent_type("reviewer".into()).finish(start + Duration::seconds(secs))
+    }
+
+    fn history(durations: &[i64], tools: ToolStats) -> AgentBaselines {
+        let mut baselines = AgentBaselines::default();
+        for (i, secs) in durations.iter().enumerate() {
+            baselines.record(&reviewer(&format!("h{i}"), *secs), tools);
+        }
+        baselines
+    }
+
+    #[test]
+    fn slow_agent_is_flagged_with_a_ratio_against_its_type() {
+        let typical = ToolStats { uses: 10, results: 10, failures: 0 };
+        let baselines = history(&[100, 110, 90, 105, 95], typical);
+
+        let slow = reviewer("a1", 300);
+        let anomaly = flag(&slow, typical, &baselines, &SystemClock).unwrap();
+        assert_eq!(anomaly.metric, Metric::Duration);
+        assert_eq!(anomaly.label(), "3x slower than typical reviewer");
+
+        assert_eq!(flag(&reviewer("a2", 120), typical, &baselines, &SystemClock), None);
+        // Faster than usual is not an anomaly
+        assert_eq!(flag(&reviewer("a3", 10), typical, &baselines, &SystemClock), None);
+    }
+
+    #[test]
+    fn failure_rate_uses_a_minimum_spread_and_needs_enough_results() {
+        let baselines = history(&[100; 5], ToolStats { uses: 10, results: 10, failures: 0 });
+        let failing = ToolStats { uses: 10, results: 10, failures: 4 };
+        let anomaly = flag(&reviewer("a1", 100), failing, &baselines, &SystemClock).unwrap();
+        assert_eq!(anomaly.label(), "40% tool failures vs 0% typical reviewer");
+
+        let too_few = ToolStats { uses: 2, results: 2, failures: 2 };
+        assert_eq!(flag(&reviewer("a2", 100), too_few, &baselines, &SystemClock), None);
+    }
+
+    #[test]
+    fn types_with_little_history_or_no_type_are_never_flagged() {
+        let tools = ToolStats::default();
+        let baselines = history(&[100, 100, 100, 100], tools);
+        assert_eq!(flag(&reviewer("a1", 10_000), tools, &baselines, &SystemClock), None);
+
+        let baselines = history(&[100; 5], tools);
+        let untyped = Agent::new("a2", Utc::now() - Duration::hours(5));
+        assert_eq!(flag(&untyped, tools, &baselines, &SystemClock), None);
+    }
+
+    #[test]
+    fn archives_contribute_their_agents_tool_stats() {
+        let agent = reviewer("a1", 60);
+        let tool_use = |is_error| {
+            let mut use_event = TranscriptEvent::new(
+                agent.started_at,
+                TranscriptEventKind::ToolUse { tool_name: ToolName::new("Bash"), input_summary: "ls".into() },
+            );
+            use_event.agent_id = Some(agent.id.clone());
+            let mut result = TranscriptEvent::new(
+                agent.started_at,
+                TranscriptEventKind::ToolResult {
+                    tool_name: ToolName::new("Bash"),
+                    result_summary: String::new(),
+                    duration_ms: None,
+                    is_error,
+                },
+            );
+            result.agent_id = Some(agent.id.clone());
+            [use_event, result]
+        };
+        let events: Vec<_> = [false, true, false].into_iter().flat_map(tool_use).collect();
+        let archive = SessionArchive::new(SessionMeta::new("s1", Utc::now(), "/p".into()))
+            .with_events(events)
+            .with_agents([(agent.id.clone(), agent.clone())].into());
+
+        let baselines = AgentBaselines::from_archives([&archive]);
+        let baseline = baselines.get("reviewer").unwrap();
+        assert_eq!(baseline.tool_count.mean(), 3.0);
+        assert!((baseline.failure_rate.mean() - 1.0 / 3.0).abs() < 1e-9);
+        assert_eq!(baseline.duration_secs.count(), 1);
+    }
+}
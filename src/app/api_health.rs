@@ -0,0 +1,169 @@
+//! API health: failed API calls across all sessions in a sliding time window.
+//!
+//! Rate limits, overloaded responses and context-length failures come back as
+//! transcript entries of their own (`ApiError` events) and used to vanish with
+//! the other unparsed lines. Each one is recorded with its kind; the header
+//! shows how many arrived within the window, by kind, until it drains, and one
+//! arriving after replay raises a toast naming its agent. A context-length
+//! failure is an error, since the agent cannot go on; the others are retried
+//! and only warn.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::app::toasts::{self, ToastLevel};
+use crate::app::AppState;
+use crate::model::{AgentId, ApiErrorKind, TranscriptEvent, TranscriptEventKind};
+use crate::watcher::truncate_str;
+
+/// Longest error message quoted in a toast.
+const MESSAGE_MAX_CHARS: usize = 60;
+
+/// One API error: when, what, and the agent it hit (none for the main thread).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SeenError {
+    at: DateTime<Utc>,
+    kind: ApiErrorKind,
+    agent: Option<AgentId>,
+}
+
+/// API errors within `window`, oldest first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiHealth {
+    errors: VecDeque<SeenError>,
+    window: Duration,
+}
+
+impl Default for ApiHealth {
+    fn default() -> Self {
+        Self { errors: VecDeque::new(), window: Duration::minutes(10) }
+    }
+}
+
+impl ApiHealth {
+    /// Record an error at `at`, dropping those more than the window older
+    /// than the newest one.
+    pub fn record(&mut self, at: DateTime<Utc>, kind: ApiErrorKind, agent: Option<AgentId>) {
+        let idx = self.errors.partition_point(|e| e.at <= at);
+        self.errors.insert(idx, SeenError { at, kind, agent });
+        if let Some(newest) = self.errors.back().map(|e| e.at) {
+            self.trim_before(newest - self.window);
+        }
+    }
+
+    /// Forget errors that have left the window as of `now`.
+    pub fn expire(&mut self, now: DateTime<Utc>) {
+        self.trim_before(now - self.window);
+    }
+
+    /// Errors in the window.
+    pub fn total(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Errors in the window per kind.
+    pub fn counts(&self) -> BTreeMap<ApiErrorKind, usize> {
+        let mut counts = BTreeMap::new();
+        for error in &self.errors {
+            *counts.entry(error.kind).or_default() += 1;
+        }
+        counts
+    }
+
+    /// Agents hit by an error of one of `kinds` in the window.
+    pub fn agents(&self, kinds: &[ApiErrorKind]) -> BTreeSet<&AgentId> {
+        self.errors.iter().filter(|e| kinds.contains(&e.kind)).filter_map(|e| e.agent.as_ref()).collect()
+    }
+
+    /// When the newest error of one of `kinds` in the window arrived.
+    pub fn latest(&self, kinds: &[ApiErrorKind]) -> Option<DateTime<Utc>> {
+        self.errors.iter().rev().find(|e| kinds.contains(&e.kind)).map(|e| e.at)
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Window as `10m`.
+    pub fn window_label(&self) -> String {
+        format!("{}m", self.window.num_minutes())
+    }
+
+    fn trim_before(&mut self, cutoff: DateTime<Utc>) {
+        while self.errors.front().is_some_and(|e| e.at < cutoff) {
+            self.errors.pop_front();
+        }
+    }
+}
+
+/// Record an API error event. After replay it also raises a toast naming the
+/// agent (or session) it hit.
+pub fn track(state: &mut AppState, event: &TranscriptEvent) {
+    let TranscriptEventKind::ApiError { error_kind, message, .. } = &event.kind else {
+        return;
+    };
+    state.domain.api_health.record(event.timestamp, *error_kind, event.agent_id.clone());
+    if !state.meta.replay_complete {
+        return;
+    }
+    let level = match error_kind {
+        ApiErrorKind::ContextLength => ToastLevel::Error,
+        _ => ToastLevel::Warn,
+    };
+    let who = match (&event.agent_id, &event.session_id) {
+        (Some(agent), _) => format!(" for agent {}", agent),
+        (None, Some(session)) => format!(" in session {}", session),
+        (None, None) => String::new(),
+    };
+    let text = format!("API {}{}: {}", error_kind.label(), who, truncate_str(message, MESSAGE_MAX_CHARS));
+    toasts::raise(state, level, text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t0() -> DateTime<Utc> {
+        "2026-03-18T10:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn counts_cover_the_window_by_kind() {
+        let mut health = ApiHealth::default();
+        health.record(t0(), ApiErrorKind::RateLimit, Some(AgentId::new("a1")));
+        health.record(t0() + Duration::minutes(4), ApiErrorKind::Overloaded, None);
+        health.record(t0() + Duration::minutes(2), ApiErrorKind::RateLimit, Some(AgentId::new("a2")));
+        assert_eq!(health.total(), 3);
+        assert_eq!(health.counts(), BTreeMap::from([(ApiErrorKind::RateLimit, 2), (ApiErrorKind::Overloaded, 1)]));
+        assert_eq!(health.agents(&[ApiErrorKind::RateLimit]).len(), 2);
+        assert_eq!(health.latest(&[ApiErrorKind::RateLimit]), Some(t0() + Duration::minutes(2)));
+
+        // One past the window pushes the oldest out
+        health.record(t0() + Duration::minutes(11), ApiErrorKind::ContextLength, None);
+        assert_eq!(health.total(), 3);
+
+        health.expire(t0() + Duration::minutes(15));
+        assert_eq!(health.counts(), BTreeMap::from([(ApiErrorKind::ContextLength, 1)]));
+        health.expire(t0() + Duration::minutes(30));
+        assert_eq!(health.total(), 0);
+    }
+
+    #[test]
+    fn live_api_errors_raise_a_toast_naming_the_agent() {
+        let mut state = AppState::new();
+        let error = |kind, message: &str| {
+            let kind = TranscriptEventKind::ApiError { error_kind: kind, message: message.into(), status: None };
+            TranscriptEvent::new(t0(), kind).with_session("s1").with_agent("a1")
+        };
+        // Replayed errors count but stay quiet
+        track(&mut state, &error(ApiErrorKind::Overloaded, "Overloaded"));
+        assert!(state.ui.toasts.is_empty());
+
+        state.meta.replay_complete = true;
+        track(&mut state, &error(ApiErrorKind::ContextLength, "Prompt is too long"));
+        let toast = state.ui.toasts.iter().next().unwrap();
+        assert_eq!((toast.level, toast.text.as_str()), (ToastLevel::Error, "API context limit for agent a1: Prompt is too long"));
+        assert_eq!(state.domain.api_health.total(), 2);
+    }
+}
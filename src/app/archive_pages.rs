@@ -0,0 +1,82 @@
+//! Paged loading of archived session metas.
+//!
+//! Reading every archive's meta up front is slow once there are thousands of
+//! them. An archive scan lists the files (cheap; newest first by modification
+//! time) and reads only the first `session::META_PAGE_SIZE` metas. The rest are
+//! read a page at a time by the main loop, prefetched once the Sessions list's
+//! cursor comes within `PREFETCH_MARGIN` rows of the end of what is loaded.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Rows from the end of the loaded list at which the next page is requested.
+pub const PREFETCH_MARGIN: usize = 50;
+
+/// Archive files on disk and which of their metas have been read.
+#[derive(Debug, Clone, Default)]
+pub struct ArchivePages {
+    /// Every archive file from the last scan, newest first
+    files: Vec<PathBuf>,
+    /// Files whose meta was read, successfully or not
+    read: HashSet<PathBuf>,
+}
+
+impl ArchivePages {
+    /// Take a scan's file list, whose first `first_page` metas the scan read.
+    /// Files read earlier stay read as long as they are still on disk.
+    pub fn listed(&mut self, files: Vec<PathBuf>, first_page: usize) {
+        let on_disk: HashSet<&PathBuf> = files.iter().collect();
+        self.read.retain(|path| on_disk.contains(path));
+        self.read.extend(files.iter().take(first_page).cloned());
+        self.files = files;
+    }
+
+    pub fn mark_read(&mut self, paths: impl IntoIterator<Item = PathBuf>) {
+        self.read.extend(paths);
+    }
+
+    /// Archive files from the last scan, newest first.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Number of archive files on disk.
+    pub fn total(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Number of files whose meta is still unread.
+    pub fn remaining(&self) -> usize {
+        self.files.iter().filter(|path| !self.read.contains(*path)).count()
+    }
+
+    /// Pure function: the next `size` unread files, newest first.
+    pub fn next_page(&self, size: usize) -> Vec<PathBuf> {
+        self.files.iter().filter(|path| !self.read.contains(*path)).take(size).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(names: &[&str]) -> Vec<PathBuf> {
+        names.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn pages_walk_unread_files_and_survive_a_rescan() {
+        let mut pages = ArchivePages::default();
+        pages.listed(paths(&["e.json", "d.json", "c.json", "b.json", "a.json"]), 2);
+        assert_eq!(pages.total(), 5);
+        assert_eq!(pages.next_page(2), paths(&["c.json", "b.json"]));
+
+        pages.mark_read(paths(&["c.json", "b.json"]));
+        assert_eq!(pages.remaining(), 1);
+
+        // A new archive lands on top and an old one is deleted; pages read deeper stay read
+        pages.listed(paths(&["f.json", "e.json", "d.json", "c.json", "a.json"]), 2);
+        assert_eq!(pages.next_page(5), paths(&["a.json"]));
+        assert_eq!(pages.remaining(), 1);
+    }
+}
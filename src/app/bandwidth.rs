@@ -0,0 +1,78 @@
+//! Degraded rendering for slow terminals (high-latency SSH).
+//!
+//! ratatui already writes only the cells that changed since the last frame, so
+//! what costs bandwidth is how often something changes. In low-bandwidth mode
+//! (`--ssh`, or switched on once frames keep taking longer than `SLOW_DRAW` to
+//! write) a tick alone redraws at most every `TICK_REDRAW_INTERVAL`, and the
+//! header clock moves per minute instead of per second. Keys and new events
+//! still redraw immediately.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Draw time (render + write to the terminal) that counts as slow.
+pub const SLOW_DRAW: Duration = Duration::from_millis(50);
+
+/// Draws looked at when deciding whether the terminal is slow.
+const SAMPLES: usize = 10;
+
+/// Longest a low-bandwidth screen goes without a tick-driven redraw.
+pub const TICK_REDRAW_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Recent draw times, newest last.
+#[derive(Debug, Clone, Default)]
+pub struct DrawLatency {
+    samples: VecDeque<Duration>,
+}
+
+impl DrawLatency {
+    /// Record how long one frame took to draw and flush.
+    ///
+    /// # Returns
+    /// True once a full window of draws has a median of at least `SLOW_DRAW`
+    /// (a median, so the first full-screen paint or one resize doesn't count).
+    pub fn record(&mut self, took: Duration) -> bool {
+        if self.samples.len() == SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(took);
+        if self.samples.len() < SAMPLES {
+            return false;
+        }
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort();
+        sorted[SAMPLES / 2] >= SLOW_DRAW
+    }
+}
+
+/// Pure function: should a tick with nothing else going on redraw the screen?
+pub fn tick_needs_redraw(low_bandwidth: bool, since_last_draw: Duration) -> bool {
+    !low_bandwidth || since_last_draw >= TICK_REDRAW_INTERVAL
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slow_only_after_a_full_window_with_a_slow_median() {
+        let mut latency = DrawLatency::default();
+        let fast = Duration::from_millis(5);
+        let slow = Duration::from_millis(120);
+
+        // One slow full-screen paint among fast frames
+        assert!(!latency.record(slow));
+        for _ in 0..(SAMPLES - 1) {
+            assert!(!latency.record(fast));
+        }
+        let verdicts: Vec<bool> = (0..SAMPLES).map(|_| latency.record(slow)).collect();
+        assert_eq!(verdicts.iter().position(|slow| *slow), Some(SAMPLES / 2 - 1));
+    }
+
+    #[test]
+    fn low_bandwidth_ticks_redraw_rarely() {
+        assert!(tick_needs_redraw(false, Duration::ZERO));
+        assert!(!tick_needs_redraw(true, Duration::from_secs(1)));
+        assert!(tick_needs_redraw(true, TICK_REDRAW_INTERVAL));
+    }
+}
@@ -0,0 +1,91 @@
+//! Session duration budgets.
+//!
+//! The config sets how long a session is expected to run, with a separate
+//! budget for orchestrated sessions (a loom task graph is loaded). The header
+//! counts down against it for the focused session and flips to an overtime
+//! warning once exceeded; archives record the budget and any overtime.
+
+use chrono::Duration;
+
+/// Expected run times from settings. `None` means no budget.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DurationBudget {
+    pub session: Option<Duration>,
+    pub orchestration: Option<Duration>,
+}
+
+impl DurationBudget {
+    /// Pure function: budget for a session; orchestrated sessions use the
+    /// orchestration budget when one is set.
+    pub fn for_session(&self, orchestrated: bool) -> Option<Duration> {
+        if orchestrated {
+            self.orchestration.or(self.session)
+        } else {
+            self.session
+        }
+    }
+}
+
+/// Where a session stands against its budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetStatus {
+    Remaining(Duration),
+    Over(Duration),
+}
+
+impl BudgetStatus {
+    /// Pure function: status after `elapsed` of a `budget`.
+    pub fn of(budget: Duration, elapsed: Duration) -> Self {
+        if elapsed > budget {
+            Self::Over(elapsed - budget)
+        } else {
+            Self::Remaining(budget - elapsed)
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        matches!(self, Self::Over(_))
+    }
+
+    /// Header text: `12m left` or `over budget by 12m`.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Remaining(left) => format!("{} left", minutes_label(*left)),
+            Self::Over(by) => format!("over budget by {}", minutes_label(*by)),
+        }
+    }
+}
+
+/// Pure function: a duration to the minute, `<1m` below one.
+pub fn minutes_label(d: Duration) -> String {
+    match d.num_minutes() {
+        0 => "<1m".to_string(),
+        m if m < 60 => format!("{}m", m),
+        m => format!("{}h{}m", m / 60, m % 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_counts_down_then_reports_overtime() {
+        let budget = Duration::minutes(45);
+        assert_eq!(BudgetStatus::of(budget, Duration::minutes(33)).label(), "12m left");
+        assert_eq!(BudgetStatus::of(budget, Duration::seconds(45 * 60 - 20)).label(), "<1m left");
+        let over = BudgetStatus::of(budget, Duration::minutes(57));
+        assert!(over.is_over());
+        assert_eq!(over.label(), "over budget by 12m");
+        assert_eq!(minutes_label(Duration::minutes(125)), "2h5m");
+    }
+
+    #[test]
+    fn orchestrated_sessions_prefer_the_orchestration_budget() {
+        let budget = DurationBudget { session: Some(Duration::minutes(30)), orchestration: Some(Duration::hours(2)) };
+        assert_eq!(budget.for_session(false), Some(Duration::minutes(30)));
+        assert_eq!(budget.for_session(true), Some(Duration::hours(2)));
+        let session_only = DurationBudget { orchestration: None, ..budget };
+        assert_eq!(session_only.for_session(true), Some(Duration::minutes(30)));
+    }
+}
@@ -0,0 +1,107 @@
+//! Session activity calendar.
+//!
+//! The Sessions view's header shows archived sessions per day as a heat map,
+//! one column per week. `[` / `]` step the selected day and `{` / `}` a week;
+//! a selected day narrows the session list to sessions started on it. Counts
+//! are rebuilt with the list (`update::apply_project_filter`) and cached, so
+//! drawing never walks the archive index.
+
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Days, NaiveDate};
+
+/// Number of heat levels above "no sessions".
+pub const HEAT_LEVELS: u8 = 4;
+
+/// Sessions started per calendar day.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionCalendar {
+    counts: BTreeMap<NaiveDate, usize>,
+}
+
+impl SessionCalendar {
+    /// Pure function: count sessions by the day they started on.
+    pub fn from_dates(dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        let mut counts = BTreeMap::new();
+        for date in dates {
+            *counts.entry(date).or_insert(0) += 1;
+        }
+        Self { counts }
+    }
+
+    pub fn count(&self, date: NaiveDate) -> usize {
+        self.counts.get(&date).copied().unwrap_or(0)
+    }
+
+    /// Most sessions on any one day.
+    pub fn busiest(&self) -> usize {
+        self.counts.values().copied().max().unwrap_or(0)
+    }
+
+    /// Newest day with a session.
+    pub fn latest(&self) -> Option<NaiveDate> {
+        self.counts.keys().next_back().copied()
+    }
+
+    /// Heat level of `date`: 0 without sessions, up to `HEAT_LEVELS` on the busiest day.
+    pub fn heat(&self, date: NaiveDate) -> u8 {
+        let count = self.count(date);
+        let busiest = self.busiest();
+        if count == 0 || busiest == 0 {
+            return 0;
+        }
+        (count * HEAT_LEVELS as usize).div_ceil(busiest).clamp(1, HEAT_LEVELS as usize) as u8
+    }
+}
+
+/// Pure function: the Monday starting `date`'s week.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Days::new(date.weekday().num_days_from_monday() as u64)
+}
+
+/// Pure function: the selected day after stepping `days` from `selected`.
+/// With nothing selected the first step lands on the newest day with sessions
+/// (or `today`); stepping past `today` clears the selection.
+pub fn step_day(selected: Option<NaiveDate>, days: i64, calendar: &SessionCalendar, today: NaiveDate) -> Option<NaiveDate> {
+    let Some(selected) = selected else {
+        return Some(calendar.latest().unwrap_or(today).min(today));
+    };
+    let stepped = if days >= 0 {
+        selected.checked_add_days(Days::new(days as u64))
+    } else {
+        selected.checked_sub_days(Days::new(days.unsigned_abs()))
+    }?;
+    (stepped <= today).then_some(stepped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn heat_scales_to_the_busiest_day() {
+        let calendar = SessionCalendar::from_dates(
+            std::iter::repeat_n(day("2026-03-16"), 8).chain([day("2026-03-17"), day("2026-03-18"), day("2026-03-18")]),
+        );
+        assert_eq!(calendar.heat(day("2026-03-16")), HEAT_LEVELS);
+        assert_eq!(calendar.heat(day("2026-03-17")), 1);
+        assert_eq!(calendar.heat(day("2026-03-18")), 1);
+        assert_eq!(calendar.heat(day("2026-03-19")), 0);
+        assert_eq!(calendar.latest(), Some(day("2026-03-18")));
+        assert_eq!(week_start(day("2026-03-18")), day("2026-03-16"));
+    }
+
+    #[test]
+    fn stepping_starts_on_the_latest_day_and_clears_past_today() {
+        let calendar = SessionCalendar::from_dates([day("2026-03-10")]);
+        let today = day("2026-03-18");
+        assert_eq!(step_day(None, -1, &calendar, today), Some(day("2026-03-10")));
+        assert_eq!(step_day(Some(day("2026-03-10")), -7, &calendar, today), Some(day("2026-03-03")));
+        assert_eq!(step_day(Some(day("2026-03-17")), 1, &calendar, today), Some(today));
+        assert_eq!(step_day(Some(today), 1, &calendar, today), None);
+    }
+}
@@ -0,0 +1,351 @@
+//! Named event channels: config rules that route events into tabs above the event stream.
+//! Rule syntax: `docs/configuration.md`, "Channels".
+
+use regex::Regex;
+
+use crate::error::RuleError;
+use crate::model::{TranscriptEvent, TranscriptEventKind};
+use crate::view::components::event_stream::{event_matches_search_transcript, format_transcript_event_lines};
+
+/// Event kinds a `kind:` term can name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KindTerm {
+    PreTool,
+    PostTool,
+    User,
+    Assistant,
+    Log,
+}
+
+impl KindTerm {
+    fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "pretool" => Some(Self::PreTool),
+            "posttool" => Some(Self::PostTool),
+            "user" => Some(Self::User),
+            "assistant" => Some(Self::Assistant),
+            "log" => Some(Self::Log),
+            _ => None,
+        }
+    }
+
+    fn matches(self, kind: &TranscriptEventKind) -> bool {
+        matches!(
+            (self, kind),
+            (Self::PreTool, TranscriptEventKind::ToolUse { .. })
+                | (Self::PostTool, TranscriptEventKind::ToolResult { .. })
+                | (Self::User, TranscriptEventKind::UserMessage)
+                | (Self::Assistant, TranscriptEventKind::AssistantMessage { .. })
+                | (Self::Log, TranscriptEventKind::ExternalLog { .. })
+        )
+    }
+}
+
+/// One term of a rule.
+#[derive(Debug, Clone)]
+enum Term {
+    Kind(Vec<KindTerm>),
+    /// Lowercased tool names
+    Tool(Vec<String>),
+    /// Lowercased origin label prefixes
+    Origin(Vec<String>),
+    /// Lowercased agent id fragments
+    Agent(Vec<String>),
+    /// Lowercased extra log names
+    Log(Vec<String>),
+    Detail(Regex),
+    /// Lowercased search text
+    Text(String),
+}
+
+impl Term {
+    fn matches(&self, event: &TranscriptEvent) -> bool {
+        match self {
+            Self::Kind(kinds) => kinds.iter().any(|kind| kind.matches(&event.kind)),
+            Self::Tool(tools) => {
+                let (_, _, _, _, tool_name) = format_transcript_event_lines(&event.kind);
+                tool_name.is_some_and(|name| tools.contains(&name.to_lowercase()))
+            }
+            Self::Origin(origins) => origins.iter().any(|origin| event.origin.label().starts_with(origin.as_str())),
+            Self::Agent(agents) => event
+                .agent_id
+                .as_ref()
+                .is_some_and(|id| agents.iter().any(|agent| id.as_str().to_lowercase().contains(agent.as_str()))),
+            Self::Log(logs) => match &event.kind {
+                TranscriptEventKind::ExternalLog { source, .. } => logs.contains(&source.to_lowercase()),
+                _ => false,
+            },
+            Self::Detail(regex) => {
+                let (_, _, detail, _, _) = format_transcript_event_lines(&event.kind);
+                detail.is_some_and(|detail| regex.is_match(&detail))
+            }
+            Self::Text(text) => event_matches_search_transcript(&event.kind, text, event.agent_id.as_ref(), event.origin),
+        }
+    }
+}
+
+/// A rule: terms that must all hold. Channels and severity styles share it.
+#[derive(Debug, Clone)]
+pub struct EventRule {
+    terms: Vec<Term>,
+}
+
+impl EventRule {
+    /// Parse a rule; `owner` names it in errors (`channel tests`).
+    pub fn parse(owner: &str, rule: &str) -> Result<Self, RuleError> {
+        let words = split_words(rule).ok_or_else(|| RuleError::UnterminatedQuote { rule: owner.to_string() })?;
+        if words.is_empty() {
+            return Err(RuleError::EmptyRule { rule: owner.to_string() });
+        }
+        let terms = words.iter().map(|word| parse_term(owner, word)).collect::<Result<_, _>>()?;
+        Ok(Self { terms })
+    }
+
+    pub fn matches(&self, event: &TranscriptEvent) -> bool {
+        self.terms.iter().all(|term| term.matches(event))
+    }
+}
+
+/// A named channel: the events matching its rule.
+#[derive(Debug, Clone)]
+pub struct EventChannel {
+    pub name: String,
+    rule: EventRule,
+}
+
+impl EventChannel {
+    /// Parse a `name: rule` definition.
+    pub fn parse(definition: &str) -> Result<Self, RuleError> {
+        let Some((name, rule)) = definition.split_once(':').filter(|(name, _)| {
+            !name.trim().is_empty() && !name.contains(char::is_whitespace)
+        }) else {
+            return Err(RuleError::MissingName(definition.to_string()));
+        };
+        let name = name.trim().to_string();
+        let rule = EventRule::parse(&format!("channel {}", name), rule)?;
+        Ok(Self { name, rule })
+    }
+
+    /// The channel of an extra log's lines.
+    pub fn for_log(name: &str) -> Self {
+        Self { name: name.to_string(), rule: EventRule { terms: vec![Term::Log(vec![name.to_lowercase()])] } }
+    }
+
+    pub fn matches(&self, event: &TranscriptEvent) -> bool {
+        self.rule.matches(event)
+    }
+}
+
+/// Pure function: split a rule into words at spaces outside double quotes,
+/// dropping the quotes (`\"` keeps one). `None` when a quote is left open.
+fn split_words(rule: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    let mut chars = rule.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if quoted && chars.peek() == Some(&'"') => word.push(chars.next()?),
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => word.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    Some(words)
+}
+
+fn parse_term(owner: &str, word: &str) -> Result<Term, RuleError> {
+    let alternatives = |value: &str| value.split('|').filter(|v| !v.is_empty()).map(str::to_lowercase).collect();
+    if let Some(pattern) = word.strip_prefix("detail~") {
+        return Regex::new(pattern).map(Term::Detail).map_err(|e| RuleError::Regex {
+            rule: owner.to_string(),
+            pattern: pattern.to_string(),
+            message: e.to_string(),
+        });
+    }
+    if let Some(kinds) = word.strip_prefix("kind:") {
+        return kinds
+            .split('|')
+            .map(|kind| {
+                KindTerm::parse(kind)
+                    .ok_or_else(|| RuleError::UnknownKind { rule: owner.to_string(), kind: kind.to_string() })
+            })
+            .collect::<Result<_, _>>()
+            .map(Term::Kind);
+    }
+    if let Some(tools) = word.strip_prefix("tool:") {
+        return Ok(Term::Tool(alternatives(tools)));
+    }
+    if let Some(origins) = word.strip_prefix("origin:") {
+        return Ok(Term::Origin(alternatives(origins)));
+    }
+    if let Some(agents) = word.strip_prefix("agent:") {
+        return Ok(Term::Agent(alternatives(agents)));
+    }
+    if let Some(logs) = word.strip_prefix("log:") {
+        return Ok(Term::Log(alternatives(logs)));
+    }
+    Ok(Term::Text(word.to_lowercase()))
+}
+
+/// The configured channels and which one the event stream shows.
+#[derive(Debug, Clone, Default)]
+pub struct EventChannels {
+    /// Definitions as written in the config, unusable ones included, so saving
+    /// settings does not drop them
+    definitions: Vec<String>,
+    channels: Vec<EventChannel>,
+    /// Index into `channels`; `None` shows all events
+    selected: Option<usize>,
+}
+
+impl EventChannels {
+    /// Parse the config's definitions. Unusable ones are left out and reported.
+    pub fn from_config(definitions: &[String]) -> (Self, Vec<RuleError>) {
+        let mut errors = Vec::new();
+        let channels = definitions
+            .iter()
+            .filter_map(|definition| EventChannel::parse(definition).map_err(|e| errors.push(e)).ok())
+            .collect();
+        (Self { definitions: definitions.to_vec(), channels, selected: None }, errors)
+    }
+
+    /// Append a channel per extra log, unless a configured channel has its name.
+    pub fn with_logs<'a>(mut self, names: impl Iterator<Item = &'a str>) -> Self {
+        for name in names {
+            if !self.channels.iter().any(|channel| channel.name == name) {
+                self.channels.push(EventChannel::for_log(name));
+            }
+        }
+        self
+    }
+
+    pub fn definitions(&self) -> &[String] {
+        &self.definitions
+    }
+
+    pub fn channels(&self) -> &[EventChannel] {
+        &self.channels
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// The channel the event stream is narrowed to.
+    pub fn current(&self) -> Option<&EventChannel> {
+        self.channels.get(self.selected?)
+    }
+
+    /// Step the selection through All and each channel, wrapping around.
+    pub fn cycle(&mut self, forward: bool) {
+        let count = self.channels.len();
+        self.selected = match (self.selected, forward) {
+            _ if count == 0 => None,
+            (None, true) => Some(0),
+            (None, false) => Some(count - 1),
+            (Some(idx), true) => Some(idx + 1).filter(|&next| next < count),
+            (Some(idx), false) => idx.checked_sub(1),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{EventOrigin, ToolName};
+    use chrono::Utc;
+
+    fn tool_use(tool: &str, input: &str) -> TranscriptEvent {
+        TranscriptEvent::new(
+            Utc::now(),
+            TranscriptEventKind::ToolUse { tool_name: ToolName::new(tool), input_summary: input.into() },
+        )
+    }
+
+    #[test]
+    fn rules_combine_kind_tool_and_detail_regex() {
+        let tests = EventChannel::parse(r#"tests: tool:Bash detail~"cargo (test|nextest)""#).unwrap();
+        assert_eq!(tests.name, "tests");
+        assert!(tests.matches(&tool_use("Bash", "cargo test --workspace")));
+        assert!(!tests.matches(&tool_use("Bash", "cargo build")));
+        assert!(!tests.matches(&tool_use("Read", "cargo test")));
+
+        let writes = EventChannel::parse("writes: kind:pretool tool:Edit|Write").unwrap();
+        assert!(writes.matches(&tool_use("Write", "src/lib.rs")));
+        assert!(!writes.matches(&tool_use("MultiEdit", "src/lib.rs")));
+        let result = TranscriptEvent::new(
+            Utc::now(),
+            TranscriptEventKind::ToolResult {
+                tool_name: ToolName::new("Edit"),
+                result_summary: String::new(),
+                duration_ms: None,
+                is_error: false,
+            },
+        );
+        assert!(!writes.matches(&result));
+
+        let hooks = EventChannel::parse("hooks: origin:hook edit").unwrap();
+        let mut event = tool_use("Edit", "src/lib.rs");
+        assert!(!hooks.matches(&event));
+        event.origin = EventOrigin::Hook;
+        assert!(hooks.matches(&event));
+    }
+
+    #[test]
+    fn unusable_definitions_are_reported_and_kept_for_saving() {
+        let definitions: Vec<String> = [
+            "tests: tool:Bash",
+            "no rule here",
+            "broken: detail~\"cargo (\"",
+            "odd: kind:pretool|sideways",
+            "open: detail~\"cargo test",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let (mut channels, errors) = EventChannels::from_config(&definitions);
+        assert_eq!(channels.channels().len(), 1);
+        assert_eq!(channels.definitions(), definitions.as_slice());
+        assert!(matches!(errors[0], RuleError::MissingName(_)));
+        assert!(matches!(errors[1], RuleError::Regex { .. }));
+        assert!(matches!(&errors[2], RuleError::UnknownKind { kind, .. } if kind == "sideways"));
+        assert!(matches!(errors[3], RuleError::UnterminatedQuote { .. }));
+
+        channels.cycle(true);
+        assert_eq!(channels.current().map(|c| c.name.as_str()), Some("tests"));
+        channels.cycle(true);
+        assert!(channels.current().is_none());
+        channels.cycle(false);
+        assert_eq!(channels.selected(), Some(0));
+    }
+
+    #[test]
+    fn extra_logs_get_a_channel_each() {
+        let (channels, _) = EventChannels::from_config(&["tests: tool:Bash".to_string()]);
+        let channels = channels.with_logs(["devserver", "tests"].into_iter());
+        let names: Vec<&str> = channels.channels().iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["tests", "devserver"]);
+        assert_eq!(channels.definitions().len(), 1);
+
+        let line = |source: &str| {
+            TranscriptEvent::new(
+                Utc::now(),
+                TranscriptEventKind::ExternalLog { source: source.into(), line: "GET / 500".into() },
+            )
+        };
+        assert!(channels.channels()[1].matches(&line("devserver")));
+        assert!(!channels.channels()[1].matches(&line("worker")));
+        assert!(EventChannel::parse("errors: kind:log 500").unwrap().matches(&line("worker")));
+    }
+}
@@ -0,0 +1,114 @@
+//! Column widths of the tables.
+//!
+//! Tables size their columns to their content (see
+//! `view::components::table`), which still leaves long agent types, goals and
+//! paths cut off on a narrow terminal. In the focused table `(` / `)` pick a
+//! column and `<` / `>` narrow or widen it; `=` goes back to the balanced
+//! widths. The adjustments are kept per table for the run, as offsets from the
+//! balanced width, so they follow the content as it changes.
+
+use std::collections::BTreeMap;
+
+use crate::app::{AppState, PanelFocus, ViewState};
+
+/// Columns one `<` / `>` press takes or gives.
+pub const RESIZE_STEP: i16 = 2;
+
+/// Largest offset from the balanced width, either way.
+const MAX_OFFSET: i16 = 200;
+
+/// A table whose columns can be resized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TableId {
+    Sessions,
+    ToolStats,
+    HotFiles,
+}
+
+/// One table's column selection and width offsets.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnAdjust {
+    pub selected: usize,
+    /// Offset from the balanced width per column; missing columns have none
+    pub offsets: Vec<i16>,
+}
+
+/// Adjusted tables; the rest show balanced widths.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableColumns {
+    tables: BTreeMap<TableId, ColumnAdjust>,
+}
+
+impl TableColumns {
+    pub fn get(&self, table: TableId) -> Option<&ColumnAdjust> {
+        self.tables.get(&table)
+    }
+
+    /// Move the column selection by `step`, wrapping within `count` columns.
+    pub fn select(&mut self, table: TableId, step: isize, count: usize) {
+        if count == 0 {
+            return;
+        }
+        let adjust = self.tables.entry(table).or_default();
+        adjust.selected = (adjust.selected as isize + step).rem_euclid(count as isize) as usize;
+    }
+
+    /// Widen (`by` > 0) or narrow the selected column.
+    pub fn resize(&mut self, table: TableId, by: i16) {
+        let adjust = self.tables.entry(table).or_default();
+        let selected = adjust.selected;
+        if adjust.offsets.len() <= selected {
+            adjust.offsets.resize(selected + 1, 0);
+        }
+        adjust.offsets[selected] = (adjust.offsets[selected] + by).clamp(-MAX_OFFSET, MAX_OFFSET);
+    }
+
+    /// Back to the balanced widths, keeping the selection.
+    pub fn reset(&mut self, table: TableId) {
+        if let Some(adjust) = self.tables.get_mut(&table) {
+            adjust.offsets.clear();
+        }
+    }
+}
+
+/// Pure function: the table the resize keys act on — the one with focus.
+/// Dashboard panels take no focus, so their tables are only balanced.
+pub fn focused_table(state: &AppState) -> Option<TableId> {
+    match (&state.ui.view, state.ui.focus) {
+        (ViewState::Sessions, PanelFocus::Left) => Some(TableId::Sessions),
+        _ => None,
+    }
+}
+
+/// Pure function: how many columns a table has.
+pub fn column_count(table: TableId) -> usize {
+    match table {
+        TableId::Sessions => 9,
+        TableId::ToolStats | TableId::HotFiles => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_wraps_and_offsets_stay_per_column() {
+        let mut columns = TableColumns::default();
+        columns.select(TableId::Sessions, -1, 9);
+        assert_eq!(columns.get(TableId::Sessions).map(|a| a.selected), Some(8));
+        columns.resize(TableId::Sessions, RESIZE_STEP);
+        columns.select(TableId::Sessions, 2, 9);
+        columns.resize(TableId::Sessions, -RESIZE_STEP);
+        let adjust = columns.get(TableId::Sessions).unwrap();
+        assert_eq!((adjust.selected, adjust.offsets[1], adjust.offsets[8]), (1, -2, 2));
+        assert!(columns.get(TableId::ToolStats).is_none());
+
+        for _ in 0..500 {
+            columns.resize(TableId::Sessions, RESIZE_STEP);
+        }
+        assert_eq!(columns.get(TableId::Sessions).unwrap().offsets[1], MAX_OFFSET);
+        columns.reset(TableId::Sessions);
+        assert!(columns.get(TableId::Sessions).unwrap().offsets.is_empty());
+    }
+}
@@ -0,0 +1,601 @@
+//! `:` command mode: ex-style commands parsed into [`Command`] and dispatched
+//! against `AppState`, so new features get a command instead of another key.
+//!
+//! Parsing and completion are pure; `execute` is the imperative shell (it may
+//! load or write session files, like other navigation actions).
+
+use std::time::Duration;
+
+use crate::app::graph_export::GraphFormat;
+use crate::app::{compat, cost_history, navigation, AppState, ConfirmAction, ConfirmState, Suspend, ViewState};
+use crate::error::CommandError;
+use crate::model::{ColorMode, SessionArchive, SessionId};
+use crate::session;
+
+/// Accepted range for `:set tick=`.
+pub const MIN_TICK: Duration = Duration::from_millis(20);
+pub const MAX_TICK: Duration = Duration::from_millis(2000);
+
+/// Static description of a command, used for prefix resolution and hints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub usage: &'static str,
+    /// Argument words offered as completions
+    pub args: &'static [&'static str],
+}
+
+/// All commands, in hint order.
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec { name: "export", usage: "export md|csv|mermaid|dot [clip]", args: &["md", "csv", "mermaid", "dot"] },
+    CommandSpec { name: "delete", usage: "delete session [id]", args: &["session"] },
+    CommandSpec { name: "migrate", usage: "migrate", args: &[] },
+    CommandSpec { name: "filter", usage: "filter [text | tool:Name | origin:Name]", args: &["tool:", "origin:"] },
+    CommandSpec { name: "set", usage: "set tick=<ms>", args: &["tick="] },
+    CommandSpec { name: "theme", usage: "theme normal|high-contrast|no-color", args: &["normal", "high-contrast", "no-color"] },
+    CommandSpec { name: "sh", usage: "sh", args: &[] },
+    CommandSpec { name: "schema", usage: "schema", args: &[] },
+    CommandSpec { name: "changelog", usage: "changelog", args: &[] },
+    CommandSpec { name: "quit", usage: "quit", args: &[] },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    /// Cost records of all archived sessions
+    Csv,
+}
+
+/// A parsed `:` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Export the session in focus (markdown) or archived spend (CSV)
+    Export(ExportFormat),
+    /// Export the task graph, to a file or (`clip`) the clipboard
+    ExportGraph { format: GraphFormat, clipboard: bool },
+    /// Ask to delete an archived session (explicit ID, or the session in focus)
+    DeleteSession(Option<SessionId>),
+    /// Rewrite the archive in focus, written by an older loom-tui, in this build's format
+    Migrate,
+    /// Set the search filter; `None` clears it
+    Filter(Option<String>),
+    /// Change the main loop tick interval
+    SetTick(Duration),
+    /// Switch the color mode (same as the Colors setting)
+    Theme(ColorMode),
+    /// Drop to a shell in the project root; the TUI resumes when it exits
+    Shell,
+    /// Show transcript fields loom-tui does not map
+    SchemaReport,
+    /// Show the release notes of the newer release found at startup
+    Changelog,
+    Quit,
+}
+
+/// Resolve a command name: exact match, else unique prefix (`:q`, `:exp`).
+fn resolve(name: &str) -> Option<&'static CommandSpec> {
+    COMMANDS.iter().find(|c| c.name == name).or_else(|| {
+        let mut matches = COMMANDS.iter().filter(|c| c.name.starts_with(name));
+        match (matches.next(), matches.next()) {
+            (Some(only), None) => Some(only),
+            _ => None,
+        }
+    })
+}
+
+/// Parse a command line (without the leading `:`).
+///
+/// # Functional Core
+/// Pure function.
+pub fn parse_command(input: &str) -> Result<Command, CommandError> {
+    let input = input.trim();
+    let (name, rest) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+    let rest = rest.trim();
+    let spec = resolve(name).ok_or_else(|| CommandError::Unknown(name.to_string()))?;
+
+    match spec.name {
+        "export" => {
+            let (format, target) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            let graph = |format| match target.trim() {
+                "" => Ok(Command::ExportGraph { format, clipboard: false }),
+                "clip" | "clipboard" => Ok(Command::ExportGraph { format, clipboard: true }),
+                other => Err(CommandError::InvalidArgument { command: "export", value: other.to_string(), expected: "clip" }),
+            };
+            match format {
+                "md" | "markdown" => Ok(Command::Export(ExportFormat::Markdown)),
+                "csv" => Ok(Command::Export(ExportFormat::Csv)),
+                "mermaid" | "mmd" => graph(GraphFormat::Mermaid),
+                "dot" | "graphviz" => graph(GraphFormat::Dot),
+                "" => Err(CommandError::MissingArgument { command: "export", expected: "format (md, csv, mermaid, dot)" }),
+                other => Err(CommandError::InvalidArgument {
+                    command: "export",
+                    value: other.to_string(),
+                    expected: "md, csv, mermaid or dot",
+                }),
+            }
+        }
+        "delete" => {
+            let (what, id) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            match what {
+                "session" => {
+                    let id = id.trim();
+                    Ok(Command::DeleteSession((!id.is_empty()).then(|| SessionId::new(id))))
+                }
+                "" => Err(CommandError::MissingArgument { command: "delete", expected: "session [id]" }),
+                other => Err(CommandError::InvalidArgument { command: "delete", value: other.to_string(), expected: "session" }),
+            }
+        }
+        "migrate" => Ok(Command::Migrate),
+        "filter" => Ok(Command::Filter((!rest.is_empty()).then(|| rest.to_string()))),
+        "set" => {
+            let (key, value) = rest.split_once('=').unwrap_or((rest, ""));
+            match key.trim() {
+                "tick" => parse_tick(value.trim()).map(Command::SetTick),
+                "" => Err(CommandError::MissingArgument { command: "set", expected: "tick=<ms>" }),
+                other => Err(CommandError::InvalidArgument { command: "set", value: other.to_string(), expected: "tick" }),
+            }
+        }
+        "theme" => {
+            let expected = "normal|high-contrast|no-color";
+            match rest {
+                "normal" => Ok(Command::Theme(ColorMode::Normal)),
+                "high-contrast" => Ok(Command::Theme(ColorMode::HighContrast)),
+                "no-color" => Ok(Command::Theme(ColorMode::NoColor)),
+                "" => Err(CommandError::MissingArgument { command: "theme", expected }),
+                other => Err(CommandError::InvalidArgument { command: "theme", value: other.to_string(), expected }),
+            }
+        }
+        "sh" => Ok(Command::Shell),
+        "schema" => Ok(Command::SchemaReport),
+        "changelog" => Ok(Command::Changelog),
+        "quit" => Ok(Command::Quit),
+        _ => Err(CommandError::Unknown(name.to_string())),
+    }
+}
+
+/// Parse `100ms`, `1s` or a bare millisecond count, within `MIN_TICK..=MAX_TICK`.
+fn parse_tick(value: &str) -> Result<Duration, CommandError> {
+    let invalid = || CommandError::InvalidArgument {
+        command: "set",
+        value: value.to_string(),
+        expected: "20ms..2000ms",
+    };
+    let millis = if let Some(ms) = value.strip_suffix("ms") {
+        ms.trim().parse::<u64>().ok()
+    } else if let Some(secs) = value.strip_suffix('s') {
+        secs.trim().parse::<f64>().ok().map(|s| (s * 1000.0).round() as u64)
+    } else {
+        value.parse::<u64>().ok()
+    };
+    let tick = Duration::from_millis(millis.ok_or_else(invalid)?);
+    if (MIN_TICK..=MAX_TICK).contains(&tick) {
+        Ok(tick)
+    } else {
+        Err(invalid())
+    }
+}
+
+/// Completion candidates for a partially typed command line.
+/// Each candidate is a full replacement for `input`.
+///
+/// # Functional Core
+/// Pure function over state (archived session IDs complete `delete session`).
+pub fn completions(state: &AppState, input: &str) -> Vec<String> {
+    let Some((name, rest)) = input.split_once(' ') else {
+        return COMMANDS
+            .iter()
+            .filter(|c| c.name.starts_with(input))
+            .map(|c| c.name.to_string())
+            .collect();
+    };
+    let Some(spec) = resolve(name) else {
+        return Vec::new();
+    };
+
+    if spec.name == "delete" {
+        if let Some(partial) = rest.strip_prefix("session ") {
+            return state
+                .domain
+                .sessions
+                .iter()
+                .map(|s| s.meta.id.as_str())
+                .filter(|id| id.starts_with(partial))
+                .map(|id| format!("delete session {}", id))
+                .collect();
+        }
+    }
+
+    spec.args
+        .iter()
+        .filter(|arg| arg.starts_with(rest) && **arg != rest)
+        .map(|arg| format!("{} {}", spec.name, arg))
+        .collect()
+}
+
+/// Tab completion: the single candidate, or the longest common prefix of several.
+/// Returns `None` when completion would not extend the input.
+pub fn complete(state: &AppState, input: &str) -> Option<String> {
+    let candidates = completions(state, input);
+    let first = candidates.first()?;
+    let common = candidates.iter().skip(1).fold(first.clone(), |acc, c| {
+        acc.chars()
+            .zip(c.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a)
+            .collect()
+    });
+    if common.len() <= input.len() {
+        return None;
+    }
+    // A finished command name with arguments gets a trailing space
+    let takes_args = resolve(&common).is_some_and(|c| c.name == common && !c.args.is_empty());
+    Some(if candidates.len() == 1 && takes_args { format!("{} ", common) } else { common })
+}
+
+/// Hint text for the command bar: matching commands, or the usage of the
+/// command being typed.
+pub fn hint(input: &str) -> String {
+    let name = input.split_whitespace().next().unwrap_or("");
+    if input.contains(' ') {
+        if let Some(spec) = resolve(name) {
+            return spec.usage.to_string();
+        }
+    }
+    COMMANDS
+        .iter()
+        .filter(|c| c.name.starts_with(name))
+        .map(|c| c.usage)
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+/// Run a parsed command.
+///
+/// # Returns
+/// * `Ok(Some(message))` - Done, with feedback for the command bar
+/// * `Ok(None)` - Done; the result is visible elsewhere (popup, filter bar)
+/// * `Err(CommandError)` - Command could not be carried out
+pub fn execute(state: &mut AppState, command: Command) -> Result<Option<String>, CommandError> {
+    match command {
+        Command::Export(ExportFormat::Markdown) => {
+            let failed = |message: String| CommandError::Failed { command: "export", message };
+            let sid = focused_session(state).ok_or_else(|| failed("no session in focus".into()))?;
+            export_session_markdown(state, &sid).map(Some)
+        }
+        Command::Export(ExportFormat::Csv) => {
+            let failed = |message: String| CommandError::Failed { command: "export", message };
+            let dir = state
+                .meta
+                .archive_dir
+                .as_ref()
+                .map(|d| d.join("exports"))
+                .ok_or_else(|| failed("no archive directory".into()))?;
+            navigation::preload_archives(state);
+            let records = cost_history::cost_records(state);
+            let csv = cost_history::to_csv(&records, &state.ui.time_format);
+            let path = dir.join(format!("costs-{}.csv", state.ui.time_format.date(state.meta.clock.now())));
+            std::fs::create_dir_all(&dir)
+                .and_then(|()| std::fs::write(&path, csv))
+                .map_err(|e| failed(format!("{}: {}", path.display(), e)))?;
+            Ok(Some(format!("exported {} cost records to {}", records.len(), path.display())))
+        }
+        Command::ExportGraph { format, clipboard } => {
+            let failed = |message: String| CommandError::Failed { command: "export", message };
+            let graph = state.domain.task_graph.as_ref().ok_or_else(|| failed("no task graph".into()))?;
+            let text = format.render(graph);
+            let tasks = graph.total_tasks();
+            if clipboard {
+                state.meta.clipboard = Some(text);
+                return Ok(Some(format!("copied task graph ({} tasks) as {}", tasks, format.extension())));
+            }
+            let dir = state
+                .meta
+                .archive_dir
+                .as_ref()
+                .map(|d| d.join("exports"))
+                .ok_or_else(|| failed("no archive directory".into()))?;
+            let date = state.ui.time_format.date(state.meta.clock.now());
+            let path = dir.join(format!("task-graph-{}.{}", date, format.extension()));
+            std::fs::create_dir_all(&dir)
+                .and_then(|()| std::fs::write(&path, text))
+                .map_err(|e| failed(format!("{}: {}", path.display(), e)))?;
+            Ok(Some(format!("exported task graph ({} tasks) to {}", tasks, path.display())))
+        }
+        Command::DeleteSession(id) => {
+            let failed = |message: String| CommandError::Failed { command: "delete", message };
+            let sid = id
+                .or_else(|| focused_session(state))
+                .ok_or_else(|| failed("no session in focus".into()))?;
+            if state.domain.active_sessions.contains_key(&sid) {
+                return Err(failed("cannot delete active session".into()));
+            }
+            if !state.domain.sessions.iter().any(|s| s.meta.id == sid) {
+                return Err(failed(format!("no archived session {}", sid)));
+            }
+            state.ui.confirm = ConfirmState::open(ConfirmAction::DeleteSessions(vec![sid]));
+            Ok(None)
+        }
+        Command::Migrate => {
+            let failed = |message: String| CommandError::Failed { command: "migrate", message };
+            let sid = focused_session(state).ok_or_else(|| failed("no session in focus".into()))?;
+            compat::migrate(state, &sid).map(Some).map_err(failed)
+        }
+        Command::Filter(filter) => {
+            state.ui.filters.applied = filter.is_some();
+            state.ui.filter = filter;
+            Ok(None)
+        }
+        Command::SetTick(tick) => {
+            state.meta.tick_rate = tick;
+            Ok(Some(format!("tick = {}ms", tick.as_millis())))
+        }
+        Command::Theme(mode) => {
+            state.ui.color_mode = mode;
+            Ok(Some(format!("colors = {}", mode.label())))
+        }
+        Command::Shell => {
+            state.meta.suspend = Some(Suspend::Shell);
+            Ok(None)
+        }
+        Command::SchemaReport => {
+            state.ui.show_schema_report = true;
+            Ok(None)
+        }
+        Command::Changelog => {
+            let failed = |message: &str| CommandError::Failed { command: "changelog", message: message.to_string() };
+            if state.meta.newer_release.is_some() {
+                state.ui.show_changelog = true;
+                Ok(None)
+            } else if state.meta.check_updates {
+                Err(failed("no newer release found"))
+            } else {
+                Err(failed("update check is off (settings)"))
+            }
+        }
+        Command::Quit => {
+            navigation::request_quit(state);
+            Ok(None)
+        }
+    }
+}
+
+/// Session a command applies to when none is named: the pinned session in
+/// Session Detail, the highlighted row in Sessions, else the newest active session.
+fn focused_session(state: &AppState) -> Option<SessionId> {
+    match state.ui.view {
+        ViewState::SessionDetail => state.ui.selected_session_id.clone(),
+        ViewState::Sessions => {
+            let idx = state.ui.selected_session_index?;
+            let active_count = state.domain.confirmed_active_count();
+            if idx < active_count {
+                state.domain.confirmed_active_sessions().nth(idx).map(|(id, _)| id.clone())
+            } else {
+                state.domain.sessions.get(idx - active_count).map(|s| s.meta.id.clone())
+            }
+        }
+        _ => state
+            .domain
+            .confirmed_active_sessions()
+            .max_by_key(|(_, m)| m.timestamp)
+            .map(|(id, _)| id.clone()),
+    }
+}
+
+/// Write a session's markdown report to the archive's `exports/`.
+/// I/O: may load the archive, writes the report.
+pub fn export_session_markdown(state: &AppState, sid: &SessionId) -> Result<String, CommandError> {
+    let failed = |message: String| CommandError::Failed { command: "export", message };
+    let archive = session_archive(state, sid).map_err(failed)?;
+    let dir = state
+        .meta
+        .archive_dir
+        .as_ref()
+        .map(|d| d.join("exports"))
+        .ok_or_else(|| failed("no archive directory".into()))?;
+    let path = session::write_markdown_export(&dir, &archive).map_err(|e| failed(e.to_string()))?;
+    Ok(format!("exported {} to {}", sid, path.display()))
+}
+
+/// Full archive for a session: built from live state for active sessions,
+/// from memory or disk for archived ones.
+fn session_archive(state: &AppState, sid: &SessionId) -> Result<SessionArchive, String> {
+    if let Some(meta) = state.domain.active_sessions.get(sid) {
+        return Ok(session::build_archive(
+            state.domain.task_graph.as_ref(),
+            &state.domain.events,
+            &state.domain.agents,
+            meta,
+        ));
+    }
+    let archived = state
+        .domain
+        .sessions
+        .iter()
+        .find(|s| &s.meta.id == sid)
+        .ok_or_else(|| format!("no session {}", sid))?;
+    match archived.complete_data() {
+        Some(archive) => Ok(archive.clone()),
+        None => session::load_session(&archived.path).map_err(|e| e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ArchivedSession, SessionMeta, Task, TaskGraph, TaskStatus, Wave};
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_each_command() {
+        assert_eq!(parse_command("export md"), Ok(Command::Export(ExportFormat::Markdown)));
+        assert_eq!(parse_command("export csv"), Ok(Command::Export(ExportFormat::Csv)));
+        assert_eq!(
+            parse_command("export mermaid"),
+            Ok(Command::ExportGraph { format: GraphFormat::Mermaid, clipboard: false })
+        );
+        assert_eq!(parse_command("export dot clip"), Ok(Command::ExportGraph { format: GraphFormat::Dot, clipboard: true }));
+        assert_eq!(parse_command("delete session s123"), Ok(Command::DeleteSession(Some(SessionId::new("s123")))));
+        assert_eq!(parse_command("delete session"), Ok(Command::DeleteSession(None)));
+        assert_eq!(parse_command("migrate"), Ok(Command::Migrate));
+        assert_eq!(parse_command("filter tool:Bash"), Ok(Command::Filter(Some("tool:Bash".into()))));
+        assert_eq!(parse_command("filter"), Ok(Command::Filter(None)));
+        assert_eq!(parse_command("set tick=100ms"), Ok(Command::SetTick(Duration::from_millis(100))));
+        assert_eq!(parse_command("theme high-contrast"), Ok(Command::Theme(ColorMode::HighContrast)));
+        assert_eq!(parse_command("sh"), Ok(Command::Shell));
+        assert_eq!(parse_command("schema"), Ok(Command::SchemaReport));
+        assert_eq!(parse_command("changelog"), Ok(Command::Changelog));
+        assert_eq!(parse_command("  quit "), Ok(Command::Quit));
+    }
+
+    #[test]
+    fn unique_prefixes_resolve() {
+        assert_eq!(parse_command("q"), Ok(Command::Quit));
+        assert_eq!(parse_command("exp md"), Ok(Command::Export(ExportFormat::Markdown)));
+        assert_eq!(parse_command("nope"), Err(CommandError::Unknown("nope".into())));
+    }
+
+    #[test]
+    fn reports_missing_and_invalid_arguments() {
+        assert!(matches!(parse_command("export"), Err(CommandError::MissingArgument { command: "export", .. })));
+        assert!(matches!(parse_command("export pdf"), Err(CommandError::InvalidArgument { command: "export", .. })));
+        assert!(matches!(parse_command("export dot file"), Err(CommandError::InvalidArgument { command: "export", .. })));
+        assert!(matches!(parse_command("theme dark"), Err(CommandError::InvalidArgument { command: "theme", .. })));
+        assert!(matches!(parse_command("set speed=1"), Err(CommandError::InvalidArgument { command: "set", .. })));
+    }
+
+    #[test]
+    fn tick_accepts_units_and_enforces_range() {
+        assert_eq!(parse_tick("1s"), Ok(Duration::from_millis(1000)));
+        assert_eq!(parse_tick("0.5s"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_tick("80"), Ok(Duration::from_millis(80)));
+        assert!(parse_tick("5ms").is_err());
+        assert!(parse_tick("10s").is_err());
+        assert!(parse_tick("fast").is_err());
+    }
+
+    #[test]
+    fn completes_command_names_and_arguments() {
+        let state = AppState::new();
+        assert_eq!(completions(&state, "e"), vec!["export"]);
+        assert_eq!(complete(&state, "e").as_deref(), Some("export "));
+        assert_eq!(completions(&state, "export "), vec!["export md", "export csv", "export mermaid", "export dot"]);
+        assert_eq!(complete(&state, "export c").as_deref(), Some("export csv"));
+        assert_eq!(complete(&state, "set t").as_deref(), Some("set tick="));
+        assert_eq!(complete(&state, "quit"), None);
+        assert_eq!(completions(&state, "").len(), COMMANDS.len());
+    }
+
+    #[test]
+    fn completes_archived_session_ids_for_delete() {
+        let mut state = AppState::new();
+        for id in ["s100", "s101", "x200"] {
+            state.domain.sessions.push(ArchivedSession::new(
+                SessionMeta::new(id, Utc::now(), "/p".into()),
+                PathBuf::new(),
+            ));
+        }
+        assert_eq!(completions(&state, "delete session s1").len(), 2);
+        assert_eq!(complete(&state, "delete session s1").as_deref(), Some("delete session s10"));
+        assert_eq!(complete(&state, "delete session x").as_deref(), Some("delete session x200"));
+    }
+
+    #[test]
+    fn hint_shows_usage_once_command_is_typed() {
+        assert_eq!(hint("set "), "set tick=<ms>");
+        assert!(hint("").contains("export md"));
+        assert!(hint("").contains("quit"));
+    }
+
+    #[test]
+    fn delete_opens_confirm_for_archived_session_only() {
+        let mut state = AppState::new();
+        state.domain.sessions.push(ArchivedSession::new(
+            SessionMeta::new("old", Utc::now(), "/p".into()),
+            PathBuf::new(),
+        ));
+        state.domain.active_sessions.insert(SessionId::new("live"), SessionMeta::new("live", Utc::now(), "/p".into()));
+
+        let err = execute(&mut state, Command::DeleteSession(Some(SessionId::new("live")))).unwrap_err();
+        assert!(err.to_string().contains("active"));
+        assert!(!state.ui.confirm.is_open());
+
+        execute(&mut state, Command::DeleteSession(Some(SessionId::new("old")))).unwrap();
+        assert_eq!(state.ui.confirm, ConfirmState::open(ConfirmAction::DeleteSessions(vec![SessionId::new("old")])));
+    }
+
+    #[test]
+    fn export_writes_markdown_for_focused_session() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut state = AppState::with_view(ViewState::SessionDetail);
+        state.meta.archive_dir = Some(tmp.path().to_path_buf());
+        let archive = SessionArchive::new(SessionMeta::new("s1", Utc::now(), "/p".into()));
+        state.domain.sessions.push(ArchivedSession::new(archive.meta.clone(), PathBuf::new()).with_data(archive));
+        state.ui.selected_session_id = Some(SessionId::new("s1"));
+
+        let message = execute(&mut state, Command::Export(ExportFormat::Markdown)).unwrap().unwrap();
+
+        let path = tmp.path().join("exports").join("s1.md");
+        assert!(message.contains(&path.display().to_string()));
+        assert!(std::fs::read_to_string(path).unwrap().starts_with("# Session s1"));
+    }
+
+    #[test]
+    fn export_csv_writes_archived_spend() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut state = AppState::new();
+        state.meta.archive_dir = Some(tmp.path().to_path_buf());
+        let mut meta = SessionMeta::new("s1", Utc::now(), "/p".into());
+        meta.token_usage.input_tokens = 1_000_000;
+        let archive = SessionArchive::new(meta.clone());
+        state.domain.sessions.push(ArchivedSession::new(meta, PathBuf::new()).with_data(archive));
+
+        let message = execute(&mut state, Command::Export(ExportFormat::Csv)).unwrap().unwrap();
+
+        assert!(message.starts_with("exported 1 cost records"));
+        let file = std::fs::read_dir(tmp.path().join("exports")).unwrap().next().unwrap().unwrap().path();
+        let csv = std::fs::read_to_string(file).unwrap();
+        assert!(csv.lines().nth(1).unwrap().starts_with(&format!("{},s1,p,main,", state.ui.time_format.date(Utc::now()))));
+    }
+
+    #[test]
+    fn export_graph_writes_a_file_or_the_clipboard() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut state = AppState::new();
+        state.meta.archive_dir = Some(tmp.path().to_path_buf());
+        let graph = |state: &mut AppState| execute(state, Command::ExportGraph { format: GraphFormat::Dot, clipboard: false });
+        assert!(matches!(graph(&mut state), Err(CommandError::Failed { command: "export", .. })));
+
+        state.domain.task_graph = Some(TaskGraph::new(vec![Wave::new(1, vec![Task::new("T1", "Scaffold".into(), TaskStatus::Running)])]));
+        let message = graph(&mut state).unwrap().unwrap();
+        assert!(message.starts_with("exported task graph (1 tasks)"));
+        let file = std::fs::read_dir(tmp.path().join("exports")).unwrap().next().unwrap().unwrap().path();
+        assert_eq!(file.extension().unwrap(), "dot");
+        assert!(std::fs::read_to_string(file).unwrap().contains("T1 [label=\"T1: Scaffold (running)\""));
+
+        execute(&mut state, Command::ExportGraph { format: GraphFormat::Mermaid, clipboard: true }).unwrap();
+        assert!(state.meta.clipboard.as_deref().unwrap().starts_with("flowchart TD"));
+    }
+
+    #[test]
+    fn shell_is_left_to_the_main_loop() {
+        let mut state = AppState::new();
+        assert_eq!(execute(&mut state, Command::Shell), Ok(None));
+        assert_eq!(state.meta.suspend, Some(Suspend::Shell));
+    }
+
+    #[test]
+    fn set_tick_updates_meta() {
+        let mut state = AppState::new();
+        execute(&mut state, Command::SetTick(Duration::from_millis(100))).unwrap();
+        assert_eq!(state.meta.tick_rate, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn theme_sets_the_color_mode() {
+        let mut state = AppState::new();
+        let message = execute(&mut state, Command::Theme(ColorMode::NoColor)).unwrap();
+        assert_eq!(message.as_deref(), Some("colors = no color"));
+        assert_eq!(state.ui.color_mode, ColorMode::NoColor);
+    }
+}
@@ -0,0 +1,150 @@
+//! Archive compatibility across loom-tui versions.
+//!
+//! Archives carry the version of the loom-tui that wrote them. One from a
+//! different major version (for 0.x, a different minor, as semver has it
+//! before 1.0) may hold fields this build reads differently, so SessionDetail
+//! shows a banner and keeps the archive read-only: no notes edits. An older
+//! archive can be migrated with `:migrate`, which rewrites it in this build's
+//! format; a newer one (or an unreadable stamp) is left alone. Archives from
+//! before versions were stamped count as compatible.
+
+use crate::app::{toasts, AppState};
+use crate::model::{SessionArchive, SessionId};
+use crate::session;
+
+/// How an archive's writer relates to this build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Compat {
+    Compatible,
+    /// Written by an older major version; migratable
+    Older(String),
+    /// Written by a newer major version, or the stamp can't be read
+    Newer(String),
+}
+
+impl Compat {
+    pub fn is_compatible(&self) -> bool {
+        matches!(self, Self::Compatible)
+    }
+
+    /// Pure function: the banner for an incompatible archive.
+    pub fn banner(&self) -> Option<String> {
+        let this = SessionArchive::BUILD_VERSION;
+        match self {
+            Self::Compatible => None,
+            Self::Older(v) => Some(format!("⚠ written by loom-tui {} (this is {}) — read-only · :migrate to convert", v, this)),
+            Self::Newer(v) => Some(format!("⚠ written by loom-tui {} (this is {}) — read-only · upgrade to edit", v, this)),
+        }
+    }
+}
+
+/// Pure function: the compatibility line of a version — its major, or
+/// `0.minor` before 1.0.
+fn line(version: &str) -> Option<(u64, u64)> {
+    let mut parts = version.trim().trim_start_matches('v').split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |m| {
+        let digits: String = m.chars().take_while(char::is_ascii_digit).collect();
+        digits.parse().ok()
+    })?;
+    Some(if major == 0 { (0, minor) } else { (major, 0) })
+}
+
+/// Pure function: can this build read an archive stamped `written_by` as is?
+pub fn check(written_by: Option<&str>) -> Compat {
+    let Some(version) = written_by else {
+        return Compat::Compatible;
+    };
+    match (line(version), line(SessionArchive::BUILD_VERSION)) {
+        (Some(theirs), Some(ours)) if theirs == ours => Compat::Compatible,
+        (Some(theirs), Some(ours)) if theirs < ours => Compat::Older(version.to_string()),
+        _ => Compat::Newer(version.to_string()),
+    }
+}
+
+/// Pure function: compatibility of an archived session, once loaded.
+pub fn of_session(state: &AppState, sid: &SessionId) -> Compat {
+    let written_by = state
+        .domain
+        .sessions
+        .iter()
+        .find(|s| &s.meta.id == sid)
+        .and_then(|s| s.data.as_ref())
+        .and_then(|data| data.written_by.as_deref());
+    check(written_by)
+}
+
+/// Rewrite an older archive in this build's format.
+/// I/O: reads and writes the archive file.
+///
+/// # Returns
+/// Feedback for the command bar, or why nothing was migrated.
+pub fn migrate(state: &mut AppState, sid: &SessionId) -> Result<String, String> {
+    match of_session(state, sid) {
+        Compat::Older(_) => {}
+        Compat::Compatible => return Err(format!("session {} needs no migration", sid)),
+        Compat::Newer(v) => return Err(format!("session {} was written by a newer loom-tui ({})", sid, v)),
+    }
+    let archived = state
+        .domain
+        .sessions
+        .iter_mut()
+        .find(|s| &s.meta.id == sid)
+        .ok_or_else(|| format!("no archived session {}", sid))?;
+    let expected = session::archive_stamp(&archived.path);
+    let mut archive = session::load_session(&archived.path).map_err(|e| e.to_string())?;
+    archive.version = SessionArchive::VERSION;
+    archive.written_by = Some(SessionArchive::BUILD_VERSION.to_string());
+    let report = session::save_session_guarded(&archived.path, &archive, expected).map_err(|e| e.to_string())?;
+    archived.stamp = report.stamp;
+    // The rewrite moves any events still paged on disk, so keep the full copy just read
+    if archived.data.is_some() {
+        archived.data = Some(archive);
+        archived.paging = None;
+    }
+    if report.clobbered {
+        toasts::warn_clobbered(state, sid);
+    }
+    Ok(format!("migrated {} to loom-tui {}", sid, SessionArchive::BUILD_VERSION))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ArchivedSession, SessionMeta};
+    use chrono::Utc;
+
+    #[test]
+    fn versions_compare_by_major_or_pre_1_0_minor() {
+        assert_eq!(line("0.1.0"), Some((0, 1)));
+        assert_eq!(line("v2.3.1-beta"), Some((2, 0)));
+        assert_eq!(line("0.4.0-rc1"), Some((0, 4)));
+        assert_eq!(line("garbage"), None);
+
+        assert!(check(None).is_compatible());
+        assert!(check(Some(SessionArchive::BUILD_VERSION)).is_compatible());
+        assert_eq!(check(Some("0.0.9")), Compat::Older("0.0.9".into()));
+        assert_eq!(check(Some("7.0.0")), Compat::Newer("7.0.0".into()));
+        assert_eq!(check(Some("nightly")), Compat::Newer("nightly".into()));
+        assert!(check(Some("7.0.0")).banner().unwrap().contains("upgrade to edit"));
+    }
+
+    #[test]
+    fn migrate_restamps_older_archives_only() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("old.json");
+        let mut archive = SessionArchive::new(SessionMeta::new("old", Utc::now(), "/p".into()));
+        archive.written_by = Some("0.0.9".into());
+        session::save_session(&path, &archive).unwrap();
+
+        let mut state = AppState::new();
+        state.domain.sessions.push(ArchivedSession::new(archive.meta.clone(), path.clone()).with_data(archive));
+        let sid = SessionId::new("old");
+
+        assert!(migrate(&mut state, &sid).unwrap().starts_with("migrated old"));
+        assert!(of_session(&state, &sid).is_compatible());
+        let on_disk = session::load_session(&path).unwrap();
+        assert_eq!(on_disk.written_by.as_deref(), Some(SessionArchive::BUILD_VERSION));
+        assert!(migrate(&mut state, &sid).unwrap_err().contains("needs no migration"));
+    }
+}
@@ -0,0 +1,359 @@
+//! Cost estimation, USD budgets and budget alerts.
+//! How spend and alerts survive restarts: `docs/design.md`, "Cost budgets".
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::app::{AppState, CommandMessage};
+use crate::error::SpendError;
+use crate::model::{Agent, AgentId, SessionId, SessionMeta};
+use crate::view::components::format::format_cost_usd;
+
+/// Daily spend file name, in the project state dir.
+pub const SPEND_FILE: &str = "spend.json";
+
+/// Percent of a budget at which an alert is raised, once each.
+pub const ALERT_LEVELS: [u8; 2] = [80, 100];
+
+// ── Pricing (per 1M tokens, in cents) ──────────────────────────────────────
+
+const OPUS_INPUT_PER_M: u64 = 1_500;   // $15
+const OPUS_OUTPUT_PER_M: u64 = 7_500;   // $75
+const SONNET_INPUT_PER_M: u64 = 300;    // $3
+const SONNET_OUTPUT_PER_M: u64 = 1_500;  // $15
+const HAIKU_INPUT_PER_M: u64 = 80;      // $0.80
+const HAIKU_OUTPUT_PER_M: u64 = 400;     // $4
+
+/// Pure function: estimated cost in cents of `input` and `output` tokens on `model`.
+pub fn estimate_cost_cents(model: &str, input: u64, output: u64) -> u64 {
+    let lower = model.to_lowercase();
+    let (input_rate, output_rate) = if lower.contains("opus") {
+        (OPUS_INPUT_PER_M, OPUS_OUTPUT_PER_M)
+    } else if lower.contains("haiku") {
+        (HAIKU_INPUT_PER_M, HAIKU_OUTPUT_PER_M)
+    } else {
+        // sonnet fallback
+        (SONNET_INPUT_PER_M, SONNET_OUTPUT_PER_M)
+    };
+    // cost = tokens * rate_per_million / 1_000_000
+    (input * input_rate + output * output_rate) / 1_000_000
+}
+
+/// Pure function: estimated cost in cents of one agent's tokens on its own model.
+pub fn agent_cost_cents(agent: &Agent) -> u64 {
+    let model = agent.model.as_deref().unwrap_or("unknown");
+    estimate_cost_cents(model, agent.token_usage.input_tokens, agent.token_usage.output_tokens)
+}
+
+/// Estimate cost per-agent (each agent's tokens × its own model's rate).
+pub fn estimate_session_cost(agents: &BTreeMap<AgentId, Agent>) -> u64 {
+    agents.values().map(agent_cost_cents).sum()
+}
+
+/// Confirmed active sessions with their subagents.
+/// Agents created via AgentMetadataUpdated often lack session_id,
+/// so unattributed agents are matched to the sole active session.
+pub fn live_session_agents(state: &AppState) -> Vec<(&SessionId, &SessionMeta, BTreeMap<AgentId, Agent>)> {
+    let active_sessions: Vec<_> = state.domain.confirmed_active_sessions().collect();
+    let single_active_sid = if active_sessions.len() == 1 { Some(active_sessions[0].0) } else { None };
+
+    active_sessions
+        .into_iter()
+        .map(|(sid, meta)| {
+            let agents = state
+                .domain
+                .agents
+                .iter()
+                .filter(|(_, a)| {
+                    a.session_id.as_ref() == Some(sid) || (a.session_id.is_none() && single_active_sid == Some(sid))
+                })
+                .map(|(id, a)| (id.clone(), a.clone()))
+                .collect();
+            (sid, meta, agents)
+        })
+        .collect()
+}
+
+/// Pure function: cost of a live session, main transcript plus subagents.
+pub fn live_session_cost(meta: &SessionMeta, agents: &BTreeMap<AgentId, Agent>) -> u64 {
+    let main_model = meta.model.as_deref().unwrap_or("unknown");
+    estimate_cost_cents(main_model, meta.token_usage.input_tokens, meta.token_usage.output_tokens)
+        + estimate_session_cost(agents)
+}
+
+/// USD budgets and where alerts go, from settings. `None` means no budget.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CostBudget {
+    pub session_cents: Option<u64>,
+    pub daily_cents: Option<u64>,
+    /// POST each alert as JSON to this URL
+    pub webhook: Option<String>,
+    /// Show each alert as a desktop notification
+    pub desktop: bool,
+}
+
+/// What a budget alert is about.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertScope {
+    Session(SessionId),
+    Daily(NaiveDate),
+}
+
+/// A budget threshold reached.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostAlert {
+    pub scope: AlertScope,
+    pub percent: u8,
+    pub spent_cents: u64,
+    pub budget_cents: u64,
+}
+
+impl CostAlert {
+    /// One-line description: `session 1a2b3c4d at 80% of its $5.00 budget ($4.02)`.
+    pub fn message(&self) -> String {
+        let what = match &self.scope {
+            AlertScope::Session(sid) => format!("session {}", sid.as_str().chars().take(8).collect::<String>()),
+            AlertScope::Daily(_) => "today's spend".to_string(),
+        };
+        let spent = format_cost_usd(self.spent_cents);
+        let budget = format_cost_usd(self.budget_cents);
+        if self.percent >= 100 {
+            format!("{} over its {} budget ({})", what, budget, spent)
+        } else {
+            format!("{} at {}% of its {} budget ({})", what, self.percent, budget, spent)
+        }
+    }
+}
+
+/// Alerts raised so far, plus those waiting for the main loop to deliver.
+#[derive(Debug, Clone, Default)]
+pub struct CostAlerts {
+    fired: HashSet<(AlertScope, u8)>,
+    /// Alerts for the desktop notification / webhook; drained by the main loop
+    pub pending: Vec<CostAlert>,
+}
+
+impl CostAlerts {
+    /// Alerts state after a restart: levels already raised today stay quiet.
+    pub fn resume(spend: &DailySpend) -> Self {
+        Self { fired: spend.alerted.iter().cloned().collect(), pending: Vec::new() }
+    }
+
+    /// Raise alerts for every level `spent` has reached that wasn't raised yet.
+    ///
+    /// # Returns
+    /// The newly raised alerts, lowest level first.
+    pub fn observe(&mut self, scope: AlertScope, spent_cents: u64, budget_cents: u64) -> Vec<CostAlert> {
+        ALERT_LEVELS
+            .iter()
+            .filter(|&&percent| spent_cents * 100 >= budget_cents * percent as u64)
+            .filter(|&&percent| self.fired.insert((scope.clone(), percent)))
+            .map(|&percent| CostAlert { scope: scope.clone(), percent, spent_cents, budget_cents })
+            .collect()
+    }
+}
+
+/// Cost of each session that ran on `date`, the day's spend being their sum.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailySpend {
+    pub date: Option<NaiveDate>,
+    pub sessions: BTreeMap<SessionId, u64>,
+    /// Budget alerts raised on `date`, so a restart doesn't raise them again
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub alerted: BTreeSet<(AlertScope, u8)>,
+    /// Changed since last saved
+    #[serde(skip)]
+    pub dirty: bool,
+}
+
+impl DailySpend {
+    /// Record a session's cost so far on `today`, starting afresh on a new day.
+    pub fn record(&mut self, today: NaiveDate, session: &SessionId, cents: u64) {
+        if self.date != Some(today) {
+            *self = Self { date: Some(today), dirty: true, ..Self::default() };
+        }
+        // Usage totals only grow; a smaller reading is a reparse in progress
+        let entry = self.sessions.entry(session.clone()).or_default();
+        if cents > *entry {
+            *entry = cents;
+            self.dirty = true;
+        }
+    }
+
+    /// Spend on `today` (zero when the record is from another day).
+    pub fn total_on(&self, today: NaiveDate) -> u64 {
+        if self.date == Some(today) { self.sessions.values().sum() } else { 0 }
+    }
+}
+
+/// Record live session costs into the day's spend and raise budget alerts.
+/// Call after token usage changes.
+pub fn check_budgets(state: &mut AppState, today: NaiveDate) {
+    let costs: Vec<(SessionId, u64)> = live_session_agents(state)
+        .into_iter()
+        .map(|(sid, meta, agents)| (sid.clone(), live_session_cost(meta, &agents)))
+        .collect();
+    for (sid, cents) in &costs {
+        state.domain.daily_spend.record(today, sid, *cents);
+    }
+
+    let budget = state.meta.cost_budget.clone();
+    let mut raised = Vec::new();
+    if let Some(limit) = budget.session_cents {
+        for (sid, cents) in costs {
+            raised.extend(state.domain.cost_alerts.observe(AlertScope::Session(sid), cents, limit));
+        }
+    }
+    if let Some(limit) = budget.daily_cents {
+        let spent = state.domain.daily_spend.total_on(today);
+        raised.extend(state.domain.cost_alerts.observe(AlertScope::Daily(today), spent, limit));
+    }
+
+    // The most severe alert is the one worth a toast
+    if let Some(alert) = raised.iter().max_by_key(|a| a.percent) {
+        state.ui.command_message = Some(CommandMessage { text: format!("⚠ {}", alert.message()), is_error: alert.percent >= 100 });
+    }
+    for alert in &raised {
+        state.domain.daily_spend.alerted.insert((alert.scope.clone(), alert.percent));
+        state.domain.daily_spend.dirty = true;
+        if state.meta.errors.len() >= 100 {
+            state.meta.errors.pop_front();
+        }
+        state.meta.errors.push_back(format!("budget: {}", alert.message()));
+    }
+    if budget.webhook.is_some() || budget.desktop {
+        state.domain.cost_alerts.pending.extend(raised);
+    }
+}
+
+/// Pure function: daily spend path for a task graph file.
+pub fn spend_path(task_graph: &Path) -> PathBuf {
+    task_graph.parent().unwrap_or(Path::new(".")).join(SPEND_FILE)
+}
+
+/// Read the recorded daily spend. A missing file yields an empty record.
+/// I/O: reads `path`.
+pub fn load_spend(path: &Path) -> Result<DailySpend, SpendError> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(serde_json::from_str(&content)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(DailySpend::default()),
+        Err(e) => Err(io_error(path, e)),
+    }
+}
+
+/// Write the daily spend record, creating its directory if needed.
+/// I/O: writes `path`.
+pub fn save_spend(path: &Path, spend: &DailySpend) -> Result<(), SpendError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(spend)?).map_err(|e| io_error(path, e))
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> SpendError {
+    SpendError::Io { path: path.display().to_string(), message: e.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TokenUsage;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, d).unwrap()
+    }
+
+    #[test]
+    fn alerts_fire_once_per_level() {
+        let mut alerts = CostAlerts::default();
+        let scope = AlertScope::Daily(day(18));
+        assert!(alerts.observe(scope.clone(), 700, 1000).is_empty());
+        let raised = alerts.observe(scope.clone(), 850, 1000);
+        assert_eq!(raised.len(), 1);
+        assert_eq!(raised[0].message(), "today's spend at 80% of its $10.00 budget ($8.50)");
+        assert!(alerts.observe(scope.clone(), 900, 1000).is_empty());
+
+        let raised = alerts.observe(scope, 1020, 1000);
+        assert_eq!(raised.len(), 1);
+        assert_eq!(raised[0].message(), "today's spend over its $10.00 budget ($10.20)");
+    }
+
+    #[test]
+    fn daily_spend_keeps_the_highest_reading_and_resets_each_day() {
+        let mut spend = DailySpend::default();
+        let sid = SessionId::new("s1");
+        spend.record(day(18), &sid, 300);
+        spend.record(day(18), &sid, 250);
+        spend.record(day(18), &SessionId::new("s2"), 100);
+        assert_eq!(spend.total_on(day(18)), 400);
+        assert_eq!(spend.total_on(day(19)), 0);
+
+        spend.record(day(19), &sid, 320);
+        assert_eq!(spend.total_on(day(19)), 320);
+    }
+
+    #[test]
+    fn check_budgets_alerts_for_a_live_session_and_persists_round_trip() {
+        let mut state = AppState::new();
+        let sid = SessionId::new("sess-costly");
+        let mut meta = SessionMeta::new(sid.clone(), chrono::Utc::now(), "/proj".into());
+        meta.confirmed = true;
+        meta.model = Some("claude-opus-4".into());
+        meta.token_usage = TokenUsage { input_tokens: 100_000, output_tokens: 50_000, ..TokenUsage::default() };
+        state.domain.active_sessions.insert(sid.clone(), meta);
+        state.meta.cost_budget = CostBudget { session_cents: Some(500), desktop: true, ..CostBudget::default() };
+
+        // $1.50 input + $3.75 output = $5.25 of a $5.00 budget: both levels at once
+        check_budgets(&mut state, day(18));
+        assert_eq!(state.domain.daily_spend.total_on(day(18)), 525);
+        assert_eq!(state.domain.cost_alerts.pending.len(), 2);
+        let toast = state.ui.command_message.as_ref().unwrap();
+        assert!(toast.is_error && toast.text.contains("over its $5.00 budget"), "{}", toast.text);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = spend_path(&dir.path().join("active_task_graph.json"));
+        save_spend(&path, &state.domain.daily_spend).unwrap();
+        let loaded = load_spend(&path).unwrap();
+        assert_eq!(loaded.total_on(day(18)), 525);
+        assert!(load_spend(&dir.path().join("missing.json")).unwrap().sessions.is_empty());
+    }
+
+    #[test]
+    fn alerts_raised_before_a_restart_are_not_raised_again() {
+        let sid = SessionId::new("sess-costly");
+        let mut meta = SessionMeta::new(sid.clone(), chrono::Utc::now(), "/proj".into());
+        meta.confirmed = true;
+        meta.model = Some("claude-opus-4".into());
+        meta.token_usage = TokenUsage { input_tokens: 100_000, output_tokens: 50_000, ..TokenUsage::default() };
+        let budget = CostBudget { session_cents: Some(500), daily_cents: Some(600), desktop: true, ..CostBudget::default() };
+
+        let mut state = AppState::new();
+        state.domain.active_sessions.insert(sid.clone(), meta.clone());
+        state.meta.cost_budget = budget.clone();
+        check_budgets(&mut state, day(18));
+        assert_eq!(state.domain.cost_alerts.pending.len(), 3);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(SPEND_FILE);
+        save_spend(&path, &state.domain.daily_spend).unwrap();
+
+        // Restart: the same spend, read back, raises nothing new
+        let loaded = load_spend(&path).unwrap();
+        let mut restarted = AppState::new();
+        restarted.domain.cost_alerts = CostAlerts::resume(&loaded);
+        restarted.domain.daily_spend = loaded;
+        restarted.domain.active_sessions.insert(sid, meta);
+        restarted.meta.cost_budget = budget;
+        check_budgets(&mut restarted, day(18));
+        assert!(restarted.domain.cost_alerts.pending.is_empty());
+        assert!(restarted.meta.errors.is_empty());
+
+        // A new day raises the daily alert afresh
+        check_budgets(&mut restarted, day(19));
+        assert_eq!(restarted.domain.cost_alerts.pending.len(), 1);
+    }
+}
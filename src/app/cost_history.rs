@@ -0,0 +1,328 @@
+//! Historical spend from archived sessions.
+//!
+//! Each archive is broken into cost records (the main transcript plus
+//! one per subagent), priced with the same table as the live estimate. The
+//! Tokens view's cost tab charts them per day or week and per project and
+//! agent type; `:export csv` writes the records themselves.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc};
+
+use crate::app::cost::estimate_cost_cents;
+use crate::app::state::project_name;
+use crate::app::AppState;
+use crate::model::{SessionArchive, SessionId};
+use crate::session;
+use crate::view::components::format::TimeFormat;
+
+/// Agent type recorded for a session's own (orchestrator) transcript.
+pub const MAIN_AGENT_TYPE: &str = "main";
+
+/// Agent type recorded for subagents that never reported one.
+pub const UNTYPED: &str = "untyped";
+
+/// Granularity of the spend chart (`w` toggles).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CostPeriod {
+    #[default]
+    Day,
+    Week,
+}
+
+impl CostPeriod {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Day => Self::Week,
+            Self::Week => Self::Day,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+        }
+    }
+
+    /// Pure function: first day of the period `date` falls in (weeks start Monday).
+    pub fn start(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Day => date,
+            Self::Week => date - Days::new(u64::from(date.weekday().num_days_from_monday())),
+        }
+    }
+
+    /// Chart label for a period starting on `start`: `03-18` / `wk 03-16`.
+    pub fn bucket_label(self, start: NaiveDate) -> String {
+        match self {
+            Self::Day => start.format("%m-%d").to_string(),
+            Self::Week => start.format("wk %m-%d").to_string(),
+        }
+    }
+}
+
+/// Spend of one transcript (a session's main transcript or one subagent).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostRecord {
+    pub session_id: SessionId,
+    /// When the session started
+    pub at: DateTime<Utc>,
+    pub project: String,
+    pub agent_type: String,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_cents: u64,
+}
+
+/// Pure function: cost records of one archive, main transcript first.
+/// Transcripts without API tokens are left out.
+pub fn archive_records(archive: &SessionArchive) -> Vec<CostRecord> {
+    let meta = &archive.meta;
+    let record = |agent_type: &str, model: Option<&str>, input: u64, output: u64| {
+        let model = model.unwrap_or("unknown");
+        CostRecord {
+            session_id: meta.id.clone(),
+            at: meta.timestamp,
+            project: project_name(&meta.project_path).to_string(),
+            agent_type: agent_type.to_string(),
+            model: model.to_string(),
+            input_tokens: input,
+            output_tokens: output,
+            cost_cents: estimate_cost_cents(model, input, output),
+        }
+    };
+
+    let mut records = Vec::new();
+    let main = &meta.token_usage;
+    if main.input_tokens + main.output_tokens > 0 {
+        records.push(record(MAIN_AGENT_TYPE, meta.model.as_deref(), main.input_tokens, main.output_tokens));
+    }
+    for agent in archive.agents.values() {
+        let usage = &agent.token_usage;
+        if usage.input_tokens + usage.output_tokens == 0 {
+            continue;
+        }
+        let agent_type = agent.agent_type.as_deref().unwrap_or(UNTYPED);
+        records.push(record(agent_type, agent.model.as_deref(), usage.input_tokens, usage.output_tokens));
+    }
+    records
+}
+
+/// Pure function: cost records of every archived session in every project,
+/// oldest session first. The startup scan covers the archives on disk; loaded
+/// archives it has not seen (archived since) are added, deleted ones dropped.
+pub fn cost_records(state: &AppState) -> Vec<CostRecord> {
+    let scanned: HashSet<&SessionId> = state.domain.archived_costs.iter().map(|r| &r.session_id).collect();
+    let loaded = state
+        .domain
+        .sessions
+        .iter()
+        .filter(|archived| !scanned.contains(&archived.meta.id))
+        .filter_map(|archived| archived.data.as_ref())
+        .flat_map(archive_records);
+    let mut records: Vec<CostRecord> = state
+        .domain
+        .archived_costs
+        .iter()
+        .cloned()
+        .chain(loaded)
+        .filter(|r| !state.domain.deleted_session_ids.contains(&r.session_id))
+        .collect();
+    records.sort_by_key(|r| r.at);
+    records
+}
+
+/// Load every archive in `archive_dir` (one at a time) and price it.
+/// Unreadable archives are skipped, like the anomaly baselines scan.
+pub fn load_records(archive_dir: &Path) -> Vec<CostRecord> {
+    let Ok((paths, _)) = session::list_archive_paths(archive_dir) else {
+        return Vec::new();
+    };
+    paths
+        .iter()
+        .filter_map(|path| session::load_session(path).ok())
+        .flat_map(|archive| archive_records(&archive))
+        .collect()
+}
+
+/// Spend totals for the cost tab. Breakdowns are sorted by spend, highest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CostHistory {
+    /// Spend per period start, oldest first; periods without spend are included
+    pub by_period: Vec<(NaiveDate, u64)>,
+    pub by_project: Vec<(String, u64)>,
+    pub by_agent_type: Vec<(String, u64)>,
+    pub total_cents: u64,
+}
+
+/// Pure function: totals of `records` per `period` (dates in `time_format`'s zone),
+/// project and agent type.
+pub fn summarize(records: &[CostRecord], period: CostPeriod, time_format: &TimeFormat) -> CostHistory {
+    let mut by_period: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+    let mut by_project: BTreeMap<&str, u64> = BTreeMap::new();
+    let mut by_agent_type: BTreeMap<&str, u64> = BTreeMap::new();
+    for r in records {
+        *by_period.entry(period.start(time_format.date(r.at))).or_default() += r.cost_cents;
+        *by_project.entry(&r.project).or_default() += r.cost_cents;
+        *by_agent_type.entry(&r.agent_type).or_default() += r.cost_cents;
+    }
+
+    // Fill gaps so the chart's x-axis is continuous
+    let mut periods = Vec::new();
+    if let (Some(&first), Some(&last)) = (by_period.keys().next(), by_period.keys().next_back()) {
+        let step = match period {
+            CostPeriod::Day => Days::new(1),
+            CostPeriod::Week => Days::new(7),
+        };
+        let mut start = first;
+        while start <= last {
+            periods.push((start, by_period.get(&start).copied().unwrap_or(0)));
+            start = start + step;
+        }
+    }
+
+    CostHistory {
+        by_period: periods,
+        by_project: ranked(by_project),
+        by_agent_type: ranked(by_agent_type),
+        total_cents: records.iter().map(|r| r.cost_cents).sum(),
+    }
+}
+
+fn ranked(totals: BTreeMap<&str, u64>) -> Vec<(String, u64)> {
+    let mut ranked: Vec<_> = totals.into_iter().map(|(k, v)| (k.to_string(), v)).collect();
+    ranked.sort_by_key(|(_, cents)| std::cmp::Reverse(*cents));
+    ranked
+}
+
+/// Pure function: `records` as CSV with a header row; dates in `time_format`'s zone.
+pub fn to_csv(records: &[CostRecord], time_format: &TimeFormat) -> String {
+    let mut csv = String::from("date,session_id,project,agent_type,model,input_tokens,output_tokens,cost_usd\n");
+    for r in records {
+        let row = [
+            time_format.date(r.at).to_string(),
+            csv_field(r.session_id.as_str()),
+            csv_field(&r.project),
+            csv_field(&r.agent_type),
+            csv_field(&r.model),
+            r.input_tokens.to_string(),
+            r.output_tokens.to_string(),
+            format!("{}.{:02}", r.cost_cents / 100, r.cost_cents % 100),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quote a field containing a comma, quote or newline (RFC 4180).
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{update, ProjectFilter};
+    use crate::event::AppEvent;
+    use crate::model::{Agent, AgentId, ArchivedSession, SessionMeta, TokenUsage};
+    use std::path::PathBuf;
+
+    fn utc() -> TimeFormat {
+        TimeFormat { utc: true, hour12: false }
+    }
+
+    fn archive(state: &mut AppState, id: &str, at: &str, project: &str, agents: &[(&str, Option<&str>, u64)]) {
+        let mut meta = SessionMeta::new(id, at.parse().unwrap(), project.to_string());
+        meta.model = Some("claude-opus-4-6".into());
+        meta.token_usage = TokenUsage { input_tokens: 100_000, ..Default::default() };
+        let agents = agents
+            .iter()
+            .map(|(agent_id, agent_type, input)| {
+                let mut a = Agent::new(*agent_id, meta.timestamp);
+                a.agent_type = agent_type.map(str::to_string);
+                a.model = Some("claude-sonnet-4-6".into());
+                a.token_usage = TokenUsage { input_tokens: *input, ..Default::default() };
+                (AgentId::new(*agent_id), a)
+            })
+            .collect();
+        let data = SessionArchive::new(meta.clone()).with_agents(agents);
+        state.domain.sessions.push(ArchivedSession::new(meta, PathBuf::new()).with_data(data));
+    }
+
+    #[test]
+    fn records_split_sessions_into_main_and_subagent_spend() {
+        let mut state = AppState::new();
+        archive(&mut state, "s1", "2026-03-18T10:00:00Z", "/work/api", &[("a1", Some("reviewer"), 1_000_000), ("a2", None, 0)]);
+        // Unloaded archives are skipped
+        let meta = SessionMeta::new("s2", Utc::now(), "/work/api".into());
+        state.domain.sessions.push(ArchivedSession::new(meta, PathBuf::new()));
+
+        let records = cost_records(&state);
+        let types: Vec<_> = records.iter().map(|r| (r.agent_type.as_str(), r.cost_cents)).collect();
+        assert_eq!(types, [(MAIN_AGENT_TYPE, 150), ("reviewer", 300)]);
+        assert!(records.iter().all(|r| r.project == "api"));
+    }
+
+    #[test]
+    fn records_cover_every_project_whatever_the_list_shows() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = AppState::new();
+        state.meta.project_path = "/work/api".into();
+        archive(&mut state, "s1", "2026-03-16T10:00:00Z", "/work/api", &[("a1", Some("reviewer"), 1_000_000)]);
+        archive(&mut state, "s2", "2026-03-18T10:00:00Z", "/work/web", &[("a2", None, 2_000_000)]);
+        for archived in &state.domain.sessions {
+            let path = session::archive_path(dir.path(), &archived.meta);
+            session::save_session(&path, archived.data.as_ref().unwrap()).unwrap();
+        }
+        // The project filter is left at Current, so the list holds only this project's archive
+        assert_eq!(state.ui.project_filter, ProjectFilter::Current);
+        state.domain.sessions.retain(|s| s.meta.project_path == "/work/api");
+
+        update(&mut state, AppEvent::CostRecordsLoaded(load_records(dir.path())));
+
+        // s1 is both scanned and loaded, and counted once (main transcript usage is not archived)
+        let history = summarize(&cost_records(&state), CostPeriod::Day, &utc());
+        assert_eq!(history.by_project, [("web".to_string(), 600), ("api".to_string(), 300)]);
+
+        state.domain.deleted_session_ids.insert(SessionId::new("s2"));
+        assert!(cost_records(&state).iter().all(|r| r.project == "api"));
+    }
+
+    #[test]
+    fn summary_fills_period_gaps_and_ranks_breakdowns() {
+        let mut state = AppState::new();
+        archive(&mut state, "s1", "2026-03-16T10:00:00Z", "/work/api", &[("a1", Some("reviewer"), 1_000_000)]);
+        archive(&mut state, "s2", "2026-03-18T10:00:00Z", "/work/web", &[("a2", None, 3_000_000)]);
+        let records = cost_records(&state);
+
+        let daily = summarize(&records, CostPeriod::Day, &utc());
+        let days: Vec<_> = daily.by_period.iter().map(|(d, c)| (d.to_string(), *c)).collect();
+        assert_eq!(days, [("2026-03-16".to_string(), 450), ("2026-03-17".to_string(), 0), ("2026-03-18".to_string(), 1050)]);
+        assert_eq!(daily.by_project, [("web".to_string(), 1050), ("api".to_string(), 450)]);
+        assert_eq!(daily.by_agent_type[0], (UNTYPED.to_string(), 900));
+        assert_eq!(daily.total_cents, 1500);
+
+        // 2026-03-16 is a Monday: both sessions fall in the same week
+        let weekly = summarize(&records, CostPeriod::Week, &utc());
+        assert_eq!(weekly.by_period.len(), 1);
+        assert_eq!(CostPeriod::Week.bucket_label(weekly.by_period[0].0), "wk 03-16");
+    }
+
+    #[test]
+    fn csv_has_a_header_and_quotes_awkward_fields() {
+        let mut state = AppState::new();
+        archive(&mut state, "s1", "2026-03-18T10:00:00Z", "/work/a,b", &[]);
+        let csv = to_csv(&cost_records(&state), &utc());
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "date,session_id,project,agent_type,model,input_tokens,output_tokens,cost_usd");
+        assert_eq!(lines[1], "2026-03-18,s1,\"a,b\",main,claude-opus-4-6,100000,0,1.50");
+    }
+}
@@ -0,0 +1,172 @@
+//! Cross-source deduplication of transcript events.
+//!
+//! One tool call can reach loom-tui more than once when several sources feed
+//! the same session (a hook stream and the transcript file, a restarted socket
+//! producer). An event is a duplicate when an event with the same `DedupSig`
+//! from a different origin was seen within `window` of it. Duplicates are
+//! counted per origin and dropped, unless `meta.show_duplicates` is on for
+//! debugging attribution. A zero window turns deduplication off.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::model::{AgentId, EventOrigin, SessionId, TranscriptEvent, TranscriptEventKind};
+
+/// Default window: sources stamp the same call within a second or two.
+pub const DEFAULT_WINDOW: Duration = Duration::milliseconds(2000);
+
+/// What makes two events the same call. Timing fields that differ between
+/// sources (a tool result's `duration_ms`) are left out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DedupSig {
+    session_id: Option<SessionId>,
+    agent_id: Option<AgentId>,
+    kind: &'static str,
+    tool_name: Option<String>,
+    text: String,
+}
+
+impl DedupSig {
+    /// Pure function: signature of one event.
+    pub fn of(event: &TranscriptEvent) -> Self {
+        let (kind, tool_name, text) = match &event.kind {
+            TranscriptEventKind::UserMessage => ("user", None, String::new()),
+            TranscriptEventKind::AssistantMessage { content } => ("assistant", None, content.clone()),
+            TranscriptEventKind::ToolUse { tool_name, input_summary } => {
+                ("tool_use", Some(tool_name.to_string()), input_summary.clone())
+            }
+            TranscriptEventKind::ToolResult { tool_name, result_summary, .. } => {
+                ("tool_result", Some(tool_name.to_string()), result_summary.clone())
+            }
+            TranscriptEventKind::ExternalLog { source, line } => ("log", Some(source.clone()), line.clone()),
+            TranscriptEventKind::ConfigChanged { file, change } => ("config", Some(file.clone()), change.clone()),
+            TranscriptEventKind::StatusUpdate { text, task_id } => ("status", task_id.clone(), text.clone()),
+            TranscriptEventKind::ApiError { error_kind, message, .. } => {
+                ("api_error", Some(error_kind.label().to_string()), message.clone())
+            }
+            TranscriptEventKind::Unknown { entry_type } => ("unknown", None, entry_type.clone()),
+        };
+        Self {
+            session_id: event.session_id.clone(),
+            agent_id: event.agent_id.clone(),
+            kind,
+            tool_name,
+            text,
+        }
+    }
+}
+
+/// Recently seen signatures plus counts of the duplicates found.
+#[derive(Debug, Clone)]
+pub struct Deduplicator {
+    window: Duration,
+    /// Oldest first, trimmed to `window` before the newest timestamp
+    recent: VecDeque<(DateTime<Utc>, EventOrigin, DedupSig)>,
+    /// Duplicates found, by the origin of the copy that arrived second
+    duplicates: BTreeMap<EventOrigin, usize>,
+}
+
+impl Default for Deduplicator {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOW)
+    }
+}
+
+impl Deduplicator {
+    pub fn new(window: Duration) -> Self {
+        Self { window, recent: VecDeque::new(), duplicates: BTreeMap::new() }
+    }
+
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Change the window; remembered signatures are kept.
+    pub fn set_window(&mut self, window: Duration) {
+        self.window = window;
+    }
+
+    /// Remember `event` and report whether another source already delivered it.
+    ///
+    /// # Returns
+    /// True when the event is a duplicate (it is counted, not remembered).
+    pub fn observe(&mut self, event: &TranscriptEvent) -> bool {
+        if self.window <= Duration::zero() {
+            return false;
+        }
+        let sig = DedupSig::of(event);
+        let duplicate = self.recent.iter().any(|(at, origin, seen)| {
+            *origin != event.origin && (*at - event.timestamp).abs() <= self.window && *seen == sig
+        });
+        if duplicate {
+            *self.duplicates.entry(event.origin).or_default() += 1;
+            return true;
+        }
+
+        let idx = self.recent.partition_point(|(at, _, _)| *at <= event.timestamp);
+        self.recent.insert(idx, (event.timestamp, event.origin, sig));
+        if let Some(&(newest, _, _)) = self.recent.back() {
+            while self.recent.front().is_some_and(|(at, _, _)| *at < newest - self.window) {
+                self.recent.pop_front();
+            }
+        }
+        false
+    }
+
+    /// Duplicates found so far, by the origin of the later copy.
+    pub fn duplicates(&self) -> &BTreeMap<EventOrigin, usize> {
+        &self.duplicates
+    }
+
+    pub fn total_duplicates(&self) -> usize {
+        self.duplicates.values().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool_use(secs: i64, origin: EventOrigin) -> TranscriptEvent {
+        let t0: DateTime<Utc> = "2026-03-18T10:00:00Z".parse().unwrap();
+        TranscriptEvent::new(t0 + Duration::seconds(secs), TranscriptEventKind::ToolUse {
+            tool_name: "Bash".into(),
+            input_summary: "cargo test".into(),
+        })
+        .with_session("s1")
+        .with_origin(origin)
+    }
+
+    #[test]
+    fn same_call_from_another_source_within_window_is_a_duplicate() {
+        let mut dedup = Deduplicator::default();
+        assert!(!dedup.observe(&tool_use(0, EventOrigin::Hook)));
+        assert!(dedup.observe(&tool_use(1, EventOrigin::MainTranscript)));
+        assert_eq!(dedup.duplicates()[&EventOrigin::MainTranscript], 1);
+
+        // Same source repeating a call is a real second call
+        assert!(!dedup.observe(&tool_use(1, EventOrigin::Hook)));
+        // Outside the window the other source's copy is new
+        assert!(!dedup.observe(&tool_use(10, EventOrigin::MainTranscript)));
+        assert_eq!(dedup.total_duplicates(), 1);
+    }
+
+    #[test]
+    fn signature_ignores_result_timing_and_zero_window_disables() {
+        let result = |ms, origin| {
+            TranscriptEvent::new(Utc::now(), TranscriptEventKind::ToolResult {
+                tool_name: "Read".into(),
+                result_summary: "ok".into(),
+                duration_ms: Some(ms),
+                is_error: false,
+            })
+            .with_origin(origin)
+        };
+        assert_eq!(DedupSig::of(&result(5, EventOrigin::Hook)), DedupSig::of(&result(90, EventOrigin::Hook)));
+
+        let mut off = Deduplicator::new(Duration::zero());
+        assert!(!off.observe(&result(5, EventOrigin::Hook)));
+        assert!(!off.observe(&result(5, EventOrigin::MainTranscript)));
+    }
+}
@@ -0,0 +1,158 @@
+//! `o`: open the file referenced by the selected step in `$EDITOR`.
+//!
+//! Finding the file and building the editor command line are pure; the main
+//! loop suspends the TUI and runs the editor (see `Suspend::Editor`).
+
+use std::path::{Path, PathBuf};
+
+use crate::app::AppState;
+use crate::model::TranscriptEventKind;
+use crate::view::components::step_tree::{agent_steps, Step};
+
+/// Editor used when neither `$VISUAL` nor `$EDITOR` is set.
+pub const DEFAULT_EDITOR: &str = "vi";
+
+/// A file to open, with the line to jump to when known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRef {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+}
+
+/// File referenced by the step under the cursor in the agent activity tree
+/// (AgentDetail, or the split view's right half).
+/// Relative paths fall back to the project root when the event has no cwd.
+pub fn selected_file_ref(state: &AppState) -> Option<FileRef> {
+    let agent = state.followed_agent()?;
+    let steps = agent_steps(state, agent.id.as_str());
+    let idx = state.ui.scroll_offsets.agent_events.min(steps.len().checked_sub(1)?);
+    let step = steps.iter().rev().nth(idx)?;
+    file_ref_in_step(step, Path::new(&state.meta.project_path))
+}
+
+/// Pure function: first Read/Edit/Write path in the step, resolved against the
+/// event's cwd (else `fallback_dir`). The line is the first numbered line of the
+/// matching tool result (Read output and Edit snippets are `cat -n` style).
+pub fn file_ref_in_step(step: &Step<'_>, fallback_dir: &Path) -> Option<FileRef> {
+    let events: Vec<_> = std::iter::once(step.head).chain(step.tools.iter().copied()).collect();
+    let (idx, event, tool, path) = events.iter().enumerate().find_map(|(i, e)| match &e.kind {
+        TranscriptEventKind::ToolUse { tool_name, input_summary }
+            if matches!(tool_name.as_str(), "Read" | "Edit" | "Write") && !input_summary.trim().is_empty() =>
+        {
+            Some((i, *e, tool_name, input_summary.trim()))
+        }
+        _ => None,
+    })?;
+
+    let path = Path::new(path);
+    let path = if path.is_relative() {
+        event.cwd.as_deref().map_or(fallback_dir, Path::new).join(path)
+    } else {
+        path.to_path_buf()
+    };
+    let line = events[idx + 1..].iter().find_map(|e| match &e.kind {
+        TranscriptEventKind::ToolResult { tool_name, result_summary, .. } if tool_name == tool => {
+            Some(first_numbered_line(result_summary))
+        }
+        _ => None,
+    });
+    Some(FileRef { path, line: line.flatten() })
+}
+
+/// Pure function: number of the first `cat -n` style line (`   42→text` or `42\ttext`).
+fn first_numbered_line(text: &str) -> Option<usize> {
+    text.lines().find_map(|line| {
+        let line = line.trim_start();
+        let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let rest = &line[digits..];
+        if digits > 0 && (rest.starts_with('→') || rest.starts_with('\t')) {
+            line[..digits].parse().ok().filter(|n| *n > 0)
+        } else {
+            None
+        }
+    })
+}
+
+/// Pure function: argv that opens `file` in `editor` (a command line such as
+/// `nvim` or `code -w`). Editors taking `file:line` get that form, the rest `+line file`.
+pub fn editor_command(editor: &str, file: &FileRef) -> Option<Vec<String>> {
+    let mut argv: Vec<String> = editor.split_whitespace().map(str::to_string).collect();
+    let program = Path::new(argv.first()?).file_name()?.to_string_lossy().into_owned();
+    let path = file.path.display().to_string();
+    match file.line {
+        None => argv.push(path),
+        Some(line) => match program.as_str() {
+            "code" | "code-insiders" | "codium" | "cursor" => {
+                argv.push("--goto".to_string());
+                argv.push(format!("{path}:{line}"));
+            }
+            "hx" | "helix" | "subl" | "zed" => argv.push(format!("{path}:{line}")),
+            _ => {
+                argv.push(format!("+{line}"));
+                argv.push(path);
+            }
+        },
+    }
+    Some(argv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ToolName, TranscriptEvent};
+    use chrono::Utc;
+
+    fn tool_use(tool: &str, path: &str) -> TranscriptEvent {
+        TranscriptEvent::new(
+            Utc::now(),
+            TranscriptEventKind::ToolUse { tool_name: ToolName::new(tool), input_summary: path.into() },
+        )
+    }
+
+    fn tool_result(tool: &str, text: &str) -> TranscriptEvent {
+        TranscriptEvent::new(
+            Utc::now(),
+            TranscriptEventKind::ToolResult { tool_name: ToolName::new(tool), result_summary: text.into(), duration_ms: None, is_error: false },
+        )
+    }
+
+    #[test]
+    fn finds_path_and_line_from_tool_result() {
+        let head = TranscriptEvent::new(Utc::now(), TranscriptEventKind::AssistantMessage { content: "look".into() });
+        let bash = tool_use("Bash", "ls");
+        let read = tool_use("Read", "src/lib.rs").with_cwd("/work/proj");
+        let result = tool_result("Read", "    40→fn main() {\n    41→}");
+        let step = Step { head: &head, tools: vec![&bash, &read, &result] };
+
+        let file = file_ref_in_step(&step, Path::new("/fallback")).unwrap();
+        assert_eq!(file, FileRef { path: PathBuf::from("/work/proj/src/lib.rs"), line: Some(40) });
+    }
+
+    #[test]
+    fn relative_path_without_cwd_uses_fallback_and_no_line() {
+        let edit = tool_use("Edit", "a.rs");
+        let step = Step { head: &edit, tools: vec![] };
+        let file = file_ref_in_step(&step, Path::new("/fallback")).unwrap();
+        assert_eq!(file, FileRef { path: PathBuf::from("/fallback/a.rs"), line: None });
+
+        let grep = tool_use("Grep", "fn main");
+        assert!(file_ref_in_step(&Step { head: &grep, tools: vec![] }, Path::new("/")).is_none());
+    }
+
+    #[test]
+    fn numbered_line_needs_arrow_or_tab() {
+        assert_eq!(first_numbered_line("The file was updated:\n  7\tlet x = 1;"), Some(7));
+        assert_eq!(first_numbered_line("2026 was a year"), None);
+    }
+
+    #[test]
+    fn editor_command_line_forms() {
+        let file = FileRef { path: PathBuf::from("/p/a.rs"), line: Some(12) };
+        assert_eq!(editor_command("nvim", &file).unwrap(), vec!["nvim", "+12", "/p/a.rs"]);
+        assert_eq!(editor_command("code -w", &file).unwrap(), vec!["code", "-w", "--goto", "/p/a.rs:12"]);
+        assert_eq!(editor_command("/usr/bin/hx", &file).unwrap(), vec!["/usr/bin/hx", "/p/a.rs:12"]);
+        let no_line = FileRef { line: None, ..file };
+        assert_eq!(editor_command("vi", &no_line).unwrap(), vec!["vi", "/p/a.rs"]);
+        assert!(editor_command("  ", &no_line).is_none());
+    }
+}
@@ -0,0 +1,146 @@
+//! Guidance for empty panels.
+//!
+//! A bare "No events" reads the same whether the watcher died, the hook was
+//! never installed or the project is just idle. `guidance` looks at what is
+//! actually known — replay progress, watcher heartbeats, the event source and
+//! the newest event — and says which it is, with the next thing to check.
+
+use chrono::{DateTime, Utc};
+
+use crate::app::AppState;
+use crate::view::components::format::format_elapsed;
+use crate::watcher::health::Degradation;
+use crate::watcher::source::SourceKind;
+
+/// Panels that show guidance when empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyPanel {
+    Agents,
+    Events,
+}
+
+impl EmptyPanel {
+    fn noun(self) -> &'static str {
+        match self {
+            Self::Agents => "agents",
+            Self::Events => "events",
+        }
+    }
+}
+
+/// What an empty panel shows: why it is empty, then what to check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guidance {
+    pub headline: String,
+    pub hints: Vec<String>,
+}
+
+/// Pure function: guidance for `panel` when it has nothing to list.
+pub fn guidance(state: &AppState, panel: EmptyPanel) -> Guidance {
+    let now = state.meta.clock.now();
+    let noun = panel.noun();
+    let last_check = last_check(state, now);
+    let mut hints = Vec::new();
+
+    let headline = if !state.meta.replay_complete {
+        format!("No {} yet — still reading existing transcripts…", noun)
+    } else if let Some((thread, degradation)) = state.meta.watchdog.degraded.first() {
+        let what = match degradation {
+            Degradation::Exited => "stopped",
+            Degradation::Stalled => "stalled",
+        };
+        hints.push("it is restarted automatically; new events show up once it is back".to_string());
+        format!("No {} — watcher thread {} has {}", noun, thread, what)
+    } else if let Some(newest) = state.domain.events.back() {
+        hints.push(format!("last event {} ago", format_elapsed((now - newest.timestamp).num_seconds().max(0))));
+        match panel {
+            EmptyPanel::Agents => "No agents — the session has not spawned any subagents yet".to_string(),
+            EmptyPanel::Events => {
+                hints.push(format!("clear the filters to see all {}", state.domain.events.len()));
+                "No events match the current filters".to_string()
+            }
+        }
+    } else {
+        match &state.meta.source {
+            SourceKind::File => {
+                hints.push(format!("start a session in {}", state.meta.project_path));
+                hints.push("run `loom-tui doctor` to see where transcripts are read from".to_string());
+                format!("No {} yet — no transcript written for this project", noun)
+            }
+            SourceKind::Stdin => {
+                hints.push("is the hook piping transcript lines into `loom-tui --stdin`?".to_string());
+                format!("No {} yet — nothing arrived on standard input", noun)
+            }
+            SourceKind::Socket(path) => {
+                hints.push(format!("is the hook installed and writing to {}?", path.display()));
+                format!("No hook {} yet — nothing written to the socket", noun)
+            }
+        }
+    };
+    if let Some(ago) = last_check {
+        hints.push(format!("watcher last checked {} ago", ago));
+    }
+    Guidance { headline, hints }
+}
+
+/// Time since the freshest heartbeat of a live watcher thread, as `12s`.
+fn last_check(state: &AppState, now: DateTime<Utc>) -> Option<String> {
+    let health = state.meta.watchdog.health.as_ref()?;
+    let newest = health.snapshot().values().filter(|status| !status.exited).map(|status| status.last_beat).max()?;
+    Some(format_elapsed((now - newest).num_seconds().max(0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{TranscriptEvent, TranscriptEventKind};
+    use crate::watcher::health::HealthMap;
+    use std::path::PathBuf;
+
+    fn live() -> AppState {
+        let mut state = AppState::new();
+        state.meta.replay_complete = true;
+        state
+    }
+
+    #[test]
+    fn says_why_nothing_has_arrived() {
+        let mut state = AppState::new();
+        assert!(guidance(&state, EmptyPanel::Events).headline.contains("still reading existing transcripts"));
+
+        state.meta.replay_complete = true;
+        state.meta.project_path = "/work/app".into();
+        let files = guidance(&state, EmptyPanel::Agents);
+        assert_eq!(files.headline, "No agents yet — no transcript written for this project");
+        assert_eq!(files.hints[0], "start a session in /work/app");
+
+        state.meta.source = SourceKind::Socket(PathBuf::from("/tmp/loom.sock"));
+        let socket = guidance(&state, EmptyPanel::Events);
+        assert_eq!(socket.headline, "No hook events yet — nothing written to the socket");
+        assert_eq!(socket.hints, ["is the hook installed and writing to /tmp/loom.sock?"]);
+    }
+
+    #[test]
+    fn a_degraded_watcher_is_named_before_anything_else() {
+        let mut state = live();
+        state.meta.watchdog.degraded = vec![("poller", Degradation::Stalled)];
+        assert_eq!(guidance(&state, EmptyPanel::Events).headline, "No events — watcher thread poller has stalled");
+    }
+
+    #[test]
+    fn with_events_it_points_at_the_filters_and_the_last_event() {
+        let mut state = live();
+        let now = state.meta.clock.now();
+        state.domain.events.push_back(TranscriptEvent::new(now - chrono::Duration::seconds(12), TranscriptEventKind::UserMessage));
+        let health = HealthMap::new();
+        let _beat = health.register("poller");
+        state.meta.watchdog.health = Some(health);
+
+        let events = guidance(&state, EmptyPanel::Events);
+        assert_eq!(events.headline, "No events match the current filters");
+        assert_eq!(events.hints[0], "last event 12s ago");
+        assert!(events.hints.last().unwrap().starts_with("watcher last checked "));
+        let agents = guidance(&state, EmptyPanel::Agents);
+        assert_eq!(agents.headline, "No agents — the session has not spawned any subagents yet");
+    }
+}
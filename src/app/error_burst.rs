@@ -0,0 +1,164 @@
+//! Error burst alerts: tool failures per session in a sliding time window.
+//!
+//! Cascading failures (a broken build every agent keeps hitting, a revoked
+//! credential) tend to precede an expensive run that achieves nothing. Each
+//! failed tool result is recorded against its session; a session with more
+//! failures than `BurstThreshold::failures` inside `BurstThreshold::window` is
+//! shown as an alert in the header until the window drains.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::model::SessionId;
+
+/// Alert when a session has more than `failures` tool failures within `window`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BurstThreshold {
+    pub failures: usize,
+    pub window: Duration,
+}
+
+impl Default for BurstThreshold {
+    fn default() -> Self {
+        Self { failures: 5, window: Duration::minutes(2) }
+    }
+}
+
+impl BurstThreshold {
+    /// Pure function: does `count` failures in one window raise the alert?
+    pub fn exceeded_by(&self, count: usize) -> bool {
+        count > self.failures
+    }
+
+    /// Window as `2m`, or `90s` when not whole minutes.
+    pub fn window_label(&self) -> String {
+        let secs = self.window.num_seconds();
+        if secs % 60 == 0 {
+            format!("{}m", secs / 60)
+        } else {
+            format!("{}s", secs)
+        }
+    }
+}
+
+/// Failure timestamps per session, oldest first, trimmed to the window.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorBursts {
+    failures: BTreeMap<SessionId, VecDeque<DateTime<Utc>>>,
+}
+
+impl ErrorBursts {
+    /// Record a failure at `at`, dropping the session's failures that are more
+    /// than `window` older than its newest one.
+    ///
+    /// # Returns
+    /// True when this failure takes the session over the threshold.
+    pub fn record(&mut self, session_id: &SessionId, at: DateTime<Utc>, threshold: &BurstThreshold) -> bool {
+        let times = self.failures.entry(session_id.clone()).or_default();
+        let was_bursting = threshold.exceeded_by(times.len());
+        let idx = times.partition_point(|t| *t <= at);
+        times.insert(idx, at);
+        if let Some(&newest) = times.back() {
+            trim_before(times, newest - threshold.window);
+        }
+        !was_bursting && threshold.exceeded_by(times.len())
+    }
+
+    /// Forget failures that have left the window as of `now`.
+    pub fn expire(&mut self, now: DateTime<Utc>, window: Duration) {
+        for times in self.failures.values_mut() {
+            trim_before(times, now - window);
+        }
+        self.failures.retain(|_, times| !times.is_empty());
+    }
+
+    /// Failures of one session currently in the window.
+    pub fn count(&self, session_id: &SessionId) -> usize {
+        self.failures.get(session_id).map_or(0, VecDeque::len)
+    }
+
+    /// Sessions over the threshold with their failure counts, most failures first.
+    pub fn bursting(&self, threshold: &BurstThreshold) -> Vec<(&SessionId, usize)> {
+        let mut sessions: Vec<_> = self
+            .failures
+            .iter()
+            .map(|(sid, times)| (sid, times.len()))
+            .filter(|(_, count)| threshold.exceeded_by(*count))
+            .collect();
+        sessions.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        sessions
+    }
+}
+
+fn trim_before(times: &mut VecDeque<DateTime<Utc>>, cutoff: DateTime<Utc>) {
+    while times.front().is_some_and(|t| *t < cutoff) {
+        times.pop_front();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn t0() -> DateTime<Utc> {
+        "2026-03-18T10:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn alert_raised_once_when_failures_exceed_threshold_in_window() {
+        let threshold = BurstThreshold::default();
+        let sid = SessionId::new("s1");
+        let mut bursts = ErrorBursts::default();
+
+        let raised: Vec<bool> = (0..7)
+            .map(|i| bursts.record(&sid, t0() + Duration::seconds(10 * i), &threshold))
+            .collect();
+        assert_eq!(raised, [false, false, false, false, false, true, false]);
+        assert_eq!(bursts.bursting(&threshold), vec![(&sid, 7)]);
+    }
+
+    #[test]
+    fn failures_spread_wider_than_the_window_never_alert() {
+        let threshold = BurstThreshold { failures: 2, window: Duration::minutes(1) };
+        let sid = SessionId::new("s1");
+        let mut bursts = ErrorBursts::default();
+
+        for i in 0..10 {
+            assert!(!bursts.record(&sid, t0() + Duration::seconds(40 * i), &threshold));
+        }
+        assert_eq!(bursts.count(&sid), 2);
+    }
+
+    #[test]
+    fn out_of_order_failures_stay_sorted_and_sessions_are_separate() {
+        let threshold = BurstThreshold { failures: 1, window: Duration::minutes(1) };
+        let (a, b) = (SessionId::new("a"), SessionId::new("b"));
+        let mut bursts = ErrorBursts::default();
+
+        bursts.record(&a, t0() + Duration::seconds(30), &threshold);
+        bursts.record(&b, t0(), &threshold);
+        assert!(bursts.record(&a, t0(), &threshold));
+        assert_eq!(bursts.bursting(&threshold), vec![(&a, 2)]);
+
+        // A late failure far outside the window pushes the old ones out
+        bursts.record(&a, t0() + Duration::minutes(5), &threshold);
+        assert_eq!(bursts.count(&a), 1);
+    }
+
+    #[test]
+    fn expire_drains_the_window_and_forgets_quiet_sessions() {
+        let threshold = BurstThreshold { failures: 1, window: Duration::minutes(2) };
+        let sid = SessionId::new("s1");
+        let mut bursts = ErrorBursts::default();
+        bursts.record(&sid, t0(), &threshold);
+        bursts.record(&sid, t0() + Duration::seconds(90), &threshold);
+
+        bursts.expire(t0() + Duration::minutes(3), threshold.window);
+        assert_eq!(bursts.count(&sid), 1);
+        assert!(bursts.bursting(&threshold).is_empty());
+
+        bursts.expire(t0() + Duration::minutes(4), threshold.window);
+        assert_eq!(bursts, ErrorBursts::default());
+    }
+}
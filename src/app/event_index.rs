@@ -0,0 +1,126 @@
+//! Searchable text of the live events, built once as they arrive.
+//!
+//! Matching the `/` filter used to format and lowercase every event of the
+//! 10k ring buffer on each keystroke. `update` now keeps the lowercase text
+//! the filter searches (header, detail, tool and agent) next to each event,
+//! in step with `DomainState::events`, so a keystroke costs a substring scan.
+//! Events put into the buffer some other way (tests, crash bundles) leave the
+//! index out of step; matching then falls back to formatting the event.
+
+use std::collections::VecDeque;
+
+use crate::app::AppState;
+use crate::model::TranscriptEvent;
+use crate::view::components::event_stream::{event_matches_search_transcript, format_transcript_event_lines};
+
+/// What the filter matches in one event, lowercased.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchText {
+    /// Header, detail, tool name and agent id, one per line
+    text: String,
+    tool: Option<String>,
+}
+
+impl SearchText {
+    pub fn of(event: &TranscriptEvent) -> Self {
+        let (_, header, detail, _, tool_name) = format_transcript_event_lines(&event.kind);
+        let tool = tool_name.map(|tool| tool.to_lowercase());
+        let text = [Some(header), detail, tool.clone(), event.agent_id.as_ref().map(|id| id.as_str().to_string())]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join("\n")
+            .to_lowercase();
+        Self { text, tool }
+    }
+
+    /// Same result as `event_matches_search_transcript` for a lowercase query.
+    pub fn matches(&self, event: &TranscriptEvent, query: &str) -> bool {
+        if let Some(origin_query) = query.strip_prefix("origin:") {
+            return event.origin.label().starts_with(origin_query);
+        }
+        if let Some(tool_query) = query.strip_prefix("tool:") {
+            return self.tool.as_deref().is_some_and(|tool| tool.contains(tool_query));
+        }
+        self.text.contains(query)
+    }
+}
+
+/// `SearchText` of each event in `DomainState::events`, same order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventIndex {
+    texts: VecDeque<SearchText>,
+}
+
+impl EventIndex {
+    /// Index the event just inserted into the buffer at `position`.
+    pub fn insert(&mut self, position: usize, event: &TranscriptEvent) {
+        if position <= self.texts.len() {
+            self.texts.insert(position, SearchText::of(event));
+        }
+    }
+
+    /// The buffer evicted its oldest event.
+    pub fn pop_front(&mut self) {
+        self.texts.pop_front();
+    }
+
+    /// Text of the event at `position` in a buffer of `len` events, if the
+    /// index is in step with it.
+    pub fn get(&self, len: usize, position: usize) -> Option<&SearchText> {
+        (self.texts.len() == len).then(|| self.texts.get(position)).flatten()
+    }
+}
+
+/// Pure function: whether the live event at `position` matches the lowercase `query`.
+pub fn matches(state: &AppState, position: usize, event: &TranscriptEvent, query: &str) -> bool {
+    match state.domain.event_index.get(state.domain.events.len(), position) {
+        Some(text) => text.matches(event, query),
+        None => event_matches_search_transcript(&event.kind, query, event.agent_id.as_ref(), event.origin),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{EventOrigin, TranscriptEventKind};
+    use chrono::Utc;
+
+    fn events() -> Vec<TranscriptEvent> {
+        let call = |tool: &str, input: &str| TranscriptEventKind::ToolUse { tool_name: tool.into(), input_summary: input.into() };
+        vec![
+            TranscriptEvent::new(Utc::now(), call("Read", "src/Main.rs")).with_agent("a01"),
+            TranscriptEvent::new(Utc::now(), call("Bash", "cargo test")).with_origin(EventOrigin::Hook),
+            TranscriptEvent::new(Utc::now(), TranscriptEventKind::AssistantMessage { content: "Done.\nAll green".into() }),
+            TranscriptEvent::new(Utc::now(), TranscriptEventKind::UserMessage),
+        ]
+    }
+
+    #[test]
+    fn indexed_text_matches_like_the_formatted_event() {
+        let queries = ["read", "main.rs", "a01", "tool:ba", "tool:main", "origin:hook", "all green", "user", "nothing"];
+        for event in events() {
+            let text = SearchText::of(&event);
+            for query in queries {
+                assert_eq!(
+                    text.matches(&event, query),
+                    event_matches_search_transcript(&event.kind, query, event.agent_id.as_ref(), event.origin),
+                    "{query} on {:?}",
+                    event.kind
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn index_out_of_step_with_the_buffer_is_not_used() {
+        let mut index = EventIndex::default();
+        let events = events();
+        index.insert(0, &events[0]);
+        index.insert(0, &events[1]);
+        assert!(index.get(2, 0).is_some_and(|text| text.matches(&events[1], "cargo")));
+        assert!(index.get(3, 0).is_none());
+        index.pop_front();
+        assert!(index.get(1, 0).is_some_and(|text| text.matches(&events[0], "main")));
+    }
+}
@@ -0,0 +1,142 @@
+//! Why tasks failed, with the events that show it.
+//!
+//! When a task turns `Failed` in the graph, the orchestrator's reason and
+//! retry count are kept together with the failed tool results its agent
+//! produced shortly before. `E` on a failed task in the Dashboard list then
+//! jumps to those events in the agent's activity, expanded, instead of leaving
+//! the user to scroll for them. The record outlives a retry that resets the
+//! task's status.
+
+use std::collections::BTreeSet;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::app::AppState;
+use crate::model::{AgentId, TaskGraph, TaskId, TaskStatus, TranscriptEvent, TranscriptEventKind};
+
+/// How far before the failure the agent's errors count as evidence.
+pub const EVIDENCE_WINDOW: Duration = Duration::minutes(10);
+
+/// A task's failure as the graph reported it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskFailure {
+    pub reason: String,
+    pub retry_count: u32,
+    pub agent_id: Option<AgentId>,
+    pub failed_at: DateTime<Utc>,
+    /// Timestamps of the agent's failed tool results in the window, oldest first
+    pub evidence: Vec<DateTime<Utc>>,
+}
+
+/// Pure function: tasks failed in `graph` that were not failed in `previous`.
+pub fn newly_failed(previous: Option<&TaskGraph>, graph: &TaskGraph) -> Vec<TaskId> {
+    let was_failed: BTreeSet<&TaskId> = previous
+        .into_iter()
+        .flat_map(TaskGraph::flat_tasks)
+        .filter(|t| matches!(t.status, TaskStatus::Failed { .. }))
+        .map(|t| &t.id)
+        .collect();
+    graph
+        .flat_tasks()
+        .filter(|t| matches!(t.status, TaskStatus::Failed { .. }) && !was_failed.contains(&t.id))
+        .map(|t| t.id.clone())
+        .collect()
+}
+
+/// Pure function: `agent`'s failed tool results in the window before `failed_at`.
+pub fn evidence<'a>(
+    events: impl IntoIterator<Item = &'a TranscriptEvent>,
+    agent: &AgentId,
+    failed_at: DateTime<Utc>,
+) -> Vec<DateTime<Utc>> {
+    let mut found: Vec<DateTime<Utc>> = events
+        .into_iter()
+        .filter(|e| e.agent_id.as_ref() == Some(agent))
+        .filter(|e| matches!(e.kind, TranscriptEventKind::ToolResult { is_error: true, .. }))
+        .filter(|e| e.timestamp <= failed_at && failed_at - e.timestamp <= EVIDENCE_WINDOW)
+        .map(|e| e.timestamp)
+        .collect();
+    found.sort();
+    found
+}
+
+/// Record the failures of `tasks` (just failed in the current graph) at `now`.
+pub fn record(state: &mut AppState, tasks: &[TaskId], now: DateTime<Utc>) {
+    let Some(graph) = state.domain.task_graph.as_ref() else {
+        return;
+    };
+    let failures: Vec<(TaskId, TaskFailure)> = graph
+        .flat_tasks()
+        .filter(|t| tasks.contains(&t.id))
+        .filter_map(|task| {
+            let TaskStatus::Failed { reason, retry_count } = &task.status else {
+                return None;
+            };
+            let evidence = task
+                .agent_id
+                .as_ref()
+                .map(|agent| evidence(&state.domain.events, agent, now))
+                .unwrap_or_default();
+            let failure = TaskFailure {
+                reason: reason.clone(),
+                retry_count: *retry_count,
+                agent_id: task.agent_id.clone(),
+                failed_at: now,
+                evidence,
+            };
+            Some((task.id.clone(), failure))
+        })
+        .collect();
+    state.domain.task_failures.extend(failures);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Task, ToolName, Wave};
+
+    fn graph(failed: &[&str]) -> TaskGraph {
+        let tasks = ["T1", "T2"]
+            .iter()
+            .map(|id| {
+                let status = if failed.contains(id) {
+                    TaskStatus::Failed { reason: "tests".into(), retry_count: 1 }
+                } else {
+                    TaskStatus::Running
+                };
+                Task::new(*id, String::new(), status)
+            })
+            .collect();
+        TaskGraph::new(vec![Wave { number: 1, tasks }])
+    }
+
+    #[test]
+    fn only_transitions_to_failed_count() {
+        assert_eq!(newly_failed(None, &graph(&["T1"])), vec![TaskId::new("T1")]);
+        assert_eq!(newly_failed(Some(&graph(&["T1"])), &graph(&["T1", "T2"])), vec![TaskId::new("T2")]);
+        assert!(newly_failed(Some(&graph(&["T1"])), &graph(&["T1"])).is_empty());
+    }
+
+    #[test]
+    fn evidence_is_the_agents_recent_failed_results() {
+        let now = Utc::now();
+        let result = |agent: &str, minutes: i64, is_error: bool| {
+            let kind = TranscriptEventKind::ToolResult {
+                tool_name: ToolName::new("Bash"),
+                result_summary: String::new(),
+                duration_ms: None,
+                is_error,
+            };
+            TranscriptEvent::new(now - Duration::minutes(minutes), kind).with_agent(agent)
+        };
+        let events = [
+            result("a01", 30, true),
+            result("a01", 5, true),
+            result("a01", 2, false),
+            result("a02", 1, true),
+            result("a01", 1, true),
+        ];
+        let found = evidence(&events, &AgentId::new("a01"), now);
+        assert_eq!(found, vec![now - Duration::minutes(5), now - Duration::minutes(1)]);
+    }
+}
@@ -0,0 +1,129 @@
+//! Search filters per view.
+//!
+//! `/` edits the filter of the view in front; the Dashboard keeps one per
+//! event channel. Enter applies it and hands the keys back to navigation, Esc
+//! drops it, and `x` clears an applied one. Switching views (or channels)
+//! stashes the filter and brings back the one last applied there, so flipping
+//! between Dashboard and AgentDetail keeps both contexts. The applied filter
+//! shows as a chip in the filtered panels' titles.
+
+use crate::app::state::{UiState, ViewState};
+
+/// Where a filter applies: a view, plus the event channel on the Dashboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterScope {
+    pub view: ViewState,
+    /// Name of the selected event channel (Dashboard only)
+    pub channel: Option<String>,
+}
+
+impl FilterScope {
+    /// Scope of the view in front.
+    pub fn of(ui: &UiState) -> Self {
+        let channel = match ui.view {
+            ViewState::Dashboard => ui.event_channels.current().map(|channel| channel.name.clone()),
+            _ => None,
+        };
+        Self { view: ui.view.clone(), channel }
+    }
+}
+
+/// Applied filters of the scopes not in front, and whether the one in front
+/// (`UiState::filter`) is applied or still being typed.
+#[derive(Debug, Clone)]
+pub struct ViewFilters {
+    /// Enter confirmed `UiState::filter`; keys go to navigation again
+    pub applied: bool,
+    /// Scope `UiState::filter` belongs to
+    scope: FilterScope,
+    saved: Vec<(FilterScope, String)>,
+}
+
+impl Default for ViewFilters {
+    fn default() -> Self {
+        Self { applied: false, scope: FilterScope { view: ViewState::Dashboard, channel: None }, saved: Vec::new() }
+    }
+}
+
+impl ViewFilters {
+    /// Filter last applied in `scope`, if it is not the one in front.
+    pub fn saved(&self, scope: &FilterScope) -> Option<&str> {
+        self.saved.iter().find(|(s, _)| s == scope).map(|(_, filter)| filter.as_str())
+    }
+}
+
+/// After a view or channel switch: stash the filter of the scope left behind
+/// and bring back the one saved for the scope now in front.
+pub fn sync_scope(ui: &mut UiState) {
+    let scope = FilterScope::of(ui);
+    if ui.filters.scope == scope {
+        return;
+    }
+    let left = std::mem::replace(&mut ui.filters.scope, scope.clone());
+    ui.filters.saved.retain(|(s, _)| *s != left);
+    if let Some(filter) = ui.filter.take().filter(|filter| !filter.is_empty()) {
+        ui.filters.saved.push((left, filter));
+    }
+    ui.filter = ui.filters.saved(&scope).map(str::to_string);
+    ui.filters.saved.retain(|(s, _)| *s != scope);
+    ui.filters.applied = ui.filter.is_some();
+}
+
+/// The filter bar is open and takes the keys.
+pub fn editing(ui: &UiState) -> bool {
+    ui.filter.is_some() && !ui.filters.applied
+}
+
+/// `/`: edit the filter in front, starting from the applied one.
+pub fn start(ui: &mut UiState) {
+    ui.filter = Some(ui.filter.take().unwrap_or_default());
+    ui.filters.applied = false;
+}
+
+/// Enter while typing: apply the filter (an empty one is dropped).
+pub fn apply(ui: &mut UiState) {
+    if ui.filter.as_deref() == Some("") {
+        ui.filter = None;
+    }
+    ui.filters.applied = ui.filter.is_some();
+}
+
+/// Esc while typing, or `x`: drop the filter in front.
+pub fn clear(ui: &mut UiState) {
+    ui.filter = None;
+    ui.filters.applied = false;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppState;
+
+    #[test]
+    fn each_view_keeps_its_last_applied_filter() {
+        let mut ui = AppState::new().ui;
+        start(&mut ui);
+        ui.filter = Some("auth".into());
+        apply(&mut ui);
+
+        ui.view = ViewState::AgentDetail;
+        sync_scope(&mut ui);
+        assert_eq!(ui.filter, None);
+        ui.filter = Some("Bash".into());
+        apply(&mut ui);
+
+        ui.view = ViewState::Dashboard;
+        sync_scope(&mut ui);
+        assert_eq!(ui.filter.as_deref(), Some("auth"));
+        assert!(ui.filters.applied, "a restored filter does not take keys");
+        let detail = FilterScope { view: ViewState::AgentDetail, channel: None };
+        assert_eq!(ui.filters.saved(&detail), Some("Bash"));
+
+        clear(&mut ui);
+        ui.view = ViewState::AgentDetail;
+        sync_scope(&mut ui);
+        ui.view = ViewState::Dashboard;
+        sync_scope(&mut ui);
+        assert_eq!(ui.filter, None);
+    }
+}
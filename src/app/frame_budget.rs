@@ -0,0 +1,139 @@
+//! Frame time budget: how long update and render take per frame, and which
+//! panels the render time went to.
+//!
+//! The main loop records each drawn frame's update work (everything since the
+//! previous draw except waiting for input) and render time; the view times its
+//! panels through `timed`. Once `SLOW_STREAK` frames in a row run over
+//! `FRAME_BUDGET`, a warning names the panels that cost the most over the
+//! streak and suggests narrowing the stream, at most once per `WARN_INTERVAL`.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use crate::app::AppState;
+
+/// Update + render time a frame should stay within.
+pub const FRAME_BUDGET: Duration = Duration::from_millis(30);
+
+/// Frames over budget in a row before warning.
+pub const SLOW_STREAK: usize = 10;
+
+/// Least time between two warnings.
+pub const WARN_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Panels named in a warning.
+const TOP_PANELS: usize = 3;
+
+/// Timings of the frames over budget since the last frame within it.
+#[derive(Debug, Clone, Default)]
+pub struct FrameBudget {
+    /// Render time per panel of the frame being drawn, written by the view
+    panels: RefCell<Vec<(&'static str, Duration)>>,
+    streak: usize,
+    update: Duration,
+    render: Duration,
+    panel_totals: BTreeMap<&'static str, Duration>,
+    last_warned: Option<Instant>,
+}
+
+impl FrameBudget {
+    /// Record a drawn frame, draining the panel times the view wrote.
+    ///
+    /// # Returns
+    /// The warning, when this frame completes a slow streak.
+    pub fn record(&mut self, update: Duration, render: Duration, now: Instant) -> Option<String> {
+        let panels = std::mem::take(self.panels.get_mut());
+        if update + render <= FRAME_BUDGET {
+            self.streak = 0;
+            self.update = Duration::ZERO;
+            self.render = Duration::ZERO;
+            self.panel_totals.clear();
+            return None;
+        }
+        self.streak += 1;
+        self.update += update;
+        self.render += render;
+        for (panel, took) in panels {
+            *self.panel_totals.entry(panel).or_default() += took;
+        }
+        let quiet = self.last_warned.is_none_or(|at| now.duration_since(at) >= WARN_INTERVAL);
+        if self.streak < SLOW_STREAK || !quiet {
+            return None;
+        }
+        self.last_warned = Some(now);
+        Some(self.warning())
+    }
+
+    /// Pure function: the warning text for the current streak.
+    fn warning(&self) -> String {
+        let frames = self.streak.max(1) as u32;
+        let mut panels: Vec<(&str, Duration)> = self.panel_totals.iter().map(|(p, d)| (*p, *d)).collect();
+        panels.sort_by_key(|(_, total)| std::cmp::Reverse(*total));
+        let costly: Vec<String> = panels
+            .iter()
+            .take(TOP_PANELS)
+            .map(|(panel, total)| format!("{} {}ms", panel, (*total / frames).as_millis()))
+            .collect();
+        let mut text = format!(
+            "UI under load: {} slow frames (update {}ms, render {}ms avg)",
+            self.streak,
+            (self.update / frames).as_millis(),
+            (self.render / frames).as_millis()
+        );
+        if !costly.is_empty() {
+            text.push_str(&format!("; {}", costly.join(", ")));
+        }
+        text.push_str(" — consider a filter or sampling");
+        text
+    }
+}
+
+/// Run `render` for `panel`, adding its time to the frame's panel times.
+pub fn timed<R>(state: &AppState, panel: &'static str, render: impl FnOnce() -> R) -> R {
+    let started = Instant::now();
+    let result = render();
+    state.meta.frame_budget.panels.borrow_mut().push((panel, started.elapsed()));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slow_frame(budget: &mut FrameBudget, now: Instant) -> Option<String> {
+        budget.panels.borrow_mut().extend([("event stream", Duration::from_millis(25)), ("task list", Duration::from_millis(5))]);
+        budget.record(Duration::from_millis(10), Duration::from_millis(32), now)
+    }
+
+    #[test]
+    fn warns_once_a_streak_of_slow_frames_completes() {
+        let mut budget = FrameBudget::default();
+        let now = Instant::now();
+        for _ in 0..SLOW_STREAK - 1 {
+            assert_eq!(slow_frame(&mut budget, now), None);
+        }
+        let warning = slow_frame(&mut budget, now).unwrap();
+        assert_eq!(
+            warning,
+            "UI under load: 10 slow frames (update 10ms, render 32ms avg); event stream 25ms, task list 5ms \
+             — consider a filter or sampling"
+        );
+
+        // Not again until the interval has passed
+        assert_eq!(slow_frame(&mut budget, now), None);
+        assert!(slow_frame(&mut budget, now + WARN_INTERVAL).is_some());
+    }
+
+    #[test]
+    fn a_frame_within_budget_breaks_the_streak() {
+        let mut budget = FrameBudget::default();
+        let now = Instant::now();
+        for _ in 0..SLOW_STREAK - 1 {
+            slow_frame(&mut budget, now);
+        }
+        assert_eq!(budget.record(Duration::from_millis(2), Duration::from_millis(8), now), None);
+        assert_eq!(slow_frame(&mut budget, now), None);
+        assert!(budget.panels.borrow().is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+//! Fuzzy subsequence matcher for the quick-switch palette.
+//!
+//! # Functional Core
+//! Pure functions only. A pattern matches when all of its characters appear in
+//! the candidate in order (case-insensitive). Scores favour consecutive runs,
+//! word starts and early matches, so `"ses12"` ranks `session-12ab` above
+//! `some-session-1-2`.
+
+/// Points per matched character.
+const MATCH_SCORE: i64 = 16;
+/// Bonus when a match directly follows the previous one.
+const CONSECUTIVE_BONUS: i64 = 24;
+/// Bonus when a match starts a word (after a separator, or a lower→upper case change).
+const WORD_START_BONUS: i64 = 20;
+/// Penalty per skipped candidate character between matches.
+const GAP_PENALTY: i64 = 2;
+/// Cap on the penalty for any single gap, so long candidates are not over-punished.
+const MAX_GAP_PENALTY: i64 = 12;
+
+/// Result of a successful fuzzy match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher is better
+    pub score: i64,
+    /// Char indices in the candidate that matched the pattern (for highlighting)
+    pub positions: Vec<usize>,
+}
+
+/// Match `pattern` against `candidate`.
+///
+/// # Arguments
+/// * `pattern` - Query typed by the user; whitespace is ignored
+/// * `candidate` - Text to search in
+///
+/// # Returns
+/// `Some(FuzzyMatch)` if every pattern character occurs in order, `None` otherwise.
+/// An empty pattern matches everything with score 0.
+pub fn fuzzy_match(pattern: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let pattern: Vec<char> = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if pattern.is_empty() {
+        return Some(FuzzyMatch { score: 0, positions: Vec::new() });
+    }
+
+    let chars: Vec<char> = candidate.chars().collect();
+    let positions = best_positions(&pattern, &chars)?;
+    let score = score_positions(&chars, &positions);
+    Some(FuzzyMatch { score, positions })
+}
+
+/// Pick match positions: for each possible start of the first pattern char,
+/// greedily match the rest and keep the highest scoring alignment.
+fn best_positions(pattern: &[char], chars: &[char]) -> Option<Vec<usize>> {
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+
+    let mut best: Option<(i64, Vec<usize>)> = None;
+    for start in (0..lower.len()).filter(|&i| lower[i] == pattern[0]) {
+        let mut positions = vec![start];
+        let mut next = start + 1;
+        for &pc in &pattern[1..] {
+            match lower[next..].iter().position(|&c| c == pc) {
+                Some(offset) => {
+                    positions.push(next + offset);
+                    next += offset + 1;
+                }
+                None => break,
+            }
+        }
+        if positions.len() < pattern.len() {
+            // Later starts can only leave fewer characters to match
+            break;
+        }
+        let score = score_positions(chars, &positions);
+        if best.as_ref().is_none_or(|(s, _)| score > *s) {
+            best = Some((score, positions));
+        }
+    }
+    best.map(|(_, positions)| positions)
+}
+
+fn score_positions(chars: &[char], positions: &[usize]) -> i64 {
+    let mut score = 0;
+    let mut prev: Option<usize> = None;
+    for &pos in positions {
+        score += MATCH_SCORE;
+        if is_word_start(chars, pos) {
+            score += WORD_START_BONUS;
+        }
+        match prev {
+            Some(p) if pos == p + 1 => score += CONSECUTIVE_BONUS,
+            Some(p) => score -= ((pos - p - 1) as i64 * GAP_PENALTY).min(MAX_GAP_PENALTY),
+            None => score -= (pos as i64 * GAP_PENALTY).min(MAX_GAP_PENALTY),
+        }
+        prev = Some(pos);
+    }
+    score
+}
+
+fn is_word_start(chars: &[char], pos: usize) -> bool {
+    match pos.checked_sub(1).map(|p| chars[p]) {
+        None => true,
+        Some(before) => {
+            !before.is_alphanumeric() || (before.is_lowercase() && chars[pos].is_uppercase())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_pattern_matches_everything() {
+        let m = fuzzy_match("", "anything").unwrap();
+        assert_eq!(m.score, 0);
+        assert!(m.positions.is_empty());
+    }
+
+    #[test]
+    fn subsequence_required_in_order() {
+        assert!(fuzzy_match("abc", "a-b-c").is_some());
+        assert!(fuzzy_match("acb", "a-b-c").is_none());
+        assert!(fuzzy_match("x", "abc").is_none());
+    }
+
+    #[test]
+    fn matching_is_case_insensitive() {
+        let m = fuzzy_match("SeS", "session").unwrap();
+        assert_eq!(m.positions, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn whitespace_in_pattern_is_ignored() {
+        assert!(fuzzy_match("t 1", "task T1").is_some());
+    }
+
+    #[test]
+    fn prefers_consecutive_and_word_start_matches() {
+        let tight = fuzzy_match("ses12", "session-12ab").unwrap();
+        let loose = fuzzy_match("ses12", "some-session-1-2").unwrap();
+        assert!(tight.score > loose.score, "{} vs {}", tight.score, loose.score);
+    }
+
+    #[test]
+    fn picks_best_alignment_not_first() {
+        // Greedy-from-first-'b' would match "b....ar"; the word "bar" scores better
+        let m = fuzzy_match("bar", "b-x-a-foo bar").unwrap();
+        assert_eq!(m.positions, vec![10, 11, 12]);
+    }
+
+    #[test]
+    fn positions_are_char_indices_for_multibyte() {
+        let m = fuzzy_match("ab", "ü-a-b").unwrap();
+        assert_eq!(m.positions, vec![2, 4]);
+    }
+}
@@ -0,0 +1,208 @@
+//! The task graph as Mermaid or Graphviz text, for `:export mermaid|dot`.
+//!
+//! Nodes carry the task id, a shortened description and the status, filled
+//! with the status colors the dashboard uses. Edges follow the task file's
+//! `depends_on` lists; a graph without any falls back to wave order, drawn
+//! as an edge from each wave's box to the next.
+
+use ratatui::style::Color;
+
+use crate::model::{Task, TaskGraph, TaskId, TaskStatus, Theme};
+
+/// Longest description kept in a node label, in chars.
+const LABEL_MAX_CHARS: usize = 48;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    Mermaid,
+    /// Graphviz
+    Dot,
+}
+
+impl GraphFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Mermaid => "mmd",
+            Self::Dot => "dot",
+        }
+    }
+
+    /// Pure function: `graph` in this format.
+    pub fn render(self, graph: &TaskGraph) -> String {
+        match self {
+            Self::Mermaid => to_mermaid(graph),
+            Self::Dot => to_dot(graph),
+        }
+    }
+}
+
+const STATUSES: [TaskStatus; 5] = [
+    TaskStatus::Pending,
+    TaskStatus::Running,
+    TaskStatus::Implemented,
+    TaskStatus::Completed,
+    TaskStatus::Failed { reason: String::new(), retry_count: 0 },
+];
+
+/// Pure function: Mermaid flowchart of `graph`, one subgraph per wave.
+pub fn to_mermaid(graph: &TaskGraph) -> String {
+    let mut out = vec!["flowchart TD".to_string()];
+    for status in &STATUSES {
+        out.push(format!("    classDef {} fill:{},color:#000", status.label(), hex(Theme::task_status_color(status))));
+    }
+    for wave in &graph.waves {
+        out.push(format!("    subgraph wave{}[\"Wave {}\"]", wave.number, wave.number));
+        for task in &wave.tasks {
+            let label = label(task).replace('"', "#quot;");
+            out.push(format!("        {}[\"{}\"]:::{}", node_id(&task.id), label, task.status.label()));
+        }
+        out.push("    end".to_string());
+    }
+    match dependency_edges(graph) {
+        Some(edges) => {
+            out.extend(edges.into_iter().map(|(from, to)| format!("    {} --> {}", node_id(from), node_id(to))));
+        }
+        None => out.extend(graph.waves.windows(2).map(|w| format!("    wave{} --> wave{}", w[0].number, w[1].number))),
+    }
+    out.push(String::new());
+    out.join("\n")
+}
+
+/// Pure function: Graphviz digraph of `graph`, one cluster per wave.
+pub fn to_dot(graph: &TaskGraph) -> String {
+    let mut out = vec![
+        "digraph tasks {".to_string(),
+        "    compound=true;".to_string(),
+        "    rankdir=TB;".to_string(),
+        "    node [shape=box, style=\"rounded,filled\"];".to_string(),
+    ];
+    for wave in &graph.waves {
+        out.push(format!("    subgraph cluster_wave{} {{", wave.number));
+        out.push(format!("        label=\"Wave {}\";", wave.number));
+        for task in &wave.tasks {
+            out.push(format!(
+                "        {} [label=\"{}\", fillcolor=\"{}\"];",
+                node_id(&task.id),
+                dot_escape(&label(task)),
+                hex(Theme::task_status_color(&task.status))
+            ));
+        }
+        out.push("    }".to_string());
+    }
+    match dependency_edges(graph) {
+        Some(edges) => {
+            out.extend(edges.into_iter().map(|(from, to)| format!("    {} -> {};", node_id(from), node_id(to))));
+        }
+        None => out.extend(graph.waves.windows(2).filter_map(|w| {
+            let (from, to) = (w[0].tasks.first()?, w[1].tasks.first()?);
+            Some(format!(
+                "    {} -> {} [ltail=cluster_wave{}, lhead=cluster_wave{}];",
+                node_id(&from.id),
+                node_id(&to.id),
+                w[0].number,
+                w[1].number
+            ))
+        })),
+    }
+    out.push("}".to_string());
+    out.push(String::new());
+    out.join("\n")
+}
+
+/// Dependency edges (dependency first) between tasks in the graph, or `None`
+/// when the task file declares no dependencies at all.
+fn dependency_edges(graph: &TaskGraph) -> Option<Vec<(&TaskId, &TaskId)>> {
+    if graph.dependencies.is_empty() {
+        return None;
+    }
+    let known = |id: &TaskId| graph.flat_tasks().any(|t| &t.id == id);
+    Some(
+        graph
+            .dependencies
+            .iter()
+            .filter(|(task, _)| known(task))
+            .flat_map(|(task, deps)| deps.iter().filter(|d| known(d)).map(move |dep| (dep, task)))
+            .collect(),
+    )
+}
+
+/// Node label: "T3: Wire main loop (running)", with the retry count of failed tasks.
+fn label(task: &Task) -> String {
+    let collapsed = task.description.split_whitespace().collect::<Vec<_>>().join(" ");
+    let description = if collapsed.chars().count() > LABEL_MAX_CHARS {
+        let kept: String = collapsed.chars().take(LABEL_MAX_CHARS - 1).collect();
+        format!("{}…", kept.trim_end())
+    } else {
+        collapsed
+    };
+    let status = match &task.status {
+        TaskStatus::Failed { retry_count, .. } if *retry_count > 0 => format!("failed, retry {}", retry_count),
+        status => status.label().to_string(),
+    };
+    if description.is_empty() {
+        format!("{} ({})", task.id, status)
+    } else {
+        format!("{}: {} ({})", task.id, description, status)
+    }
+}
+
+/// Task ids as node ids: anything but ASCII letters, digits and `_` becomes `_`.
+fn node_id(id: &TaskId) -> String {
+    id.as_str()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn hex(color: Color) -> String {
+    match color {
+        Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        _ => "#ffffff".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Wave;
+    use std::collections::BTreeMap;
+
+    fn graph() -> TaskGraph {
+        TaskGraph::new(vec![
+            Wave::new(1, vec![
+                Task::new("T1", "Create \"scaffold\"".into(), TaskStatus::Completed),
+                Task::new("T2", "Models".into(), TaskStatus::Failed { reason: "tests".into(), retry_count: 2 }),
+            ]),
+            Wave::new(2, vec![Task::new("T3-a", "Wire main loop".into(), TaskStatus::Running)]),
+        ])
+    }
+
+    #[test]
+    fn mermaid_without_dependencies_links_waves() {
+        let text = to_mermaid(&graph());
+        assert!(text.starts_with("flowchart TD\n"));
+        assert!(text.contains("classDef failed fill:#e65050"));
+        assert!(text.contains("    subgraph wave1[\"Wave 1\"]\n        T1[\"T1: Create #quot;scaffold#quot; (completed)\"]:::completed"));
+        assert!(text.contains("T2[\"T2: Models (failed, retry 2)\"]:::failed"));
+        assert!(text.contains("T3_a[\"T3-a: Wire main loop (running)\"]:::running"));
+        assert!(text.contains("    wave1 --> wave2\n"));
+    }
+
+    #[test]
+    fn dot_draws_declared_dependencies() {
+        let deps = BTreeMap::from([
+            (TaskId::new("T3-a"), vec![TaskId::new("T1"), TaskId::new("T2")]),
+            (TaskId::new("T2"), vec![TaskId::new("gone")]),
+        ]);
+        let text = to_dot(&graph().with_dependencies(deps));
+        assert!(text.starts_with("digraph tasks {\n"));
+        assert!(text.contains("T1 [label=\"T1: Create \\\"scaffold\\\" (completed)\", fillcolor=\"#50d278\"];"));
+        assert!(text.contains("    T1 -> T3_a;\n    T2 -> T3_a;\n"));
+        assert!(!text.contains("gone") && !text.contains("lhead"));
+        assert!(text.ends_with("}\n"));
+    }
+}
@@ -0,0 +1,118 @@
+//! In-flight background I/O.
+//!
+//! Loads, the spend auto-save, overflow log appends and alert deliveries run
+//! on their own threads.
+//! Quitting under a write would leave its file truncated (or drop the
+//! alert), so the main loop counts them here and, on quit, keeps draining
+//! with a "finishing writes…" screen until the writes are done or
+//! `QUIT_GRACE` runs out. Loads are only reads and are dropped.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Longest quit waits for writes before giving up on them.
+pub const QUIT_GRACE: Duration = Duration::from_secs(3);
+
+/// What a background thread is doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IoKind {
+    /// Reading archives, pages, previews, diffs or outputs
+    Load,
+    /// Auto-saving the day's spend
+    Save,
+    /// Appending sampled sessions' events to the overflow log
+    Append,
+    /// Posting cost alerts
+    Deliver,
+}
+
+impl IoKind {
+    /// Must quit wait for it?
+    pub fn is_write(self) -> bool {
+        !matches!(self, Self::Load)
+    }
+
+    fn noun(self, count: usize) -> &'static str {
+        match (self, count) {
+            (Self::Load, 1) => "load",
+            (Self::Load, _) => "loads",
+            (Self::Save, 1) => "save",
+            (Self::Save, _) => "saves",
+            (Self::Append, 1) => "overflow append",
+            (Self::Append, _) => "overflow appends",
+            (Self::Deliver, 1) => "alert delivery",
+            (Self::Deliver, _) => "alert deliveries",
+        }
+    }
+}
+
+/// Background operations under way, by kind.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InFlight {
+    counts: BTreeMap<IoKind, usize>,
+}
+
+impl InFlight {
+    pub fn start(&mut self, kind: IoKind) {
+        *self.counts.entry(kind).or_default() += 1;
+    }
+
+    pub fn finish(&mut self, kind: IoKind) {
+        if let Some(count) = self.counts.get_mut(&kind) {
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    pub fn count(&self, kind: IoKind) -> usize {
+        self.counts.get(&kind).copied().unwrap_or(0)
+    }
+
+    /// Writes under way.
+    pub fn writes(&self) -> usize {
+        self.counts.iter().filter(|(kind, _)| kind.is_write()).map(|(_, count)| count).sum()
+    }
+
+    /// Pure function: may quit go ahead after waiting `waited` for writes?
+    pub fn may_quit(&self, waited: Duration) -> bool {
+        self.writes() == 0 || waited >= QUIT_GRACE
+    }
+
+    /// Pure function: the writes under way, e.g. "1 save, 2 alert deliveries".
+    pub fn writes_label(&self) -> String {
+        self.counts
+            .iter()
+            .filter(|(kind, count)| kind.is_write() && **count > 0)
+            .map(|(kind, count)| format!("{} {}", count, kind.noun(*count)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quit_waits_for_writes_only_until_the_grace_runs_out() {
+        let mut in_flight = InFlight::default();
+        in_flight.start(IoKind::Load);
+        assert!(in_flight.may_quit(Duration::ZERO), "loads don't hold quit");
+
+        in_flight.start(IoKind::Save);
+        in_flight.start(IoKind::Append);
+        in_flight.start(IoKind::Deliver);
+        in_flight.start(IoKind::Deliver);
+        assert_eq!(in_flight.writes(), 4);
+        assert_eq!(in_flight.writes_label(), "1 save, 1 overflow append, 2 alert deliveries");
+        assert!(!in_flight.may_quit(Duration::from_secs(1)));
+        assert!(in_flight.may_quit(QUIT_GRACE));
+
+        in_flight.finish(IoKind::Save);
+        in_flight.finish(IoKind::Save);
+        in_flight.finish(IoKind::Append);
+        in_flight.finish(IoKind::Deliver);
+        in_flight.finish(IoKind::Deliver);
+        assert_eq!((in_flight.writes(), in_flight.count(IoKind::Load)), (0, 1));
+        assert!(in_flight.may_quit(Duration::ZERO));
+    }
+}
@@ -0,0 +1,260 @@
+//! JSON tree inspector.
+//!
+//! `J` on a step of the agent activity tree opens its events as JSON in a
+//! foldable tree: Enter folds or unfolds an object or array, `/` searches
+//! keys (unfolding the way to a match, `n` for the next), `y` copies the value
+//! under the cursor and `Y` its path (`.kind.tool_name`, `[0].input`).
+//! Events are inspected as loom-tui holds them, every parsed field included.
+
+use std::collections::HashSet;
+
+use serde_json::Value;
+
+use crate::app::AppState;
+use crate::view::components::step_tree::agent_steps;
+
+/// One visible node of the tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonRow {
+    /// JSON Pointer to the node (`/0/kind`); folding and lookups key on it
+    pub pointer: String,
+    /// jq-style path shown and copied (`[0].kind`)
+    pub path: String,
+    pub depth: usize,
+    /// Object key or array index; `None` for the root
+    pub label: Option<String>,
+    pub kind: RowKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RowKind {
+    /// Object or array with its entry count
+    Container { array: bool, len: usize, folded: bool },
+    /// Scalar as JSON text
+    Scalar(String),
+}
+
+/// Foldable view over one JSON value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonInspector {
+    pub title: String,
+    root: Value,
+    folded: HashSet<String>,
+    pub cursor: usize,
+    /// Key search being typed (`/`)
+    pub search: Option<String>,
+    /// Last key search run, repeated by `n`
+    pub query: Option<String>,
+}
+
+impl JsonInspector {
+    pub fn new(title: String, root: Value) -> Self {
+        Self { title, root, folded: HashSet::new(), cursor: 0, search: None, query: None }
+    }
+
+    /// Visible rows, depth first; children of folded nodes are left out.
+    pub fn rows(&self) -> Vec<JsonRow> {
+        let mut rows = Vec::new();
+        self.walk(&self.root, String::new(), String::new(), None, 0, &mut rows, false);
+        rows
+    }
+
+    /// Every node with folding ignored, for key search.
+    fn all_rows(&self) -> Vec<JsonRow> {
+        let mut rows = Vec::new();
+        self.walk(&self.root, String::new(), String::new(), None, 0, &mut rows, true);
+        rows
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn walk(
+        &self,
+        value: &Value,
+        pointer: String,
+        path: String,
+        label: Option<String>,
+        depth: usize,
+        rows: &mut Vec<JsonRow>,
+        unfold_all: bool,
+    ) {
+        let children: Vec<(String, &Value)> = match value {
+            Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), v)).collect(),
+            Value::Array(items) => items.iter().enumerate().map(|(i, v)| (i.to_string(), v)).collect(),
+            scalar => {
+                rows.push(JsonRow { pointer, path, depth, label, kind: RowKind::Scalar(scalar.to_string()) });
+                return;
+            }
+        };
+        let folded = self.folded.contains(&pointer);
+        let array = value.is_array();
+        rows.push(JsonRow {
+            pointer: pointer.clone(),
+            path: path.clone(),
+            depth,
+            label,
+            kind: RowKind::Container { array, len: children.len(), folded },
+        });
+        if folded && !unfold_all {
+            return;
+        }
+        for (key, child) in children {
+            let child_pointer = format!("{}/{}", pointer, key.replace('~', "~0").replace('/', "~1"));
+            let child_path = if array { format!("{}[{}]", path, key) } else { format!("{}{}", path, key_path(&key)) };
+            self.walk(child, child_pointer, child_path, Some(key), depth + 1, rows, unfold_all);
+        }
+    }
+
+    /// Row under the cursor (clamped).
+    pub fn selected(&self) -> Option<JsonRow> {
+        let rows = self.rows();
+        let idx = self.cursor.min(rows.len().checked_sub(1)?);
+        rows.into_iter().nth(idx)
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        let last = self.rows().len().saturating_sub(1);
+        self.cursor = self.cursor.saturating_add_signed(delta).min(last);
+    }
+
+    pub fn jump_to_end(&mut self) {
+        self.cursor = self.rows().len().saturating_sub(1);
+    }
+
+    /// Enter: fold or unfold the container under the cursor.
+    pub fn toggle(&mut self) {
+        if let Some(row) = self.selected().filter(|row| matches!(row.kind, RowKind::Container { .. })) {
+            if !self.folded.remove(&row.pointer) {
+                self.folded.insert(row.pointer);
+            }
+        }
+    }
+
+    /// `h`: fold the open container under the cursor, else step to its parent.
+    pub fn fold_or_parent(&mut self) {
+        let Some(row) = self.selected() else { return };
+        if matches!(row.kind, RowKind::Container { folded: false, len, .. } if len > 0) {
+            self.folded.insert(row.pointer);
+            return;
+        }
+        let parent = row.pointer.rsplit_once('/').map(|(parent, _)| parent.to_string());
+        if let Some(idx) = parent.and_then(|parent| self.rows().iter().position(|r| r.pointer == parent)) {
+            self.cursor = idx;
+        }
+    }
+
+    /// `l`: unfold the container under the cursor.
+    pub fn unfold(&mut self) {
+        if let Some(row) = self.selected() {
+            self.folded.remove(&row.pointer);
+        }
+    }
+
+    /// Move to the next node after the cursor whose key contains `query`
+    /// (case-insensitive, wrapping), unfolding its ancestors.
+    ///
+    /// # Returns
+    /// Whether a match was found.
+    pub fn find_next(&mut self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        let all = self.all_rows();
+        let from = self.selected().and_then(|row| all.iter().position(|r| r.pointer == row.pointer)).unwrap_or(0);
+        let hit = (1..=all.len())
+            .map(|step| &all[(from + step) % all.len()])
+            .find(|row| row.label.as_ref().is_some_and(|label| label.to_lowercase().contains(&query)));
+        let Some(pointer) = hit.map(|row| row.pointer.clone()) else {
+            return false;
+        };
+        self.folded.retain(|folded| !pointer.starts_with(&format!("{}/", folded)));
+        self.cursor = self.rows().iter().position(|row| row.pointer == pointer).unwrap_or(self.cursor);
+        true
+    }
+
+    /// Pretty JSON of the node under the cursor.
+    pub fn selected_value(&self) -> Option<String> {
+        let row = self.selected()?;
+        self.root.pointer(&row.pointer).and_then(|value| serde_json::to_string_pretty(value).ok())
+    }
+}
+
+/// Pure function: `.key`, or `["key"]` when the key is not a plain identifier.
+fn key_path(key: &str) -> String {
+    let plain = key.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_alphanumeric() || c == '_');
+    if plain {
+        format!(".{}", key)
+    } else {
+        format!("[{}]", Value::String(key.to_string()))
+    }
+}
+
+/// Inspector over the events of the step under the cursor in the agent
+/// activity tree (AgentDetail, or the split view's right half).
+pub fn selected_step_inspector(state: &AppState) -> Option<JsonInspector> {
+    let agent = state.followed_agent()?;
+    let steps = agent_steps(state, agent.id.as_str());
+    let idx = state.ui.scroll_offsets.agent_events.min(steps.len().checked_sub(1)?);
+    let step = steps.iter().rev().nth(idx)?;
+    let events: Vec<Value> = std::iter::once(step.head)
+        .chain(step.tools.iter().copied())
+        .filter_map(|event| serde_json::to_value(event).ok())
+        .collect();
+    let title = format!("{} · step of {} event(s)", agent.id, events.len());
+    let root = match <[Value; 1]>::try_from(events) {
+        Ok([event]) => event,
+        Err(events) => Value::Array(events),
+    };
+    Some(JsonInspector::new(title, root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn inspector() -> JsonInspector {
+        JsonInspector::new(
+            "test".into(),
+            json!({
+                "kind": "tool_use",
+                "input": { "command": "cargo test", "dry-run": false },
+                "tags": ["a", "b"],
+            }),
+        )
+    }
+
+    #[test]
+    fn rows_fold_and_carry_copyable_paths() {
+        let mut tree = inspector();
+        let paths: Vec<String> = tree.rows().iter().map(|r| r.path.clone()).collect();
+        assert_eq!(paths, ["", ".input", ".input.command", ".input[\"dry-run\"]", ".kind", ".tags", ".tags[0]", ".tags[1]"]);
+
+        tree.cursor = 1;
+        tree.toggle();
+        assert_eq!(tree.rows().len(), 6);
+        assert_eq!(tree.rows()[1].kind, RowKind::Container { array: false, len: 2, folded: true });
+        assert_eq!(tree.selected_value().unwrap(), "{\n  \"command\": \"cargo test\",\n  \"dry-run\": false\n}");
+
+        // h on a scalar steps to its parent; h on an open container folds it
+        tree.cursor = 4;
+        tree.fold_or_parent();
+        assert_eq!(tree.selected().unwrap().path, ".tags");
+        tree.cursor = 2;
+        tree.fold_or_parent();
+        assert_eq!(tree.cursor, 0);
+        tree.fold_or_parent();
+        assert_eq!(tree.rows().len(), 1);
+    }
+
+    #[test]
+    fn key_search_unfolds_the_way_to_the_match() {
+        let mut tree = inspector();
+        tree.cursor = 1;
+        tree.toggle();
+        tree.cursor = 0;
+        assert!(tree.find_next("COMMAND"));
+        assert_eq!(tree.selected().unwrap().path, ".input.command");
+        assert!(tree.find_next("command"), "wraps around to the same match");
+        assert_eq!(tree.selected().unwrap().path, ".input.command");
+        assert!(!tree.find_next("missing"));
+    }
+}
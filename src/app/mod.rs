@@ -1,7 +1,58 @@
+pub mod activity;
+pub mod agent_defs;
+pub mod agent_groups;
+pub mod agent_kinds;
+pub mod allowlist;
+pub mod archive_pages;
+pub mod anomaly;
+pub mod api_health;
+pub mod bandwidth;
+pub mod budget;
+pub mod calendar;
+pub mod clipboard;
+pub mod columns;
+pub mod channels;
+pub mod command;
+pub mod compat;
+pub mod cost;
+pub mod cost_history;
+pub mod dedup;
+pub mod editor;
+pub mod empty_states;
+pub mod error_burst;
+pub mod event_index;
+pub mod failures;
+pub mod filters;
+pub mod frame_budget;
+pub mod fuzzy;
+pub mod graph_export;
+pub mod in_flight;
+pub mod json_tree;
 pub mod navigation;
+pub mod notes;
+pub mod outcome;
+pub mod pacing;
+pub mod palette;
+pub mod panels;
+pub mod progress;
+pub mod reorder;
+pub mod retries;
+pub mod review;
+pub mod runs;
+pub mod sampler;
+pub mod settings;
+pub mod severity;
+pub mod spec_docs;
 pub mod state;
+pub mod summary;
+pub mod task_links;
+pub mod timeline;
+pub mod toasts;
+pub mod tool_outputs;
+pub mod undo;
 pub mod update;
+pub mod updates;
 
 pub use navigation::handle_key;
-pub use state::{AppState, DeleteConfirmState, LayoutPickerState, PanelFocus, PromptPopupState, ScrollState, TaskViewMode, ViewState};
+pub use state::{AppState, CommandMessage, CommandState, ConfirmAction, ConfirmState, DiffPopupState, DiffRequest, EventFilters, LayoutPickerState, PaletteState, PanelFocus, PendingPlan, ProjectFilter, PromptPopupState, ScrollState, SessionDetailTab, SettingsState, Suspend, TaskViewMode, TokenDashboardTab, TruncatedEvents, UnseenEvents, ViewState, WatchdogState, WaveBanner};
 pub use update::update;
@@ -1,13 +1,44 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use crate::app::{AppState, DeleteConfirmState, LayoutPickerState, PanelFocus, PromptPopupState, TaskViewMode, ViewState};
+use crate::app::agent_groups::{self, AgentRow};
+use crate::app::archive_pages;
+use crate::app::calendar;
+use crate::app::columns;
+use crate::app::palette::{palette_results, NavTarget};
+use crate::app::command;
+use crate::app::editor;
+use crate::app::filters;
+use crate::app::json_tree;
+use crate::app::notes;
+use crate::app::outcome;
+use crate::app::review::{self, Verdict};
+use crate::app::settings::{self, SettingField};
+use crate::app::timeline;
+use crate::app::toasts::{self, ToastLevel};
+use crate::app::tool_outputs;
+use crate::app::update::apply_project_filter;
+use crate::app::undo::{push_undo, undo_last, TrashedSession, UndoAction};
+use crate::app::{AppState, CommandMessage, CommandState, ConfirmAction, ConfirmState, DiffPopupState, DiffRequest, LayoutPickerState, PaletteState, PanelFocus, PromptPopupState, SessionDetailTab, SettingsState, Suspend, TaskViewMode, TokenDashboardTab, ViewState};
+use crate::model::{AgentId, SessionId, SourceRef};
 use crate::tmux;
 
 /// Jump size for Ctrl+D / Ctrl+U (fixed at 20 lines).
 const PAGE_JUMP: usize = 20;
 
+/// Fewest lines one event occupies in the SessionDetail events panel (header + separator).
+const MIN_LINES_PER_EVENT: usize = 2;
+
 /// Key event handler. Mutates state in place.
 pub fn handle_key(state: &mut AppState, key: KeyEvent) {
+    dispatch_key(state, key);
+    // A view or channel switch brings back that scope's filter
+    filters::sync_scope(&mut state.ui);
+}
+
+fn dispatch_key(state: &mut AppState, key: KeyEvent) {
+    // Command feedback stays up only until the next key
+    state.ui.command_message = None;
+
     // Help overlay has priority
     if state.ui.show_help {
         handle_help_key(state, key);
@@ -20,6 +51,36 @@ pub fn handle_key(state: &mut AppState, key: KeyEvent) {
         return;
     }
 
+    // Diff popup scrolls like the prompt popup
+    if state.ui.diff_popup.is_open() {
+        handle_diff_popup_key(state, key);
+        return;
+    }
+
+    // So does the captured tool output pager
+    if state.ui.output_pager.is_some() {
+        handle_output_pager_key(state, key);
+        return;
+    }
+
+    // And the JSON inspector
+    if state.ui.json_inspector.is_some() {
+        handle_json_inspector_key(state, key);
+        return;
+    }
+
+    // The schema report closes on any key, like help
+    if state.ui.show_schema_report {
+        state.ui.show_schema_report = false;
+        return;
+    }
+
+    // So do the release notes
+    if state.ui.show_changelog {
+        state.ui.show_changelog = false;
+        return;
+    }
+
     // Agent popup has third priority
     if state.ui.show_agent_popup.is_some() {
         handle_popup_key(state, key);
@@ -32,23 +93,56 @@ pub fn handle_key(state: &mut AppState, key: KeyEvent) {
         return;
     }
 
-    // Delete confirm has fifth priority
-    if state.ui.delete_confirm.is_open() {
-        handle_delete_confirm_key(state, key);
+    // Confirm dialog has fifth priority
+    if state.ui.confirm.is_open() {
+        handle_confirm_key(state, key);
+        return;
+    }
+
+    // Settings overlay edits in place
+    if state.ui.settings.is_open() {
+        handle_settings_key(state, key);
+        return;
+    }
+
+    // Notes editor captures every key until saved or discarded
+    if state.ui.notes_editor.is_some() {
+        handle_notes_editor_key(state, key);
+        return;
+    }
+
+    // Review note input captures typing until submitted or cancelled
+    if state.ui.review_note.is_some() {
+        handle_review_note_key(state, key);
+        return;
+    }
+
+    // Command line captures typing like the filter bar
+    if state.ui.command.is_open() {
+        handle_command_key(state, key);
+        return;
+    }
+
+    // Quick-switch palette captures typing like the filter bar
+    if state.ui.palette.is_open() {
+        handle_palette_key(state, key);
         return;
     }
 
     // Filter mode has priority over normal navigation
-    if state.ui.filter.is_some() {
+    if filters::editing(&state.ui) {
         handle_filter_key(state, key);
         return;
     }
 
+    // An end-of-session toast takes its quick actions; other keys pass through
+    if state.ui.session_toasts.front().is_some() && handle_toast_key(state, key) {
+        return;
+    }
+
     // Normal navigation
     match key.code {
-        KeyCode::Char('q') => {
-            state.meta.should_quit = true;
-        }
+        KeyCode::Char('q') => request_quit(state),
         KeyCode::Char('1') => {
             state.ui.marked_sessions.clear();
             state.ui.view = ViewState::Dashboard;
@@ -68,20 +162,46 @@ pub fn handle_key(state: &mut AppState, key: KeyEvent) {
             state.ui.marked_sessions.clear();
             switch_to_token_dashboard(state);
         }
+        KeyCode::Char('5') => {
+            state.ui.marked_sessions.clear();
+            state.ui.view = ViewState::Review;
+        }
+        KeyCode::Char('6') => {
+            state.ui.marked_sessions.clear();
+            state.ui.view = ViewState::Timeline;
+        }
         KeyCode::Tab => toggle_focus(state),
         KeyCode::Char('l') => toggle_focus_right(state),
         KeyCode::Char('h') => toggle_focus_left(state),
+        KeyCode::Left if key.modifiers.contains(KeyModifiers::SHIFT) => pan_active_by(state, -PAN_STEP),
+        KeyCode::Right if key.modifiers.contains(KeyModifiers::SHIFT) => pan_active_by(state, PAN_STEP),
         KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => scroll_page_down(state),
         KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => scroll_page_up(state),
+        KeyCode::Char('u') => undo(state),
+        KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => state.meta.suspend = Some(Suspend::Stop),
+        KeyCode::Char('z') => state.ui.zoomed = !state.ui.zoomed,
+        KeyCode::Char('o') => open_selected_file(state),
+        KeyCode::Char('J') => open_json_inspector(state),
+        KeyCode::Char('D') if matches!(state.ui.view, ViewState::Dashboard) => open_task_diff(state),
+        KeyCode::Char('E') if matches!(state.ui.view, ViewState::Dashboard) => view_failure_evidence(state),
         KeyCode::Char('j') | KeyCode::Down => scroll_down(state),
         KeyCode::Char('k') | KeyCode::Up => scroll_up(state),
         KeyCode::Char('g') => jump_to_top(state),
         KeyCode::Char('G') => jump_to_bottom(state),
         KeyCode::Enter => drill_down(state),
+        KeyCode::Esc if state.ui.view == ViewState::Sessions && state.ui.session_date.is_some() => {
+            set_session_date(state, None);
+        }
         KeyCode::Esc => go_back(state),
         KeyCode::Char('/') => start_filter(state),
+        KeyCode::Char('x') if state.ui.filter.is_some() => filters::clear(&mut state.ui),
+        KeyCode::Char(':') => open_command_line(state),
+        KeyCode::Char(',') => open_settings(state),
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => open_palette(state),
         KeyCode::Char('p') => show_agent_popup(state),
         KeyCode::Char('v') => toggle_task_view_mode(state),
+        KeyCode::Char('|') => toggle_split_view(state),
+        KeyCode::Char('S') => toggle_spec_panel(state),
         KeyCode::Char('?') => toggle_help(state),
         KeyCode::Char(' ') => match state.ui.view {
             ViewState::Sessions => toggle_session_mark(state),
@@ -93,8 +213,99 @@ pub fn handle_key(state: &mut AppState, key: KeyEvent) {
             }
         }
         KeyCode::Char('L') => open_layout_picker(state),
+        KeyCode::Char('P') if matches!(state.ui.view, ViewState::Sessions) => cycle_project_filter(state),
+        KeyCode::Char('O') if matches!(state.ui.view, ViewState::Sessions) => cycle_outcome_filter(state),
+        KeyCode::Char('R') if matches!(state.ui.view, ViewState::Sessions) => {
+            state.ui.group_sessions_by_run = !state.ui.group_sessions_by_run;
+        }
+        KeyCode::Char('[') if matches!(state.ui.view, ViewState::Sessions) => step_session_date(state, -1),
+        KeyCode::Char(']') if matches!(state.ui.view, ViewState::Sessions) => step_session_date(state, 1),
+        KeyCode::Char('{') if matches!(state.ui.view, ViewState::Sessions) => step_session_date(state, -7),
+        KeyCode::Char('}') if matches!(state.ui.view, ViewState::Sessions) => step_session_date(state, 7),
+        KeyCode::Char(c @ ('(' | ')' | '<' | '>' | '=')) => adjust_columns(state, c),
+        KeyCode::Char('n') if matches!(state.ui.view, ViewState::SessionDetail) => toggle_session_detail_tab(state),
+        KeyCode::Char('c') if matches!(state.ui.view, ViewState::TokenDashboard) => toggle_token_dashboard_tab(state),
+        KeyCode::Char('c') if matches!(state.ui.view, ViewState::Dashboard) => cycle_event_channel(state, true),
+        KeyCode::Char('C') if matches!(state.ui.view, ViewState::Dashboard) => cycle_event_channel(state, false),
+        KeyCode::Char('T') if matches!(state.ui.view, ViewState::AgentDetail) => {
+            agent_groups::regroup(state, |g| g.grouped = !g.grouped);
+        }
+        KeyCode::Char('t') if matches!(state.ui.view, ViewState::AgentDetail) => cycle_agent_type_filter(state),
+        KeyCode::Char('w')
+            if state.ui.view == ViewState::TokenDashboard && state.ui.token_dashboard_tab == TokenDashboardTab::Cost =>
+        {
+            state.ui.cost_period = state.ui.cost_period.toggle();
+        }
+        KeyCode::Char('w') => state.ui.truncated_events.toggle(&state.ui.view),
+        KeyCode::Char('a') if matches!(state.ui.view, ViewState::Review) => review::start_decision(state, Verdict::Approved),
+        KeyCode::Char('r') if matches!(state.ui.view, ViewState::Review) => review::start_decision(state, Verdict::Rejected),
+        KeyCode::Char('e') if state.ui.session_detail_tab == SessionDetailTab::Notes => open_notes_editor(state),
         _ => {}
     }
+    request_older_events(state);
+    request_more_metas(state);
+    settle_unseen_events(state);
+}
+
+/// Panels back at the newest event have seen everything; drop their "new events" pill.
+fn settle_unseen_events(state: &mut AppState) {
+    if state.ui.scroll_offsets.event_stream == 0 {
+        state.ui.unseen_events.event_stream = 0;
+    }
+    if state.ui.scroll_offsets.agent_events == 0 {
+        state.ui.unseen_events.agent = None;
+    }
+}
+
+/// Ask for the next page of archive events once the SessionDetail events panel is
+/// scrolled within `PAGE_JUMP` rows of the end of what is loaded. Before the panel
+/// has been drawn, falls back to a lower bound on the loaded height.
+pub(crate) fn request_older_events(state: &mut AppState) {
+    use crate::view::session_detail::visible_event_count;
+    if state.ui.view != ViewState::SessionDetail
+        || state.ui.session_detail_tab != SessionDetailTab::Events
+        || state.ui.loading_events.is_some()
+    {
+        return;
+    }
+    let Some(sid) = state.ui.selected_session_id.clone() else {
+        return;
+    };
+    let has_more = state
+        .domain
+        .sessions
+        .iter()
+        .find(|s| s.meta.id == sid)
+        .and_then(|s| s.paging.as_ref())
+        .is_some_and(|p| p.remaining() > 0);
+    if !has_more {
+        return;
+    }
+    let last_offset = match state.ui.scroll_limits.session_detail_right.get() {
+        Some(max_offset) => max_offset,
+        None => visible_event_count(state) * MIN_LINES_PER_EVENT,
+    };
+    if state.ui.scroll_offsets.session_detail_right.saturating_add(PAGE_JUMP) >= last_offset {
+        state.ui.loading_events = Some(sid);
+    }
+}
+
+/// Ask for the next page of archive metas once the Sessions list's cursor is
+/// within `archive_pages::PREFETCH_MARGIN` rows of the end of what is listed.
+/// Re-checked after each page, so a project filter that leaves a page mostly
+/// empty keeps the pages coming.
+pub(crate) fn request_more_metas(state: &mut AppState) {
+    if state.ui.view != ViewState::Sessions
+        || state.ui.loading_metas
+        || state.domain.archive_pages.remaining() == 0
+    {
+        return;
+    }
+    let listed = state.domain.confirmed_active_count() + state.domain.sessions.len();
+    let selected = state.ui.selected_session_index.unwrap_or(0);
+    if selected + archive_pages::PREFETCH_MARGIN >= listed {
+        state.ui.loading_metas = true;
+    }
 }
 
 fn handle_help_key(state: &mut AppState, _key: KeyEvent) {
@@ -120,11 +331,41 @@ fn handle_prompt_popup_key(state: &mut AppState, key: KeyEvent) {
     }
 }
 
+fn handle_diff_popup_key(state: &mut AppState, key: KeyEvent) {
+    let control = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('D') => {
+            state.ui.diff_popup = DiffPopupState::Closed;
+        }
+        code => {
+            let DiffPopupState::Open { diff, scroll, .. } = &mut state.ui.diff_popup else {
+                return;
+            };
+            let last = diff.lines.len().saturating_sub(1);
+            *scroll = match code {
+                KeyCode::Char('d') if control => scroll.saturating_add(PAGE_JUMP),
+                KeyCode::Char('u') if control => scroll.saturating_sub(PAGE_JUMP),
+                KeyCode::Char('j') | KeyCode::Down => scroll.saturating_add(1),
+                KeyCode::Char('k') | KeyCode::Up => scroll.saturating_sub(1),
+                KeyCode::Char('g') => 0,
+                KeyCode::Char('G') => last,
+                _ => *scroll,
+            }
+            .min(last);
+        }
+    }
+}
+
 fn handle_popup_key(state: &mut AppState, key: KeyEvent) {
     match key.code {
         KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('p') => {
             state.ui.show_agent_popup = None;
         }
+        // The full prompt is drawn by the agent's list views
+        KeyCode::Enter if matches!(state.ui.view, ViewState::AgentDetail | ViewState::SessionDetail) => {
+            state.ui.show_agent_popup = None;
+            state.ui.prompt_popup = PromptPopupState::Open { scroll: 0 };
+        }
         _ => {}
     }
 }
@@ -183,6 +424,76 @@ fn toggle_session_mark(state: &mut AppState) {
     scroll_down(state);
 }
 
+/// Sessions view: step the project scope (current → other projects → all).
+fn cycle_project_filter(state: &mut AppState) {
+    let projects: Vec<String> = state
+        .domain
+        .archive_index
+        .iter()
+        .map(|(_, meta)| meta.project_path.clone())
+        .filter(|p| !p.is_empty())
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    state.ui.project_filter = state.ui.project_filter.next(&projects, &state.meta.project_path);
+    // Marks on sessions leaving the list must not be bulk-deleted unseen
+    state.ui.marked_sessions.clear();
+    apply_project_filter(state);
+}
+
+/// Sessions view: step the outcome filter (all → success → … → unknown → all).
+fn cycle_outcome_filter(state: &mut AppState) {
+    state.ui.outcome_filter = outcome::next_filter(state.ui.outcome_filter);
+    // Marks on sessions leaving the list must not be bulk-deleted unseen
+    state.ui.marked_sessions.clear();
+    apply_project_filter(state);
+}
+
+/// Column keys on the focused table: `(` / `)` pick a column, `<` / `>`
+/// narrow or widen it, `=` rebalances. No-op without a focused table.
+fn adjust_columns(state: &mut AppState, key: char) {
+    let Some(table) = columns::focused_table(state) else {
+        return;
+    };
+    let count = columns::column_count(table);
+    let widths = &mut state.ui.table_columns;
+    match key {
+        '(' => widths.select(table, -1, count),
+        ')' => widths.select(table, 1, count),
+        '<' => widths.resize(table, -columns::RESIZE_STEP),
+        '>' => widths.resize(table, columns::RESIZE_STEP),
+        _ => widths.reset(table),
+    }
+}
+
+/// Move the calendar's selected day by `days`, narrowing the Sessions list to it.
+fn step_session_date(state: &mut AppState, days: i64) {
+    let today = state.ui.time_format.date(state.meta.clock.now());
+    let date = calendar::step_day(state.ui.session_date, days, state.session_calendar(), today);
+    set_session_date(state, date);
+}
+
+fn set_session_date(state: &mut AppState, date: Option<chrono::NaiveDate>) {
+    state.ui.session_date = date;
+    // Marks on sessions leaving the list must not be bulk-deleted unseen
+    state.ui.marked_sessions.clear();
+    apply_project_filter(state);
+    // Land on the first session of the day; active sessions above it aren't filtered
+    let active_count = state.domain.confirmed_active_count();
+    state.ui.selected_session_index = if !state.domain.sessions.is_empty() {
+        Some(active_count)
+    } else {
+        (active_count > 0).then_some(0)
+    };
+    state.ui.command_message = Some(CommandMessage {
+        text: match date {
+            Some(date) => format!("{} session(s) on {}", state.session_calendar().count(date), date.format("%a %Y-%m-%d")),
+            None => "showing sessions from every day".into(),
+        },
+        is_error: false,
+    });
+}
+
 fn initiate_delete(state: &mut AppState) {
     let active_count = state.domain.confirmed_active_count();
     let ids: Vec<_> = if !state.ui.marked_sessions.is_empty() {
@@ -201,55 +512,102 @@ fn initiate_delete(state: &mut AppState) {
         return;
     };
 
-    state.ui.delete_confirm = DeleteConfirmState::Open { session_ids: ids };
+    state.ui.confirm = ConfirmState::open(ConfirmAction::DeleteSessions(ids));
 }
 
-fn handle_delete_confirm_key(state: &mut AppState, key: KeyEvent) {
-    match key.code {
-        KeyCode::Char('y') => {
-            if let DeleteConfirmState::Open { session_ids } = &state.ui.delete_confirm {
-                let ids = session_ids.clone();
-                for id in &ids {
-                    if let Some(session) = state.domain.sessions.iter().find(|s| &s.meta.id == id) {
-                        if let Err(e) = crate::session::delete_session(&session.path) {
-                            state.meta.errors.push_back(format!("delete {id}: {e}"));
-                        }
-                    }
-                    // Write tombstone so session isn't re-discovered on restart
-                    if let Some(ref archive_dir) = state.meta.archive_dir {
-                        if let Err(e) = crate::session::mark_deleted(archive_dir, id.as_str()) {
-                            state.meta.errors.push_back(format!("tombstone {id}: {e}"));
-                        }
-                    }
-                    state.domain.deleted_session_ids.insert(id.clone());
-                }
-                state.domain.sessions.retain(|s| !ids.contains(&s.meta.id));
-                state.ui.marked_sessions.clear();
-                // Clamp selected index to new bounds
-                let total = state.domain.confirmed_active_count() + state.domain.sessions.len();
-                if total == 0 {
-                    state.ui.selected_session_index = None;
-                } else if let Some(idx) = state.ui.selected_session_index {
-                    if idx >= total {
-                        state.ui.selected_session_index = Some(total - 1);
-                    }
+/// Quit, asking first while agents are still running.
+pub(crate) fn request_quit(state: &mut AppState) {
+    let running = state.domain.agents.values().filter(|agent| agent.finished_at.is_none()).count();
+    if running == 0 {
+        state.meta.should_quit = true;
+    } else {
+        state.ui.confirm = ConfirmState::open(ConfirmAction::Quit { running });
+    }
+}
+
+fn undo(state: &mut AppState) {
+    let text = undo_last(state).unwrap_or_else(|| "nothing to undo".to_string());
+    state.ui.command_message = Some(CommandMessage { text, is_error: false });
+}
+
+/// Keys of the confirm dialog: y / n answer, ←/→ or Tab move the focus,
+/// Enter takes the focused answer, Esc says No. Other keys are swallowed.
+fn handle_confirm_key(state: &mut AppState, key: KeyEvent) {
+    let ConfirmState::Open { yes_focused, .. } = &mut state.ui.confirm else {
+        return;
+    };
+    let accept = match key.code {
+        KeyCode::Char('y') => true,
+        KeyCode::Enter => *yes_focused,
+        KeyCode::Char('n') | KeyCode::Esc => false,
+        KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::BackTab | KeyCode::Char('h') | KeyCode::Char('l') => {
+            *yes_focused = !*yes_focused;
+            return;
+        }
+        _ => return,
+    };
+    let ConfirmState::Open { action, .. } = std::mem::replace(&mut state.ui.confirm, ConfirmState::Closed) else {
+        return;
+    };
+    if accept {
+        match action {
+            ConfirmAction::DeleteSessions(ids) => delete_sessions(state, &ids),
+            ConfirmAction::Quit { .. } => state.meta.should_quit = true,
+        }
+    }
+}
+
+/// Move archived sessions to the trash, undoable with `u`.
+fn delete_sessions(state: &mut AppState, ids: &[SessionId]) {
+    let marked = std::mem::take(&mut state.ui.marked_sessions);
+    let selected_index = state.ui.selected_session_index;
+    let now = state.meta.clock.now();
+    let mut trashed = Vec::new();
+    for id in ids {
+        if let Some(index) = state.domain.sessions.iter().position(|s| &s.meta.id == id) {
+            let session = state.domain.sessions.remove(index);
+            // Trash rather than delete so the action can be undone
+            let trash_path = match crate::session::trash_session(&session.path, now) {
+                Ok(path) => Some(path),
+                Err(e) => {
+                    state.meta.errors.push_back(format!("delete {id}: {e}"));
+                    None
                 }
+            };
+            trashed.push(TrashedSession { session, index, trash_path });
+        }
+        // Write tombstone so session isn't re-discovered on restart
+        if let Some(ref archive_dir) = state.meta.archive_dir {
+            if let Err(e) = crate::session::mark_deleted(archive_dir, id.as_str()) {
+                state.meta.errors.push_back(format!("tombstone {id}: {e}"));
             }
-            state.ui.delete_confirm = DeleteConfirmState::Closed;
         }
-        KeyCode::Char('n') | KeyCode::Esc => {
-            state.ui.delete_confirm = DeleteConfirmState::Closed;
+        state.domain.deleted_session_ids.insert(id.clone());
+        state.evict_session_preview(id);
+    }
+    // Clamp selected index to new bounds
+    let total = state.domain.confirmed_active_count() + state.domain.sessions.len();
+    if total == 0 {
+        state.ui.selected_session_index = None;
+    } else if let Some(idx) = state.ui.selected_session_index {
+        if idx >= total {
+            state.ui.selected_session_index = Some(total - 1);
         }
-        _ => {}
+    }
+    if !trashed.is_empty() {
+        let action = UndoAction::DeleteSessions { sessions: trashed, marked, selected_index };
+        state.ui.command_message = Some(CommandMessage {
+            text: format!("deleted {} — u to undo", action.describe()),
+            is_error: false,
+        });
+        push_undo(state, action);
     }
 }
 
 fn handle_filter_key(state: &mut AppState, key: KeyEvent) {
     match key.code {
-        KeyCode::Esc => {
-            state.ui.filter = None;
-        }
-        KeyCode::Enter => {}
+        KeyCode::Esc => filters::clear(&mut state.ui),
+        KeyCode::Enter => filters::apply(&mut state.ui),
         KeyCode::Backspace => {
             if let Some(ref mut filter) = state.ui.filter {
                 filter.pop();
@@ -264,8 +622,219 @@ fn handle_filter_key(state: &mut AppState, key: KeyEvent) {
     }
 }
 
-fn switch_to_token_dashboard(state: &mut AppState) {
-    // Preload unloaded archives
+fn open_command_line(state: &mut AppState) {
+    state.ui.command = CommandState::Open { input: String::new() };
+}
+
+fn handle_command_key(state: &mut AppState, key: KeyEvent) {
+    let CommandState::Open { input } = &mut state.ui.command else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => {
+            state.ui.command = CommandState::Closed;
+        }
+        KeyCode::Enter => {
+            let line = std::mem::take(input);
+            state.ui.command = CommandState::Closed;
+            if line.trim().is_empty() {
+                return;
+            }
+            state.ui.command_message = match command::parse_command(&line)
+                .and_then(|cmd| command::execute(state, cmd))
+            {
+                Ok(Some(text)) => Some(CommandMessage { text, is_error: false }),
+                Ok(None) => None,
+                Err(e) => Some(CommandMessage { text: e.to_string(), is_error: true }),
+            };
+        }
+        KeyCode::Tab => {
+            let line = input.clone();
+            if let Some(completed) = command::complete(state, &line) {
+                state.ui.command = CommandState::Open { input: completed };
+            }
+        }
+        // Backspace on an empty line leaves command mode, like vim
+        KeyCode::Backspace if input.pop().is_none() => {
+            state.ui.command = CommandState::Closed;
+        }
+        KeyCode::Char(c) => input.push(c),
+        _ => {}
+    }
+}
+
+fn open_settings(state: &mut AppState) {
+    state.ui.settings = SettingsState::Open { selected: 0, original: Box::new(settings::current(state)) };
+}
+
+/// j/k select, h/l (or ←/→, Space) adjust with live preview,
+/// Enter keeps and saves, Esc or `,` reverts.
+fn handle_settings_key(state: &mut AppState, key: KeyEvent) {
+    let SettingsState::Open { selected, original } = &mut state.ui.settings else {
+        return;
+    };
+    let field = SettingField::ALL[*selected];
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            *selected = (*selected + 1).min(SettingField::ALL.len() - 1);
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            *selected = selected.saturating_sub(1);
+        }
+        KeyCode::Char('l') | KeyCode::Right | KeyCode::Char(' ') => {
+            let next = field.adjust(&settings::current(state), true);
+            settings::apply(state, &next);
+        }
+        KeyCode::Char('h') | KeyCode::Left => {
+            let next = field.adjust(&settings::current(state), false);
+            settings::apply(state, &next);
+        }
+        KeyCode::Esc | KeyCode::Char(',') => {
+            let original = original.clone();
+            settings::apply(state, &original);
+            state.ui.settings = SettingsState::Closed;
+        }
+        KeyCode::Enter => {
+            state.ui.settings = SettingsState::Closed;
+            let current = settings::current(state);
+            state.ui.command_message = Some(match state.meta.config_path {
+                Some(ref path) => match settings::save_settings(path, &current) {
+                    Ok(()) => CommandMessage { text: format!("settings saved to {}", path.display()), is_error: false },
+                    Err(e) => CommandMessage { text: format!("settings not saved: {e}"), is_error: true },
+                },
+                None => CommandMessage { text: "settings applied (no config file to save to)".into(), is_error: false },
+            });
+        }
+        _ => {}
+    }
+}
+
+fn open_palette(state: &mut AppState) {
+    state.ui.palette = PaletteState::Open { query: String::new(), selected: 0 };
+}
+
+fn handle_palette_key(state: &mut AppState, key: KeyEvent) {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc => {
+            state.ui.palette = PaletteState::Closed;
+        }
+        KeyCode::Enter => {
+            let PaletteState::Open { query, selected } = std::mem::replace(&mut state.ui.palette, PaletteState::Closed) else {
+                return;
+            };
+            if let Some((entry, _)) = palette_results(state, &query).into_iter().nth(selected) {
+                jump_to_target(state, &entry.target);
+            }
+        }
+        KeyCode::Down => move_palette_selection(state, true),
+        KeyCode::Char('n') if ctrl => move_palette_selection(state, true),
+        KeyCode::Up => move_palette_selection(state, false),
+        KeyCode::Char('p') if ctrl => move_palette_selection(state, false),
+        KeyCode::Backspace => {
+            if let PaletteState::Open { query, selected } = &mut state.ui.palette {
+                query.pop();
+                *selected = 0;
+            }
+        }
+        KeyCode::Char(c) if !ctrl => {
+            if let PaletteState::Open { query, selected } = &mut state.ui.palette {
+                query.push(c);
+                *selected = 0;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn move_palette_selection(state: &mut AppState, down: bool) {
+    let count = match &state.ui.palette {
+        PaletteState::Open { query, .. } => palette_results(state, query).len(),
+        PaletteState::Closed => return,
+    };
+    if let PaletteState::Open { selected, .. } = &mut state.ui.palette {
+        *selected = if down {
+            (*selected + 1).min(count.saturating_sub(1))
+        } else {
+            selected.saturating_sub(1)
+        };
+    }
+}
+
+/// Switch to the view that shows `target` and select it there.
+/// Sessions go through the Sessions list so archived ones load like a normal drill-down.
+/// Quick actions of the end-of-session toast on screen.
+/// Returns whether the key was one of them.
+fn handle_toast_key(state: &mut AppState, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Enter => {
+            if let Some(toast) = state.ui.session_toasts.pop() {
+                jump_to_target(state, &NavTarget::Session(toast.session_id));
+            }
+        }
+        KeyCode::Char('e') => {
+            if let Some(toast) = state.ui.session_toasts.pop() {
+                match command::export_session_markdown(state, &toast.session_id) {
+                    Ok(text) => toasts::raise(state, ToastLevel::Info, text),
+                    Err(e) => toasts::raise(state, ToastLevel::Error, e.to_string()),
+                }
+            }
+        }
+        KeyCode::Esc => {
+            state.ui.session_toasts.pop();
+        }
+        _ => return false,
+    }
+    true
+}
+
+fn jump_to_target(state: &mut AppState, target: &NavTarget) {
+    state.ui.marked_sessions.clear();
+    match target {
+        NavTarget::Session(sid) => {
+            let active_count = state.domain.confirmed_active_count();
+            let idx = state
+                .domain
+                .confirmed_active_sessions()
+                .position(|(id, _)| id == sid)
+                .or_else(|| {
+                    state.domain.sessions.iter()
+                        .position(|s| &s.meta.id == sid)
+                        .map(|i| i + active_count)
+                });
+            if let Some(idx) = idx {
+                state.ui.view = ViewState::Sessions;
+                state.ui.selected_session_index = Some(idx);
+                drill_down(state);
+            }
+        }
+        NavTarget::Agent(agent_id) => {
+            if let Some(idx) = agent_groups::reveal(state, agent_id) {
+                state.ui.prompt_popup = PromptPopupState::Closed;
+                state.ui.view = ViewState::AgentDetail;
+                state.ui.focus = PanelFocus::Left;
+                state.ui.selected_agent_index = Some(idx);
+                state.ui.scroll_offsets.agent_events = 0;
+            }
+        }
+        NavTarget::Task(task_id) => {
+            let idx = state
+                .domain
+                .task_graph
+                .as_ref()
+                .and_then(|g| g.flat_tasks().position(|t| &t.id == task_id));
+            if let Some(idx) = idx {
+                state.ui.view = ViewState::Dashboard;
+                state.ui.focus = PanelFocus::Left;
+                state.ui.selected_task_index = Some(idx);
+                state.ui.scroll_offsets.task_list = idx;
+            }
+        }
+    }
+}
+
+/// Load every archive not yet in memory; token and cost figures need their agents.
+pub(crate) fn preload_archives(state: &mut AppState) {
     for session in &mut state.domain.sessions {
         if session.data.is_none() {
             match crate::session::load_session(&session.path) {
@@ -274,11 +843,36 @@ fn switch_to_token_dashboard(state: &mut AppState) {
             }
         }
     }
+}
+
+fn switch_to_token_dashboard(state: &mut AppState) {
+    preload_archives(state);
     state.ui.view = ViewState::TokenDashboard;
     state.ui.scroll_offsets.token_dashboard_left = 0;
     state.ui.focus = PanelFocus::Left;
 }
 
+/// Fold or unfold the agent list section whose header is selected.
+fn toggle_selected_group(state: &mut AppState) {
+    let rows = agent_groups::agent_rows(state);
+    if let Some(AgentRow::Group { agent_type, .. }) = state.ui.selected_agent_index.and_then(|idx| rows.get(idx)) {
+        agent_groups::regroup(state, |g| g.toggle_collapsed(agent_type));
+    }
+}
+
+/// Narrow the agent list to the next agent type, then back to all of them.
+fn cycle_agent_type_filter(state: &mut AppState) {
+    let types = agent_groups::agent_types(state);
+    agent_groups::regroup(state, |g| g.type_filter = g.next_filter(&types));
+    state.ui.command_message = Some(CommandMessage {
+        text: match &state.ui.agent_grouping.type_filter {
+            Some(agent_type) => format!("agents: {agent_type} only"),
+            None => "agents: all types".into(),
+        },
+        is_error: false,
+    });
+}
+
 fn switch_to_agent_detail(state: &mut AppState) {
     state.ui.view = ViewState::AgentDetail;
     if state.ui.selected_agent_index.is_none() && !state.domain.agents.is_empty() {
@@ -291,6 +885,32 @@ fn task_count(state: &AppState) -> usize {
     state.domain.task_graph.as_ref().map(|g| g.total_tasks()).unwrap_or(0)
 }
 
+/// Toggle the Dashboard split: task list on the left, followed agent's activity on the right.
+/// From other views this also switches to the Dashboard.
+fn toggle_split_view(state: &mut AppState) {
+    if state.ui.view == ViewState::Dashboard {
+        state.ui.split_view = !state.ui.split_view;
+    } else {
+        state.ui.marked_sessions.clear();
+        state.ui.view = ViewState::Dashboard;
+        state.ui.split_view = true;
+    }
+    // The right half's cursor now belongs to a different panel
+    state.ui.scroll_offsets.agent_events = 0;
+}
+
+/// `S`: spec / plan preview beside the Dashboard panels; from another view,
+/// go to the Dashboard with it shown.
+fn toggle_spec_panel(state: &mut AppState) {
+    if state.ui.view == ViewState::Dashboard {
+        state.ui.show_spec_panel = !state.ui.show_spec_panel;
+    } else {
+        state.ui.marked_sessions.clear();
+        state.ui.view = ViewState::Dashboard;
+        state.ui.show_spec_panel = true;
+    }
+}
+
 fn toggle_focus(state: &mut AppState) {
     state.ui.focus = match state.ui.focus {
         PanelFocus::Left => PanelFocus::Right,
@@ -310,6 +930,7 @@ fn toggle_focus_left(state: &mut AppState) {
 fn active_scroll_offset_mut(state: &mut AppState) -> &mut usize {
     match (&state.ui.view, &state.ui.focus) {
         (ViewState::Dashboard, PanelFocus::Left) => &mut state.ui.scroll_offsets.task_list,
+        (ViewState::Dashboard, PanelFocus::Right) if state.ui.split_view => &mut state.ui.scroll_offsets.agent_events,
         (ViewState::Dashboard, PanelFocus::Right) => &mut state.ui.scroll_offsets.event_stream,
         (ViewState::AgentDetail, _) => &mut state.ui.scroll_offsets.agent_events,
         (ViewState::Sessions, _) => &mut state.ui.scroll_offsets.task_list, // unused, Sessions uses selected_session_index
@@ -317,6 +938,54 @@ fn active_scroll_offset_mut(state: &mut AppState) -> &mut usize {
         (ViewState::SessionDetail, PanelFocus::Right) => &mut state.ui.scroll_offsets.session_detail_right,
         (ViewState::TokenDashboard, PanelFocus::Left) => &mut state.ui.scroll_offsets.token_dashboard_left,
         (ViewState::TokenDashboard, PanelFocus::Right) => &mut state.ui.scroll_offsets.task_list, // fallback, unused
+        (ViewState::Review, _) => &mut state.ui.scroll_offsets.review,
+        (ViewState::Timeline, _) => &mut state.ui.scroll_offsets.timeline,
+    }
+}
+
+/// Largest useful offset of the active wrapped event panel, as last rendered.
+/// None for other panels, or before the first draw (scrolling is then unbounded).
+fn scroll_limit(state: &AppState) -> Option<usize> {
+    match (&state.ui.view, &state.ui.focus) {
+        (ViewState::Dashboard, PanelFocus::Right) if state.ui.split_view => None,
+        (ViewState::Dashboard, PanelFocus::Right) => state.ui.scroll_limits.event_stream.get(),
+        (ViewState::SessionDetail, PanelFocus::Right) => state.ui.scroll_limits.session_detail_right.get(),
+        // One line per event
+        (ViewState::Timeline, _) => Some(timeline::timeline_events(state).len().saturating_sub(1)),
+        _ => None,
+    }
+}
+
+/// Move the active scroll offset by `delta` rows, staying within the rendered content.
+fn scroll_active_by(state: &mut AppState, delta: isize) {
+    let limit = scroll_limit(state).unwrap_or(usize::MAX);
+    let offset = active_scroll_offset_mut(state);
+    *offset = (*offset).min(limit).saturating_add_signed(delta).min(limit);
+}
+
+/// Columns one Shift+←/→ press pans an event panel sideways.
+const PAN_STEP: isize = 8;
+
+/// The focused event panel's horizontal offset and its rendered limit, if it pans.
+fn pan_target(state: &mut AppState) -> Option<(&mut usize, Option<usize>)> {
+    let (offsets, limits) = (&mut state.ui.scroll_offsets, &state.ui.scroll_limits);
+    match (&state.ui.view, state.ui.focus) {
+        (ViewState::Dashboard, PanelFocus::Right) if !state.ui.split_view => {
+            Some((&mut offsets.event_stream_x, limits.event_stream_x.get()))
+        }
+        (ViewState::AgentDetail, PanelFocus::Right) => Some((&mut offsets.agent_events_x, limits.agent_events_x.get())),
+        (ViewState::SessionDetail, PanelFocus::Right) => {
+            Some((&mut offsets.session_detail_right_x, limits.session_detail_right_x.get()))
+        }
+        _ => None,
+    }
+}
+
+/// Pan the focused event panel by `delta` columns, staying within its widest line.
+fn pan_active_by(state: &mut AppState, delta: isize) {
+    if let Some((offset, limit)) = pan_target(state) {
+        let limit = limit.unwrap_or(usize::MAX);
+        *offset = (*offset).min(limit).saturating_add_signed(delta).min(limit);
     }
 }
 
@@ -332,7 +1001,8 @@ fn session_agent_count(state: &AppState) -> usize {
 fn item_count(state: &AppState) -> Option<usize> {
     match (&state.ui.view, &state.ui.focus) {
         (ViewState::Dashboard, PanelFocus::Left) => Some(task_count(state)),
-        (ViewState::AgentDetail, PanelFocus::Left) => Some(state.domain.agents.len()),
+        (ViewState::AgentDetail, PanelFocus::Left) if state.ui.agent_grouping.is_flat() => Some(state.domain.agents.len()),
+        (ViewState::AgentDetail, PanelFocus::Left) => Some(agent_groups::agent_rows(state).len()),
         (ViewState::SessionDetail, PanelFocus::Left) => Some(session_agent_count(state) + 1), // +1 for Main
         (ViewState::Sessions, _) => Some(state.domain.confirmed_active_count() + state.domain.sessions.len()),
         (ViewState::TokenDashboard, PanelFocus::Left) => {
@@ -341,6 +1011,7 @@ fn item_count(state: &AppState) -> Option<usize> {
             let archived = state.domain.sessions.iter().filter(|s| s.data.is_some()).count();
             Some(active + archived)
         }
+        (ViewState::Review, _) => Some(review::review_queue(state).len()),
         _ => None,
     }
 }
@@ -400,9 +1071,11 @@ fn scroll_down(state: &mut AppState) {
                 }
             }
         }
-        _ => {
-            *active_scroll_offset_mut(state) = active_scroll_offset_mut(state).saturating_add(1);
+        (ViewState::Review, _) => {
+            let max = item_count(state).unwrap_or(0).saturating_sub(1);
+            state.ui.scroll_offsets.review = (state.ui.scroll_offsets.review + 1).min(max);
         }
+        _ => scroll_active_by(state, 1),
     }
     if disables_auto_scroll(state) {
         state.ui.auto_scroll = false;
@@ -441,9 +1114,10 @@ fn scroll_up(state: &mut AppState) {
             state.ui.scroll_offsets.token_dashboard_left =
                 state.ui.scroll_offsets.token_dashboard_left.saturating_sub(1);
         }
-        _ => {
-            *active_scroll_offset_mut(state) = active_scroll_offset_mut(state).saturating_sub(1);
+        (ViewState::Review, _) => {
+            state.ui.scroll_offsets.review = state.ui.scroll_offsets.review.saturating_sub(1);
         }
+        _ => scroll_active_by(state, -1),
     }
     if disables_auto_scroll(state) {
         state.ui.auto_scroll = false;
@@ -494,9 +1168,11 @@ fn scroll_page_down(state: &mut AppState) {
                 }
             }
         }
-        _ => {
-            *active_scroll_offset_mut(state) = active_scroll_offset_mut(state).saturating_add(PAGE_JUMP);
+        (ViewState::Review, _) => {
+            let max = item_count(state).unwrap_or(0).saturating_sub(1);
+            state.ui.scroll_offsets.review = (state.ui.scroll_offsets.review + PAGE_JUMP).min(max);
         }
+        _ => scroll_active_by(state, PAGE_JUMP as isize),
     }
     if disables_auto_scroll(state) {
         state.ui.auto_scroll = false;
@@ -533,9 +1209,10 @@ fn scroll_page_up(state: &mut AppState) {
             state.ui.scroll_offsets.token_dashboard_left =
                 state.ui.scroll_offsets.token_dashboard_left.saturating_sub(PAGE_JUMP);
         }
-        _ => {
-            *active_scroll_offset_mut(state) = active_scroll_offset_mut(state).saturating_sub(PAGE_JUMP);
+        (ViewState::Review, _) => {
+            state.ui.scroll_offsets.review = state.ui.scroll_offsets.review.saturating_sub(PAGE_JUMP);
         }
+        _ => scroll_active_by(state, -(PAGE_JUMP as isize)),
     }
     if disables_auto_scroll(state) {
         state.ui.auto_scroll = false;
@@ -564,10 +1241,17 @@ fn jump_to_top(state: &mut AppState) {
         (ViewState::TokenDashboard, PanelFocus::Left) => {
             state.ui.scroll_offsets.token_dashboard_left = 0;
         }
+        (ViewState::Review, _) => {
+            state.ui.scroll_offsets.review = 0;
+        }
         _ => {
             *active_scroll_offset_mut(state) = 0;
         }
     }
+    // Back at the newest event in a live panel: follow new events again
+    if disables_auto_scroll(state) {
+        state.ui.auto_scroll = true;
+    }
 }
 
 fn jump_to_bottom(state: &mut AppState) {
@@ -605,33 +1289,46 @@ fn jump_to_bottom(state: &mut AppState) {
                 }
             }
         }
+        (ViewState::Review, _) => {
+            state.ui.scroll_offsets.review = item_count(state).unwrap_or(0).saturating_sub(1);
+        }
         _ => {
-            *active_scroll_offset_mut(state) = usize::MAX / 2;
+            *active_scroll_offset_mut(state) = scroll_limit(state).unwrap_or(usize::MAX / 2);
         }
     }
 }
 
 fn drill_down(state: &mut AppState) {
     match state.ui.view {
+        ViewState::Dashboard if state.ui.split_view && state.ui.focus == PanelFocus::Right => {
+            toggle_selected_step(state);
+        }
         ViewState::Dashboard => {
             if let Some(task_idx) = state.ui.selected_task_index {
                 if let Some(ref task_graph) = state.domain.task_graph {
                     let all_tasks: Vec<_> = task_graph.flat_tasks().collect();
 
                     if let Some(task) = all_tasks.get(task_idx) {
-                        if let Some(ref agent_id) = task.agent_id {
-                            let agent_idx = state
-                                .sorted_agent_keys()
-                                .iter()
-                                .position(|k| *k == *agent_id);
-                            state.ui.selected_agent_index = agent_idx;
-                            state.ui.view = ViewState::AgentDetail;
+                        if let Some(agent_id) = task.agent_id.clone() {
+                            state.ui.selected_agent_index = agent_groups::reveal(state, &agent_id);
+                            if state.ui.split_view {
+                                // Follow the task's agent in the right half instead of leaving
+                                state.ui.scroll_offsets.agent_events = 0;
+                            } else {
+                                state.ui.view = ViewState::AgentDetail;
+                            }
                         }
                     }
                 }
             }
         }
-        ViewState::AgentDetail => {}
+        ViewState::AgentDetail => {
+            if state.ui.focus == PanelFocus::Right {
+                toggle_selected_step(state);
+            } else {
+                toggle_selected_group(state);
+            }
+        }
         ViewState::Sessions => {
             if let Some(idx) = state.ui.selected_session_index {
                 let active_count = state.domain.confirmed_active_count();
@@ -656,8 +1353,364 @@ fn drill_down(state: &mut AppState) {
                 }
             }
         }
-        ViewState::SessionDetail => {}
-        ViewState::TokenDashboard => {}
+        ViewState::SessionDetail => {
+            if state.ui.focus == PanelFocus::Right && state.ui.session_detail_tab == SessionDetailTab::Notes {
+                open_notes_editor(state);
+            }
+        }
+        ViewState::TokenDashboard | ViewState::Review | ViewState::Timeline => {}
+    }
+}
+
+/// Switch the SessionDetail right panel between events and notes.
+fn toggle_session_detail_tab(state: &mut AppState) {
+    state.ui.session_detail_tab = match state.ui.session_detail_tab {
+        SessionDetailTab::Events => SessionDetailTab::Notes,
+        SessionDetailTab::Notes => SessionDetailTab::Events,
+    };
+    state.ui.scroll_offsets.session_detail_right = 0;
+    state.ui.scroll_limits.session_detail_right.set(None);
+}
+
+/// Switch the TokenDashboard between the session table and historical cost.
+fn toggle_token_dashboard_tab(state: &mut AppState) {
+    state.ui.token_dashboard_tab = match state.ui.token_dashboard_tab {
+        TokenDashboardTab::Sessions => TokenDashboardTab::Cost,
+        TokenDashboardTab::Cost => TokenDashboardTab::Sessions,
+    };
+}
+
+/// Show the next (previous) event channel in the Dashboard event stream.
+fn cycle_event_channel(state: &mut AppState, forward: bool) {
+    state.ui.event_channels.cycle(forward);
+    state.ui.scroll_offsets.event_stream = 0;
+}
+
+fn open_notes_editor(state: &mut AppState) {
+    if state.ui.view != ViewState::SessionDetail {
+        return;
+    }
+    if let Err(reason) = notes::open_editor(state) {
+        state.ui.command_message = Some(CommandMessage { text: reason, is_error: true });
+    }
+}
+
+/// Notes editor keys: typing edits, Enter breaks the line, Ctrl+S saves into
+/// the archive, Esc discards the edit.
+fn handle_notes_editor_key(state: &mut AppState, key: KeyEvent) {
+    if key.code == KeyCode::Char('s') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        state.ui.command_message = Some(notes::save_notes(state));
+        return;
+    }
+    if key.code == KeyCode::Esc {
+        state.ui.notes_editor = None;
+        state.ui.command_message = Some(CommandMessage { text: "notes edit discarded".into(), is_error: false });
+        return;
+    }
+    let Some(editor) = state.ui.notes_editor.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => editor.insert(c),
+        KeyCode::Enter => editor.insert('\n'),
+        KeyCode::Backspace => editor.backspace(),
+        KeyCode::Delete => editor.delete(),
+        KeyCode::Left => editor.left(),
+        KeyCode::Right => editor.right(),
+        KeyCode::Up => editor.up(),
+        KeyCode::Down => editor.down(),
+        KeyCode::Home => editor.home(),
+        KeyCode::End => editor.end(),
+        _ => {}
+    }
+}
+
+/// Review note keys: typing edits the note, Enter records the decision,
+/// Esc cancels without writing anything.
+fn handle_review_note_key(state: &mut AppState, key: KeyEvent) {
+    let Some(note) = state.ui.review_note.as_mut() else {
+        return;
+    };
+    match key.code {
+        KeyCode::Esc => state.ui.review_note = None,
+        KeyCode::Enter => state.ui.command_message = Some(review::submit_decision(state)),
+        KeyCode::Backspace => {
+            note.input.pop();
+        }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => note.input.push(c),
+        _ => {}
+    }
+}
+
+/// Ask the main loop for a `git diff` of the selected task's modified files
+/// since its session started, shown in the diff popup.
+/// Jump from the selected failed task to its agent's failed tool results
+/// (see `app::failures`): AgentDetail with the activity focused, the steps
+/// holding them expanded and the cursor on the latest.
+fn view_failure_evidence(state: &mut AppState) {
+    use crate::view::components::step_tree::agent_steps;
+    let task = state
+        .ui
+        .selected_task_index
+        .and_then(|idx| state.domain.task_graph.as_ref()?.flat_tasks().nth(idx));
+    let Some(failure) = task.and_then(|t| state.domain.task_failures.get(&t.id)) else {
+        state.ui.command_message = Some(CommandMessage { text: "no failure recorded for this task".into(), is_error: true });
+        return;
+    };
+    let (Some(agent_id), false) = (failure.agent_id.clone(), failure.evidence.is_empty()) else {
+        let text = format!("no failed tool results before the failure ({})", failure.reason);
+        state.ui.command_message = Some(CommandMessage { text, is_error: true });
+        return;
+    };
+    let evidence = failure.evidence.clone();
+    let Some(idx) = agent_groups::reveal(state, &agent_id) else {
+        state.ui.command_message = Some(CommandMessage { text: format!("agent {} is gone", agent_id), is_error: true });
+        return;
+    };
+    state.ui.view = ViewState::AgentDetail;
+    state.ui.focus = PanelFocus::Right;
+    state.ui.selected_agent_index = Some(idx);
+    state.ui.auto_scroll = false;
+
+    let steps = agent_steps(state, agent_id.as_str());
+    let holding: Vec<usize> = (0..steps.len())
+        .filter(|&i| {
+            let step = &steps[i];
+            std::iter::once(step.head).chain(step.tools.iter().copied()).any(|e| evidence.contains(&e.timestamp))
+        })
+        .collect();
+    let keys: Vec<_> = holding.iter().map(|&i| steps[i].key()).collect();
+    // The cursor counts steps newest first
+    let cursor = holding.last().map(|&i| steps.len() - 1 - i).unwrap_or(0);
+    state.ui.expanded_steps.extend(keys);
+    state.ui.scroll_offsets.agent_events = cursor;
+}
+
+fn open_task_diff(state: &mut AppState) {
+    let task = state
+        .ui
+        .selected_task_index
+        .and_then(|idx| state.domain.task_graph.as_ref()?.flat_tasks().nth(idx));
+    let Some(task) = task else {
+        state.ui.command_message = Some(CommandMessage { text: "no task selected".into(), is_error: true });
+        return;
+    };
+    if task.files_modified.is_empty() {
+        let text = format!("{} has no modified files recorded", task.id);
+        state.ui.command_message = Some(CommandMessage { text, is_error: true });
+        return;
+    }
+    let Some(since) = task_session_start(state, task.agent_id.as_ref()) else {
+        state.ui.command_message = Some(CommandMessage { text: "session start unknown".into(), is_error: true });
+        return;
+    };
+    state.ui.diff_popup = DiffPopupState::Loading(DiffRequest {
+        task_id: task.id.clone(),
+        repo: std::path::PathBuf::from(&state.meta.project_path),
+        since,
+        paths: task.files_modified.clone(),
+    });
+}
+
+/// Start of the session the task's agent ran in, else of the newest active
+/// session, else when the agent started.
+fn task_session_start(state: &AppState, agent_id: Option<&AgentId>) -> Option<chrono::DateTime<chrono::Utc>> {
+    let agent = agent_id.and_then(|id| state.domain.agents.get(id));
+    let agent_session = agent.and_then(|a| a.session_id.as_ref()).and_then(|sid| {
+        state
+            .domain
+            .active_sessions
+            .get(sid)
+            .or_else(|| state.domain.sessions.iter().find(|s| &s.meta.id == sid).map(|s| &s.meta))
+    });
+    agent_session
+        .map(|m| m.timestamp)
+        .or_else(|| state.domain.confirmed_active_sessions().map(|(_, m)| m.timestamp).max())
+        .or_else(|| agent.map(|a| a.started_at))
+}
+
+/// Open the selected step's captured tool output in the pager, else hand the
+/// file it references to the main loop, which suspends the TUI and opens it in `$EDITOR`.
+fn open_selected_file(state: &mut AppState) {
+    let follows_agent = state.ui.view == ViewState::AgentDetail
+        || (state.ui.view == ViewState::Dashboard && state.ui.split_view && state.ui.focus == PanelFocus::Right);
+    let error = |text: String| Some(CommandMessage { text, is_error: true });
+    if !follows_agent {
+        state.ui.command_message = error("select a step in agent detail to open its file".into());
+        return;
+    }
+    // A captured full output wins over the file the step touched
+    if let Some(pager) = tool_outputs::selected_step_pager(state) {
+        state.ui.output_pager = Some(pager);
+        return;
+    }
+    match editor::selected_file_ref(state) {
+        Some(file) if file.path.exists() => state.meta.suspend = Some(Suspend::Editor(file)),
+        Some(file) => state.ui.command_message = error(format!("{} no longer exists", file.path.display())),
+        None => state.ui.command_message = error("selected step references no file".into()),
+    }
+}
+
+fn handle_output_pager_key(state: &mut AppState, key: KeyEvent) {
+    let control = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('o') => state.ui.output_pager = None,
+        code => {
+            let Some(pager) = state.ui.output_pager.as_mut() else {
+                return;
+            };
+            let last = pager.lines.len().saturating_sub(1);
+            pager.scroll = match code {
+                KeyCode::Char('d') if control => pager.scroll.saturating_add(PAGE_JUMP),
+                KeyCode::Char('u') if control => pager.scroll.saturating_sub(PAGE_JUMP),
+                KeyCode::Char('j') | KeyCode::Down => pager.scroll.saturating_add(1),
+                KeyCode::Char('k') | KeyCode::Up => pager.scroll.saturating_sub(1),
+                KeyCode::Char('g') => 0,
+                KeyCode::Char('G') => last,
+                _ => pager.scroll,
+            }
+            .min(last);
+        }
+    }
+}
+
+/// `J`: inspect the selected step's events as a JSON tree.
+fn open_json_inspector(state: &mut AppState) {
+    let follows_agent = state.ui.view == ViewState::AgentDetail
+        || (state.ui.view == ViewState::Dashboard && state.ui.split_view && state.ui.focus == PanelFocus::Right);
+    match json_tree::selected_step_inspector(state).filter(|_| follows_agent) {
+        Some(inspector) => state.ui.json_inspector = Some(inspector),
+        None => {
+            state.ui.command_message =
+                Some(CommandMessage { text: "select a step in agent detail to inspect it".into(), is_error: true });
+        }
+    }
+}
+
+fn handle_json_inspector_key(state: &mut AppState, key: KeyEvent) {
+    let Some(inspector) = state.ui.json_inspector.as_mut() else {
+        return;
+    };
+    // Typing a key search
+    if let Some(search) = inspector.search.as_mut() {
+        match key.code {
+            KeyCode::Esc => inspector.search = None,
+            KeyCode::Enter => {
+                let query = inspector.search.take().unwrap_or_default();
+                if !query.is_empty() {
+                    if !inspector.find_next(&query) {
+                        state.ui.command_message =
+                            Some(CommandMessage { text: format!("no key matches '{}'", query), is_error: true });
+                    }
+                    inspector.query = Some(query);
+                }
+            }
+            KeyCode::Backspace => {
+                search.pop();
+            }
+            KeyCode::Char(c) => search.push(c),
+            _ => {}
+        }
+        return;
+    }
+    let control = key.modifiers.contains(KeyModifiers::CONTROL);
+    let copied = match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('J') => {
+            state.ui.json_inspector = None;
+            return;
+        }
+        KeyCode::Char('d') if control => {
+            inspector.move_cursor(PAGE_JUMP as isize);
+            None
+        }
+        KeyCode::Char('u') if control => {
+            inspector.move_cursor(-(PAGE_JUMP as isize));
+            None
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            inspector.move_cursor(1);
+            None
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            inspector.move_cursor(-1);
+            None
+        }
+        KeyCode::Char('g') => {
+            inspector.cursor = 0;
+            None
+        }
+        KeyCode::Char('G') => {
+            inspector.jump_to_end();
+            None
+        }
+        KeyCode::Enter | KeyCode::Char(' ') => {
+            inspector.toggle();
+            None
+        }
+        KeyCode::Char('h') | KeyCode::Left => {
+            inspector.fold_or_parent();
+            None
+        }
+        KeyCode::Char('l') | KeyCode::Right => {
+            inspector.unfold();
+            None
+        }
+        KeyCode::Char('/') => {
+            inspector.search = Some(String::new());
+            None
+        }
+        KeyCode::Char('n') => {
+            if let Some(query) = inspector.query.clone() {
+                inspector.find_next(&query);
+            }
+            None
+        }
+        KeyCode::Char('y') => inspector.selected_value().map(|value| ("value", value)),
+        KeyCode::Char('Y') => inspector.selected().map(|row| {
+            let path = if row.path.is_empty() { ".".to_string() } else { row.path };
+            ("path", path)
+        }),
+        _ => None,
+    };
+    if let Some((what, text)) = copied {
+        toasts::raise(state, ToastLevel::Info, format!("copied {} ({} bytes)", what, text.len()));
+        state.meta.clipboard = Some(text);
+    }
+}
+
+/// Expand or collapse the step under the cursor in the agent activity tree
+/// (AgentDetail, or the split view's right half). Expanding reads the whole
+/// text of the step's clipped events back from their transcripts.
+fn toggle_selected_step(state: &mut AppState) {
+    use crate::view::components::step_tree::{agent_steps, step_key_at};
+    let Some(agent) = state.followed_agent() else {
+        return;
+    };
+    let steps = agent_steps(state, agent.id.as_str());
+    let Some(key) = step_key_at(&steps, state.ui.scroll_offsets.agent_events) else {
+        return;
+    };
+    let clipped: Vec<SourceRef> = steps
+        .iter()
+        .filter(|step| step.key() == key)
+        .flat_map(|step| std::iter::once(step.head).chain(step.tools.iter().copied()))
+        .filter_map(|event| event.full_text_at.clone())
+        .collect();
+    if state.ui.expanded_steps.remove(&key) {
+        for at in &clipped {
+            state.ui.full_texts.remove(at);
+        }
+        return;
+    }
+    state.ui.expanded_steps.insert(key);
+    for at in clipped {
+        match crate::watcher::full_text::read_full_text(&at) {
+            Ok(Some(text)) => {
+                state.ui.full_texts.insert(at, text);
+            }
+            Ok(None) => {}
+            Err(e) => toasts::raise(state, ToastLevel::Warn, format!("full text of {}: {}", at.path.display(), e)),
+        }
     }
 }
 
@@ -701,7 +1754,7 @@ fn go_back(state: &mut AppState) {
             state.ui.prompt_popup = PromptPopupState::Closed;
             state.ui.view = ViewState::Sessions;
         }
-        ViewState::TokenDashboard => {
+        ViewState::TokenDashboard | ViewState::Review | ViewState::Timeline => {
             state.ui.view = ViewState::Dashboard;
         }
         ViewState::Dashboard => {}
@@ -709,7 +1762,7 @@ fn go_back(state: &mut AppState) {
 }
 
 fn start_filter(state: &mut AppState) {
-    state.ui.filter = Some(String::new());
+    filters::start(&mut state.ui);
 }
 
 fn toggle_help(state: &mut AppState) {
@@ -718,6 +1771,11 @@ fn toggle_help(state: &mut AppState) {
 
 fn toggle_auto_scroll(state: &mut AppState) {
     state.ui.auto_scroll = !state.ui.auto_scroll;
+    // Following resumes at the newest event (events render newest first)
+    if state.ui.auto_scroll {
+        state.ui.scroll_offsets.event_stream = 0;
+        state.ui.scroll_offsets.agent_events = 0;
+    }
 }
 
 fn show_agent_popup(state: &mut AppState) {
@@ -736,17 +1794,11 @@ fn show_agent_popup(state: &mut AppState) {
             }
         }
         ViewState::AgentDetail => {
-            if state.ui.selected_agent_index.is_some() {
-                state.ui.prompt_popup = PromptPopupState::Open { scroll: 0 };
-            }
+            state.ui.show_agent_popup = agent_groups::selected_agent(state).map(|agent| agent.id.clone());
         }
         ViewState::SessionDetail => {
-            // Index 0 = Main (no prompt), index >= 1 = agent
-            if let Some(idx) = state.ui.selected_session_agent_index {
-                if idx >= 1 {
-                    state.ui.prompt_popup = PromptPopupState::Open { scroll: 0 };
-                }
-            }
+            // Index 0 = Main (no agent), index >= 1 = agent
+            state.ui.show_agent_popup = crate::view::session_detail::selected_session_agent_id(state);
         }
         _ => {}
     }
@@ -909,6 +1961,25 @@ mod tests {
         assert!(!state.ui.auto_scroll);
     }
 
+    #[test]
+    fn event_stream_scroll_stays_within_rendered_content() {
+        let mut state = AppState::new();
+        state.ui.focus = PanelFocus::Right;
+        state.ui.scroll_limits.event_stream.set(Some(30));
+
+        handle_key(&mut state, key(KeyCode::Char('G')));
+        assert_eq!(state.ui.scroll_offsets.event_stream, 30);
+        handle_key(&mut state, key(KeyCode::Char('j')));
+        assert_eq!(state.ui.scroll_offsets.event_stream, 30);
+        handle_key(&mut state, key(KeyCode::Char('k')));
+        assert_eq!(state.ui.scroll_offsets.event_stream, 29);
+
+        // Content shrank (e.g. a filter was applied): the next scroll starts from the new end
+        state.ui.scroll_limits.event_stream.set(Some(5));
+        handle_key(&mut state, key(KeyCode::Char('k')));
+        assert_eq!(state.ui.scroll_offsets.event_stream, 4);
+    }
+
     #[test]
     fn j_moves_agent_selection_down() {
         let mut state = AppState::new();
@@ -967,6 +2038,33 @@ mod tests {
         assert_eq!(state.ui.scroll_offsets.agent_events, 1);
     }
 
+    #[test]
+    fn enter_toggles_step_under_cursor_in_agent_detail() {
+        use crate::model::{TranscriptEvent, TranscriptEventKind};
+
+        let mut state = AppState::new();
+        state.ui.view = ViewState::AgentDetail;
+        state.ui.focus = PanelFocus::Right;
+        state.domain.agents.insert(AgentId::new("a01"), Agent::new("a01", Utc::now()));
+        state.recompute_sorted_keys();
+        state.ui.selected_agent_index = Some(0);
+        let older = Utc::now() - chrono::Duration::seconds(10);
+        for (ts, content) in [(older, "first"), (Utc::now(), "second")] {
+            state.domain.events.push_back(
+                TranscriptEvent::new(ts, TranscriptEventKind::AssistantMessage { content: content.into() })
+                    .with_agent("a01"),
+            );
+        }
+
+        // Cursor 1 = second-newest step
+        state.ui.scroll_offsets.agent_events = 1;
+        handle_key(&mut state, key(KeyCode::Enter));
+        assert!(state.ui.expanded_steps.contains(&older));
+
+        handle_key(&mut state, key(KeyCode::Enter));
+        assert!(state.ui.expanded_steps.is_empty());
+    }
+
     #[test]
     fn k_scrolls_agent_events_right_panel() {
         let mut state = AppState::new();
@@ -1237,6 +2335,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::useless_vec)]
     fn multiple_tasks_drill_down_selects_correct_agent() {
         let now = Utc::now();
         let mut state = AppState::new();
@@ -1326,24 +2425,36 @@ mod tests {
     }
 
     #[test]
-    fn p_opens_prompt_popup_in_agent_detail() {
+    fn p_opens_agent_popup_in_agent_detail_and_enter_shows_prompt() {
         let mut state = AppState::new();
         state.ui.view = ViewState::AgentDetail;
         state.domain.agents.insert(AgentId::new("a01"), Agent::new("a01", Utc::now()));
+        state.recompute_sorted_keys();
         state.ui.selected_agent_index = Some(0);
 
         handle_key(&mut state, key(KeyCode::Char('p')));
+        assert_eq!(state.ui.show_agent_popup, Some(AgentId::new("a01")));
+
+        handle_key(&mut state, key(KeyCode::Enter));
+        assert_eq!(state.ui.show_agent_popup, None);
         assert!(state.ui.prompt_popup.is_open());
     }
 
     #[test]
-    fn p_opens_prompt_popup_in_session_detail() {
+    fn p_opens_agent_popup_in_session_detail() {
+        use crate::model::SessionArchive;
+
         let mut state = AppState::new();
         state.ui.view = ViewState::SessionDetail;
+        let meta = SessionMeta::new("s1", Utc::now(), "/proj".to_string());
+        let mut archive = SessionArchive::new(meta.clone());
+        archive.agents.insert(AgentId::new("a01"), Agent::new("a01", Utc::now()));
+        state.domain.sessions = vec![ArchivedSession::new(meta, std::path::PathBuf::new()).with_data(archive)];
+        state.ui.selected_session_id = Some(crate::model::SessionId::new("s1"));
         state.ui.selected_session_agent_index = Some(1); // index 0 = Main (no popup)
 
         handle_key(&mut state, key(KeyCode::Char('p')));
-        assert!(state.ui.prompt_popup.is_open());
+        assert_eq!(state.ui.show_agent_popup, Some(AgentId::new("a01")));
     }
 
     #[test]
@@ -1354,6 +2465,7 @@ mod tests {
 
         handle_key(&mut state, key(KeyCode::Char('p')));
         assert!(!state.ui.prompt_popup.is_open());
+        assert_eq!(state.ui.show_agent_popup, None);
     }
 
     #[test]
@@ -0,0 +1,344 @@
+//! Postmortem notes for archived sessions (SessionDetail Notes tab).
+//!
+//! `e` on the Notes tab opens the built-in editor on the session's notes, or on
+//! `POSTMORTEM_TEMPLATE` when there are none yet. Ctrl+S writes the text into the
+//! archive file (see `session::save_notes`), Esc discards the edit.
+
+use crate::app::compat::{self, Compat};
+use crate::app::toasts;
+use crate::app::{AppState, CommandMessage};
+use crate::model::SessionId;
+use crate::session;
+
+/// Starting point for notes on a session that has none.
+pub const POSTMORTEM_TEMPLATE: &str = "\
+## Summary
+
+
+## What went well
+
+
+## What went wrong
+
+
+## Follow-ups
+
+";
+
+/// Multi-line text being edited, with a cursor kept on a char boundary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotesEditor {
+    /// Archived session the notes belong to (immune to list reordering)
+    pub session_id: SessionId,
+    pub text: String,
+    /// Byte offset into `text`
+    pub cursor: usize,
+}
+
+impl NotesEditor {
+    /// Editor on `text` with the cursor at the end.
+    pub fn new(session_id: SessionId, text: String) -> Self {
+        let cursor = text.len();
+        Self { session_id, text, cursor }
+    }
+
+    pub fn insert(&mut self, c: char) {
+        self.text.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    pub fn backspace(&mut self) {
+        if let Some(c) = self.text[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+            self.text.remove(self.cursor);
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if self.cursor < self.text.len() {
+            self.text.remove(self.cursor);
+        }
+    }
+
+    pub fn left(&mut self) {
+        if let Some(c) = self.text[..self.cursor].chars().next_back() {
+            self.cursor -= c.len_utf8();
+        }
+    }
+
+    pub fn right(&mut self) {
+        if let Some(c) = self.text[self.cursor..].chars().next() {
+            self.cursor += c.len_utf8();
+        }
+    }
+
+    /// Start of the cursor's line.
+    pub fn home(&mut self) {
+        self.cursor = self.line_start(self.cursor);
+    }
+
+    /// End of the cursor's line.
+    pub fn end(&mut self) {
+        self.cursor = self.text[self.cursor..].find('\n').map_or(self.text.len(), |i| self.cursor + i);
+    }
+
+    /// Previous line, same column where it fits.
+    pub fn up(&mut self) {
+        let start = self.line_start(self.cursor);
+        if start == 0 {
+            self.cursor = 0;
+            return;
+        }
+        let column = self.text[start..self.cursor].chars().count();
+        let prev_start = self.line_start(start - 1);
+        self.cursor = self.offset_in_line(prev_start, column);
+    }
+
+    /// Next line, same column where it fits.
+    pub fn down(&mut self) {
+        let start = self.line_start(self.cursor);
+        let column = self.text[start..self.cursor].chars().count();
+        match self.text[self.cursor..].find('\n') {
+            Some(i) => self.cursor = self.offset_in_line(self.cursor + i + 1, column),
+            None => self.cursor = self.text.len(),
+        }
+    }
+
+    /// Cursor as (line, column) in chars, for rendering.
+    pub fn cursor_position(&self) -> (usize, usize) {
+        let before = &self.text[..self.cursor];
+        let line = before.matches('\n').count();
+        let column = before[self.line_start(self.cursor)..].chars().count();
+        (line, column)
+    }
+
+    fn line_start(&self, offset: usize) -> usize {
+        self.text[..offset].rfind('\n').map_or(0, |i| i + 1)
+    }
+
+    /// Byte offset of `column` chars into the line starting at `start`, clamped to the line end.
+    fn offset_in_line(&self, start: usize, column: usize) -> usize {
+        self.text[start..]
+            .char_indices()
+            .take_while(|(_, c)| *c != '\n')
+            .nth(column)
+            .map_or_else(
+                || self.text[start..].find('\n').map_or(self.text.len(), |i| start + i),
+                |(i, _)| start + i,
+            )
+    }
+}
+
+/// Open the editor on the notes of the session shown in SessionDetail.
+///
+/// # Returns
+/// Why notes can't be edited (live session, archive not loaded or from
+/// another version), if so.
+pub fn open_editor(state: &mut AppState) -> Result<(), String> {
+    let sid = crate::view::session_detail::get_selected_session_data(state)
+        .map(|d| d.meta.id.clone())
+        .ok_or("no session selected")?;
+    if state.domain.active_sessions.contains_key(&sid) {
+        return Err("notes can be written once the session is archived".to_string());
+    }
+    let data = state
+        .domain
+        .sessions
+        .iter()
+        .find(|s| s.meta.id == sid)
+        .and_then(|s| s.data.as_ref())
+        .ok_or("session is still loading")?;
+    match compat::check(data.written_by.as_deref()) {
+        Compat::Compatible => {}
+        Compat::Older(v) => return Err(format!("archive written by loom-tui {} is read-only; :migrate it first", v)),
+        Compat::Newer(v) => return Err(format!("archive written by loom-tui {} is read-only", v)),
+    }
+    let text = data.notes.clone().unwrap_or_else(|| POSTMORTEM_TEMPLATE.to_string());
+    state.ui.notes_editor = Some(NotesEditor::new(sid, text));
+    Ok(())
+}
+
+/// Close the editor and write its text into the session's archive. Blank text
+/// removes the notes. An archive created during this run and never written to
+/// disk is saved in full into the archive directory.
+/// I/O: writes the archive file. On failure the editor stays open so nothing is lost.
+///
+/// # Returns
+/// Toast describing the outcome.
+pub fn save_notes(state: &mut AppState) -> CommandMessage {
+    let Some(editor) = state.ui.notes_editor.take() else {
+        return CommandMessage { text: "no notes open".into(), is_error: true };
+    };
+    let trimmed = editor.text.trim_end();
+    let notes = (!trimmed.trim().is_empty()).then(|| format!("{trimmed}\n"));
+
+    let archive_dir = state.meta.archive_dir.clone();
+    let result = match state.domain.sessions.iter_mut().find(|s| s.meta.id == editor.session_id) {
+        None => Err("session is no longer in the archive list".to_string()),
+        Some(archived) if archived.path.as_os_str().is_empty() => {
+            match (archive_dir, archived.data.as_mut()) {
+                (Some(dir), Some(data)) => {
+                    data.notes = notes.clone();
+                    let path = session::archive_path(&dir, &archived.meta);
+                    session::save_session_guarded(&path, data, archived.stamp)
+                        .map(|report| {
+                            archived.path = report.path;
+                            archived.stamp = report.stamp;
+                            report.clobbered
+                        })
+                        .map_err(|e| e.to_string())
+                }
+                _ => Err("no archive directory to save to".to_string()),
+            }
+        }
+        Some(archived) => session::save_notes(&archived.path, notes.as_deref(), archived.stamp)
+            .map(|(report, events)| {
+                if let Some(data) = archived.data.as_mut() {
+                    data.notes = notes.clone();
+                }
+                archived.stamp = report.stamp;
+                // The rewrite moved the events; older pages are read from where they are now
+                if let Some(paging) = archived.paging.as_mut() {
+                    paging.spans = events.spans;
+                    paging.file_len = events.file_len;
+                }
+                report.clobbered
+            })
+            .map_err(|e| e.to_string()),
+    };
+
+    match result {
+        Ok(clobbered) => {
+            if clobbered {
+                toasts::warn_clobbered(state, &editor.session_id);
+            }
+            CommandMessage { text: format!("notes saved for {}", editor.session_id), is_error: false }
+        }
+        Err(e) => {
+            state.ui.notes_editor = Some(editor);
+            CommandMessage { text: format!("notes not saved: {e}"), is_error: true }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::ViewState;
+    use crate::model::{ArchivedSession, SessionArchive, SessionMeta};
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn editor(text: &str) -> NotesEditor {
+        NotesEditor::new(SessionId::new("s1"), text.to_string())
+    }
+
+    #[test]
+    fn editing_keeps_cursor_on_char_boundaries() {
+        let mut e = editor("né");
+        e.backspace();
+        assert_eq!(e.text, "n");
+        e.insert('ü');
+        e.left();
+        e.insert('\n');
+        assert_eq!(e.text, "n\nü");
+        assert_eq!(e.cursor_position(), (1, 0));
+        e.right();
+        e.delete();
+        assert_eq!(e.text, "n\nü");
+    }
+
+    #[test]
+    fn up_and_down_keep_column_where_it_fits() {
+        let mut e = editor("abcdef\nxy\nlonger line");
+        assert_eq!(e.cursor_position(), (2, 11));
+        e.up();
+        assert_eq!(e.cursor_position(), (1, 2));
+        e.up();
+        assert_eq!(e.cursor_position(), (0, 2));
+        e.end();
+        assert_eq!(e.cursor_position(), (0, 6));
+        e.down();
+        e.down();
+        assert_eq!(e.cursor_position(), (2, 2));
+        e.home();
+        e.up();
+        e.up();
+        e.up();
+        assert_eq!(e.cursor, 0);
+    }
+
+    fn state_with_archive(path: PathBuf) -> AppState {
+        let mut state = AppState::new();
+        let meta = SessionMeta::new("s1", Utc::now(), "/proj".to_string());
+        let data = SessionArchive::new(meta.clone());
+        state.domain.sessions = vec![ArchivedSession::new(meta, path).with_data(data)];
+        state.ui.view = ViewState::SessionDetail;
+        state.ui.selected_session_id = Some(SessionId::new("s1"));
+        state
+    }
+
+    #[test]
+    fn open_uses_template_then_save_writes_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = state_with_archive(PathBuf::new());
+        state.meta.archive_dir = Some(dir.path().to_path_buf());
+
+        open_editor(&mut state).unwrap();
+        assert_eq!(state.ui.notes_editor.as_ref().unwrap().text, POSTMORTEM_TEMPLATE);
+
+        state.ui.notes_editor.as_mut().unwrap().text = "flaky test\n\n\n".into();
+        let message = save_notes(&mut state);
+        assert!(!message.is_error, "{}", message.text);
+        assert!(state.ui.notes_editor.is_none());
+
+        let saved_path = state.domain.sessions[0].path.clone();
+        assert!(saved_path.starts_with(dir.path()));
+        assert_eq!(session::load_session(&saved_path).unwrap().notes.as_deref(), Some("flaky test\n"));
+        assert_eq!(state.domain.sessions[0].data.as_ref().unwrap().notes.as_deref(), Some("flaky test\n"));
+    }
+
+    #[test]
+    fn saving_over_another_instances_write_warns() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.json");
+        let mut state = state_with_archive(path.clone());
+        let ours = state.domain.sessions[0].data.clone().unwrap();
+        state.domain.sessions[0].stamp = session::save_session_guarded(&path, &ours, None).unwrap().stamp;
+
+        // Our own earlier save: no warning
+        open_editor(&mut state).unwrap();
+        assert!(!save_notes(&mut state).is_error);
+        assert!(state.ui.toasts.is_empty());
+
+        // Another instance rewrites the archive in between
+        let theirs = SessionArchive::new(SessionMeta::new("s1", Utc::now(), "/someone/else".to_string()));
+        session::save_session(&path, &theirs).unwrap();
+        open_editor(&mut state).unwrap();
+        assert!(!save_notes(&mut state).is_error);
+
+        let toast = state.ui.toasts.iter().last().unwrap();
+        assert_eq!(toast.level, toasts::ToastLevel::Warn);
+        assert!(toast.text.contains("changed by another loom-tui"));
+        assert_eq!(state.meta.errors.back(), Some(&toast.text));
+    }
+
+    #[test]
+    fn failed_save_keeps_editor_open() {
+        let mut state = state_with_archive(PathBuf::from("/nonexistent/dir/s1.json"));
+        open_editor(&mut state).unwrap();
+        let message = save_notes(&mut state);
+        assert!(message.is_error);
+        assert!(state.ui.notes_editor.is_some());
+    }
+
+    #[test]
+    fn live_sessions_cannot_have_notes_yet() {
+        let mut state = AppState::new();
+        let mut meta = SessionMeta::new("s1", Utc::now(), "/proj".to_string());
+        meta.confirmed = true;
+        state.domain.active_sessions.insert(SessionId::new("s1"), meta);
+        state.ui.selected_session_id = Some(SessionId::new("s1"));
+        assert!(open_editor(&mut state).unwrap_err().contains("archived"));
+    }
+}
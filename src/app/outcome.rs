@@ -0,0 +1,241 @@
+//! Session outcome classification: whether an ended session got its work done.
+//! What the verdict is judged on: `docs/design.md`, "Session outcomes".
+
+use crate::model::{
+    AgentId, MessageKind, SessionArchive, SessionMeta, SessionOutcome, SessionStatus, TaskStatus, TranscriptEventKind,
+};
+
+/// What an ended session's outcome is judged on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutcomeEvidence {
+    pub status: SessionStatus,
+    /// Tasks run by the session's agents (the whole graph for a run's
+    /// orchestrator, whose agents the graph doesn't name)
+    pub tasks: usize,
+    pub tasks_completed: usize,
+    pub tasks_failed: usize,
+    pub agents: usize,
+    /// Agents whose last tool call failed, or whose task did
+    pub failed_agents: usize,
+    /// Events of the main thread: prompts, replies, tool calls and results
+    pub main_events: usize,
+    /// The main thread's last event is a reply, not a prompt or a tool call
+    /// still waiting on its result
+    pub answered: bool,
+}
+
+impl OutcomeEvidence {
+    /// Pure function: the evidence in `archive`.
+    pub fn of(archive: &SessionArchive) -> Self {
+        let meta = &archive.meta;
+        let agent_tasks: Vec<&TaskStatus> = archive
+            .task_graph
+            .iter()
+            .flat_map(|graph| graph.flat_tasks())
+            .filter(|task| task.agent_id.as_ref().is_some_and(|id| archive.agents.contains_key(id)))
+            .map(|task| &task.status)
+            .collect();
+        let tasks: Vec<&TaskStatus> = match (&archive.task_graph, agent_tasks.is_empty(), &meta.loom_plan_id) {
+            (Some(graph), true, Some(_)) => graph.flat_tasks().map(|task| &task.status).collect(),
+            _ => agent_tasks,
+        };
+        let failed_tasks: Vec<&AgentId> = archive
+            .task_graph
+            .iter()
+            .flat_map(|graph| graph.flat_tasks())
+            .filter(|task| matches!(task.status, TaskStatus::Failed { .. }))
+            .filter_map(|task| task.agent_id.as_ref())
+            .collect();
+        let failed_agents = archive
+            .agents
+            .values()
+            .filter(|agent| {
+                if failed_tasks.contains(&&agent.id) {
+                    return true;
+                }
+                let last_call = agent.messages.iter().rev().find_map(|message| match &message.kind {
+                    MessageKind::Tool(call) => Some(call),
+                    MessageKind::Reasoning { .. } => None,
+                });
+                last_call.is_some_and(|call| call.success == Some(false))
+            })
+            .count();
+        let main: Vec<&TranscriptEventKind> = archive
+            .events
+            .iter()
+            .filter(|event| event.agent_id.is_none())
+            .map(|event| &event.kind)
+            .filter(|kind| {
+                matches!(
+                    kind,
+                    TranscriptEventKind::UserMessage
+                        | TranscriptEventKind::AssistantMessage { .. }
+                        | TranscriptEventKind::ToolUse { .. }
+                        | TranscriptEventKind::ToolResult { .. }
+                )
+            })
+            .collect();
+        Self {
+            status: meta.status.clone(),
+            tasks: tasks.len(),
+            tasks_completed: tasks.iter().filter(|status| matches!(status, TaskStatus::Completed)).count(),
+            tasks_failed: tasks.iter().filter(|status| matches!(status, TaskStatus::Failed { .. })).count(),
+            agents: archive.agents.len(),
+            failed_agents,
+            main_events: main.len(),
+            answered: matches!(main.last(), Some(TranscriptEventKind::AssistantMessage { .. })),
+        }
+    }
+}
+
+/// Pure function: the outcome `evidence` points to.
+///
+/// With tasks, they decide: all done is a success, some done a partial
+/// success, none done but some failed a failure, none done or failed an
+/// abort. Without tasks the main thread does: a session that stopped before
+/// answering was aborted, one whose agents all failed failed, and one that
+/// answered succeeded, partly when some of its agents failed.
+pub fn classify(evidence: &OutcomeEvidence) -> SessionOutcome {
+    let e = evidence;
+    match e.status {
+        SessionStatus::Active => return SessionOutcome::Unknown,
+        SessionStatus::Failed => return SessionOutcome::Failed,
+        SessionStatus::Completed | SessionStatus::Cancelled => {}
+    }
+    if e.tasks > 0 {
+        return if e.tasks_completed == e.tasks {
+            SessionOutcome::Success
+        } else if e.tasks_completed > 0 {
+            SessionOutcome::PartialSuccess
+        } else if e.tasks_failed > 0 {
+            SessionOutcome::Failed
+        } else {
+            SessionOutcome::Aborted
+        };
+    }
+    if e.main_events == 0 {
+        SessionOutcome::Unknown
+    } else if e.agents > 0 && e.failed_agents == e.agents {
+        SessionOutcome::Failed
+    } else if !e.answered {
+        SessionOutcome::Aborted
+    } else if e.failed_agents > 0 {
+        SessionOutcome::PartialSuccess
+    } else {
+        SessionOutcome::Success
+    }
+}
+
+/// Pure function: the outcome `meta` is listed, filtered and counted under.
+pub fn listed(meta: &SessionMeta) -> Option<SessionOutcome> {
+    match meta.status {
+        SessionStatus::Active => None,
+        _ => Some(meta.outcome.unwrap_or(SessionOutcome::Unknown)),
+    }
+}
+
+/// Sessions list outcome filter step: all → each outcome in turn → all.
+pub fn next_filter(filter: Option<SessionOutcome>) -> Option<SessionOutcome> {
+    match filter {
+        None => Some(SessionOutcome::ALL[0]),
+        Some(outcome) => SessionOutcome::ALL.iter().skip_while(|o| **o != outcome).nth(1).copied(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Agent, AgentMessage, Task, TaskGraph, ToolCall, ToolName, TranscriptEvent, Wave};
+    use chrono::Utc;
+
+    fn evidence(status: SessionStatus) -> OutcomeEvidence {
+        OutcomeEvidence {
+            status,
+            tasks: 0,
+            tasks_completed: 0,
+            tasks_failed: 0,
+            agents: 0,
+            failed_agents: 0,
+            main_events: 4,
+            answered: true,
+        }
+    }
+
+    fn with_tasks(tasks: usize, completed: usize, failed: usize) -> OutcomeEvidence {
+        OutcomeEvidence { tasks, tasks_completed: completed, tasks_failed: failed, ..evidence(SessionStatus::Completed) }
+    }
+
+    #[test]
+    fn tasks_decide_when_there_are_any() {
+        assert_eq!(classify(&with_tasks(3, 3, 0)), SessionOutcome::Success);
+        assert_eq!(classify(&with_tasks(3, 2, 1)), SessionOutcome::PartialSuccess);
+        assert_eq!(classify(&with_tasks(3, 1, 0)), SessionOutcome::PartialSuccess);
+        assert_eq!(classify(&with_tasks(3, 0, 2)), SessionOutcome::Failed);
+        assert_eq!(classify(&with_tasks(3, 0, 0)), SessionOutcome::Aborted);
+        // A run that finished its tasks succeeded even if it then went idle
+        let idle = OutcomeEvidence { status: SessionStatus::Cancelled, answered: false, ..with_tasks(2, 2, 0) };
+        assert_eq!(classify(&idle), SessionOutcome::Success);
+    }
+
+    #[test]
+    fn without_tasks_the_main_thread_and_agents_decide() {
+        assert_eq!(classify(&evidence(SessionStatus::Completed)), SessionOutcome::Success);
+        let unanswered = OutcomeEvidence { answered: false, ..evidence(SessionStatus::Cancelled) };
+        assert_eq!(classify(&unanswered), SessionOutcome::Aborted);
+        let some_failed = OutcomeEvidence { agents: 3, failed_agents: 1, ..evidence(SessionStatus::Completed) };
+        assert_eq!(classify(&some_failed), SessionOutcome::PartialSuccess);
+        let all_failed = OutcomeEvidence { agents: 2, failed_agents: 2, ..evidence(SessionStatus::Completed) };
+        assert_eq!(classify(&all_failed), SessionOutcome::Failed);
+        let silent = OutcomeEvidence { main_events: 0, answered: false, ..evidence(SessionStatus::Completed) };
+        assert_eq!(classify(&silent), SessionOutcome::Unknown);
+    }
+
+    #[test]
+    fn status_overrides() {
+        assert_eq!(classify(&evidence(SessionStatus::Failed)), SessionOutcome::Failed);
+        assert_eq!(classify(&evidence(SessionStatus::Active)), SessionOutcome::Unknown);
+    }
+
+    #[test]
+    fn evidence_covers_the_sessions_own_tasks_agents_and_last_reply() {
+        let now = Utc::now();
+        let failing_call = ToolCall::new(ToolName::new("Bash"), "cargo test".into()).with_result("exit 1".into(), false);
+        let mut a1 = Agent::new("a1", now);
+        a1.messages.push(AgentMessage::tool(now, failing_call));
+        let a2 = Agent::new("a2", now);
+        let mut t1 = Task::new("T1", "one".into(), TaskStatus::Completed);
+        t1.agent_id = Some("a1".into());
+        let mut t2 = Task::new("T2", "two".into(), TaskStatus::Failed { reason: "tests".into(), retry_count: 0 });
+        t2.agent_id = Some("a2".into());
+        let mut other = Task::new("T3", "someone else's".into(), TaskStatus::Pending);
+        other.agent_id = Some("a9".into());
+        let graph = TaskGraph::new(vec![Wave::new(1, vec![t1, t2, other])]);
+        let archive = SessionArchive::new(SessionMeta::new("s1", now, "/p".into()).with_status(SessionStatus::Completed))
+            .with_task_graph(graph)
+            .with_agents([(a1.id.clone(), a1), (a2.id.clone(), a2)].into())
+            .with_events(vec![
+                TranscriptEvent::new(now, TranscriptEventKind::UserMessage),
+                TranscriptEvent::new(now, TranscriptEventKind::AssistantMessage { content: "done".into() }),
+                TranscriptEvent::new(now, TranscriptEventKind::UserMessage).with_agent("a1"),
+            ]);
+
+        let evidence = OutcomeEvidence::of(&archive);
+        assert_eq!((evidence.tasks, evidence.tasks_completed, evidence.tasks_failed), (2, 1, 1));
+        assert_eq!((evidence.agents, evidence.failed_agents), (2, 2));
+        assert_eq!(evidence.main_events, 2);
+        assert!(evidence.answered);
+        assert_eq!(classify(&evidence), SessionOutcome::PartialSuccess);
+    }
+
+    #[test]
+    fn the_filter_cycles_through_every_outcome() {
+        let mut filter = None;
+        let mut seen = Vec::new();
+        for _ in 0..=SessionOutcome::ALL.len() {
+            filter = next_filter(filter);
+            seen.push(filter);
+        }
+        assert_eq!(seen.last(), Some(&None));
+        assert_eq!(seen[..5].iter().flatten().copied().collect::<Vec<_>>(), SessionOutcome::ALL);
+    }
+}
@@ -0,0 +1,251 @@
+//! Run pacing advisor.
+//!
+//! A run held up by provider limits looks just like one held up by a bug:
+//! agents stop producing events. The advisor reads the API health record (see
+//! `app::api_health`) next to recent token throughput and says which it is —
+//! "provider returning 429s — 3 agents likely throttled, consider pausing the
+//! wave" — in the Dashboard's `pacing` panel. When the advice turns to
+//! throttling after replay it is raised once as a warning toast.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::app::api_health::ApiHealth;
+use crate::app::toasts::{self, ToastLevel};
+use crate::app::AppState;
+use crate::model::{Agent, AgentId, ApiErrorKind};
+
+/// Recent stretch whose throughput is compared against the baseline.
+pub const RECENT: Duration = Duration::minutes(2);
+
+/// Stretch, recent one included, the baseline throughput is taken over.
+pub const BASELINE: Duration = Duration::minutes(10);
+
+/// A running agent quiet for this long while the provider throttles is
+/// probably waiting on a retry.
+pub const QUIET: Duration = Duration::seconds(60);
+
+/// Errors that mean the provider is pacing requests: retried, not fatal.
+const THROTTLING: [ApiErrorKind; 2] = [ApiErrorKind::RateLimit, ApiErrorKind::Overloaded];
+
+/// Token totals reported per agent, kept as increments over time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Throughput {
+    totals: HashMap<AgentId, u64>,
+    /// Tokens added at each report, oldest first, trimmed to `BASELINE`
+    added: VecDeque<(DateTime<Utc>, u64)>,
+}
+
+impl Throughput {
+    /// Record `agent`'s running token total as of `at`. The first report of an
+    /// agent only sets its starting point: it carries everything spent before.
+    pub fn observe(&mut self, agent: &AgentId, total: u64, at: DateTime<Utc>) {
+        let previous = self.totals.insert(agent.clone(), total);
+        let added = previous.map_or(0, |previous| total.saturating_sub(previous));
+        if added > 0 {
+            let idx = self.added.partition_point(|(t, _)| *t <= at);
+            self.added.insert(idx, (at, added));
+        }
+        self.expire(at);
+    }
+
+    /// Forget increments older than `BASELINE` as of `now`.
+    pub fn expire(&mut self, now: DateTime<Utc>) {
+        while self.added.front().is_some_and(|(t, _)| *t < now - BASELINE) {
+            self.added.pop_front();
+        }
+    }
+
+    /// Pure function: tokens per minute over the `span` before `now`.
+    pub fn per_minute(&self, now: DateTime<Utc>, span: Duration) -> u64 {
+        let tokens: u64 = self.added.iter().filter(|(t, _)| *t >= now - span && *t <= now).map(|(_, n)| n).sum();
+        tokens / span.num_minutes().max(1) as u64
+    }
+}
+
+/// Pacing state kept between ticks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pacing {
+    pub throughput: Throughput,
+    /// The last advice was throttling (so its toast is raised once)
+    pub throttled: bool,
+}
+
+/// What the advisor makes of the last few minutes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Advice {
+    /// The provider is pacing requests
+    pub throttled: bool,
+    pub headline: String,
+    /// Supporting figures, one per line
+    pub details: Vec<String>,
+    /// Agents likely waiting on the provider
+    pub waiting: Vec<AgentId>,
+}
+
+/// Pure function: advice from the API errors in `health`, `throughput` and the
+/// running `agents` as of `now`.
+///
+/// While the provider returns 429s or 529s, an agent is likely throttled if it
+/// hit one of them or has been quiet for `QUIET`. Without them, a throughput
+/// drop points away from the provider.
+pub fn advise(health: &ApiHealth, throughput: &Throughput, agents: &BTreeMap<AgentId, Agent>, now: DateTime<Utc>) -> Advice {
+    let counts = health.counts();
+    let count = |kind| counts.get(&kind).copied().unwrap_or(0);
+    let (rate_limited, overloaded, context) =
+        (count(ApiErrorKind::RateLimit), count(ApiErrorKind::Overloaded), count(ApiErrorKind::ContextLength));
+    let recent = throughput.per_minute(now, RECENT);
+    let baseline = throughput.per_minute(now, BASELINE);
+
+    let mut details = Vec::new();
+    if rate_limited + overloaded + context > 0 {
+        details.push(format!(
+            "last {}: {} rate limited (429) · {} overloaded (529) · {} context limit",
+            health.window_label(),
+            rate_limited,
+            overloaded,
+            context
+        ));
+    }
+    details.push(format!("throughput: {} tok/min now, {} tok/min over {}m", recent, baseline, BASELINE.num_minutes()));
+
+    let running: Vec<&Agent> = agents.values().filter(|agent| agent.finished_at.is_none()).collect();
+    let waiting: Vec<AgentId> = if rate_limited + overloaded > 0 {
+        let hit = health.agents(&THROTTLING);
+        running
+            .iter()
+            .filter(|agent| {
+                let last_seen = agent.messages.last().map_or(agent.started_at, |m| m.timestamp);
+                hit.contains(&agent.id) || now - last_seen >= QUIET
+            })
+            .map(|agent| agent.id.clone())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    if !waiting.is_empty() {
+        details.push(format!("waiting: {}", waiting.iter().map(AgentId::as_str).collect::<Vec<_>>().join(", ")));
+    }
+    let agents_phrase = |n: usize, what: &str| match n {
+        1 => format!("1 agent {}", what),
+        n => format!("{} agents {}", n, what),
+    };
+    let waiting_phrase = |what: &str| match waiting.len() {
+        0 => String::new(),
+        n => format!("{}, ", agents_phrase(n, what)),
+    };
+
+    let headline = if rate_limited > 0 {
+        format!("provider returning 429s — {}consider pausing the wave", waiting_phrase("likely throttled"))
+    } else if overloaded > 0 {
+        format!("provider overloaded (529s) — {}consider pausing the wave", waiting_phrase("retrying"))
+    } else if context > 0 {
+        let stuck = health.agents(&[ApiErrorKind::ContextLength]).len().max(1);
+        format!("{} — not a provider limit; compact or split the task", agents_phrase(stuck, "hit the context limit"))
+    } else if !running.is_empty() && baseline > 0 && recent * 4 < baseline {
+        "throughput dropped with no API errors — the stall is not provider limits".to_string()
+    } else {
+        "no provider limits hit — pace is fine".to_string()
+    };
+    Advice { throttled: rate_limited + overloaded > 0, headline, details, waiting }
+}
+
+/// Tick: age out throughput and, after replay, raise a warning toast when
+/// the advice turns to throttling.
+pub fn tick(state: &mut AppState, now: DateTime<Utc>) {
+    state.domain.pacing.throughput.expire(now);
+    let advice = advise(&state.domain.api_health, &state.domain.pacing.throughput, &state.domain.agents, now);
+    let turned = advice.throttled && !state.domain.pacing.throttled;
+    state.domain.pacing.throttled = advice.throttled;
+    if turned && state.meta.replay_complete {
+        toasts::raise(state, ToastLevel::Warn, advice.headline);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::AgentMessage;
+
+    fn t0() -> DateTime<Utc> {
+        "2026-03-18T10:00:00Z".parse().unwrap()
+    }
+
+    fn agents(now: DateTime<Utc>, quiet_for: &[(&str, i64)]) -> BTreeMap<AgentId, Agent> {
+        quiet_for
+            .iter()
+            .map(|(id, secs)| {
+                let mut agent = Agent::new(*id, now - Duration::minutes(30));
+                agent.messages.push(AgentMessage::reasoning(now - Duration::seconds(*secs), "working".into()));
+                (agent.id.clone(), agent)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn throughput_counts_what_was_added_since_each_agents_first_report() {
+        let mut throughput = Throughput::default();
+        let a1 = AgentId::new("a1");
+        throughput.observe(&a1, 50_000, t0());
+        throughput.observe(&a1, 56_000, t0() + Duration::minutes(1));
+        throughput.observe(&AgentId::new("a2"), 9_000, t0() + Duration::minutes(1));
+        throughput.observe(&a1, 60_000, t0() + Duration::minutes(9));
+
+        let now = t0() + Duration::minutes(10);
+        assert_eq!(throughput.per_minute(now, RECENT), 2_000);
+        assert_eq!(throughput.per_minute(now, BASELINE), 1_000);
+        throughput.expire(now + Duration::minutes(10));
+        assert_eq!(throughput.per_minute(now + Duration::minutes(10), BASELINE), 0);
+    }
+
+    #[test]
+    fn rate_limits_point_at_the_agents_likely_throttled() {
+        let now = t0();
+        let mut health = ApiHealth::default();
+        health.record(now - Duration::seconds(20), ApiErrorKind::RateLimit, Some(AgentId::new("a1")));
+        health.record(now - Duration::seconds(10), ApiErrorKind::RateLimit, Some(AgentId::new("a1")));
+        // a1 hit the limit; a2 and a3 have gone quiet; a4 is still busy
+        let agents = agents(now, &[("a1", 5), ("a2", 90), ("a3", 120), ("a4", 5)]);
+
+        let advice = advise(&health, &Throughput::default(), &agents, now);
+        assert!(advice.throttled);
+        assert_eq!(advice.headline, "provider returning 429s — 3 agents likely throttled, consider pausing the wave");
+        assert_eq!(advice.waiting, [AgentId::new("a1"), AgentId::new("a2"), AgentId::new("a3")]);
+        assert!(advice.details[0].starts_with("last 10m: 2 rate limited (429)"));
+    }
+
+    #[test]
+    fn without_api_errors_a_stall_is_not_put_on_the_provider() {
+        let now = t0() + Duration::minutes(10);
+        let mut throughput = Throughput::default();
+        let a1 = AgentId::new("a1");
+        throughput.observe(&a1, 0, t0());
+        throughput.observe(&a1, 40_000, t0() + Duration::minutes(3));
+
+        let advice = advise(&ApiHealth::default(), &throughput, &agents(now, &[("a1", 300)]), now);
+        assert!(!advice.throttled);
+        assert_eq!(advice.headline, "throughput dropped with no API errors — the stall is not provider limits");
+
+        let idle = advise(&ApiHealth::default(), &Throughput::default(), &BTreeMap::new(), now);
+        assert_eq!(idle.headline, "no provider limits hit — pace is fine");
+    }
+
+    #[test]
+    fn turning_to_throttled_raises_one_toast() {
+        let mut state = AppState::new();
+        state.meta.replay_complete = true;
+        let now = state.meta.clock.now();
+        state.domain.api_health.record(now, ApiErrorKind::Overloaded, None);
+
+        tick(&mut state, now);
+        tick(&mut state, now + Duration::seconds(1));
+        let texts: Vec<&str> = state.ui.toasts.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, ["provider overloaded (529s) — consider pausing the wave"]);
+        assert!(state.domain.pacing.throttled);
+
+        state.domain.api_health.expire(now + Duration::minutes(11));
+        tick(&mut state, now + Duration::minutes(11));
+        assert!(!state.domain.pacing.throttled);
+    }
+}
@@ -0,0 +1,180 @@
+//! Quick-switch palette: a flat list of navigable entities (sessions, agents,
+//! tasks) ranked by fuzzy match against the typed query.
+//!
+//! # Functional Core
+//! Pure functions over `AppState`; the jump itself lives in navigation.
+
+use crate::app::fuzzy::{fuzzy_match, FuzzyMatch};
+use crate::app::AppState;
+use crate::model::{AgentId, SessionId, TaskId};
+
+/// Most results the palette keeps after ranking.
+pub const MAX_RESULTS: usize = 50;
+
+/// Longest detail text shown next to an entry title.
+const DETAIL_MAX_CHARS: usize = 60;
+
+/// Something the palette can jump to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NavTarget {
+    Session(SessionId),
+    Agent(AgentId),
+    Task(TaskId),
+}
+
+impl NavTarget {
+    /// Short kind tag shown in the palette's first column.
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            Self::Session(_) => "session",
+            Self::Agent(_) => "agent",
+            Self::Task(_) => "task",
+        }
+    }
+}
+
+/// One palette row: where it leads and the text it is matched against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaletteEntry {
+    pub target: NavTarget,
+    /// `title  detail` — the matched and displayed text
+    pub text: String,
+    /// Char count of the title prefix of `text` (rest is muted detail)
+    pub title_len: usize,
+}
+
+impl PaletteEntry {
+    fn new(target: NavTarget, title: String, detail: Option<String>) -> Self {
+        let title_len = title.chars().count();
+        let text = match detail.filter(|d| !d.is_empty()) {
+            Some(detail) => format!("{}  {}", title, detail),
+            None => title,
+        };
+        Self { target, text, title_len }
+    }
+}
+
+/// Collect every navigable entity in display order:
+/// sessions (active, then archived), agents (sorted), tasks (wave order).
+pub fn navigable_entities(state: &AppState) -> Vec<PaletteEntry> {
+    let sessions = state
+        .domain
+        .confirmed_active_sessions()
+        .map(|(_, m)| m)
+        .chain(state.domain.sessions.iter().map(|s| &s.meta))
+        .map(|meta| {
+            let detail = meta
+                .goal_summary(DETAIL_MAX_CHARS)
+                .unwrap_or_else(|| state.ui.time_format.date_time(meta.timestamp));
+            PaletteEntry::new(NavTarget::Session(meta.id.clone()), meta.id.to_string(), Some(detail))
+        });
+
+    let agents = state.sorted_agent_keys().iter().filter_map(|id| {
+        let agent = state.domain.agents.get(id)?;
+        let detail = [
+            agent.agent_type.clone(),
+            agent.task_description.as_deref().map(|d| one_line(d, DETAIL_MAX_CHARS)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" · ");
+        Some(PaletteEntry::new(NavTarget::Agent(id.clone()), id.to_string(), Some(detail)))
+    });
+
+    let tasks = state
+        .domain
+        .task_graph
+        .iter()
+        .flat_map(|g| g.flat_tasks())
+        .map(|task| {
+            let detail = format!(
+                "{} [{}]",
+                one_line(&task.description, DETAIL_MAX_CHARS),
+                task.status.label()
+            );
+            PaletteEntry::new(NavTarget::Task(task.id.clone()), task.id.to_string(), Some(detail))
+        });
+
+    sessions.chain(agents).chain(tasks).collect()
+}
+
+/// Rank entries against `query`, best first. Ties keep display order.
+/// An empty query returns entries unchanged (up to `MAX_RESULTS`).
+pub fn rank_entries(entries: Vec<PaletteEntry>, query: &str) -> Vec<(PaletteEntry, FuzzyMatch)> {
+    let mut ranked: Vec<_> = entries
+        .into_iter()
+        .filter_map(|entry| fuzzy_match(query, &entry.text).map(|m| (entry, m)))
+        .collect();
+    ranked.sort_by_key(|(_, m)| std::cmp::Reverse(m.score));
+    ranked.truncate(MAX_RESULTS);
+    ranked
+}
+
+/// Palette results for the current state and query.
+pub fn palette_results(state: &AppState, query: &str) -> Vec<(PaletteEntry, FuzzyMatch)> {
+    rank_entries(navigable_entities(state), query)
+}
+
+fn one_line(text: &str, max_chars: usize) -> String {
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if collapsed.chars().count() <= max_chars {
+        collapsed
+    } else {
+        let kept: String = collapsed.chars().take(max_chars.saturating_sub(1)).collect();
+        format!("{}…", kept.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Agent, ArchivedSession, SessionMeta, Task, TaskGraph, TaskStatus, Wave};
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn populated_state() -> AppState {
+        let mut state = AppState::new();
+        state.domain.sessions.push(ArchivedSession::new(
+            SessionMeta::new("sess-archived", Utc::now(), "/proj".into()).with_goal("Add dark mode".into()),
+            PathBuf::new(),
+        ));
+        let mut agent = Agent::new("a-reviewer", Utc::now());
+        agent.agent_type = Some("code-reviewer".into());
+        state.domain.agents.insert(AgentId::new("a-reviewer"), agent);
+        state.recompute_sorted_keys();
+        state.domain.task_graph = Some(TaskGraph::new(vec![Wave::new(
+            1,
+            vec![Task::new("T1", "Write the parser".into(), TaskStatus::Running)],
+        )]));
+        state
+    }
+
+    #[test]
+    fn entities_cover_sessions_agents_and_tasks_in_order() {
+        let entries = navigable_entities(&populated_state());
+        let kinds: Vec<_> = entries.iter().map(|e| e.target.kind_label()).collect();
+        assert_eq!(kinds, vec!["session", "agent", "task"]);
+        assert_eq!(entries[0].text, "sess-archived  Add dark mode");
+        assert_eq!(entries[0].title_len, "sess-archived".len());
+        assert_eq!(entries[1].text, "a-reviewer  code-reviewer");
+        assert_eq!(entries[2].text, "T1  Write the parser [running]");
+    }
+
+    #[test]
+    fn rank_filters_and_orders_by_score() {
+        let results = palette_results(&populated_state(), "parser");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.target, NavTarget::Task(TaskId::new("T1")));
+
+        let results = palette_results(&populated_state(), "dark");
+        assert_eq!(results[0].0.target, NavTarget::Session(SessionId::new("sess-archived")));
+    }
+
+    #[test]
+    fn empty_query_keeps_display_order() {
+        let results = palette_results(&populated_state(), "");
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[1].0.target, NavTarget::Agent(AgentId::new("a-reviewer")));
+    }
+}
@@ -0,0 +1,149 @@
+//! Dashboard composition from config: a grid of rows, each split into panels.
+//! Layout syntax: `docs/configuration.md`, "Dashboard".
+
+use serde::{Deserialize, Serialize};
+
+/// Something the Dashboard can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PanelKind {
+    /// Tasks by wave, or the kanban board (`v`)
+    TaskList,
+    /// Live events, or the followed agent in split view (`|`); the plan gate sits on top
+    EventStream,
+    AgentList,
+    /// Tool calls and failures per tool
+    ToolStats,
+    /// Files read and edited most often
+    HotFiles,
+    /// Recent errors
+    Errors,
+    /// The spec / plan in use (`S` also shows it beside the grid)
+    Spec,
+    /// Whether provider limits are slowing the run (`app::pacing`)
+    Pacing,
+}
+
+impl PanelKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::TaskList => "task list",
+            Self::EventStream => "event stream",
+            Self::AgentList => "agent list",
+            Self::ToolStats => "tool stats",
+            Self::HotFiles => "hot files",
+            Self::Errors => "errors",
+            Self::Spec => "spec",
+            Self::Pacing => "pacing",
+        }
+    }
+}
+
+fn unit_weight() -> u16 {
+    1
+}
+
+/// A panel and its share of the row's width.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PanelSlot {
+    pub panel: PanelKind,
+    #[serde(default = "unit_weight")]
+    pub width: u16,
+}
+
+/// A row of panels and its share of the content height.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PanelRow {
+    #[serde(default = "unit_weight")]
+    pub height: u16,
+    pub panels: Vec<PanelSlot>,
+}
+
+/// Rows of panels, top to bottom.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DashboardLayout {
+    pub rows: Vec<PanelRow>,
+}
+
+impl Default for DashboardLayout {
+    fn default() -> Self {
+        let slot = |panel| PanelSlot { panel, width: 1 };
+        Self {
+            rows: vec![PanelRow { height: 1, panels: vec![slot(PanelKind::TaskList), slot(PanelKind::EventStream)] }],
+        }
+    }
+}
+
+impl DashboardLayout {
+    /// Pure function: the layout made renderable. Zero weights become 1, a panel
+    /// placed twice keeps its first position, empty rows are dropped, and a
+    /// layout with nothing left falls back to the default.
+    pub fn normalized(&self) -> Self {
+        let mut seen = Vec::new();
+        let rows: Vec<PanelRow> = self
+            .rows
+            .iter()
+            .map(|row| PanelRow {
+                height: row.height.max(1),
+                panels: row
+                    .panels
+                    .iter()
+                    .filter(|slot| {
+                        let first = !seen.contains(&slot.panel);
+                        seen.push(slot.panel);
+                        first
+                    })
+                    .map(|slot| PanelSlot { panel: slot.panel, width: slot.width.max(1) })
+                    .collect(),
+            })
+            .filter(|row| !row.panels.is_empty())
+            .collect();
+        if rows.is_empty() { Self::default() } else { Self { rows } }
+    }
+
+    pub fn contains(&self, panel: PanelKind) -> bool {
+        self.rows.iter().any(|row| row.panels.iter().any(|slot| slot.panel == panel))
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_json_parses_with_default_weights() {
+        let layout: DashboardLayout = serde_json::from_str(
+            r#"{ "rows": [
+                { "height": 2, "panels": [{ "panel": "task_list" }, { "panel": "event_stream", "width": 2 }] },
+                { "panels": [{ "panel": "hot_files" }, { "panel": "errors" }] }
+            ] }"#,
+        )
+        .unwrap();
+        assert_eq!(layout.rows[0].panels[1], PanelSlot { panel: PanelKind::EventStream, width: 2 });
+        assert_eq!(layout.rows[1].height, 1);
+        assert!(layout.contains(PanelKind::Errors));
+        assert!(!layout.contains(PanelKind::AgentList));
+    }
+
+    #[test]
+    fn normalize_drops_duplicates_and_empty_rows() {
+        let slot = |panel, width| PanelSlot { panel, width };
+        let layout = DashboardLayout {
+            rows: vec![
+                PanelRow { height: 0, panels: vec![slot(PanelKind::Errors, 0), slot(PanelKind::ToolStats, 3)] },
+                PanelRow { height: 1, panels: vec![slot(PanelKind::Errors, 1)] },
+            ],
+        };
+        let normalized = layout.normalized();
+        assert_eq!(normalized.rows.len(), 1);
+        assert_eq!(normalized.rows[0].height, 1);
+        assert_eq!(normalized.rows[0].panels, [slot(PanelKind::Errors, 1), slot(PanelKind::ToolStats, 3)]);
+
+        assert_eq!(DashboardLayout { rows: Vec::new() }.normalized(), DashboardLayout::default());
+    }
+}
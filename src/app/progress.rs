@@ -0,0 +1,59 @@
+//! Live progress text for tasks, from agents' status lines.
+//!
+//! Agents may write status lines such as `loom-status: building parser 40%`,
+//! which hooks forward as `StatusUpdate` events. The latest one per task is
+//! shown next to the task in the task list while it runs — finer than
+//! pending / running / done. An update names its task, or is matched to the
+//! task its agent works on.
+
+use crate::app::AppState;
+use crate::model::{TaskId, TranscriptEvent, TranscriptEventKind};
+
+/// Prefix of a status line, dropped from the progress text when a hook
+/// forwards the line as written.
+pub const STATUS_PREFIX: &str = "loom-status:";
+
+/// Pure function: the progress text of a status line.
+pub fn status_text(line: &str) -> &str {
+    let line = line.trim();
+    line.strip_prefix(STATUS_PREFIX).map(str::trim_start).unwrap_or(line)
+}
+
+/// Pure function: the task a status update is about — the one it names, or
+/// the task the graph (or the linker) gave its agent.
+pub fn task_of(state: &AppState, event: &TranscriptEvent, task_id: Option<&str>) -> Option<TaskId> {
+    if let Some(id) = task_id {
+        return Some(TaskId::new(id));
+    }
+    let agent = event.agent_id.as_ref()?;
+    let graph = state.domain.task_graph.as_ref()?;
+    graph.waves.iter().flat_map(|w| &w.tasks).find(|t| t.agent_id.as_ref() == Some(agent)).map(|t| t.id.clone())
+}
+
+/// Record a status update as its task's progress text; an empty one clears it.
+pub fn track(state: &mut AppState, event: &TranscriptEvent) {
+    let TranscriptEventKind::StatusUpdate { text, task_id } = &event.kind else {
+        return;
+    };
+    let Some(task) = task_of(state, event, task_id.as_deref()) else {
+        return;
+    };
+    let text = status_text(text);
+    if text.is_empty() {
+        state.domain.task_progress.remove(&task);
+    } else {
+        state.domain.task_progress.insert(task, text.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_lines_lose_their_prefix() {
+        assert_eq!(status_text("loom-status: building parser 40%"), "building parser 40%");
+        assert_eq!(status_text("  running tests "), "running tests");
+        assert_eq!(status_text("loom-status:"), "");
+    }
+}
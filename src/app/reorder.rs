@@ -0,0 +1,87 @@
+//! Clock-skew tolerant ordering of the live event list.
+//!
+//! Hook scripts and transcript files stamp the same moment with slightly
+//! different clocks, and their events reach `update` in whatever order the
+//! sources are drained. Appending in arrival order makes the merged stream
+//! jitter; instead an event that is at most `skew` older than the newest event
+//! is inserted at its timestamp position. Anything older (a replayed backlog,
+//! a session catching up) is appended as it arrives, so file order survives.
+
+use std::collections::VecDeque;
+
+use chrono::Duration;
+
+use crate::model::TranscriptEvent;
+
+/// Default tolerance: covers clock drift between local hook and transcript writers.
+pub const DEFAULT_SKEW: Duration = Duration::milliseconds(1500);
+
+/// Insert `event` into `events` (oldest first), placing a late event that is
+/// within `skew` of the newest at its timestamp position. Events with equal
+/// timestamps keep arrival order. A zero `skew` always appends.
+///
+/// # Returns
+/// Index the event was inserted at.
+pub fn insert_ordered(events: &mut VecDeque<TranscriptEvent>, event: TranscriptEvent, skew: Duration) -> usize {
+    let within_skew = events
+        .back()
+        .is_some_and(|newest| newest.timestamp > event.timestamp && newest.timestamp - event.timestamp <= skew);
+    if !within_skew {
+        events.push_back(event);
+        return events.len() - 1;
+    }
+
+    // Walk back over the (few) newer events; they all lie inside the skew window
+    let mut idx = events.len();
+    while idx > 0 && events[idx - 1].timestamp > event.timestamp {
+        idx -= 1;
+    }
+    events.insert(idx, event);
+    idx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TranscriptEventKind;
+    use chrono::{DateTime, Utc};
+
+    fn at(millis: i64, text: &str) -> TranscriptEvent {
+        let t0: DateTime<Utc> = "2026-03-18T10:00:00Z".parse().unwrap();
+        TranscriptEvent::new(
+            t0 + Duration::milliseconds(millis),
+            TranscriptEventKind::AssistantMessage { content: text.into() },
+        )
+    }
+
+    fn texts(events: &VecDeque<TranscriptEvent>) -> Vec<&str> {
+        events
+            .iter()
+            .map(|e| match &e.kind {
+                TranscriptEventKind::AssistantMessage { content } => content.as_str(),
+                _ => "",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn late_events_within_skew_land_in_timestamp_order() {
+        let mut events = VecDeque::new();
+        for (ms, text) in [(0, "a"), (1000, "c"), (1200, "d")] {
+            insert_ordered(&mut events, at(ms, text), DEFAULT_SKEW);
+        }
+        assert_eq!(insert_ordered(&mut events, at(500, "b"), DEFAULT_SKEW), 1);
+        // Equal timestamps keep arrival order
+        assert_eq!(insert_ordered(&mut events, at(1000, "c2"), DEFAULT_SKEW), 3);
+        assert_eq!(texts(&events), ["a", "b", "c", "c2", "d"]);
+    }
+
+    #[test]
+    fn events_older_than_the_skew_or_with_skew_off_are_appended() {
+        let mut events = VecDeque::new();
+        insert_ordered(&mut events, at(10_000, "new"), DEFAULT_SKEW);
+        insert_ordered(&mut events, at(0, "backlog"), DEFAULT_SKEW);
+        insert_ordered(&mut events, at(9_900, "late"), Duration::zero());
+        assert_eq!(texts(&events), ["new", "backlog", "late"]);
+    }
+}
@@ -0,0 +1,157 @@
+//! Retry lineage of tasks, from the task graph snapshots seen while running.
+//!
+//! The graph only holds a task's current status and retry count. Comparing
+//! each snapshot with what was seen before gives the attempts: when each one
+//! started, and whether it failed (with the orchestrator's reason) or went
+//! through. Reviewers see them in the Review task detail. The task list marks
+//! retried tasks. A task whose last attempts failed for the same reason is
+//! looping rather than making progress.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::model::{TaskGraph, TaskId, TaskStatus};
+
+/// How an attempt ended, or that it has not yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AttemptOutcome {
+    Running,
+    Failed { reason: String, retry_count: u32 },
+    Done,
+}
+
+/// One run of a task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    /// When the snapshot showing it running (or already over) arrived
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub outcome: AttemptOutcome,
+}
+
+impl Attempt {
+    fn new(at: DateTime<Utc>, outcome: AttemptOutcome) -> Self {
+        let ended_at = (outcome != AttemptOutcome::Running).then_some(at);
+        Self { started_at: at, ended_at, outcome }
+    }
+
+    fn end(&mut self, at: DateTime<Utc>, outcome: AttemptOutcome) {
+        self.ended_at = Some(at);
+        self.outcome = outcome;
+    }
+}
+
+/// Attempts per task, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskAttempts {
+    tasks: BTreeMap<TaskId, Vec<Attempt>>,
+}
+
+impl TaskAttempts {
+    /// Fold a new graph snapshot, taken at `now`, into the lineage.
+    pub fn observe(&mut self, graph: &TaskGraph, now: DateTime<Utc>) {
+        for task in graph.flat_tasks() {
+            let attempts = self.tasks.entry(task.id.clone()).or_default();
+            let last = attempts.last().map(|a| a.outcome.clone());
+            let running = last == Some(AttemptOutcome::Running);
+            match &task.status {
+                TaskStatus::Pending => {}
+                TaskStatus::Running if running => {}
+                TaskStatus::Running => attempts.push(Attempt::new(now, AttemptOutcome::Running)),
+                TaskStatus::Implemented | TaskStatus::Completed if running => {
+                    if let Some(attempt) = attempts.last_mut() {
+                        attempt.end(now, AttemptOutcome::Done);
+                    }
+                }
+                TaskStatus::Implemented | TaskStatus::Completed => {}
+                TaskStatus::Failed { reason, retry_count } => {
+                    let failed = AttemptOutcome::Failed { reason: reason.clone(), retry_count: *retry_count };
+                    if let Some(attempt) = attempts.last_mut().filter(|_| running) {
+                        attempt.end(now, failed);
+                    } else if last.as_ref() != Some(&failed) {
+                        // Failed again without a snapshot showing the retry running
+                        attempts.push(Attempt::new(now, failed));
+                    }
+                }
+            }
+            if attempts.is_empty() {
+                self.tasks.remove(&task.id);
+            }
+        }
+    }
+
+    /// The task's attempts, oldest first (empty until it first ran).
+    pub fn get(&self, task: &TaskId) -> &[Attempt] {
+        self.tasks.get(task).map_or(&[], Vec::as_slice)
+    }
+}
+
+/// Pure function: true when the last two attempts failed for the same reason,
+/// i.e. retrying is not getting anywhere.
+pub fn is_looping(attempts: &[Attempt]) -> bool {
+    let reasons: Vec<&str> = attempts
+        .iter()
+        .rev()
+        .take(2)
+        .filter_map(|a| match &a.outcome {
+            AttemptOutcome::Failed { reason, .. } => Some(reason.as_str()),
+            _ => None,
+        })
+        .collect();
+    matches!(reasons.as_slice(), [a, b] if a == b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Task, Wave};
+    use chrono::Duration;
+
+    fn snapshot(status: TaskStatus) -> TaskGraph {
+        TaskGraph::new(vec![Wave { number: 1, tasks: vec![Task::new("T1", String::new(), status)] }])
+    }
+
+    fn failed(reason: &str, retry_count: u32) -> TaskStatus {
+        TaskStatus::Failed { reason: reason.into(), retry_count }
+    }
+
+    #[test]
+    fn snapshots_become_attempts() {
+        let t0 = Utc::now();
+        let mut attempts = TaskAttempts::default();
+        let statuses = [
+            TaskStatus::Pending,
+            TaskStatus::Running,
+            TaskStatus::Running,
+            failed("tests failed", 0),
+            failed("tests failed", 0),
+            TaskStatus::Running,
+            TaskStatus::Implemented,
+        ];
+        for (i, status) in statuses.into_iter().enumerate() {
+            attempts.observe(&snapshot(status), t0 + Duration::minutes(i as i64));
+        }
+
+        let lineage = attempts.get(&TaskId::new("T1"));
+        assert_eq!(lineage.len(), 2);
+        assert_eq!(lineage[0].started_at, t0 + Duration::minutes(1));
+        assert_eq!(lineage[0].ended_at, Some(t0 + Duration::minutes(3)));
+        assert_eq!(lineage[0].outcome, AttemptOutcome::Failed { reason: "tests failed".into(), retry_count: 0 });
+        assert_eq!(lineage[1].outcome, AttemptOutcome::Done);
+        assert!(!is_looping(lineage));
+    }
+
+    #[test]
+    fn repeated_failures_for_one_reason_are_looping() {
+        let t0 = Utc::now();
+        let mut attempts = TaskAttempts::default();
+        attempts.observe(&snapshot(failed("lint", 0)), t0);
+        // Retried and failed again between two polls
+        attempts.observe(&snapshot(failed("lint", 1)), t0);
+        let lineage = attempts.get(&TaskId::new("T1"));
+        assert_eq!(lineage.len(), 2);
+        assert!(is_looping(lineage));
+        assert!(attempts.get(&TaskId::new("T9")).is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+//! Human review checkpoint (Review view, `5`).
+//!
+//! Lists implemented or completed tasks whose `review_status` is still
+//! pending. `a` / `r` approve or reject the selected task with an optional
+//! note; each decision is appended as one JSON line to `reviews.jsonl` next to
+//! the task graph, where orchestrators pick it up. The latest line per task wins.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::app::{AppState, CommandMessage};
+use crate::error::ReviewError;
+use crate::model::{ReviewStatus, Task, TaskId, TaskStatus};
+
+/// Decision log file name, in the project state dir.
+pub const REVIEWS_FILE: &str = "reviews.jsonl";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Approved,
+    Rejected,
+}
+
+impl Verdict {
+    pub fn label(self) -> &'static str {
+        match self {
+            Verdict::Approved => "approved",
+            Verdict::Rejected => "rejected",
+        }
+    }
+}
+
+/// One line of `reviews.jsonl`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewDecision {
+    pub task_id: TaskId,
+    pub verdict: Verdict,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+    pub reviewed_at: DateTime<Utc>,
+}
+
+/// Note being typed for a decision, before it is written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewNote {
+    pub task_id: TaskId,
+    pub verdict: Verdict,
+    pub input: String,
+}
+
+/// Pure function: decision log path for a task graph file.
+pub fn reviews_path(task_graph: &Path) -> PathBuf {
+    task_graph.parent().unwrap_or(Path::new(".")).join(REVIEWS_FILE)
+}
+
+/// Pure function: tasks awaiting human review, in graph order.
+pub fn review_queue(state: &AppState) -> Vec<&Task> {
+    let Some(graph) = state.domain.task_graph.as_ref() else {
+        return Vec::new();
+    };
+    graph
+        .flat_tasks()
+        .filter(|t| matches!(t.status, TaskStatus::Implemented | TaskStatus::Completed))
+        .filter(|t| t.review_status == ReviewStatus::Pending)
+        .collect()
+}
+
+/// Task under the Review view cursor.
+pub fn selected_task(state: &AppState) -> Option<&Task> {
+    let queue = review_queue(state);
+    let idx = state.ui.scroll_offsets.review.min(queue.len().checked_sub(1)?);
+    queue.get(idx).copied()
+}
+
+/// Latest decision per task. A missing file means no decisions yet; lines that
+/// don't parse (another writer's format, a torn write) are skipped.
+pub fn load_reviews(path: &Path) -> Result<BTreeMap<TaskId, ReviewDecision>, ReviewError> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => return Err(io_error(path, e)),
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<ReviewDecision>(line).ok())
+        .map(|d| (d.task_id.clone(), d))
+        .collect())
+}
+
+/// Append one decision to the log, creating it (and its directory) if needed.
+pub fn append_review(path: &Path, decision: &ReviewDecision) -> Result<(), ReviewError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+    }
+    let mut line = serde_json::to_string(decision)?;
+    line.push('\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| io_error(path, e))
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> ReviewError {
+    ReviewError::Io { path: path.display().to_string(), message: e.to_string() }
+}
+
+/// Start typing the note for a decision on the selected task.
+pub fn start_decision(state: &mut AppState, verdict: Verdict) {
+    match selected_task(state) {
+        Some(task) => {
+            state.ui.review_note = Some(ReviewNote { task_id: task.id.clone(), verdict, input: String::new() })
+        }
+        None => {
+            state.ui.command_message = Some(CommandMessage { text: "no task awaiting review".into(), is_error: true })
+        }
+    }
+}
+
+/// Close the note input and record the decision.
+/// I/O: appends to `reviews.jsonl`. On failure the note stays open so nothing is lost.
+///
+/// # Returns
+/// Toast describing the outcome.
+pub fn submit_decision(state: &mut AppState) -> CommandMessage {
+    let Some(note) = state.ui.review_note.take() else {
+        return CommandMessage { text: "no review in progress".into(), is_error: true };
+    };
+    let Some(path) = state.meta.reviews_path.clone() else {
+        state.ui.review_note = Some(note);
+        return CommandMessage { text: "no reviews file to write to".into(), is_error: true };
+    };
+    let decision = ReviewDecision {
+        task_id: note.task_id.clone(),
+        verdict: note.verdict,
+        note: note.input.trim().to_string(),
+        reviewed_at: state.meta.clock.now(),
+    };
+    match append_review(&path, &decision) {
+        Ok(()) => {
+            let text = format!("{} {}", decision.task_id, decision.verdict.label());
+            state.domain.reviews.insert(decision.task_id.clone(), decision);
+            CommandMessage { text, is_error: false }
+        }
+        Err(e) => {
+            state.ui.review_note = Some(note);
+            CommandMessage { text: format!("review not saved: {e}"), is_error: true }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{TaskGraph, Wave};
+
+    fn decision(task: &str, verdict: Verdict, note: &str) -> ReviewDecision {
+        ReviewDecision { task_id: TaskId::new(task), verdict, note: note.into(), reviewed_at: Utc::now() }
+    }
+
+    #[test]
+    fn latest_decision_per_task_wins_and_bad_lines_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join(REVIEWS_FILE);
+        assert!(load_reviews(&path).unwrap().is_empty());
+
+        append_review(&path, &decision("T1", Verdict::Rejected, "missing tests")).unwrap();
+        append_review(&path, &decision("T2", Verdict::Approved, "")).unwrap();
+        std::fs::OpenOptions::new().append(true).open(&path).unwrap().write_all(b"not json\n").unwrap();
+        append_review(&path, &decision("T1", Verdict::Approved, "fixed")).unwrap();
+
+        let reviews = load_reviews(&path).unwrap();
+        assert_eq!(reviews.len(), 2);
+        assert_eq!(reviews[&TaskId::new("T1")].verdict, Verdict::Approved);
+        assert_eq!(reviews[&TaskId::new("T1")].note, "fixed");
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(raw.lines().nth(1).unwrap().contains(r#""verdict":"approved""#));
+        assert!(!raw.lines().nth(1).unwrap().contains("note"), "empty notes are omitted");
+    }
+
+    #[test]
+    fn queue_holds_finished_tasks_with_pending_review() {
+        let mut state = AppState::new();
+        let mut passed = Task::new("T3", "c".into(), TaskStatus::Completed);
+        passed.review_status = ReviewStatus::Passed;
+        state.domain.task_graph = Some(TaskGraph::new(vec![Wave::new(1, vec![
+            Task::new("T1", "a".into(), TaskStatus::Implemented),
+            Task::new("T2", "b".into(), TaskStatus::Running),
+            passed,
+            Task::new("T4", "d".into(), TaskStatus::Completed),
+        ])]));
+
+        let ids: Vec<&str> = review_queue(&state).iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["T1", "T4"]);
+        state.ui.scroll_offsets.review = 9;
+        assert_eq!(selected_task(&state).unwrap().id.as_str(), "T4");
+    }
+
+    #[test]
+    fn reviews_file_sits_next_to_task_graph() {
+        assert_eq!(
+            reviews_path(Path::new("/p/.work/state/task_graph.json")),
+            PathBuf::from("/p/.work/state/reviews.jsonl")
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Orchestration runs.
+//!
+//! One loom orchestration drives several Claude Code sessions — the parent and
+//! its workers. A session joins a run when a hook payload on the stream names
+//! one (`runId` / `run_id`), or when it is confirmed while the project's task
+//! graph is in progress: the graph is project-level, so every session live
+//! during it belongs to its run (the task file's `run_id`, else one minted when
+//! the graph first showed up). The run is kept in `SessionMeta::loom_plan_id`,
+//! so archives remember it. `R` in the Sessions view groups the table by run
+//! under a header row of run-level totals.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Utc};
+
+use crate::app::{outcome, AppState};
+use crate::model::{Clock, SessionMeta, SessionOutcome, SessionStatus};
+
+/// Totals over the sessions of one run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunSummary {
+    pub sessions: usize,
+    pub active: usize,
+    pub agents: u32,
+    /// The graph is shared, so this is the largest count rather than a sum
+    pub tasks: u32,
+    pub events: u32,
+    /// Distinct failed task ids
+    pub failed_tasks: usize,
+    /// Ended sessions per outcome
+    pub outcomes: BTreeMap<SessionOutcome, usize>,
+    pub started: DateTime<Utc>,
+    pub ended: DateTime<Utc>,
+}
+
+impl RunSummary {
+    /// Wall time from the first session's start to the last one's end.
+    pub fn span(&self) -> chrono::Duration {
+        self.ended - self.started
+    }
+}
+
+/// Pure function: totals per run over `sessions`; sessions in no run are left out.
+pub fn summarize(sessions: &[&SessionMeta], clock: &dyn Clock) -> BTreeMap<String, RunSummary> {
+    let mut runs: BTreeMap<String, (RunSummary, BTreeSet<&str>)> = BTreeMap::new();
+    for meta in sessions {
+        let Some(run) = meta.loom_plan_id.as_ref() else { continue };
+        let active = meta.status == SessionStatus::Active;
+        let ran = if active {
+            meta.elapsed(clock)
+        } else {
+            meta.duration.and_then(|d| chrono::Duration::from_std(d).ok()).unwrap_or_default()
+        };
+        let ended = meta.timestamp + ran;
+        let (summary, failed) = runs.entry(run.clone()).or_insert_with(|| {
+            let summary = RunSummary {
+                sessions: 0,
+                active: 0,
+                agents: 0,
+                tasks: 0,
+                events: 0,
+                failed_tasks: 0,
+                outcomes: BTreeMap::new(),
+                started: meta.timestamp,
+                ended,
+            };
+            (summary, BTreeSet::new())
+        });
+        summary.sessions += 1;
+        summary.active += usize::from(active);
+        summary.agents += meta.agent_count;
+        summary.tasks = summary.tasks.max(meta.task_count);
+        summary.events += meta.event_count;
+        summary.started = summary.started.min(meta.timestamp);
+        summary.ended = summary.ended.max(ended);
+        failed.extend(meta.failed_tasks.iter().map(|id| id.as_str()));
+        summary.failed_tasks = failed.len();
+        if let Some(ended) = outcome::listed(meta) {
+            *summary.outcomes.entry(ended).or_default() += 1;
+        }
+    }
+    runs.into_iter().map(|(run, (summary, _))| (run, summary)).collect()
+}
+
+/// Follow the task graph: while it is in progress its run is current (minted
+/// at `now` when the task file names none); once it is done no run is.
+pub fn observe_graph(state: &mut AppState, now: DateTime<Utc>) {
+    let graph = state.domain.task_graph.as_ref().filter(|graph| graph.in_progress());
+    state.domain.current_run = graph.map(|graph| match &graph.run_id {
+        Some(id) => id.clone(),
+        None => state
+            .domain
+            .current_run
+            .clone()
+            .unwrap_or_else(|| format!("run-{}", now.format("%Y%m%d-%H%M%S"))),
+    });
+    join_current_run(state);
+}
+
+/// Put the confirmed active sessions that are in no run yet into the current one.
+pub fn join_current_run(state: &mut AppState) {
+    let Some(run) = state.domain.current_run.as_ref() else { return };
+    for meta in state.domain.active_sessions.values_mut().filter(|meta| meta.confirmed) {
+        meta.loom_plan_id.get_or_insert_with(|| run.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{SessionId, SystemClock, Task, TaskGraph, TaskId, TaskStatus, Wave};
+    use chrono::Duration;
+
+    fn session(id: &str, run: Option<&str>, start: DateTime<Utc>, minutes: i64) -> SessionMeta {
+        let mut meta = SessionMeta::new(id, start, "/proj".into())
+            .with_duration(std::time::Duration::from_secs(minutes as u64 * 60));
+        meta.status = SessionStatus::Completed;
+        meta.loom_plan_id = run.map(str::to_string);
+        meta.agent_count = 2;
+        meta.task_count = 5;
+        meta.event_count = 10;
+        meta
+    }
+
+    #[test]
+    fn run_totals_span_the_sessions_of_each_run() {
+        let start = Utc::now() - Duration::hours(2);
+        let mut worker = session("w1", Some("auth"), start + Duration::minutes(5), 30);
+        worker.failed_tasks = vec![TaskId::new("T2")];
+        worker.outcome = Some(SessionOutcome::Success);
+        let mut parent = session("p1", Some("auth"), start, 20);
+        parent.failed_tasks = vec![TaskId::new("T2"), TaskId::new("T4")];
+        let sessions = [parent, worker, session("solo", None, start, 5)];
+        let refs: Vec<&SessionMeta> = sessions.iter().collect();
+
+        let runs = summarize(&refs, &SystemClock);
+        assert_eq!(runs.len(), 1);
+        let auth = &runs["auth"];
+        assert_eq!((auth.sessions, auth.agents, auth.tasks, auth.events, auth.failed_tasks), (2, 4, 5, 20, 2));
+        assert_eq!(auth.span(), Duration::minutes(35));
+        // The parent ended before outcomes were recorded
+        let outcomes = BTreeMap::from([(SessionOutcome::Success, 1), (SessionOutcome::Unknown, 1)]);
+        assert_eq!(auth.outcomes, outcomes);
+    }
+
+    #[test]
+    fn sessions_confirmed_during_a_graph_join_its_run() {
+        let mut state = AppState::new();
+        let mut parent = SessionMeta::new("p1", Utc::now(), "/proj".into());
+        parent.confirmed = true;
+        state.domain.active_sessions.insert(SessionId::new("p1"), parent);
+        let graph = |status| TaskGraph::new(vec![Wave::new(1, vec![Task::new("T1", "Build".into(), status)])]);
+
+        state.domain.task_graph = Some(graph(TaskStatus::Running));
+        observe_graph(&mut state, Utc::now());
+        let run = state.domain.current_run.clone().unwrap();
+        assert!(run.starts_with("run-"));
+
+        // A worker confirmed later joins too; the minted id stays
+        let mut worker = SessionMeta::new("w1", Utc::now(), "/proj".into());
+        worker.confirmed = true;
+        state.domain.active_sessions.insert(SessionId::new("w1"), worker);
+        observe_graph(&mut state, Utc::now() + Duration::minutes(1));
+        assert!(state.domain.active_sessions.values().all(|m| m.loom_plan_id.as_ref() == Some(&run)));
+
+        state.domain.task_graph = Some(graph(TaskStatus::Completed));
+        observe_graph(&mut state, Utc::now());
+        assert_eq!(state.domain.current_run, None);
+    }
+}
@@ -0,0 +1,194 @@
+//! Ingestion sampling for runaway sessions; the full data goes to an overflow log.
+//! When sampling starts and stops: `docs/design.md`, "Ingestion sampling".
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::model::{SessionId, TranscriptEvent, TranscriptEventKind};
+
+/// Length of one rate window.
+const WINDOW: Duration = Duration::seconds(1);
+
+/// Sample a session once it delivers more than `events_per_sec` events in a
+/// window, keeping one high-volume event in `keep_one_in`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleThreshold {
+    pub events_per_sec: usize,
+    pub keep_one_in: usize,
+}
+
+impl Default for SampleThreshold {
+    fn default() -> Self {
+        Self { events_per_sec: 500, keep_one_in: 10 }
+    }
+}
+
+impl SampleThreshold {
+    /// Sampling rate as shown on screen: `1/10`.
+    pub fn label(&self) -> String {
+        format!("1/{}", self.keep_one_in)
+    }
+}
+
+/// Pure function: is this a kind a runaway session floods with?
+pub fn is_high_volume(kind: &TranscriptEventKind) -> bool {
+    matches!(kind, TranscriptEventKind::AssistantMessage { .. } | TranscriptEventKind::ToolUse { .. })
+}
+
+/// Arrivals of one session in its current window.
+#[derive(Debug, Clone, Default)]
+struct SessionRate {
+    window_start: Option<DateTime<Utc>>,
+    count: usize,
+    sampling: bool,
+    /// High-volume events seen since sampling began
+    high_volume: usize,
+}
+
+impl SessionRate {
+    /// Start a new window once `now` is past the current one, ending sampling
+    /// when the closed window was calm or was followed by a silent one.
+    fn roll(&mut self, now: DateTime<Utc>, threshold: &SampleThreshold) {
+        if let Some(start) = self.window_start {
+            if now - start < WINDOW {
+                return;
+            }
+            let calm = self.count <= threshold.events_per_sec / 2 || now - start >= WINDOW * 2;
+            if self.sampling && calm {
+                self.sampling = false;
+                self.high_volume = 0;
+            }
+        }
+        self.window_start = Some(now);
+        self.count = 0;
+    }
+}
+
+/// Per-session arrival rates plus the overflow log queue.
+#[derive(Debug, Clone, Default)]
+pub struct IngestSampler {
+    threshold: SampleThreshold,
+    sessions: BTreeMap<Option<SessionId>, SessionRate>,
+    overflow: Vec<TranscriptEvent>,
+}
+
+impl IngestSampler {
+    pub fn new(threshold: SampleThreshold) -> Self {
+        Self { threshold, ..Default::default() }
+    }
+
+    pub fn threshold(&self) -> &SampleThreshold {
+        &self.threshold
+    }
+
+    /// Count an event arriving at `now`.
+    ///
+    /// # Returns
+    /// False when the event is sampled out of the live buffer.
+    pub fn admit(&mut self, event: &TranscriptEvent, now: DateTime<Utc>) -> bool {
+        let threshold = self.threshold;
+        let rate = self.sessions.entry(event.session_id.clone()).or_default();
+        rate.roll(now, &threshold);
+        rate.count += 1;
+        if rate.count > threshold.events_per_sec {
+            rate.sampling = true;
+        }
+        if !rate.sampling {
+            return true;
+        }
+
+        self.overflow.push(event.clone());
+        if !is_high_volume(&event.kind) {
+            return true;
+        }
+        rate.high_volume += 1;
+        (rate.high_volume - 1).is_multiple_of(threshold.keep_one_in.max(1))
+    }
+
+    /// Forget sessions with no events in the last two windows as of `now`.
+    pub fn expire(&mut self, now: DateTime<Utc>) {
+        self.sessions.retain(|_, rate| rate.window_start.is_some_and(|start| now - start < WINDOW * 2));
+    }
+
+    /// Number of sessions currently sampled.
+    pub fn sampling(&self) -> usize {
+        self.sessions.values().filter(|rate| rate.sampling).count()
+    }
+
+    /// Drain the events queued for the overflow log.
+    pub fn take_overflow(&mut self) -> Vec<TranscriptEvent> {
+        std::mem::take(&mut self.overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ToolName;
+
+    fn event(kind: TranscriptEventKind) -> TranscriptEvent {
+        TranscriptEvent::new(Utc::now(), kind).with_session("s1")
+    }
+
+    fn assistant() -> TranscriptEvent {
+        event(TranscriptEventKind::AssistantMessage { content: "again".into() })
+    }
+
+    fn tool_result() -> TranscriptEvent {
+        event(TranscriptEventKind::ToolResult {
+            tool_name: ToolName::new("Bash"),
+            result_summary: String::new(),
+            duration_ms: None,
+            is_error: false,
+        })
+    }
+
+    fn sampler() -> IngestSampler {
+        IngestSampler::new(SampleThreshold { events_per_sec: 20, keep_one_in: 10 })
+    }
+
+    #[test]
+    fn overload_samples_high_volume_kinds_and_logs_everything() {
+        let mut sampler = sampler();
+        let t0: DateTime<Utc> = "2026-03-18T10:00:00Z".parse().unwrap();
+
+        let kept = (0..20).filter(|_| sampler.admit(&assistant(), t0)).count();
+        assert_eq!(kept, 20);
+        assert_eq!(sampler.sampling(), 0);
+        assert!(sampler.take_overflow().is_empty());
+
+        // The 21st event in the window trips sampling: one in ten kept from here
+        let kept = (0..30).filter(|_| sampler.admit(&assistant(), t0)).count();
+        assert_eq!(kept, 3);
+        assert_eq!(sampler.sampling(), 1);
+        // Tool results are never sampled out
+        assert!((0..5).all(|_| sampler.admit(&tool_result(), t0)));
+        assert_eq!(sampler.take_overflow().len(), 35);
+    }
+
+    #[test]
+    fn sampling_ends_after_a_calm_window_or_silence() {
+        let mut sampler = sampler();
+        let t0: DateTime<Utc> = "2026-03-18T10:00:00Z".parse().unwrap();
+        (0..25).for_each(|_| {
+            sampler.admit(&assistant(), t0);
+        });
+        assert_eq!(sampler.sampling(), 1);
+
+        // Closing the busy first window keeps sampling; the calm second one ends it
+        (0..8).for_each(|_| {
+            sampler.admit(&assistant(), t0 + Duration::milliseconds(1500));
+        });
+        assert_eq!(sampler.sampling(), 1);
+        assert!(sampler.admit(&assistant(), t0 + Duration::milliseconds(2600)));
+        assert_eq!(sampler.sampling(), 0);
+
+        (0..25).for_each(|_| {
+            sampler.admit(&assistant(), t0 + Duration::seconds(3));
+        });
+        assert_eq!(sampler.sampling(), 1);
+        sampler.expire(t0 + Duration::seconds(5));
+        assert_eq!(sampler.sampling(), 0);
+    }
+}
@@ -0,0 +1,635 @@
+//! Runtime settings editable from the `,` overlay.
+//!
+//! Edits apply to `AppState` immediately (live preview); Enter persists them
+//! into the user config file next to the path overrides (see `paths::PathsConfig`),
+//! Esc restores the values the overlay was opened with.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::agent_kinds::{AgentKindStyles, KindStyle};
+use crate::app::command::{MAX_TICK, MIN_TICK};
+use crate::app::budget::{self, DurationBudget};
+use crate::app::channels::EventChannels;
+use crate::app::severity::{self, SeverityRules};
+use crate::app::cost::CostBudget;
+use crate::app::{dedup, reorder, update};
+use crate::app::error_burst::BurstThreshold;
+use crate::app::panels::DashboardLayout;
+use crate::app::AppState;
+use crate::error::{ConfigError, RuleError};
+use crate::model::ColorMode;
+use crate::view::components::format::{format_cost_usd, TimeFormat};
+use crate::watcher::logs::ExtraLog;
+use crate::watcher::TruncationLimits;
+
+/// Tick rate step for one left/right press.
+const TICK_STEP_MS: u64 = 50;
+
+/// Accepted range for the stale session timeout, in minutes.
+const STALE_MINUTES: std::ops::RangeInclusive<u64> = 1..=120;
+
+/// Accepted range for the error burst threshold, in tool failures.
+const BURST_FAILURES: std::ops::RangeInclusive<usize> = 1..=50;
+
+/// Accepted range for the error burst window, in seconds, and one step of it.
+const BURST_WINDOW_SECS: std::ops::RangeInclusive<u64> = 30..=1800;
+const BURST_WINDOW_STEP_SECS: u64 = 30;
+
+/// Upper bound for the long tool call limit, in minutes (0 turns the alert off).
+const LONG_TOOL_CALL_MAX_MINS: u64 = 120;
+
+/// Upper bound for the deduplication window, in milliseconds (0 turns it off), and one step of it.
+const DEDUP_WINDOW_MAX_MS: u64 = 10_000;
+const DEDUP_WINDOW_STEP_MS: u64 = 500;
+
+/// Upper bound for the clock skew tolerance, in milliseconds (0 turns reordering off), and one step of it.
+const REORDER_SKEW_MAX_MS: u64 = 10_000;
+const REORDER_SKEW_STEP_MS: u64 = 250;
+
+/// Upper bound for session duration budgets, in minutes (0 means no budget), and one step of it.
+const BUDGET_MAX_MINS: u64 = 600;
+const BUDGET_STEP_MINS: u64 = 5;
+
+/// Upper bound for USD budgets, in cents (0 means no budget), and one step of it.
+const COST_BUDGET_MAX_CENTS: u64 = 100_000;
+const COST_BUDGET_STEP_CENTS: u64 = 100;
+
+/// Least chars a truncation limit keeps, so a stray `0` can't empty every event.
+const TRUNCATION_MIN_CHARS: usize = 100;
+
+/// Persisted form of the editable options. Missing keys keep their defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub tick_ms: u64,
+    /// Slow the tick to one second after a quiet spell with nothing animating
+    pub idle_throttle: bool,
+    /// Confirmed sessions with no events for this long are archived as cancelled
+    pub stale_timeout_mins: u64,
+    /// Alert when a session has more tool failures than this within the burst window
+    pub error_burst_failures: usize,
+    pub error_burst_window_secs: u64,
+    /// Flag tool calls still unanswered after this many minutes (0: off)
+    pub long_tool_call_mins: u64,
+    /// Events from different sources with the same signature this close together are one event
+    pub dedup_window_ms: u64,
+    /// Keep such duplicates in the event list instead of dropping them
+    pub show_duplicates: bool,
+    /// Late events up to this much older than the newest are shown in timestamp order
+    pub reorder_skew_ms: u64,
+    /// 12-hour clock in timestamps (defaults from the locale)
+    pub clock_12h: bool,
+    /// Timestamps in UTC instead of the local time zone
+    pub utc_times: bool,
+    /// Expected duration of a session, in minutes (0: none)
+    pub session_budget_mins: u64,
+    /// Expected duration of an orchestrated session (a task graph is loaded); 0 falls back to the session budget
+    pub orchestration_budget_mins: u64,
+    /// Alert when a session's estimated cost reaches 80% / 100% of this, in cents (0: none)
+    pub session_cost_budget_cents: u64,
+    /// Alert when the day's estimated spend reaches 80% / 100% of this, in cents (0: none)
+    pub daily_cost_budget_cents: u64,
+    /// POST cost alerts as JSON to this URL (config file only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_alert_webhook: Option<String>,
+    /// Also show cost alerts as desktop notifications
+    pub cost_alert_desktop: bool,
+    /// Look for a newer release on startup (see `updates`)
+    pub check_updates: bool,
+    pub show_tool_results: bool,
+    pub show_assistant_messages: bool,
+    pub color_mode: ColorMode,
+    /// Dashboard panel grid (config file only)
+    #[serde(skip_serializing_if = "DashboardLayout::is_default")]
+    pub dashboard: DashboardLayout,
+    /// Named event channels, `name: rule` each (config file only; see `channels`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub channels: Vec<String>,
+    /// Event styles, `style: rule` each (config file only; see `severity`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub severity: Vec<String>,
+    /// Extra log files tailed into the event stream (config file only; see `watcher::logs`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extra_logs: Vec<ExtraLog>,
+    /// Icon and color per agent type, name or namespace (config file only; see `agent_kinds`)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub agent_kinds: BTreeMap<String, KindStyle>,
+    /// Chars of transcript text kept per event (config file only; read when the watcher starts)
+    #[serde(skip_serializing_if = "TruncationLimits::is_default")]
+    pub truncation: TruncationLimits,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            tick_ms: crate::app::state::AppMeta::DEFAULT_TICK_RATE.as_millis() as u64,
+            idle_throttle: true,
+            stale_timeout_mins: crate::app::state::AppMeta::DEFAULT_STALE_TIMEOUT.num_minutes() as u64,
+            error_burst_failures: BurstThreshold::default().failures,
+            error_burst_window_secs: BurstThreshold::default().window.num_seconds() as u64,
+            long_tool_call_mins: crate::app::state::AppMeta::DEFAULT_LONG_TOOL_CALL.num_minutes() as u64,
+            dedup_window_ms: dedup::DEFAULT_WINDOW.num_milliseconds() as u64,
+            show_duplicates: false,
+            reorder_skew_ms: reorder::DEFAULT_SKEW.num_milliseconds() as u64,
+            clock_12h: TimeFormat::default().hour12,
+            utc_times: false,
+            session_budget_mins: 0,
+            orchestration_budget_mins: 0,
+            session_cost_budget_cents: 0,
+            daily_cost_budget_cents: 0,
+            cost_alert_webhook: None,
+            cost_alert_desktop: false,
+            check_updates: false,
+            show_tool_results: true,
+            show_assistant_messages: true,
+            color_mode: ColorMode::Normal,
+            dashboard: DashboardLayout::default(),
+            channels: Vec::new(),
+            severity: Vec::new(),
+            extra_logs: Vec::new(),
+            agent_kinds: BTreeMap::new(),
+            truncation: TruncationLimits::default(),
+        }
+    }
+}
+
+/// One row of the settings overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingField {
+    TickRate,
+    StaleTimeout,
+    ShowToolResults,
+    ShowAssistantMessages,
+    IdleThrottle,
+    ColorMode,
+    ErrorBurstFailures,
+    ErrorBurstWindow,
+    LongToolCall,
+    DedupWindow,
+    ShowDuplicates,
+    ReorderSkew,
+    Clock,
+    TimeZone,
+    SessionBudget,
+    OrchestrationBudget,
+    SessionCostBudget,
+    DailyCostBudget,
+    CostAlertDesktop,
+    CheckUpdates,
+}
+
+impl SettingField {
+    /// Rows in display order.
+    pub const ALL: [SettingField; 20] = [
+        Self::TickRate,
+        Self::StaleTimeout,
+        Self::ShowToolResults,
+        Self::ShowAssistantMessages,
+        Self::IdleThrottle,
+        Self::ColorMode,
+        Self::ErrorBurstFailures,
+        Self::ErrorBurstWindow,
+        Self::LongToolCall,
+        Self::DedupWindow,
+        Self::ShowDuplicates,
+        Self::ReorderSkew,
+        Self::Clock,
+        Self::TimeZone,
+        Self::SessionBudget,
+        Self::OrchestrationBudget,
+        Self::SessionCostBudget,
+        Self::DailyCostBudget,
+        Self::CostAlertDesktop,
+        Self::CheckUpdates,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::TickRate => "Tick rate",
+            Self::IdleThrottle => "Slow tick when idle",
+            Self::ColorMode => "Colors",
+            Self::StaleTimeout => "Stale session timeout",
+            Self::ErrorBurstFailures => "Error burst alert above",
+            Self::ErrorBurstWindow => "Error burst window",
+            Self::LongToolCall => "Long tool call alert",
+            Self::DedupWindow => "Duplicate event window",
+            Self::ShowDuplicates => "Show duplicate events",
+            Self::ReorderSkew => "Clock skew tolerance",
+            Self::Clock => "Clock",
+            Self::TimeZone => "Time zone",
+            Self::SessionBudget => "Session budget",
+            Self::OrchestrationBudget => "Orchestration budget",
+            Self::SessionCostBudget => "Session cost budget",
+            Self::DailyCostBudget => "Daily cost budget",
+            Self::CostAlertDesktop => "Desktop cost alerts",
+            Self::CheckUpdates => "Check for updates",
+            Self::ShowToolResults => "Show tool results",
+            Self::ShowAssistantMessages => "Show assistant messages",
+        }
+    }
+
+    /// Display value for the current settings.
+    pub fn value(self, settings: &Settings) -> String {
+        let on_off = |b: bool| if b { "on" } else { "off" }.to_string();
+        match self {
+            Self::TickRate => format!("{}ms", settings.tick_ms),
+            Self::IdleThrottle => on_off(settings.idle_throttle),
+            Self::ColorMode => settings.color_mode.label().to_string(),
+            Self::StaleTimeout => format!("{}m", settings.stale_timeout_mins),
+            Self::ErrorBurstFailures => format!("{} failures", settings.error_burst_failures),
+            Self::ErrorBurstWindow => burst_threshold(settings).window_label(),
+            Self::LongToolCall => match settings.long_tool_call_mins.min(LONG_TOOL_CALL_MAX_MINS) {
+                0 => "off".to_string(),
+                mins => format!("after {}m", mins),
+            },
+            Self::DedupWindow => window_label(settings.dedup_window_ms.min(DEDUP_WINDOW_MAX_MS)),
+            Self::ReorderSkew => window_label(settings.reorder_skew_ms.min(REORDER_SKEW_MAX_MS)),
+            Self::ShowDuplicates => on_off(settings.show_duplicates),
+            Self::Clock => if settings.clock_12h { "12-hour" } else { "24-hour" }.to_string(),
+            Self::TimeZone => if settings.utc_times { "UTC" } else { "local" }.to_string(),
+            Self::SessionBudget => budget_label(settings.session_budget_mins),
+            Self::OrchestrationBudget => budget_label(settings.orchestration_budget_mins),
+            Self::SessionCostBudget => cost_budget_label(settings.session_cost_budget_cents),
+            Self::DailyCostBudget => cost_budget_label(settings.daily_cost_budget_cents),
+            Self::CostAlertDesktop => on_off(settings.cost_alert_desktop),
+            Self::CheckUpdates => on_off(settings.check_updates),
+            Self::ShowToolResults => on_off(settings.show_tool_results),
+            Self::ShowAssistantMessages => on_off(settings.show_assistant_messages),
+        }
+    }
+
+    /// Step the value up (`forward`) or down, clamped to its range. Toggles flip either way.
+    ///
+    /// # Functional Core
+    /// Pure function.
+    pub fn adjust(self, settings: &Settings, forward: bool) -> Settings {
+        let mut next = settings.clone();
+        match self {
+            Self::TickRate => {
+                let (min, max) = (MIN_TICK.as_millis() as u64, MAX_TICK.as_millis() as u64);
+                let tick = if forward {
+                    settings.tick_ms.saturating_add(TICK_STEP_MS)
+                } else {
+                    settings.tick_ms.saturating_sub(TICK_STEP_MS)
+                };
+                next.tick_ms = tick.clamp(min, max);
+            }
+            Self::StaleTimeout => {
+                let mins = if forward {
+                    settings.stale_timeout_mins.saturating_add(1)
+                } else {
+                    settings.stale_timeout_mins.saturating_sub(1)
+                };
+                next.stale_timeout_mins = mins.clamp(*STALE_MINUTES.start(), *STALE_MINUTES.end());
+            }
+            Self::ErrorBurstFailures => {
+                let failures = if forward {
+                    settings.error_burst_failures.saturating_add(1)
+                } else {
+                    settings.error_burst_failures.saturating_sub(1)
+                };
+                next.error_burst_failures = failures.clamp(*BURST_FAILURES.start(), *BURST_FAILURES.end());
+            }
+            Self::ErrorBurstWindow => {
+                let secs = if forward {
+                    settings.error_burst_window_secs.saturating_add(BURST_WINDOW_STEP_SECS)
+                } else {
+                    settings.error_burst_window_secs.saturating_sub(BURST_WINDOW_STEP_SECS)
+                };
+                next.error_burst_window_secs = secs.clamp(*BURST_WINDOW_SECS.start(), *BURST_WINDOW_SECS.end());
+            }
+            Self::LongToolCall => {
+                let mins = if forward {
+                    settings.long_tool_call_mins.saturating_add(1)
+                } else {
+                    settings.long_tool_call_mins.saturating_sub(1)
+                };
+                next.long_tool_call_mins = mins.min(LONG_TOOL_CALL_MAX_MINS);
+            }
+            Self::DedupWindow => {
+                let ms = if forward {
+                    settings.dedup_window_ms.saturating_add(DEDUP_WINDOW_STEP_MS)
+                } else {
+                    settings.dedup_window_ms.saturating_sub(DEDUP_WINDOW_STEP_MS)
+                };
+                next.dedup_window_ms = ms.min(DEDUP_WINDOW_MAX_MS);
+            }
+            Self::ShowDuplicates => next.show_duplicates = !settings.show_duplicates,
+            Self::ReorderSkew => {
+                let ms = if forward {
+                    settings.reorder_skew_ms.saturating_add(REORDER_SKEW_STEP_MS)
+                } else {
+                    settings.reorder_skew_ms.saturating_sub(REORDER_SKEW_STEP_MS)
+                };
+                next.reorder_skew_ms = ms.min(REORDER_SKEW_MAX_MS);
+            }
+            Self::IdleThrottle => next.idle_throttle = !settings.idle_throttle,
+            Self::Clock => next.clock_12h = !settings.clock_12h,
+            Self::TimeZone => next.utc_times = !settings.utc_times,
+            Self::SessionBudget => next.session_budget_mins = step_budget(settings.session_budget_mins, forward),
+            Self::OrchestrationBudget => {
+                next.orchestration_budget_mins = step_budget(settings.orchestration_budget_mins, forward);
+            }
+            Self::SessionCostBudget => {
+                next.session_cost_budget_cents = step_cost_budget(settings.session_cost_budget_cents, forward);
+            }
+            Self::DailyCostBudget => {
+                next.daily_cost_budget_cents = step_cost_budget(settings.daily_cost_budget_cents, forward);
+            }
+            Self::CostAlertDesktop => next.cost_alert_desktop = !settings.cost_alert_desktop,
+            Self::CheckUpdates => next.check_updates = !settings.check_updates,
+            Self::ColorMode => {
+                next.color_mode = if forward {
+                    settings.color_mode.next()
+                } else {
+                    settings.color_mode.next().next()
+                };
+            }
+            Self::ShowToolResults => next.show_tool_results = !settings.show_tool_results,
+            Self::ShowAssistantMessages => next.show_assistant_messages = !settings.show_assistant_messages,
+        }
+        next
+    }
+}
+
+/// Settings as currently in effect.
+pub fn current(state: &AppState) -> Settings {
+    Settings {
+        tick_ms: state.meta.tick_rate.as_millis() as u64,
+        idle_throttle: state.meta.idle_throttle,
+        stale_timeout_mins: state.meta.stale_timeout.num_minutes() as u64,
+        error_burst_failures: state.meta.error_burst.failures,
+        error_burst_window_secs: state.meta.error_burst.window.num_seconds() as u64,
+        long_tool_call_mins: state.meta.long_tool_call.map_or(0, |d| d.num_minutes() as u64),
+        dedup_window_ms: state.domain.dedup.window().num_milliseconds() as u64,
+        show_duplicates: state.meta.show_duplicates,
+        reorder_skew_ms: state.meta.reorder_skew.num_milliseconds() as u64,
+        clock_12h: state.ui.time_format.hour12,
+        utc_times: state.ui.time_format.utc,
+        session_budget_mins: state.meta.budget.session.map_or(0, |d| d.num_minutes() as u64),
+        orchestration_budget_mins: state.meta.budget.orchestration.map_or(0, |d| d.num_minutes() as u64),
+        session_cost_budget_cents: state.meta.cost_budget.session_cents.unwrap_or(0),
+        daily_cost_budget_cents: state.meta.cost_budget.daily_cents.unwrap_or(0),
+        cost_alert_webhook: state.meta.cost_budget.webhook.clone(),
+        cost_alert_desktop: state.meta.cost_budget.desktop,
+        check_updates: state.meta.check_updates,
+        show_tool_results: state.ui.event_filters.tool_results,
+        show_assistant_messages: state.ui.event_filters.assistant_messages,
+        color_mode: state.ui.color_mode,
+        dashboard: state.ui.dashboard_layout.clone(),
+        channels: state.ui.event_channels.definitions().to_vec(),
+        severity: state.ui.severity_rules.definitions().to_vec(),
+        extra_logs: state.meta.extra_logs.clone(),
+        agent_kinds: state.ui.agent_kinds.config().clone(),
+        truncation: state.meta.truncation,
+    }
+}
+
+/// Put `settings` into effect. Out-of-range values (hand-edited config) are clamped.
+pub fn apply(state: &mut AppState, settings: &Settings) {
+    let tick = Duration::from_millis(settings.tick_ms);
+    state.meta.tick_rate = tick.clamp(MIN_TICK, MAX_TICK);
+    state.meta.idle_throttle = settings.idle_throttle;
+    state.meta.check_updates = settings.check_updates;
+    let mins = settings.stale_timeout_mins.clamp(*STALE_MINUTES.start(), *STALE_MINUTES.end());
+    state.meta.stale_timeout = chrono::Duration::minutes(mins as i64);
+    state.meta.error_burst = burst_threshold(settings);
+    state.meta.long_tool_call = match settings.long_tool_call_mins.min(LONG_TOOL_CALL_MAX_MINS) {
+        0 => None,
+        mins => Some(chrono::Duration::minutes(mins as i64)),
+    };
+    let dedup_ms = settings.dedup_window_ms.min(DEDUP_WINDOW_MAX_MS);
+    state.domain.dedup.set_window(chrono::Duration::milliseconds(dedup_ms as i64));
+    state.meta.show_duplicates = settings.show_duplicates;
+    let skew_ms = settings.reorder_skew_ms.min(REORDER_SKEW_MAX_MS);
+    state.meta.reorder_skew = chrono::Duration::milliseconds(skew_ms as i64);
+    state.ui.event_filters.tool_results = settings.show_tool_results;
+    state.ui.event_filters.assistant_messages = settings.show_assistant_messages;
+    state.ui.color_mode = settings.color_mode;
+    state.ui.dashboard_layout = settings.dashboard.normalized();
+    if state.ui.event_channels.definitions() != settings.channels.as_slice() || state.meta.extra_logs != settings.extra_logs {
+        let (channels, mut errors) = EventChannels::from_config(&settings.channels);
+        state.ui.event_channels = channels.with_logs(settings.extra_logs.iter().map(|log| log.name.as_str()));
+        errors.extend(settings.extra_logs.iter().filter_map(|log| {
+            let color = log.color.as_deref()?;
+            severity::color(color).is_none().then(|| RuleError::UnknownStyle {
+                rule: format!("log {}", log.name),
+                word: color.to_string(),
+            })
+        }));
+        state.meta.extra_logs = settings.extra_logs.clone();
+        state.meta.errors.extend(errors.iter().map(ToString::to_string));
+    }
+    state.meta.truncation = TruncationLimits {
+        prompt: settings.truncation.prompt.max(TRUNCATION_MIN_CHARS),
+        tool_input: settings.truncation.tool_input.max(TRUNCATION_MIN_CHARS),
+        content: settings.truncation.content.max(TRUNCATION_MIN_CHARS),
+    };
+    if state.ui.severity_rules.definitions() != settings.severity.as_slice() {
+        let (rules, errors) = SeverityRules::from_config(&settings.severity);
+        state.ui.severity_rules = rules;
+        state.meta.errors.extend(errors.iter().map(ToString::to_string));
+    }
+    if state.ui.agent_kinds.config() != &settings.agent_kinds {
+        let (kinds, errors) = AgentKindStyles::from_config(&settings.agent_kinds);
+        state.ui.agent_kinds = kinds;
+        state.meta.errors.extend(errors.iter().map(ToString::to_string));
+    }
+    let zone_changed = state.ui.time_format.utc != settings.utc_times;
+    state.ui.time_format = TimeFormat { utc: settings.utc_times, hour12: settings.clock_12h };
+    if zone_changed {
+        // Sessions fall on different calendar days in the other zone
+        update::apply_project_filter(state);
+    }
+    let budget = |mins: u64| match mins.min(BUDGET_MAX_MINS) {
+        0 => None,
+        mins => Some(chrono::Duration::minutes(mins as i64)),
+    };
+    state.meta.budget = DurationBudget {
+        session: budget(settings.session_budget_mins),
+        orchestration: budget(settings.orchestration_budget_mins),
+    };
+    let cents = |cents: u64| Some(cents.min(COST_BUDGET_MAX_CENTS)).filter(|&c| c > 0);
+    state.meta.cost_budget = CostBudget {
+        session_cents: cents(settings.session_cost_budget_cents),
+        daily_cents: cents(settings.daily_cost_budget_cents),
+        webhook: settings.cost_alert_webhook.clone().filter(|url| !url.trim().is_empty()),
+        desktop: settings.cost_alert_desktop,
+    };
+}
+
+/// Pure function: a millisecond window as `1.5s`, or `off` at zero.
+fn window_label(ms: u64) -> String {
+    match ms {
+        0 => "off".to_string(),
+        ms => format!("{:.1}s", ms as f64 / 1000.0),
+    }
+}
+
+/// Pure function: a budget in minutes as `45m` / `2h0m`, or `none` at zero.
+fn budget_label(mins: u64) -> String {
+    match mins.min(BUDGET_MAX_MINS) {
+        0 => "none".to_string(),
+        mins => budget::minutes_label(chrono::Duration::minutes(mins as i64)),
+    }
+}
+
+/// Pure function: one budget step up or down, clamped to the accepted range.
+fn step_budget(mins: u64, forward: bool) -> u64 {
+    let mins = if forward { mins.saturating_add(BUDGET_STEP_MINS) } else { mins.saturating_sub(BUDGET_STEP_MINS) };
+    mins.min(BUDGET_MAX_MINS)
+}
+
+/// Pure function: a USD budget in cents as `$25.00`, or `none` at zero.
+fn cost_budget_label(cents: u64) -> String {
+    match cents.min(COST_BUDGET_MAX_CENTS) {
+        0 => "none".to_string(),
+        cents => format_cost_usd(cents),
+    }
+}
+
+/// Pure function: one USD budget step up or down, clamped to the accepted range.
+fn step_cost_budget(cents: u64, forward: bool) -> u64 {
+    let cents =
+        if forward { cents.saturating_add(COST_BUDGET_STEP_CENTS) } else { cents.saturating_sub(COST_BUDGET_STEP_CENTS) };
+    cents.min(COST_BUDGET_MAX_CENTS)
+}
+
+/// Pure function: burst threshold from settings, clamped to the accepted ranges.
+fn burst_threshold(settings: &Settings) -> BurstThreshold {
+    let secs = settings.error_burst_window_secs.clamp(*BURST_WINDOW_SECS.start(), *BURST_WINDOW_SECS.end());
+    BurstThreshold {
+        failures: settings.error_burst_failures.clamp(*BURST_FAILURES.start(), *BURST_FAILURES.end()),
+        window: chrono::Duration::seconds(secs as i64),
+    }
+}
+
+/// Read settings from the config file. A missing file yields defaults.
+/// I/O: reads `path`.
+pub fn load_settings(path: &Path) -> Result<Settings, ConfigError> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(serde_json::from_str(&content)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Settings::default()),
+        Err(e) => Err(ConfigError::Io { path: path.display().to_string(), message: e.to_string() }),
+    }
+}
+
+/// Write settings into the config file, keeping any other keys (path overrides) intact.
+/// I/O: creates the parent directory if needed.
+pub fn save_settings(path: &Path, settings: &Settings) -> Result<(), ConfigError> {
+    let io_err = |e: std::io::Error| ConfigError::Io { path: path.display().to_string(), message: e.to_string() };
+    let mut config = match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str::<serde_json::Value>(&content)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::json!({}),
+        Err(e) => return Err(io_err(e)),
+    };
+    let serde_json::Value::Object(ref mut map) = config else {
+        return Err(ConfigError::Json("config root is not an object".to_string()));
+    };
+    if let serde_json::Value::Object(fields) = serde_json::to_value(settings)? {
+        map.extend(fields);
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(io_err)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&config)?).map_err(io_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::panels::{PanelKind, PanelRow, PanelSlot};
+
+    #[test]
+    fn adjust_clamps_and_toggles() {
+        let settings = Settings { tick_ms: 30, ..Settings::default() };
+        assert_eq!(SettingField::TickRate.adjust(&settings, false).tick_ms, 20);
+        assert_eq!(SettingField::TickRate.adjust(&settings, true).tick_ms, 80);
+
+        let settings = Settings { stale_timeout_mins: 120, ..Settings::default() };
+        assert_eq!(SettingField::StaleTimeout.adjust(&settings, true).stale_timeout_mins, 120);
+
+        assert!(!SettingField::ShowToolResults.adjust(&Settings::default(), true).show_tool_results);
+
+        let settings = Settings { error_burst_window_secs: 30, error_burst_failures: 1, ..Settings::default() };
+        assert_eq!(SettingField::ErrorBurstWindow.adjust(&settings, false).error_burst_window_secs, 30);
+        assert_eq!(SettingField::ErrorBurstWindow.adjust(&settings, true).error_burst_window_secs, 60);
+        assert_eq!(SettingField::ErrorBurstFailures.adjust(&settings, false).error_burst_failures, 1);
+
+        let settings = Settings { dedup_window_ms: 0, ..Settings::default() };
+        assert_eq!(SettingField::DedupWindow.value(&settings), "off");
+        assert_eq!(SettingField::DedupWindow.adjust(&settings, false).dedup_window_ms, 0);
+        assert_eq!(SettingField::DedupWindow.adjust(&settings, true).dedup_window_ms, 500);
+    }
+
+    #[test]
+    fn apply_then_current_round_trips() {
+        let mut state = AppState::new();
+        assert_eq!(current(&state), Settings::default());
+
+        let settings = Settings {
+            tick_ms: 100,
+            idle_throttle: false,
+            stale_timeout_mins: 3,
+            error_burst_failures: 8,
+            error_burst_window_secs: 90,
+            long_tool_call_mins: 0,
+            dedup_window_ms: 0,
+            show_duplicates: true,
+            reorder_skew_ms: 250,
+            clock_12h: true,
+            utc_times: true,
+            session_budget_mins: 45,
+            orchestration_budget_mins: 120,
+            session_cost_budget_cents: 500,
+            daily_cost_budget_cents: 2_500,
+            cost_alert_webhook: Some("https://hooks.example.com/loom".into()),
+            cost_alert_desktop: true,
+            check_updates: true,
+            show_tool_results: false,
+            show_assistant_messages: true,
+            color_mode: ColorMode::NoColor,
+            dashboard: DashboardLayout {
+                rows: vec![PanelRow {
+                    height: 1,
+                    panels: vec![PanelSlot { panel: PanelKind::Errors, width: 2 }, PanelSlot { panel: PanelKind::HotFiles, width: 1 }],
+                }],
+            },
+            channels: vec!["tests: tool:Bash detail~\"cargo test\"".into(), "broken".into()],
+            severity: vec!["bold red: detail~\"DROP TABLE\"".into()],
+            extra_logs: vec![ExtraLog { name: "devserver".into(), path: "/tmp/dev.log".into(), color: Some("mauve".into()) }],
+            agent_kinds: [("loom".to_string(), KindStyle { icon: Some("L".into()), color: Some("teal".into()) })].into(),
+            truncation: TruncationLimits { prompt: 1000, tool_input: 8000, content: 64_000 },
+        };
+        apply(&mut state, &settings);
+        assert_eq!(current(&state), settings);
+        assert_eq!(state.ui.event_channels.channels().len(), 2);
+        assert!(state.meta.errors.iter().any(|e| e.contains("broken")));
+        assert!(state.meta.errors.iter().any(|e| e.contains("mauve")));
+    }
+
+    #[test]
+    fn save_keeps_other_config_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("loom-tui").join("config.json");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, r#"{"archive_dir": "/archives", "tick_ms": 500}"#).unwrap();
+
+        let settings = Settings { tick_ms: 100, ..Settings::default() };
+        save_settings(&path, &settings).unwrap();
+
+        assert_eq!(load_settings(&path).unwrap(), settings);
+        let raw: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(raw["archive_dir"], "/archives");
+    }
+
+    #[test]
+    fn missing_file_loads_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(load_settings(&dir.path().join("none.json")).unwrap(), Settings::default());
+    }
+}
@@ -0,0 +1,137 @@
+//! Severity styles: config rules that color dangerous events in the event stream.
+//! Style and rule syntax: `docs/configuration.md`, "Severity".
+
+use std::str::FromStr;
+
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::app::channels::EventRule;
+use crate::error::RuleError;
+use crate::model::{Theme, TranscriptEvent};
+
+/// Pure function: a color name from the theme palette, or `#rrggbb`.
+pub(crate) fn color(word: &str) -> Option<Color> {
+    let color = match word {
+        "red" => Theme::ERROR,
+        "green" => Theme::SUCCESS,
+        "yellow" => Theme::WARNING,
+        "blue" => Theme::INFO,
+        "cyan" | "teal" => Theme::ACCENT,
+        "orange" | "amber" => Theme::ACCENT_WARM,
+        "purple" | "magenta" => Theme::ACCENT_PURPLE,
+        "white" => Theme::TEXT,
+        "gray" | "grey" => Theme::MUTED_TEXT,
+        hex if hex.starts_with('#') && hex.len() == 7 => Color::from_str(hex).ok()?,
+        _ => return None,
+    };
+    Some(color)
+}
+
+/// Pure function: parse the style words of a definition.
+fn parse_style(owner: &str, words: &str) -> Result<Style, RuleError> {
+    let unknown = |word: &str| RuleError::UnknownStyle { rule: owner.to_string(), word: word.to_string() };
+    let mut style = Style::default();
+    let mut words = words.split_whitespace().map(str::to_lowercase);
+    while let Some(word) = words.next() {
+        style = match word.as_str() {
+            "bold" => style.add_modifier(Modifier::BOLD),
+            "italic" => style.add_modifier(Modifier::ITALIC),
+            "underline" => style.add_modifier(Modifier::UNDERLINED),
+            "dim" => style.add_modifier(Modifier::DIM),
+            "reversed" => style.add_modifier(Modifier::REVERSED),
+            "on" => {
+                let bg = words.next().ok_or_else(|| unknown("on"))?;
+                style.bg(color(&bg).ok_or_else(|| unknown(&bg))?)
+            }
+            other => style.fg(color(other).ok_or_else(|| unknown(other))?),
+        };
+    }
+    Ok(style)
+}
+
+/// A style and the events it applies to.
+#[derive(Debug, Clone)]
+pub struct SeverityRule {
+    pub style: Style,
+    rule: EventRule,
+}
+
+impl SeverityRule {
+    /// Parse a `style: rule` definition.
+    pub fn parse(definition: &str) -> Result<Self, RuleError> {
+        let Some((words, rule)) = definition.split_once(':').filter(|(words, _)| !words.trim().is_empty()) else {
+            return Err(RuleError::MissingStyle(definition.to_string()));
+        };
+        let owner = format!("severity {}", words.trim());
+        Ok(Self { style: parse_style(&owner, words)?, rule: EventRule::parse(&owner, rule)? })
+    }
+}
+
+/// The configured severity rules.
+#[derive(Debug, Clone, Default)]
+pub struct SeverityRules {
+    /// Definitions as written in the config, unusable ones included, so saving
+    /// settings does not drop them
+    definitions: Vec<String>,
+    rules: Vec<SeverityRule>,
+}
+
+impl SeverityRules {
+    /// Parse the config's definitions. Unusable ones are left out and reported.
+    pub fn from_config(definitions: &[String]) -> (Self, Vec<RuleError>) {
+        let mut errors = Vec::new();
+        let rules = definitions
+            .iter()
+            .filter_map(|definition| SeverityRule::parse(definition).map_err(|e| errors.push(e)).ok())
+            .collect();
+        (Self { definitions: definitions.to_vec(), rules }, errors)
+    }
+
+    pub fn definitions(&self) -> &[String] {
+        &self.definitions
+    }
+
+    /// Style of the first rule the event matches.
+    pub fn style_for(&self, event: &TranscriptEvent) -> Option<Style> {
+        self.rules.iter().find(|rule| rule.rule.matches(event)).map(|rule| rule.style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ToolName, TranscriptEventKind};
+    use chrono::Utc;
+
+    fn bash(command: &str) -> TranscriptEvent {
+        TranscriptEvent::new(
+            Utc::now(),
+            TranscriptEventKind::ToolUse { tool_name: ToolName::new("Bash"), input_summary: command.into() },
+        )
+    }
+
+    #[test]
+    fn first_matching_rule_styles_the_event() {
+        let definitions: Vec<String> = [
+            r#"bold white on red: detail~"DROP TABLE""#,
+            r#"#ffaa00: tool:Bash"#,
+            "blinking: tool:Bash",
+            "on: tool:Bash",
+            "no style here",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let (rules, errors) = SeverityRules::from_config(&definitions);
+
+        assert_eq!(
+            rules.style_for(&bash("psql -c 'DROP TABLE users'")),
+            Some(Style::default().fg(Theme::TEXT).bg(Theme::ERROR).add_modifier(Modifier::BOLD))
+        );
+        assert_eq!(rules.style_for(&bash("ls")), Some(Style::default().fg(Color::Rgb(255, 170, 0))));
+        assert_eq!(rules.definitions().len(), 5);
+        assert!(matches!(&errors[0], RuleError::UnknownStyle { word, .. } if word == "blinking"));
+        assert!(matches!(&errors[1], RuleError::UnknownStyle { word, .. } if word == "on"));
+        assert!(matches!(errors[2], RuleError::MissingStyle(_)));
+    }
+}
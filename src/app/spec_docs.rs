@@ -0,0 +1,124 @@
+//! Spec / plan preview.
+//!
+//! Orchestrations write their intent as markdown under `.claude/specs/` and
+//! `.claude/plans/`. The watcher follows the one in use — the file the task
+//! graph names in `plan_file` / `spec_file`, else the newest plan, else the
+//! newest spec — and `S` shows it next to the Dashboard with the lines about
+//! the running tasks highlighted.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::model::{Task, TaskGraph, TaskStatus};
+
+/// Directories under `.claude/` searched when the graph names no file, in
+/// order of preference.
+pub const DOC_DIRS: [&str; 2] = ["plans", "specs"];
+
+/// Largest document read; a bigger one is cut here.
+pub const MAX_DOC_BYTES: usize = 256 * 1024;
+
+/// The spec or plan being previewed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecDoc {
+    pub path: PathBuf,
+    /// Path shown in the panel title (`plans/auth.md`)
+    pub name: String,
+    pub content: String,
+}
+
+/// Pure function: the `.claude` directory holding the task graph's state dir.
+pub fn claude_dir(task_graph: &Path) -> PathBuf {
+    task_graph.parent().and_then(Path::parent).unwrap_or(Path::new(".")).to_path_buf()
+}
+
+/// The document to preview and its modification time.
+/// Named files resolve against the project root (the `.claude` dir's parent).
+/// I/O: stats the named files and lists `DOC_DIRS`.
+pub fn find_active(claude_dir: &Path, named: &[String]) -> Option<(PathBuf, SystemTime)> {
+    let root = claude_dir.parent().unwrap_or(Path::new("."));
+    let modified = |path: &Path| path.metadata().and_then(|m| m.modified()).ok();
+    if let Some(found) = named.iter().map(|name| root.join(name)).find_map(|path| modified(&path).map(|at| (path, at))) {
+        return Some(found);
+    }
+    DOC_DIRS.iter().find_map(|dir| {
+        let mut dirs = vec![claude_dir.join(dir)];
+        let mut newest: Option<(PathBuf, SystemTime)> = None;
+        while let Some(dir) = dirs.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+            for path in entries.flatten().map(|entry| entry.path()) {
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "md") {
+                    let Some(at) = modified(&path) else { continue };
+                    if newest.as_ref().is_none_or(|(_, newest)| at > *newest) {
+                        newest = Some((path, at));
+                    }
+                }
+            }
+        }
+        newest
+    })
+}
+
+/// Read a document for the preview, cut at `MAX_DOC_BYTES`.
+/// I/O: reads `path`.
+pub fn load(claude_dir: &Path, path: &Path) -> std::io::Result<SpecDoc> {
+    let bytes = std::fs::read(path)?;
+    let content = String::from_utf8_lossy(&bytes[..bytes.len().min(MAX_DOC_BYTES)]).into_owned();
+    let name = path.strip_prefix(claude_dir).unwrap_or(path).display().to_string();
+    Ok(SpecDoc { path: path.to_path_buf(), name, content })
+}
+
+/// Pure function: lowercase words, keeping `-` and `_` (`T3`, `auth-flow`).
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Pure function: whether a document line is about `task` — it names the
+/// task id as a word or contains the task description.
+pub fn mentions_task(line: &str, task: &Task) -> bool {
+    let id = task.id.as_str().to_lowercase();
+    if words(line).any(|word| word == id) {
+        return true;
+    }
+    let description = task.description.trim().to_lowercase();
+    !description.is_empty() && line.to_lowercase().contains(&description)
+}
+
+/// Pure function: the graph's running tasks.
+pub fn running_tasks(graph: Option<&TaskGraph>) -> Vec<&Task> {
+    graph.into_iter().flat_map(TaskGraph::flat_tasks).filter(|task| task.status == TaskStatus::Running).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_file_wins_over_the_newest_plan() {
+        let tmp = tempfile::tempdir().unwrap();
+        let claude = tmp.path().join(".claude");
+        std::fs::create_dir_all(claude.join("plans/auth")).unwrap();
+        std::fs::create_dir_all(claude.join("specs")).unwrap();
+        std::fs::write(claude.join("specs/auth.md"), "# Spec").unwrap();
+        std::fs::write(claude.join("plans/auth/plan.md"), "# Plan").unwrap();
+
+        let (path, _) = find_active(&claude, &[]).unwrap();
+        assert_eq!(path, claude.join("plans/auth/plan.md"));
+        assert_eq!(load(&claude, &path).unwrap().name, "plans/auth/plan.md");
+
+        let named = [".claude/missing.md".to_string(), ".claude/specs/auth.md".to_string()];
+        assert_eq!(find_active(&claude, &named).unwrap().0, claude.join("specs/auth.md"));
+    }
+
+    #[test]
+    fn lines_mention_a_task_by_id_or_description() {
+        let task = Task::new("T3", "Wire the main loop".into(), TaskStatus::Running);
+        assert!(mentions_task("- [ ] **T3**: loop", &task));
+        assert!(mentions_task("Then wire the main loop to the watcher", &task));
+        assert!(!mentions_task("- [ ] T30: docs", &task));
+    }
+}
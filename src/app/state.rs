@@ -1,7 +1,52 @@
-use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 use std::time::Instant;
 
-use crate::model::{Agent, AgentId, ArchivedSession, SessionId, SessionMeta, TaskGraph, TranscriptEvent};
+use crate::app::agent_groups::{self, AgentGrouping};
+use crate::app::anomaly::{AgentBaselines, ToolStats};
+use crate::app::editor::FileRef;
+use crate::app::event_index::EventIndex;
+use crate::app::filters::ViewFilters;
+use crate::app::frame_budget::FrameBudget;
+use crate::app::in_flight::InFlight;
+use crate::app::json_tree::JsonInspector;
+use crate::app::budget::DurationBudget;
+use crate::app::cost::{CostAlerts, CostBudget, DailySpend};
+use crate::app::cost_history::{CostPeriod, CostRecord};
+use crate::app::dedup::Deduplicator;
+use crate::app::api_health::ApiHealth;
+use crate::app::error_burst::{BurstThreshold, ErrorBursts};
+use crate::app::pacing::Pacing;
+use crate::app::activity::{OpenCalls, RunningTool};
+use crate::app::archive_pages::ArchivePages;
+use crate::app::tool_outputs::{OutputPager, ToolOutputIndex};
+use crate::app::calendar::SessionCalendar;
+use crate::app::channels::EventChannels;
+use crate::app::columns::TableColumns;
+use crate::app::sampler::IngestSampler;
+use crate::app::notes::NotesEditor;
+use crate::app::panels::DashboardLayout;
+use crate::app::review::{ReviewDecision, ReviewNote};
+use crate::app::agent_defs::AgentDefinition;
+use crate::app::agent_kinds::AgentKindStyles;
+use crate::app::settings::Settings;
+use crate::app::severity::SeverityRules;
+use crate::app::spec_docs::SpecDoc;
+use crate::app::failures::TaskFailure;
+use crate::app::retries::TaskAttempts;
+use crate::app::task_links::TaskLink;
+use crate::app::toasts::{ToastQueue, ToastStack};
+use crate::git::Diff;
+use crate::view::components::format::TimeFormat;
+use crate::app::undo::UndoAction;
+use crate::app::updates::Release;
+use crate::watcher::health::{Degradation, HealthMap};
+use crate::watcher::logs::ExtraLog;
+use crate::watcher::schema_drift::SchemaDrift;
+use crate::watcher::source::SourceKind;
+use crate::watcher::TruncationLimits;
+use crate::model::{Agent, AgentId, Clock, ColorMode, ArchivedSession, SessionId, SessionMeta, SessionOutcome, SessionPreview, SourceRef, SystemClock, TaskGraph, TaskId, TranscriptEvent, TranscriptEventKind, WaveCompletion};
 
 /// UI state: view mode, focus, scrolling, selections, display flags
 #[derive(Debug, Clone)]
@@ -12,24 +57,60 @@ pub struct UiState {
     /// Task view mode (wave vs kanban) for Dashboard
     pub task_view_mode: TaskViewMode,
 
+    /// Color rendering: normal, high contrast, or none (`--high-contrast`, `--no-color`, settings)
+    pub color_mode: ColorMode,
+
+    /// Timestamp display: time zone and 12/24-hour clock (settings)
+    pub time_format: TimeFormat,
+
+    /// Plain-text single-column log instead of panels, for screen readers (`--linear`)
+    pub linear_log: bool,
+
+    /// Degraded rendering for slow links (`--ssh`, or detected from draw times); see `app::bandwidth`
+    pub low_bandwidth: bool,
+
+    /// Dashboard right half shows the followed agent's activity instead of the event stream (`|`)
+    pub split_view: bool,
+
+    /// Dashboard shows the spec / plan preview beside its panels (`S`)
+    pub show_spec_panel: bool,
+
     /// Current panel focus
     pub focus: PanelFocus,
 
+    /// Agent list sections per agent type and type filter (`T`, `t`)
+    pub agent_grouping: AgentGrouping,
+
+    /// The focused panel fills the content area (`z`)
+    pub zoomed: bool,
+
     /// Show help overlay
     pub show_help: bool,
 
     /// Show agent popup overlay (agent ID if active)
     pub show_agent_popup: Option<AgentId>,
 
-    /// Active filter string (None if no filter)
+    /// Filter of the view in front, being typed or applied (None if no filter)
     pub filter: Option<String>,
 
+    /// Whether `filter` is applied, and the filters of the other views (see `filters`)
+    pub filters: ViewFilters,
+
     /// Auto-scroll mode for event stream
     pub auto_scroll: bool,
 
+    /// Events that arrived out of view while auto-scroll was off ("▲ N new" pill)
+    pub unseen_events: UnseenEvents,
+
+    /// Views whose event panels show one line per event, cut at the panel edge (`w`)
+    pub truncated_events: TruncatedEvents,
+
     /// Scroll offsets for each panel
     pub scroll_offsets: ScrollState,
 
+    /// Largest useful offsets of the wrapped event panels, as last rendered
+    pub scroll_limits: ScrollLimits,
+
     /// Index of selected task in current view's task list
     pub selected_task_index: Option<usize>,
 
@@ -45,20 +126,139 @@ pub struct UiState {
     /// Session ID currently being loaded from disk (shows loading indicator)
     pub loading_session: Option<SessionId>,
 
+    /// Session ID whose preview is being built in the background (Sessions view)
+    pub preview_loading: Option<SessionId>,
+
+    /// Archived session whose next (older) page of events should be streamed in
+    pub loading_events: Option<SessionId>,
+
+    /// The next page of archive metas should be read in (Sessions view)
+    pub loading_metas: bool,
+
+    /// Expanded steps in the agent detail activity tree, keyed by the step's first event timestamp
+    pub expanded_steps: HashSet<chrono::DateTime<chrono::Utc>>,
+    /// Whole text of clipped events in expanded steps, read back from their transcripts
+    pub full_texts: HashMap<SourceRef, String>,
+
     /// Prompt popup state (Closed or Open with scroll offset)
     pub prompt_popup: PromptPopupState,
 
     /// Layout picker popup state
     pub layout_picker: LayoutPickerState,
 
-    /// Delete confirm popup state
-    pub delete_confirm: DeleteConfirmState,
+    /// Yes/No dialog guarding a destructive action
+    pub confirm: ConfirmState,
+
+    /// Quick-switch palette state (Ctrl+P)
+    pub palette: PaletteState,
+
+    /// `:` command line state
+    pub command: CommandState,
+
+    /// Settings overlay state (`,`)
+    pub settings: SettingsState,
+
+    /// Event kinds shown in the live event streams (Dashboard, agent popup)
+    pub event_filters: EventFilters,
+
+    /// Named event channels from the config and the one the Dashboard event stream shows (`c`)
+    pub event_channels: EventChannels,
+
+    /// Styles the config gives events matching its severity rules
+    pub severity_rules: SeverityRules,
+
+    /// Icons and colors of agent kinds, with the config's overrides
+    pub agent_kinds: AgentKindStyles,
+
+    /// Status toast (command outcome, undo, watcher notices), shown until the next key press
+    pub command_message: Option<CommandMessage>,
+
+    /// End-of-session toasts, shown until acted on or dismissed (see `toasts`)
+    pub session_toasts: ToastQueue,
+
+    /// Transient info / warning / error toasts, dropped on Tick once expired
+    pub toasts: ToastStack,
+
+    /// Column selection and width offsets per table (`(` `)` `<` `>` `=`)
+    pub table_columns: TableColumns,
 
     /// Sessions marked for bulk delete
     pub marked_sessions: HashSet<SessionId>,
 
+    /// Sessions view groups the table by orchestration run (`R`)
+    pub group_sessions_by_run: bool,
+
+    /// Which projects' archives the Sessions list shows
+    pub project_filter: ProjectFilter,
+
+    /// Day the Sessions list is narrowed to, picked on the activity calendar
+    pub session_date: Option<chrono::NaiveDate>,
+
+    /// Outcome the Sessions list is narrowed to (`O`)
+    pub outcome_filter: Option<SessionOutcome>,
+
     /// Index of selected agent within session detail view's agent list
     pub selected_session_agent_index: Option<usize>,
+
+    /// What the SessionDetail right panel shows (`n` toggles)
+    pub session_detail_tab: SessionDetailTab,
+
+    /// Dashboard panel grid (from config; normalized)
+    pub dashboard_layout: DashboardLayout,
+
+    /// What the TokenDashboard shows (`c` toggles)
+    pub token_dashboard_tab: TokenDashboardTab,
+
+    /// Spend chart granularity on the cost tab (`w` toggles)
+    pub cost_period: CostPeriod,
+
+    /// Built-in notes editor, open over the Notes tab
+    pub notes_editor: Option<NotesEditor>,
+
+    /// `git diff` popup for the selected task's modified files (`D`)
+    pub diff_popup: DiffPopupState,
+
+    /// Pager over a captured tool output (`o` on a step that has one)
+    pub output_pager: Option<OutputPager>,
+
+    /// JSON tree over the selected step's events (`J`)
+    pub json_inspector: Option<JsonInspector>,
+
+    /// Unknown transcript fields report (`:schema`)
+    pub show_schema_report: bool,
+
+    /// Release notes of the newer release (`:changelog`)
+    pub show_changelog: bool,
+
+    /// Note being typed for an approve/reject decision in the Review view
+    pub review_note: Option<ReviewNote>,
+
+    /// Transient "wave complete" banner (cleared on Tick once expired)
+    pub wave_banner: Option<WaveBanner>,
+}
+
+/// Celebration banner shown when every task in a wave completes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaveBanner {
+    pub completion: WaveCompletion,
+    /// Time from the wave's first started task to its completion (None if never observed running)
+    pub elapsed: Option<chrono::Duration>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl WaveBanner {
+    /// How long the banner stays on screen
+    pub const DURATION_SECS: i64 = 8;
+}
+
+/// Plan-mode approval gate: a session called `ExitPlanMode` and is blocked
+/// until the user answers (the tool's result). Cleared when the result arrives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingPlan {
+    pub plan: String,
+    pub requested_at: chrono::DateTime<chrono::Utc>,
+    /// Subagent that asked, None for the session's main transcript
+    pub agent_id: Option<AgentId>,
 }
 
 /// Prompt popup overlay state — encapsulates visibility and scroll offset
@@ -82,6 +282,30 @@ impl PromptPopupState {
     }
 }
 
+/// `git diff` popup state. The main loop runs git for `Loading` and answers
+/// with `AppEvent::GitDiffLoaded`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffPopupState {
+    Closed,
+    Loading(DiffRequest),
+    Open { task_id: TaskId, diff: Diff, scroll: usize },
+}
+
+impl DiffPopupState {
+    pub fn is_open(&self) -> bool {
+        !matches!(self, Self::Closed)
+    }
+}
+
+/// What to diff: a task's modified files since its session started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffRequest {
+    pub task_id: TaskId,
+    pub repo: std::path::PathBuf,
+    pub since: chrono::DateTime<chrono::Utc>,
+    pub paths: Vec<String>,
+}
+
 /// Layout picker popup state
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LayoutPickerState {
@@ -95,14 +319,150 @@ impl LayoutPickerState {
     }
 }
 
-/// Delete confirmation popup state
+/// Quick-switch palette state — query and highlighted result live together
+/// so closing the palette resets both.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteState {
+    Closed,
+    Open { query: String, selected: usize },
+}
+
+/// Sessions list project scope. Archives are namespaced per project on disk;
+/// by default only the project loom-tui was started in is listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ProjectFilter {
+    /// The current project (everything when the current project is unknown)
+    #[default]
+    Current,
+    /// One other project, by project path
+    Project(String),
+    /// Every project's archives
+    All,
+}
+
+impl ProjectFilter {
+    /// Whether an archive from `project_path` is listed, given the current project.
+    pub fn matches(&self, project_path: &str, current: &str) -> bool {
+        match self {
+            Self::Current => current.is_empty() || project_path == current,
+            Self::Project(p) => project_path == p,
+            Self::All => true,
+        }
+    }
+
+    /// Next scope in the cycle: current → each other project (in `projects` order) → all → current.
+    pub fn next(&self, projects: &[String], current: &str) -> Self {
+        let others: Vec<&String> = projects.iter().filter(|p| p.as_str() != current).collect();
+        let after = match self {
+            Self::Current => 0,
+            Self::Project(p) => others.iter().position(|o| *o == p).map_or(others.len(), |i| i + 1),
+            Self::All => return Self::Current,
+        };
+        others.get(after).map_or(Self::All, |p| Self::Project((*p).clone()))
+    }
+
+    /// Short label for the Sessions list title.
+    pub fn label(&self, current: &str) -> String {
+        match self {
+            Self::Current if current.is_empty() => "all projects".to_string(),
+            Self::Current => project_name(current).to_string(),
+            Self::Project(p) => project_name(p).to_string(),
+            Self::All => "all projects".to_string(),
+        }
+    }
+}
+
+/// Last path component of a project path (the repo name).
+pub fn project_name(project_path: &str) -> &str {
+    project_path.rsplit('/').find(|s| !s.is_empty()).unwrap_or(project_path)
+}
+
+impl PaletteState {
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Open { .. })
+    }
+}
+
+/// `:` command line state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandState {
+    Closed,
+    Open { input: String },
+}
+
+impl CommandState {
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Open { .. })
+    }
+}
+
+/// Settings overlay state. `original` holds the values at open time so Esc
+/// can undo the live preview.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingsState {
+    Closed,
+    Open { selected: usize, original: Box<Settings> },
+}
+
+impl SettingsState {
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Open { .. })
+    }
+}
+
+/// Which event kinds the live event streams show (search filtering applies on top).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventFilters {
+    pub tool_results: bool,
+    pub assistant_messages: bool,
+}
+
+impl Default for EventFilters {
+    fn default() -> Self {
+        Self { tool_results: true, assistant_messages: true }
+    }
+}
+
+impl EventFilters {
+    pub fn allows(&self, kind: &TranscriptEventKind) -> bool {
+        match kind {
+            TranscriptEventKind::ToolResult { .. } => self.tool_results,
+            TranscriptEventKind::AssistantMessage { .. } => self.assistant_messages,
+            _ => true,
+        }
+    }
+}
+
+/// Feedback line for an executed `:` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandMessage {
+    pub text: String,
+    pub is_error: bool,
+}
+
+/// What a confirm dialog does on Yes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfirmAction {
+    /// Move archived sessions to the trash
+    DeleteSessions(Vec<SessionId>),
+    /// Quit while agents are still running
+    Quit { running: usize },
+}
+
+/// Confirm dialog state. While open it takes every key; the watcher keeps
+/// feeding events underneath.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum DeleteConfirmState {
+pub enum ConfirmState {
     Closed,
-    Open { session_ids: Vec<SessionId> },
+    /// No is focused until the user moves to Yes
+    Open { action: ConfirmAction, yes_focused: bool },
 }
 
-impl DeleteConfirmState {
+impl ConfirmState {
+    pub fn open(action: ConfirmAction) -> Self {
+        Self::Open { action, yes_focused: false }
+    }
+
     pub fn is_open(&self) -> bool {
         matches!(self, Self::Open { .. })
     }
@@ -117,6 +477,9 @@ pub struct DomainState {
     /// Ring buffer of transcript events (max 10,000)
     pub events: VecDeque<TranscriptEvent>,
 
+    /// Filter text of `events`, kept in step as they arrive (see `app::event_index`)
+    pub event_index: EventIndex,
+
     /// List of archived sessions (meta always available, full data loaded on demand)
     pub sessions: Vec<ArchivedSession>,
 
@@ -128,6 +491,83 @@ pub struct DomainState {
 
     /// Session IDs that were deleted by the user (skip on re-discovery)
     pub deleted_session_ids: HashSet<SessionId>,
+
+    /// Every archive whose meta has been read so far, across all projects;
+    /// `sessions` holds the ones matching `ui.project_filter`
+    pub archive_index: Vec<(std::path::PathBuf, SessionMeta)>,
+
+    /// Archive files on disk and how far their metas are paged in
+    pub archive_pages: ArchivePages,
+
+    /// When each wave was first seen with a started task (for wave durations)
+    pub wave_started_at: BTreeMap<u32, crate::model::MonoStamp>,
+
+    /// Orchestration phases in the order the task graph entered them, with
+    /// when each was first seen (for per-phase timing)
+    pub phase_started_at: Vec<(crate::model::Phase, crate::model::MonoStamp)>,
+
+    /// Reversible destructive actions, most recent last (`u` pops)
+    pub undo_stack: Vec<UndoAction>,
+
+    /// Latest human review decision per task (from and appended to `reviews.jsonl`)
+    pub reviews: BTreeMap<TaskId, ReviewDecision>,
+
+    /// Sessions blocked on a plan approval, by session
+    pub pending_plans: BTreeMap<SessionId, PendingPlan>,
+
+    /// Recent tool failures per session (error burst alerts)
+    pub error_bursts: ErrorBursts,
+
+    /// Recent API errors across sessions (header API health)
+    pub api_health: ApiHealth,
+
+    /// Token throughput and the last pacing advice (`app::pacing`)
+    pub pacing: Pacing,
+
+    /// Today's spend per session (persisted to `spend.json` by the main loop when dirty)
+    pub daily_spend: DailySpend,
+
+    /// Cost budget alerts raised so far and those awaiting delivery
+    pub cost_alerts: CostAlerts,
+
+    /// Per-agent-type history for anomaly flags (startup scan plus sessions archived since)
+    pub agent_baselines: AgentBaselines,
+
+    /// Spend of every archive on disk, across all projects (startup scan)
+    pub archived_costs: Vec<CostRecord>,
+
+    /// Recently seen event signatures, for dropping copies delivered by another source
+    pub dedup: Deduplicator,
+
+    /// Per-session arrival rates; samples runaway sessions (see `app::sampler`)
+    pub sampler: IngestSampler,
+
+    /// Full tool outputs the hook captured to the state dir, by tool call id
+    pub tool_outputs: ToolOutputIndex,
+
+    /// Transcript fields loom-tui does not know, with counts (`:schema`)
+    pub schema_drift: SchemaDrift,
+
+    /// Agents the linker attached to tasks the graph left without one
+    pub task_links: BTreeMap<TaskId, TaskLink>,
+
+    /// Latest status line per task, shown while it runs (see `app::progress`)
+    pub task_progress: BTreeMap<TaskId, String>,
+
+    /// Failed tasks' reasons and evidence events (see `app::failures`)
+    pub task_failures: BTreeMap<TaskId, TaskFailure>,
+
+    /// Each task's attempts, from the graph snapshots seen (see `app::retries`)
+    pub task_attempts: TaskAttempts,
+
+    /// Spec / plan the orchestration works from (`S` previews it)
+    pub spec_doc: Option<SpecDoc>,
+
+    /// The project's `.claude/agents` definitions (see `agent_defs`)
+    pub agent_definitions: Vec<AgentDefinition>,
+
+    /// Orchestration run that sessions confirmed now join (see `runs`)
+    pub current_run: Option<String>,
 }
 
 /// Application metadata: lifecycle, errors, configuration
@@ -136,12 +576,18 @@ pub struct AppMeta {
     /// Error message ring buffer (for status bar display)
     pub errors: VecDeque<String>,
 
+    /// Where update and the views read the time (`SystemClock` unless a test injects one)
+    pub clock: Arc<dyn Clock>,
+
     /// Application start time (for elapsed time display)
     pub started_at: Instant,
 
     /// Project root path (for session metadata)
     pub project_path: String,
 
+    /// Where events are read from (`--stdin` / `--socket`; files by default)
+    pub source: SourceKind,
+
     /// Signal to quit the application
     pub should_quit: bool,
 
@@ -150,9 +596,174 @@ pub struct AppMeta {
 
     /// Archive directory path (for delete tombstones)
     pub archive_dir: Option<std::path::PathBuf>,
+
+    /// Main loop tick interval (adjustable via `:set tick=` and settings)
+    pub tick_rate: std::time::Duration,
+
+    /// Slow the tick to `IDLE_TICK_RATE` while nothing is happening (settings)
+    pub idle_throttle: bool,
+
+    /// Last key press or watcher/background event (Tick excluded); drives idle throttling
+    pub last_activity: Instant,
+
+    /// Confirmed sessions silent for this long are archived as cancelled (FR-010)
+    pub stale_timeout: chrono::Duration,
+
+    /// More tool failures than this within its window raise an error burst alert (settings)
+    pub error_burst: BurstThreshold,
+
+    /// Tool calls unanswered for longer than this are flagged with a warning (settings; None: off)
+    pub long_tool_call: Option<chrono::Duration>,
+
+    /// Keep cross-source duplicates in the event list (settings; for debugging attribution)
+    pub show_duplicates: bool,
+
+    /// Late events up to this much older than the newest are inserted in timestamp order (settings)
+    pub reorder_skew: chrono::Duration,
+
+    /// Expected session run times; the header counts down against them (settings)
+    pub budget: DurationBudget,
+
+    /// USD budgets per session and per day, and where alerts go (settings)
+    pub cost_budget: CostBudget,
+
+    /// Where the day's spend is persisted (None: not persisted)
+    pub spend_path: Option<std::path::PathBuf>,
+
+    /// User config file that settings are saved to (None: settings are not persisted)
+    pub config_path: Option<std::path::PathBuf>,
+
+    /// Watcher thread supervision (checked on Tick)
+    pub watchdog: WatchdogState,
+
+    /// Tick gap tracking for suspend/resume detection (checked on Tick)
+    pub resume: ResumeState,
+
+    /// Terminal hand-off requested by Ctrl+Z or `:sh`; performed and cleared by the main loop
+    pub suspend: Option<Suspend>,
+
+    /// Text to copy to the clipboard; written as OSC 52 and cleared by the main loop
+    pub clipboard: Option<String>,
+
+    /// Background loads and writes under way; quit waits for the writes
+    pub in_flight: InFlight,
+
+    /// Update and render times of slow frames, per panel (see `app::frame_budget`)
+    pub frame_budget: FrameBudget,
+
+    /// Human review decision log next to the task graph (None: decisions are not recorded)
+    pub reviews_path: Option<std::path::PathBuf>,
+
+    /// Extra log files tailed into the event stream (config file only; see `watcher::logs`)
+    pub extra_logs: Vec<ExtraLog>,
+
+    /// Chars of transcript text kept per event (config file only; see `watcher::TruncationLimits`)
+    pub truncation: TruncationLimits,
+
+    /// Look for a newer release on startup (settings; see `updates`)
+    pub check_updates: bool,
+
+    /// Newer release found by the startup check
+    pub newer_release: Option<Release>,
+}
+
+/// Ways of handing the terminal back to the user without quitting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Suspend {
+    /// Stop the process group like a shell's Ctrl+Z; resumes on `fg`
+    Stop,
+    /// Run `$SHELL` in the project root; resumes when it exits
+    Shell,
+    /// Open a file in `$VISUAL` / `$EDITOR`; resumes when the editor exits
+    Editor(FileRef),
+}
+
+/// Watcher thread health as seen by the UI. `update` fills `degraded` on Tick
+/// and raises `restart_requested`; the main loop performs the restart.
+#[derive(Debug, Clone, Default)]
+pub struct WatchdogState {
+    /// Heartbeat map shared with the watcher threads (None when no watcher runs)
+    pub health: Option<HealthMap>,
+    /// Threads currently exited or stalled (drives the header indicator)
+    pub degraded: Vec<(&'static str, Degradation)>,
+    /// A restart is due; cleared by the main loop once performed
+    pub restart_requested: bool,
+    /// When the last restart was requested (for backoff)
+    pub last_restart: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl WatchdogState {
+    /// Minimum gap between restarts, so a thread that dies on startup doesn't spin
+    pub const RESTART_BACKOFF: chrono::Duration = chrono::Duration::seconds(5);
+
+    pub fn is_degraded(&self) -> bool {
+        !self.degraded.is_empty()
+    }
+}
+
+/// Suspend/resume detection. After laptop sleep the first Tick sees a gap far
+/// longer than any tick interval; stale session expiry is then held off for a
+/// grace period so sources can deliver what was written meanwhile.
+#[derive(Debug, Clone, Default)]
+pub struct ResumeState {
+    /// Wall and monotonic time of the previous Tick
+    pub last_tick: Option<(chrono::DateTime<chrono::Utc>, Instant)>,
+    /// Stale session expiry is suppressed until this instant
+    pub grace_until: Option<Instant>,
+}
+
+impl ResumeState {
+    /// A gap between ticks longer than this, on either clock, means the process was suspended
+    pub const SUSPEND_GAP: chrono::Duration = chrono::Duration::seconds(60);
+
+    /// Grace period after a resume (one unconfirmed-session timeout)
+    pub const RESUME_GRACE: std::time::Duration = std::time::Duration::from_secs(30);
+
+    /// Record a Tick at wall time `now` / monotonic time `mono_now`.
+    ///
+    /// # Returns
+    /// The gap since the previous Tick when it indicates a suspend; the grace period starts then.
+    pub fn observe_tick(
+        &mut self,
+        now: chrono::DateTime<chrono::Utc>,
+        mono_now: Instant,
+    ) -> Option<chrono::Duration> {
+        let previous = self.last_tick.replace((now, mono_now));
+        let (wall, mono) = previous?;
+        // Monotonic clocks stop during sleep on Linux and macOS; the wall clock keeps going
+        let mono_gap = chrono::Duration::from_std(mono_now.saturating_duration_since(mono)).unwrap_or_default();
+        let gap = (now - wall).max(mono_gap);
+        if gap <= Self::SUSPEND_GAP {
+            return None;
+        }
+        self.grace_until = Some(mono_now + Self::RESUME_GRACE);
+        Some(gap)
+    }
+
+    /// Whether stale session expiry is held off at `mono_now`.
+    pub fn in_grace(&self, mono_now: Instant) -> bool {
+        self.grace_until.is_some_and(|until| mono_now < until)
+    }
 }
 
-/// Cache state (private): sorted keys, dirty flags, agent tool counts
+impl AppMeta {
+    /// Default main loop tick interval
+    pub const DEFAULT_TICK_RATE: std::time::Duration = std::time::Duration::from_millis(250);
+
+    /// Tick interval while idle (never faster than `tick_rate`)
+    pub const IDLE_TICK_RATE: std::time::Duration = std::time::Duration::from_secs(1);
+
+    /// Quiet period after which the tick slows down
+    pub const IDLE_AFTER: std::time::Duration = std::time::Duration::from_secs(10);
+
+    /// Default stale timeout for confirmed sessions
+    pub const DEFAULT_STALE_TIMEOUT: chrono::Duration = chrono::Duration::minutes(10);
+
+    /// Default limit after which an unanswered tool call is flagged
+    pub const DEFAULT_LONG_TOOL_CALL: chrono::Duration = chrono::Duration::minutes(5);
+}
+
+/// Cache state (private): sorted keys, dirty flags, agent tool counts, session previews
 #[derive(Debug, Clone)]
 struct CacheState {
     /// Cached sorted agent keys (recomputed when dirty)
@@ -163,6 +774,21 @@ struct CacheState {
 
     /// Cached tool counts per agent (incremented on ToolUse events)
     agent_tool_counts: BTreeMap<AgentId, usize>,
+
+    /// Tool uses, results and failures per agent (anomaly flags)
+    agent_tool_stats: BTreeMap<AgentId, ToolStats>,
+
+    /// Tool calls awaiting their result per agent (current activity line)
+    agent_open_calls: BTreeMap<AgentId, OpenCalls>,
+
+    /// Preview summaries for archived sessions (Sessions view quick-preview pane)
+    session_previews: BTreeMap<SessionId, SessionPreview>,
+
+    /// Sessions whose preview failed to load (not retried)
+    failed_previews: HashSet<SessionId>,
+
+    /// Archived sessions per day in the project scope (Sessions view calendar)
+    session_calendar: SessionCalendar,
 }
 
 /// Main application state.
@@ -193,6 +819,12 @@ pub enum ViewState {
 
     /// Token cost dashboard (per-session and per-model breakdown)
     TokenDashboard,
+
+    /// Tasks awaiting human review (approve/reject into `reviews.jsonl`)
+    Review,
+
+    /// Events of all active sessions interleaved on one timeline
+    Timeline,
 }
 
 /// Task view mode for Dashboard
@@ -205,6 +837,26 @@ pub enum TaskViewMode {
     Kanban,
 }
 
+/// Right panel tab in SessionDetail
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionDetailTab {
+    /// Per-agent filtered events (default)
+    Events,
+
+    /// Postmortem notes stored in the archive
+    Notes,
+}
+
+/// Tab shown in the TokenDashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenDashboardTab {
+    /// Per-session and per-model tokens (default)
+    Sessions,
+
+    /// Historical spend per day/week, project and agent type
+    Cost,
+}
+
 /// Panel focus for two-panel layouts
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PanelFocus {
@@ -238,6 +890,75 @@ pub struct ScrollState {
 
     /// Scroll offset for token dashboard left panel (session table)
     pub token_dashboard_left: usize,
+
+    /// Cursor in the review queue
+    pub review: usize,
+
+    /// Scroll offset for the multi-session timeline
+    pub timeline: usize,
+
+    /// Horizontal offsets of the event panels (0: word-wrapped, unless truncated)
+    pub event_stream_x: usize,
+    pub agent_events_x: usize,
+    pub session_detail_right_x: usize,
+}
+
+/// Event panels drawn truncated instead of word-wrapped, per view. Wrapped is
+/// easier to read; truncated keeps one event per line for scanning.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TruncatedEvents {
+    /// Dashboard event stream
+    pub dashboard: bool,
+    /// AgentDetail activity
+    pub agent_detail: bool,
+    /// SessionDetail events
+    pub session_detail: bool,
+}
+
+impl TruncatedEvents {
+    /// Whether `view`'s event panel is truncated; views without one never are.
+    pub fn is_truncated(&self, view: &ViewState) -> bool {
+        match view {
+            ViewState::Dashboard => self.dashboard,
+            ViewState::AgentDetail => self.agent_detail,
+            ViewState::SessionDetail => self.session_detail,
+            _ => false,
+        }
+    }
+
+    /// Flip `view`'s event panel between wrapped and truncated.
+    pub fn toggle(&mut self, view: &ViewState) {
+        let flag = match view {
+            ViewState::Dashboard => &mut self.dashboard,
+            ViewState::AgentDetail => &mut self.agent_detail,
+            ViewState::SessionDetail => &mut self.session_detail,
+            _ => return,
+        };
+        *flag = !*flag;
+    }
+}
+
+/// Per-panel count of events that arrived above the viewport while auto-scroll
+/// was off. Reset once the panel is back at the newest event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnseenEvents {
+    /// Dashboard event stream
+    pub event_stream: usize,
+    /// AgentDetail activity, for the agent it was counted against
+    pub agent: Option<(AgentId, usize)>,
+}
+
+/// Largest useful scroll offset of each word-wrapped event panel (None until drawn).
+/// Wrapped heights depend on the panel width, which only the view knows, so the
+/// view records them on every render and navigation clamps against them.
+#[derive(Debug, Clone, Default)]
+pub struct ScrollLimits {
+    pub event_stream: Cell<Option<usize>>,
+    pub session_detail_right: Cell<Option<usize>>,
+    /// Largest useful horizontal offsets: the widest line's overflow
+    pub event_stream_x: Cell<Option<usize>>,
+    pub agent_events_x: Cell<Option<usize>>,
+    pub session_detail_right_x: Cell<Option<usize>>,
 }
 
 impl DomainState {
@@ -262,18 +983,62 @@ impl Default for UiState {
             show_help: false,
             show_agent_popup: None,
             filter: None,
+            filters: ViewFilters::default(),
             auto_scroll: true,
+            unseen_events: UnseenEvents::default(),
+            truncated_events: TruncatedEvents::default(),
             scroll_offsets: ScrollState::default(),
+            scroll_limits: ScrollLimits::default(),
             selected_task_index: None,
             selected_agent_index: None,
             selected_session_index: None,
             selected_session_id: None,
             loading_session: None,
+            preview_loading: None,
+            loading_events: None,
+            loading_metas: false,
+            expanded_steps: HashSet::new(),
+            full_texts: HashMap::new(),
             prompt_popup: PromptPopupState::Closed,
             layout_picker: LayoutPickerState::Closed,
-            delete_confirm: DeleteConfirmState::Closed,
+            confirm: ConfirmState::Closed,
+            palette: PaletteState::Closed,
+            command: CommandState::Closed,
+            settings: SettingsState::Closed,
+            event_filters: EventFilters::default(),
+            event_channels: EventChannels::default(),
+            severity_rules: SeverityRules::default(),
+            agent_kinds: AgentKindStyles::default(),
+            command_message: None,
+            session_toasts: ToastQueue::default(),
+            toasts: ToastStack::default(),
+            table_columns: TableColumns::default(),
+            split_view: false,
+            show_spec_panel: false,
+            zoomed: false,
+            agent_grouping: AgentGrouping::default(),
+            color_mode: ColorMode::default(),
+            time_format: TimeFormat::default(),
+            linear_log: false,
+            low_bandwidth: false,
             marked_sessions: HashSet::new(),
+            group_sessions_by_run: false,
             selected_session_agent_index: None,
+            session_detail_tab: SessionDetailTab::Events,
+            dashboard_layout: DashboardLayout::default(),
+            token_dashboard_tab: TokenDashboardTab::Sessions,
+            cost_period: CostPeriod::default(),
+            notes_editor: None,
+            diff_popup: DiffPopupState::Closed,
+            output_pager: None,
+            json_inspector: None,
+            show_schema_report: false,
+            show_changelog: false,
+            review_note: None,
+            wave_banner: None,
+            project_filter: ProjectFilter::default(),
+            session_date: None,
+            outcome_filter: None,
         }
     }
 }
@@ -283,10 +1048,36 @@ impl Default for DomainState {
         Self {
             agents: BTreeMap::new(),
             events: VecDeque::with_capacity(10_000),
+            event_index: EventIndex::default(),
             sessions: Vec::new(),
             active_sessions: BTreeMap::new(),
             task_graph: None,
             deleted_session_ids: HashSet::new(),
+            archive_index: Vec::new(),
+            archive_pages: ArchivePages::default(),
+            wave_started_at: BTreeMap::new(),
+            phase_started_at: Vec::new(),
+            undo_stack: Vec::new(),
+            reviews: BTreeMap::new(),
+            pending_plans: BTreeMap::new(),
+            error_bursts: ErrorBursts::default(),
+            api_health: ApiHealth::default(),
+            pacing: Pacing::default(),
+            daily_spend: DailySpend::default(),
+            agent_baselines: AgentBaselines::default(),
+            archived_costs: Vec::new(),
+            cost_alerts: CostAlerts::default(),
+            dedup: Deduplicator::default(),
+            sampler: IngestSampler::default(),
+            tool_outputs: ToolOutputIndex::default(),
+            schema_drift: SchemaDrift::default(),
+            task_links: BTreeMap::new(),
+            task_progress: BTreeMap::new(),
+            task_failures: BTreeMap::new(),
+            task_attempts: TaskAttempts::default(),
+            spec_doc: None,
+            agent_definitions: Vec::new(),
+            current_run: None,
         }
     }
 }
@@ -295,11 +1086,36 @@ impl Default for AppMeta {
     fn default() -> Self {
         Self {
             errors: VecDeque::with_capacity(100),
+            clock: Arc::new(SystemClock),
             started_at: Instant::now(),
             project_path: String::new(),
+            source: SourceKind::default(),
             should_quit: false,
             replay_complete: false,
             archive_dir: None,
+            tick_rate: Self::DEFAULT_TICK_RATE,
+            idle_throttle: true,
+            last_activity: Instant::now(),
+            stale_timeout: Self::DEFAULT_STALE_TIMEOUT,
+            error_burst: BurstThreshold::default(),
+            long_tool_call: Some(Self::DEFAULT_LONG_TOOL_CALL),
+            show_duplicates: false,
+            reorder_skew: crate::app::reorder::DEFAULT_SKEW,
+            budget: DurationBudget::default(),
+            cost_budget: CostBudget::default(),
+            spend_path: None,
+            config_path: None,
+            watchdog: WatchdogState::default(),
+            resume: ResumeState::default(),
+            suspend: None,
+            clipboard: None,
+            in_flight: InFlight::default(),
+            frame_budget: FrameBudget::default(),
+            reviews_path: None,
+            extra_logs: Vec::new(),
+            truncation: TruncationLimits::default(),
+            check_updates: false,
+            newer_release: None,
         }
     }
 }
@@ -310,6 +1126,11 @@ impl Default for CacheState {
             sorted_keys: Vec::new(),
             dirty: true,
             agent_tool_counts: BTreeMap::new(),
+            agent_tool_stats: BTreeMap::new(),
+            agent_open_calls: BTreeMap::new(),
+            session_previews: BTreeMap::new(),
+            failed_previews: HashSet::new(),
+            session_calendar: SessionCalendar::default(),
         }
     }
 }
@@ -342,6 +1163,14 @@ impl AppState {
         }
     }
 
+    /// Read the time from `clock`, starting the app's own timers on it.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.meta.started_at = clock.instant();
+        self.meta.last_activity = clock.instant();
+        self.meta.clock = clock;
+        self
+    }
+
     /// Set project path for session metadata
     pub fn with_project_path(mut self, path: String) -> Self {
         self.meta.project_path = path;
@@ -354,6 +1183,35 @@ impl AppState {
         &self.cache.sorted_keys
     }
 
+    /// Tick interval the main loop should use at `now`: `meta.tick_rate`, or
+    /// `IDLE_TICK_RATE` once nothing has happened for `IDLE_AFTER` and nothing
+    /// on screen is animating (wave banner countdown, loading indicators).
+    pub fn effective_tick_rate(&self, now: Instant) -> std::time::Duration {
+        let animating = self.ui.wave_banner.is_some()
+            || self.ui.loading_session.is_some()
+            || self.ui.loading_events.is_some()
+            || self.ui.loading_metas
+            || self.ui.preview_loading.is_some();
+        let idle = now.saturating_duration_since(self.meta.last_activity) >= AppMeta::IDLE_AFTER;
+        if self.meta.idle_throttle && idle && !animating {
+            self.meta.tick_rate.max(AppMeta::IDLE_TICK_RATE)
+        } else {
+            self.meta.tick_rate
+        }
+    }
+
+    /// Agent shown in the split view's activity pane: the one selected in AgentDetail,
+    /// else the most recently started active agent (first in sorted order).
+    pub fn followed_agent(&self) -> Option<&Agent> {
+        agent_groups::selected_agent(self)
+            .or_else(|| self.sorted_agent_keys().first().and_then(|k| self.domain.agents.get(k)))
+    }
+
+    /// Most recently requested plan approval still waiting on the user.
+    pub fn newest_pending_plan(&self) -> Option<(&SessionId, &PendingPlan)> {
+        self.domain.pending_plans.iter().max_by_key(|(_, p)| p.requested_at)
+    }
+
     /// Recompute cached sorted agent keys. Call after any agent mutation.
     pub fn recompute_sorted_keys(&mut self) {
         let mut keys: Vec<_> = self.domain.agents.keys().cloned().collect();
@@ -390,6 +1248,106 @@ impl AppState {
     pub(crate) fn increment_tool_count(&mut self, id: &AgentId) {
         *self.cache.agent_tool_counts.entry(id.clone()).or_insert(0) += 1;
     }
+
+    /// Tool uses, results and failures seen for an agent.
+    pub fn agent_tool_stats(&self, id: &AgentId) -> ToolStats {
+        self.cache.agent_tool_stats.get(id).copied().unwrap_or_default()
+    }
+
+    /// Count a ToolUse / ToolResult event toward an agent's tool stats and open calls.
+    pub(crate) fn observe_tool_event(&mut self, id: &AgentId, event: &TranscriptEvent) {
+        self.cache.agent_tool_stats.entry(id.clone()).or_default().observe(&event.kind);
+        self.cache.agent_open_calls.entry(id.clone()).or_default().observe(event);
+    }
+
+    /// The open call started by the ToolUse `event`, while its agent runs.
+    pub fn open_tool_call(&self, event: &TranscriptEvent) -> Option<&RunningTool> {
+        let id = event.agent_id.as_ref()?;
+        let running = self.domain.agents.get(id).is_some_and(|agent| agent.finished_at.is_none());
+        self.cache.agent_open_calls.get(id).filter(|_| running).and_then(|calls| calls.opened_by(event))
+    }
+
+    /// Flag the open calls of running agents that have exceeded `limit` as of `now`.
+    ///
+    /// # Returns
+    /// Each call flagged by this pass, with its agent.
+    pub(crate) fn flag_long_tool_calls(
+        &mut self,
+        now: chrono::DateTime<chrono::Utc>,
+        limit: chrono::Duration,
+    ) -> Vec<(AgentId, RunningTool)> {
+        let agents = &self.domain.agents;
+        let mut flagged = Vec::new();
+        for (id, calls) in self.cache.agent_open_calls.iter_mut() {
+            if agents.get(id).is_some_and(|agent| agent.finished_at.is_none()) {
+                flagged.extend(calls.flag_overdue(now, limit).into_iter().map(|call| (id.clone(), call)));
+            }
+        }
+        flagged
+    }
+
+    /// The agent's newest tool call still awaiting its result, while it runs.
+    pub fn running_tool(&self, id: &AgentId) -> Option<&RunningTool> {
+        let running = self.domain.agents.get(id).is_some_and(|agent| agent.finished_at.is_none());
+        self.cache.agent_open_calls.get(id).filter(|_| running).and_then(OpenCalls::current)
+    }
+
+    /// Get cached preview for an archived session.
+    pub fn session_preview(&self, id: &SessionId) -> Option<&SessionPreview> {
+        self.cache.session_previews.get(id)
+    }
+
+    /// Store a session preview (replaces any previous entry).
+    pub fn cache_session_preview(&mut self, id: SessionId, preview: SessionPreview) {
+        self.cache.failed_previews.remove(&id);
+        self.cache.session_previews.insert(id, preview);
+    }
+
+    /// Record that a session preview could not be built, so it is not retried.
+    pub fn mark_preview_failed(&mut self, id: SessionId) {
+        self.cache.failed_previews.insert(id);
+    }
+
+    /// Whether the preview for this session previously failed to load.
+    pub fn is_preview_failed(&self, id: &SessionId) -> bool {
+        self.cache.failed_previews.contains(id)
+    }
+
+    /// Archived sessions per day, as of the last `apply_project_filter`.
+    pub fn session_calendar(&self) -> &SessionCalendar {
+        &self.cache.session_calendar
+    }
+
+    pub(crate) fn set_session_calendar(&mut self, calendar: SessionCalendar) {
+        self.cache.session_calendar = calendar;
+    }
+
+    /// Drop cached preview state for a session (e.g. after delete).
+    pub(crate) fn evict_session_preview(&mut self, id: &SessionId) {
+        self.cache.session_previews.remove(id);
+        self.cache.failed_previews.remove(id);
+    }
+
+    /// Archived session highlighted in the Sessions view whose preview still needs
+    /// a background load: not in memory, not cached, not previously failed.
+    /// Returns `(session_id, archive_path)` for the loader.
+    pub fn pending_session_preview(&self) -> Option<(SessionId, std::path::PathBuf)> {
+        if self.ui.view != ViewState::Sessions {
+            return None;
+        }
+        let idx = self.ui.selected_session_index?;
+        let archive_idx = idx.checked_sub(self.domain.confirmed_active_count())?;
+        let session = self.domain.sessions.get(archive_idx)?;
+        let id = &session.meta.id;
+        if session.complete_data().is_some()
+            || session.path.as_os_str().is_empty()
+            || self.cache.session_previews.contains_key(id)
+            || self.cache.failed_previews.contains(id)
+        {
+            return None;
+        }
+        Some((id.clone(), session.path.clone()))
+    }
 }
 
 impl ScrollState {
@@ -408,6 +1366,10 @@ impl ScrollState {
         self.session_detail_left = 0;
         self.session_detail_right = 0;
         self.token_dashboard_left = 0;
+        self.review = 0;
+        self.event_stream_x = 0;
+        self.agent_events_x = 0;
+        self.session_detail_right_x = 0;
     }
 }
 
@@ -463,6 +1425,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn test_scroll_state_reset() {
         let mut scroll = ScrollState::default();
         scroll.task_list = 10;
@@ -470,6 +1433,7 @@ mod tests {
         scroll.agent_list = 5;
         scroll.agent_events = 15;
         scroll.sessions = 3;
+        scroll.event_stream_x = 40;
 
         scroll.reset();
 
@@ -478,6 +1442,7 @@ mod tests {
         assert_eq!(scroll.agent_list, 0);
         assert_eq!(scroll.agent_events, 0);
         assert_eq!(scroll.sessions, 0);
+        assert_eq!(scroll.event_stream_x, 0);
     }
 
     #[test]
@@ -652,6 +1617,35 @@ mod tests {
         assert!(!state.is_cache_dirty());
     }
 
+    #[test]
+    fn pending_session_preview_only_for_unloaded_highlighted_archive() {
+        use chrono::Utc;
+        use std::path::PathBuf;
+
+        let mut state = AppState::with_view(ViewState::Sessions);
+        let meta = SessionMeta::new("s1", Utc::now(), "/proj".to_string());
+        state.domain.sessions.push(ArchivedSession::new(meta, PathBuf::from("/tmp/s1.json")));
+        state.ui.selected_session_index = Some(0);
+
+        let (sid, path) = state.pending_session_preview().unwrap();
+        assert_eq!(sid.as_str(), "s1");
+        assert_eq!(path, PathBuf::from("/tmp/s1.json"));
+
+        // Cached → nothing to load
+        state.cache_session_preview(sid.clone(), SessionPreview::default());
+        assert!(state.pending_session_preview().is_none());
+
+        // Failed → not retried
+        state.evict_session_preview(&sid);
+        state.mark_preview_failed(sid.clone());
+        assert!(state.pending_session_preview().is_none());
+
+        // Other views never request previews
+        state.evict_session_preview(&sid);
+        state.ui.view = ViewState::Dashboard;
+        assert!(state.pending_session_preview().is_none());
+    }
+
     #[test]
     fn domain_state_no_transcript_agent_map() {
         // Verify removed fields are gone (compile test)
@@ -671,4 +1665,64 @@ mod tests {
         assert!(!meta.should_quit);
         assert!(!meta.replay_complete);
     }
+
+    #[test]
+    fn project_filter_matches_scope() {
+        assert!(ProjectFilter::Current.matches("/a", "/a"));
+        assert!(!ProjectFilter::Current.matches("/b", "/a"));
+        assert!(ProjectFilter::Current.matches("/b", ""), "unknown current project lists everything");
+        assert!(ProjectFilter::Project("/b".into()).matches("/b", "/a"));
+        assert!(!ProjectFilter::Project("/b".into()).matches("/a", "/a"));
+        assert!(ProjectFilter::All.matches("", "/a"));
+    }
+
+    #[test]
+    fn project_filter_cycles_current_others_all() {
+        let projects = vec!["/a".to_string(), "/b".to_string(), "/c".to_string()];
+        let mut filter = ProjectFilter::Current;
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            filter = filter.next(&projects, "/b");
+            seen.push(filter.clone());
+        }
+        assert_eq!(seen, vec![
+            ProjectFilter::Project("/a".into()),
+            ProjectFilter::Project("/c".into()),
+            ProjectFilter::All,
+            ProjectFilter::Current,
+        ]);
+        assert_eq!(ProjectFilter::Current.next(&[], "/b"), ProjectFilter::All);
+    }
+
+    #[test]
+    fn project_filter_label_uses_repo_name() {
+        assert_eq!(ProjectFilter::Current.label("/home/u/dev/app/"), "app");
+        assert_eq!(ProjectFilter::Current.label(""), "all projects");
+        assert_eq!(ProjectFilter::All.label("/home/u/app"), "all projects");
+    }
+
+    #[test]
+    fn tick_slows_when_idle_and_not_animating() {
+        let mut state = AppState::new();
+        let now = state.meta.last_activity;
+        assert_eq!(state.effective_tick_rate(now), AppMeta::DEFAULT_TICK_RATE);
+
+        let later = now + AppMeta::IDLE_AFTER;
+        assert_eq!(state.effective_tick_rate(later), AppMeta::IDLE_TICK_RATE);
+
+        state.ui.loading_session = Some(SessionId::new("s1"));
+        assert_eq!(state.effective_tick_rate(later), AppMeta::DEFAULT_TICK_RATE);
+
+        state.ui.loading_session = None;
+        state.meta.idle_throttle = false;
+        assert_eq!(state.effective_tick_rate(later), AppMeta::DEFAULT_TICK_RATE);
+    }
+
+    #[test]
+    fn idle_tick_never_speeds_up_a_slow_tick_rate() {
+        let mut state = AppState::new();
+        state.meta.tick_rate = std::time::Duration::from_secs(2);
+        let later = state.meta.last_activity + AppMeta::IDLE_AFTER;
+        assert_eq!(state.effective_tick_rate(later), std::time::Duration::from_secs(2));
+    }
 }
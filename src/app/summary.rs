@@ -0,0 +1,144 @@
+//! Heads-up summary line.
+//!
+//! One plain-language line under the header for people only half-watching:
+//! `3 agents active · wave 2/4 · last failure: T7 tests flaky · est 25m left`.
+//! The estimate assumes the remaining tasks go at the pace of the finished
+//! ones since the run's first session started.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::app::budget::minutes_label;
+use crate::app::AppState;
+use crate::model::{TaskGraph, TaskStatus};
+use crate::watcher::truncate_str;
+
+/// Longest failure reason quoted.
+const REASON_MAX_CHARS: usize = 24;
+
+/// Pure function: the summary, or None with nothing running and no tasks.
+pub fn summarize(state: &AppState, now: DateTime<Utc>) -> Option<String> {
+    let graph = state.domain.task_graph.as_ref().filter(|graph| graph.total_tasks() > 0);
+    let sessions = state.domain.confirmed_active_count();
+    if graph.is_none() && sessions == 0 {
+        return None;
+    }
+
+    let mut parts = Vec::new();
+    match state.domain.agents.values().filter(|agent| agent.finished_at.is_none()).count() {
+        0 if sessions > 0 => parts.push("no agents running".to_string()),
+        0 => {}
+        1 => parts.push("1 agent active".to_string()),
+        n => parts.push(format!("{} agents active", n)),
+    }
+    if let Some(graph) = graph {
+        parts.extend(graph_parts(graph));
+        if let Some(left) = run_started(state).and_then(|start| estimate_left(graph, now - start)) {
+            parts.push(format!("est {} left", minutes_label(left)));
+        }
+    }
+    Some(parts.join(" · "))
+}
+
+/// Pure function: wave progress and the last failed task.
+fn graph_parts(graph: &TaskGraph) -> Vec<String> {
+    let total = graph.total_tasks();
+    let mut parts = Vec::new();
+    if graph.completed_tasks() == total {
+        parts.push(format!("all {} tasks done", total));
+    } else {
+        let current = graph.current_wave();
+        let position = graph.waves.iter().position(|wave| wave.number == current).map_or(0, |idx| idx + 1);
+        parts.push(format!("wave {}/{}", position, graph.waves.len()));
+    }
+    let failed = graph.flat_tasks().filter_map(|task| match &task.status {
+        TaskStatus::Failed { reason, .. } => Some((task, reason)),
+        _ => None,
+    });
+    if let Some((task, reason)) = failed.last() {
+        let reason = reason.lines().next().unwrap_or("").trim();
+        let what = if reason.is_empty() { task.description.as_str() } else { reason };
+        parts.push(format!("last failure: {} {}", task.id.as_str(), truncate_str(what, REASON_MAX_CHARS)).trim_end().to_string());
+    }
+    parts
+}
+
+/// Pure function: time left if the remaining tasks take as long each as the
+/// `completed` ones did over `elapsed`; None before the first completes.
+pub fn estimate_left(graph: &TaskGraph, elapsed: Duration) -> Option<Duration> {
+    let completed = graph.completed_tasks() as i64;
+    let remaining = graph.total_tasks() as i64 - completed;
+    if completed == 0 || remaining <= 0 || elapsed <= Duration::zero() {
+        return None;
+    }
+    Some(elapsed * remaining as i32 / completed as i32)
+}
+
+/// Start of the run in progress: its first confirmed session, else the
+/// oldest confirmed active session.
+fn run_started(state: &AppState) -> Option<DateTime<Utc>> {
+    let sessions = || state.domain.confirmed_active_sessions().map(|(_, meta)| meta);
+    let in_run = state
+        .domain
+        .current_run
+        .as_ref()
+        .and_then(|run| sessions().filter(|meta| meta.loom_plan_id.as_ref() == Some(run)).map(|m| m.timestamp).min());
+    in_run.or_else(|| sessions().map(|meta| meta.timestamp).min())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Agent, AgentId, SessionId, SessionMeta, Task, Wave};
+
+    fn graph() -> TaskGraph {
+        let failed = TaskStatus::Failed { reason: "tests flaky\nstack trace".into(), retry_count: 1 };
+        TaskGraph::new(vec![
+            Wave::new(1, vec![Task::new("T1", "Parse".into(), TaskStatus::Completed)]),
+            Wave::new(2, vec![
+                Task::new("T2", "Wire".into(), TaskStatus::Running),
+                Task::new("T7", "Tests".into(), failed),
+            ]),
+            Wave::new(3, vec![Task::new("T8", "Docs".into(), TaskStatus::Pending)]),
+        ])
+    }
+
+    #[test]
+    fn summary_names_agents_wave_failure_and_estimate() {
+        let now = Utc::now();
+        let mut state = AppState::new();
+        assert_eq!(summarize(&state, now), None);
+
+        let mut meta = SessionMeta::new("s1", now - Duration::minutes(10), "/proj".into());
+        meta.confirmed = true;
+        state.domain.active_sessions.insert(SessionId::new("s1"), meta);
+        assert_eq!(summarize(&state, now).as_deref(), Some("no agents running"));
+
+        for id in ["a1", "a2", "a3"] {
+            state.domain.agents.insert(AgentId::new(id), Agent::new(id, now));
+        }
+        state.domain.task_graph = Some(graph());
+        assert_eq!(
+            summarize(&state, now).as_deref(),
+            Some("3 agents active · wave 2/3 · last failure: T7 tests flaky · est 30m left")
+        );
+    }
+
+    #[test]
+    fn estimate_waits_for_a_completed_task_and_stops_when_done() {
+        let graph = graph();
+        assert_eq!(estimate_left(&graph, Duration::minutes(12)), Some(Duration::minutes(36)));
+        assert_eq!(estimate_left(&graph, Duration::zero()), None);
+
+        // Totals are counted when the graph is built
+        let mut waves = graph.waves.clone();
+        for task in waves.iter_mut().flat_map(|wave| wave.tasks.iter_mut()) {
+            task.status = TaskStatus::Completed;
+        }
+        let done = TaskGraph::new(waves.clone());
+        assert_eq!(estimate_left(&done, Duration::minutes(12)), None);
+        assert_eq!(graph_parts(&done), ["all 4 tasks done"]);
+
+        waves[0].tasks[0].status = TaskStatus::Pending;
+        assert_eq!(estimate_left(&TaskGraph::new(waves), Duration::minutes(12)), Some(Duration::minutes(4)));
+    }
+}
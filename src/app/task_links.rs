@@ -0,0 +1,198 @@
+//! Task → agent backfill.
+//!
+//! Orchestrators with sparse task files leave `agent` out of the graph, so
+//! Enter on a task has nowhere to drill into. The agents themselves say what
+//! they were spawned for: their prompt names the task id, quotes the task
+//! description, or at least shares most of its words. Those matches fill in
+//! `Task.agent_id`, each with a confidence the task list shows next to the
+//! agent. Agents the graph already names are never reassigned.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::app::AppState;
+use crate::model::{Agent, AgentId, Task, TaskId};
+
+/// How sure the linker is that an agent worked on a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LinkConfidence {
+    /// Most of the task description's words appear in the prompt
+    Low,
+    /// The prompt quotes the task description
+    Medium,
+    /// The prompt names the task id (or the agent was spawned with it)
+    High,
+}
+
+impl LinkConfidence {
+    /// Marker drawn before an inferred agent in the task list.
+    pub fn marker(self) -> &'static str {
+        match self {
+            LinkConfidence::High => "~",
+            LinkConfidence::Medium => "?",
+            LinkConfidence::Low => "??",
+        }
+    }
+}
+
+/// An agent the linker attached to a task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskLink {
+    pub agent_id: AgentId,
+    pub confidence: LinkConfidence,
+}
+
+/// Share of the task description's significant words the prompt must carry
+/// for a `Low` link.
+const WORD_OVERLAP: f64 = 0.6;
+
+/// Pure function: lowercase words of at least `min_len` characters.
+fn words(text: &str, min_len: usize) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
+        .filter(|word| word.chars().count() >= min_len)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Pure function: how well `agent` matches `task`, if at all.
+pub fn link_confidence(task: &Task, agent: &Agent) -> Option<LinkConfidence> {
+    if agent.task_id.as_ref() == Some(&task.id) {
+        return Some(LinkConfidence::High);
+    }
+    let prompt = agent.task_description.as_deref()?;
+    let prompt_words = words(prompt, 1);
+
+    // A bare number ("3") is too common to trust on its own; want "task 3"
+    let id = task.id.as_str().to_lowercase();
+    let names_id = if id.chars().any(char::is_alphabetic) {
+        prompt_words.contains(&id)
+    } else {
+        prompt_words.windows(2).any(|pair| pair[0] == "task" && pair[1] == id)
+    };
+    if names_id {
+        return Some(LinkConfidence::High);
+    }
+
+    let description = words(&task.description, 1).join(" ");
+    if !description.is_empty() && prompt_words.join(" ").contains(&description) {
+        return Some(LinkConfidence::Medium);
+    }
+
+    let significant = words(&task.description, 4);
+    if significant.len() < 2 {
+        return None;
+    }
+    let prompt_set: HashSet<&str> = prompt_words.iter().map(String::as_str).collect();
+    let shared = significant.iter().filter(|word| prompt_set.contains(word.as_str())).count();
+    (shared as f64 / significant.len() as f64 >= WORD_OVERLAP).then_some(LinkConfidence::Low)
+}
+
+/// Pure function: links for the tasks without an agent, one agent per task.
+/// The strongest matches are taken first; among equals, the earlier agent
+/// and the earlier task win.
+pub fn infer_links<'a>(
+    tasks: impl Iterator<Item = &'a Task>,
+    agents: impl Iterator<Item = &'a Agent>,
+) -> BTreeMap<TaskId, TaskLink> {
+    let tasks: Vec<&Task> = tasks.collect();
+    let taken: HashSet<&AgentId> = tasks.iter().filter_map(|task| task.agent_id.as_ref()).collect();
+    let mut agents: Vec<&Agent> = agents.filter(|agent| !taken.contains(&agent.id)).collect();
+    agents.sort_by_key(|agent| agent.started_at);
+
+    let mut candidates = Vec::new();
+    for (task_idx, task) in tasks.iter().enumerate().filter(|(_, task)| task.agent_id.is_none()) {
+        for (agent_idx, agent) in agents.iter().enumerate() {
+            if let Some(confidence) = link_confidence(task, agent) {
+                candidates.push((std::cmp::Reverse(confidence), agent_idx, task_idx));
+            }
+        }
+    }
+    candidates.sort();
+
+    let mut links = BTreeMap::new();
+    let mut used = HashSet::new();
+    for (std::cmp::Reverse(confidence), agent_idx, task_idx) in candidates {
+        let task = &tasks[task_idx].id;
+        if links.contains_key(task) || !used.insert(agent_idx) {
+            continue;
+        }
+        links.insert(task.clone(), TaskLink { agent_id: agents[agent_idx].id.clone(), confidence });
+    }
+    links
+}
+
+/// Re-run the linker over the current graph and agents, writing the links
+/// into `Task.agent_id`. Earlier inferred links are dropped first, so a
+/// stronger match found later replaces a weaker one.
+pub fn backfill(state: &mut AppState) {
+    let Some(graph) = state.domain.task_graph.as_mut() else {
+        state.domain.task_links.clear();
+        return;
+    };
+    for task in graph.waves.iter_mut().flat_map(|wave| wave.tasks.iter_mut()) {
+        if state.domain.task_links.contains_key(&task.id) {
+            task.agent_id = None;
+        }
+    }
+    let links = infer_links(graph.flat_tasks(), state.domain.agents.values());
+    for task in graph.waves.iter_mut().flat_map(|wave| wave.tasks.iter_mut()) {
+        if let Some(link) = links.get(&task.id) {
+            task.agent_id = Some(link.agent_id.clone());
+        }
+    }
+    state.domain.task_links = links;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TaskStatus;
+    use chrono::{Duration, Utc};
+
+    fn task(id: &str, description: &str) -> Task {
+        Task::new(id, description.to_string(), TaskStatus::Running)
+    }
+
+    fn agent(id: &str, minute: i64, prompt: &str) -> Agent {
+        let mut agent = Agent::new(id, Utc::now() + Duration::minutes(minute));
+        agent.task_description = Some(prompt.to_string());
+        agent
+    }
+
+    #[test]
+    fn confidence_follows_how_the_prompt_refers_to_the_task() {
+        let t = task("T3", "Add retry to the webhook sender");
+        assert_eq!(link_confidence(&t, &agent("a", 0, "Implement t3: retries")), Some(LinkConfidence::High));
+        assert_eq!(
+            link_confidence(&t, &agent("a", 0, "Please add retry to the webhook sender.")),
+            Some(LinkConfidence::Medium)
+        );
+        assert_eq!(
+            link_confidence(&t, &agent("a", 0, "The webhook sender needs a retry loop")),
+            Some(LinkConfidence::Low)
+        );
+        assert_eq!(link_confidence(&t, &agent("a", 0, "Write the changelog")), None);
+
+        // Numeric ids only count as "task N"
+        let numbered = task("2", "Migrate");
+        assert_eq!(link_confidence(&numbered, &agent("a", 0, "Fix 2 bugs")), None);
+        assert_eq!(link_confidence(&numbered, &agent("a", 0, "Do task 2")), Some(LinkConfidence::High));
+    }
+
+    #[test]
+    fn strongest_matches_win_and_declared_agents_stay_put() {
+        let mut declared = task("T1", "Set up the database schema");
+        declared.agent_id = Some(AgentId::new("a1"));
+        let tasks = [declared, task("T2", "Write database migrations"), task("T3", "Write API docs")];
+        let agents = [
+            agent("a1", 0, "T2 database migrations"),
+            agent("a2", 1, "Write database migrations for users"),
+            agent("a3", 2, "You own T2"),
+        ];
+
+        let links = infer_links(tasks.iter(), agents.iter());
+
+        // a3 names T2 outright and beats a2's quote; a1 is T1's declared agent
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[&TaskId::new("T2")], TaskLink { agent_id: AgentId::new("a3"), confidence: LinkConfidence::High });
+    }
+}
@@ -0,0 +1,92 @@
+//! Multi-session timeline (Timeline view, `6`).
+//!
+//! Interleaves the events of every confirmed active session on one timeline,
+//! newest first, so several sessions run side by side can be followed without
+//! switching between them. Each session keeps a color from `SESSION_COLORS`
+//! in the order the sessions started; the view draws a legend of them.
+
+use ratatui::style::Color;
+
+use crate::app::{event_index, AppState};
+use crate::model::{SessionId, Theme, TranscriptEvent};
+
+/// Colors handed to sessions in start order, repeating past the sixth.
+pub const SESSION_COLORS: [Color; 6] = [
+    Theme::ACCENT,
+    Theme::ACCENT_WARM,
+    Theme::ACCENT_PURPLE,
+    Theme::SUCCESS,
+    Theme::TOOL_WEBFETCH,
+    Theme::INFO,
+];
+
+/// Most events listed.
+pub const MAX_TIMELINE_EVENTS: usize = 500;
+
+/// Pure function: the confirmed active sessions, oldest first, with their colors.
+pub fn session_colors(state: &AppState) -> Vec<(&SessionId, Color)> {
+    let mut sessions: Vec<_> = state.domain.confirmed_active_sessions().collect();
+    sessions.sort_by_key(|(_, meta)| meta.timestamp);
+    sessions
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (id, _))| (id, SESSION_COLORS[idx % SESSION_COLORS.len()]))
+        .collect()
+}
+
+/// Pure function: events of the confirmed active sessions the event filters
+/// and the `/` search allow, newest first.
+pub fn timeline_events(state: &AppState) -> Vec<&TranscriptEvent> {
+    let query = state.ui.filter.as_deref().filter(|q| !q.is_empty()).map(str::to_lowercase);
+    state
+        .domain
+        .events
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, e)| {
+            e.session_id
+                .as_ref()
+                .and_then(|id| state.domain.active_sessions.get(id))
+                .is_some_and(|meta| meta.confirmed)
+        })
+        .filter(|(_, e)| state.ui.event_filters.allows(&e.kind))
+        .filter(|(position, e)| query.as_deref().is_none_or(|q| event_index::matches(state, *position, e, q)))
+        .map(|(_, e)| e)
+        .take(MAX_TIMELINE_EVENTS)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{SessionMeta, TranscriptEventKind};
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn events_of_confirmed_sessions_interleave_newest_first() {
+        let mut state = AppState::new();
+        let now = Utc::now();
+        for (id, minutes_ago, confirmed) in [("late", 1, true), ("early", 10, true), ("phantom", 5, false)] {
+            let mut meta = SessionMeta::new(id, now - Duration::minutes(minutes_ago), "/proj".into());
+            meta.confirmed = confirmed;
+            state.domain.active_sessions.insert(SessionId::new(id), meta);
+        }
+        for (secs, session) in [(1, "early"), (2, "late"), (3, "phantom"), (4, "early"), (5, "archived")] {
+            state.domain.events.push_back(
+                TranscriptEvent::new(now + Duration::seconds(secs), TranscriptEventKind::UserMessage).with_session(session),
+            );
+        }
+
+        let sessions: Vec<&str> = timeline_events(&state)
+            .iter()
+            .filter_map(|e| e.session_id.as_ref().map(|id| id.as_str()))
+            .collect();
+        assert_eq!(sessions, ["early", "late", "early"]);
+
+        let colors = session_colors(&state);
+        assert_eq!(colors.len(), 2);
+        assert_eq!((colors[0].0.as_str(), colors[0].1), ("early", Theme::ACCENT));
+        assert_eq!((colors[1].0.as_str(), colors[1].1), ("late", Theme::ACCENT_WARM));
+    }
+}
@@ -0,0 +1,280 @@
+//! Toasts: leveled feedback from background work, and the end-of-session toast.
+//! How each kind is shown and dismissed: `docs/design.md`, "Toasts".
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::app::AppState;
+use crate::model::{SessionId, SessionMeta, SessionOutcome, SessionStatus};
+
+/// Most toasts queued; the oldest is dropped past this.
+pub const MAX_TOASTS: usize = 5;
+
+/// Most toasts stacked on screen; the oldest is dropped past this.
+pub const MAX_STACK: usize = 4;
+
+/// Entries kept in the Errors panel.
+const MAX_ERRORS: usize = 100;
+
+/// How bad a toast's news is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl ToastLevel {
+    /// How long a toast of this level stays up.
+    pub fn ttl(self) -> chrono::Duration {
+        match self {
+            Self::Info => chrono::Duration::seconds(4),
+            Self::Warn => chrono::Duration::seconds(8),
+            Self::Error => chrono::Duration::seconds(12),
+        }
+    }
+}
+
+/// One transient notice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    pub level: ToastLevel,
+    pub text: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Toasts on screen, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToastStack {
+    toasts: VecDeque<Toast>,
+}
+
+impl ToastStack {
+    /// Stack a toast; the same text again only restarts its timer.
+    pub fn push(&mut self, level: ToastLevel, text: String, now: DateTime<Utc>) {
+        self.toasts.retain(|t| t.text != text);
+        if self.toasts.len() >= MAX_STACK {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(Toast { level, text, expires_at: now + level.ttl() });
+    }
+
+    /// Drop the toasts whose time is up.
+    pub fn expire(&mut self, now: DateTime<Utc>) {
+        self.toasts.retain(|t| t.expires_at > now);
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Toast> {
+        self.toasts.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+}
+
+/// Raise a toast. Warnings and errors are also kept in the Errors panel.
+pub fn raise(state: &mut AppState, level: ToastLevel, text: impl Into<String>) {
+    let text = text.into();
+    if level != ToastLevel::Info {
+        record_error(state, text.clone());
+    }
+    state.ui.toasts.push(level, text, state.meta.clock.now());
+}
+
+/// Warn that a save replaced an archive another instance had written since we read it.
+pub fn warn_clobbered(state: &mut AppState, session_id: &SessionId) {
+    raise(
+        state,
+        ToastLevel::Warn,
+        format!("archive {} was changed by another loom-tui; saved over their version", session_id),
+    );
+}
+
+/// Add `text` to the Errors panel, dropping the oldest entry past `MAX_ERRORS`.
+pub fn record_error(state: &mut AppState, text: String) {
+    if state.meta.errors.len() >= MAX_ERRORS {
+        state.meta.errors.pop_front();
+    }
+    state.meta.errors.push_back(text);
+}
+
+/// One ended session's outcome.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionToast {
+    pub session_id: SessionId,
+    pub status: SessionStatus,
+    /// How the session went, when classified (never `Unknown`)
+    pub ended: Option<SessionOutcome>,
+    pub duration: Option<Duration>,
+    /// Failed tool results seen in the session
+    pub tool_errors: usize,
+    /// Goal or project, for telling sessions apart
+    pub label: String,
+}
+
+impl SessionToast {
+    pub fn from_meta(meta: &SessionMeta, tool_errors: usize) -> Self {
+        let project = meta.project_path.rsplit('/').find(|s| !s.is_empty()).unwrap_or("session");
+        Self {
+            session_id: meta.id.clone(),
+            status: meta.status.clone(),
+            ended: meta.outcome.filter(|o| *o != SessionOutcome::Unknown),
+            duration: meta.duration,
+            tool_errors,
+            label: meta.goal_summary(40).unwrap_or_else(|| project.to_string()),
+        }
+    }
+
+    /// Pure function: the outcome in a few words.
+    pub fn outcome(&self) -> String {
+        let ended = match (self.ended, &self.status) {
+            (Some(SessionOutcome::Success), _) => "succeeded",
+            (Some(SessionOutcome::PartialSuccess), _) => "partly succeeded",
+            (Some(SessionOutcome::Failed), _) | (_, SessionStatus::Failed) => "failed",
+            (Some(SessionOutcome::Aborted), _) => "was aborted",
+            (_, SessionStatus::Completed) => "completed",
+            (_, SessionStatus::Cancelled) => "went idle",
+            (_, SessionStatus::Active) => "is active",
+        };
+        match self.tool_errors {
+            0 => ended.to_string(),
+            1 => format!("{} · 1 tool error", ended),
+            n => format!("{} · {} tool errors", ended, n),
+        }
+    }
+
+    /// Pure function: how bad the news is. Failures are errors; aborted and
+    /// partly successful sessions, and unclassified ones that went idle or hit
+    /// tool errors, are warnings.
+    pub fn level(&self) -> ToastLevel {
+        match (self.ended, &self.status) {
+            (Some(SessionOutcome::Failed), _) | (None, SessionStatus::Failed) => ToastLevel::Error,
+            (Some(SessionOutcome::Aborted | SessionOutcome::PartialSuccess), _) => ToastLevel::Warn,
+            (Some(SessionOutcome::Success), _) | (None, SessionStatus::Active) => ToastLevel::Info,
+            (_, SessionStatus::Completed) if self.tool_errors == 0 => ToastLevel::Info,
+            _ => ToastLevel::Warn,
+        }
+    }
+}
+
+/// Toasts waiting to be seen, front first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ToastQueue {
+    toasts: VecDeque<SessionToast>,
+}
+
+impl ToastQueue {
+    /// Queue a toast, replacing an earlier one for the same session.
+    pub fn push(&mut self, toast: SessionToast) {
+        self.toasts.retain(|t| t.session_id != toast.session_id);
+        if self.toasts.len() >= MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(toast);
+    }
+
+    /// The toast on screen.
+    pub fn front(&self) -> Option<&SessionToast> {
+        self.toasts.front()
+    }
+
+    /// Take the toast on screen off the queue.
+    pub fn pop(&mut self) -> Option<SessionToast> {
+        self.toasts.pop_front()
+    }
+
+    /// Toasts behind the one on screen.
+    pub fn waiting(&self) -> usize {
+        self.toasts.len().saturating_sub(1)
+    }
+
+    /// Drop a session's toast (it was opened some other way, or resumed).
+    pub fn forget(&mut self, session_id: &SessionId) {
+        self.toasts.retain(|t| &t.session_id != session_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn toast(id: &str) -> SessionToast {
+        let meta = SessionMeta::new(id, Utc::now(), "/work/app".into()).with_status(SessionStatus::Completed);
+        SessionToast::from_meta(&meta, 2)
+    }
+
+    #[test]
+    fn queue_replaces_per_session_and_caps_its_length() {
+        let mut queue = ToastQueue::default();
+        for id in ["s1", "s2", "s1"] {
+            queue.push(toast(id));
+        }
+        assert_eq!(queue.front().map(|t| t.session_id.as_str()), Some("s2"));
+        assert_eq!(queue.waiting(), 1);
+
+        for i in 0..MAX_TOASTS {
+            queue.push(toast(&format!("x{}", i)));
+        }
+        assert_eq!(queue.waiting(), MAX_TOASTS - 1);
+        assert_eq!(queue.front().map(|t| t.session_id.as_str()), Some("x0"));
+        queue.forget(&SessionId::new("x0"));
+        assert_eq!(queue.pop().map(|t| t.session_id), Some(SessionId::new("x1")));
+    }
+
+    #[test]
+    fn stack_expires_by_level_and_restarts_repeats() {
+        let now = Utc::now();
+        let mut stack = ToastStack::default();
+        stack.push(ToastLevel::Info, "copied".into(), now);
+        stack.push(ToastLevel::Error, "save failed".into(), now);
+        stack.push(ToastLevel::Info, "copied".into(), now + chrono::Duration::seconds(3));
+        assert_eq!(stack.iter().map(|t| t.text.as_str()).collect::<Vec<_>>(), ["save failed", "copied"]);
+
+        stack.expire(now + chrono::Duration::seconds(6));
+        assert_eq!(stack.iter().count(), 2, "the repeat restarted the info timer");
+        stack.expire(now + chrono::Duration::seconds(8));
+        assert_eq!(stack.iter().map(|t| t.level).collect::<Vec<_>>(), [ToastLevel::Error]);
+
+        for i in 0..MAX_STACK {
+            stack.push(ToastLevel::Warn, format!("w{}", i), now);
+        }
+        assert_eq!(stack.iter().next().map(|t| t.text.as_str()), Some("w0"));
+    }
+
+    #[test]
+    fn warnings_and_errors_are_kept_in_the_errors_panel() {
+        let mut state = AppState::new();
+        raise(&mut state, ToastLevel::Info, "copied path");
+        raise(&mut state, ToastLevel::Error, "auto-save: disk full");
+        assert_eq!(state.ui.toasts.iter().count(), 2);
+        assert_eq!(state.meta.errors, ["auto-save: disk full"]);
+    }
+
+    #[test]
+    fn outcome_names_the_ending_and_failures() {
+        let toast = toast("s1");
+        assert_eq!(toast.label, "app");
+        assert_eq!(toast.outcome(), "completed · 2 tool errors");
+        assert_eq!(toast.level(), ToastLevel::Warn);
+    }
+
+    #[test]
+    fn a_classified_outcome_names_the_ending_and_sets_the_level() {
+        let meta = |outcome| {
+            SessionMeta::new("s1", Utc::now(), "/work/app".into())
+                .with_status(SessionStatus::Cancelled)
+                .with_outcome(outcome)
+        };
+        let toast = |outcome| SessionToast::from_meta(&meta(outcome), 0);
+        assert_eq!((toast(SessionOutcome::Success).outcome(), toast(SessionOutcome::Success).level()), ("succeeded".into(), ToastLevel::Info));
+        assert_eq!(toast(SessionOutcome::PartialSuccess).level(), ToastLevel::Warn);
+        assert_eq!(toast(SessionOutcome::Aborted).outcome(), "was aborted");
+        assert_eq!(toast(SessionOutcome::Failed).level(), ToastLevel::Error);
+        // Unknown says nothing the status doesn't
+        assert_eq!((toast(SessionOutcome::Unknown).outcome(), toast(SessionOutcome::Unknown).level()), ("went idle".into(), ToastLevel::Warn));
+    }
+}
@@ -0,0 +1,205 @@
+//! Full tool outputs captured by the hook.
+//!
+//! Transcripts cut tool results (to 16k characters unless the config's
+//! `truncation` says otherwise), and piping whole outputs through the event
+//! stream would bloat it. A hook can instead write each
+//! call's full output to its own file, `<state dir>/tool-outputs/<tool_use_id>.<ext>`
+//! (the state dir holds the task graph). The watcher indexes that directory;
+//! steps of the agent activity tree show the size of their captured outputs and
+//! `o` opens the newest in a pager. The main loop streams the file into the
+//! pager `CHUNK_BYTES` at a time as the view nears the end of what is loaded.
+
+use std::collections::BTreeMap;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use crate::app::AppState;
+use crate::model::{TranscriptEvent, TranscriptEventKind};
+use crate::view::components::step_tree::{agent_steps, Step};
+
+/// Directory next to the task graph that the hook writes outputs to.
+pub const OUTPUT_DIR: &str = "tool-outputs";
+
+/// Most bytes read from an output file at once.
+pub const CHUNK_BYTES: u64 = 64 * 1024;
+
+/// Lines from the end of the loaded text at which the next chunk is read.
+pub const PREFETCH_LINES: usize = 200;
+
+/// Pure function: captured output directory for a task graph file.
+pub fn output_dir(task_graph: &Path) -> PathBuf {
+    task_graph.parent().unwrap_or(Path::new(".")).join(OUTPUT_DIR)
+}
+
+/// One captured output file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolOutput {
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// Captured outputs by tool call id (the file stem).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ToolOutputIndex {
+    outputs: BTreeMap<String, ToolOutput>,
+}
+
+impl FromIterator<(String, ToolOutput)> for ToolOutputIndex {
+    fn from_iter<I: IntoIterator<Item = (String, ToolOutput)>>(iter: I) -> Self {
+        Self { outputs: iter.into_iter().collect() }
+    }
+}
+
+impl ToolOutputIndex {
+    pub fn get(&self, tool_use_id: &str) -> Option<&ToolOutput> {
+        self.outputs.get(tool_use_id)
+    }
+
+    /// Output captured for a ToolResult event.
+    pub fn for_event(&self, event: &TranscriptEvent) -> Option<&ToolOutput> {
+        match event.kind {
+            TranscriptEventKind::ToolResult { .. } => self.get(event.tool_use_id.as_deref()?),
+            _ => None,
+        }
+    }
+}
+
+/// Pure function: the step's tool results with a captured output, oldest first.
+pub fn step_outputs<'a>(step: &Step<'a>, index: &'a ToolOutputIndex) -> Vec<(&'a TranscriptEvent, &'a ToolOutput)> {
+    std::iter::once(step.head)
+        .chain(step.tools.iter().copied())
+        .filter_map(|event| index.for_event(event).map(|output| (event, output)))
+        .collect()
+}
+
+/// Pager over the newest captured output of the step under the cursor in the
+/// agent activity tree (AgentDetail, or the split view's right half).
+pub fn selected_step_pager(state: &AppState) -> Option<OutputPager> {
+    let agent = state.followed_agent()?;
+    let steps = agent_steps(state, agent.id.as_str());
+    let idx = state.ui.scroll_offsets.agent_events.min(steps.len().checked_sub(1)?);
+    let step = steps.iter().rev().nth(idx)?;
+    let (event, output) = step_outputs(step, &state.domain.tool_outputs).pop()?;
+    let tool_name = match &event.kind {
+        TranscriptEventKind::ToolResult { tool_name, .. } => tool_name.to_string(),
+        _ => String::new(),
+    };
+    Some(OutputPager::new(tool_name, output.clone()))
+}
+
+/// Text read from an output file, up to `next_offset`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputChunk {
+    pub text: String,
+    pub next_offset: u64,
+    pub eof: bool,
+}
+
+/// Read up to `CHUNK_BYTES` of `path` from `offset`, ending after the last
+/// newline so lines (and UTF-8 characters) are not split across chunks.
+/// I/O: reads `path`.
+pub fn read_chunk(path: &Path, offset: u64) -> std::io::Result<OutputChunk> {
+    let mut file = std::fs::File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut buf = Vec::new();
+    file.take(CHUNK_BYTES).read_to_end(&mut buf)?;
+    let eof = (buf.len() as u64) < CHUNK_BYTES;
+    if !eof {
+        if let Some(newline) = buf.iter().rposition(|b| *b == b'\n') {
+            buf.truncate(newline + 1);
+        }
+    }
+    Ok(OutputChunk {
+        text: String::from_utf8_lossy(&buf).into_owned(),
+        next_offset: offset + buf.len() as u64,
+        eof,
+    })
+}
+
+/// Pager popup over one captured output, filled a chunk at a time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputPager {
+    pub tool_name: String,
+    pub output: ToolOutput,
+    pub lines: Vec<String>,
+    /// Bytes of the file read so far
+    pub offset: u64,
+    pub eof: bool,
+    /// A chunk read is under way
+    pub loading: bool,
+    pub scroll: usize,
+}
+
+impl OutputPager {
+    pub fn new(tool_name: String, output: ToolOutput) -> Self {
+        Self { tool_name, output, lines: Vec::new(), offset: 0, eof: false, loading: false, scroll: 0 }
+    }
+
+    /// The next chunk to read (file and offset) once the view nears the end of
+    /// the loaded lines; marks the read as under way.
+    pub fn request_chunk(&mut self) -> Option<(PathBuf, u64)> {
+        if self.eof || self.loading || self.scroll + PREFETCH_LINES < self.lines.len() {
+            return None;
+        }
+        self.loading = true;
+        Some((self.output.path.clone(), self.offset))
+    }
+
+    /// Take the chunk read from `offset`; answers for another offset are stale.
+    pub fn append(&mut self, offset: u64, chunk: OutputChunk) {
+        if offset != self.offset {
+            return;
+        }
+        self.loading = false;
+        self.lines.extend(chunk.text.lines().map(str::to_string));
+        self.offset = chunk.next_offset;
+        self.eof = chunk.eof;
+    }
+
+    /// Stop reading after a failed read.
+    pub fn fail(&mut self) {
+        self.loading = false;
+        self.eof = true;
+    }
+
+    /// Percentage of the file read so far.
+    pub fn percent_loaded(&self) -> u64 {
+        if self.eof || self.output.bytes == 0 {
+            100
+        } else {
+            (self.offset * 100 / self.output.bytes).min(99)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pager_streams_the_file_in_whole_line_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("toolu_01.txt");
+        let line = "x".repeat(99);
+        let content: String = (0..2000).map(|_| format!("{}\n", line)).collect();
+        std::fs::write(&path, &content).unwrap();
+
+        let mut pager = OutputPager::new("Bash".into(), ToolOutput { path: path.clone(), bytes: content.len() as u64 });
+        let (file, offset) = pager.request_chunk().unwrap();
+        assert!(pager.request_chunk().is_none(), "one read at a time");
+        pager.append(offset, read_chunk(&file, offset).unwrap());
+        assert_eq!(pager.lines.len(), CHUNK_BYTES as usize / 100);
+        assert!(pager.lines.iter().all(|l| *l == line));
+        assert!(!pager.eof && pager.percent_loaded() < 100);
+
+        // Nothing more is read until the view scrolls near the end
+        assert!(pager.request_chunk().is_none());
+        pager.scroll = pager.lines.len() - PREFETCH_LINES;
+        while let Some((file, offset)) = pager.request_chunk() {
+            pager.append(offset, read_chunk(&file, offset).unwrap());
+            pager.scroll = pager.lines.len();
+        }
+        assert_eq!(pager.lines.len(), 2000);
+        assert_eq!(pager.percent_loaded(), 100);
+    }
+}
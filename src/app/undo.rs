@@ -0,0 +1,155 @@
+//! Undo stack for destructive actions.
+//!
+//! Deleted session archives are moved to the archive trash (see
+//! `session::trash_session`) rather than removed, so `u` can put them back
+//! along with the list selection and marks that were active at the time.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::app::AppState;
+use crate::model::{ArchivedSession, SessionId};
+
+/// Most actions remembered; older ones fall off (their trash is purged on a later start).
+pub const UNDO_LIMIT: usize = 10;
+
+/// How long trashed archives are kept before `session::purge_trash` removes them.
+pub const TRASH_GRACE_DAYS: i64 = 7;
+
+/// A destructive action that can be reverted.
+#[derive(Debug, Clone)]
+pub enum UndoAction {
+    /// Archived sessions deleted from the Sessions view
+    DeleteSessions {
+        sessions: Vec<TrashedSession>,
+        /// Bulk-delete marks cleared by the delete
+        marked: HashSet<SessionId>,
+        /// Sessions list selection before the delete
+        selected_index: Option<usize>,
+    },
+}
+
+impl UndoAction {
+    /// Short past-tense description for the undo toast.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::DeleteSessions { sessions, .. } => match sessions.as_slice() {
+                [one] => format!("session {}", one.session.meta.id),
+                many => format!("{} sessions", many.len()),
+            },
+        }
+    }
+}
+
+/// A session removed from the archive list, with enough to put it back.
+#[derive(Debug, Clone)]
+pub struct TrashedSession {
+    pub session: ArchivedSession,
+    /// Position in `domain.sessions` before removal
+    pub index: usize,
+    /// Where the archive file was moved (None if trashing failed or there was no file)
+    pub trash_path: Option<PathBuf>,
+}
+
+/// Record an undoable action, dropping the oldest beyond `UNDO_LIMIT`.
+pub fn push_undo(state: &mut AppState, action: UndoAction) {
+    let stack = &mut state.domain.undo_stack;
+    stack.push(action);
+    if stack.len() > UNDO_LIMIT {
+        stack.remove(0);
+    }
+}
+
+/// Revert the most recent destructive action.
+/// I/O: moves trashed archives back and drops their tombstones; failures are
+/// pushed to `meta.errors` and the in-memory state is restored regardless.
+///
+/// # Returns
+/// Toast text describing what was restored, or None if there was nothing to undo.
+pub fn undo_last(state: &mut AppState) -> Option<String> {
+    let action = state.domain.undo_stack.pop()?;
+    let description = action.describe();
+
+    match action {
+        UndoAction::DeleteSessions { sessions, marked, selected_index } => {
+            // Ascending index order so earlier inserts don't shift later ones
+            let mut sessions = sessions;
+            sessions.sort_by_key(|t| t.index);
+            for trashed in sessions {
+                let id = trashed.session.meta.id.clone();
+                if let Some(ref from) = trashed.trash_path {
+                    if let Err(e) = crate::session::restore_session(from, &trashed.session.path) {
+                        state.meta.errors.push_back(format!("restore {id}: {e}"));
+                    }
+                }
+                if let Some(ref archive_dir) = state.meta.archive_dir {
+                    if let Err(e) = crate::session::unmark_deleted(archive_dir, id.as_str()) {
+                        state.meta.errors.push_back(format!("tombstone {id}: {e}"));
+                    }
+                }
+                state.domain.deleted_session_ids.remove(&id);
+                let index = trashed.index.min(state.domain.sessions.len());
+                state.domain.sessions.insert(index, trashed.session);
+            }
+            state.ui.marked_sessions = marked;
+            state.ui.selected_session_index = selected_index;
+        }
+    }
+
+    Some(format!("restored {}", description))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::SessionMeta;
+    use chrono::Utc;
+
+    fn archived(id: &str) -> ArchivedSession {
+        ArchivedSession::new(SessionMeta::new(id, Utc::now(), "/p".into()), PathBuf::new())
+    }
+
+    #[test]
+    fn undo_restores_sessions_at_original_positions() {
+        let mut state = AppState::new();
+        state.domain.sessions = vec![archived("b")];
+        state.domain.deleted_session_ids.insert(SessionId::new("a"));
+        state.domain.deleted_session_ids.insert(SessionId::new("c"));
+        push_undo(&mut state, UndoAction::DeleteSessions {
+            sessions: vec![
+                TrashedSession { session: archived("c"), index: 2, trash_path: None },
+                TrashedSession { session: archived("a"), index: 0, trash_path: None },
+            ],
+            marked: HashSet::from([SessionId::new("a"), SessionId::new("c")]),
+            selected_index: Some(2),
+        });
+
+        assert_eq!(undo_last(&mut state).as_deref(), Some("restored 2 sessions"));
+        let ids: Vec<_> = state.domain.sessions.iter().map(|s| s.meta.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "c"]);
+        assert!(state.domain.deleted_session_ids.is_empty());
+        assert_eq!(state.ui.marked_sessions.len(), 2);
+        assert_eq!(state.ui.selected_session_index, Some(2));
+        assert!(state.domain.undo_stack.is_empty());
+    }
+
+    #[test]
+    fn undo_with_empty_stack_is_noop() {
+        let mut state = AppState::new();
+        assert_eq!(undo_last(&mut state), None);
+    }
+
+    #[test]
+    fn stack_is_capped() {
+        let mut state = AppState::new();
+        for i in 0..UNDO_LIMIT + 3 {
+            push_undo(&mut state, UndoAction::DeleteSessions {
+                sessions: vec![TrashedSession { session: archived(&i.to_string()), index: 0, trash_path: None }],
+                marked: HashSet::new(),
+                selected_index: None,
+            });
+        }
+        assert_eq!(state.domain.undo_stack.len(), UNDO_LIMIT);
+        assert_eq!(undo_last(&mut state).as_deref(), Some("restored session 12"));
+    }
+}
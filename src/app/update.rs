@@ -1,38 +1,111 @@
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
-
-use crate::app::{handle_key, AppState, ViewState};
-use crate::event::AppEvent;
-use crate::model::{ArchivedSession, SessionId, SessionMeta, SessionStatus, TranscriptEventKind};
+use std::sync::Arc;
+
+use crate::app::navigation::{request_more_metas, request_older_events};
+use crate::app::calendar::SessionCalendar;
+use crate::app::toasts::{self, SessionToast, ToastLevel};
+use crate::app::outcome::{self, OutcomeEvidence};
+use crate::app::{api_health, cost, failures, pacing, progress, reorder, runs, task_links, updates};
+use crate::app::{handle_key, AppState, CommandMessage, DiffPopupState, PendingPlan, ViewState, WatchdogState, WaveBanner};
+use crate::event::{AppEvent, SessionLoad};
+use crate::model::{
+    ArchivedSession, MonoStamp, SessionArchive, SessionId, SessionMeta, SessionOutcome, SessionStatus, TranscriptEvent, TranscriptEventKind,
+};
 use crate::session;
+use crate::view::components::format::format_elapsed;
+use crate::watcher::health::{degraded_threads, Degradation};
+
+/// Tool a plan-mode session calls to ask the user to approve its plan.
+const EXIT_PLAN_MODE: &str = "ExitPlanMode";
 
 /// Event handler (Elm-inspired loop). Mutates state in place.
 pub fn update(state: &mut AppState, event: AppEvent) {
     let mut agents_changed = false;
+    let clock = Arc::clone(&state.meta.clock);
+    // The watcher re-sends metadata on every rescan; those events count as
+    // activity only when they change something (see their arms below)
+    let passive = matches!(
+        event,
+        AppEvent::Tick(_) | AppEvent::SessionMetadataUpdated { .. } | AppEvent::AgentMetadataUpdated { .. }
+    );
+    if !passive {
+        state.meta.last_activity = clock.instant();
+    }
 
     match event {
         AppEvent::TaskGraphUpdated(graph) => {
             let total = graph.total_tasks() as u32;
+            let now = clock.now();
+
+            // Start the clock on waves that are in progress but not yet done
+            for wave in graph.waves.iter().filter(|w| w.is_started() && !w.is_complete()) {
+                state.domain.wave_started_at.entry(wave.number).or_insert_with(|| MonoStamp::observe(now, &*clock));
+            }
+
+            // Start the clock on a phase the orchestration just moved into
+            if let Some(phase) = graph.phase {
+                if state.domain.phase_started_at.last().is_none_or(|(last, _)| *last != phase) {
+                    state.domain.phase_started_at.push((phase, MonoStamp::observe(now, &*clock)));
+                }
+            }
+
+            // Celebrate a wave that just finished (needs a previous graph to compare against)
+            let completion = state
+                .domain
+                .task_graph
+                .as_ref()
+                .and_then(|prev| graph.wave_completed_since(prev));
+            if let Some(completion) = completion {
+                let elapsed = state
+                    .domain
+                    .wave_started_at
+                    .get(&completion.wave)
+                    .map(|stamp| stamp.elapsed(&*clock));
+                state.ui.wave_banner = Some(WaveBanner {
+                    completion,
+                    elapsed,
+                    expires_at: now + chrono::Duration::seconds(WaveBanner::DURATION_SECS),
+                });
+            }
+
+            let failed = failures::newly_failed(state.domain.task_graph.as_ref(), &graph);
+            state.domain.task_attempts.observe(&graph, now);
             state.domain.task_graph = Some(graph);
+            task_links::backfill(state);
+            failures::record(state, &failed, now);
             // Update task count on all active sessions (task graph is project-level)
             for meta in state.domain.active_sessions.values_mut() {
                 meta.task_count = total;
             }
+            runs::observe_graph(state, now);
         }
 
         AppEvent::TranscriptEventReceived(event) => {
+            // Another source already delivered this event: count it, and drop it unless debugging
+            if state.domain.dedup.observe(&event) && !state.meta.show_duplicates {
+                return;
+            }
+            // Startup replay arrives in one burst by design; only live floods are sampled
+            let admitted = !state.meta.replay_complete || state.domain.sampler.admit(&event, clock.now());
+
             // Attribute to agent if agent_id set
             if let Some(ref agent_id) = event.agent_id {
                 // Track tool use on agent
                 if let TranscriptEventKind::ToolUse { .. } = &event.kind {
                     state.increment_tool_count(agent_id);
                 }
+                if matches!(event.kind, TranscriptEventKind::ToolUse { .. } | TranscriptEventKind::ToolResult { .. }) {
+                    state.observe_tool_event(agent_id, &event);
+                }
             }
 
             // Update session metadata for the session this event belongs to
             if let Some(ref sid) = event.session_id {
                 if let Some(meta) = state.domain.active_sessions.get_mut(sid) {
+                    let was_confirmed = meta.confirmed;
                     meta.event_count += 1;
-                    meta.last_event_at = Some(event.timestamp);
+                    meta.touch(event.timestamp, &*clock);
                     // Confirm session on UserMessage (real user prompt received)
                     if matches!(event.kind, TranscriptEventKind::UserMessage) {
                         meta.confirmed = true;
@@ -43,14 +116,30 @@ pub fn update(state: &mut AppState, event: AppEvent) {
                     {
                         meta.confirmed = true;
                     }
+                    // A session confirmed while an orchestration runs is one of its sessions
+                    if meta.confirmed && !was_confirmed {
+                        runs::join_current_run(state);
+                    }
                 }
             }
 
-            // Push to ring buffer (evict oldest if at capacity)
+            track_plan_approval(state, &event);
+            track_tool_failure(state, &event);
+            api_health::track(state, &event);
+            progress::track(state, &event);
+            // Sampled out: counted above, kept only in the overflow log
+            if !admitted {
+                return;
+            }
+            count_unseen(state, &event);
+
+            // Push to ring buffer (evict oldest if at capacity), in timestamp order within the skew window
             if state.domain.events.len() >= 10_000 {
                 state.domain.events.pop_front();
+                state.domain.event_index.pop_front();
             }
-            state.domain.events.push_back(event);
+            let position = reorder::insert_ordered(&mut state.domain.events, event, state.meta.reorder_skew);
+            state.domain.event_index.insert(position, &state.domain.events[position]);
         }
 
         AppEvent::SessionDiscovered { session_id, transcript_path } => {
@@ -58,23 +147,26 @@ pub fn update(state: &mut AppState, event: AppEvent) {
             if state.domain.deleted_session_ids.contains(&session_id) {
                 // no-op: tombstoned
             } else if !state.domain.active_sessions.contains_key(&session_id) {
-                let now = chrono::Utc::now();
+                let now = clock.now();
                 let mut meta = SessionMeta::new(
                     session_id.clone(),
                     now,
                     state.meta.project_path.clone(),
-                );
+                )
+                .with_monotonic_baseline(&*clock);
                 meta.transcript_path = Some(transcript_path.display().to_string());
                 state.domain.active_sessions.insert(session_id, meta);
             }
         }
 
         AppEvent::SessionCompleted { session_id } => {
+            state.domain.pending_plans.remove(&session_id);
             if let Some(mut meta) = state.domain.active_sessions.remove(&session_id) {
                 meta.status = SessionStatus::Completed;
-                let now = chrono::Utc::now();
-                let dur = (now - meta.timestamp).to_std().unwrap_or_default();
+                let now = clock.now();
+                let dur = meta.elapsed(&*clock).to_std().unwrap_or_default();
                 meta.duration = Some(dur);
+                meta.record_budget(state.meta.budget.for_session(state.domain.task_graph.is_some()));
 
                 // Mark all agents from this session as finished (backstop)
                 for agent in state.domain.agents.values_mut() {
@@ -84,18 +176,23 @@ pub fn update(state: &mut AppState, event: AppEvent) {
                     }
                 }
 
-                let archive = session::build_archive(
+                let mut archive = session::build_archive(
                     state.domain.task_graph.as_ref(),
                     &state.domain.events,
                     &state.domain.agents,
                     &meta,
                 );
+                record_outcome(&mut meta, &mut archive);
+                state.cache_session_preview(meta.id.clone(), session::build_preview(&archive));
+                state.domain.agent_baselines.record_archive(&archive);
+                queue_session_toast(state, &meta);
                 let archived = ArchivedSession::new(meta, PathBuf::new()).with_data(archive);
                 state.domain.sessions.insert(0, archived);
             }
         }
 
         AppEvent::SessionReactivated { session_id } => {
+            state.ui.session_toasts.forget(&session_id);
             // Move from archived back to active, or create fresh entry
             // First check if we have it in sessions list to restore meta
             let archived_meta = state.domain.sessions.iter()
@@ -105,14 +202,16 @@ pub fn update(state: &mut AppState, event: AppEvent) {
             if !state.domain.active_sessions.contains_key(&session_id) {
                 let meta = if let Some(mut m) = archived_meta {
                     m.status = SessionStatus::Active;
-                    m.last_event_at = Some(chrono::Utc::now());
-                    m
+                    m.outcome = None;
+                    m.last_event_at = Some(clock.now());
+                    m.with_monotonic_baseline(&*clock)
                 } else {
                     SessionMeta::new(
                         session_id.clone(),
-                        chrono::Utc::now(),
+                        clock.now(),
                         state.meta.project_path.clone(),
                     )
+                    .with_monotonic_baseline(&*clock)
                 };
                 state.domain.active_sessions.insert(session_id, meta);
             }
@@ -127,24 +226,45 @@ pub fn update(state: &mut AppState, event: AppEvent) {
         }
 
         AppEvent::Tick(now) => {
+            if state.ui.wave_banner.as_ref().is_some_and(|b| now >= b.expires_at) {
+                state.ui.wave_banner = None;
+            }
+            state.ui.toasts.expire(now);
+
+            check_watchdog(state, now);
+            state.domain.error_bursts.expire(now, state.meta.error_burst.window);
+            state.domain.api_health.expire(now);
+            pacing::tick(state, now);
+            state.domain.sampler.expire(now);
+            alert_long_tool_calls(state, now);
+
+            // After a suspend, hold off stale cleanup for a grace period so healthy
+            // sessions aren't cancelled before their sources catch up
+            let mono_now = clock.instant();
+            if let Some(gap) = state.meta.resume.observe_tick(now, mono_now) {
+                update(state, AppEvent::Info {
+                    source: "clock".to_string(),
+                    message: format!("resumed after suspend ({} asleep)", format_elapsed(gap.num_seconds())),
+                });
+            }
+
             // Skip stale cleanup until initial event replay is done.
             // During replay, historical timestamps would cause all sessions to expire
             // because Tick uses real-time `now` but events have old timestamps.
-            if state.meta.replay_complete {
+            if state.meta.replay_complete && !state.meta.resume.in_grace(mono_now) {
                 // Expire stale sessions:
-                // - Confirmed sessions: 10 minute timeout (FR-010)
+                // - Confirmed sessions: `meta.stale_timeout`, 10 minutes by default (FR-010)
                 // - Unconfirmed sessions: 30 second timeout (FR-013)
-                let confirmed_cutoff = now - chrono::Duration::minutes(10);
-                let unconfirmed_cutoff = now - chrono::Duration::seconds(30);
+                // Idle time is measured on the monotonic clock, so a wall clock jump
+                // (NTP step, resume from suspend) does not expire live sessions.
                 let stale_ids: Vec<(SessionId, bool)> = state
                     .domain
                     .active_sessions
                     .iter()
                     .filter(|(_, meta)| {
-                        let cutoff = if meta.confirmed { confirmed_cutoff } else { unconfirmed_cutoff };
-                        meta.last_event_at
-                            .map(|t| t < cutoff)
-                            .unwrap_or(meta.timestamp < cutoff)
+                        let timeout =
+                            if meta.confirmed { state.meta.stale_timeout } else { chrono::Duration::seconds(30) };
+                        meta.idle_at(now, mono_now) > timeout
                     })
                     .map(|(id, meta)| (id.clone(), meta.confirmed))
                     .collect();
@@ -161,14 +281,17 @@ pub fn update(state: &mut AppState, event: AppEvent) {
                         // Only archive confirmed sessions; drop phantom sessions silently (FR-013)
                         if was_confirmed {
                             meta.status = SessionStatus::Cancelled;
-                            let dur = (now - meta.timestamp).to_std().unwrap_or_default();
+                            let dur = meta.elapsed(&*clock).to_std().unwrap_or_default();
                             meta.duration = Some(dur);
-                            let archive = session::build_archive(
+                            meta.record_budget(state.meta.budget.for_session(state.domain.task_graph.is_some()));
+                            let mut archive = session::build_archive(
                                 state.domain.task_graph.as_ref(),
                                 &state.domain.events,
                                 &state.domain.agents,
                                 &meta,
                             );
+                            record_outcome(&mut meta, &mut archive);
+                            queue_session_toast(state, &meta);
                             let archived = ArchivedSession::new(meta, PathBuf::new()).with_data(archive);
                             state.domain.sessions.insert(0, archived);
                         }
@@ -181,79 +304,299 @@ pub fn update(state: &mut AppState, event: AppEvent) {
             if state.meta.errors.len() >= 100 {
                 state.meta.errors.pop_front();
             }
-            // Clear loading state if this error is from a session load
-            if let Some(ref sid) = state.ui.loading_session {
-                if source.contains(sid.as_str()) {
-                    state.ui.loading_session = None;
-                }
-            }
             let error_msg = format!("{}: {}", source, error);
             state.meta.errors.push_back(error_msg);
         }
 
+        AppEvent::SessionLoadFailed { session_id, load, error } => {
+            if state.meta.errors.len() >= 100 {
+                state.meta.errors.pop_front();
+            }
+            match load {
+                SessionLoad::Head => {
+                    if state.ui.loading_session.as_ref() == Some(&session_id) {
+                        state.ui.loading_session = None;
+                    }
+                }
+                // Don't retry a preview that failed
+                SessionLoad::Preview => {
+                    if state.ui.preview_loading.as_ref() == Some(&session_id) {
+                        state.ui.preview_loading = None;
+                    }
+                    state.mark_preview_failed(session_id.clone());
+                }
+                // A failed event page stops paging for that archive instead of retrying forever
+                SessionLoad::Events => {
+                    if state.ui.loading_events.as_ref() == Some(&session_id) {
+                        state.ui.loading_events = None;
+                    }
+                    if let Some(session) = state.domain.sessions.iter_mut().find(|s| s.meta.id == session_id) {
+                        session.paging = None;
+                    }
+                }
+            }
+            state.meta.errors.push_back(format!("{}:{}: {}", load, session_id, error));
+        }
+
+        AppEvent::Info { source, message } => {
+            state.ui.command_message = Some(CommandMessage {
+                text: format!("{}: {}", source, message),
+                is_error: false,
+            });
+        }
+
         AppEvent::SessionLoaded(archive) => {
+            state.ui.loading_session = None;
+            state.cache_session_preview(archive.meta.id.clone(), session::build_preview(&archive));
+            if let Some(session) = state.domain.sessions.iter_mut().find(|s| s.meta.id == archive.meta.id) {
+                session.data = Some(archive);
+                session.paging = None;
+                state.ui.view = ViewState::SessionDetail;
+                state.ui.scroll_offsets.session_detail_left = 0;
+                state.ui.scroll_offsets.session_detail_right = 0;
+                state.ui.focus = crate::app::PanelFocus::Left;
+            } else {
+                state.meta.errors.push_back(format!("session {} not found after load", archive.meta.id));
+            }
+        }
+
+        AppEvent::SessionHeadLoaded { archive, paging, stamp } => {
             state.ui.loading_session = None;
             if let Some(session) = state.domain.sessions.iter_mut().find(|s| s.meta.id == archive.meta.id) {
                 session.data = Some(archive);
+                session.paging = (paging.total() > 0).then_some(paging);
+                session.stamp = stamp;
                 state.ui.view = ViewState::SessionDetail;
                 state.ui.scroll_offsets.session_detail_left = 0;
                 state.ui.scroll_offsets.session_detail_right = 0;
                 state.ui.focus = crate::app::PanelFocus::Left;
+                request_older_events(state);
             } else {
                 state.meta.errors.push_back(format!("session {} not found after load", archive.meta.id));
             }
         }
 
+        AppEvent::SessionEventsLoaded { session_id, start, events } => {
+            if state.ui.loading_events.as_ref() == Some(&session_id) {
+                state.ui.loading_events = None;
+            }
+            let mut completed = None;
+            if let Some(session) = state.domain.sessions.iter_mut().find(|s| s.meta.id == session_id) {
+                if let (Some(archive), Some(paging)) = (session.data.as_mut(), session.paging.as_mut()) {
+                    // Pages arrive newest first, so each one goes in front of what's loaded
+                    archive.events.splice(0..0, events);
+                    paging.loaded = paging.total() - start.min(paging.total());
+                    if paging.remaining() == 0 {
+                        session.paging = None;
+                        completed = Some(session::build_preview(archive));
+                    }
+                }
+            }
+            if let Some(preview) = completed {
+                state.cache_session_preview(session_id, preview);
+            }
+            request_older_events(state);
+        }
+
+        AppEvent::AgentBaselinesLoaded(baselines) => {
+            state.domain.agent_baselines.merge(&baselines);
+        }
+
+        AppEvent::CostRecordsLoaded(records) => {
+            state.domain.archived_costs = records;
+        }
+
+        AppEvent::ReleaseChecked(Err(_)) => {}
+
+        AppEvent::ReleaseChecked(Ok(release)) => {
+            if updates::is_newer(&release.version, SessionArchive::BUILD_VERSION) {
+                state.meta.newer_release = Some(release);
+            }
+        }
+
+        AppEvent::WriteFinished { kind, error } => {
+            state.meta.in_flight.finish(kind);
+            if let Some(error) = error {
+                toasts::raise(state, ToastLevel::Error, error);
+            }
+        }
+
+        AppEvent::ArchiveFilesListed(files) => {
+            state.domain.archive_pages.listed(files, session::META_PAGE_SIZE);
+        }
+
         AppEvent::SessionMetasLoaded(metas) => {
-            state.domain.sessions = metas
+            let rescanned: HashSet<&PathBuf> = metas.iter().map(|(path, _)| path).collect();
+            let listed: HashSet<&PathBuf> = state.domain.archive_pages.files().iter().collect();
+            let deeper: Vec<_> = std::mem::take(&mut state.domain.archive_index)
                 .into_iter()
-                .filter(|(_, meta)| !state.domain.deleted_session_ids.contains(&meta.id))
-                .map(|(path, meta)| ArchivedSession::new(meta, path))
+                .filter(|(path, _)| listed.contains(path) && !rescanned.contains(path))
                 .collect();
+            state.domain.archive_index = metas;
+            state.domain.archive_index.extend(deeper);
+            apply_project_filter(state);
+            request_more_metas(state);
+        }
+
+        AppEvent::SessionMetasLoadedPartial(metas) => {
+            // Added or refreshed in place; the full scan that follows drops removed archives
+            for (path, meta) in metas {
+                match state.domain.archive_index.iter_mut().find(|(known, _)| *known == path) {
+                    Some(entry) => entry.1 = meta,
+                    None => state.domain.archive_index.push((path, meta)),
+                }
+            }
+            state.domain.archive_index.sort_by_key(|(_, meta)| std::cmp::Reverse(meta.timestamp));
+            apply_project_filter(state);
+        }
+
+        AppEvent::SessionMetaPageLoaded { paths, mut metas } => {
+            state.ui.loading_metas = false;
+            state.domain.archive_pages.mark_read(paths);
+            // Pages are older than what's loaded, so each one goes after it
+            metas.sort_by_key(|(_, meta)| std::cmp::Reverse(meta.timestamp));
+            let known: HashSet<PathBuf> = state.domain.archive_index.iter().map(|(path, _)| path.clone()).collect();
+            state.domain.archive_index.extend(metas.into_iter().filter(|(path, _)| !known.contains(path)));
+            apply_project_filter(state);
+            request_more_metas(state);
         }
 
         AppEvent::LoadSessionRequested(sid) => {
             state.ui.loading_session = Some(sid);
         }
 
+        AppEvent::SessionPreviewLoaded { session_id, preview } => {
+            if state.ui.preview_loading.as_ref() == Some(&session_id) {
+                state.ui.preview_loading = None;
+            }
+            state.cache_session_preview(session_id, preview);
+        }
+
+        AppEvent::GitDiffLoaded { task_id, result } => {
+            // Ignore answers for a popup that was closed or moved on meanwhile
+            if matches!(&state.ui.diff_popup, DiffPopupState::Loading(req) if req.task_id == task_id) {
+                let (popup, message) = match result.map(|out| crate::git::parse_diff(&out)) {
+                    Ok(diff) if diff.lines.is_empty() => {
+                        (DiffPopupState::Closed, Some((format!("no changes to {}'s files since session start", task_id), false)))
+                    }
+                    Ok(diff) => (DiffPopupState::Open { task_id, diff, scroll: 0 }, None),
+                    Err(e) => (DiffPopupState::Closed, Some((format!("git diff failed: {}", e), true))),
+                };
+                state.ui.diff_popup = popup;
+                state.ui.command_message = message.map(|(text, is_error)| CommandMessage { text, is_error });
+            }
+        }
+
+        AppEvent::ToolOutputsIndexed(index) => {
+            state.domain.tool_outputs = index;
+        }
+
+        AppEvent::SpecDocLoaded(doc) => {
+            state.domain.spec_doc = doc;
+        }
+
+        AppEvent::AgentDefinitionsLoaded(definitions) => {
+            state.domain.agent_definitions = definitions;
+        }
+
+        AppEvent::SessionRunTagged { session_id, run_id } => {
+            if let Some(meta) = state.domain.active_sessions.get_mut(&session_id) {
+                meta.loom_plan_id = Some(run_id);
+            }
+        }
+
+        AppEvent::SchemaDriftObserved(drift) => {
+            for field in state.domain.schema_drift.merge(drift) {
+                state.meta.errors.push_back(format!("schema: transcripts carry unknown field {} (see :schema)", field));
+                if state.meta.errors.len() > 100 {
+                    state.meta.errors.pop_front();
+                }
+            }
+        }
+
+        AppEvent::ToolOutputChunkLoaded { path, offset, result } => {
+            // Ignore answers for a pager that was closed or switched to another output
+            if let Some(pager) = state.ui.output_pager.as_mut().filter(|pager| pager.output.path == path) {
+                match result {
+                    Ok(chunk) => pager.append(offset, chunk),
+                    Err(e) => {
+                        pager.fail();
+                        let text = format!("read {}: {}", path.display(), e);
+                        state.ui.command_message = Some(CommandMessage { text, is_error: true });
+                    }
+                }
+            }
+        }
+
         AppEvent::AgentFinished { agent_id } => {
             if let Some(agent) = state.domain.agents.get_mut(&agent_id) {
                 if agent.finished_at.is_none() {
-                    agent.finished_at = Some(chrono::Utc::now());
+                    agent.finished_at = Some(clock.now());
                     agents_changed = true;
                 }
             }
         }
 
-        AppEvent::SessionMetadataUpdated { session_id, model, token_usage } => {
+        AppEvent::SessionMetadataUpdated { session_id, model, token_usage, goal } => {
             if let Some(meta) = state.domain.active_sessions.get_mut(&session_id) {
+                let changed = meta.model != model
+                    || meta.token_usage != token_usage
+                    || (meta.goal.is_none() && goal.is_some());
+                if changed {
+                    state.meta.last_activity = clock.instant();
+                }
                 meta.model = model;
                 meta.token_usage = token_usage;
+                // The initial prompt never changes; keep the first one seen
+                if meta.goal.is_none() {
+                    meta.goal = goal;
+                }
             }
+            cost::check_budgets(state, clock.now().with_timezone(&chrono::Local).date_naive());
         }
 
         AppEvent::AgentMetadataUpdated { agent_id, metadata } => {
             use crate::model::Agent;
             // Ensure agent entry exists (create if metadata arrives before discovery)
-            let now = chrono::Utc::now();
+            let now = clock.now();
             let len_before = state.domain.agents.len();
+            let created = !state.domain.agents.contains_key(&agent_id);
             let agent = state.domain.agents
                 .entry(agent_id.clone())
-                .or_insert_with(|| Agent::new(agent_id.clone(), now));
+                .or_insert_with(|| Agent::new(agent_id.clone(), now).with_monotonic_baseline(&*clock));
+            let changed = created
+                || (metadata.model.is_some() && agent.model != metadata.model)
+                || agent.token_usage != metadata.token_usage
+                || agent.skills != metadata.skills
+                || (metadata.task_description.is_some() && agent.task_description != metadata.task_description)
+                || (metadata.agent_type.is_some() && agent.agent_type != metadata.agent_type);
+            if changed {
+                state.meta.last_activity = clock.instant();
+            }
 
             // SET semantics — watcher sends absolute totals from full file parse.
             if let Some(ref m) = metadata.model {
                 agent.model = Some(m.clone());
             }
             agent.token_usage = metadata.token_usage.clone();
+            agent.record_context_sample(agent.token_usage.context_window());
             agent.skills = metadata.skills.clone();
+            let described = metadata.task_description.is_some() && agent.task_description != metadata.task_description;
             if metadata.task_description.is_some() {
                 agent.task_description = metadata.task_description.clone();
             }
+            if metadata.agent_type.is_some() {
+                agent.agent_type = metadata.agent_type.clone();
+            }
+            if described {
+                task_links::backfill(state);
+            }
 
+            state.domain.pacing.throughput.observe(&agent_id, metadata.token_usage.api_tokens(), now);
             if state.domain.agents.len() > len_before {
                 agents_changed = true;
             }
+            cost::check_budgets(state, clock.now().with_timezone(&chrono::Local).date_naive());
         }
     }
 
@@ -262,6 +605,249 @@ pub fn update(state: &mut AppState, event: AppEvent) {
     }
 }
 
+/// Refresh watcher thread health and request a restart (with backoff) when a
+/// thread has exited or stopped heartbeating.
+fn check_watchdog(state: &mut AppState, now: chrono::DateTime<chrono::Utc>) {
+    let Some(ref health) = state.meta.watchdog.health else {
+        return;
+    };
+    let degraded = degraded_threads(&health.snapshot(), now);
+    let watchdog = &mut state.meta.watchdog;
+    let backoff_elapsed = watchdog
+        .last_restart
+        .is_none_or(|at| now - at >= WatchdogState::RESTART_BACKOFF);
+
+    if !degraded.is_empty() && backoff_elapsed && !watchdog.restart_requested {
+        watchdog.restart_requested = true;
+        watchdog.last_restart = Some(now);
+        for (name, reason) in &degraded {
+            let reason = match reason {
+                Degradation::Exited => "exited",
+                Degradation::Stalled => "stalled",
+            };
+            if state.meta.errors.len() >= 100 {
+                state.meta.errors.pop_front();
+            }
+            state.meta.errors.push_back(format!("watcher {name} {reason}; restarting"));
+        }
+    }
+    state.meta.watchdog.degraded = degraded;
+}
+
+/// Rebuild the archived session list from `archive_index`, keeping only archives
+/// in the Sessions list's project scope, on its selected day and with its selected
+/// outcome, and recount the activity calendar. Call after the index or either filter changes.
+pub fn apply_project_filter(state: &mut AppState) {
+    let current = state.meta.project_path.as_str();
+    let time_format = state.ui.time_format;
+    let in_scope: Vec<&(PathBuf, SessionMeta)> = state
+        .domain
+        .archive_index
+        .iter()
+        .filter(|(_, meta)| state.ui.project_filter.matches(&meta.project_path, current))
+        .collect();
+    let calendar = SessionCalendar::from_dates(in_scope.iter().map(|(_, meta)| time_format.date(meta.timestamp)));
+    let metas = in_scope
+        .into_iter()
+        .filter(|(_, meta)| state.ui.session_date.is_none_or(|date| time_format.date(meta.timestamp) == date))
+        .filter(|(_, meta)| state.ui.outcome_filter.is_none_or(|want| outcome::listed(meta) == Some(want)))
+        .cloned()
+        .collect();
+    state.set_session_calendar(calendar);
+    merge_session_metas(state, metas);
+}
+
+/// Reconcile the archived session list with a fresh scan of the archive dir.
+/// Loaded archives and the highlighted row survive a rescan; sessions whose file
+/// disappeared are dropped, and in-memory archives not yet written to disk are kept.
+fn merge_session_metas(state: &mut AppState, metas: Vec<(PathBuf, SessionMeta)>) {
+    let active_count = state.domain.confirmed_active_count();
+    let highlighted = state
+        .ui
+        .selected_session_index
+        .and_then(|idx| idx.checked_sub(active_count))
+        .and_then(|idx| state.domain.sessions.get(idx))
+        .map(|s| s.meta.id.clone());
+
+    let on_disk: HashSet<SessionId> = metas.iter().map(|(_, meta)| meta.id.clone()).collect();
+    let (mut sessions, saved): (Vec<_>, Vec<_>) = std::mem::take(&mut state.domain.sessions)
+        .into_iter()
+        .partition(|s| s.data.is_some() && s.path.as_os_str().is_empty() && !on_disk.contains(&s.meta.id));
+    let mut saved: HashMap<SessionId, ArchivedSession> =
+        saved.into_iter().map(|s| (s.meta.id.clone(), s)).collect();
+
+    for (path, meta) in metas {
+        if state.domain.deleted_session_ids.contains(&meta.id) {
+            continue;
+        }
+        let entry = match saved.remove(&meta.id) {
+            Some(mut existing) => {
+                if existing.meta.event_count != meta.event_count {
+                    state.evict_session_preview(&meta.id);
+                }
+                existing.meta = meta;
+                existing.path = path;
+                existing
+            }
+            None => ArchivedSession::new(meta, path),
+        };
+        sessions.push(entry);
+    }
+
+    // Whatever is left was removed from disk by someone else
+    for id in saved.keys() {
+        state.evict_session_preview(id);
+        state.ui.marked_sessions.remove(id);
+    }
+    if state.ui.loading_session.as_ref().is_some_and(|id| saved.contains_key(id)) {
+        state.ui.loading_session = None;
+    }
+
+    state.domain.sessions = sessions;
+
+    let total = active_count + state.domain.sessions.len();
+    let restored = highlighted
+        .and_then(|id| state.domain.sessions.iter().position(|s| s.meta.id == id))
+        .map(|idx| active_count + idx);
+    state.ui.selected_session_index = match restored {
+        Some(idx) => Some(idx),
+        None if total == 0 => None,
+        None => state.ui.selected_session_index.map(|idx| idx.min(total - 1)),
+    };
+}
+
+/// Plan-mode approval gate: `ExitPlanMode` blocks its session until the user
+/// answers, which arrives as the tool's result (approved or not).
+fn track_plan_approval(state: &mut AppState, event: &TranscriptEvent) {
+    let Some(ref sid) = event.session_id else {
+        return;
+    };
+    match &event.kind {
+        TranscriptEventKind::ToolUse { tool_name, input_summary } if tool_name.as_str() == EXIT_PLAN_MODE => {
+            state.domain.pending_plans.insert(
+                sid.clone(),
+                PendingPlan {
+                    plan: input_summary.clone(),
+                    requested_at: event.timestamp,
+                    agent_id: event.agent_id.clone(),
+                },
+            );
+        }
+        TranscriptEventKind::ToolResult { tool_name, .. } if tool_name.as_str() == EXIT_PLAN_MODE => {
+            state.domain.pending_plans.remove(sid);
+        }
+        _ => {}
+    }
+}
+
+/// Classify how the session `meta` ended from its `archive`, on both.
+fn record_outcome(meta: &mut SessionMeta, archive: &mut SessionArchive) {
+    let ended = outcome::classify(&OutcomeEvidence::of(archive));
+    meta.outcome = Some(ended);
+    archive.meta.outcome = Some(ended);
+}
+
+/// Queue an end-of-session toast unless the user is looking at the session.
+/// A session that failed, was aborted or partly succeeded is also noted in the
+/// Errors panel either way.
+fn queue_session_toast(state: &mut AppState, meta: &SessionMeta) {
+    if let Some(ended) = meta.outcome.filter(|o| matches!(o, SessionOutcome::Failed | SessionOutcome::Aborted | SessionOutcome::PartialSuccess)) {
+        toasts::record_error(state, format!("session {}: {}", meta.id, ended.label()));
+    }
+    let watching = state.ui.view == ViewState::SessionDetail && state.ui.selected_session_id.as_ref() == Some(&meta.id);
+    if watching {
+        return;
+    }
+    let tool_errors = state
+        .domain
+        .events
+        .iter()
+        .filter(|e| e.session_id.as_ref() == Some(&meta.id))
+        .filter(|e| matches!(e.kind, TranscriptEventKind::ToolResult { is_error: true, .. }))
+        .count();
+    state.ui.session_toasts.push(SessionToast::from_meta(meta, tool_errors));
+}
+
+/// Record a failed tool result against its session (a subagent's events count
+/// toward the session it belongs to). Crossing the threshold after replay also
+/// raises a toast; the header keeps showing the alert until the window drains.
+fn track_tool_failure(state: &mut AppState, event: &TranscriptEvent) {
+    if !matches!(event.kind, TranscriptEventKind::ToolResult { is_error: true, .. }) {
+        return;
+    }
+    let sid = event.session_id.clone().or_else(|| {
+        let agent = state.domain.agents.get(event.agent_id.as_ref()?)?;
+        agent.session_id.clone()
+    });
+    let Some(sid) = sid else {
+        return;
+    };
+    let threshold = state.meta.error_burst;
+    if state.domain.error_bursts.record(&sid, event.timestamp, &threshold) && state.meta.replay_complete {
+        let text = format!(
+            "error burst in session {}: {} tool failures within {}",
+            sid,
+            state.domain.error_bursts.count(&sid),
+            threshold.window_label()
+        );
+        state.ui.command_message = Some(CommandMessage { text, is_error: true });
+    }
+}
+
+/// Flag tool calls that have gone unanswered past `meta.long_tool_call`. After
+/// replay each newly flagged call raises a warning toast and is logged with the
+/// errors; the agent row and the event stream keep it highlighted until it ends.
+fn alert_long_tool_calls(state: &mut AppState, now: chrono::DateTime<chrono::Utc>) {
+    let Some(limit) = state.meta.long_tool_call else {
+        return;
+    };
+    let flagged = state.flag_long_tool_calls(now, limit);
+    if !state.meta.replay_complete {
+        return;
+    }
+    for (id, call) in flagged {
+        let agent = state.domain.agents.get(&id).map_or_else(|| id.to_string(), |a| a.display_name().to_string());
+        let text = format!("long-running tool call in {}: {}", agent, call.label(now));
+        if state.meta.errors.len() >= 100 {
+            state.meta.errors.pop_front();
+        }
+        state.meta.errors.push_back(format!("tool: {}", text));
+        state.ui.command_message = Some(CommandMessage { text: format!("⚠ {}", text), is_error: true });
+    }
+}
+
+/// Count an arriving event toward the "new events" pill of each live panel that
+/// is scrolled away from the newest events (events render newest first).
+fn count_unseen(state: &mut AppState, event: &TranscriptEvent) {
+    use crate::view::components::event_stream::event_belongs_to_agent;
+
+    if state.ui.auto_scroll {
+        return;
+    }
+    if state.ui.scroll_offsets.event_stream > 0 && state.ui.event_filters.allows(&event.kind) {
+        state.ui.unseen_events.event_stream += 1;
+    }
+    let shows_activity = match state.ui.view {
+        ViewState::AgentDetail => true,
+        ViewState::Dashboard => state.ui.split_view,
+        _ => false,
+    };
+    if !shows_activity || state.ui.scroll_offsets.agent_events == 0 {
+        return;
+    }
+    let Some(agent) = state.followed_agent() else {
+        return;
+    };
+    if !event_belongs_to_agent(event, agent.id.as_str(), agent.session_id.as_ref()) {
+        return;
+    }
+    let id = agent.id.clone();
+    match &mut state.ui.unseen_events.agent {
+        Some((counted, n)) if *counted == id => *n += 1,
+        slot => *slot = Some((id, 1)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -269,9 +855,11 @@ mod tests {
     use crate::app::AppState;
     use crate::event::AppEvent;
     use crate::model::{
-        Agent, AgentId, SessionId, SessionMeta, TaskGraph, TranscriptEvent, TranscriptEventKind,
-        Wave,
+        Agent, AgentId, Clock, EventPaging, ManualClock, SessionId, SessionMeta, Task, TaskGraph, TaskStatus, TranscriptEvent,
+        TranscriptEventKind, Wave,
     };
+    use crate::app::PanelFocus;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
     use std::path::PathBuf;
 
     // -------------------------------------------------------------------------
@@ -315,6 +903,164 @@ mod tests {
         assert_eq!(meta.task_count, 2);
     }
 
+    #[test]
+    fn task_graph_without_agents_is_linked_from_agent_prompts() {
+        use crate::app::task_links::LinkConfidence;
+        use crate::model::TaskId;
+
+        let mut state = AppState::new();
+        let mut agent = Agent::new("a07", Utc::now());
+        agent.task_description = Some("You are working on T2. Write the migrations.".into());
+        state.domain.agents.insert(agent.id.clone(), agent);
+
+        let graph = TaskGraph::new(vec![Wave::new(
+            1,
+            vec![
+                Task::new("T1", "Schema".to_string(), TaskStatus::Completed),
+                Task::new("T2", "Migrations".to_string(), TaskStatus::Running),
+            ],
+        )]);
+        update(&mut state, AppEvent::TaskGraphUpdated(graph.clone()));
+
+        let linked = |state: &AppState| state.domain.task_graph.as_ref().unwrap().waves[0].tasks[1].agent_id.clone();
+        assert_eq!(linked(&state), Some(AgentId::new("a07")));
+        assert_eq!(state.domain.task_links[&TaskId::new("T2")].confidence, LinkConfidence::High);
+
+        // A re-read graph gets the link again
+        update(&mut state, AppEvent::TaskGraphUpdated(graph));
+        assert_eq!(linked(&state), Some(AgentId::new("a07")));
+    }
+
+    fn two_wave_graph(first: TaskStatus) -> TaskGraph {
+        TaskGraph::new(vec![
+            Wave::new(1, vec![Task::new("T1", "one".into(), first)]),
+            Wave::new(
+                2,
+                vec![
+                    Task::new("T2", "two".into(), TaskStatus::Pending),
+                    Task::new("T3", "three".into(), TaskStatus::Pending),
+                ],
+            ),
+        ])
+    }
+
+    #[test]
+    fn task_graph_wave_completion_shows_banner() {
+        let mut state = AppState::new();
+        update(&mut state, AppEvent::TaskGraphUpdated(two_wave_graph(TaskStatus::Running)));
+        assert!(state.ui.wave_banner.is_none());
+        assert!(state.domain.wave_started_at.contains_key(&1));
+
+        update(&mut state, AppEvent::TaskGraphUpdated(two_wave_graph(TaskStatus::Completed)));
+
+        let banner = state.ui.wave_banner.as_ref().expect("banner after wave 1 completes");
+        assert_eq!(banner.completion.wave, 1);
+        assert_eq!(banner.completion.next_wave, Some((2, 2)));
+        assert!(banner.elapsed.is_some());
+    }
+
+    #[test]
+    fn task_graph_first_load_does_not_celebrate() {
+        let mut state = AppState::new();
+        update(&mut state, AppEvent::TaskGraphUpdated(two_wave_graph(TaskStatus::Completed)));
+        assert!(state.ui.wave_banner.is_none());
+    }
+
+    #[test]
+    fn events_other_than_tick_count_as_activity() {
+        let mut state = AppState::new();
+        let start = state.meta.last_activity - std::time::Duration::from_secs(60);
+        state.meta.last_activity = start;
+
+        update(&mut state, AppEvent::Tick(Utc::now()));
+        assert_eq!(state.meta.last_activity, start);
+
+        update(&mut state, AppEvent::ReplayComplete);
+        assert!(state.meta.last_activity > start);
+    }
+
+    #[test]
+    fn resent_metadata_does_not_keep_the_tick_awake() {
+        use crate::app::state::AppMeta;
+        use crate::model::TokenUsage;
+        use crate::watcher::TranscriptMetadata;
+
+        let clock = ManualClock::new(Utc::now());
+        let mut state = AppState::new().with_clock(Arc::new(clock.clone()));
+        let sid = SessionId::new("sess-idle");
+        state.domain.active_sessions.insert(sid.clone(), SessionMeta::new(sid.clone(), clock.now(), "/proj".to_string()));
+        let usage = TokenUsage { input_tokens: 10, ..TokenUsage::default() };
+        let session_event = || AppEvent::SessionMetadataUpdated {
+            session_id: sid.clone(),
+            model: Some("claude-opus-4".into()),
+            token_usage: usage.clone(),
+            goal: None,
+        };
+        let agent_event = || AppEvent::AgentMetadataUpdated {
+            agent_id: AgentId::new("agent-idle"),
+            metadata: TranscriptMetadata { model: Some("claude-opus-4".into()), token_usage: usage.clone(), ..Default::default() },
+        };
+        update(&mut state, session_event());
+        update(&mut state, agent_event());
+
+        // The watcher re-sends the same metadata on every rescan
+        let rescan = chrono::Duration::seconds(2);
+        let mut waited = chrono::Duration::zero();
+        while waited.to_std().unwrap() < AppMeta::IDLE_AFTER {
+            clock.advance(rescan);
+            waited += rescan;
+            update(&mut state, session_event());
+            update(&mut state, agent_event());
+        }
+        assert_eq!(state.effective_tick_rate(clock.instant()), AppMeta::IDLE_TICK_RATE);
+
+        update(&mut state, AppEvent::SessionMetadataUpdated {
+            session_id: sid.clone(),
+            model: Some("claude-opus-4".into()),
+            token_usage: TokenUsage { input_tokens: 20, ..TokenUsage::default() },
+            goal: None,
+        });
+        assert_eq!(state.effective_tick_rate(clock.instant()), AppMeta::DEFAULT_TICK_RATE);
+    }
+
+    #[test]
+    fn tick_clears_expired_wave_banner() {
+        let mut state = AppState::new();
+        update(&mut state, AppEvent::TaskGraphUpdated(two_wave_graph(TaskStatus::Running)));
+        update(&mut state, AppEvent::TaskGraphUpdated(two_wave_graph(TaskStatus::Completed)));
+        let expires_at = state.ui.wave_banner.as_ref().unwrap().expires_at;
+
+        update(&mut state, AppEvent::Tick(expires_at - chrono::Duration::seconds(1)));
+        assert!(state.ui.wave_banner.is_some());
+
+        update(&mut state, AppEvent::Tick(expires_at));
+        assert!(state.ui.wave_banner.is_none());
+    }
+
+    #[test]
+    fn session_metadata_updated_keeps_first_goal() {
+        let mut state = AppState::new();
+        let sid = SessionId::new("sess-goal");
+        state
+            .domain
+            .active_sessions
+            .insert(sid.clone(), SessionMeta::new(sid.clone(), Utc::now(), "/proj".to_string()));
+
+        for goal in ["build the parser", "something later"] {
+            update(
+                &mut state,
+                AppEvent::SessionMetadataUpdated {
+                    session_id: sid.clone(),
+                    model: None,
+                    token_usage: Default::default(),
+                    goal: Some(goal.to_string()),
+                },
+            );
+        }
+
+        assert_eq!(state.domain.active_sessions[&sid].goal.as_deref(), Some("build the parser"));
+    }
+
     // -------------------------------------------------------------------------
     // TranscriptEventReceived
     // -------------------------------------------------------------------------
@@ -332,6 +1078,137 @@ mod tests {
         assert_eq!(state.domain.events[0].kind, TranscriptEventKind::UserMessage);
     }
 
+    #[test]
+    fn exit_plan_mode_waits_for_approval_until_its_result() {
+        let mut state = AppState::new();
+        let tool = |kind| TranscriptEvent::new(Utc::now(), kind).with_session("sess-1");
+        update(&mut state, AppEvent::TranscriptEventReceived(tool(TranscriptEventKind::ToolUse {
+            tool_name: "ExitPlanMode".into(),
+            input_summary: "1. Parse\n2. Test".into(),
+        })));
+        let (sid, plan) = state.newest_pending_plan().unwrap();
+        assert_eq!((sid.as_str(), plan.plan.as_str()), ("sess-1", "1. Parse\n2. Test"));
+
+        // Other tools' results don't answer the gate
+        update(&mut state, AppEvent::TranscriptEventReceived(tool(TranscriptEventKind::ToolResult {
+            tool_name: "Read".into(),
+            result_summary: String::new(),
+            duration_ms: None,
+            is_error: false,
+        })));
+        assert!(state.newest_pending_plan().is_some());
+
+        update(&mut state, AppEvent::TranscriptEventReceived(tool(TranscriptEventKind::ToolResult {
+            tool_name: "ExitPlanMode".into(),
+            result_summary: "User has approved your plan".into(),
+            duration_ms: None,
+            is_error: false,
+        })));
+        assert!(state.domain.pending_plans.is_empty());
+    }
+
+    #[test]
+    fn failed_tool_results_raise_an_error_burst_alert_that_drains() {
+        let mut state = AppState::new();
+        state.meta.replay_complete = true;
+        let mut agent = Agent::new("a01", Utc::now());
+        agent.session_id = Some(SessionId::new("sess-1"));
+        state.domain.agents.insert(AgentId::new("a01"), agent);
+        let start = Utc::now();
+        let failure = |i: i64, is_error| {
+            let mut event = TranscriptEvent::new(start + chrono::Duration::seconds(i), TranscriptEventKind::ToolResult {
+                tool_name: "Bash".into(),
+                result_summary: "exit 1".into(),
+                duration_ms: None,
+                is_error,
+            });
+            event.agent_id = Some(AgentId::new("a01"));
+            event
+        };
+
+        update(&mut state, AppEvent::TranscriptEventReceived(failure(0, false)));
+        for i in 1..=5 {
+            update(&mut state, AppEvent::TranscriptEventReceived(failure(i, true)));
+        }
+        assert!(state.ui.command_message.is_none(), "5 failures is not above the default threshold");
+
+        update(&mut state, AppEvent::TranscriptEventReceived(failure(6, true)));
+        let toast = state.ui.command_message.as_ref().unwrap();
+        assert!(toast.is_error && toast.text.contains("sess-1: 6 tool failures within 2m"), "{}", toast.text);
+        assert_eq!(state.domain.error_bursts.bursting(&state.meta.error_burst).len(), 1);
+
+        update(&mut state, AppEvent::Tick(start + chrono::Duration::minutes(3)));
+        assert!(state.domain.error_bursts.bursting(&state.meta.error_burst).is_empty());
+    }
+
+    #[test]
+    fn tool_call_unanswered_past_the_limit_raises_one_warning() {
+        let mut state = AppState::new();
+        state.meta.replay_complete = true;
+        let start = Utc::now();
+        state.domain.agents.insert(AgentId::new("a01"), Agent::new("a01", start));
+        let call = TranscriptEvent::new(start, TranscriptEventKind::ToolUse {
+            tool_name: "Bash".into(),
+            input_summary: "cargo test".into(),
+        })
+        .with_agent("a01");
+        update(&mut state, AppEvent::TranscriptEventReceived(call.clone()));
+
+        update(&mut state, AppEvent::Tick(start + chrono::Duration::minutes(6)));
+        let toast = state.ui.command_message.take().unwrap();
+        assert!(toast.is_error && toast.text.contains("a01: Bash cargo test … 6m"), "{}", toast.text);
+        assert!(state.open_tool_call(&call).is_some_and(|open| open.overdue));
+        assert_eq!(state.meta.errors.len(), 1);
+
+        update(&mut state, AppEvent::Tick(start + chrono::Duration::minutes(7)));
+        assert!(state.ui.command_message.is_none(), "flagged once");
+    }
+
+    #[test]
+    fn flooding_session_is_sampled_out_of_the_buffer_but_counted() {
+        use crate::app::sampler::{IngestSampler, SampleThreshold};
+        let mut state = AppState::new();
+        state.meta.replay_complete = true;
+        state.domain.sampler = IngestSampler::new(SampleThreshold { events_per_sec: 10, keep_one_in: 10 });
+        let sid = SessionId::new("sess-1");
+        state.domain.active_sessions.insert(sid.clone(), SessionMeta::new(sid.clone(), Utc::now(), "/p".into()));
+        let text = || {
+            TranscriptEvent::new(Utc::now(), TranscriptEventKind::AssistantMessage { content: "again".into() })
+                .with_session("sess-1")
+        };
+
+        for _ in 0..30 {
+            update(&mut state, AppEvent::TranscriptEventReceived(text()));
+        }
+
+        // 10 before the flood was detected, then one in ten of the remaining 20
+        assert_eq!(state.domain.events.len(), 12);
+        assert_eq!(state.domain.active_sessions[&sid].event_count, 30);
+        assert_eq!(state.domain.sampler.sampling(), 1);
+        assert_eq!(state.domain.sampler.take_overflow().len(), 20);
+    }
+
+    #[test]
+    fn events_arriving_above_a_scrolled_panel_are_counted_until_back_at_top() {
+        let mut state = AppState::new();
+        let event = || TranscriptEvent::new(Utc::now(), TranscriptEventKind::UserMessage);
+
+        // Following: nothing is unseen
+        update(&mut state, AppEvent::TranscriptEventReceived(event()));
+        assert_eq!(state.ui.unseen_events.event_stream, 0);
+
+        state.ui.focus = PanelFocus::Right;
+        update(&mut state, AppEvent::Key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::empty())));
+        assert!(!state.ui.auto_scroll);
+        update(&mut state, AppEvent::TranscriptEventReceived(event()));
+        update(&mut state, AppEvent::TranscriptEventReceived(event()));
+        assert_eq!(state.ui.unseen_events.event_stream, 2);
+
+        update(&mut state, AppEvent::Key(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::empty())));
+        assert_eq!(state.ui.unseen_events.event_stream, 0);
+        assert!(state.ui.auto_scroll);
+    }
+
     #[test]
     fn transcript_event_received_updates_session_event_count() {
         let mut state = AppState::new();
@@ -386,6 +1263,22 @@ mod tests {
         assert_eq!(state.agent_tool_count(&aid), 1);
     }
 
+    #[test]
+    fn received_events_are_indexed_in_buffer_order() {
+        let mut state = AppState::new();
+        let now = Utc::now();
+        for (offset, content) in [(0, "first"), (1000, "third"), (500, "second")] {
+            let kind = TranscriptEventKind::AssistantMessage { content: content.into() };
+            let event = TranscriptEvent::new(now + chrono::Duration::milliseconds(offset), kind);
+            update(&mut state, AppEvent::TranscriptEventReceived(event));
+        }
+
+        // The late event went in at its timestamp; its text sits at the same position
+        let index = &state.domain.event_index;
+        assert!(index.get(3, 1).is_some_and(|text| text.matches(&state.domain.events[1], "second")));
+        assert!(index.get(3, 2).is_some_and(|text| text.matches(&state.domain.events[2], "third")));
+    }
+
     #[test]
     fn transcript_event_ring_buffer_evicts_oldest_at_capacity() {
         let mut state = AppState::new();
@@ -517,6 +1410,67 @@ mod tests {
         assert!(state.domain.active_sessions.is_empty());
     }
 
+    #[test]
+    fn session_completed_elsewhere_raises_a_toast_that_opens_it() {
+        let mut state = AppState::new();
+        let now = Utc::now();
+        for id in ["s1", "s2"] {
+            state.domain.active_sessions.insert(SessionId::new(id), SessionMeta::new(id, now, "/proj".to_string()));
+        }
+        let failed = TranscriptEventKind::ToolResult {
+            tool_name: "Bash".into(),
+            result_summary: "exit 1".into(),
+            duration_ms: None,
+            is_error: true,
+        };
+        state.domain.events.push_back(TranscriptEvent::new(now, failed).with_session("s1"));
+
+        update(&mut state, AppEvent::SessionCompleted { session_id: SessionId::new("s1") });
+        let toast = state.ui.session_toasts.front().unwrap();
+        // It stopped on the failed result without a reply
+        assert_eq!((toast.session_id.as_str(), toast.outcome()), ("s1", "was aborted · 1 tool error".to_string()));
+
+        // Other keys leave it up; Enter opens the session
+        update(&mut state, AppEvent::Key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::empty())));
+        assert!(state.ui.session_toasts.front().is_some());
+        update(&mut state, AppEvent::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty())));
+        assert_eq!(state.ui.view, ViewState::SessionDetail);
+        assert_eq!(state.ui.selected_session_id, Some(SessionId::new("s1")));
+        assert!(state.ui.session_toasts.front().is_none());
+
+        // No toast for the session on screen
+        state.ui.selected_session_id = Some(SessionId::new("s2"));
+        update(&mut state, AppEvent::SessionCompleted { session_id: SessionId::new("s2") });
+        assert!(state.ui.session_toasts.front().is_none());
+    }
+
+    #[test]
+    fn session_completed_records_its_outcome_until_reactivated() {
+        let mut state = AppState::new();
+        let now = Utc::now();
+        for id in ["s1", "s2"] {
+            state.domain.active_sessions.insert(SessionId::new(id), SessionMeta::new(id, now, "/proj".to_string()));
+            state.domain.events.push_back(TranscriptEvent::new(now, TranscriptEventKind::UserMessage).with_session(id));
+        }
+        let reply = TranscriptEventKind::AssistantMessage { content: "Fixed.".into() };
+        state.domain.events.push_back(TranscriptEvent::new(now, reply).with_session("s1"));
+
+        update(&mut state, AppEvent::SessionCompleted { session_id: SessionId::new("s1") });
+        update(&mut state, AppEvent::SessionCompleted { session_id: SessionId::new("s2") });
+        let outcome_of = |state: &AppState, id: &str| {
+            let session = state.domain.sessions.iter().find(|s| s.meta.id.as_str() == id).unwrap();
+            assert_eq!(session.data.as_ref().unwrap().meta.outcome, session.meta.outcome);
+            session.meta.outcome
+        };
+        assert_eq!(outcome_of(&state, "s1"), Some(SessionOutcome::Success));
+        assert_eq!(outcome_of(&state, "s2"), Some(SessionOutcome::Aborted));
+        // Only the bad news stays behind in the Errors panel
+        assert_eq!(state.meta.errors, ["session s2: aborted"]);
+
+        update(&mut state, AppEvent::SessionReactivated { session_id: SessionId::new("s2") });
+        assert_eq!(state.domain.active_sessions[&SessionId::new("s2")].outcome, None);
+    }
+
     // -------------------------------------------------------------------------
     // SessionReactivated (FR-011)
     // -------------------------------------------------------------------------
@@ -646,6 +1600,97 @@ mod tests {
         assert!(state.domain.active_sessions.contains_key(&sid));
     }
 
+    #[test]
+    fn tick_measures_idle_time_on_the_monotonic_clock() {
+        let clock = ManualClock::new(Utc::now());
+        let mut state = AppState::new().with_clock(Arc::new(clock.clone()));
+        state.meta.replay_complete = true;
+
+        // Last event seen just now, but the wall clock has since jumped an hour ahead
+        let sid = SessionId::new("sess-clock-jump");
+        let mut meta = SessionMeta::new(sid.clone(), clock.now(), "/proj".to_string()).with_monotonic_baseline(&clock);
+        meta.confirmed = true;
+        meta.touch(clock.now(), &clock);
+        state.domain.active_sessions.insert(sid.clone(), meta);
+
+        clock.step_wall(chrono::Duration::hours(1));
+        update(&mut state, AppEvent::Tick(clock.now()));
+
+        assert!(state.domain.active_sessions.contains_key(&sid));
+    }
+
+    #[test]
+    fn usage_is_recorded_on_the_clocks_day() {
+        use crate::model::TokenUsage;
+
+        let at: chrono::DateTime<Utc> = "2026-03-18T12:00:00Z".parse().unwrap();
+        let clock = ManualClock::new(at);
+        let mut state = AppState::new().with_clock(Arc::new(clock));
+        let sid = SessionId::new("sess-spend");
+        let mut meta = SessionMeta::new(sid.clone(), at, "/proj".to_string());
+        meta.confirmed = true;
+        state.domain.active_sessions.insert(sid.clone(), meta);
+
+        update(&mut state, AppEvent::SessionMetadataUpdated {
+            session_id: sid,
+            model: Some("claude-opus-4".into()),
+            token_usage: TokenUsage { input_tokens: 100_000, ..TokenUsage::default() },
+            goal: None,
+        });
+
+        assert_eq!(state.domain.daily_spend.date, Some(at.with_timezone(&chrono::Local).date_naive()));
+        assert_eq!(state.domain.daily_spend.total_on(at.with_timezone(&chrono::Local).date_naive()), 150);
+    }
+
+    #[test]
+    fn tick_expires_sessions_idle_past_the_stale_timeout() {
+        let clock = ManualClock::new(Utc::now());
+        let mut state = AppState::new().with_clock(Arc::new(clock.clone()));
+        state.meta.replay_complete = true;
+
+        let sid = SessionId::new("sess-idle");
+        let mut meta = SessionMeta::new(sid.clone(), clock.now(), "/proj".to_string()).with_monotonic_baseline(&clock);
+        meta.confirmed = true;
+        meta.touch(clock.now(), &clock);
+        state.domain.active_sessions.insert(sid.clone(), meta);
+
+        clock.advance(state.meta.stale_timeout);
+        update(&mut state, AppEvent::Tick(clock.now()));
+        assert!(state.domain.active_sessions.contains_key(&sid));
+
+        clock.advance(chrono::Duration::seconds(1));
+        update(&mut state, AppEvent::Tick(clock.now()));
+        assert!(!state.domain.active_sessions.contains_key(&sid));
+        assert!(state.domain.sessions.iter().any(|s| s.meta.id == sid));
+    }
+
+    #[test]
+    fn tick_after_suspend_skips_stale_expiry_for_a_grace_period() {
+        let mut state = AppState::new();
+        state.meta.replay_complete = true;
+
+        let sid = SessionId::new("sess-asleep");
+        let old_ts = Utc::now() - chrono::Duration::minutes(15);
+        let mut meta = SessionMeta::new(sid.clone(), old_ts, "/proj".to_string());
+        meta.confirmed = true;
+        meta.last_event_at = Some(old_ts);
+        state.domain.active_sessions.insert(sid.clone(), meta);
+
+        // Previous tick was before the laptop went to sleep
+        let now = Utc::now();
+        state.meta.resume.last_tick = Some((now - chrono::Duration::minutes(14), std::time::Instant::now()));
+        update(&mut state, AppEvent::Tick(now));
+
+        assert!(state.domain.active_sessions.contains_key(&sid));
+        let message = state.ui.command_message.as_ref().unwrap();
+        assert_eq!(message.text, "clock: resumed after suspend (14m0s asleep)");
+
+        // Once the grace period is over the session expires as usual
+        state.meta.resume.grace_until = None;
+        update(&mut state, AppEvent::Tick(now + chrono::Duration::seconds(1)));
+        assert!(!state.domain.active_sessions.contains_key(&sid));
+    }
+
     // -------------------------------------------------------------------------
     // Error handling
     // -------------------------------------------------------------------------
@@ -689,6 +1734,20 @@ mod tests {
     // ReplayComplete
     // -------------------------------------------------------------------------
 
+    #[test]
+    fn info_event_shows_toast_without_logging_error() {
+        let mut state = AppState::new();
+        update(&mut state, AppEvent::Info {
+            source: "/archives".into(),
+            message: "was removed; recreated it".into(),
+        });
+
+        let toast = state.ui.command_message.as_ref().unwrap();
+        assert_eq!(toast.text, "/archives: was removed; recreated it");
+        assert!(!toast.is_error);
+        assert!(state.meta.errors.is_empty());
+    }
+
     #[test]
     fn replay_complete_sets_flag() {
         let mut state = AppState::new();
@@ -699,6 +1758,53 @@ mod tests {
         assert!(state.meta.replay_complete);
     }
 
+    // -------------------------------------------------------------------------
+    // Watchdog
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn tick_requests_restart_for_exited_watcher_with_backoff() {
+        use crate::watcher::health::{HealthMap, POLLER};
+
+        let health = HealthMap::new();
+        drop(health.register(POLLER));
+        let mut state = AppState::new();
+        state.meta.watchdog.health = Some(health.clone());
+        let now = chrono::Utc::now();
+
+        update(&mut state, AppEvent::Tick(now));
+        assert!(state.meta.watchdog.is_degraded());
+        assert!(state.meta.watchdog.restart_requested);
+        assert!(state.meta.errors.back().unwrap().contains("poller exited"));
+
+        // Main loop restarted it, but the new thread died again straight away
+        state.meta.watchdog.restart_requested = false;
+        drop(health.register(POLLER));
+        update(&mut state, AppEvent::Tick(now + chrono::Duration::seconds(1)));
+        assert!(!state.meta.watchdog.restart_requested, "within backoff");
+
+        update(&mut state, AppEvent::Tick(now + WatchdogState::RESTART_BACKOFF));
+        assert!(state.meta.watchdog.restart_requested);
+    }
+
+    #[test]
+    fn tick_clears_degraded_once_watcher_beats_again() {
+        use crate::watcher::health::{HealthMap, POLLER};
+
+        let health = HealthMap::new();
+        let heartbeat = health.register(POLLER);
+        let mut state = AppState::new();
+        state.meta.watchdog.health = Some(health);
+
+        let later = chrono::Utc::now() + crate::watcher::health::HEARTBEAT_TIMEOUT;
+        update(&mut state, AppEvent::Tick(later));
+        assert!(state.meta.watchdog.is_degraded());
+
+        assert!(heartbeat.beat());
+        update(&mut state, AppEvent::Tick(chrono::Utc::now()));
+        assert!(!state.meta.watchdog.is_degraded());
+    }
+
     // -------------------------------------------------------------------------
     // SessionLoaded
     // -------------------------------------------------------------------------
@@ -724,6 +1830,152 @@ mod tests {
         assert!(state.domain.sessions[0].data.is_some());
     }
 
+    #[test]
+    fn session_loaded_caches_preview() {
+        use crate::model::SessionArchive;
+
+        let mut state = AppState::new();
+        let sid = SessionId::new("sess-prev");
+        let meta = SessionMeta::new(sid.clone(), Utc::now(), "/proj".to_string());
+        state.domain.sessions.push(ArchivedSession::new(meta.clone(), PathBuf::from("/tmp/p.json")));
+
+        let archive = SessionArchive::new(meta)
+            .with_events(vec![TranscriptEvent::new(Utc::now(), TranscriptEventKind::UserMessage)]);
+        update(&mut state, AppEvent::SessionLoaded(archive));
+
+        assert_eq!(state.session_preview(&sid).unwrap().event_count, 1);
+    }
+
+    // -------------------------------------------------------------------------
+    // SessionPreviewLoaded
+    // -------------------------------------------------------------------------
+
+    #[test]
+    fn session_head_then_event_pages_fill_archive() {
+        use crate::model::SessionArchive;
+
+        let mut state = AppState::new();
+        let sid = SessionId::new("sess-paged");
+        let meta = SessionMeta::new(sid.clone(), Utc::now(), "/proj".to_string());
+        state.domain.sessions.push(ArchivedSession::new(meta.clone(), PathBuf::from("/tmp/paged.json")));
+        state.ui.selected_session_id = Some(sid.clone());
+        state.ui.loading_session = Some(sid.clone());
+
+        let paging = EventPaging::new(vec![0..0; 3], 0);
+        update(&mut state, AppEvent::SessionHeadLoaded { archive: SessionArchive::new(meta), paging: paging.clone(), stamp: None });
+
+        // Detail opens immediately; first page requested because nothing is visible yet
+        assert!(matches!(state.ui.view, ViewState::SessionDetail));
+        assert!(state.ui.loading_session.is_none());
+        assert_eq!(state.domain.sessions[0].paging, Some(paging));
+        assert_eq!(state.ui.loading_events.as_ref(), Some(&sid));
+
+        let event = |content: &str| {
+            TranscriptEvent::new(Utc::now(), TranscriptEventKind::AssistantMessage { content: content.into() })
+        };
+        update(&mut state, AppEvent::SessionEventsLoaded {
+            session_id: sid.clone(),
+            start: 1,
+            events: vec![event("b"), event("c")],
+        });
+        assert_eq!(state.domain.sessions[0].paging.as_ref().unwrap().remaining(), 1);
+        assert!(state.session_preview(&sid).is_none());
+
+        state.ui.loading_events = Some(sid.clone());
+        update(&mut state, AppEvent::SessionEventsLoaded { session_id: sid.clone(), start: 0, events: vec![event("a")] });
+
+        let session = &state.domain.sessions[0];
+        assert!(session.paging.is_none());
+        assert!(state.ui.loading_events.is_none());
+        let contents: Vec<_> = session.data.as_ref().unwrap().events.iter().map(|e| match &e.kind {
+            TranscriptEventKind::AssistantMessage { content } => content.as_str(),
+            _ => "",
+        }).collect();
+        assert_eq!(contents, ["a", "b", "c"]);
+        // Preview cached only once the archive is complete
+        assert_eq!(state.session_preview(&sid).unwrap().event_count, 3);
+    }
+
+    #[test]
+    fn error_for_event_page_stops_paging() {
+        use crate::error::{LoomError, SessionError};
+        use crate::model::SessionArchive;
+
+        let mut state = AppState::new();
+        let sid = SessionId::new("sess-bad-page");
+        let meta = SessionMeta::new(sid.clone(), Utc::now(), "/proj".to_string());
+        let mut archived = ArchivedSession::new(meta.clone(), PathBuf::from("/tmp/bad.json"))
+            .with_data(SessionArchive::new(meta));
+        archived.paging = Some(EventPaging::new(vec![0..0; 10], 0));
+        state.domain.sessions.push(archived);
+        state.ui.loading_events = Some(sid.clone());
+
+        update(&mut state, AppEvent::SessionLoadFailed {
+            session_id: sid.clone(),
+            load: SessionLoad::Events,
+            error: LoomError::Session(SessionError::Json("truncated".to_string())),
+        });
+
+        assert!(state.ui.loading_events.is_none());
+        assert!(state.domain.sessions[0].paging.is_none());
+        assert_eq!(state.meta.errors.back().unwrap(), "events:sess-bad-page: session: JSON: truncated");
+    }
+
+    #[test]
+    fn load_failure_only_clears_the_session_it_names() {
+        use crate::error::{LoomError, SessionError};
+
+        let mut state = AppState::new();
+        state.ui.loading_session = Some(SessionId::new("s1"));
+        state.ui.preview_loading = Some(SessionId::new("s1"));
+
+        // "s10" contains "s1", but it is a different session
+        for load in [SessionLoad::Head, SessionLoad::Preview] {
+            update(&mut state, AppEvent::SessionLoadFailed {
+                session_id: SessionId::new("s10"),
+                load,
+                error: LoomError::Session(SessionError::Json("bad".to_string())),
+            });
+        }
+
+        assert_eq!(state.ui.loading_session, Some(SessionId::new("s1")));
+        assert_eq!(state.ui.preview_loading, Some(SessionId::new("s1")));
+        assert!(!state.is_preview_failed(&SessionId::new("s1")));
+    }
+
+    #[test]
+    fn session_preview_loaded_caches_and_clears_loading() {
+        use crate::model::SessionPreview;
+
+        let mut state = AppState::new();
+        let sid = SessionId::new("sess-preview");
+        state.ui.preview_loading = Some(sid.clone());
+
+        let preview = SessionPreview { event_count: 12, ..Default::default() };
+        update(&mut state, AppEvent::SessionPreviewLoaded { session_id: sid.clone(), preview });
+
+        assert!(state.ui.preview_loading.is_none());
+        assert_eq!(state.session_preview(&sid).unwrap().event_count, 12);
+    }
+
+    #[test]
+    fn error_for_preview_load_marks_preview_failed() {
+        use crate::error::{LoomError, SessionError};
+
+        let mut state = AppState::new();
+        let sid = SessionId::new("sess-corrupt");
+        state.ui.preview_loading = Some(sid.clone());
+
+        update(&mut state, AppEvent::SessionLoadFailed {
+            session_id: sid.clone(),
+            load: SessionLoad::Preview,
+            error: LoomError::Session(SessionError::Json("bad".to_string())),
+        });
+
+        assert!(state.ui.preview_loading.is_none());
+        assert!(state.is_preview_failed(&sid));
+    }
+
     // -------------------------------------------------------------------------
     // SessionMetasLoaded
     // -------------------------------------------------------------------------
@@ -742,6 +1994,134 @@ mod tests {
         assert_eq!(state.domain.sessions.len(), 2);
     }
 
+    #[test]
+    fn partial_metas_fill_the_list_before_the_scan_finishes() {
+        let mut state = AppState::new();
+        let now = Utc::now();
+        let meta = |id: &str, minutes: i64| SessionMeta::new(id, now - chrono::Duration::minutes(minutes), "/proj".to_string());
+        let path = |id: &str| PathBuf::from(format!("/tmp/{id}.json"));
+
+        update(&mut state, AppEvent::SessionMetasLoadedPartial(vec![(path("old"), meta("old", 30))]));
+        update(&mut state, AppEvent::SessionMetasLoadedPartial(vec![(path("new"), meta("new", 1)), (path("old"), meta("old", 20))]));
+        let ids: Vec<_> = state.domain.sessions.iter().map(|s| s.meta.id.as_str()).collect();
+        assert_eq!(ids, ["new", "old"]);
+
+        // The finished scan has the final say
+        update(&mut state, AppEvent::SessionMetasLoaded(vec![(path("new"), meta("new", 1))]));
+        assert_eq!(state.domain.sessions.len(), 1);
+    }
+
+    #[test]
+    fn session_metas_loaded_lists_only_current_project() {
+        let mut state = AppState::new().with_project_path("/home/u/app".to_string());
+        let now = Utc::now();
+        let metas = vec![
+            (PathBuf::from("/tmp/a/s1.json"), SessionMeta::new("s1", now, "/home/u/app".to_string())),
+            (PathBuf::from("/tmp/b/s2.json"), SessionMeta::new("s2", now, "/home/u/other".to_string())),
+        ];
+
+        update(&mut state, AppEvent::SessionMetasLoaded(metas));
+
+        assert_eq!(state.domain.archive_index.len(), 2);
+        let ids: Vec<_> = state.domain.sessions.iter().map(|s| s.meta.id.as_str()).collect();
+        assert_eq!(ids, vec!["s1"]);
+
+        state.ui.project_filter = crate::app::ProjectFilter::All;
+        apply_project_filter(&mut state);
+        assert_eq!(state.domain.sessions.len(), 2);
+    }
+
+    #[test]
+    fn session_metas_rescan_keeps_loaded_data_and_selection() {
+        use crate::model::SessionArchive;
+
+        let mut state = AppState::with_view(ViewState::Sessions);
+        let now = Utc::now();
+        let meta = |id: &str| SessionMeta::new(id, now, "/proj".to_string());
+        state.domain.sessions = vec![
+            ArchivedSession::new(meta("unsaved"), PathBuf::new())
+                .with_data(SessionArchive::new(meta("unsaved"))),
+            ArchivedSession::new(meta("s1"), PathBuf::from("/tmp/s1.json")),
+            ArchivedSession::new(meta("s2"), PathBuf::from("/tmp/s2.json"))
+                .with_data(SessionArchive::new(meta("s2"))),
+        ];
+        state.ui.selected_session_index = Some(2);
+        state.ui.marked_sessions.insert(SessionId::new("s1"));
+
+        // s1 removed by another instance, s3 added
+        let metas = vec![
+            (PathBuf::from("/tmp/s3.json"), meta("s3")),
+            (PathBuf::from("/tmp/s2.json"), meta("s2")),
+        ];
+        update(&mut state, AppEvent::SessionMetasLoaded(metas));
+
+        let ids: Vec<_> = state.domain.sessions.iter().map(|s| s.meta.id.as_str()).collect();
+        assert_eq!(ids, ["unsaved", "s3", "s2"]);
+        assert!(state.domain.sessions[2].data.is_some());
+        assert_eq!(state.ui.selected_session_index, Some(2));
+        assert!(state.ui.marked_sessions.is_empty());
+    }
+
+    #[test]
+    fn session_metas_rescan_clamps_selection_when_highlighted_removed() {
+        let mut state = AppState::with_view(ViewState::Sessions);
+        let now = Utc::now();
+        state.domain.sessions = vec![
+            ArchivedSession::new(SessionMeta::new("s1", now, "/p".to_string()), PathBuf::from("/tmp/s1.json")),
+            ArchivedSession::new(SessionMeta::new("s2", now, "/p".to_string()), PathBuf::from("/tmp/s2.json")),
+        ];
+        state.ui.selected_session_index = Some(1);
+
+        let metas = vec![(PathBuf::from("/tmp/s1.json"), SessionMeta::new("s1", now, "/p".to_string()))];
+        update(&mut state, AppEvent::SessionMetasLoaded(metas));
+        assert_eq!(state.ui.selected_session_index, Some(0));
+
+        update(&mut state, AppEvent::SessionMetasLoaded(Vec::new()));
+        assert!(state.domain.sessions.is_empty());
+        assert_eq!(state.ui.selected_session_index, None);
+    }
+
+    #[test]
+    fn archive_metas_page_in_near_the_end_and_survive_a_rescan() {
+        let mut state = AppState::with_view(ViewState::Sessions);
+        state.ui.project_filter = crate::app::ProjectFilter::All;
+        let start = Utc::now();
+        let file = |i: usize| PathBuf::from(format!("/tmp/s{i:03}.json"));
+        let meta = |i: usize| {
+            (file(i), SessionMeta::new(format!("s{i:03}"), start - chrono::Duration::minutes(i as i64), "/p".to_string()))
+        };
+        let total = session::META_PAGE_SIZE + 10;
+        let first: Vec<_> = (0..session::META_PAGE_SIZE).map(meta).collect();
+
+        update(&mut state, AppEvent::ArchiveFilesListed((0..total).map(file).collect()));
+        update(&mut state, AppEvent::SessionMetasLoaded(first.clone()));
+        assert_eq!(state.domain.sessions.len(), session::META_PAGE_SIZE);
+        assert_eq!(state.domain.archive_pages.remaining(), 10);
+        // The cursor is at the top, far from the end of the loaded page
+        assert!(!state.ui.loading_metas);
+
+        // Moving down into the prefetch margin asks for the next page
+        state.ui.selected_session_index = Some(session::META_PAGE_SIZE - 52);
+        update(&mut state, AppEvent::Key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::empty())));
+        assert!(!state.ui.loading_metas);
+        update(&mut state, AppEvent::Key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::empty())));
+        assert!(state.ui.loading_metas);
+        let rest: Vec<PathBuf> = state.domain.archive_pages.next_page(session::META_PAGE_SIZE);
+        assert_eq!(rest.len(), 10);
+
+        let metas = (session::META_PAGE_SIZE..total).map(meta).collect();
+        update(&mut state, AppEvent::SessionMetaPageLoaded { paths: rest, metas });
+        assert!(!state.ui.loading_metas);
+        assert_eq!(state.domain.sessions.len(), total);
+        assert_eq!(state.domain.archive_pages.remaining(), 0);
+
+        // A rescan rereads only the first page but keeps the deeper ones
+        update(&mut state, AppEvent::ArchiveFilesListed((0..total).map(file).collect()));
+        update(&mut state, AppEvent::SessionMetasLoaded(first));
+        assert_eq!(state.domain.sessions.len(), total);
+        assert!(!state.ui.loading_metas);
+    }
+
     // -------------------------------------------------------------------------
     // AgentMetadataUpdated
     // -------------------------------------------------------------------------
@@ -772,6 +2152,7 @@ mod tests {
         let agent = &state.domain.agents[&aid];
         assert_eq!(agent.model, Some("claude-3-opus".to_string()));
         assert_eq!(agent.token_usage.input_tokens, 100);
+        assert_eq!(agent.context_samples, vec![100]);
     }
 
     #[test]
@@ -844,20 +2225,21 @@ mod tests {
     }
 
     // -------------------------------------------------------------------------
-    // Error — loading_session cleared on matching source
+    // Error — loading_session cleared only by a failed load of that session
     // -------------------------------------------------------------------------
 
     #[test]
-    fn error_clears_loading_session_when_source_matches() {
-        use crate::error::{LoomError, WatcherError};
+    fn load_failure_clears_loading_session_when_id_matches() {
+        use crate::error::{LoomError, SessionError};
 
         let mut state = AppState::new();
         let sid = SessionId::new("sess-failing");
         state.ui.loading_session = Some(sid.clone());
 
-        update(&mut state, AppEvent::Error {
-            source: format!("load:{}", sid.as_str()),
-            error: LoomError::Watcher(WatcherError::Io("not found".to_string())),
+        update(&mut state, AppEvent::SessionLoadFailed {
+            session_id: sid,
+            load: SessionLoad::Head,
+            error: LoomError::Session(SessionError::Json("not found".to_string())),
         });
 
         assert!(state.ui.loading_session.is_none());
@@ -963,4 +2345,44 @@ mod tests {
 
         assert!(state.domain.agents[&aid].finished_at.is_some());
     }
+
+    #[test]
+    fn git_diff_loaded_opens_popup_only_for_pending_task() {
+        use crate::app::{DiffPopupState, DiffRequest};
+        use crate::model::TaskId;
+        let mut state = AppState::new();
+        let request = DiffRequest {
+            task_id: TaskId::new("T1"),
+            repo: PathBuf::from("/repo"),
+            since: chrono::Utc::now(),
+            paths: vec!["a.rs".into()],
+        };
+        state.ui.diff_popup = DiffPopupState::Loading(request);
+
+        let output = "diff --git a/a.rs b/a.rs\n@@ -1 +1 @@\n-x\n+y\n".to_string();
+        update(&mut state, AppEvent::GitDiffLoaded { task_id: TaskId::new("T2"), result: Ok(output.clone()) });
+        assert!(matches!(state.ui.diff_popup, DiffPopupState::Loading(_)));
+
+        update(&mut state, AppEvent::GitDiffLoaded { task_id: TaskId::new("T1"), result: Ok(output) });
+        match &state.ui.diff_popup {
+            DiffPopupState::Open { diff, .. } => assert_eq!((diff.added, diff.removed), (1, 1)),
+            other => panic!("expected open popup, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn git_diff_failure_closes_popup_with_error() {
+        use crate::app::{DiffPopupState, DiffRequest};
+        use crate::model::TaskId;
+        let mut state = AppState::new();
+        state.ui.diff_popup = DiffPopupState::Loading(DiffRequest {
+            task_id: TaskId::new("T1"),
+            repo: PathBuf::from("/repo"),
+            since: chrono::Utc::now(),
+            paths: vec![],
+        });
+        update(&mut state, AppEvent::GitDiffLoaded { task_id: TaskId::new("T1"), result: Err("not a git repository".into()) });
+        assert_eq!(state.ui.diff_popup, DiffPopupState::Closed);
+        assert!(state.ui.command_message.as_ref().unwrap().is_error);
+    }
 }
@@ -0,0 +1,88 @@
+//! Opt-in startup check for a newer release; nothing is downloaded or installed.
+//! Why and how: `docs/design.md`, "Release check".
+
+use std::process::{Command, Stdio};
+
+use serde::Deserialize;
+
+/// Latest release of loom-tui on GitHub.
+pub const RELEASES_URL: &str = "https://api.github.com/repos/peterstorm/loom-tui/releases/latest";
+
+/// A published release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    /// Version without the leading `v`, e.g. "0.4.0"
+    pub version: String,
+    /// Release notes (markdown as written on GitHub)
+    pub notes: String,
+}
+
+#[derive(Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    #[serde(default)]
+    body: Option<String>,
+}
+
+/// Pure function: the release described by a GitHub releases API response.
+pub fn parse_release(json: &str) -> Result<Release, String> {
+    let release: GithubRelease = serde_json::from_str(json).map_err(|e| format!("release response: {}", e))?;
+    Ok(Release {
+        version: release.tag_name.trim_start_matches('v').to_string(),
+        notes: release.body.unwrap_or_default().replace("\r\n", "\n"),
+    })
+}
+
+/// Pure function: `major.minor.patch` of a version; pre-release suffixes are ignored.
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.trim().trim_start_matches('v').split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+    let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+    Some((major, minor, patch))
+}
+
+/// Pure function: is `candidate` newer than `current`? Unreadable versions never are.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    matches!((parse_version(candidate), parse_version(current)), (Some(c), Some(v)) if c > v)
+}
+
+/// Fetch the latest release with `curl` (5s timeout).
+/// I/O: runs curl. Returns `Err` with the failure on error.
+pub fn fetch_latest() -> Result<Release, String> {
+    let output = Command::new("curl")
+        .args(["-fsS", "-m", "5", "-H", "Accept: application/vnd.github+json", RELEASES_URL])
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("curl: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    parse_release(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_github_release() {
+        let json = r#"{"tag_name":"v0.4.0","name":"0.4.0","body":"- new hook fields\r\n- faster parser"}"#;
+        assert_eq!(
+            parse_release(json),
+            Ok(Release { version: "0.4.0".into(), notes: "- new hook fields\n- faster parser".into() })
+        );
+        assert!(parse_release(r#"{"message":"Not Found"}"#).is_err());
+    }
+
+    #[test]
+    fn compares_versions_numerically() {
+        assert!(is_newer("0.10.0", "0.9.3"));
+        assert!(is_newer("v1.0.0", "0.1.0"));
+        assert!(is_newer("0.1.1", "0.1.0-rc1"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+        assert!(!is_newer("0.0.9", "0.1.0"));
+        assert!(!is_newer("latest", "0.1.0"));
+    }
+}
@@ -0,0 +1,243 @@
+//! Crash safety: terminal restore, crash bundles and termination signals.
+//!
+//! A panic on the UI thread used to leave the terminal in raw mode on the
+//! alternate screen, with the report painted over the last frame. The panic
+//! hook now restores the terminal before color-eyre prints its report. On a
+//! panic or a fatal error main writes a crash bundle to
+//! `<archive_dir>/crashes/` — the cause, version, a summary of the state,
+//! watcher health, the settings in force and the last `CRASH_EVENTS` events —
+//! and prints its path; `loom-tui crash-report --latest` prints the newest
+//! one for a bug report. SIGTERM and SIGINT only raise a flag; the main loop
+//! sees it and quits through the normal cleanup.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use crossterm::{
+    cursor::Show,
+    execute,
+    terminal::{disable_raw_mode, LeaveAlternateScreen},
+};
+
+use crate::app::{settings, AppState};
+use crate::watcher::health::{self, Degradation};
+
+/// Directory under the archive directory holding crash logs.
+pub const CRASH_DIR: &str = "crashes";
+
+/// Events written to a crash log, newest last.
+pub const CRASH_EVENTS: usize = 100;
+
+/// Set by the signal handler.
+static TERMINATE: AtomicBool = AtomicBool::new(false);
+
+/// The UI thread's panic, with its backtrace, kept for the crash log.
+static LAST_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+/// Leave raw mode and the alternate screen, show the cursor. Errors are
+/// ignored: there is nothing left to report them to.
+pub fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(std::io::stdout(), LeaveAlternateScreen, Show);
+}
+
+/// Wrap the installed panic hook (color-eyre's): a panic on the UI thread
+/// restores the terminal and is kept for the crash log before the report
+/// prints. Panics on watcher threads are left to the watchdog.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() == Some("main") {
+            restore_terminal();
+            let backtrace = std::backtrace::Backtrace::force_capture();
+            if let Ok(mut last) = LAST_PANIC.lock() {
+                *last = Some(format!("{}\n\n{}", info, backtrace));
+            }
+        }
+        previous(info);
+    }));
+}
+
+/// The UI thread's last panic, if any.
+pub fn take_panic() -> Option<String> {
+    LAST_PANIC.lock().ok()?.take()
+}
+
+/// Turn SIGTERM and SIGINT into a quit request (see `terminate_requested`).
+#[cfg(unix)]
+pub fn install_signal_handlers() {
+    extern "C" fn on_signal(_: libc::c_int) {
+        TERMINATE.store(true, Ordering::SeqCst);
+    }
+    let handler: extern "C" fn(libc::c_int) = on_signal;
+    // SAFETY: the handler only stores to an atomic, which is async-signal-safe.
+    unsafe {
+        libc::signal(libc::SIGTERM, handler as libc::sighandler_t);
+        libc::signal(libc::SIGINT, handler as libc::sighandler_t);
+    }
+}
+
+#[cfg(not(unix))]
+pub fn install_signal_handlers() {}
+
+/// Has a termination signal arrived?
+pub fn terminate_requested() -> bool {
+    TERMINATE.load(Ordering::SeqCst)
+}
+
+/// Most recent errors listed in the state summary.
+const CRASH_ERRORS: usize = 5;
+
+/// Pure function: the crash bundle for `cause` (a panic or fatal error).
+pub fn crash_report(cause: &str, state: &AppState, now: DateTime<Utc>) -> String {
+    let version = env!("CARGO_PKG_VERSION");
+    let mut report = format!("loom-tui {} crashed at {}\n", version, now.to_rfc3339());
+    let mut section = |title: &str, body: String| report.push_str(&format!("\n== {} ==\n{}\n", title, body.trim_end()));
+    section("cause", cause.to_string());
+    section("state", state_summary(state));
+    section("watcher", watcher_health(state, now));
+    section("config", config(state));
+
+    let events = &state.domain.events;
+    let recent: Vec<String> = events
+        .iter()
+        .skip(events.len().saturating_sub(CRASH_EVENTS))
+        .map(|event| serde_json::to_string(event).unwrap_or_else(|e| format!("<unserializable: {}>", e)))
+        .collect();
+    section(&format!("last {} events", recent.len()), recent.join("\n"));
+    report
+}
+
+/// Pure function: what was on screen and in memory.
+fn state_summary(state: &AppState) -> String {
+    let domain = &state.domain;
+    let running = domain.agents.values().filter(|agent| agent.finished_at.is_none()).count();
+    let mut lines = vec![
+        format!("view: {:?} · replay complete: {}", state.ui.view, state.meta.replay_complete),
+        format!(
+            "sessions: {} active, {} archived · agents: {} ({} running) · events: {}",
+            domain.active_sessions.len(),
+            domain.sessions.len(),
+            domain.agents.len(),
+            running,
+            domain.events.len()
+        ),
+    ];
+    if let Some(graph) = &domain.task_graph {
+        lines.push(format!(
+            "task graph: {}/{} tasks done · {} waves",
+            graph.completed_tasks(),
+            graph.total_tasks(),
+            graph.waves.len()
+        ));
+    }
+    let errors = &state.meta.errors;
+    lines.extend(errors.iter().skip(errors.len().saturating_sub(CRASH_ERRORS)).map(|e| format!("error: {}", e)));
+    lines.join("\n")
+}
+
+/// Pure function: each watcher thread's last heartbeat.
+fn watcher_health(state: &AppState, now: DateTime<Utc>) -> String {
+    let Some(health) = &state.meta.watchdog.health else {
+        return "no watcher".to_string();
+    };
+    let snapshot = health.snapshot();
+    let degraded = health::degraded_threads(&snapshot, now);
+    snapshot
+        .iter()
+        .map(|(name, status)| {
+            let verdict = match degraded.iter().find(|(n, _)| n == name).map(|(_, d)| d) {
+                Some(Degradation::Exited) => "exited",
+                Some(Degradation::Stalled) => "stalled",
+                None => "ok",
+            };
+            let ago = (now - status.last_beat).num_seconds();
+            format!("{}: {} (generation {}, last beat {}s ago)", name, verdict, status.generation, ago)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Pure function: the settings in force, with the webhook URL redacted.
+fn config(state: &AppState) -> String {
+    let mut settings = settings::current(state);
+    if settings.cost_alert_webhook.is_some() {
+        settings.cost_alert_webhook = Some("<redacted>".to_string());
+    }
+    serde_json::to_string_pretty(&settings).unwrap_or_else(|e| format!("<unserializable: {}>", e))
+}
+
+/// Write a crash bundle to `dir/crash-<time>.log`.
+/// I/O: creates the directory and the file.
+pub fn write_crash_log(dir: &Path, report: &str, now: DateTime<Utc>) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!("crash-{}.log", now.format("%Y%m%d-%H%M%S")));
+    std::fs::File::create(&path)?.write_all(report.as_bytes())?;
+    Ok(path)
+}
+
+/// The crash bundles in `dir`, newest first (their names sort by time).
+/// I/O: lists the directory.
+pub fn crash_logs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut logs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+            name.starts_with("crash-") && name.ends_with(".log")
+        })
+        .collect();
+    logs.sort();
+    logs.reverse();
+    logs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{TranscriptEvent, TranscriptEventKind};
+    use crate::watcher::health::HealthMap;
+
+    #[test]
+    fn crash_bundle_has_cause_state_watcher_config_and_last_events() {
+        let now = Utc::now();
+        let mut state = AppState::new();
+        state.meta.cost_budget.webhook = Some("https://hooks.example/secret".into());
+        state.meta.errors.push_back("watcher: disk full".into());
+        let health = HealthMap::new();
+        let _beat = health.register(health::POLLER);
+        state.meta.watchdog.health = Some(health);
+        state.domain.events = (0..CRASH_EVENTS + 5)
+            .map(|i| TranscriptEvent::new(now, TranscriptEventKind::AssistantMessage { content: format!("m{}", i) }))
+            .collect();
+
+        let report = crash_report("panicked at src/app/update.rs:1:1:\nboom", &state, now);
+        assert!(report.contains("== cause ==\npanicked at src/app/update.rs:1:1:\nboom\n"));
+        assert!(report.contains("events: 105"));
+        assert!(report.contains("error: watcher: disk full"));
+        assert!(report.contains("poller: ok (generation 0"));
+        assert!(report.contains("<redacted>") && !report.contains("secret"));
+        assert!(report.contains("== last 100 events =="));
+        assert!(!report.contains("\"m4\""));
+        assert!(report.contains("\"m5\"") && report.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn crash_logs_list_newest_first() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join(CRASH_DIR);
+        assert!(crash_logs(&dir).is_empty());
+        let earlier = Utc::now() - chrono::Duration::hours(1);
+        let first = write_crash_log(&dir, "first", earlier).unwrap();
+        let second = write_crash_log(&dir, "second", Utc::now()).unwrap();
+        std::fs::write(dir.join("notes.txt"), "").unwrap();
+        assert_eq!(crash_logs(&dir), [second.clone(), first]);
+        assert_eq!(std::fs::read_to_string(second).unwrap(), "second");
+    }
+}
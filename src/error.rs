@@ -10,6 +10,15 @@ pub enum ParseError {
     Json(String),
     #[error("invalid format: {0}")]
     InvalidFormat(String),
+    /// A transcript entry that parsed but lacks fields its kind requires
+    #[error("{entry_type} at line {line} {problem} — {consequence}")]
+    Schema {
+        entry_type: String,
+        line: usize,
+        problem: String,
+        /// What loom-tui did instead (skipped, defaulted), so the user can judge impact
+        consequence: &'static str,
+    },
 }
 
 #[derive(Debug, Clone, thiserror::Error)]
@@ -18,6 +27,8 @@ pub enum SessionError {
     Json(String),
     #[error("I/O {path}: {message}")]
     Io { path: String, message: String },
+    #[error("{path} is being written by another loom-tui instance")]
+    Locked { path: String },
 }
 
 impl From<serde_json::Error> for SessionError {
@@ -40,6 +51,80 @@ impl From<std::io::Error> for WatcherError {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CommandError {
+    #[error("unknown command: {0}")]
+    Unknown(String),
+    #[error("{command}: expected {expected}")]
+    MissingArgument { command: &'static str, expected: &'static str },
+    #[error("{command}: invalid argument '{value}' (expected {expected})")]
+    InvalidArgument { command: &'static str, value: String, expected: &'static str },
+    #[error("{command}: {message}")]
+    Failed { command: &'static str, message: String },
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ConfigError {
+    #[error("config JSON: {0}")]
+    Json(String),
+    #[error("I/O {path}: {message}")]
+    Io { path: String, message: String },
+}
+
+impl From<serde_json::Error> for ConfigError {
+    fn from(e: serde_json::Error) -> Self {
+        ConfigError::Json(e.to_string())
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ReviewError {
+    #[error("review JSON: {0}")]
+    Json(String),
+    #[error("I/O {path}: {message}")]
+    Io { path: String, message: String },
+}
+
+impl From<serde_json::Error> for ReviewError {
+    fn from(e: serde_json::Error) -> Self {
+        ReviewError::Json(e.to_string())
+    }
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SpendError {
+    #[error("spend JSON: {0}")]
+    Json(String),
+    #[error("I/O {path}: {message}")]
+    Io { path: String, message: String },
+}
+
+impl From<serde_json::Error> for SpendError {
+    fn from(e: serde_json::Error) -> Self {
+        SpendError::Json(e.to_string())
+    }
+}
+
+/// An event rule from the config (channel or severity style) that could not
+/// be used. `rule` names it: `channel tests`, `style bold red`.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum RuleError {
+    #[error("channel '{0}': expected `name: rule`")]
+    MissingName(String),
+    #[error("severity rule '{0}': expected `style: rule`")]
+    MissingStyle(String),
+    #[error("{rule}: unknown style word '{word}' (expected a color, bold, italic, underline, dim, reversed or `on <color>`)")]
+    UnknownStyle { rule: String, word: String },
+    #[error("{rule}: empty rule")]
+    EmptyRule { rule: String },
+    #[error("{rule}: unterminated quote")]
+    UnterminatedQuote { rule: String },
+    #[error("{rule}: unknown kind '{kind}' (expected pretool, posttool, user, assistant)")]
+    UnknownKind { rule: String, kind: String },
+    #[error("{rule}: invalid regex '{pattern}': {message}")]
+    Regex { rule: String, pattern: String, message: String },
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum LoomError {
     #[error("session: {0}")]
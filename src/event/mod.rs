@@ -3,9 +3,17 @@ use std::path::PathBuf;
 use chrono::{DateTime, Utc};
 use crossterm::event::KeyEvent;
 
+use crate::app::anomaly::AgentBaselines;
+use crate::app::cost_history::CostRecord;
+use crate::app::agent_defs::AgentDefinition;
+use crate::app::in_flight::IoKind;
+use crate::app::spec_docs::SpecDoc;
+use crate::app::tool_outputs::{OutputChunk, ToolOutputIndex};
+use crate::app::updates::Release;
 use crate::error::LoomError;
-use crate::model::{AgentId, SessionArchive, SessionId, SessionMeta, TaskGraph, TokenUsage};
+use crate::model::{AgentId, ArchiveStamp, EventPaging, SessionArchive, SessionId, SessionMeta, SessionPreview, TaskGraph, TaskId, TokenUsage};
 use crate::model::TranscriptEvent;
+use crate::watcher::schema_drift::SchemaDrift;
 use crate::watcher::TranscriptMetadata;
 
 /// All events that can occur in the application.
@@ -21,6 +29,9 @@ pub enum AppEvent {
     /// New session discovered on disk (transcript path found)
     SessionDiscovered { session_id: SessionId, transcript_path: PathBuf },
 
+    /// A hook payload named the orchestration run a session belongs to
+    SessionRunTagged { session_id: SessionId, run_id: String },
+
     /// Session completed (no more activity expected)
     SessionCompleted { session_id: SessionId },
 
@@ -33,11 +44,12 @@ pub enum AppEvent {
         metadata: TranscriptMetadata,
     },
 
-    /// Session-level metadata from main transcript (model, tokens)
+    /// Session-level metadata from main transcript (model, tokens, initial prompt)
     SessionMetadataUpdated {
         session_id: SessionId,
         model: Option<String>,
         token_usage: TokenUsage,
+        goal: Option<String>,
     },
 
     /// Agent transcript finished (result entry seen or idle timeout)
@@ -52,19 +64,103 @@ pub enum AppEvent {
     /// Error occurred (non-fatal - parse, I/O, watcher, session)
     Error { source: String, error: LoomError },
 
+    /// A background read of an archived session failed
+    SessionLoadFailed { session_id: SessionId, load: SessionLoad, error: LoomError },
+
+    /// Informational notice worth surfacing to the user (e.g. a watched dir recovered)
+    Info { source: String, message: String },
+
     /// Session loaded from archive
     SessionLoaded(SessionArchive),
 
-    /// Lightweight session metas loaded at startup
+    /// Archive loaded without its events, with where they sit on disk for paging in
+    /// and the file's stamp at load time
+    SessionHeadLoaded { archive: SessionArchive, paging: EventPaging, stamp: Option<ArchiveStamp> },
+
+    /// Page of older archive events, covering indices `start..` up to the already loaded tail
+    SessionEventsLoaded { session_id: SessionId, start: usize, events: Vec<TranscriptEvent> },
+
+    /// Per-agent-type history scanned from the archives at startup
+    AgentBaselinesLoaded(AgentBaselines),
+
+    /// Spend of every archived session, scanned from the archives at startup
+    CostRecordsLoaded(Vec<CostRecord>),
+
+    /// Latest published release (or why it could not be fetched), from the opt-in startup check
+    ReleaseChecked(Result<Release, String>),
+
+    /// A background write finished, with its error if it failed
+    WriteFinished { kind: IoKind, error: Option<String> },
+
+    /// Archive files found by an archive scan, newest first
+    ArchiveFilesListed(Vec<PathBuf>),
+
+    /// Metas of the newest archives, read by an archive scan; older pages
+    /// already read are kept for files still on disk
     SessionMetasLoaded(Vec<(PathBuf, SessionMeta)>),
 
+    /// Some of those metas, sent as soon as a reader thread has them so the
+    /// Sessions list fills in while the scan goes on
+    SessionMetasLoadedPartial(Vec<(PathBuf, SessionMeta)>),
+
+    /// Page of archive metas read on demand; `paths` were read, successfully or not
+    SessionMetaPageLoaded { paths: Vec<PathBuf>, metas: Vec<(PathBuf, SessionMeta)> },
+
     /// Request to load a full session archive by session ID
     LoadSessionRequested(SessionId),
 
+    /// Preview summary built in the background for the Sessions view
+    SessionPreviewLoaded { session_id: SessionId, preview: SessionPreview },
+
+    /// `git diff` output (or git's error) for the task in the diff popup
+    GitDiffLoaded { task_id: TaskId, result: Result<String, String> },
+
+    /// Captured tool outputs found in the state dir (sent when the set changes)
+    ToolOutputsIndexed(ToolOutputIndex),
+
+    /// Chunk of a captured output read from `offset` for the output pager
+    ToolOutputChunkLoaded { path: PathBuf, offset: u64, result: Result<OutputChunk, String> },
+
+    /// The spec / plan in use changed (re-read on every edit); `None` when there is none
+    SpecDocLoaded(Option<SpecDoc>),
+
+    /// The project's agent definitions changed (re-read on every edit)
+    AgentDefinitionsLoaded(Vec<AgentDefinition>),
+
+    /// Unknown transcript fields counted while parsing a batch of entries
+    SchemaDriftObserved(SchemaDrift),
+
     /// Initial event file replay is complete — safe to run stale session cleanup
     ReplayComplete,
 }
 
+/// Which background read of an archive a `SessionLoadFailed` came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionLoad {
+    /// Everything but the events, for SessionDetail
+    Head,
+    /// A page of older events
+    Events,
+    /// Summary for the Sessions preview pane
+    Preview,
+}
+
+impl SessionLoad {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Head => "session",
+            Self::Events => "events",
+            Self::Preview => "preview",
+        }
+    }
+}
+
+impl std::fmt::Display for SessionLoad {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
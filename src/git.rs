@@ -0,0 +1,186 @@
+use std::path::Path;
+use std::process::Command;
+
+use chrono::{DateTime, Utc};
+
+/// Hash of the empty tree, the diff base when the repo has no commit before the session.
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// Kind of a line in `git diff` output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    /// `diff --git a/… b/…`
+    FileHeader,
+    /// `index`, `---`, `+++`, mode and rename lines
+    Meta,
+    /// `@@ -a,b +c,d @@`
+    Hunk,
+    Added,
+    Removed,
+    Context,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub text: String,
+}
+
+/// Parsed `git diff` output with totals for the popup title.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diff {
+    pub lines: Vec<DiffLine>,
+    pub files: usize,
+    pub added: usize,
+    pub removed: usize,
+}
+
+/// Parse unified `git diff` output.
+///
+/// # Functional Core
+/// Pure function.
+pub fn parse_diff(output: &str) -> Diff {
+    let mut diff = Diff::default();
+    let mut in_header = false;
+    for line in output.lines() {
+        let kind = if line.starts_with("diff --git ") {
+            in_header = true;
+            diff.files += 1;
+            DiffLineKind::FileHeader
+        } else if line.starts_with("@@") {
+            in_header = false;
+            DiffLineKind::Hunk
+        } else if in_header {
+            DiffLineKind::Meta
+        } else if line.starts_with('+') {
+            diff.added += 1;
+            DiffLineKind::Added
+        } else if line.starts_with('-') {
+            diff.removed += 1;
+            DiffLineKind::Removed
+        } else {
+            DiffLineKind::Context
+        };
+        diff.lines.push(DiffLine { kind, text: line.to_string() });
+    }
+    diff
+}
+
+/// `git diff` of `paths` in `repo` between the last commit before `since` and
+/// the working tree, so both committed and uncommitted changes show. Untracked
+/// files among `paths` are appended as new-file diffs.
+/// Returns `Err` with git's stderr on failure.
+pub fn diff_since(repo: &Path, since: DateTime<Utc>, paths: &[String]) -> Result<String, String> {
+    let base = run_git(repo, &["rev-list", "-1", &format!("--before={}", since.to_rfc3339()), "HEAD"])?;
+    let base = match base.trim() {
+        "" => EMPTY_TREE.to_string(),
+        hash => hash.to_string(),
+    };
+    let mut args = vec!["diff", "--no-color", "--no-ext-diff", base.as_str(), "--"];
+    args.extend(paths.iter().map(String::as_str));
+    let mut output = run_git(repo, &args)?;
+
+    let mut args = vec!["ls-files", "--others", "--exclude-standard", "--"];
+    args.extend(paths.iter().map(String::as_str));
+    for untracked in run_git(repo, &args)?.lines() {
+        // `--no-index` exits 1 when the files differ, which they always do here
+        let (_, stdout) = git_output(repo, &["diff", "--no-color", "--no-ext-diff", "--no-index", "/dev/null", untracked])?;
+        output.push_str(&stdout);
+    }
+    Ok(output)
+}
+
+fn run_git(repo: &Path, args: &[&str]) -> Result<String, String> {
+    match git_output(repo, args)? {
+        (true, stdout) => Ok(stdout),
+        (false, _) => Err(format!("git {} failed", args.first().unwrap_or(&""))),
+    }
+}
+
+/// Run git in `repo`. Returns (exited successfully, stdout); `Err` with stderr
+/// when git could not run or reported an error (exit code above 1).
+fn git_output(repo: &Path, args: &[&str]) -> Result<(bool, String), String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()
+        .map_err(|e| format!("failed to run git: {}", e))?;
+
+    match output.status.code() {
+        Some(0) => Ok((true, String::from_utf8_lossy(&output.stdout).into_owned())),
+        Some(1) => Ok((false, String::from_utf8_lossy(&output.stdout).into_owned())),
+        _ => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_diff_classifies_lines_and_counts() {
+        let output = "\
+diff --git a/src/a.rs b/src/a.rs
+index 1111111..2222222 100644
+--- a/src/a.rs
++++ b/src/a.rs
+@@ -1,3 +1,3 @@
+ fn main() {
+-    old();
++    new();
++    more();
+ }
+diff --git a/b.rs b/b.rs
+new file mode 100644
+--- /dev/null
++++ b/b.rs
+@@ -0,0 +1 @@
++x
+";
+        let diff = parse_diff(output);
+        assert_eq!((diff.files, diff.added, diff.removed), (2, 3, 1));
+        let kinds: Vec<DiffLineKind> = diff.lines.iter().map(|l| l.kind).collect();
+        assert_eq!(kinds[..5], [
+            DiffLineKind::FileHeader,
+            DiffLineKind::Meta,
+            DiffLineKind::Meta,
+            DiffLineKind::Meta,
+            DiffLineKind::Hunk,
+        ]);
+        assert_eq!(kinds[5..9], [DiffLineKind::Context, DiffLineKind::Removed, DiffLineKind::Added, DiffLineKind::Added]);
+        assert_eq!(diff.lines[12].kind, DiffLineKind::Meta, "+++ in a header is not an addition");
+    }
+
+    #[test]
+    fn diff_since_covers_committed_uncommitted_and_untracked() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@t", "-C"])
+                .arg(repo)
+                .args(args)
+                .output()
+                .map(|o| o.status.success())
+                .unwrap_or(false)
+        };
+        if !git(&["init", "-q"]) {
+            return; // git not installed
+        }
+        std::fs::write(repo.join("a.rs"), "old\n").unwrap();
+        assert!(git(&["add", "a.rs"]));
+        assert!(git(&["commit", "-qm", "base", "--date=2000-01-01T00:00:00Z"]));
+        std::fs::write(repo.join("a.rs"), "new\n").unwrap();
+        std::fs::write(repo.join("b.rs"), "fresh\n").unwrap();
+
+        let since = Utc::now();
+        let diff = parse_diff(&diff_since(repo, since, &["a.rs".into(), "b.rs".into()]).unwrap());
+        assert_eq!((diff.files, diff.added, diff.removed), (2, 2, 1));
+    }
+
+    #[test]
+    fn parse_empty_output() {
+        assert_eq!(parse_diff(""), Diff::default());
+    }
+}
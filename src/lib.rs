@@ -1,10 +1,14 @@
 // Module declarations
 pub mod app;
+pub mod crash;
 pub mod error;
 pub mod event;
+pub mod git;
 pub mod model;
+pub mod notify;
 pub mod paths;
 pub mod session;
+pub mod snapshot;
 pub mod tmux;
 
 pub mod watcher;
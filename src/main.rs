@@ -6,35 +6,127 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use loom_tui::{
-    app::{update, AppState},
-    event::AppEvent,
+    app::{
+        anomaly,
+        bandwidth::{self, DrawLatency},
+        clipboard, cost, cost_history,
+        editor::{self, FileRef},
+        in_flight::{IoKind, QUIT_GRACE},
+        toasts::{self, ToastLevel},
+        tool_outputs::{self, OutputPager},
+        update, updates, AppState, CommandMessage, DiffPopupState, Suspend,
+    },
+    crash,
+    event::{AppEvent, SessionLoad},
+    git,
+    model::ColorMode,
+    notify,
     paths::Paths,
-    session,
+    session, snapshot,
     view::render,
-    watcher,
+    watcher::{
+        self,
+        schema_drift::scan_transcripts,
+        source::{SourceHandle, SourceKind},
+    },
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 
 fn main() -> Result<()> {
-    // Install color-eyre panic handler for better error messages
+    // Install color-eyre panic handler for better error messages, behind a
+    // hook that restores the terminal first; signals quit through cleanup
     color_eyre::install()?;
+    crash::install_panic_hook();
+    crash::install_signal_handlers();
 
-    // Parse CLI args: optional first arg is project root path
-    let args: Vec<String> = std::env::args().collect();
+    // Parse CLI args: optional first arg is project root path,
+    // or `paths [project_root]` to print where files are read from and exit,
+    // or `doctor [project_root]` to add the schema drift of recent transcripts,
+    // or `crash-report [project_root]` to list crash bundles (`--latest` prints the newest),
+    // or `snapshot [project_root] [--out PATH]` to write the dashboard as text and exit.
+    // `--no-color` / `--high-contrast` / `--linear` / `--ssh` may appear anywhere,
+    // as may `--stdin` / `--socket=PATH` to read events from a stream instead of files.
+    let mut argv: Vec<String> = std::env::args().collect();
+    let out = take_flag_value(&mut argv, "--out").map(PathBuf::from);
+    let (flags, args): (Vec<String>, Vec<String>) = argv.into_iter().partition(|a| a.starts_with("--"));
+    let subcommand = args.get(1).is_some_and(|a| ["paths", "doctor", "crash-report", "snapshot"].contains(&a.as_str()));
+    let doctor = subcommand && (args[1] == "paths" || args[1] == "doctor");
     let project_root = args
-        .get(1)
+        .get(if subcommand { 2 } else { 1 })
         .map(PathBuf::from)
         .unwrap_or_else(|| std::env::current_dir().expect("Failed to get current directory"));
 
     // Resolve all file paths
-    let paths = Paths::resolve(&project_root);
+    let report = Paths::report(&project_root);
+    if args.get(1).is_some_and(|a| a == "crash-report") {
+        let logs = crash::crash_logs(&report.paths.archive_dir.join(crash::CRASH_DIR));
+        if flags.iter().any(|f| f == "--latest") {
+            match logs.first() {
+                Some(path) => print!("{}", std::fs::read_to_string(path)?),
+                None => println!("no crash reports"),
+            }
+        } else {
+            logs.iter().for_each(|path| println!("{}", path.display()));
+        }
+        return Ok(());
+    }
+    if doctor {
+        print!("{}", report.render());
+        if args[1] == "doctor" {
+            let (drift, files) = scan_transcripts(&report.paths.transcript_dir);
+            println!("\nschema drift ({} transcripts):", files);
+            print!("{}", drift.render());
+        }
+        return Ok(());
+    }
+    let paths = report.paths.clone();
 
     // Initialize application state
     let mut state = AppState::new()
         .with_project_path(project_root.display().to_string());
+    state.meta.errors.extend(report.warnings());
+
+    // Saved settings (tick rate, stale timeout, event filters) from the user config
+    let config_path = Paths::user_config_path(&|key| std::env::var(key).ok());
+    match loom_tui::app::settings::load_settings(&config_path) {
+        Ok(settings) => loom_tui::app::settings::apply(&mut state, &settings),
+        Err(e) => state.meta.errors.push_back(e.to_string()),
+    }
+    state.meta.config_path = Some(config_path);
+
+    state.ui.linear_log = flags.iter().any(|f| f == "--linear");
+    state.ui.low_bandwidth = flags.iter().any(|f| f == "--ssh");
+
+    // Color flags (and the NO_COLOR convention) override the saved color mode
+    if flags.iter().any(|f| f == "--high-contrast") {
+        state.ui.color_mode = ColorMode::HighContrast;
+    }
+    if flags.iter().any(|f| f == "--no-color") || std::env::var("NO_COLOR").is_ok_and(|v| !v.is_empty()) {
+        state.ui.color_mode = ColorMode::NoColor;
+    }
+
+    // Human review decisions recorded so far (Review view)
+    let reviews_path = loom_tui::app::review::reviews_path(&paths.task_graph);
+    match loom_tui::app::review::load_reviews(&reviews_path) {
+        Ok(reviews) => state.domain.reviews = reviews,
+        Err(e) => state.meta.errors.push_back(e.to_string()),
+    }
+    state.meta.reviews_path = Some(reviews_path);
+
+    // Today's spend so far, for the daily cost budget
+    let spend_path = loom_tui::app::cost::spend_path(&paths.task_graph);
+    match loom_tui::app::cost::load_spend(&spend_path) {
+        Ok(spend) => {
+            state.domain.cost_alerts = loom_tui::app::cost::CostAlerts::resume(&spend);
+            state.domain.daily_spend = spend;
+        }
+        Err(e) => state.meta.errors.push_back(e.to_string()),
+    }
+    state.meta.spend_path = Some(spend_path);
 
     // Load deleted session tombstones
     state.meta.archive_dir = Some(paths.archive_dir.clone());
@@ -43,6 +135,23 @@ fn main() -> Result<()> {
         .into_iter()
         .map(loom_tui::model::SessionId::new)
         .collect();
+    // Archives from before per-project namespacing move into their project's subdir
+    if let Err(e) = session::migrate_flat_archives(&paths.archive_dir) {
+        state.meta.errors.push_back(e.to_string());
+    }
+    // Trashed archives past the undo grace period are gone for good
+    if let Err(e) = session::purge_trash(
+        &paths.archive_dir,
+        chrono::Duration::days(loom_tui::app::undo::TRASH_GRACE_DAYS),
+        Utc::now(),
+    ) {
+        state.meta.errors.push_back(e.to_string());
+    }
+
+    // No terminal for a snapshot: load once, render to text, exit
+    if args.get(1).is_some_and(|a| a == "snapshot") {
+        return write_snapshot(&mut state, &paths, &project_root, out.as_deref());
+    }
 
     // Terminal initialization
     enable_raw_mode()?;
@@ -51,62 +160,220 @@ fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Start file watchers (returns channel for receiving events)
-    let watcher_rx = watcher::start_watching(&paths)
-        .map_err(|e| color_eyre::eyre::eyre!("Failed to start file watcher: {}", e))?;
+    // Start the event source (file watchers unless a stream was requested)
+    let source_kind = SourceKind::from_flags(&flags);
+    state.meta.source = source_kind.clone();
+    let extra_logs: Vec<_> = state.meta.extra_logs.iter().map(|log| log.resolved(&project_root)).collect();
+    let (watcher, watcher_rx) = watcher::source::start_source(&source_kind, &paths, &extra_logs, state.meta.truncation)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to start event source: {}", e))?;
+    state.meta.watchdog.health = Some(watcher.health());
 
     // Main event loop (Elm Architecture)
-    let tick_rate = Duration::from_millis(250);
     let mut last_tick = Instant::now();
 
-    let result = run_event_loop(
-        &mut terminal,
-        &mut state,
-        &watcher_rx,
-        tick_rate,
-        &mut last_tick,
-    );
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        run_event_loop(&mut terminal, &mut state, &watcher, &watcher_rx, &mut last_tick)
+    }));
+
+    // The panic hook already restored the terminal; leave a crash bundle behind
+    let crash_dir = paths.archive_dir.join(crash::CRASH_DIR);
+    let result = match result {
+        Ok(result) => result,
+        Err(payload) => {
+            let panic = crash::take_panic().unwrap_or_else(|| "panic".to_string());
+            write_crash_bundle(&crash_dir, &panic, &state);
+            std::panic::resume_unwind(payload);
+        }
+    };
 
     // Terminal cleanup (always execute even if event loop errored)
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
+    if let SourceKind::Socket(ref path) = source_kind {
+        let _ = std::fs::remove_file(path);
+    }
+    // Writes still running past the grace period may leave their files short
+    if state.meta.in_flight.writes() > 0 {
+        eprintln!(
+            "loom-tui: quit with {} unfinished after {}s",
+            state.meta.in_flight.writes_label(),
+            QUIT_GRACE.as_secs()
+        );
+    }
+
+    // A fatal error gets a crash bundle too
+    if let Err(ref e) = result {
+        write_crash_bundle(&crash_dir, &format!("{:?}", e), &state);
+    }
 
     // Return event loop result
     result
 }
 
+/// Remove `name PATH` or `name=PATH` from `argv`, returning the value.
+fn take_flag_value(argv: &mut Vec<String>, name: &str) -> Option<String> {
+    let prefix = format!("{}=", name);
+    let at = argv.iter().position(|a| a == name || a.starts_with(&prefix))?;
+    let flag = argv.remove(at);
+    match flag.strip_prefix(&prefix) {
+        Some(value) => Some(value.to_string()),
+        None => (at < argv.len()).then(|| argv.remove(at)),
+    }
+}
+
+/// Load the current state once from the project's files, render the dashboard
+/// to text and write it to `out` (standard output without one).
+fn write_snapshot(state: &mut AppState, paths: &Paths, project_root: &Path, out: Option<&Path>) -> Result<()> {
+    let extra_logs: Vec<_> = state.meta.extra_logs.iter().map(|log| log.resolved(project_root)).collect();
+    let (_watcher, watcher_rx) = watcher::start_supervised(paths, &extra_logs, state.meta.truncation)
+        .map_err(|e| color_eyre::eyre::eyre!("Failed to start event source: {}", e))?;
+    if let Some(dir) = state.meta.archive_dir.clone() {
+        update(state, AppEvent::AgentBaselinesLoaded(anomaly::load_baselines(&dir)));
+    }
+    if !snapshot::load(state, &watcher_rx, snapshot::SETTLE, snapshot::REPLAY_LIMIT) {
+        eprintln!(
+            "loom-tui: transcript replay did not finish within {}s; the snapshot is partial",
+            snapshot::REPLAY_LIMIT.as_secs()
+        );
+    }
+    let text = snapshot::render_text(state, snapshot::SNAPSHOT_WIDTH, snapshot::SNAPSHOT_HEIGHT);
+    snapshot::write(out, &text)?;
+    if let Some(path) = out {
+        eprintln!("loom-tui: snapshot written to {}", path.display());
+    }
+    Ok(())
+}
+
+/// Write a crash bundle for `cause` and say where it went.
+fn write_crash_bundle(dir: &std::path::Path, cause: &str, state: &AppState) {
+    let now = state.meta.clock.now();
+    match crash::write_crash_log(dir, &crash::crash_report(cause, state, now), now) {
+        Ok(path) => eprintln!("loom-tui: crash report written to {} (see `loom-tui crash-report --latest`)", path.display()),
+        Err(e) => eprintln!("loom-tui: could not write crash report: {}", e),
+    }
+}
+
 /// Main event loop following Elm Architecture.
 /// Separated from main() for testability.
+/// The tick interval is read from `state.effective_tick_rate` each pass (`:set tick=`,
+/// idle throttling). While idle the loop still wakes at `meta.tick_rate` to drain the
+/// watcher, so a landing event redraws immediately; quiet wake-ups skip the redraw.
 fn run_event_loop(
     terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
     state: &mut AppState,
+    watcher: &SourceHandle,
     watcher_rx: &std::sync::mpsc::Receiver<AppEvent>,
-    tick_rate: Duration,
     last_tick: &mut Instant,
 ) -> Result<()> {
     // Channel for background session loads
     let (load_tx, load_rx) = std::sync::mpsc::channel::<AppEvent>();
     let mut load_in_flight = false;
 
+    // Channel for background archive event pages (SessionDetail)
+    let (page_tx, page_rx) = std::sync::mpsc::channel::<AppEvent>();
+    let mut page_in_flight = false;
+
+    // Channel for background pages of archive metas (Sessions view)
+    let (meta_tx, meta_rx) = std::sync::mpsc::channel::<AppEvent>();
+    let mut meta_in_flight = false;
+
+    // Channel for background session preview loads (Sessions view)
+    let (preview_tx, preview_rx) = std::sync::mpsc::channel::<AppEvent>();
+    let mut preview_in_flight = false;
+
+    // Channel for `git diff` runs (diff popup)
+    let (diff_tx, diff_rx) = std::sync::mpsc::channel::<AppEvent>();
+    let mut diff_in_flight = false;
+
+    // Channel for fire-and-forget background work (alert delivery, archive scans)
+    let (bg_tx, bg_rx) = std::sync::mpsc::channel::<AppEvent>();
+
+    // Agent-type history for anomaly flags: scan the archives once, off the UI thread
+    if let Some(dir) = state.meta.archive_dir.clone() {
+        let tx = bg_tx.clone();
+        state.meta.in_flight.start(IoKind::Load);
+        std::thread::spawn(move || {
+            let _ = tx.send(AppEvent::AgentBaselinesLoaded(anomaly::load_baselines(&dir)));
+        });
+    }
+
+    // Spend of every archived session, for the cost tab and `:export csv`
+    if let Some(dir) = state.meta.archive_dir.clone() {
+        let tx = bg_tx.clone();
+        state.meta.in_flight.start(IoKind::Load);
+        std::thread::spawn(move || {
+            let _ = tx.send(AppEvent::CostRecordsLoaded(cost_history::load_records(&dir)));
+        });
+    }
+
+    // Opt-in release check; a failure is dropped (see `updates`)
+    if state.meta.check_updates {
+        let tx = bg_tx.clone();
+        state.meta.in_flight.start(IoKind::Load);
+        std::thread::spawn(move || {
+            let _ = tx.send(AppEvent::ReleaseChecked(updates::fetch_latest()));
+        });
+    }
+
+    let mut needs_draw = true;
+
+    // Set once quit is requested, while writes still run
+    let mut quit_requested: Option<Instant> = None;
+
+    // Draw times, to fall back to low-bandwidth rendering on a slow terminal
+    let mut draw_latency = DrawLatency::default();
+    let mut last_draw = Instant::now();
+
+    // Time spent waiting for input since the last draw; the rest of the gap is update work
+    let mut idle = Duration::ZERO;
+
     loop {
-        // Render current state
-        terminal.draw(|frame| {
-            render(state, frame);
-        })?;
+        // Render current state (only when something changed)
+        if needs_draw {
+            let started = Instant::now();
+            terminal.draw(|frame| {
+                render(state, frame);
+            })?;
+            let update_time = started.saturating_duration_since(last_draw).saturating_sub(idle);
+            last_draw = Instant::now();
+            idle = Duration::ZERO;
+            needs_draw = false;
+            if let Some(warning) = state.meta.frame_budget.record(update_time, last_draw - started, last_draw) {
+                toasts::raise(state, ToastLevel::Warn, warning);
+                needs_draw = true;
+            }
+            if draw_latency.record(last_draw - started) && !state.ui.low_bandwidth {
+                state.ui.low_bandwidth = true;
+                state.ui.command_message = Some(CommandMessage {
+                    text: "slow terminal: switched to low-bandwidth rendering".into(),
+                    is_error: false,
+                });
+                needs_draw = true;
+            }
+        }
 
-        // Poll keyboard events with timeout
+        // Poll keyboard events with timeout, waking at least once per fast tick
+        let tick_rate = state.effective_tick_rate(Instant::now());
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
-            .unwrap_or(Duration::ZERO);
+            .unwrap_or(Duration::ZERO)
+            .min(state.meta.tick_rate);
 
-        if event::poll(timeout)? {
+        let polling = Instant::now();
+        let ready = event::poll(timeout)?;
+        idle += polling.elapsed();
+        if ready {
+            // Any terminal event (including resize) warrants a redraw
+            needs_draw = true;
             if let Event::Key(key) = event::read()? {
                 // Send key event to update
                 update(state, AppEvent::Key(key));
             }
         }
 
+        let activity_before = state.meta.last_activity;
+
         // Drain file watcher events
         while let Ok(event) = watcher_rx.try_recv() {
             update(state, event);
@@ -116,6 +383,100 @@ fn run_event_loop(
         while let Ok(event) = load_rx.try_recv() {
             update(state, event);
             load_in_flight = false;
+            state.meta.in_flight.finish(IoKind::Load);
+        }
+
+        // Drain background event pages
+        while let Ok(event) = page_rx.try_recv() {
+            update(state, event);
+            page_in_flight = false;
+            state.meta.in_flight.finish(IoKind::Load);
+        }
+
+        // Drain background meta pages
+        while let Ok(event) = meta_rx.try_recv() {
+            // Read errors arrive ahead of their page
+            if matches!(event, AppEvent::SessionMetaPageLoaded { .. }) {
+                meta_in_flight = false;
+                state.meta.in_flight.finish(IoKind::Load);
+            }
+            update(state, event);
+        }
+
+        // Drain background preview results
+        while let Ok(event) = preview_rx.try_recv() {
+            update(state, event);
+            preview_in_flight = false;
+            state.meta.in_flight.finish(IoKind::Load);
+        }
+
+        // Drain git diff results
+        while let Ok(event) = diff_rx.try_recv() {
+            update(state, event);
+            diff_in_flight = false;
+            state.meta.in_flight.finish(IoKind::Load);
+        }
+
+        // Drain background work results
+        while let Ok(event) = bg_rx.try_recv() {
+            if matches!(
+                event,
+                AppEvent::AgentBaselinesLoaded(_)
+                    | AppEvent::CostRecordsLoaded(_)
+                    | AppEvent::ToolOutputChunkLoaded { .. }
+                    | AppEvent::ReleaseChecked(_)
+            ) {
+                state.meta.in_flight.finish(IoKind::Load);
+            }
+            update(state, event);
+        }
+
+        // Run git for a diff popup waiting on it
+        if !diff_in_flight {
+            if let DiffPopupState::Loading(ref request) = state.ui.diff_popup {
+                let tx = diff_tx.clone();
+                let request = request.clone();
+                diff_in_flight = true;
+                state.meta.in_flight.start(IoKind::Load);
+                std::thread::spawn(move || {
+                    let result = git::diff_since(&request.repo, request.since, &request.paths);
+                    let _ = tx.send(AppEvent::GitDiffLoaded { task_id: request.task_id, result });
+                });
+            }
+        }
+
+        // Stream the next chunk of a captured tool output into its pager
+        if let Some((path, offset)) = state.ui.output_pager.as_mut().and_then(OutputPager::request_chunk) {
+            let tx = bg_tx.clone();
+            state.meta.in_flight.start(IoKind::Load);
+            std::thread::spawn(move || {
+                let result = tool_outputs::read_chunk(&path, offset).map_err(|e| e.to_string());
+                let _ = tx.send(AppEvent::ToolOutputChunkLoaded { path, offset, result });
+            });
+        }
+
+        // Spawn background preview load for the highlighted archived session
+        if !preview_in_flight {
+            if let Some((sid, path)) = state.pending_session_preview() {
+                let tx = preview_tx.clone();
+                preview_in_flight = true;
+                state.meta.in_flight.start(IoKind::Load);
+                state.ui.preview_loading = Some(sid.clone());
+                std::thread::spawn(move || {
+                    match session::load_session_preview(&path) {
+                        Ok(preview) => {
+                            let _ = tx.send(AppEvent::SessionPreviewLoaded { session_id: sid, preview });
+                        }
+                        Err(e) => {
+                            let _ = tx.send(AppEvent::SessionLoadFailed {
+                                session_id: sid,
+                                load: SessionLoad::Preview,
+                                error: e.into(),
+                            });
+                        }
+                    }
+                });
+            }
         }
 
         // Spawn background session load if requested and not already in flight
@@ -123,16 +484,19 @@ fn run_event_loop(
             if !load_in_flight {
                 if let Some(session) = state.domain.sessions.iter().find(|s| &s.meta.id == sid) {
                     let path = session.path.clone();
+                    let sid = sid.clone();
                     let tx = load_tx.clone();
                     load_in_flight = true;
+                    state.meta.in_flight.start(IoKind::Load);
                     std::thread::spawn(move || {
-                        match session::load_session(&path) {
-                            Ok(archive) => {
-                                let _ = tx.send(AppEvent::SessionLoaded(archive));
+                        match session::load_session_head(&path) {
+                            Ok((archive, paging, stamp)) => {
+                                let _ = tx.send(AppEvent::SessionHeadLoaded { archive, paging, stamp });
                             }
                             Err(e) => {
-                                let _ = tx.send(AppEvent::Error {
-                                    source: path.display().to_string(),
+                                let _ = tx.send(AppEvent::SessionLoadFailed {
+                                    session_id: sid,
+                                    load: SessionLoad::Head,
                                     error: e.into(),
                                 });
                             }
@@ -142,21 +506,257 @@ fn run_event_loop(
             }
         }
 
+        // Spawn background load of the next (older) page of archive events
+        if let Some(ref sid) = state.ui.loading_events {
+            if !page_in_flight {
+                let next = state
+                    .domain
+                    .sessions
+                    .iter()
+                    .find(|s| &s.meta.id == sid)
+                    .and_then(|s| {
+                        let paging = s.paging.clone()?;
+                        let range = paging.next_page(session::EVENT_PAGE_SIZE);
+                        Some((s.path.clone(), paging, range))
+                    });
+                match next {
+                    Some((path, paging, range)) => {
+                        let tx = page_tx.clone();
+                        let sid = sid.clone();
+                        page_in_flight = true;
+                        state.meta.in_flight.start(IoKind::Load);
+                        std::thread::spawn(move || {
+                            let start = range.start;
+                            match session::load_event_page(&path, &paging, range) {
+                                Ok(events) => {
+                                    let _ = tx.send(AppEvent::SessionEventsLoaded { session_id: sid, start, events });
+                                }
+                                Err(e) => {
+                                    let _ = tx.send(AppEvent::SessionLoadFailed {
+                                        session_id: sid,
+                                        load: SessionLoad::Events,
+                                        error: e.into(),
+                                    });
+                                }
+                            }
+                        });
+                    }
+                    // Session deleted or already fully loaded
+                    None => state.ui.loading_events = None,
+                }
+            }
+        }
+
+        // Spawn background read of the next page of archive metas
+        if state.ui.loading_metas && !meta_in_flight {
+            let paths = state.domain.archive_pages.next_page(session::META_PAGE_SIZE);
+            if paths.is_empty() {
+                state.ui.loading_metas = false;
+            } else {
+                let tx = meta_tx.clone();
+                meta_in_flight = true;
+                state.meta.in_flight.start(IoKind::Load);
+                std::thread::spawn(move || {
+                    let (metas, errors) = session::load_session_metas(&paths);
+                    for error in errors {
+                        let _ = tx.send(AppEvent::Error { source: "sessions".to_string(), error: error.into() });
+                    }
+                    let _ = tx.send(AppEvent::SessionMetaPageLoaded { paths, metas });
+                });
+            }
+        }
+
+        // Something arrived from the watcher or a background load: redraw at the fast rate
+        if state.meta.last_activity != activity_before {
+            needs_draw = true;
+        }
+
         // Tick event
         if last_tick.elapsed() >= tick_rate {
-            update(state, AppEvent::Tick(Utc::now()));
+            update(state, AppEvent::Tick(state.meta.clock.now()));
             *last_tick = Instant::now();
+            // Persist the day's spend at most once per tick, one save at a time
+            if state.domain.daily_spend.dirty && state.meta.in_flight.count(IoKind::Save) == 0 {
+                state.domain.daily_spend.dirty = false;
+                if let Some(path) = state.meta.spend_path.clone() {
+                    let spend = state.domain.daily_spend.clone();
+                    let tx = bg_tx.clone();
+                    state.meta.in_flight.start(IoKind::Save);
+                    std::thread::spawn(move || {
+                        let error = cost::save_spend(&path, &spend).err().map(|e| format!("auto-save: {}", e));
+                        let _ = tx.send(AppEvent::WriteFinished { kind: IoKind::Save, error });
+                    });
+                }
+            }
+            // Low-bandwidth mode leaves quiet ticks (clocks, relative times) unpainted for a while
+            needs_draw |= bandwidth::tick_needs_redraw(state.ui.low_bandwidth, last_draw.elapsed());
+        }
+
+        // Cost budget alerts go out as desktop notifications / webhook posts
+        let alerts = std::mem::take(&mut state.domain.cost_alerts.pending);
+        if !alerts.is_empty() {
+            let budget = state.meta.cost_budget.clone();
+            let tx = bg_tx.clone();
+            state.meta.in_flight.start(IoKind::Deliver);
+            std::thread::spawn(move || {
+                for alert in &alerts {
+                    let results = [
+                        budget.desktop.then(|| notify::desktop(alert)),
+                        budget.webhook.as_deref().map(|url| notify::webhook(url, alert)),
+                    ];
+                    for error in results.into_iter().flatten().filter_map(|r| r.err()) {
+                        let _ = tx.send(AppEvent::Info {
+                            source: "notify".into(),
+                            message: format!("cost alert not delivered: {}", error),
+                        });
+                    }
+                }
+                let _ = tx.send(AppEvent::WriteFinished { kind: IoKind::Deliver, error: None });
+            });
         }
 
-        // Check quit condition
+        // Sampled sessions still get every event, in the overflow log; one append
+        // at a time so the log keeps arrival order
+        if state.meta.in_flight.count(IoKind::Append) == 0 {
+            let overflow = state.domain.sampler.take_overflow();
+            if let (false, Some(dir)) = (overflow.is_empty(), state.meta.archive_dir.clone()) {
+                let tx = bg_tx.clone();
+                state.meta.in_flight.start(IoKind::Append);
+                std::thread::spawn(move || {
+                    let error = session::append_overflow(&dir.join("overflow"), &overflow)
+                        .err()
+                        .map(|e| format!("overflow: {}", e));
+                    let _ = tx.send(AppEvent::WriteFinished { kind: IoKind::Append, error });
+                });
+            }
+        }
+
+        // `y` / `Y` in the JSON inspector: copy through the terminal
+        if let Some(text) = state.meta.clipboard.take() {
+            let mut stdout = std::io::stdout();
+            if let Err(e) = stdout.write_all(clipboard::osc52(&text).as_bytes()).and_then(|()| stdout.flush()) {
+                toasts::raise(state, ToastLevel::Error, format!("copy failed: {}", e));
+                needs_draw = true;
+            }
+        }
+
+        // Ctrl+Z or `:sh`: hand the terminal over, then pick up where we left off
+        if let Some(request) = state.meta.suspend.take() {
+            state.ui.command_message = Some(suspend_terminal(terminal, state, request)?);
+            *last_tick = Instant::now();
+            needs_draw = true;
+        }
+
+        // Watchdog found a dead or stalled watcher thread on Tick
+        if state.meta.watchdog.restart_requested {
+            watcher.restart();
+            state.meta.watchdog.restart_requested = false;
+        }
+
+        // SIGTERM / SIGINT quit like `q` would, without asking
+        if crash::terminate_requested() {
+            state.meta.should_quit = true;
+        }
+
+        // Check quit condition: writes under way get a grace period to finish
         if state.meta.should_quit {
-            break;
+            let waited = quit_requested.get_or_insert_with(Instant::now).elapsed();
+            if state.meta.in_flight.may_quit(waited) {
+                break;
+            }
+            needs_draw = true;
         }
     }
 
     Ok(())
 }
 
+/// Restore the terminal, run `request`, then re-enter the TUI with a full redraw.
+/// State and watcher threads are untouched; watcher events queue up meanwhile.
+///
+/// # Returns
+/// Toast describing how the hand-off ended.
+fn suspend_terminal(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    state: &AppState,
+    request: Suspend,
+) -> Result<CommandMessage> {
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    let outcome = match request {
+        Suspend::Stop => stop_process_group(),
+        Suspend::Shell => run_shell(&state.meta.project_path),
+        Suspend::Editor(ref file) => run_editor(file),
+    };
+
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+    terminal.clear()?;
+
+    // Every thread was stopped too: their heartbeats are stale, not stalled
+    if matches!(request, Suspend::Stop) {
+        if let Some(ref health) = state.meta.watchdog.health {
+            health.resume();
+        }
+    }
+
+    Ok(match outcome {
+        Ok(text) => CommandMessage { text, is_error: false },
+        Err(text) => CommandMessage { text, is_error: true },
+    })
+}
+
+/// Send SIGTSTP to our process group, as the terminal would for Ctrl+Z outside
+/// raw mode. Returns once the job is continued (`fg`).
+#[cfg(unix)]
+fn stop_process_group() -> std::result::Result<String, String> {
+    // SAFETY: kill(2) with pid 0 only signals our own process group.
+    if unsafe { libc::kill(0, libc::SIGTSTP) } == 0 {
+        Ok("resumed".to_string())
+    } else {
+        Err(format!("suspend failed: {}", std::io::Error::last_os_error()))
+    }
+}
+
+#[cfg(not(unix))]
+fn stop_process_group() -> std::result::Result<String, String> {
+    Err("suspend is not supported on this platform; use :sh".to_string())
+}
+
+/// Open `file` in `$VISUAL` / `$EDITOR` and wait for the editor to exit.
+fn run_editor(file: &FileRef) -> std::result::Result<String, String> {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|key| std::env::var(key).ok())
+        .find(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| editor::DEFAULT_EDITOR.to_string());
+    let argv = editor::editor_command(&editor, file).ok_or_else(|| format!("invalid editor command {editor:?}"))?;
+    std::process::Command::new(&argv[0])
+        .args(&argv[1..])
+        .status()
+        .map(|_| format!("closed {}", file.path.display()))
+        .map_err(|e| format!("could not start {}: {e}", argv[0]))
+}
+
+/// Run the user's shell in `project_path` and wait for it to exit.
+fn run_shell(project_path: &str) -> std::result::Result<String, String> {
+    let shell = std::env::var("SHELL")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "cmd".to_string() } else { "/bin/sh".to_string() });
+    println!("loom-tui is still watching. Exit the shell to return.");
+    std::process::Command::new(&shell)
+        .current_dir(project_path)
+        .status()
+        .map(|status| match status.code() {
+            Some(0) | None => "back from shell".to_string(),
+            Some(code) => format!("back from shell (exit {code})"),
+        })
+        .map_err(|e| format!("could not start {shell}: {e}"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,6 +4,7 @@ use std::time::Duration;
 
 use super::ids::{AgentId, SessionId, TaskId, ToolName};
 use super::serde_utils::duration_opt_millis;
+use super::{Clock, MonoStamp};
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct TokenUsage {
@@ -45,6 +46,38 @@ impl TokenUsage {
     }
 }
 
+/// Agent types Claude Code ships with; every other type is a custom agent.
+pub const BUILTIN_AGENT_TYPES: [&str; 5] = ["general-purpose", "Explore", "Plan", "statusline-setup", "output-style-setup"];
+
+/// An agent type split into its parts: `reviewer`, `my-plugin:reviewer` (a
+/// plugin agent) or `backend/reviewer` (a team's agent). The namespace is
+/// everything before the last `:` or `/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgentKind<'a> {
+    pub namespace: Option<&'a str>,
+    pub name: &'a str,
+}
+
+impl<'a> AgentKind<'a> {
+    /// Pure function: split a raw agent type. Surrounding whitespace and an
+    /// empty namespace are ignored.
+    pub fn parse(raw: &'a str) -> Self {
+        let raw = raw.trim();
+        match raw.rfind([':', '/']) {
+            Some(at) if at + 1 < raw.len() => {
+                let namespace = raw[..at].trim_end_matches([':', '/']);
+                Self { namespace: Some(namespace).filter(|ns| !ns.is_empty()), name: &raw[at + 1..] }
+            }
+            _ => Self { namespace: None, name: raw.trim_end_matches([':', '/']) },
+        }
+    }
+
+    /// One of Claude Code's own agent types.
+    pub fn is_builtin(&self) -> bool {
+        self.namespace.is_none() && BUILTIN_AGENT_TYPES.contains(&self.name)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Agent {
     pub id: AgentId,
@@ -69,6 +102,12 @@ pub struct Agent {
     pub skills: Vec<String>,
     #[serde(default)]
     pub token_usage: TokenUsage,
+    /// Context window size over time, oldest first (one sample per change, capped)
+    #[serde(default)]
+    pub context_samples: Vec<u64>,
+    /// Monotonic baseline for `started_at` (live agents only)
+    #[serde(skip)]
+    pub started_mono: Option<MonoStamp>,
 }
 
 impl Default for Agent {
@@ -85,11 +124,16 @@ impl Default for Agent {
             session_id: None,
             skills: Vec::new(),
             token_usage: TokenUsage::default(),
+            context_samples: Vec::new(),
+            started_mono: None,
         }
     }
 }
 
 impl Agent {
+    /// Most context samples kept per agent; older ones are dropped first.
+    pub const MAX_CONTEXT_SAMPLES: usize = 120;
+
     pub fn new(id: impl Into<AgentId>, started_at: DateTime<Utc>) -> Self {
         Self {
             id: id.into(),
@@ -103,9 +147,17 @@ impl Agent {
             session_id: None,
             skills: Vec::new(),
             token_usage: TokenUsage::default(),
+            context_samples: Vec::new(),
+            started_mono: None,
         }
     }
 
+    /// Pin `started_at` to the monotonic clock so live elapsed time survives wall clock jumps.
+    pub fn with_monotonic_baseline(mut self, clock: &dyn Clock) -> Self {
+        self.started_mono = Some(MonoStamp::observe(self.started_at, clock));
+        self
+    }
+
     pub fn with_task(mut self, task_id: impl Into<TaskId>) -> Self {
         self.task_id = Some(task_id.into());
         self
@@ -131,9 +183,37 @@ impl Agent {
         self
     }
 
-    /// Display name: agent_type if available, otherwise short ID
+    /// Run time: up to `finished_at` once finished, otherwise monotonic when
+    /// baselined, else against wall time.
+    pub fn elapsed(&self, clock: &dyn Clock) -> chrono::Duration {
+        match (self.finished_at, self.started_mono) {
+            (Some(end), _) => end - self.started_at,
+            (None, Some(stamp)) => stamp.elapsed(clock),
+            (None, None) => clock.now() - self.started_at,
+        }
+    }
+
+    /// The agent's type split into namespace and name.
+    pub fn kind(&self) -> Option<AgentKind<'_>> {
+        self.agent_type.as_deref().map(AgentKind::parse).filter(|kind| !kind.name.is_empty())
+    }
+
+    /// Display name: the agent type's name (without its namespace) if
+    /// available, otherwise short ID
     pub fn display_name(&self) -> &str {
-        self.agent_type.as_deref().unwrap_or(self.id.as_str())
+        self.kind().map_or(self.id.as_str(), |kind| kind.name)
+    }
+
+    /// Record the current context window size. Repeats of the last sample and
+    /// empty readings are skipped, so samples mark growth steps and compaction drops.
+    pub fn record_context_sample(&mut self, tokens: u64) {
+        if tokens == 0 || self.context_samples.last() == Some(&tokens) {
+            return;
+        }
+        if self.context_samples.len() >= Self::MAX_CONTEXT_SAMPLES {
+            self.context_samples.remove(0);
+        }
+        self.context_samples.push(tokens);
     }
 }
 
@@ -225,6 +305,41 @@ mod tests {
         assert_eq!(agent.finished_at, Some(later));
     }
 
+    #[test]
+    fn context_samples_skip_repeats_and_cap_length() {
+        let mut agent = Agent::new("a01", Utc::now());
+        for tokens in [0, 100, 100, 250, 40] {
+            agent.record_context_sample(tokens);
+        }
+        assert_eq!(agent.context_samples, vec![100, 250, 40]);
+
+        for tokens in 1..=(Agent::MAX_CONTEXT_SAMPLES as u64 + 10) {
+            agent.record_context_sample(tokens);
+        }
+        assert_eq!(agent.context_samples.len(), Agent::MAX_CONTEXT_SAMPLES);
+        assert_eq!(agent.context_samples.last(), Some(&(Agent::MAX_CONTEXT_SAMPLES as u64 + 10)));
+    }
+
+    #[test]
+    fn agent_kind_splits_plugin_and_team_namespaces() {
+        assert_eq!(AgentKind::parse("Explore"), AgentKind { namespace: None, name: "Explore" });
+        assert!(AgentKind::parse("Explore").is_builtin());
+        assert_eq!(
+            AgentKind::parse("loom-plugin:code-reviewer"),
+            AgentKind { namespace: Some("loom-plugin"), name: "code-reviewer" }
+        );
+        assert_eq!(AgentKind::parse(" backend/api::impl "), AgentKind { namespace: Some("backend/api"), name: "impl" });
+        assert_eq!(AgentKind::parse(":reviewer"), AgentKind { namespace: None, name: "reviewer" });
+        assert!(!AgentKind::parse("team:Explore").is_builtin());
+
+        let mut agent = Agent::new("a01", Utc::now());
+        assert_eq!(agent.display_name(), "a01");
+        agent.agent_type = Some("loom-plugin:code-reviewer".into());
+        assert_eq!(agent.display_name(), "code-reviewer");
+        agent.agent_type = Some(" ".into());
+        assert_eq!((agent.kind(), agent.display_name()), (None, "a01"));
+    }
+
     #[test]
     fn tool_call_serializes_duration_as_millis() {
         let call = ToolCall::new("Read", "file.rs".to_string())
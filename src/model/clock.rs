@@ -0,0 +1,145 @@
+//! Where the time comes from, and monotonic elapsed-time tracking.
+
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Instant;
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Current wall-clock and monotonic time.
+///
+/// Time-dependent logic (stale-session expiry, elapsed displays, auto-save)
+/// reads the time from here rather than `Utc::now()` / `Instant::now()`, so
+/// tests can use a `ManualClock`, which only moves when told to.
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+    fn instant(&self) -> Instant;
+}
+
+/// The system's clocks.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Clock that stands still until advanced. Clones share one time, so a test
+/// can keep a handle to the clock it put into the state.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    time: Arc<Mutex<(DateTime<Utc>, Instant)>>,
+}
+
+impl ManualClock {
+    /// Clock reading `start`; its monotonic time starts at the real current instant.
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self { time: Arc::new(Mutex::new((start, Instant::now()))) }
+    }
+
+    /// Let `by` pass on both clocks.
+    pub fn advance(&self, by: Duration) {
+        let mut time = self.time.lock().unwrap_or_else(PoisonError::into_inner);
+        time.0 += by;
+        time.1 += by.to_std().unwrap_or_default();
+    }
+
+    /// Step the wall clock alone by `by` (an NTP correction, or a suspend the
+    /// monotonic clock did not see).
+    pub fn step_wall(&self, by: Duration) {
+        self.time.lock().unwrap_or_else(PoisonError::into_inner).0 += by;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.time.lock().unwrap_or_else(PoisonError::into_inner).0
+    }
+
+    fn instant(&self) -> Instant {
+        self.time.lock().unwrap_or_else(PoisonError::into_inner).1
+    }
+}
+
+/// Monotonic baseline for a wall-clock time. Runtime-only; never serialized.
+///
+/// `Utc::now() - started_at` jumps whenever the system clock does (NTP steps,
+/// suspend/resume). The gap before observation is measured once on the wall
+/// clock, everything after it on the monotonic clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonoStamp {
+    observed_at: Instant,
+    /// How long before observation the stamped time was (never negative)
+    lag: Duration,
+}
+
+impl MonoStamp {
+    /// Stamp `at` as observed now on `clock`.
+    pub fn observe(at: DateTime<Utc>, clock: &dyn Clock) -> Self {
+        Self::observe_at(at, clock.now(), clock.instant())
+    }
+
+    /// Pure function: stamp `at` as observed at wall time `wall_now`, monotonic time `mono_now`.
+    pub fn observe_at(at: DateTime<Utc>, wall_now: DateTime<Utc>, mono_now: Instant) -> Self {
+        Self { observed_at: mono_now, lag: (wall_now - at).max(Duration::zero()) }
+    }
+
+    /// Time since the stamped wall-clock time, now on `clock` (the clock it was observed on).
+    pub fn elapsed(&self, clock: &dyn Clock) -> Duration {
+        self.elapsed_at(clock.instant())
+    }
+
+    /// Pure function: time since the stamped wall-clock time, as of `mono_now`.
+    pub fn elapsed_at(&self, mono_now: Instant) -> Duration {
+        let since = mono_now.saturating_duration_since(self.observed_at);
+        self.lag + Duration::from_std(since).unwrap_or(Duration::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elapsed_follows_the_monotonic_clock_after_observation() {
+        let wall = Utc::now();
+        let mono = Instant::now();
+        let stamp = MonoStamp::observe_at(wall - Duration::seconds(30), wall, mono);
+
+        let later = mono + std::time::Duration::from_secs(90);
+        assert_eq!(stamp.elapsed_at(later), Duration::seconds(120));
+        // An Instant before observation (can't happen live) does not go negative
+        assert_eq!(stamp.elapsed_at(mono), Duration::seconds(30));
+    }
+
+    #[test]
+    fn a_manual_clock_moves_only_when_told() {
+        let start = Utc::now();
+        let clock = ManualClock::new(start);
+        let stamp = MonoStamp::observe(start - Duration::seconds(10), &clock);
+        assert_eq!(clock.now(), start);
+        assert_eq!(stamp.elapsed(&clock), Duration::seconds(10));
+
+        clock.clone().advance(Duration::minutes(2));
+        assert_eq!(clock.now(), start + Duration::minutes(2));
+        assert_eq!(stamp.elapsed(&clock), Duration::seconds(130));
+
+        // A wall clock step leaves monotonic elapsed time alone
+        clock.step_wall(Duration::hours(-1));
+        assert_eq!(stamp.elapsed(&clock), Duration::seconds(130));
+    }
+
+    #[test]
+    fn future_wall_times_clamp_to_zero_lag() {
+        let wall = Utc::now();
+        let mono = Instant::now();
+        let stamp = MonoStamp::observe_at(wall + Duration::seconds(5), wall, mono);
+        assert_eq!(stamp.elapsed_at(mono), Duration::zero());
+    }
+}
@@ -1,17 +1,44 @@
-use serde::{Deserialize, Serialize};
+//! Identifier newtypes.
+//!
+//! Agent ids, session ids, task ids and tool names come with every event and
+//! are cloned into maps, events and views all the time. Each is interned: one
+//! shared `Arc<str>` per distinct value, kept in a registry per type, so a
+//! clone is a reference count bump and equal ids usually compare by pointer.
+//! Registries only grow; the set of distinct ids in a run is small.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashSet;
 use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+/// The registry's shared copy of `value`, added on first sight.
+fn intern(registry: &'static OnceLock<Mutex<HashSet<Arc<str>>>>, value: &str) -> Arc<str> {
+    let mut interned = registry.get_or_init(Default::default).lock().unwrap_or_else(PoisonError::into_inner);
+    match interned.get(value) {
+        Some(shared) => Arc::clone(shared),
+        None => {
+            let shared: Arc<str> = Arc::from(value);
+            interned.insert(Arc::clone(&shared));
+            shared
+        }
+    }
+}
 
 macro_rules! id_newtype {
     ($name:ident) => {
-        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
-        #[serde(transparent)]
-        pub struct $name(String);
+        #[derive(Clone)]
+        pub struct $name(Arc<str>);
 
         impl $name {
             pub fn new(s: impl Into<String>) -> Self {
                 let id = s.into();
                 assert!(!id.is_empty(), "{} cannot be empty", stringify!($name));
-                Self(id)
+                Self::interned(&id)
+            }
+
+            fn interned(id: &str) -> Self {
+                static REGISTRY: OnceLock<Mutex<HashSet<Arc<str>>>> = OnceLock::new();
+                Self(intern(&REGISTRY, id))
             }
 
             pub fn as_str(&self) -> &str {
@@ -19,6 +46,38 @@ macro_rules! id_newtype {
             }
         }
 
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                Arc::ptr_eq(&self.0, &other.0) || self.0 == other.0
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.as_str().cmp(other.as_str())
+            }
+        }
+
+        impl std::hash::Hash for $name {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.as_str().hash(state)
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&self.as_str()).finish()
+            }
+        }
+
         impl fmt::Display for $name {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 write!(f, "{}", self.0)
@@ -42,6 +101,19 @@ macro_rules! id_newtype {
                 Self::new(s)
             }
         }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let id = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+                Ok(Self::interned(&id))
+            }
+        }
     };
 }
 
@@ -95,4 +167,17 @@ mod tests {
         let id: TaskId = String::from("T2").into();
         assert_eq!(id.as_str(), "T2");
     }
+
+    #[test]
+    fn equal_ids_share_one_interned_string() {
+        let parsed: AgentId = serde_json::from_str(r#""agent-interned""#).unwrap();
+        let built = AgentId::new(String::from("agent-interned"));
+        assert!(Arc::ptr_eq(&parsed.0, &built.0));
+        assert_eq!(parsed, built);
+        assert_eq!(serde_json::to_string(&built).unwrap(), r#""agent-interned""#);
+        assert_eq!(format!("{:?}", built), r#"AgentId("agent-interned")"#);
+
+        // One registry per type
+        assert!(!Arc::ptr_eq(&built.0, &SessionId::new("agent-interned").0));
+    }
 }
@@ -1,4 +1,5 @@
 pub mod agent;
+pub mod clock;
 pub mod ids;
 pub mod serde_utils;
 pub mod session;
@@ -6,9 +7,12 @@ pub mod task;
 pub mod theme;
 pub mod transcript_event;
 
-pub use agent::{Agent, AgentMessage, MessageKind, TokenUsage, ToolCall};
+pub use agent::{Agent, AgentKind, AgentMessage, MessageKind, TokenUsage, ToolCall};
+pub use clock::{Clock, ManualClock, MonoStamp, SystemClock};
 pub use ids::{AgentId, SessionId, TaskId, ToolName};
-pub use session::{ArchivedSession, SessionArchive, SessionMeta, SessionStatus};
-pub use task::{ReviewStatus, Task, TaskGraph, TaskStatus, Wave};
-pub use theme::Theme;
-pub use transcript_event::{TranscriptEvent, TranscriptEventKind};
+pub use session::{
+    ArchiveStamp, ArchivedSession, EventPaging, SessionArchive, SessionMeta, SessionOutcome, SessionPreview, SessionStatus,
+};
+pub use task::{Phase, ReviewStatus, Task, TaskGraph, TaskStatus, Wave, WaveCompletion};
+pub use theme::{ColorMode, Theme};
+pub use transcript_event::{ApiErrorKind, EventOrigin, SourceRef, TranscriptEvent, TranscriptEventKind};
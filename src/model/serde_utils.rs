@@ -1,4 +1,4 @@
-/// Shared serde utilities for domain models
+//! Shared serde utilities for domain models
 
 /// Deserialize a Vec<T> from JSON with element-wise fallback.
 ///
@@ -1,11 +1,13 @@
 use super::agent::TokenUsage;
-use super::ids::{AgentId, SessionId, TaskId};
+use super::ids::{AgentId, SessionId, TaskId, ToolName};
 use super::serde_utils::{deserialize_vec_or_empty, duration_opt_millis};
-use super::{Agent, TaskGraph, TranscriptEvent};
+use super::{Agent, Clock, MonoStamp, TaskGraph, TranscriptEvent};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
-use std::time::Duration;
+use std::collections::{BTreeMap, VecDeque};
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionMeta {
@@ -24,17 +26,36 @@ pub struct SessionMeta {
     pub project_path: String,
     #[serde(default)]
     pub git_branch: Option<String>,
+    /// Orchestration run the session belongs to (see `app::runs`)
     #[serde(default)]
     pub loom_plan_id: Option<String>,
     #[serde(default)]
     pub wave_count: Option<u32>,
     #[serde(default)]
     pub failed_tasks: Vec<TaskId>,
+    /// How the session went, classified when it ended (see `app::outcome`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<SessionOutcome>,
     #[serde(default)]
     pub transcript_path: Option<String>,
+    /// Initial user prompt of the main session (what it was asked to do)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goal: Option<String>,
+    /// Expected duration configured when the session was archived
+    #[serde(default, with = "duration_opt_millis", skip_serializing_if = "Option::is_none")]
+    pub budget: Option<Duration>,
+    /// How far `duration` ran past `budget`
+    #[serde(default, with = "duration_opt_millis", skip_serializing_if = "Option::is_none")]
+    pub over_budget: Option<Duration>,
     /// Last time an event was received for this session (for stale session cleanup)
     #[serde(skip)]
     pub last_event_at: Option<DateTime<Utc>>,
+    /// Monotonic baseline for `timestamp` (live sessions only)
+    #[serde(skip)]
+    pub started_mono: Option<MonoStamp>,
+    /// Monotonic baseline for `last_event_at` (live sessions only)
+    #[serde(skip)]
+    pub last_event_mono: Option<MonoStamp>,
     /// Whether a real user prompt was received (filters out subagent phantom sessions)
     #[serde(skip)]
     pub confirmed: bool,
@@ -60,8 +81,12 @@ impl PartialEq for SessionMeta {
             && self.loom_plan_id == other.loom_plan_id
             && self.wave_count == other.wave_count
             && self.failed_tasks == other.failed_tasks
+            && self.outcome == other.outcome
             && self.transcript_path == other.transcript_path
-        // last_event_at, confirmed intentionally excluded (runtime-only, not serialized)
+            && self.goal == other.goal
+            && self.budget == other.budget
+            && self.over_budget == other.over_budget
+        // last_event_at, monotonic baselines, confirmed intentionally excluded (runtime-only, not serialized)
     }
 }
 
@@ -80,8 +105,14 @@ impl SessionMeta {
             loom_plan_id: None,
             wave_count: None,
             failed_tasks: Vec::new(),
+            outcome: None,
             transcript_path: None,
+            goal: None,
+            budget: None,
+            over_budget: None,
             last_event_at: Some(timestamp),
+            started_mono: None,
+            last_event_mono: None,
             confirmed: false,
             model: None,
             token_usage: TokenUsage::default(),
@@ -107,6 +138,72 @@ impl SessionMeta {
         self.loom_plan_id = Some(plan_id);
         self
     }
+
+    pub fn with_goal(mut self, goal: String) -> Self {
+        self.goal = Some(goal);
+        self
+    }
+
+    pub fn with_outcome(mut self, outcome: SessionOutcome) -> Self {
+        self.outcome = Some(outcome);
+        self
+    }
+
+    /// Record the configured `budget` and any overtime against `duration` (call once `duration` is final).
+    pub fn record_budget(&mut self, budget: Option<chrono::Duration>) {
+        self.budget = budget.and_then(|b| b.to_std().ok());
+        self.over_budget = match (self.budget, self.duration) {
+            (Some(budget), Some(duration)) => duration.checked_sub(budget).filter(|d| !d.is_zero()),
+            _ => None,
+        };
+    }
+
+    /// Pin `timestamp` and `last_event_at` to the monotonic clock so live
+    /// elapsed and idle times survive wall clock jumps.
+    pub fn with_monotonic_baseline(mut self, clock: &dyn Clock) -> Self {
+        self.started_mono = Some(MonoStamp::observe(self.timestamp, clock));
+        self.last_event_mono = self.last_event_at.map(|at| MonoStamp::observe(at, clock));
+        self
+    }
+
+    /// Record that an event stamped `at` arrived for this session, now on `clock`.
+    pub fn touch(&mut self, at: DateTime<Utc>, clock: &dyn Clock) {
+        self.last_event_at = Some(at);
+        self.last_event_mono = Some(MonoStamp::observe(at, clock));
+    }
+
+    /// Time since the session started: monotonic when baselined, else against wall time.
+    pub fn elapsed(&self, clock: &dyn Clock) -> chrono::Duration {
+        match self.started_mono {
+            Some(stamp) => stamp.elapsed(clock),
+            None => clock.now() - self.timestamp,
+        }
+    }
+
+    /// Pure function: time since the last event as of wall time `now` / monotonic time `mono_now`
+    /// (for stale session cleanup). Falls back to the start time when no event was seen.
+    pub fn idle_at(&self, now: DateTime<Utc>, mono_now: Instant) -> chrono::Duration {
+        match self.last_event_mono {
+            Some(stamp) => stamp.elapsed_at(mono_now),
+            None => now - self.last_event_at.unwrap_or(self.timestamp),
+        }
+    }
+
+    /// Single-line goal for compact display: whitespace collapsed,
+    /// truncated to `max_chars` with a trailing ellipsis.
+    pub fn goal_summary(&self, max_chars: usize) -> Option<String> {
+        let goal = self.goal.as_deref()?;
+        let collapsed = goal.split_whitespace().collect::<Vec<_>>().join(" ");
+        if collapsed.is_empty() {
+            return None;
+        }
+        if collapsed.chars().count() <= max_chars {
+            Some(collapsed)
+        } else {
+            let kept: String = collapsed.chars().take(max_chars.saturating_sub(1)).collect();
+            Some(format!("{}…", kept.trim_end()))
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -118,6 +215,37 @@ pub enum SessionStatus {
     Cancelled,
 }
 
+/// How an ended session went, judged from its tasks, agents and last events.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionOutcome {
+    /// Its work got done
+    Success,
+    /// Some of its work got done, some failed or was left
+    PartialSuccess,
+    /// Its work failed
+    Failed,
+    /// It stopped mid-turn, or before any of its work got done
+    Aborted,
+    /// Too little to go on
+    Unknown,
+}
+
+impl SessionOutcome {
+    pub const ALL: [Self; 5] = [Self::Success, Self::PartialSuccess, Self::Failed, Self::Aborted, Self::Unknown];
+
+    /// Short name for badges and filters.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::PartialSuccess => "partial",
+            Self::Failed => "failed",
+            Self::Aborted => "aborted",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct SessionArchive {
     pub meta: SessionMeta,
@@ -132,6 +260,12 @@ pub struct SessionArchive {
     pub events: Vec<TranscriptEvent>,
     #[serde(default)]
     pub agents: BTreeMap<AgentId, Agent>,
+    /// Free-text postmortem notes (SessionDetail Notes tab)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// loom-tui version that wrote the archive (None: written before versions were stamped)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub written_by: Option<String>,
 }
 
 /// Lightweight session index entry. Meta is always available; full archive loaded on demand.
@@ -141,6 +275,10 @@ pub struct ArchivedSession {
     pub path: std::path::PathBuf,
     /// None = not yet loaded from disk
     pub data: Option<SessionArchive>,
+    /// Some while `data` holds only the newest events and older pages are still on disk
+    pub paging: Option<EventPaging>,
+    /// Archive file as this instance last loaded or saved it (None: never seen on disk)
+    pub stamp: Option<ArchiveStamp>,
 }
 
 impl ArchivedSession {
@@ -149,6 +287,8 @@ impl ArchivedSession {
             meta,
             path,
             data: None,
+            paging: None,
+            stamp: None,
         }
     }
 
@@ -156,12 +296,120 @@ impl ArchivedSession {
         self.data = Some(archive);
         self
     }
+
+    /// Archive data, but only once every event page has been loaded.
+    pub fn complete_data(&self) -> Option<&SessionArchive> {
+        self.data.as_ref().filter(|_| self.paging.is_none())
+    }
+}
+
+/// On-disk identity of an archive file as last seen by this instance.
+/// A different stamp on disk means another writer touched the file since.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl ArchiveStamp {
+    /// Stamp from file metadata, or None where modification times are unsupported.
+    pub fn of(metadata: &std::fs::Metadata) -> Option<Self> {
+        Some(Self { modified: metadata.modified().ok()?, len: metadata.len() })
+    }
+}
+
+/// Progress of streaming an archive's events from disk, newest page first.
+/// Loaded events are always the tail `[total - loaded, total)` of the archive's event array.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventPaging {
+    /// Byte span of each event in the archive file, in array order
+    pub spans: Arc<[Range<usize>]>,
+    /// Archive size when the spans were taken; any other size means they are stale
+    pub file_len: u64,
+    pub loaded: usize,
+}
+
+impl EventPaging {
+    pub fn new(spans: Vec<Range<usize>>, file_len: u64) -> Self {
+        Self { spans: spans.into(), file_len, loaded: 0 }
+    }
+
+    /// Events in the archive.
+    pub fn total(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Events still on disk.
+    pub fn remaining(&self) -> usize {
+        self.total().saturating_sub(self.loaded)
+    }
+
+    /// Index range of the next (older) page of at most `page_size` events.
+    pub fn next_page(&self, page_size: usize) -> Range<usize> {
+        let end = self.remaining();
+        end.saturating_sub(page_size)..end
+    }
+}
+
+/// Compact session summary for the Sessions view preview pane.
+/// Cheap to render; built once per session and cached by the caller.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionPreview {
+    pub event_count: usize,
+    pub agent_count: usize,
+    /// Tool call counts, most used first (ties broken by name)
+    pub tool_counts: Vec<(ToolName, usize)>,
+    /// Most recent events, newest first (at most `RECENT_EVENTS`)
+    pub recent_events: Vec<TranscriptEvent>,
+}
+
+impl SessionPreview {
+    /// Number of trailing events kept for the preview.
+    pub const RECENT_EVENTS: usize = 5;
+
+    /// Build a preview from events in chronological order.
+    /// Single pass: counts ToolUse events per tool and keeps the last few events.
+    pub fn from_events<'a>(
+        events: impl IntoIterator<Item = &'a TranscriptEvent>,
+        agent_count: usize,
+    ) -> Self {
+        let mut counts: BTreeMap<&ToolName, usize> = BTreeMap::new();
+        let mut recent: VecDeque<&TranscriptEvent> = VecDeque::with_capacity(Self::RECENT_EVENTS);
+        let mut event_count = 0;
+
+        for event in events {
+            event_count += 1;
+            if let super::TranscriptEventKind::ToolUse { tool_name, .. } = &event.kind {
+                *counts.entry(tool_name).or_insert(0) += 1;
+            }
+            if recent.len() == Self::RECENT_EVENTS {
+                recent.pop_front();
+            }
+            recent.push_back(event);
+        }
+
+        let mut tool_counts: Vec<(ToolName, usize)> = counts
+            .into_iter()
+            .map(|(name, n)| (name.clone(), n))
+            .collect();
+        tool_counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        Self {
+            event_count,
+            agent_count,
+            tool_counts,
+            recent_events: recent.into_iter().rev().cloned().collect(),
+        }
+    }
 }
 
 impl SessionArchive {
     /// Current archive format version.
     pub const VERSION: u32 = 2;
 
+    /// Version of this build, stamped into the archives it writes.
+    pub const BUILD_VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
     pub fn new(meta: SessionMeta) -> Self {
         Self {
             meta,
@@ -169,6 +417,8 @@ impl SessionArchive {
             task_graph: None,
             events: Vec::new(),
             agents: BTreeMap::new(),
+            notes: None,
+            written_by: Some(Self::BUILD_VERSION.to_string()),
         }
     }
 
@@ -212,6 +462,41 @@ mod tests {
         assert_eq!(meta.git_branch, Some("main".into()));
     }
 
+    #[test]
+    fn goal_summary_collapses_whitespace_and_truncates() {
+        let meta = SessionMeta::new("s1", ts(), "/proj".to_string())
+            .with_goal("  Add   a login\npage with OAuth  ".into());
+
+        assert_eq!(meta.goal_summary(100).as_deref(), Some("Add a login page with OAuth"));
+        assert_eq!(meta.goal_summary(12).as_deref(), Some("Add a login…"));
+        assert_eq!(SessionMeta::new("s2", ts(), String::new()).goal_summary(10), None);
+    }
+
+    #[test]
+    fn session_meta_without_goal_deserializes() {
+        let meta = SessionMeta::new("s1", ts(), "/proj".to_string());
+        let json = serde_json::to_string(&meta).unwrap();
+        assert!(!json.contains("goal"));
+
+        let with_goal = meta.with_goal("ship it".into());
+        let json = serde_json::to_string(&with_goal).unwrap();
+        let restored: SessionMeta = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.goal.as_deref(), Some("ship it"));
+    }
+
+    #[test]
+    fn record_budget_stores_overtime_and_round_trips() {
+        let mut meta = SessionMeta::new("s1", ts(), "/proj".to_string()).with_duration(Duration::from_secs(57 * 60));
+        meta.record_budget(Some(chrono::Duration::minutes(45)));
+        assert_eq!(meta.over_budget, Some(Duration::from_secs(12 * 60)));
+
+        let restored: SessionMeta = serde_json::from_str(&serde_json::to_string(&meta).unwrap()).unwrap();
+        assert_eq!(restored, meta);
+
+        meta.record_budget(Some(chrono::Duration::hours(1)));
+        assert_eq!(meta.over_budget, None);
+    }
+
     #[test]
     fn session_status_serializes_lowercase() {
         let status = SessionStatus::Active;
@@ -392,6 +677,47 @@ mod tests {
         assert_eq!(archive.events[1].kind, TranscriptEventKind::UserMessage);
     }
 
+    #[test]
+    fn session_preview_counts_tools_and_keeps_recent_newest_first() {
+        let tool = |name: &str| {
+            TranscriptEvent::new(
+                ts(),
+                TranscriptEventKind::ToolUse {
+                    tool_name: name.into(),
+                    input_summary: String::new(),
+                },
+            )
+        };
+        let mut events = vec![TranscriptEvent::new(ts(), TranscriptEventKind::UserMessage)];
+        events.extend(["Read", "Bash", "Read", "Edit", "Bash", "Read"].map(tool));
+
+        let preview = SessionPreview::from_events(&events, 2);
+
+        assert_eq!(preview.event_count, 7);
+        assert_eq!(preview.agent_count, 2);
+        assert_eq!(
+            preview.tool_counts,
+            vec![("Read".into(), 3), ("Bash".into(), 2), ("Edit".into(), 1)]
+        );
+        assert_eq!(preview.recent_events.len(), SessionPreview::RECENT_EVENTS);
+        // Newest first: last event was a Read
+        assert_eq!(preview.recent_events[0], events[6]);
+        assert_eq!(preview.recent_events[4], events[2]);
+    }
+
+    #[test]
+    fn event_paging_walks_backwards_from_newest() {
+        let mut paging = EventPaging::new(vec![0..0; 1200], 0);
+        assert_eq!(paging.next_page(500), 700..1200);
+
+        paging.loaded = 1000;
+        assert_eq!(paging.remaining(), 200);
+        assert_eq!(paging.next_page(500), 0..200);
+
+        paging.loaded = 1200;
+        assert!(paging.next_page(500).is_empty());
+    }
+
     /// FR-027: No migration — old archives remain as-is (empty events, not transformed).
     #[test]
     fn no_migration_of_old_archives() {
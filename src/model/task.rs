@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use super::ids::{AgentId, TaskId};
@@ -7,6 +9,19 @@ pub struct TaskGraph {
     pub waves: Vec<Wave>,
     total_tasks: usize,
     completed_tasks: usize,
+    /// Orchestration stage from the task file's `current_phase`
+    #[serde(default, alias = "current_phase")]
+    pub phase: Option<Phase>,
+    /// Spec / plan files the task file names (`plan_file`, `spec_file`),
+    /// relative to the project root
+    #[serde(default)]
+    pub docs: Vec<String>,
+    /// Orchestration run id from the task file's `run_id` (or `plan_id`)
+    #[serde(default, alias = "plan_id")]
+    pub run_id: Option<String>,
+    /// Tasks each task waits on, from the task file's `depends_on` lists
+    #[serde(default)]
+    pub dependencies: BTreeMap<TaskId, Vec<TaskId>>,
 }
 
 impl TaskGraph {
@@ -22,6 +37,10 @@ impl TaskGraph {
             waves,
             total_tasks,
             completed_tasks,
+            phase: None,
+            docs: Vec::new(),
+            run_id: None,
+            dependencies: BTreeMap::new(),
         }
     }
 
@@ -30,9 +49,38 @@ impl TaskGraph {
             waves: Vec::new(),
             total_tasks: 0,
             completed_tasks: 0,
+            phase: None,
+            docs: Vec::new(),
+            run_id: None,
+            dependencies: BTreeMap::new(),
         }
     }
 
+    pub fn with_phase(mut self, phase: Option<Phase>) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    pub fn with_docs(mut self, docs: Vec<String>) -> Self {
+        self.docs = docs;
+        self
+    }
+
+    pub fn with_run_id(mut self, run_id: Option<String>) -> Self {
+        self.run_id = run_id;
+        self
+    }
+
+    pub fn with_dependencies(mut self, dependencies: BTreeMap<TaskId, Vec<TaskId>>) -> Self {
+        self.dependencies = dependencies;
+        self
+    }
+
+    /// True while some task is neither completed nor failed.
+    pub fn in_progress(&self) -> bool {
+        self.flat_tasks().any(|t| !matches!(t.status, TaskStatus::Completed | TaskStatus::Failed { .. }))
+    }
+
     pub fn total_tasks(&self) -> usize {
         self.total_tasks
     }
@@ -63,6 +111,89 @@ impl TaskGraph {
         // All waves complete, return last wave number
         self.waves.last().map(|w| w.number).unwrap_or(0)
     }
+
+    /// Detect a wave that finished between `previous` and this graph.
+    /// Returns the highest-numbered wave that is complete now but was not before,
+    /// together with the next wave still in progress (if any).
+    pub fn wave_completed_since(&self, previous: &TaskGraph) -> Option<WaveCompletion> {
+        let was_complete = |number: u32| {
+            previous
+                .waves
+                .iter()
+                .any(|w| w.number == number && w.is_complete())
+        };
+
+        let (index, wave) = self
+            .waves
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| w.is_complete() && !was_complete(w.number))
+            .max_by_key(|(_, w)| w.number)?;
+
+        let next = self.waves[index + 1..].iter().find(|w| !w.is_complete());
+
+        Some(WaveCompletion {
+            wave: wave.number,
+            task_count: wave.tasks.len(),
+            files_modified: wave.tasks.iter().map(|t| t.files_modified.len()).sum(),
+            tests_passed: wave
+                .tasks
+                .iter()
+                .filter(|t| t.tests_passed == Some(true))
+                .count(),
+            next_wave: next.map(|w| (w.number, w.tasks.len())),
+        })
+    }
+}
+
+/// Stage of a loom orchestration, in order.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Plan,
+    Execute,
+    Review,
+    /// Past review; every step of the stepper is done
+    Done,
+}
+
+impl Phase {
+    /// Steps shown in the header stepper.
+    pub const STEPS: [Phase; 3] = [Phase::Plan, Phase::Execute, Phase::Review];
+
+    /// Parse a `current_phase` value, accepting the verb forms orchestrators
+    /// write (`planning`, `executing`, `implement`, `complete`, ...).
+    pub fn parse(value: &str) -> Option<Phase> {
+        match value.trim().to_lowercase().as_str() {
+            "plan" | "planning" | "spec" | "specify" | "decompose" => Some(Phase::Plan),
+            "execute" | "executing" | "execution" | "implement" | "implementing" | "implementation" => {
+                Some(Phase::Execute)
+            }
+            "review" | "reviewing" | "verify" | "verifying" => Some(Phase::Review),
+            "done" | "complete" | "completed" | "finished" => Some(Phase::Done),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Phase::Plan => "Plan",
+            Phase::Execute => "Execute",
+            Phase::Review => "Review",
+            Phase::Done => "Done",
+        }
+    }
+}
+
+/// Summary of a wave whose tasks all just completed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaveCompletion {
+    pub wave: u32,
+    pub task_count: usize,
+    pub files_modified: usize,
+    pub tests_passed: usize,
+    /// Next incomplete wave: (number, task count)
+    pub next_wave: Option<(u32, usize)>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -75,6 +206,22 @@ impl Wave {
     pub fn new(number: u32, tasks: Vec<Task>) -> Self {
         Self { number, tasks }
     }
+
+    /// True when the wave has tasks and every one of them is completed.
+    pub fn is_complete(&self) -> bool {
+        !self.tasks.is_empty()
+            && self
+                .tasks
+                .iter()
+                .all(|t| matches!(t.status, TaskStatus::Completed))
+    }
+
+    /// True once any task in the wave has left the pending state.
+    pub fn is_started(&self) -> bool {
+        self.tasks
+            .iter()
+            .any(|t| !matches!(t.status, TaskStatus::Pending))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -120,6 +267,18 @@ pub enum TaskStatus {
     },
 }
 
+impl TaskStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TaskStatus::Pending => "pending",
+            TaskStatus::Running => "running",
+            TaskStatus::Implemented => "implemented",
+            TaskStatus::Completed => "completed",
+            TaskStatus::Failed { .. } => "failed",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ReviewStatus {
@@ -358,4 +517,60 @@ mod tests {
         assert_eq!(graph.completed_tasks(), 1);
         assert_eq!(graph.waves.len(), 3);
     }
+
+    fn wave_of(number: u32, statuses: &[TaskStatus]) -> Wave {
+        Wave::new(
+            number,
+            statuses
+                .iter()
+                .enumerate()
+                .map(|(i, st)| Task::new(format!("W{}T{}", number, i), String::new(), st.clone()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn wave_completed_since_detects_transition() {
+        let before = TaskGraph::new(vec![
+            wave_of(1, &[TaskStatus::Completed, TaskStatus::Running]),
+            wave_of(2, &[TaskStatus::Pending, TaskStatus::Pending, TaskStatus::Pending]),
+        ]);
+        let mut done = wave_of(1, &[TaskStatus::Completed, TaskStatus::Completed]);
+        done.tasks[0].files_modified = vec!["a.rs".into(), "b.rs".into()];
+        done.tasks[1].tests_passed = Some(true);
+        let after = TaskGraph::new(vec![
+            done,
+            wave_of(2, &[TaskStatus::Pending, TaskStatus::Pending, TaskStatus::Pending]),
+        ]);
+
+        let completion = after.wave_completed_since(&before).unwrap();
+        assert_eq!(completion.wave, 1);
+        assert_eq!(completion.task_count, 2);
+        assert_eq!(completion.files_modified, 2);
+        assert_eq!(completion.tests_passed, 1);
+        assert_eq!(completion.next_wave, Some((2, 3)));
+    }
+
+    #[test]
+    fn wave_completed_since_ignores_already_complete_waves() {
+        let graph = TaskGraph::new(vec![
+            wave_of(1, &[TaskStatus::Completed]),
+            wave_of(2, &[TaskStatus::Running]),
+        ]);
+        assert_eq!(graph.wave_completed_since(&graph), None);
+    }
+
+    #[test]
+    fn wave_completed_since_last_wave_has_no_next() {
+        let before = TaskGraph::new(vec![wave_of(1, &[TaskStatus::Implemented])]);
+        let after = TaskGraph::new(vec![wave_of(1, &[TaskStatus::Completed])]);
+        let completion = after.wave_completed_since(&before).unwrap();
+        assert_eq!(completion.next_wave, None);
+    }
+
+    #[test]
+    fn empty_wave_is_never_complete() {
+        assert!(!Wave::new(1, vec![]).is_complete());
+        assert!(!Wave::new(1, vec![]).is_started());
+    }
 }
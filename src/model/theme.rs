@@ -1,4 +1,5 @@
 use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
 
 pub struct Theme;
 
@@ -78,6 +79,92 @@ impl Theme {
     }
 }
 
+/// How the palette reaches the terminal (`--high-contrast`, `--no-color` / `NO_COLOR`, settings).
+/// Widgets always draw with `Theme` colors; the view remaps the finished frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorMode {
+    #[default]
+    Normal,
+    /// Bright named colors only, no dimming
+    HighContrast,
+    /// No colors at all; highlights become reverse video
+    NoColor,
+}
+
+impl ColorMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::HighContrast => "high contrast",
+            Self::NoColor => "no color",
+        }
+    }
+
+    /// Next mode in settings cycle order.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Normal => Self::HighContrast,
+            Self::HighContrast => Self::NoColor,
+            Self::NoColor => Self::Normal,
+        }
+    }
+
+    /// Whether signals normally carried by color alone (task status, panel focus)
+    /// also get symbols and labels.
+    pub fn uses_labels(self) -> bool {
+        self != Self::Normal
+    }
+
+    /// Status glyph whose shape alone identifies the status (labelled modes).
+    pub fn task_status_label(status: &crate::model::TaskStatus) -> &'static str {
+        use crate::model::TaskStatus;
+        match status {
+            TaskStatus::Pending => "·",
+            TaskStatus::Running => "…",
+            TaskStatus::Implemented => "+",
+            TaskStatus::Completed => "✓",
+            TaskStatus::Failed { .. } => "✗",
+        }
+    }
+
+    /// Pure function: high-contrast replacement for a palette color. Neutrals become
+    /// white (text) or light gray (chrome); hues snap to the nearest bright ANSI color.
+    pub fn high_contrast(color: Color) -> Color {
+        let Color::Rgb(r, g, b) = color else {
+            return color;
+        };
+        let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+        if max - min < 40 {
+            return if max >= 180 { Color::White } else { Color::Gray };
+        }
+        let (r, g, b) = (r as f32, g as f32, b as f32);
+        let delta = (max - min) as f32;
+        let hue = if max as f32 == r {
+            60.0 * (((g - b) / delta).rem_euclid(6.0))
+        } else if max as f32 == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        match hue as u16 {
+            0..=19 | 330..=360 => Color::LightRed,
+            20..=64 => Color::LightYellow,
+            65..=159 => Color::LightGreen,
+            160..=199 => Color::LightCyan,
+            200..=249 => Color::LightBlue,
+            _ => Color::LightMagenta,
+        }
+    }
+
+    /// Backgrounds that are just panel chrome (dropped in labelled modes, unlike
+    /// selection and badge backgrounds, which become reverse video).
+    pub fn is_chrome_background(color: Color) -> bool {
+        matches!(color, Color::Reset)
+            || [Theme::BACKGROUND, Theme::SURFACE, Theme::HEADER_BG, Theme::FOOTER_BG].contains(&color)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -113,4 +200,23 @@ mod tests {
         assert_eq!(Theme::tool_color("TaskCreate"), Theme::TOOL_TASK);
         assert_eq!(Theme::tool_color("Unknown"), Theme::MUTED_TEXT);
     }
+
+    #[test]
+    fn high_contrast_snaps_palette_to_bright_colors() {
+        assert_eq!(ColorMode::high_contrast(Theme::TEXT), Color::White);
+        assert_eq!(ColorMode::high_contrast(Theme::MUTED_TEXT), Color::Gray);
+        assert_eq!(ColorMode::high_contrast(Theme::SUCCESS), Color::LightGreen);
+        assert_eq!(ColorMode::high_contrast(Theme::ERROR), Color::LightRed);
+        assert_eq!(ColorMode::high_contrast(Theme::ACCENT_WARM), Color::LightYellow);
+        assert_eq!(ColorMode::high_contrast(Theme::ACCENT), Color::LightCyan);
+        assert_eq!(ColorMode::high_contrast(Theme::ACCENT_PURPLE), Color::LightMagenta);
+        assert_eq!(ColorMode::high_contrast(Color::Reset), Color::Reset);
+    }
+
+    #[test]
+    fn color_mode_cycles_and_labels() {
+        assert_eq!(ColorMode::Normal.next().next().next(), ColorMode::Normal);
+        assert!(!ColorMode::Normal.uses_labels());
+        assert!(ColorMode::NoColor.uses_labels());
+    }
 }
@@ -1,3 +1,6 @@
+use std::path::Path;
+use std::sync::Arc;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
@@ -13,6 +16,68 @@ pub struct TranscriptEvent {
     pub session_id: Option<SessionId>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub agent_id: Option<AgentId>,
+    /// Working directory of the transcript entry (relative tool paths resolve against it)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Tool call id of a ToolUse / ToolResult (keys captured tool outputs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_use_id: Option<String>,
+    /// Which source produced the event
+    pub origin: EventOrigin,
+    /// Where the untruncated entry lives, for events whose text was clipped
+    /// when read (see `watcher::TruncationLimits`)
+    #[serde(skip)]
+    pub full_text_at: Option<SourceRef>,
+}
+
+/// A content block of one transcript line: the line's byte offset in its
+/// file and the block's index in `message.content`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceRef {
+    pub path: Arc<Path>,
+    pub offset: u64,
+    pub block: usize,
+}
+
+/// Where an event entered loom-tui, for telling apart duplicate or
+/// conflicting events once several sources feed the same session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventOrigin {
+    /// Main session transcript file
+    MainTranscript,
+    /// Subagent transcript file (`{session}/subagents/agent-*.jsonl`)
+    SubagentTranscript,
+    /// Piped in by a hook script or remote tail (`--stdin` / `--socket`)
+    Hook,
+    /// Constructed in-process rather than read from a source
+    Injected,
+    /// A line of an extra log file from the config (`extra_logs`)
+    ExternalLog,
+}
+
+impl EventOrigin {
+    /// Name used by the `origin:` filter.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MainTranscript => "main",
+            Self::SubagentTranscript => "subagent",
+            Self::Hook => "hook",
+            Self::Injected => "injected",
+            Self::ExternalLog => "log",
+        }
+    }
+
+    /// One-cell marker shown next to events in the stream.
+    pub fn glyph(self) -> &'static str {
+        match self {
+            Self::MainTranscript => "·",
+            Self::SubagentTranscript => "↳",
+            Self::Hook => "⇣",
+            Self::Injected => "✱",
+            Self::ExternalLog => "▤",
+        }
+    }
 }
 
 impl TranscriptEvent {
@@ -22,6 +87,10 @@ impl TranscriptEvent {
             kind,
             session_id: None,
             agent_id: None,
+            cwd: None,
+            tool_use_id: None,
+            origin: EventOrigin::Injected,
+            full_text_at: None,
         }
     }
 
@@ -34,6 +103,16 @@ impl TranscriptEvent {
         self.agent_id = Some(agent_id.into());
         self
     }
+
+    pub fn with_cwd(mut self, cwd: impl Into<String>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+
+    pub fn with_origin(mut self, origin: EventOrigin) -> Self {
+        self.origin = origin;
+        self
+    }
 }
 
 /// Custom Deserialize for TranscriptEvent.
@@ -78,6 +157,23 @@ impl<'de> Deserialize<'de> for TranscriptEvent {
             .map(|v| serde_json::from_value(v).map_err(serde::de::Error::custom))
             .transpose()?;
 
+        let cwd: Option<String> = map
+            .remove("cwd")
+            .map(|v| serde_json::from_value(v).map_err(serde::de::Error::custom))
+            .transpose()?;
+
+        let tool_use_id: Option<String> = map
+            .remove("tool_use_id")
+            .map(|v| serde_json::from_value(v).map_err(serde::de::Error::custom))
+            .transpose()?;
+
+        // Archives written before provenance was recorded came from transcript files
+        let origin: EventOrigin = match map.remove("origin") {
+            Some(v) => serde_json::from_value(v).map_err(serde::de::Error::custom)?,
+            None if agent_id.is_some() => EventOrigin::SubagentTranscript,
+            None => EventOrigin::MainTranscript,
+        };
+
         // Remaining map contains "event" discriminant + variant fields — feed to
         // TranscriptEventKind's derived Deserialize (internally tagged).
         let kind: TranscriptEventKind =
@@ -88,6 +184,10 @@ impl<'de> Deserialize<'de> for TranscriptEvent {
             kind,
             session_id,
             agent_id,
+            cwd,
+            tool_use_id,
+            origin,
+            full_text_at: None,
         })
     }
 }
@@ -106,11 +206,71 @@ pub enum TranscriptEventKind {
         result_summary: String,
         #[serde(default)]
         duration_ms: Option<u64>,
+        /// The tool reported failure (`is_error` on the transcript's tool_result)
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        is_error: bool,
+    },
+    /// A line of an extra log file tailed alongside the transcripts
+    ExternalLog { source: String, line: String },
+    /// A project instruction or settings file (`CLAUDE.md`, `.claude/settings.json`,
+    /// an agent definition) changed while running; `change` is created / edited / removed
+    ConfigChanged { file: String, change: String },
+    /// Progress line an agent wrote (`loom-status: building parser 40%`), forwarded
+    /// by a hook; `task_id` names the task, else it is the agent's task
+    StatusUpdate {
+        text: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        task_id: Option<String>,
+    },
+    /// The API call behind a turn failed (`isApiErrorMessage` assistant entries,
+    /// `api_error` system entries); `status` is the HTTP status when given
+    ApiError {
+        error_kind: ApiErrorKind,
+        message: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        status: Option<u16>,
     },
     /// Catch-all for forward compatibility
     Unknown { entry_type: String },
 }
 
+/// What an API error was about.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorKind {
+    /// The conversation no longer fits the model's context window
+    ContextLength,
+    RateLimit,
+    Overloaded,
+    Other,
+}
+
+impl ApiErrorKind {
+    /// Pure function: the kind an error with `status` and `message` is.
+    pub fn classify(status: Option<u16>, message: &str) -> Self {
+        let text = message.to_lowercase();
+        let says = |needles: &[&str]| needles.iter().any(|needle| text.contains(needle));
+        if says(&["prompt is too long", "context length", "context_length", "context window", "maximum context"]) {
+            Self::ContextLength
+        } else if status == Some(429) || says(&["rate_limit", "rate limit", "usage limit"]) {
+            Self::RateLimit
+        } else if status == Some(529) || says(&["overloaded"]) {
+            Self::Overloaded
+        } else {
+            Self::Other
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ContextLength => "context limit",
+            Self::RateLimit => "rate limit",
+            Self::Overloaded => "overloaded",
+            Self::Other => "api error",
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,6 +281,24 @@ mod tests {
 
     // --- round-trip tests ---
 
+    #[test]
+    fn api_errors_classify_by_message_then_status() {
+        assert_eq!(ApiErrorKind::classify(Some(400), "Prompt is too long: 210000 tokens > 200000 maximum"), ApiErrorKind::ContextLength);
+        assert_eq!(ApiErrorKind::classify(Some(429), "Number of requests exceeded"), ApiErrorKind::RateLimit);
+        assert_eq!(ApiErrorKind::classify(None, "Claude AI usage limit reached"), ApiErrorKind::RateLimit);
+        assert_eq!(ApiErrorKind::classify(Some(529), "Overloaded"), ApiErrorKind::Overloaded);
+        assert_eq!(ApiErrorKind::classify(Some(500), "Internal server error"), ApiErrorKind::Other);
+    }
+
+    #[test]
+    fn api_error_round_trip() {
+        let kind = TranscriptEventKind::ApiError { error_kind: ApiErrorKind::Overloaded, message: "Overloaded".into(), status: Some(529) };
+        let event = TranscriptEvent::new(ts(), kind).with_agent("a1");
+        let json = serde_json::to_string(&event).unwrap();
+        let back: TranscriptEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(event, back);
+    }
+
     #[test]
     fn user_message_round_trip() {
         let event = TranscriptEvent::new(ts(), TranscriptEventKind::UserMessage);
@@ -164,6 +342,7 @@ mod tests {
                 tool_name: ToolName::new("Bash"),
                 result_summary: "ok".to_string(),
                 duration_ms: Some(42),
+                is_error: false,
             },
         );
         let json = serde_json::to_string(&event).unwrap();
@@ -316,6 +495,21 @@ mod tests {
         assert_eq!(back.agent_id, Some(AgentId::new("agent-xyz")));
     }
 
+    #[test]
+    fn origin_round_trips_and_is_inferred_for_older_archives() {
+        let event = TranscriptEvent::new(ts(), TranscriptEventKind::UserMessage).with_origin(EventOrigin::Hook);
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains(r#""origin":"hook""#));
+        assert_eq!(serde_json::from_str::<TranscriptEvent>(&json).unwrap(), event);
+
+        let legacy = |extra: &str| {
+            let json = format!(r#"{{"timestamp":"2026-03-18T10:00:00Z","event":"user_message"{extra}}}"#);
+            serde_json::from_str::<TranscriptEvent>(&json).unwrap().origin
+        };
+        assert_eq!(legacy(""), EventOrigin::MainTranscript);
+        assert_eq!(legacy(r#","agent_id":"a1""#), EventOrigin::SubagentTranscript);
+    }
+
     // --- session_id and agent_id deserialize correctly alongside event fields ---
 
     #[test]
@@ -0,0 +1,86 @@
+use std::process::{Command, Stdio};
+
+use crate::app::cost::{AlertScope, CostAlert};
+
+/// Pure function: JSON body POSTed to the alert webhook.
+pub fn webhook_payload(alert: &CostAlert) -> serde_json::Value {
+    let (scope, session_id, date) = match &alert.scope {
+        AlertScope::Session(sid) => ("session", Some(sid.to_string()), None),
+        AlertScope::Daily(date) => ("daily", None, Some(date.to_string())),
+    };
+    serde_json::json!({
+        "text": format!("loom-tui: {}", alert.message()),
+        "scope": scope,
+        "session_id": session_id,
+        "date": date,
+        "percent": alert.percent,
+        "spent_cents": alert.spent_cents,
+        "budget_cents": alert.budget_cents,
+    })
+}
+
+/// Show `alert` as a desktop notification (`notify-send`, or `osascript` on macOS).
+/// Returns `Err` with the failure on error.
+pub fn desktop(alert: &CostAlert) -> Result<(), String> {
+    let body = alert.message();
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!("display notification {:?} with title \"loom-tui\"", body);
+        let mut c = Command::new("osascript");
+        c.args(["-e", &script]);
+        c
+    } else {
+        let mut c = Command::new("notify-send");
+        c.args(["loom-tui", &body]);
+        c
+    };
+    run(&mut command)
+}
+
+/// POST `alert` as JSON to `url` with `curl` (5s timeout).
+/// Returns `Err` with the failure on error.
+pub fn webhook(url: &str, alert: &CostAlert) -> Result<(), String> {
+    let body = webhook_payload(alert).to_string();
+    run(Command::new("curl").args([
+        "-fsS",
+        "-m",
+        "5",
+        "-H",
+        "Content-Type: application/json",
+        "-d",
+        &body,
+        url,
+    ]))
+}
+
+fn run(command: &mut Command) -> Result<(), String> {
+    let output = command
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("{}: {}", command.get_program().to_string_lossy(), e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::SessionId;
+
+    #[test]
+    fn webhook_payload_describes_the_alert() {
+        let alert = CostAlert {
+            scope: AlertScope::Session(SessionId::new("sess-1")),
+            percent: 80,
+            spent_cents: 410,
+            budget_cents: 500,
+        };
+        let payload = webhook_payload(&alert);
+        assert_eq!(payload["scope"], "session");
+        assert_eq!(payload["session_id"], "sess-1");
+        assert_eq!(payload["percent"], 80);
+        assert_eq!(payload["text"], "loom-tui: session sess-1 at 80% of its $5.00 budget ($4.10)");
+    }
+}
@@ -1,5 +1,11 @@
+use std::fmt;
 use std::path::{Path, PathBuf};
 
+use serde::Deserialize;
+
+/// Environment variable overriding the archive directory (highest precedence).
+pub const ARCHIVE_DIR_ENV: &str = "LOOM_TUI_ARCHIVE_DIR";
+
 /// Resolved paths for all loom-tui file locations.
 /// Pure data structure with no I/O.
 #[derive(Debug, Clone)]
@@ -12,15 +18,133 @@ pub struct Paths {
     /// Example: ~/.claude/projects/-home-user-dev-myproject/
     pub transcript_dir: PathBuf,
 
-    /// Directory for archived session storage
+    /// Root directory for archived session storage; archives live in
+    /// per-project subdirectories named by `project_hash` (see `session::archive_path`)
     /// Example: ~/.local/share/loom-tui/sessions/
     pub archive_dir: PathBuf,
 }
 
+/// Path overrides read from a config file, user-wide or per-project.
+/// Relative paths are resolved against the project root (project config)
+/// or the config file's directory (user config).
+///
+/// ```json
+/// { "archive_dir": "/mnt/shared/loom-archives" }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct PathsConfig {
+    pub archive_dir: Option<PathBuf>,
+    pub transcript_dir: Option<PathBuf>,
+    pub task_graph: Option<PathBuf>,
+}
+
+/// A config file consulted during resolution and what was found there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigFile {
+    pub path: PathBuf,
+    pub status: ConfigStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigStatus {
+    Missing,
+    Loaded(PathsConfig),
+    /// Unreadable or malformed; ignored
+    Invalid(String),
+}
+
+impl ConfigFile {
+    fn config(&self) -> Option<&PathsConfig> {
+        match &self.status {
+            ConfigStatus::Loaded(config) => Some(config),
+            _ => None,
+        }
+    }
+}
+
+/// Where a resolved path came from, highest precedence first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSource {
+    /// Environment override such as `LOOM_TUI_ARCHIVE_DIR`
+    Env(&'static str),
+    /// `<project>/.claude/loom-tui.json`
+    ProjectConfig,
+    /// `$XDG_CONFIG_HOME/loom-tui/config.json`
+    UserConfig,
+    /// Default under an XDG base directory variable
+    Xdg(&'static str),
+    /// Built-in default
+    Default,
+}
+
+impl fmt::Display for PathSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Env(var) => write!(f, "${}", var),
+            Self::ProjectConfig => write!(f, "project config"),
+            Self::UserConfig => write!(f, "user config"),
+            Self::Xdg(var) => write!(f, "${} default", var),
+            Self::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// One resolved location, why it was chosen, and whether it exists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedPath {
+    pub name: &'static str,
+    pub path: PathBuf,
+    pub source: PathSource,
+    pub exists: bool,
+}
+
+/// Everything `loom-tui paths` reports: the paths in use, their sources, and
+/// the config files that were consulted.
+#[derive(Debug, Clone)]
+pub struct PathReport {
+    pub paths: Paths,
+    pub resolved: Vec<ResolvedPath>,
+    pub config_files: Vec<ConfigFile>,
+}
+
+impl PathReport {
+    /// Problems worth surfacing at startup (malformed config files).
+    pub fn warnings(&self) -> Vec<String> {
+        self.config_files
+            .iter()
+            .filter_map(|c| match &c.status {
+                ConfigStatus::Invalid(e) => Some(format!("{}: {}", c.path.display(), e)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Pure function: human-readable doctor report.
+    pub fn render(&self) -> String {
+        let mut out = String::from("loom-tui paths\n\n");
+        for r in &self.resolved {
+            let mark = if r.exists { "ok     " } else { "missing" };
+            out.push_str(&format!("  {:<15} {}  {}  ({})\n", r.name, mark, r.path.display(), r.source));
+        }
+        out.push_str("\nconfig files\n\n");
+        for c in &self.config_files {
+            let status = match &c.status {
+                ConfigStatus::Missing => "not present".to_string(),
+                ConfigStatus::Loaded(_) => "loaded".to_string(),
+                ConfigStatus::Invalid(e) => format!("ignored: {}", e),
+            };
+            out.push_str(&format!("  {}  {}\n", c.path.display(), status));
+        }
+        out
+    }
+}
+
 impl Paths {
     /// Resolves all paths relative to the given project root.
     ///
-    /// Pure function: only performs path concatenation and environment variable reads.
+    /// Reads environment variables and the user/project config files;
+    /// see `resolve_with` for precedence.
     /// Does NOT create directories or verify file existence - that is the caller's responsibility.
     ///
     /// # Arguments
@@ -30,7 +154,9 @@ impl Paths {
     /// # Environment
     ///
     /// * `HOME` - Used to resolve transcript_dir (~/.claude/projects/PROJECT_HASH/)
-    ///   and archive_dir (~/.local/share/loom-tui/sessions/).
+    ///   and the XDG fallbacks (archive_dir defaults to ~/.local/share/loom-tui/sessions/).
+    /// * `XDG_DATA_HOME`, `XDG_CONFIG_HOME` - XDG base directories.
+    /// * `LOOM_TUI_ARCHIVE_DIR` - Archive directory override.
     ///
     /// # Examples
     ///
@@ -47,25 +173,94 @@ impl Paths {
     /// );
     /// ```
     pub fn resolve(project_root: &Path) -> Self {
-        let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-        let home_path = PathBuf::from(&home);
+        Self::report(project_root).paths
+    }
 
-        let hash = Self::project_hash(project_root);
+    /// Resolve paths and record where each came from (for `loom-tui paths`).
+    /// I/O: reads environment variables, config files, and path existence.
+    pub fn report(project_root: &Path) -> PathReport {
+        let env = |key: &str| std::env::var(key).ok();
+        let config_files = vec![
+            load_config(&Self::user_config_path(&env)),
+            load_config(&Self::project_config_path(project_root)),
+        ];
+        let mut resolved = Self::resolve_with(
+            project_root,
+            &env,
+            config_files[0].config(),
+            config_files[1].config(),
+        );
+        for r in &mut resolved {
+            r.exists = r.path.exists();
+        }
+        let path_of = |name: &str| {
+            resolved.iter().find(|r| r.name == name).map(|r| r.path.clone()).unwrap_or_default()
+        };
+        let paths = Self {
+            task_graph: path_of("task_graph"),
+            transcript_dir: path_of("transcript_dir"),
+            archive_dir: path_of("archive_dir"),
+        };
+        PathReport { paths, resolved, config_files }
+    }
 
-        Self {
-            task_graph: project_root
-                .join(".claude")
-                .join("state")
-                .join("active_task_graph.json"),
+    /// Pure resolution. Precedence per path, highest first:
+    /// environment override, project config, user config, XDG base dir, built-in default.
+    /// `exists` is left false; `report` fills it in.
+    pub fn resolve_with(
+        project_root: &Path,
+        env: &dyn Fn(&str) -> Option<String>,
+        user_config: Option<&PathsConfig>,
+        project_config: Option<&PathsConfig>,
+    ) -> Vec<ResolvedPath> {
+        let home = PathBuf::from(env("HOME").unwrap_or_else(|| "/tmp".to_string()));
+        let user_base = Self::user_config_path(env).parent().map(Path::to_path_buf).unwrap_or_default();
+        let from_configs = |pick: fn(&PathsConfig) -> Option<&PathBuf>| {
+            project_config
+                .and_then(pick)
+                .map(|p| (project_root.join(p), PathSource::ProjectConfig))
+                .or_else(|| user_config.and_then(pick).map(|p| (user_base.join(p), PathSource::UserConfig)))
+        };
+        let resolved = |name, (path, source)| ResolvedPath { name, path, source, exists: false };
+
+        let task_graph = from_configs(|c| c.task_graph.as_ref()).unwrap_or_else(|| {
+            (project_root.join(".claude").join("state").join("active_task_graph.json"), PathSource::Default)
+        });
+
+        // Claude Code's own layout; not ours to move under XDG
+        let transcript_dir = from_configs(|c| c.transcript_dir.as_ref()).unwrap_or_else(|| {
+            (
+                home.join(".claude").join("projects").join(Self::project_hash(project_root)),
+                PathSource::Default,
+            )
+        });
+
+        let archive_dir = env(ARCHIVE_DIR_ENV)
+            .filter(|v| !v.is_empty())
+            .map(|v| (PathBuf::from(v), PathSource::Env(ARCHIVE_DIR_ENV)))
+            .or_else(|| from_configs(|c| c.archive_dir.as_ref()))
+            .unwrap_or_else(|| {
+                let (data_home, source) = xdg_base(env, "XDG_DATA_HOME", &home, &[".local", "share"]);
+                (data_home.join("loom-tui").join("sessions"), source)
+            });
+
+        vec![
+            resolved("task_graph", task_graph),
+            resolved("transcript_dir", transcript_dir),
+            resolved("archive_dir", archive_dir),
+        ]
+    }
 
-            transcript_dir: home_path.join(".claude").join("projects").join(hash),
+    /// User config file: `$XDG_CONFIG_HOME/loom-tui/config.json` (default `~/.config/...`).
+    /// Pure function over the given environment.
+    pub fn user_config_path(env: &dyn Fn(&str) -> Option<String>) -> PathBuf {
+        let home = PathBuf::from(env("HOME").unwrap_or_else(|| "/tmp".to_string()));
+        xdg_base(env, "XDG_CONFIG_HOME", &home, &[".config"]).0.join("loom-tui").join("config.json")
+    }
 
-            archive_dir: home_path
-                .join(".local")
-                .join("share")
-                .join("loom-tui")
-                .join("sessions"),
-        }
+    /// Per-project config file: `<project_root>/.claude/loom-tui.json`.
+    pub fn project_config_path(project_root: &Path) -> PathBuf {
+        project_root.join(".claude").join("loom-tui.json")
     }
 
     /// Compute the project hash from an absolute path.
@@ -90,6 +285,33 @@ impl Paths {
     }
 }
 
+/// XDG base directory from `var`, or `$HOME/<fallback...>` when unset, empty, or
+/// relative (the spec says relative values must be ignored).
+fn xdg_base(
+    env: &dyn Fn(&str) -> Option<String>,
+    var: &'static str,
+    home: &Path,
+    fallback: &[&str],
+) -> (PathBuf, PathSource) {
+    match env(var).map(PathBuf::from).filter(|p| p.is_absolute()) {
+        Some(base) => (base, PathSource::Xdg(var)),
+        None => (fallback.iter().fold(home.to_path_buf(), |p, c| p.join(c)), PathSource::Default),
+    }
+}
+
+/// Read a config file. I/O: a missing file is not an error.
+fn load_config(path: &Path) -> ConfigFile {
+    let status = match std::fs::read_to_string(path) {
+        Ok(content) => match serde_json::from_str(&content) {
+            Ok(config) => ConfigStatus::Loaded(config),
+            Err(e) => ConfigStatus::Invalid(e.to_string()),
+        },
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => ConfigStatus::Missing,
+        Err(e) => ConfigStatus::Invalid(e.to_string()),
+    };
+    ConfigFile { path: path.to_path_buf(), status }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,6 +455,105 @@ mod tests {
         );
     }
 
+    // ---------------------------------------------------------------------------
+    // Precedence tests (pure resolve_with)
+    // ---------------------------------------------------------------------------
+
+    fn env_of(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |key| vars.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone())
+    }
+
+    fn resolved<'a>(all: &'a [ResolvedPath], name: &str) -> &'a ResolvedPath {
+        all.iter().find(|r| r.name == name).unwrap()
+    }
+
+    #[test]
+    fn archive_dir_honors_xdg_data_home() {
+        let env = env_of(&[("HOME", "/home/u"), ("XDG_DATA_HOME", "/data")]);
+        let all = Paths::resolve_with(Path::new("/p"), &env, None, None);
+        let archive = resolved(&all, "archive_dir");
+        assert_eq!(archive.path, Path::new("/data/loom-tui/sessions"));
+        assert_eq!(archive.source, PathSource::Xdg("XDG_DATA_HOME"));
+    }
+
+    #[test]
+    fn relative_or_empty_xdg_values_are_ignored() {
+        for value in ["", "relative/data"] {
+            let env = env_of(&[("HOME", "/home/u"), ("XDG_DATA_HOME", value)]);
+            let all = Paths::resolve_with(Path::new("/p"), &env, None, None);
+            assert_eq!(resolved(&all, "archive_dir").path, Path::new("/home/u/.local/share/loom-tui/sessions"));
+        }
+    }
+
+    #[test]
+    fn env_override_beats_configs() {
+        let env = env_of(&[("HOME", "/home/u"), (ARCHIVE_DIR_ENV, "/override")]);
+        let config = PathsConfig { archive_dir: Some("/from-config".into()), ..Default::default() };
+        let all = Paths::resolve_with(Path::new("/p"), &env, Some(&config), Some(&config));
+        let archive = resolved(&all, "archive_dir");
+        assert_eq!(archive.path, Path::new("/override"));
+        assert_eq!(archive.source, PathSource::Env(ARCHIVE_DIR_ENV));
+    }
+
+    #[test]
+    fn project_config_beats_user_config() {
+        let env = env_of(&[("HOME", "/home/u")]);
+        let user = PathsConfig {
+            archive_dir: Some("archives".into()),
+            transcript_dir: Some("/user/transcripts".into()),
+            ..Default::default()
+        };
+        let project = PathsConfig { archive_dir: Some("local-archives".into()), ..Default::default() };
+        let all = Paths::resolve_with(Path::new("/p"), &env, Some(&user), Some(&project));
+
+        // Relative project paths are relative to the project root
+        assert_eq!(resolved(&all, "archive_dir").path, Path::new("/p/local-archives"));
+        assert_eq!(resolved(&all, "archive_dir").source, PathSource::ProjectConfig);
+        assert_eq!(resolved(&all, "transcript_dir").path, Path::new("/user/transcripts"));
+        assert_eq!(resolved(&all, "transcript_dir").source, PathSource::UserConfig);
+    }
+
+    #[test]
+    fn user_config_relative_paths_resolve_against_config_dir() {
+        let env = env_of(&[("HOME", "/home/u"), ("XDG_CONFIG_HOME", "/cfg")]);
+        let user = PathsConfig { archive_dir: Some("archives".into()), ..Default::default() };
+        let all = Paths::resolve_with(Path::new("/p"), &env, Some(&user), None);
+        assert_eq!(resolved(&all, "archive_dir").path, Path::new("/cfg/loom-tui/archives"));
+    }
+
+    #[test]
+    fn user_config_path_follows_xdg_config_home() {
+        assert_eq!(
+            Paths::user_config_path(&env_of(&[("HOME", "/home/u")])),
+            Path::new("/home/u/.config/loom-tui/config.json")
+        );
+        assert_eq!(
+            Paths::user_config_path(&env_of(&[("XDG_CONFIG_HOME", "/cfg")])),
+            Path::new("/cfg/loom-tui/config.json")
+        );
+    }
+
+    #[test]
+    fn report_reads_project_config_and_flags_invalid_files() {
+        let _guard = HomeGuard::set("/nonexistent-home");
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".claude")).unwrap();
+        let config_path = Paths::project_config_path(dir.path());
+
+        std::fs::write(&config_path, r#"{"task_graph": "graph.json"}"#).unwrap();
+        let report = Paths::report(dir.path());
+        assert_eq!(report.paths.task_graph, dir.path().join("graph.json"));
+        assert!(report.warnings().is_empty());
+        assert!(report.render().contains("project config"));
+
+        std::fs::write(&config_path, "{not json").unwrap();
+        let report = Paths::report(dir.path());
+        assert_eq!(report.paths.task_graph, dir.path().join(".claude/state/active_task_graph.json"));
+        assert_eq!(report.warnings().len(), 1);
+        assert!(report.render().contains("ignored:"));
+    }
+
     // ---------------------------------------------------------------------------
     // Helpers
     // ---------------------------------------------------------------------------
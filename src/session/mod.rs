@@ -1,12 +1,30 @@
 use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
-use std::time::Instant;
+use std::time::{Instant, SystemTime};
 
 use serde::Deserialize;
 
 use crate::error::SessionError;
-use crate::model::{Agent, AgentId, SessionArchive, SessionMeta, TaskGraph, TranscriptEvent};
+use crate::paths::Paths;
+use crate::model::{
+    Agent, AgentId, ArchiveStamp, Clock, EventPaging, SessionArchive, SessionMeta, SessionPreview, TaskGraph, TaskStatus,
+    TranscriptEvent, TranscriptEventKind,
+};
+
+/// Number of events fetched per page when an archive is streamed into SessionDetail.
+pub const EVENT_PAGE_SIZE: usize = 500;
+
+/// Number of archive metas read per page when the Sessions list is paged in.
+pub const META_PAGE_SIZE: usize = 200;
+
+/// Threads reading archive metas side by side.
+pub const META_READERS: usize = 4;
+
+/// Archives a meta reader takes at a time; each finished chunk is reported.
+pub const META_CHUNK: usize = 25;
 
 // ============================================================================
 // FUNCTIONAL CORE: Pure functions for serialization and data transformation
@@ -38,6 +56,128 @@ pub fn deserialize_session(content: &str) -> Result<SessionArchive, SessionError
     serde_json::from_str(content).map_err(SessionError::from)
 }
 
+/// Everything in an archive except the events, which are skipped.
+#[derive(Deserialize)]
+struct ArchiveHead {
+    meta: SessionMeta,
+    #[serde(default)]
+    version: u32,
+    #[serde(default)]
+    task_graph: Option<TaskGraph>,
+    #[serde(default)]
+    agents: BTreeMap<AgentId, Agent>,
+    #[serde(default)]
+    notes: Option<String>,
+    #[serde(default)]
+    written_by: Option<String>,
+}
+
+/// Byte span of each element of the archive's top-level `events` array.
+/// Pure function: a single pass over the JSON text, tracking only nesting and strings.
+/// Expects well-formed JSON; callers parse the same content with serde first.
+pub fn event_spans(content: &str) -> Vec<Range<usize>> {
+    let bytes = content.as_bytes();
+    let mut spans = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut key_start = None;
+    let mut last_key: &[u8] = b"";
+    let mut in_events = false;
+    let mut element_start = None;
+
+    for (i, &b) in bytes.iter().enumerate() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+                if let Some(start) = key_start.take() {
+                    last_key = &bytes[start..i];
+                }
+            }
+            continue;
+        }
+        match b {
+            b'"' => {
+                in_string = true;
+                if depth == 1 {
+                    key_start = Some(i + 1);
+                }
+            }
+            b'{' | b'[' => {
+                // The last top-level string before a `[` is that array's key
+                if depth == 1 {
+                    in_events = b == b'[' && last_key == b"events";
+                } else if depth == 2 && in_events {
+                    element_start = Some(i);
+                }
+                depth += 1;
+            }
+            b'}' | b']' => {
+                depth = depth.saturating_sub(1);
+                if depth == 2 && in_events {
+                    if let Some(start) = element_start.take() {
+                        spans.push(start..i + 1);
+                    }
+                } else if depth == 1 {
+                    in_events = false;
+                }
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+/// Deserialize an archive without materializing its events.
+/// Pure function: events are skipped, only their byte spans are kept.
+///
+/// # Arguments
+/// * `content` - JSON string to parse
+///
+/// # Returns
+/// * `Ok((SessionArchive, Vec<Range<usize>>))` - Archive with empty `events`, and each event's byte span in `content`
+/// * `Err(SessionError)` - Deserialization error
+pub fn deserialize_session_head(content: &str) -> Result<(SessionArchive, Vec<Range<usize>>), SessionError> {
+    let head: ArchiveHead = serde_json::from_str(content)?;
+    let archive = SessionArchive {
+        meta: head.meta,
+        version: head.version,
+        task_graph: head.task_graph,
+        events: Vec::new(),
+        agents: head.agents,
+        notes: head.notes,
+        written_by: head.written_by,
+    };
+    Ok((archive, event_spans(content)))
+}
+
+/// Deserialize one page of events from their byte spans.
+/// Pure function: only the bytes inside `spans` are parsed.
+///
+/// # Arguments
+/// * `content` - Bytes the spans index into
+/// * `spans` - Byte span of each event in the page, oldest first
+///
+/// # Returns
+/// * `Ok(Vec<TranscriptEvent>)` - Events in span order (old-format events dropped)
+/// * `Err(SessionError)` - A span is out of bounds or not valid JSON
+pub fn deserialize_event_page(content: &[u8], spans: &[Range<usize>]) -> Result<Vec<TranscriptEvent>, SessionError> {
+    let mut events = Vec::with_capacity(spans.len());
+    for span in spans {
+        let bytes = content
+            .get(span.clone())
+            .ok_or_else(|| SessionError::Json(format!("event span {span:?} outside archive")))?;
+        let value: serde_json::Value = serde_json::from_slice(bytes)?;
+        // Same leniency as a full load: unparseable (old-format) events are dropped
+        events.extend(serde_json::from_value(value).ok());
+    }
+    Ok(events)
+}
+
 /// Generate deterministic filename for session archive.
 /// Pure function: based solely on session metadata.
 ///
@@ -53,6 +193,26 @@ pub fn generate_filename(meta: &SessionMeta) -> String {
     format!("{}.json", meta.id)
 }
 
+/// Per-project archive directory under the archive root.
+/// Pure function: `{root}/{project_hash}`, or the root itself when the project is unknown.
+///
+/// # Example
+/// `/home/u/.local/share/loom-tui/sessions` + `/home/u/dev/app`
+/// → `/home/u/.local/share/loom-tui/sessions/-home-u-dev-app`
+pub fn project_archive_dir(root: &Path, project_path: &str) -> PathBuf {
+    if project_path.is_empty() {
+        root.to_path_buf()
+    } else {
+        root.join(Paths::project_hash(Path::new(project_path)))
+    }
+}
+
+/// Namespaced path for a session archive: `{root}/{project_hash}/{session_id}.json`.
+/// Pure function.
+pub fn archive_path(root: &Path, meta: &SessionMeta) -> PathBuf {
+    project_archive_dir(root, &meta.project_path).join(generate_filename(meta))
+}
+
 /// Extract session metadata from full session archive.
 /// Pure function: data transformation only.
 ///
@@ -108,6 +268,119 @@ pub fn build_archive(
     archive
 }
 
+/// Build the Sessions view preview summary from a full archive.
+/// Pure function: data transformation only.
+pub fn build_preview(archive: &SessionArchive) -> SessionPreview {
+    SessionPreview::from_events(&archive.events, archive.agents.len())
+}
+
+/// Render a session archive as a Markdown report: summary, notes, tasks, agents, event log.
+/// Pure function: data transformation only.
+///
+/// # Arguments
+/// * `archive` - Fully loaded session archive
+///
+/// # Returns
+/// Markdown document as a string
+pub fn export_markdown(archive: &SessionArchive) -> String {
+    use std::fmt::Write;
+
+    let meta = &archive.meta;
+    let mut md = String::new();
+    let _ = writeln!(md, "# Session {}\n", meta.id);
+    if let Some(goal) = &meta.goal {
+        let _ = writeln!(md, "> {}\n", goal.lines().collect::<Vec<_>>().join("\n> "));
+    }
+    let _ = writeln!(md, "- **Started:** {}", meta.timestamp.format("%Y-%m-%d %H:%M:%S UTC"));
+    if let Some(d) = meta.duration {
+        let _ = writeln!(md, "- **Duration:** {}s", d.as_secs());
+    }
+    if let Some(budget) = meta.budget {
+        let over = meta.over_budget.map(|d| format!(" (over by {}s)", d.as_secs())).unwrap_or_default();
+        let _ = writeln!(md, "- **Budget:** {}s{}", budget.as_secs(), over);
+    }
+    let _ = writeln!(md, "- **Status:** {}", format!("{:?}", meta.status).to_lowercase());
+    let _ = writeln!(md, "- **Project:** `{}`", meta.project_path);
+    if let Some(branch) = &meta.git_branch {
+        let _ = writeln!(md, "- **Branch:** `{}`", branch);
+    }
+    let _ = writeln!(
+        md,
+        "- **Agents:** {} · **Tasks:** {} · **Events:** {}",
+        archive.agents.len(),
+        archive.task_graph.as_ref().map(|g| g.total_tasks()).unwrap_or(0),
+        archive.events.len()
+    );
+
+    if let Some(notes) = archive.notes.as_deref().map(str::trim).filter(|n| !n.is_empty()) {
+        let _ = writeln!(md, "\n## Notes\n\n{}", notes);
+    }
+
+    if let Some(graph) = archive.task_graph.as_ref().filter(|g| g.total_tasks() > 0) {
+        let _ = writeln!(md, "\n## Tasks");
+        for wave in &graph.waves {
+            let _ = writeln!(md, "\n### Wave {}\n", wave.number);
+            for task in &wave.tasks {
+                let check = if matches!(task.status, TaskStatus::Completed) { "x" } else { " " };
+                let status = match &task.status {
+                    TaskStatus::Failed { reason, .. } => format!("failed: {}", reason),
+                    other => format!("{:?}", other).to_lowercase(),
+                };
+                let _ = writeln!(md, "- [{}] **{}** {} _({})_", check, task.id, task.description, status);
+            }
+        }
+    }
+
+    if !archive.agents.is_empty() {
+        let _ = writeln!(md, "\n## Agents\n");
+        for agent in archive.agents.values() {
+            let task = agent
+                .task_description
+                .as_deref()
+                .and_then(|d| d.lines().find(|l| !l.trim().is_empty()))
+                .unwrap_or("");
+            let _ = writeln!(md, "- `{}` {} {}", agent.id, agent.display_name(), task);
+        }
+    }
+
+    if !archive.events.is_empty() {
+        let _ = writeln!(md, "\n## Events\n");
+        for event in &archive.events {
+            let agent = event.agent_id.as_ref().map(|a| format!(" [{}]", a)).unwrap_or_default();
+            let text = match &event.kind {
+                TranscriptEventKind::UserMessage => "User message".to_string(),
+                TranscriptEventKind::AssistantMessage { content } => {
+                    format!("Assistant: {}", content.lines().next().unwrap_or(""))
+                }
+                TranscriptEventKind::ToolUse { tool_name, input_summary } => {
+                    format!("**{}** {}", tool_name, input_summary.lines().next().unwrap_or(""))
+                }
+                TranscriptEventKind::ToolResult { tool_name, result_summary, .. } => {
+                    format!("{} → {}", tool_name, result_summary.lines().next().unwrap_or(""))
+                }
+                TranscriptEventKind::ExternalLog { source, line } => format!("{}: {}", source, line),
+                TranscriptEventKind::ConfigChanged { file, change } => format!("`{}` {}", file, change),
+                TranscriptEventKind::StatusUpdate { text, .. } => format!("Status: {}", text),
+                TranscriptEventKind::ApiError { error_kind, message, .. } => {
+                    format!("**API error ({})**: {}", error_kind.label(), message.lines().next().unwrap_or(""))
+                }
+                TranscriptEventKind::Unknown { entry_type } => format!("({})", entry_type),
+            };
+            let _ = writeln!(md, "- `{}`{} {}", event.timestamp.format("%H:%M:%S"), agent, text);
+        }
+    }
+
+    md
+}
+
+/// Path of the advisory lock record guarding writes to an archive.
+/// Pure function: `s1.json` -> `s1.json.lock` (not a `.json` file, so never listed).
+pub fn lock_path(archive_path: &Path) -> PathBuf {
+    let mut name = archive_path.as_os_str().to_owned();
+    name.push(".lock");
+    PathBuf::from(name)
+}
+
 /// Check if auto-save should trigger based on elapsed time.
 /// Pure function: time comparison only.
 ///
@@ -126,8 +399,27 @@ pub fn should_auto_save(last_save: Instant, now: Instant, interval_secs: u64) ->
 // IMPERATIVE SHELL: I/O operations for save/load/list/delete
 // ============================================================================
 
+/// Result of a guarded save.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveReport {
+    pub path: PathBuf,
+    /// Stamp of the file just written; pass it to the next save of the same archive
+    pub stamp: Option<ArchiveStamp>,
+    /// Another writer changed the archive since `expected`; this save replaced
+    /// their version (last writer wins) and the caller should warn the user
+    pub clobbered: bool,
+}
+
+/// Current stamp of an archive file, or None if it doesn't exist.
+/// I/O operation: reads file metadata only.
+pub fn archive_stamp(path: &Path) -> Option<ArchiveStamp> {
+    ArchiveStamp::of(&fs::metadata(path).ok()?)
+}
+
 /// Save session archive to disk.
 /// I/O operation: writes file, creates directories if needed.
+/// Equivalent to `save_session_guarded` with no stamp seen yet; the app saves
+/// archives it has loaded through `save_session_guarded`.
 ///
 /// # Arguments
 /// * `path` - Full path to archive file (including filename)
@@ -135,22 +427,119 @@ pub fn should_auto_save(last_save: Instant, now: Instant, interval_secs: u64) ->
 ///
 /// # Returns
 /// * `Ok(PathBuf)` - Path to saved file
-/// * `Err(SessionError)` - I/O or serialization error
+/// * `Err(SessionError)` - I/O or serialization error, or `Locked` if another instance is writing
 pub fn save_session(path: &Path, archive: &SessionArchive) -> Result<PathBuf, SessionError> {
+    save_session_guarded(path, archive, None).map(|report| report.path)
+}
+
+/// Save session archive under an advisory lock, safe against concurrent instances.
+/// I/O operation: takes the per-archive lock record, writes a temp file and renames
+/// it over the archive so readers never see a half-written file.
+///
+/// # Arguments
+/// * `path` - Full path to archive file (including filename)
+/// * `archive` - Session archive to save
+/// * `expected` - Stamp from this instance's previous save/load (None if never seen)
+///
+/// # Returns
+/// * `Ok(SaveReport)` - Saved; `clobbered` set if someone else wrote the file since `expected`
+/// * `Err(SessionError::Locked)` - Another instance holds the lock; retry on the next tick
+/// * `Err(SessionError)` - I/O or serialization error
+pub fn save_session_guarded(
+    path: &Path,
+    archive: &SessionArchive,
+    expected: Option<ArchiveStamp>,
+) -> Result<SaveReport, SessionError> {
+    let io_err = |p: &Path, e: std::io::Error| SessionError::Io { path: p.display().to_string(), message: e.to_string() };
+
     // Serialize (functional core)
     let content = serialize_session(archive)?;
 
     // Create parent directory if needed (I/O)
     if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)
-            .map_err(|e| SessionError::Io { path: parent.display().to_string(), message: e.to_string() })?;
+        fs::create_dir_all(parent).map_err(|e| io_err(parent, e))?;
     }
 
-    // Write to disk (I/O)
-    fs::write(path, &content)
+    // Hold the lock record for the whole read-check-write; released when `_lock` drops
+    let _lock = lock_archive(path)?;
+
+    let on_disk = archive_stamp(path);
+    let clobbered = on_disk.is_some() && on_disk != expected;
+
+    write_atomic(path, &content)?;
+
+    Ok(SaveReport { path: path.to_path_buf(), stamp: archive_stamp(path), clobbered })
+}
+
+/// Set or clear the notes of an archive on disk, leaving everything else as written.
+/// I/O operation: patches the JSON under the archive lock, so events that are not
+/// loaded in memory (paged archives) and fields from other versions survive.
+///
+/// # Arguments
+/// * `path` - Full path to archive file
+/// * `notes` - New notes, None to remove them
+/// * `expected` - Stamp from this instance's previous save/load
+///
+/// # Returns
+/// * `Ok((SaveReport, EventPaging))` - Saved, and where the events sit in the rewritten file
+/// * `Err(SessionError::Locked)` - Another instance is writing the archive
+/// * `Err(SessionError)` - I/O error, or the file is not a JSON object
+pub fn save_notes(
+    path: &Path,
+    notes: Option<&str>,
+    expected: Option<ArchiveStamp>,
+) -> Result<(SaveReport, EventPaging), SessionError> {
+    let _lock = lock_archive(path)?;
+    let clobbered = archive_stamp(path) != expected;
+    let content = fs::read_to_string(path)
         .map_err(|e| SessionError::Io { path: path.display().to_string(), message: e.to_string() })?;
+    let mut value: serde_json::Value = serde_json::from_str(&content)?;
+    let Some(map) = value.as_object_mut() else {
+        return Err(SessionError::Json(format!("{} is not a session archive", path.display())));
+    };
+    match notes {
+        Some(text) => map.insert("notes".to_string(), serde_json::Value::String(text.to_string())),
+        None => map.remove("notes"),
+    };
+    let content = serde_json::to_string_pretty(&value)?;
+    write_atomic(path, &content)?;
+
+    let report = SaveReport { path: path.to_path_buf(), stamp: archive_stamp(path), clobbered };
+    Ok((report, EventPaging::new(event_spans(&content), content.len() as u64)))
+}
 
-    Ok(path.to_path_buf())
+/// Take the advisory lock record for an archive. The lock is held until the file drops.
+/// I/O operation: creates `{archive}.lock` if needed.
+fn lock_archive(path: &Path) -> Result<fs::File, SessionError> {
+    let lock_file = lock_path(path);
+    let lock = fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_file)
+        .map_err(|e| SessionError::Io { path: lock_file.display().to_string(), message: e.to_string() })?;
+    match lock.try_lock() {
+        Ok(()) => Ok(lock),
+        Err(fs::TryLockError::WouldBlock) => Err(SessionError::Locked { path: path.display().to_string() }),
+        Err(fs::TryLockError::Error(e)) => {
+            Err(SessionError::Io { path: lock_file.display().to_string(), message: e.to_string() })
+        }
+    }
+}
+
+/// Write `content` to a temp file and rename it over `path`, so readers never see
+/// a half-written archive (rename is atomic on the same filesystem).
+fn write_atomic(path: &Path, content: &str) -> Result<(), SessionError> {
+    let io_err = |p: &Path, e: std::io::Error| SessionError::Io { path: p.display().to_string(), message: e.to_string() };
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp = PathBuf::from(tmp_name);
+    fs::write(&tmp, content).map_err(|e| io_err(&tmp, e))?;
+    if let Err(e) = fs::rename(&tmp, path) {
+        let _ = fs::remove_file(&tmp);
+        return Err(io_err(path, e));
+    }
+    Ok(())
 }
 
 /// Load session archive from disk.
@@ -171,6 +560,64 @@ pub fn load_session(path: &Path) -> Result<SessionArchive, SessionError> {
     deserialize_session(&content)
 }
 
+/// Load everything SessionDetail needs for its left panel, deferring events.
+/// I/O operation: reads file; events are located but not deserialized.
+///
+/// # Arguments
+/// * `path` - Full path to archive file
+///
+/// # Returns
+/// * `Ok((SessionArchive, EventPaging, Option<ArchiveStamp>))` - Archive with empty `events`,
+///   where its events sit on disk, and the file's stamp to guard later saves with
+/// * `Err(SessionError)` - I/O or deserialization error
+pub fn load_session_head(path: &Path) -> Result<(SessionArchive, EventPaging, Option<ArchiveStamp>), SessionError> {
+    // Stamp first: a write landing before the read makes the next save warn, never miss it
+    let stamp = archive_stamp(path);
+    let content = fs::read_to_string(path)
+        .map_err(|e| SessionError::Io { path: path.display().to_string(), message: e.to_string() })?;
+
+    let (archive, spans) = deserialize_session_head(&content)?;
+    Ok((archive, EventPaging::new(spans, content.len() as u64), stamp))
+}
+
+/// Load one page of events from an archive on disk.
+/// I/O operation: seeks to the page and reads only its bytes, so paging
+/// through an archive reads it once rather than once per page.
+///
+/// # Arguments
+/// * `path` - Full path to archive file
+/// * `paging` - Event locations taken when the head was loaded
+/// * `range` - Index range into the archive's events array
+///
+/// # Returns
+/// * `Ok(Vec<TranscriptEvent>)` - Events in `range`, oldest first
+/// * `Err(SessionError)` - I/O or deserialization error, or the archive was rewritten since the head load
+pub fn load_event_page(path: &Path, paging: &EventPaging, range: Range<usize>) -> Result<Vec<TranscriptEvent>, SessionError> {
+    let io_err = |e: std::io::Error| SessionError::Io { path: path.display().to_string(), message: e.to_string() };
+    let spans = paging.spans.get(range).unwrap_or_default();
+    let (Some(first), Some(last)) = (spans.first(), spans.last()) else {
+        return Ok(Vec::new());
+    };
+
+    let mut file = fs::File::open(path).map_err(io_err)?;
+    if file.metadata().map_err(io_err)?.len() != paging.file_len {
+        return Err(SessionError::Io { path: path.display().to_string(), message: "archive changed on disk while paging".to_string() });
+    }
+    let mut window = vec![0; last.end - first.start];
+    file.seek(SeekFrom::Start(first.start as u64)).map_err(io_err)?;
+    file.read_exact(&mut window).map_err(io_err)?;
+
+    let relative: Vec<Range<usize>> = spans.iter().map(|s| s.start - first.start..s.end - first.start).collect();
+    deserialize_event_page(&window, &relative)
+}
+
+/// Load a session archive and reduce it to a preview summary.
+/// I/O operation: the full archive is dropped once the preview is built,
+/// so only the small summary stays resident.
+pub fn load_session_preview(path: &Path) -> Result<SessionPreview, SessionError> {
+    load_session(path).map(|archive| build_preview(&archive))
+}
+
 /// List all session archives in directory.
 /// I/O operation: reads directory and parses each archive file.
 /// Returns full archives so callers retain agents/events/task_graph.
@@ -189,43 +636,52 @@ pub fn load_session(path: &Path) -> Result<SessionArchive, SessionError> {
 ///   - Errors for corrupt/unreadable files
 /// * `Err(SessionError)` - I/O error reading directory itself
 pub fn list_sessions(dir: &Path) -> Result<(Vec<SessionArchive>, Vec<SessionError>), SessionError> {
-    if !dir.exists() {
-        return Ok((Vec::new(), Vec::new()));
+    let mut errors = Vec::new();
+    let mut sessions = Vec::new();
+
+    for path in archive_files(dir, &mut errors)? {
+        match load_session(&path) {
+            Ok(archive) => sessions.push(archive),
+            Err(e) => errors.push(e),
+        }
     }
 
-    let entries = fs::read_dir(dir)
-        .map_err(|e| SessionError::Io { path: dir.display().to_string(), message: e.to_string() })?;
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.meta.timestamp));
 
-    let mut sessions = Vec::new();
-    let mut errors = Vec::new();
+    Ok((sessions, errors))
+}
+
+/// Archive `.json` files in `dir` and in its per-project subdirectories
+/// (see `project_archive_dir`). Dot-directories such as `.trash` are skipped.
+/// Missing `dir` yields no files; unreadable entries are pushed to `errors`.
+fn archive_files(dir: &Path, errors: &mut Vec<SessionError>) -> Result<Vec<PathBuf>, SessionError> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let io_err = |path: &Path, e: std::io::Error| SessionError::Io { path: path.display().to_string(), message: e.to_string() };
+    let is_json = |path: &Path| path.extension().and_then(|s| s.to_str()) == Some("json");
 
+    let entries = fs::read_dir(dir).map_err(|e| io_err(dir, e))?;
+    let mut files = Vec::new();
     for entry in entries {
-        let entry = match entry {
-            Ok(e) => e,
+        let path = match entry {
+            Ok(e) => e.path(),
             Err(e) => {
-                errors.push(SessionError::Io {
-                    path: dir.display().to_string(),
-                    message: e.to_string(),
-                });
+                errors.push(io_err(dir, e));
                 continue;
             }
         };
-
-        let path = entry.path();
-
-        if path.extension().and_then(|s| s.to_str()) != Some("json") {
-            continue;
-        }
-
-        match load_session(&path) {
-            Ok(archive) => sessions.push(archive),
-            Err(e) => errors.push(e),
+        let hidden = path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with('.'));
+        if is_json(&path) {
+            files.push(path);
+        } else if path.is_dir() && !hidden {
+            match fs::read_dir(&path) {
+                Ok(sub) => files.extend(sub.flatten().map(|e| e.path()).filter(|p| is_json(p))),
+                Err(e) => errors.push(io_err(&path, e)),
+            }
         }
     }
-
-    sessions.sort_by(|a, b| b.meta.timestamp.cmp(&a.meta.timestamp));
-
-    Ok((sessions, errors))
+    Ok(files)
 }
 
 /// Helper for deserializing only the `meta` field from a session archive JSON.
@@ -245,35 +701,95 @@ struct MetaOnly {
 /// * `Err(SessionError)` - I/O error reading directory itself
 #[allow(clippy::type_complexity)]
 pub fn list_session_metas(dir: &Path) -> Result<(Vec<(PathBuf, SessionMeta)>, Vec<SessionError>), SessionError> {
-    if !dir.exists() {
-        return Ok((Vec::new(), Vec::new()));
-    }
+    let mut errors = Vec::new();
+    let files = archive_files(dir, &mut errors)?;
+    let (mut metas, read_errors) = load_session_metas(&files);
+    errors.extend(read_errors);
 
-    let entries = fs::read_dir(dir)
-        .map_err(|e| SessionError::Io { path: dir.display().to_string(), message: e.to_string() })?;
+    metas.sort_by_key(|m| std::cmp::Reverse(m.1.timestamp));
 
-    let mut metas = Vec::new();
+    Ok((metas, errors))
+}
+
+/// List archive files without reading them, most recently modified first.
+/// Cheap enough to run over thousands of archives; metas are then read a
+/// page at a time with `load_session_metas`.
+///
+/// # Returns
+/// * `Ok((Vec<PathBuf>, Vec<SessionError>))` - Archive paths and errors for unreadable entries
+/// * `Err(SessionError)` - I/O error reading directory itself
+pub fn list_archive_paths(dir: &Path) -> Result<(Vec<PathBuf>, Vec<SessionError>), SessionError> {
     let mut errors = Vec::new();
+    let files = archive_files(dir, &mut errors)?;
+    let mut dated: Vec<(Option<SystemTime>, PathBuf)> =
+        files.into_iter().map(|path| (fs::metadata(&path).and_then(|m| m.modified()).ok(), path)).collect();
+    dated.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    Ok((dated.into_iter().map(|(_, path)| path).collect(), errors))
+}
 
-    for entry in entries {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(e) => {
-                errors.push(SessionError::Io {
-                    path: dir.display().to_string(),
-                    message: e.to_string(),
-                });
-                continue;
-            }
-        };
+/// Read the metas of `paths`, in order.
+/// I/O operation: reads each file, deserializing only its `meta` field.
+///
+/// # Returns
+/// Tuple of (successful metas, errors for corrupt/unreadable files)
+#[allow(clippy::type_complexity)]
+pub fn load_session_metas(paths: &[PathBuf]) -> (Vec<(PathBuf, SessionMeta)>, Vec<SessionError>) {
+    load_session_metas_streamed(paths, |_| {})
+}
 
-        let path = entry.path();
+/// Like `load_session_metas`, but `META_READERS` threads read `META_CHUNK`
+/// archives at a time, and `on_chunk` gets each chunk's metas as soon as it
+/// is read (in whatever order chunks finish).
+/// I/O operation: reads each file on a scoped reader thread.
+///
+/// # Returns
+/// Tuple of (successful metas in the order of `paths`, errors for corrupt/unreadable files)
+#[allow(clippy::type_complexity)]
+pub fn load_session_metas_streamed(
+    paths: &[PathBuf],
+    on_chunk: impl Fn(Vec<(PathBuf, SessionMeta)>) + Sync,
+) -> (Vec<(PathBuf, SessionMeta)>, Vec<SessionError>) {
+    let chunks: Vec<&[PathBuf]> = paths.chunks(META_CHUNK).collect();
+    let next = std::sync::atomic::AtomicUsize::new(0);
+    let mut read: Vec<(usize, (Vec<(PathBuf, SessionMeta)>, Vec<SessionError>))> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..META_READERS.min(chunks.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        let Some(chunk) = chunks.get(index) else {
+                            return done;
+                        };
+                        let (metas, errors) = read_session_metas(chunk);
+                        if !metas.is_empty() {
+                            on_chunk(metas.clone());
+                        }
+                        done.push((index, (metas, errors)));
+                    }
+                })
+            })
+            .collect();
+        // A panicking reader loses only its chunks
+        workers.into_iter().flat_map(|worker| worker.join().unwrap_or_default()).collect()
+    });
+
+    read.sort_by_key(|(index, _)| *index);
+    read.into_iter().fold((Vec::new(), Vec::new()), |(mut metas, mut errors), (_, (m, e))| {
+        metas.extend(m);
+        errors.extend(e);
+        (metas, errors)
+    })
+}
 
-        if path.extension().and_then(|s| s.to_str()) != Some("json") {
-            continue;
-        }
+/// Read the metas of `paths` one after another, in order.
+#[allow(clippy::type_complexity)]
+fn read_session_metas(paths: &[PathBuf]) -> (Vec<(PathBuf, SessionMeta)>, Vec<SessionError>) {
+    let mut errors = Vec::new();
+    let mut metas = Vec::new();
 
-        let content = match fs::read_to_string(&path) {
+    for path in paths {
+        let content = match fs::read_to_string(path) {
             Ok(c) => c,
             Err(e) => {
                 errors.push(SessionError::Io {
@@ -285,14 +801,12 @@ pub fn list_session_metas(dir: &Path) -> Result<(Vec<(PathBuf, SessionMeta)>, Ve
         };
 
         match serde_json::from_str::<MetaOnly>(&content) {
-            Ok(meta_only) => metas.push((path, meta_only.meta)),
+            Ok(meta_only) => metas.push((path.clone(), meta_only.meta)),
             Err(e) => errors.push(SessionError::from(e)),
         }
     }
 
-    metas.sort_by(|a, b| b.1.timestamp.cmp(&a.1.timestamp));
-
-    Ok((metas, errors))
+    (metas, errors)
 }
 
 /// Delete session archive file.
@@ -306,7 +820,10 @@ pub fn list_session_metas(dir: &Path) -> Result<(Vec<(PathBuf, SessionMeta)>, Ve
 /// * `Err(SessionError)` - I/O error
 pub fn delete_session(path: &Path) -> Result<(), SessionError> {
     fs::remove_file(path)
-        .map_err(|e| SessionError::Io { path: path.display().to_string(), message: e.to_string() })
+        .map_err(|e| SessionError::Io { path: path.display().to_string(), message: e.to_string() })?;
+    // Stale lock record is harmless but would otherwise accumulate
+    let _ = fs::remove_file(lock_path(path));
+    Ok(())
 }
 
 /// Load deleted session IDs from tombstone file.
@@ -334,6 +851,186 @@ pub fn mark_deleted(archive_dir: &Path, session_id: &str) -> Result<(), SessionE
         .map_err(|e| SessionError::Io { path: path.display().to_string(), message: e.to_string() })
 }
 
+/// Remove a session ID from the tombstone file (undo of `mark_deleted`).
+/// Missing file is not an error.
+pub fn unmark_deleted(archive_dir: &Path, session_id: &str) -> Result<(), SessionError> {
+    let path = archive_dir.join(".deleted");
+    let Ok(content) = fs::read_to_string(&path) else {
+        return Ok(());
+    };
+    let kept: String = content
+        .lines()
+        .filter(|l| !l.is_empty() && *l != session_id)
+        .map(|l| format!("{}\n", l))
+        .collect();
+    fs::write(&path, kept)
+        .map_err(|e| SessionError::Io { path: path.display().to_string(), message: e.to_string() })
+}
+
+/// Subdirectory of the archive dir holding deleted sessions until purged.
+pub const TRASH_DIR: &str = ".trash";
+
+/// Parse the deletion time encoded in a trashed file name (`{unix_secs}-{name}`).
+/// Pure function: returns None for names not produced by `trash_session`.
+pub fn trashed_at(path: &Path) -> Option<chrono::DateTime<chrono::Utc>> {
+    let name = path.file_name()?.to_str()?;
+    let (secs, _) = name.split_once('-')?;
+    chrono::DateTime::from_timestamp(secs.parse().ok()?, 0)
+}
+
+/// Move a session archive into the `.trash` subdirectory beside it instead of deleting it.
+/// I/O operation: the file name is prefixed with the deletion time so `purge_trash`
+/// can age it (rename keeps the original mtime).
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Path of the trashed file (pass to `restore_session` to undo)
+/// * `Err(SessionError)` - I/O error
+pub fn trash_session(path: &Path, now: chrono::DateTime<chrono::Utc>) -> Result<PathBuf, SessionError> {
+    let io_err = |p: &Path, e: std::io::Error| SessionError::Io { path: p.display().to_string(), message: e.to_string() };
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+        return Err(SessionError::Io { path: path.display().to_string(), message: "not a file path".to_string() });
+    };
+    let trash_dir = dir.join(TRASH_DIR);
+    fs::create_dir_all(&trash_dir).map_err(|e| io_err(&trash_dir, e))?;
+    let trashed = trash_dir.join(format!("{}-{}", now.timestamp(), name.to_string_lossy()));
+    fs::rename(path, &trashed).map_err(|e| io_err(path, e))?;
+    // Stale lock record is harmless but would otherwise accumulate
+    let _ = fs::remove_file(lock_path(path));
+    Ok(trashed)
+}
+
+/// Move a trashed session archive back to its original path.
+/// I/O operation: fails if the original path has been reused in the meantime.
+pub fn restore_session(trashed: &Path, original: &Path) -> Result<(), SessionError> {
+    if original.exists() {
+        return Err(SessionError::Io { path: original.display().to_string(), message: "already exists".to_string() });
+    }
+    fs::rename(trashed, original)
+        .map_err(|e| SessionError::Io { path: trashed.display().to_string(), message: e.to_string() })
+}
+
+/// Permanently delete trashed archives older than `grace`, in the archive root
+/// and every per-project subdirectory.
+/// I/O operation: files with unrecognised names are left alone.
+///
+/// # Returns
+/// * `Ok(usize)` - Number of files removed
+/// * `Err(SessionError)` - I/O error reading the archive directory
+pub fn purge_trash(archive_dir: &Path, grace: chrono::Duration, now: chrono::DateTime<chrono::Utc>) -> Result<usize, SessionError> {
+    if !archive_dir.exists() {
+        return Ok(0);
+    }
+    let entries = fs::read_dir(archive_dir)
+        .map_err(|e| SessionError::Io { path: archive_dir.display().to_string(), message: e.to_string() })?;
+    let project_dirs = entries.flatten().map(|e| e.path()).filter(|p| p.is_dir());
+
+    let mut removed = 0;
+    for dir in std::iter::once(archive_dir.to_path_buf()).chain(project_dirs) {
+        let Ok(trashed) = fs::read_dir(dir.join(TRASH_DIR)) else {
+            continue;
+        };
+        for path in trashed.flatten().map(|e| e.path()) {
+            if trashed_at(&path).is_some_and(|at| now - at >= grace) && fs::remove_file(&path).is_ok() {
+                removed += 1;
+            }
+        }
+    }
+    Ok(removed)
+}
+
+/// Move archives written before per-project namespacing (flat in the archive root)
+/// into their project's subdirectory.
+/// I/O operation: archives with no project, an existing namespaced copy, or whose
+/// lock is held by another instance are left where they are.
+///
+/// # Returns
+/// * `Ok(usize)` - Number of archives moved
+/// * `Err(SessionError)` - I/O error reading the archive directory
+pub fn migrate_flat_archives(archive_dir: &Path) -> Result<usize, SessionError> {
+    if !archive_dir.exists() {
+        return Ok(0);
+    }
+    let io_err = |path: &Path, e: std::io::Error| SessionError::Io { path: path.display().to_string(), message: e.to_string() };
+    let entries = fs::read_dir(archive_dir).map_err(|e| io_err(archive_dir, e))?;
+
+    let mut moved = 0;
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(meta) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<MetaOnly>(&content).ok())
+            .map(|m| m.meta)
+        else {
+            continue;
+        };
+        let target = archive_path(archive_dir, &meta);
+        if target == path || target.exists() {
+            continue;
+        }
+        // Another instance mid-save keeps its file; try again next start
+        let lock_file = lock_path(&path);
+        let lock = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_file)
+            .map_err(|e| io_err(&lock_file, e))?;
+        if lock.try_lock().is_err() {
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| io_err(parent, e))?;
+        }
+        fs::rename(&path, &target).map_err(|e| io_err(&path, e))?;
+        drop(lock);
+        let _ = fs::remove_file(&lock_file);
+        moved += 1;
+    }
+    Ok(moved)
+}
+
+/// Write a Markdown export of a session into `dir` (created if missing).
+/// I/O operation: file name is `{session_id}.md`, overwriting any earlier export.
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Path of the written file
+/// * `Err(SessionError)` - I/O error
+pub fn write_markdown_export(dir: &Path, archive: &SessionArchive) -> Result<PathBuf, SessionError> {
+    let io_err = |path: &Path, e: std::io::Error| SessionError::Io { path: path.display().to_string(), message: e.to_string() };
+    fs::create_dir_all(dir).map_err(|e| io_err(dir, e))?;
+    let path = dir.join(format!("{}.md", archive.meta.id));
+    fs::write(&path, export_markdown(archive)).map_err(|e| io_err(&path, e))?;
+    Ok(path)
+}
+
+/// Append events to the per-session overflow logs in `dir` (created if missing).
+/// I/O operation: one JSON event per line in `{session_id}.jsonl`; events
+/// without a session go to `unknown.jsonl`.
+pub fn append_overflow(dir: &Path, events: &[TranscriptEvent]) -> Result<(), SessionError> {
+    use std::io::Write;
+    let io_err = |path: &Path, e: std::io::Error| SessionError::Io { path: path.display().to_string(), message: e.to_string() };
+    fs::create_dir_all(dir).map_err(|e| io_err(dir, e))?;
+
+    let mut by_session: BTreeMap<&str, String> = BTreeMap::new();
+    for event in events {
+        let lines = by_session.entry(event.session_id.as_ref().map_or("unknown", |sid| sid.as_str())).or_default();
+        lines.push_str(&serde_json::to_string(event)?);
+        lines.push('\n');
+    }
+    for (session_id, lines) in by_session {
+        let path = dir.join(format!("{}.jsonl", session_id));
+        let mut f = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| io_err(&path, e))?;
+        f.write_all(lines.as_bytes()).map_err(|e| io_err(&path, e))?;
+    }
+    Ok(())
+}
+
 /// Auto-save tick: save session if interval elapsed.
 /// Combines pure time check with I/O save operation.
 ///
@@ -342,6 +1039,7 @@ pub fn mark_deleted(archive_dir: &Path, session_id: &str) -> Result<(), SessionE
 /// * `archive` - Session archive to save
 /// * `last_save` - Instant of last save operation
 /// * `interval_secs` - Auto-save interval in seconds (typically 30)
+/// * `clock` - Where the current instant is read from
 ///
 /// # Returns
 /// * `Ok(Some(Instant))` - New save timestamp if save occurred
@@ -352,8 +1050,9 @@ pub fn auto_save_tick(
     archive: &SessionArchive,
     last_save: Instant,
     interval_secs: u64,
+    clock: &dyn Clock,
 ) -> Result<Option<Instant>, SessionError> {
-    let now = Instant::now();
+    let now = clock.instant();
 
     if should_auto_save(last_save, now, interval_secs) {
         // Save and return new timestamp
@@ -367,7 +1066,7 @@ pub fn auto_save_tick(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{SessionStatus, TaskGraph, TranscriptEvent, TranscriptEventKind};
+    use crate::model::{SessionStatus, TaskGraph};
     use chrono::Utc;
     use std::collections::{BTreeMap, VecDeque};
     use std::time::Duration;
@@ -453,6 +1152,202 @@ mod tests {
         assert!(archive.events.is_empty());
     }
 
+    #[test]
+    fn build_preview_uses_archive_events_and_agents() {
+        let meta = SessionMeta::new("s1", Utc::now(), "/proj".to_string());
+        let mut agents = BTreeMap::new();
+        agents.insert(AgentId::new("a1"), Agent::new("a1", Utc::now()));
+        let events = vec![TranscriptEvent::new(
+            Utc::now(),
+            TranscriptEventKind::ToolUse {
+                tool_name: "Bash".into(),
+                input_summary: "cargo test".to_string(),
+            },
+        )];
+        let archive = SessionArchive::new(meta).with_events(events).with_agents(agents);
+
+        let preview = build_preview(&archive);
+
+        assert_eq!(preview.event_count, 1);
+        assert_eq!(preview.agent_count, 1);
+        assert_eq!(preview.tool_counts, vec![("Bash".into(), 1)]);
+    }
+
+    #[test]
+    fn export_markdown_includes_goal_tasks_agents_and_events() {
+        use crate::model::{Task, Wave};
+
+        let meta = SessionMeta::new("s1", Utc::now(), "/proj".to_string())
+            .with_status(SessionStatus::Completed)
+            .with_goal("Add a login page".to_string());
+        let graph = TaskGraph::new(vec![Wave::new(
+            1,
+            vec![
+                Task::new("T1", "Build form".into(), TaskStatus::Completed),
+                Task::new("T2", "Wire OAuth".into(), TaskStatus::Running),
+            ],
+        )]);
+        let mut agents = BTreeMap::new();
+        agents.insert(AgentId::new("a1"), Agent::new("a1", Utc::now()));
+        let events = vec![TranscriptEvent::new(
+            Utc::now(),
+            TranscriptEventKind::ToolUse {
+                tool_name: "Bash".into(),
+                input_summary: "cargo test\n--all".to_string(),
+            },
+        )
+        .with_agent("a1")];
+        let archive = SessionArchive::new(meta)
+            .with_task_graph(graph)
+            .with_agents(agents)
+            .with_events(events);
+
+        let md = export_markdown(&archive);
+
+        assert!(md.starts_with("# Session s1\n"));
+        assert!(md.contains("> Add a login page"));
+        assert!(md.contains("- **Status:** completed"));
+        assert!(md.contains("### Wave 1"));
+        assert!(md.contains("- [x] **T1** Build form _(completed)_"));
+        assert!(md.contains("- [ ] **T2** Wire OAuth _(running)_"));
+        assert!(md.contains("## Agents"));
+        assert!(md.contains("[a1] **Bash** cargo test\n"), "only the first line of a summary is kept");
+    }
+
+    #[test]
+    fn export_markdown_includes_notes() {
+        let mut archive = SessionArchive::new(SessionMeta::new("s1", Utc::now(), "/proj".to_string()));
+        archive.notes = Some("## What went wrong\nflaky test\n".to_string());
+        let md = export_markdown(&archive);
+        assert!(md.contains("\n## Notes\n\n## What went wrong\nflaky test\n"));
+
+        archive.notes = Some("  \n".to_string());
+        assert!(!export_markdown(&archive).contains("## Notes"));
+    }
+
+    #[test]
+    fn save_notes_patches_archive_and_keeps_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.json");
+        let events = vec![TranscriptEvent::new(Utc::now(), TranscriptEventKind::UserMessage)];
+        let archive = SessionArchive::new(SessionMeta::new("s1", Utc::now(), "/proj".to_string())).with_events(events);
+        let saved = save_session_guarded(&path, &archive, None).unwrap();
+
+        let (report, moved) = save_notes(&path, Some("root cause: stale cache"), saved.stamp).unwrap();
+        assert!(!report.clobbered);
+        let (head, paging, stamp) = load_session_head(&path).unwrap();
+        assert_eq!(head.notes.as_deref(), Some("root cause: stale cache"));
+        assert_eq!(paging, moved);
+        assert_eq!(stamp, report.stamp);
+
+        save_notes(&path, None, stamp).unwrap();
+        assert_eq!(load_session(&path).unwrap().notes, None);
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("notes"));
+    }
+
+    #[test]
+    fn write_markdown_export_creates_dir_and_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("exports");
+        let archive = SessionArchive::new(SessionMeta::new("s9", Utc::now(), "/proj".to_string()));
+
+        let path = write_markdown_export(&dir, &archive).unwrap();
+
+        assert_eq!(path, dir.join("s9.md"));
+        assert!(fs::read_to_string(&path).unwrap().starts_with("# Session s9"));
+    }
+
+    fn paged_archive_json(event_count: usize) -> String {
+        let meta = SessionMeta::new("s1", Utc::now(), "/proj".to_string());
+        let mut agents = BTreeMap::new();
+        agents.insert(AgentId::new("a1"), Agent::new("a1", Utc::now()));
+        let events = (0..event_count)
+            .map(|i| {
+                TranscriptEvent::new(
+                    Utc::now(),
+                    TranscriptEventKind::AssistantMessage { content: format!("msg {i}") },
+                )
+            })
+            .collect();
+        serialize_session(&SessionArchive::new(meta).with_events(events).with_agents(agents)).unwrap()
+    }
+
+    #[test]
+    fn deserialize_session_head_locates_events_without_loading_them() {
+        let json = paged_archive_json(7);
+
+        let (head, spans) = deserialize_session_head(&json).unwrap();
+
+        assert_eq!(spans.len(), 7);
+        assert!(spans.iter().all(|span| json[span.clone()].starts_with('{') && json[span.clone()].ends_with('}')));
+        assert!(head.events.is_empty());
+        assert_eq!(head.agents.len(), 1);
+        assert_eq!(head.meta.id.as_str(), "s1");
+        assert_eq!(head.version, SessionArchive::VERSION);
+    }
+
+    #[test]
+    fn deserialize_event_page_returns_requested_slice() {
+        let json = paged_archive_json(7);
+        let full = deserialize_session(&json).unwrap();
+
+        let spans = event_spans(&json);
+
+        let page = deserialize_event_page(json.as_bytes(), &spans[4..7]).unwrap();
+        assert_eq!(page, full.events[4..7]);
+        assert!(deserialize_event_page(json.as_bytes(), &[]).unwrap().is_empty());
+        let past_end = json.len()..json.len() + 1;
+        assert!(deserialize_event_page(json.as_bytes(), std::slice::from_ref(&past_end)).is_err());
+    }
+
+    #[test]
+    fn event_spans_skip_brackets_in_strings_and_nested_events_keys() {
+        let json = r#"{"notes": "events: [{]", "agents": {"a": {"events": [{"x": 1}]}}, "events": [{"s": "}\"]"}, [1], 2], "z": []}"#;
+
+        let spans = event_spans(json);
+
+        let elements: Vec<&str> = spans.iter().map(|span| &json[span.clone()]).collect();
+        assert_eq!(elements, [r#"{"s": "}\"]"}"#, "[1]"]);
+    }
+
+    #[test]
+    fn load_event_page_reads_only_the_page_and_rejects_a_rewritten_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.json");
+        let json = paged_archive_json(1200);
+        std::fs::write(&path, &json).unwrap();
+        let full = deserialize_session(&json).unwrap();
+
+        let (_, paging, stamp) = load_session_head(&path).unwrap();
+        assert_eq!(paging.total(), 1200);
+        let range = paging.next_page(EVENT_PAGE_SIZE);
+        assert_eq!(load_event_page(&path, &paging, range.clone()).unwrap(), full.events[range]);
+
+        save_notes(&path, Some("rewritten"), stamp).unwrap();
+        let err = load_event_page(&path, &paging, 0..10).unwrap_err();
+        assert!(err.to_string().contains("changed on disk"));
+    }
+
+    #[test]
+    fn deserialize_event_page_drops_old_format_events() {
+        let json = r#"{
+            "meta": {
+                "id": "s-old",
+                "timestamp": "2026-01-01T00:00:00Z",
+                "status": "completed",
+                "agent_count": 0,
+                "task_count": 0,
+                "event_count": 1,
+                "project_path": "/p"
+            },
+            "events": [{"timestamp": "2026-01-01T00:00:01Z", "event": "session_start"}]
+        }"#;
+
+        let (_, spans) = deserialize_session_head(json).unwrap();
+        assert_eq!(spans.len(), 1);
+        assert!(deserialize_event_page(json.as_bytes(), &spans).unwrap().is_empty());
+    }
+
     #[test]
     fn should_auto_save_triggers_after_interval() {
         let start = Instant::now();
@@ -656,4 +1551,181 @@ mod tests {
         assert!(metas.is_empty());
         assert!(errors.is_empty());
     }
+
+    #[test]
+    fn trash_and_restore_round_trip() {
+        use tempfile::TempDir;
+
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("s1.json");
+        fs::write(&path, "{}").unwrap();
+        fs::write(lock_path(&path), "").unwrap();
+        let now = chrono::Utc::now();
+
+        let trashed = trash_session(&path, now).unwrap();
+        assert!(!path.exists());
+        assert!(!lock_path(&path).exists());
+        assert!(trashed.starts_with(temp.path().join(TRASH_DIR)));
+        assert_eq!(trashed_at(&trashed).unwrap().timestamp(), now.timestamp());
+
+        // Trash dir is not picked up as an archive
+        let (metas, errors) = list_session_metas(temp.path()).unwrap();
+        assert!(metas.is_empty() && errors.is_empty());
+
+        restore_session(&trashed, &path).unwrap();
+        assert!(path.exists());
+        assert!(!trashed.exists());
+    }
+
+    #[test]
+    fn purge_trash_removes_only_expired_files() {
+        use tempfile::TempDir;
+
+        let temp = TempDir::new().unwrap();
+        let now = chrono::Utc::now();
+        for (name, age_days) in [("old.json", 10), ("new.json", 1)] {
+            let path = temp.path().join(name);
+            fs::write(&path, "{}").unwrap();
+            trash_session(&path, now - chrono::Duration::days(age_days)).unwrap();
+        }
+        fs::write(temp.path().join(TRASH_DIR).join("stray.txt"), "").unwrap();
+
+        assert_eq!(purge_trash(temp.path(), chrono::Duration::days(7), now).unwrap(), 1);
+        let mut left: Vec<_> = fs::read_dir(temp.path().join(TRASH_DIR))
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        left.sort();
+        assert_eq!(left.len(), 2);
+        assert!(left[0].ends_with("-new.json"));
+        assert_eq!(left[1], "stray.txt");
+    }
+
+    #[test]
+    fn unmark_deleted_removes_only_that_id() {
+        use tempfile::TempDir;
+
+        let temp = TempDir::new().unwrap();
+        mark_deleted(temp.path(), "a").unwrap();
+        mark_deleted(temp.path(), "b").unwrap();
+        unmark_deleted(temp.path(), "a").unwrap();
+
+        let ids = load_deleted_ids(temp.path());
+        assert_eq!(ids, HashSet::from(["b".to_string()]));
+    }
+
+    #[test]
+    fn archive_path_is_namespaced_by_project_hash() {
+        let root = Path::new("/data/sessions");
+        let meta = SessionMeta::new("s1", Utc::now(), "/home/u/app".to_string());
+        assert_eq!(archive_path(root, &meta), PathBuf::from("/data/sessions/-home-u-app/s1.json"));
+
+        let legacy = SessionMeta::new("s2", Utc::now(), String::new());
+        assert_eq!(archive_path(root, &legacy), PathBuf::from("/data/sessions/s2.json"));
+    }
+
+    #[test]
+    fn list_session_metas_includes_project_subdirs_but_not_trash() {
+        use tempfile::TempDir;
+
+        let temp = TempDir::new().unwrap();
+        let flat = SessionArchive::new(SessionMeta::new("flat", Utc::now(), String::new()));
+        let namespaced = SessionArchive::new(SessionMeta::new("ns", Utc::now(), "/home/u/app".to_string()));
+        save_session(&archive_path(temp.path(), &flat.meta), &flat).unwrap();
+        let ns_path = archive_path(temp.path(), &namespaced.meta);
+        save_session(&ns_path, &namespaced).unwrap();
+        trash_session(&ns_path, Utc::now()).unwrap();
+        save_session(&ns_path, &namespaced).unwrap();
+
+        let (metas, errors) = list_session_metas(temp.path()).unwrap();
+        assert!(errors.is_empty(), "{errors:?}");
+        let mut ids: Vec<_> = metas.iter().map(|(_, m)| m.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["flat", "ns"]);
+    }
+
+    #[test]
+    fn archive_paths_list_newest_first_and_metas_load_by_page() {
+        use std::time::Duration;
+        use tempfile::TempDir;
+
+        let temp = TempDir::new().unwrap();
+        let base = SystemTime::now() - Duration::from_secs(3600);
+        for (i, id) in ["old", "mid", "new"].into_iter().enumerate() {
+            let path = temp.path().join(format!("{id}.json"));
+            save_session(&path, &SessionArchive::new(SessionMeta::new(id, Utc::now(), String::new()))).unwrap();
+            let file = fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(base + Duration::from_secs(60 * i as u64)).unwrap();
+        }
+        fs::write(temp.path().join("corrupt.json"), "{ not json").unwrap();
+
+        let (paths, errors) = list_archive_paths(temp.path()).unwrap();
+        assert!(errors.is_empty(), "{errors:?}");
+        let names: Vec<_> = paths.iter().map(|p| p.file_name().unwrap().to_str().unwrap()).collect();
+        assert_eq!(names, ["corrupt.json", "new.json", "mid.json", "old.json"]);
+
+        let (metas, errors) = load_session_metas(&paths[..2]);
+        let ids: Vec<_> = metas.iter().map(|(_, m)| m.id.as_str()).collect();
+        assert_eq!(ids, ["new"]);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn streamed_meta_reads_report_chunks_and_keep_path_order() {
+        use tempfile::TempDir;
+
+        let temp = TempDir::new().unwrap();
+        let mut paths: Vec<PathBuf> = (0..META_CHUNK * 2 + 3)
+            .map(|i| {
+                let path = temp.path().join(format!("s{i:03}.json"));
+                save_session(&path, &SessionArchive::new(SessionMeta::new(format!("s{i:03}"), Utc::now(), "/p".into()))).unwrap();
+                path
+            })
+            .collect();
+        paths.insert(1, temp.path().join("missing.json"));
+
+        let reported = std::sync::Mutex::new(Vec::new());
+        let (metas, errors) = load_session_metas_streamed(&paths, |chunk| reported.lock().unwrap().push(chunk.len()));
+
+        assert_eq!(errors.len(), 1);
+        let ids: Vec<_> = metas.iter().map(|(_, m)| m.id.as_str().to_string()).collect();
+        let expected: Vec<_> = (0..META_CHUNK * 2 + 3).map(|i| format!("s{i:03}")).collect();
+        assert_eq!(ids, expected);
+        let mut reported = reported.into_inner().unwrap();
+        reported.sort();
+        assert_eq!(reported, [4, META_CHUNK - 1, META_CHUNK]);
+    }
+
+    #[test]
+    fn migrate_flat_archives_moves_into_project_dir() {
+        use tempfile::TempDir;
+
+        let temp = TempDir::new().unwrap();
+        let meta = SessionMeta::new("s1", Utc::now(), "/home/u/app".to_string());
+        let flat = temp.path().join(generate_filename(&meta));
+        save_session(&flat, &SessionArchive::new(meta.clone())).unwrap();
+        let unknown = SessionMeta::new("s2", Utc::now(), String::new());
+        save_session(&temp.path().join("s2.json"), &SessionArchive::new(unknown)).unwrap();
+
+        assert_eq!(migrate_flat_archives(temp.path()).unwrap(), 1);
+        assert!(!flat.exists());
+        assert!(!lock_path(&flat).exists());
+        assert!(archive_path(temp.path(), &meta).exists());
+        assert!(temp.path().join("s2.json").exists(), "archives without a project stay put");
+        assert_eq!(migrate_flat_archives(temp.path()).unwrap(), 0);
+    }
+
+    #[test]
+    fn purge_trash_covers_project_subdirs() {
+        use tempfile::TempDir;
+
+        let temp = TempDir::new().unwrap();
+        let meta = SessionMeta::new("s1", Utc::now(), "/home/u/app".to_string());
+        let path = archive_path(temp.path(), &meta);
+        save_session(&path, &SessionArchive::new(meta)).unwrap();
+        let now = Utc::now();
+        trash_session(&path, now - chrono::Duration::days(30)).unwrap();
+
+        assert_eq!(purge_trash(temp.path(), chrono::Duration::days(7), now).unwrap(), 1);
+    }
 }
@@ -0,0 +1,152 @@
+//! Headless snapshots: `loom-tui snapshot [project_root] [--out PATH]`.
+//!
+//! CI jobs have no terminal to run the dashboard in, but a build artifact
+//! showing where the orchestration stood is still useful. The snapshot starts
+//! the file watcher, feeds its initial replay (transcripts, task graph,
+//! archived sessions) through `update` exactly as the live loop would, renders
+//! the dashboard once into a `SNAPSHOT_WIDTH` x `SNAPSHOT_HEIGHT` frame and
+//! writes it out as plain text.
+
+use std::path::Path;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use ratatui::backend::TestBackend;
+use ratatui::text::Span;
+use ratatui::Terminal;
+
+use crate::app::{update, AppState};
+use crate::event::AppEvent;
+use crate::view::render;
+
+/// Columns of the rendered frame.
+pub const SNAPSHOT_WIDTH: u16 = 160;
+
+/// Rows of the rendered frame.
+pub const SNAPSHOT_HEIGHT: u16 = 48;
+
+/// Quiet time after the transcript replay before the state counts as loaded:
+/// the task graph and archive sources report from their own threads, on the
+/// same 200ms poll.
+pub const SETTLE: Duration = Duration::from_millis(600);
+
+/// Longest wait for the replay to finish.
+pub const REPLAY_LIMIT: Duration = Duration::from_secs(30);
+
+/// Feed the watcher's initial replay into `state`: every event up to the
+/// transcripts' `ReplayComplete`, then whatever else arrives until the sources
+/// have been quiet for `settle`.
+///
+/// # Imperative Shell
+/// Blocks on `rx` for at most `limit`.
+///
+/// # Returns
+/// False when the replay did not finish within `limit` (or the sources hung
+/// up first); `state` then holds what had arrived.
+pub fn load(state: &mut AppState, rx: &Receiver<AppEvent>, settle: Duration, limit: Duration) -> bool {
+    let deadline = Instant::now() + limit;
+    let mut replayed = false;
+    loop {
+        let left = deadline.saturating_duration_since(Instant::now());
+        let wait = if replayed { settle.min(left) } else { left };
+        match rx.recv_timeout(wait) {
+            Ok(event) => {
+                replayed |= matches!(event, AppEvent::ReplayComplete);
+                update(state, event);
+            }
+            Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => return replayed,
+        }
+        if left.is_zero() {
+            return replayed;
+        }
+    }
+}
+
+/// The whole UI for `state` in a `width` x `height` frame, as text: one line
+/// per row, trailing blanks trimmed.
+pub fn render_text(state: &AppState, width: u16, height: u16) -> String {
+    let Ok(mut terminal) = Terminal::new(TestBackend::new(width, height));
+    let Ok(_) = terminal.draw(|frame| render(state, frame));
+    let buffer = terminal.backend().buffer();
+    let mut text = String::new();
+    for y in 0..height {
+        let mut row = String::new();
+        // A wide symbol covers the cells after it
+        let mut covered = 0;
+        for x in 0..width {
+            let symbol = buffer[(x, y)].symbol();
+            if covered == 0 {
+                row.push_str(symbol);
+            }
+            covered = covered.max(Span::raw(symbol).width()).saturating_sub(1);
+        }
+        text.push_str(row.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// Write `text` to `out`, or to standard output without one.
+/// I/O: creates the file's directory when missing.
+pub fn write(out: Option<&Path>, text: &str) -> std::io::Result<()> {
+    match out {
+        Some(path) => {
+            if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(path, text)
+        }
+        None => {
+            use std::io::Write;
+            std::io::stdout().write_all(text.as_bytes())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Task, TaskGraph, TaskStatus, Wave};
+    use std::sync::mpsc;
+
+    fn graph() -> TaskGraph {
+        TaskGraph::new(vec![Wave::new(1, vec![Task::new("T1", "Wire the snapshot command".into(), TaskStatus::Running)])])
+    }
+
+    #[test]
+    fn load_takes_the_replay_and_what_settles_after_it() {
+        let (tx, rx) = mpsc::channel();
+        tx.send(AppEvent::ReplayComplete).unwrap();
+        tx.send(AppEvent::TaskGraphUpdated(graph())).unwrap();
+        drop(tx);
+
+        let mut state = AppState::new();
+        assert!(load(&mut state, &rx, Duration::from_millis(10), Duration::from_secs(5)));
+        assert!(state.meta.replay_complete);
+        assert_eq!(state.domain.task_graph.as_ref().map(TaskGraph::total_tasks), Some(1));
+    }
+
+    #[test]
+    fn load_gives_up_on_a_replay_that_never_finishes() {
+        let (tx, rx) = mpsc::channel();
+        tx.send(AppEvent::TaskGraphUpdated(graph())).unwrap();
+
+        let mut state = AppState::new();
+        assert!(!load(&mut state, &rx, Duration::from_millis(10), Duration::from_millis(50)));
+        assert!(state.domain.task_graph.is_some());
+        drop(tx);
+    }
+
+    #[test]
+    fn render_text_is_the_dashboard_as_trimmed_lines() {
+        let mut state = AppState::new();
+        state.domain.task_graph = Some(graph());
+        let text = render_text(&state, 100, 30);
+
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 30);
+        assert!(text.contains("Wire the snapshot command"), "{text}");
+        assert!(lines.iter().all(|line| !line.ends_with(' ')));
+        assert!(lines.iter().all(|line| Span::raw(*line).width() <= 100));
+    }
+}
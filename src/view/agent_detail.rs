@@ -2,16 +2,18 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Paragraph, Sparkline},
     Frame,
 };
 
+use crate::app::{agent_defs, agent_groups};
 use crate::app::state::{AppState, PanelFocus};
-use crate::model::Theme;
-use crate::view::components::{render_agent_event_stream, render_agent_list, render_prompt_popup};
+use crate::model::{Agent, Theme};
+use crate::view::components::format::format_token_count;
+use crate::view::components::{render_agent_list, render_prompt_popup, render_step_tree};
 
 /// Pure rendering function: render agent detail view.
-/// Left panel: selectable agent list. Right panel: filtered events for selected agent.
+/// Left panel: selectable agent list. Right panel: selected agent's activity as a step tree.
 pub fn render_agent_detail(frame: &mut Frame, state: &AppState, area: Rect) {
     // Layout: [agent_header][main_area][footer]
     let chunks = Layout::default()
@@ -23,13 +25,9 @@ pub fn render_agent_detail(frame: &mut Frame, state: &AppState, area: Rect) {
         ])
         .split(area);
 
-    // Resolve selected agent via sorted order
-    let sorted_keys = state.sorted_agent_keys();
-    let selected_agent = state.ui.selected_agent_index.and_then(|idx| {
-        sorted_keys.get(idx).and_then(|k| state.domain.agents.get(k))
-    });
+    let selected_agent = agent_groups::selected_agent(state);
 
-    render_agent_header(frame, chunks[0], selected_agent, state);
+    render_agent_header(frame, chunks[0], state, selected_agent);
 
     // Split main area: [agent_list(30%) | agent_events(70%)]
     let main_chunks = Layout::default()
@@ -41,34 +39,64 @@ pub fn render_agent_detail(frame: &mut Frame, state: &AppState, area: Rect) {
         .split(chunks[1]);
 
     render_agent_list(frame, main_chunks[0], state);
+    render_agent_activity(frame, main_chunks[1], state, selected_agent);
 
-    // Right panel: filtered events for selected agent
+    render_footer(frame, chunks[2], state);
+
+    render_agent_prompt(frame, area, state, selected_agent);
+}
+
+/// Render the focused panel alone for zoom (`z`): the agent list on the left,
+/// the selected agent's activity on the right.
+pub fn render_focused_panel(frame: &mut Frame, state: &AppState, area: Rect) {
+    let selected_agent = agent_groups::selected_agent(state);
+    match state.ui.focus {
+        PanelFocus::Left => render_agent_list(frame, area, state),
+        PanelFocus::Right => render_agent_activity(frame, area, state, selected_agent),
+    }
+    render_agent_prompt(frame, area, state, selected_agent);
+}
+
+/// Right panel: selected agent's steps (cursor = agent_events offset).
+fn render_agent_activity(frame: &mut Frame, area: Rect, state: &AppState, selected_agent: Option<&Agent>) {
     let is_right_focused = matches!(state.ui.focus, PanelFocus::Right);
     if let Some(agent) = selected_agent {
-        render_agent_event_stream(
+        // Context sparkline above the activity once there is a trend to show
+        let activity_area = if agent.context_samples.len() >= 2 {
+            let right_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(4), Constraint::Min(0)])
+                .split(area);
+            render_context_sparkline(frame, right_chunks[0], agent);
+            right_chunks[1]
+        } else {
+            area
+        };
+        render_step_tree(
             frame,
-            main_chunks[1],
+            activity_area,
             state,
             agent.id.as_str(),
             state.ui.scroll_offsets.agent_events,
             is_right_focused,
         );
     } else {
-        render_no_agent_selected(frame, main_chunks[1], is_right_focused);
+        render_no_agent_selected(frame, area, is_right_focused);
     }
+}
 
-    render_footer(frame, chunks[2], state);
-
-    // Prompt popup overlay (rendered last, on top)
+/// Prompt popup overlay (rendered last, on top).
+fn render_agent_prompt(frame: &mut Frame, area: Rect, state: &AppState, selected_agent: Option<&Agent>) {
     if state.ui.prompt_popup.is_open() {
         if let Some(agent) = selected_agent {
             let text = agent.task_description.as_deref().unwrap_or("No prompt available");
             render_prompt_popup(
                 frame,
                 area,
-                &agent.display_name(),
+                agent.display_name(),
                 agent.model.as_deref(),
                 agent.agent_type.as_deref(),
+                agent_defs::for_agent(&state.domain.agent_definitions, agent),
                 text,
                 &agent.messages,
                 &agent.skills,
@@ -79,12 +107,13 @@ pub fn render_agent_detail(frame: &mut Frame, state: &AppState, area: Rect) {
     }
 }
 
-/// Render header showing selected agent info.
+/// Render header showing selected agent info; the border names the model
+/// and tools its definition configures.
 fn render_agent_header(
     frame: &mut Frame,
     area: Rect,
-    agent: Option<&crate::model::Agent>,
     state: &AppState,
+    agent: Option<&crate::model::Agent>,
 ) {
     let header_line = match agent {
         Some(agent) => {
@@ -94,13 +123,7 @@ fn render_agent_header(
                 ("Active", Theme::TASK_RUNNING)
             };
 
-            let duration = if let Some(finished) = agent.finished_at {
-                let elapsed = finished.signed_duration_since(agent.started_at);
-                format!("{}s", elapsed.num_seconds())
-            } else {
-                let elapsed = state.meta.started_at.elapsed();
-                format!("{}s", elapsed.as_secs())
-            };
+            let duration = format!("{}s", agent.elapsed(&*state.meta.clock).num_seconds());
 
             let task_info = agent.task_description.as_deref()
                 .or_else(|| agent.task_id.as_ref().map(|id| id.as_str()))
@@ -129,19 +152,51 @@ fn render_agent_header(
         )),
     };
 
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::PANEL_BORDER));
+    if let Some(def) = agent.and_then(|agent| agent_defs::for_agent(&state.domain.agent_definitions, agent)) {
+        let configured = format!(" {} · {} ", def.model.as_deref().unwrap_or("inherit"), def.tools_label());
+        block = block.title_bottom(Line::from(Span::styled(configured, Style::default().fg(Theme::MUTED_TEXT))));
+    }
     let header = Paragraph::new(header_line)
+        .block(block)
+        .style(Style::default().fg(Theme::TEXT));
+
+    frame.render_widget(header, area);
+}
+
+/// Render the agent's context window history; sharp drops are compactions.
+fn render_context_sparkline(frame: &mut Frame, area: Rect, agent: &Agent) {
+    let samples = &agent.context_samples;
+    let current = samples.last().copied().unwrap_or(0);
+    let peak = samples.iter().copied().max().unwrap_or(0);
+
+    // Show the most recent samples that fit the panel
+    let width = area.width.saturating_sub(2) as usize;
+    let visible = &samples[samples.len().saturating_sub(width)..];
+
+    let title = format!(
+        " Context ~{} (peak ~{}) ",
+        format_token_count(current),
+        format_token_count(peak)
+    );
+    let sparkline = Sparkline::default()
         .block(
             Block::default()
+                .title(title)
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Theme::PANEL_BORDER)),
         )
-        .style(Style::default().fg(Theme::TEXT));
+        .data(visible)
+        .max(peak)
+        .style(Style::default().fg(Theme::INFO));
 
-    frame.render_widget(header, area);
+    frame.render_widget(sparkline, area);
 }
 
 /// Render placeholder when no agent is selected.
-fn render_no_agent_selected(frame: &mut Frame, area: Rect, is_focused: bool) {
+pub(crate) fn render_no_agent_selected(frame: &mut Frame, area: Rect, is_focused: bool) {
     let paragraph = Paragraph::new("Select an agent to view activity")
         .style(Style::default().fg(Theme::MUTED_TEXT))
         .block(
@@ -164,8 +219,14 @@ fn render_footer(frame: &mut Frame, area: Rect, _state: &AppState) {
         Span::raw(":switch | "),
         Span::styled("j/k", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw(":select/scroll | "),
+        Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(":expand | "),
+        Span::styled("o", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(":output/file | "),
+        Span::styled("J", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(":json | "),
         Span::styled("p", Style::default().add_modifier(Modifier::BOLD)),
-        Span::raw(":prompt | "),
+        Span::raw(":agent stats | "),
         Span::styled("Space", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw(":auto-scroll | "),
         Span::styled("Esc", Style::default().add_modifier(Modifier::BOLD)),
@@ -230,6 +291,29 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn header_names_the_configured_model_and_tools() {
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        let mut state = AppState::new();
+        let mut agent = Agent::new("a01", Utc::now());
+        agent.agent_type = Some("code-reviewer".into());
+        state.domain.agents.insert(AgentId::new("a01"), agent);
+        state.recompute_sorted_keys();
+        state.ui.selected_agent_index = Some(0);
+        state.domain.agent_definitions = vec![agent_defs::AgentDefinition {
+            name: "code-reviewer".into(),
+            description: None,
+            model: Some("sonnet".into()),
+            tools: Some(vec!["Read".into(), "Grep".into()]),
+            path: ".claude/agents/code-reviewer.md".into(),
+        }];
+
+        terminal.draw(|frame| render_agent_detail(frame, &state, frame.area())).unwrap();
+        let buffer = terminal.backend().buffer();
+        let third: String = (0..buffer.area.width).map(|x| buffer.cell((x, 2)).unwrap().symbol()).collect();
+        assert!(third.contains("sonnet · Read, Grep"), "{third}");
+    }
+
     #[test]
     fn render_with_finished_agent() {
         let backend = TestBackend::new(80, 20);
@@ -246,6 +330,29 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn render_shows_context_sparkline_with_history() {
+        let backend = TestBackend::new(100, 20);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut state = AppState::new();
+        let mut agent = Agent::new("a01", Utc::now());
+        for tokens in [20_000, 60_000, 150_000, 30_000] {
+            agent.record_context_sample(tokens);
+        }
+        state.domain.agents.insert(AgentId::new("a01"), agent);
+        state.recompute_sorted_keys();
+        state.ui.selected_agent_index = Some(0);
+
+        terminal
+            .draw(|frame| render_agent_detail(frame, &state, frame.area()))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let text: String = buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("Context ~30k (peak ~150k)"), "{text}");
+    }
+
     #[test]
     fn render_with_focus_right() {
         let backend = TestBackend::new(80, 20);
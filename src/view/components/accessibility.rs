@@ -0,0 +1,157 @@
+//! `ColorMode` post-pass over the finished frame.
+//!
+//! Widgets draw with `Theme` colors only. In the labelled modes this pass first
+//! writes a FOCUSED label onto focused panels (focus is otherwise just an
+//! `ACTIVE_BORDER` border), then remaps (high contrast) or strips (no color)
+//! every cell's colors. Selection backgrounds survive as reverse video.
+
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::model::{ColorMode, Theme};
+
+/// Written into the border of each focused panel in labelled modes (ASCII, one cell per char).
+pub const FOCUS_LABEL: &str = " FOCUSED ";
+
+/// Apply `mode` to everything rendered into `buf`. No-op for `ColorMode::Normal`.
+pub fn apply_color_mode(buf: &mut Buffer, mode: ColorMode) {
+    if !mode.uses_labels() {
+        return;
+    }
+    label_focused_panels(buf);
+
+    for cell in buf.content.iter_mut() {
+        let highlighted = !ColorMode::is_chrome_background(cell.bg);
+        cell.modifier.remove(Modifier::DIM);
+        match mode {
+            ColorMode::HighContrast if highlighted => {
+                cell.fg = Color::Black;
+                cell.bg = Color::White;
+            }
+            ColorMode::HighContrast => {
+                cell.fg = ColorMode::high_contrast(cell.fg);
+                cell.bg = Color::Reset;
+            }
+            _ => {
+                if highlighted {
+                    cell.modifier.insert(Modifier::REVERSED);
+                }
+                cell.fg = Color::Reset;
+                cell.bg = Color::Reset;
+            }
+        }
+    }
+}
+
+/// Find focused panels by their top-right corner and write `FOCUS_LABEL` into the
+/// top border, or the bottom border when titles leave no room at the top.
+fn label_focused_panels(buf: &mut Buffer) {
+    let area = buf.area;
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            if !is_focused_border(buf, x, y, "┐") {
+                continue;
+            }
+            if place_label(buf, x, y) {
+                continue;
+            }
+            // Follow the right edge down to the bottom-right corner
+            let mut bottom = y + 1;
+            while bottom < area.bottom() && is_focused_border(buf, x, bottom, "│") {
+                bottom += 1;
+            }
+            if bottom < area.bottom() && is_focused_border(buf, x, bottom, "┘") {
+                place_label(buf, x, bottom);
+            }
+        }
+    }
+}
+
+/// Write the label into the run of plain border left of the corner at (`corner_x`, `y`),
+/// keeping one border cell next to the corner.
+///
+/// # Returns
+/// Whether the run was long enough.
+fn place_label(buf: &mut Buffer, corner_x: u16, y: u16) -> bool {
+    let width = FOCUS_LABEL.len() as u16;
+    let mut run = 0;
+    while run < corner_x && is_focused_border(buf, corner_x - 1 - run, y, "─") {
+        run += 1;
+    }
+    if run < width + 1 {
+        return false;
+    }
+    let style = Style::default().fg(Theme::ACTIVE_BORDER).add_modifier(Modifier::BOLD);
+    buf.set_string(corner_x - 1 - width, y, FOCUS_LABEL, style);
+    true
+}
+
+fn is_focused_border(buf: &Buffer, x: u16, y: u16, symbol: &str) -> bool {
+    buf.cell((x, y))
+        .is_some_and(|c| c.symbol() == symbol && c.fg == Theme::ACTIVE_BORDER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::layout::Rect;
+    use ratatui::widgets::{Block, Borders};
+    use ratatui::Terminal;
+
+    fn draw_panels(mode: ColorMode) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(40, 6)).unwrap();
+        terminal
+            .draw(|frame| {
+                let focused = Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Theme::ACTIVE_BORDER))
+                    .title("Tasks");
+                let other = Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Theme::PANEL_BORDER))
+                    .title("Events");
+                frame.render_widget(focused, Rect::new(0, 0, 20, 6));
+                frame.render_widget(other, Rect::new(20, 0, 20, 6));
+                frame.buffer_mut().set_string(1, 2, "sel", Style::default().bg(Theme::SELECTION_BG));
+                apply_color_mode(frame.buffer_mut(), mode);
+            })
+            .unwrap()
+            .buffer
+            .clone()
+    }
+
+    fn row(buf: &Buffer, y: u16) -> String {
+        (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect()
+    }
+
+    #[test]
+    fn labels_only_the_focused_panel() {
+        let buf = draw_panels(ColorMode::NoColor);
+        let top = row(&buf, 0);
+        assert_eq!(top.matches("FOCUSED").count(), 1);
+        assert!(top[..top.char_indices().nth(20).unwrap().0].contains("FOCUSED"));
+    }
+
+    #[test]
+    fn no_color_strips_colors_and_reverses_selection() {
+        let buf = draw_panels(ColorMode::NoColor);
+        assert!(buf.content.iter().all(|c| c.fg == Color::Reset && c.bg == Color::Reset));
+        assert!(buf[(1, 2)].modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn high_contrast_maps_to_named_colors() {
+        let buf = draw_panels(ColorMode::HighContrast);
+        assert_eq!(buf[(0, 0)].fg, Color::LightCyan);
+        assert_eq!(buf[(20, 0)].fg, Color::Gray);
+        assert_eq!(buf[(1, 2)].bg, Color::White);
+    }
+
+    #[test]
+    fn normal_mode_leaves_frame_untouched() {
+        let buf = draw_panels(ColorMode::Normal);
+        assert!(!row(&buf, 0).contains("FOCUSED"));
+        assert_eq!(buf[(0, 0)].fg, Theme::ACTIVE_BORDER);
+    }
+}
@@ -0,0 +1,108 @@
+use chrono::{Datelike, Days, NaiveDate};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::calendar::{week_start, SessionCalendar};
+use crate::app::AppState;
+use crate::model::Theme;
+
+/// Height of the calendar: a row per weekday plus borders.
+pub const CALENDAR_HEIGHT: u16 = 9;
+
+/// Width of the weekday labels.
+const LABEL_WIDTH: u16 = 4;
+
+/// Each heat level has its own glyph so the map still reads without color.
+const HEAT_GLYPHS: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+
+/// Pure function: Mondays of the weeks shown in `width` columns, oldest first.
+/// The last week holds `today`, unless the selected day is further back than
+/// fits; then the grid starts at the selected day's week.
+pub fn calendar_weeks(width: u16, today: NaiveDate, selected: Option<NaiveDate>) -> Vec<NaiveDate> {
+    let count = (width.saturating_sub(LABEL_WIDTH) / 2).clamp(1, 53) as u64;
+    let span = Days::new(7 * (count - 1));
+    let mut first = week_start(today).checked_sub_days(span).unwrap_or(NaiveDate::MIN);
+    if let Some(day) = selected.filter(|day| week_start(*day) < first) {
+        first = week_start(day);
+    }
+    (0..count).filter_map(|weeks| first.checked_add_days(Days::new(7 * weeks))).collect()
+}
+
+fn weekday_line(
+    weekday: u64,
+    weeks: &[NaiveDate],
+    calendar: &SessionCalendar,
+    today: NaiveDate,
+    selected: Option<NaiveDate>,
+) -> Line<'static> {
+    let label = match weekday {
+        0 => "Mon ",
+        2 => "Wed ",
+        4 => "Fri ",
+        _ => "    ",
+    };
+    let mut spans = vec![Span::styled(label, Style::default().fg(Theme::MUTED_TEXT))];
+    for week in weeks {
+        let day = *week + Days::new(weekday);
+        if day > today {
+            spans.push(Span::raw("  "));
+            continue;
+        }
+        let heat = calendar.heat(day);
+        let mut style = Style::default().fg(if heat == 0 { Theme::SEPARATOR } else { Theme::ACCENT });
+        if selected == Some(day) {
+            style = style.fg(Theme::BACKGROUND).bg(Theme::WARNING).add_modifier(Modifier::BOLD);
+        }
+        spans.push(Span::styled(HEAT_GLYPHS[heat as usize], style));
+        spans.push(Span::raw(" "));
+    }
+    Line::from(spans)
+}
+
+/// Render the Sessions view's activity heat map: weeks across, weekdays down.
+pub fn render_activity_calendar(frame: &mut Frame, area: Rect, state: &AppState) {
+    let calendar = state.session_calendar();
+    let today = state.ui.time_format.date(state.meta.clock.now());
+    let selected = state.ui.session_date;
+    let weeks = calendar_weeks(area.width.saturating_sub(2), today, selected);
+
+    let lines: Vec<Line> = (0..7).map(|weekday| weekday_line(weekday, &weeks, calendar, today, selected)).collect();
+
+    let title = match selected {
+        Some(day) => format!(
+            " Activity · {} {}: {} session(s) · Esc: every day ",
+            day.weekday(),
+            day.format("%Y-%m-%d"),
+            calendar.count(day)
+        ),
+        None => " Activity · [ ]: day  { }: week ".to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::PANEL_BORDER))
+        .title(title);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weeks_end_with_the_current_week() {
+        let today: NaiveDate = "2026-03-18".parse().unwrap();
+        let weeks = calendar_weeks(LABEL_WIDTH + 6, today, None);
+        let expected: Vec<NaiveDate> =
+            ["2026-03-02", "2026-03-09", "2026-03-16"].iter().map(|d| d.parse().unwrap()).collect();
+        assert_eq!(weeks, expected);
+        // A selection within view leaves the grid alone; an older one scrolls it back
+        assert_eq!(calendar_weeks(LABEL_WIDTH + 6, today, Some("2026-03-03".parse().unwrap())), expected);
+        let weeks = calendar_weeks(LABEL_WIDTH + 6, today, Some("2026-02-20".parse().unwrap()));
+        assert_eq!(weeks[0], "2026-02-16".parse::<NaiveDate>().unwrap());
+    }
+}
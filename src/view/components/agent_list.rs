@@ -1,50 +1,148 @@
-use chrono::Utc;
 use ratatui::{
     layout::Rect,
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem},
     Frame,
 };
 
+use crate::app::activity::RunningTool;
+use crate::app::agent_groups::{agent_rows, AgentRow};
+use crate::app::agent_kinds::AgentKindStyles;
+use crate::app::cost_history::UNTYPED;
+use crate::app::anomaly::{self, Anomaly};
+use crate::app::empty_states::{guidance, EmptyPanel};
 use crate::app::{AppState, PanelFocus};
-use crate::model::{Agent, SessionMeta, SessionStatus, Theme};
+use crate::model::{Agent, AgentId, Clock, SessionMeta, SessionStatus, Theme};
+use super::empty_state::guidance_lines;
 use super::format::{format_duration, format_elapsed, format_token_count};
 
 /// Render agent list panel for agent detail view (uses global state).
+/// Grouped, the list has a header per agent type; the title names a type filter.
 pub fn render_agent_list(frame: &mut Frame, area: Rect, state: &AppState) {
-    let sorted_keys = state.sorted_agent_keys();
-    let agents: Vec<&Agent> = sorted_keys
+    let rows = agent_rows(state);
+    let keys: Vec<&AgentId> = rows.iter().filter_map(AgentRow::agent_id).collect();
+    let agents: Vec<&Agent> = keys
         .iter()
-        .filter_map(|k| state.domain.agents.get(k))
+        .filter_map(|k| state.domain.agents.get(*k))
         .collect();
-    let tool_counts: Vec<usize> = sorted_keys
+    let tool_counts: Vec<usize> = keys
         .iter()
         .map(|k| state.agent_tool_count(k))
         .collect();
+    let clock = &*state.meta.clock;
+    let anomalies: Vec<Option<Anomaly>> = keys
+        .iter()
+        .zip(&agents)
+        .map(|(k, agent)| anomaly::flag(agent, state.agent_tool_stats(k), &state.domain.agent_baselines, clock))
+        .collect();
+    let running: Vec<Option<&RunningTool>> = keys.iter().map(|k| state.running_tool(k)).collect();
     let is_focused = matches!(state.ui.focus, PanelFocus::Left);
 
-    render_agent_list_generic(
-        frame,
-        area,
-        &agents,
-        state.ui.selected_agent_index,
-        Some(&tool_counts),
-        is_focused,
-    );
+    let grouping = &state.ui.agent_grouping;
+    if grouping.is_flat() && !agents.is_empty() {
+        render_agent_list_generic(
+            frame,
+            area,
+            &agents,
+            &state.ui.agent_kinds,
+            state.ui.selected_agent_index,
+            Some(&tool_counts),
+            &anomalies,
+            &running,
+            is_focused,
+            clock,
+        );
+        return;
+    }
+
+    // Agent items are built as one slice (for duplicate-name detection), then
+    // interleaved with the section headers
+    let selected_row = state.ui.selected_agent_index;
+    let selected_agent = selected_row
+        .and_then(|idx| rows.get(idx))
+        .and_then(AgentRow::agent_id)
+        .and_then(|id| keys.iter().position(|k| *k == id));
+    let mut agent_items = if agents.is_empty() {
+        Vec::new()
+    } else {
+        build_agent_items_generic(&agents, &state.ui.agent_kinds, selected_agent, Some(&tool_counts), &anomalies, &running, clock)
+    }
+    .into_iter();
+    let mut items: Vec<ListItem> = rows
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, row)| match row {
+            AgentRow::Group { agent_type, count, collapsed } => {
+                let kind = state.ui.agent_kinds.style_for(Some(agent_type.as_str()).filter(|t| *t != UNTYPED));
+                Some(build_group_item(agent_type, kind, *count, *collapsed, selected_row == Some(idx)))
+            }
+            AgentRow::Agent(_) => agent_items.next(),
+        })
+        .collect();
+    if items.is_empty() {
+        let lines = match grouping.type_filter {
+            Some(_) => vec![Line::from(Span::styled("No agents", Style::default().fg(Theme::MUTED_TEXT)))],
+            None => guidance_lines(&guidance(state, EmptyPanel::Agents)),
+        };
+        items.push(ListItem::new(lines));
+    }
+
+    let title = match &grouping.type_filter {
+        Some(agent_type) => format!("Agents · {agent_type}"),
+        None => "Agents".to_string(),
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(if is_focused {
+                    Theme::ACTIVE_BORDER
+                } else {
+                    Theme::PANEL_BORDER
+                }))
+                .title(title),
+        )
+        .highlight_style(Style::default().bg(Theme::SELECTION_BG));
+
+    frame.render_widget(list, area);
+}
+
+/// Build a section header: `▾ ◆ reviewer (30)`, `▸` when folded.
+fn build_group_item(
+    agent_type: &str,
+    (icon, color): (&str, Color),
+    count: usize,
+    collapsed: bool,
+    is_selected: bool,
+) -> ListItem<'static> {
+    let bg = if is_selected { Theme::SELECTION_BG } else { Theme::BACKGROUND };
+    let fold = if collapsed { "▸" } else { "▾" };
+    ListItem::new(Line::from(vec![
+        Span::styled(format!("{} ", fold), Style::default().fg(Theme::ACCENT).bg(bg)),
+        Span::styled(format!("{} ", icon), Style::default().fg(color).bg(bg)),
+        Span::styled(agent_type.to_string(), Style::default().fg(color).bg(bg).add_modifier(Modifier::BOLD)),
+        Span::styled(format!(" ({})", count), Style::default().fg(Theme::MUTED_TEXT).bg(bg)),
+    ]))
 }
 
 /// Render agent list panel from a generic agent slice.
 /// Reusable across agent detail and session detail views.
+/// `anomalies` and `running` are indexed like `agents` (empty for none).
+#[allow(clippy::too_many_arguments)]
 pub fn render_agent_list_generic(
     frame: &mut Frame,
     area: Rect,
     agents: &[&Agent],
+    kinds: &AgentKindStyles,
     selected: Option<usize>,
     tool_counts: Option<&[usize]>,
+    anomalies: &[Option<Anomaly>],
+    running: &[Option<&RunningTool>],
     is_focused: bool,
+    clock: &dyn Clock,
 ) {
-    let items = build_agent_items_generic(agents, selected, tool_counts);
+    let items = build_agent_items_generic(agents, kinds, selected, tool_counts, anomalies, running, clock);
 
     let list = List::new(items)
         .block(
@@ -64,19 +162,28 @@ pub fn render_agent_list_generic(
 
 /// Render agent list with a "Main" entry prepended at index 0.
 /// Index 0 = Main orchestrator, index n>=1 = sorted_agents[n-1].
+/// `anomalies` is indexed like `agents` (empty for none).
+#[allow(clippy::too_many_arguments)]
 pub fn render_agent_list_with_main(
     frame: &mut Frame,
     area: Rect,
     agents: &[&Agent],
+    kinds: &AgentKindStyles,
     selected: Option<usize>,
+    anomalies: &[Option<Anomaly>],
     is_focused: bool,
     session_meta: &SessionMeta,
+    clock: &dyn Clock,
 ) {
-    let main_item = build_main_item(selected == Some(0), session_meta);
+    let main_item = build_main_item(selected == Some(0), session_meta, clock);
     let agent_items = build_agent_items_generic(
         agents,
+        kinds,
         selected.and_then(|i| i.checked_sub(1)),
         None,
+        anomalies,
+        &[],
+        clock,
     );
 
     let mut items = vec![main_item];
@@ -99,7 +206,7 @@ pub fn render_agent_list_with_main(
 }
 
 /// Build the "Main" list item for the orchestrator entry.
-fn build_main_item(is_selected: bool, meta: &SessionMeta) -> ListItem<'static> {
+fn build_main_item(is_selected: bool, meta: &SessionMeta, clock: &dyn Clock) -> ListItem<'static> {
     let bg = if is_selected { Theme::SELECTION_BG } else { Theme::BACKGROUND };
     let name_style = if is_selected {
         Style::default().fg(Theme::ACCENT).bg(bg).add_modifier(Modifier::BOLD)
@@ -110,8 +217,7 @@ fn build_main_item(is_selected: bool, meta: &SessionMeta) -> ListItem<'static> {
     let duration_str = match meta.duration {
         Some(d) => format_duration(Some(d)),
         None if meta.status == SessionStatus::Active => {
-            let elapsed = Utc::now().signed_duration_since(meta.timestamp);
-            format_duration(elapsed.to_std().ok())
+            format_duration(meta.elapsed(clock).to_std().ok())
         }
         None => format_duration(None),
     };
@@ -128,11 +234,18 @@ fn build_main_item(is_selected: bool, meta: &SessionMeta) -> ListItem<'static> {
     ListItem::new(Line::from(spans))
 }
 
-/// Pure function: build list items from an agent slice.
+/// Pure function: build list items from an agent slice. An agent with a tool
+/// call in flight (`running`) gets a second line naming it, in warning colors
+/// once the call has run past the long-call limit. The name is led by the
+/// agent kind's icon and followed by its namespace.
 fn build_agent_items_generic(
     agents: &[&Agent],
+    kinds: &AgentKindStyles,
     selected: Option<usize>,
     tool_counts: Option<&[usize]>,
+    anomalies: &[Option<Anomaly>],
+    running: &[Option<&RunningTool>],
+    clock: &dyn Clock,
 ) -> Vec<ListItem<'static>> {
     if agents.is_empty() {
         return vec![ListItem::new(Line::from(Span::styled(
@@ -141,7 +254,7 @@ fn build_agent_items_generic(
         )))];
     }
 
-    let now = Utc::now();
+    let now = clock.now();
 
     // Count display names to detect duplicates
     let name_counts: std::collections::HashMap<String, usize> = agents
@@ -171,15 +284,7 @@ fn build_agent_items_generic(
                 base_name
             };
 
-            let elapsed = if is_active {
-                let secs = (now - agent.started_at).num_seconds();
-                format_elapsed(secs)
-            } else if let Some(end) = agent.finished_at {
-                let secs = (end - agent.started_at).num_seconds();
-                format_elapsed(secs)
-            } else {
-                String::new()
-            };
+            let elapsed = format_elapsed(agent.elapsed(clock).num_seconds());
 
             let tool_count = tool_counts
                 .and_then(|tc| tc.get(idx).copied())
@@ -199,14 +304,16 @@ fn build_agent_items_generic(
                 Style::default().fg(Theme::MUTED_TEXT).bg(bg)
             };
 
+            let (kind_icon, kind_color) = kinds.style(agent);
             let mut spans = vec![
                 Span::styled(format!("{} ", icon), Style::default().fg(icon_color).bg(bg)),
+                Span::styled(format!("{} ", kind_icon), Style::default().fg(kind_color).bg(bg)),
                 Span::styled(name, name_style),
-                Span::styled(
-                    format!("  {}", elapsed),
-                    Style::default().fg(Theme::MUTED_TEXT).bg(bg),
-                ),
             ];
+            if let Some(namespace) = agent.kind().and_then(|kind| kind.namespace) {
+                spans.push(Span::styled(format!(" · {}", namespace), Style::default().fg(Theme::MUTED_TEXT).bg(bg)));
+            }
+            spans.push(Span::styled(format!("  {}", elapsed), Style::default().fg(Theme::MUTED_TEXT).bg(bg)));
 
             if tool_count > 0 {
                 spans.push(Span::styled(
@@ -223,7 +330,23 @@ fn build_agent_items_generic(
                 ));
             }
 
-            ListItem::new(Line::from(spans))
+            if let Some(Some(anomaly)) = anomalies.get(idx) {
+                spans.push(Span::styled(
+                    format!("  ⚠ {}", anomaly.label()),
+                    Style::default().fg(Theme::WARNING).bg(bg),
+                ));
+            }
+
+            let mut lines = vec![Line::from(spans)];
+            if let Some(Some(call)) = running.get(idx) {
+                let (marker, style) = if call.overdue {
+                    ("⚠", Style::default().fg(Theme::WARNING).bg(bg).add_modifier(Modifier::BOLD))
+                } else {
+                    ("↳", Style::default().fg(Theme::ACCENT_WARM).bg(bg))
+                };
+                lines.push(Line::from(Span::styled(format!("  {} now running: {}", marker, call.label(now)), style)));
+            }
+            ListItem::new(lines)
         })
         .collect()
 }
@@ -231,10 +354,12 @@ fn build_agent_items_generic(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::SystemClock;
+    use chrono::Utc;
 
     #[test]
     fn build_agent_items_empty() {
-        let items = build_agent_items_generic(&[], None, None);
+        let items = build_agent_items_generic(&[], &AgentKindStyles::default(), None, None, &[], &[], &SystemClock);
         assert_eq!(items.len(), 1); // "No agents"
     }
 
@@ -245,10 +370,86 @@ mod tests {
         let a2 = Agent::new("a02", Utc::now());
         let agents: Vec<&Agent> = vec![&a1, &a2];
 
-        let items = build_agent_items_generic(&agents, Some(0), None);
+        let items = build_agent_items_generic(&agents, &AgentKindStyles::default(), Some(0), None, &[], &[], &SystemClock);
         assert_eq!(items.len(), 2);
     }
 
+    #[test]
+    fn build_agent_items_shows_anomaly_flag() {
+        let a1 = Agent::new("a01", Utc::now());
+        let anomaly = Anomaly {
+            metric: anomaly::Metric::Duration,
+            agent_type: "reviewer".into(),
+            z: 4.0,
+            value: 300.0,
+            typical: 100.0,
+        };
+        let items = build_agent_items_generic(&[&a1], &AgentKindStyles::default(), None, None, &[Some(anomaly)], &[], &SystemClock);
+        let backend = ratatui::backend::TestBackend::new(80, 1);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| f.render_widget(List::new(items), f.area())).unwrap();
+        let row: String = terminal.backend().buffer().content.iter().map(|c| c.symbol()).collect();
+        assert!(row.contains("⚠ 3x slower than typical reviewer"));
+    }
+
+    #[test]
+    fn build_agent_items_shows_running_tool_line() {
+        let a1 = Agent::new("a01", Utc::now());
+        let call = RunningTool {
+            tool_name: crate::model::ToolName::new("Bash"),
+            input_summary: "cargo test".into(),
+            started_at: Utc::now() - chrono::Duration::seconds(34),
+            overdue: false,
+        };
+        let items = build_agent_items_generic(&[&a1], &AgentKindStyles::default(), None, None, &[], &[Some(&call)], &SystemClock);
+        let backend = ratatui::backend::TestBackend::new(60, 2);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| f.render_widget(List::new(items), f.area())).unwrap();
+        let buffer = terminal.backend().buffer();
+        let second: String = (0..buffer.area.width).map(|x| buffer.cell((x, 1)).unwrap().symbol()).collect();
+        assert!(second.contains("↳ now running: Bash cargo test … 3"));
+    }
+
+    #[test]
+    fn grouped_list_shows_section_headers_with_counts() {
+        let mut state = AppState::new();
+        for (id, agent_type) in [("a01", "reviewer"), ("a02", "reviewer"), ("a03", "implementer")] {
+            let mut agent = Agent::new(id, Utc::now());
+            agent.agent_type = Some(agent_type.into());
+            state.domain.agents.insert(AgentId::new(id), agent);
+        }
+        state.recompute_sorted_keys();
+        state.ui.agent_grouping.grouped = true;
+        state.ui.agent_grouping.collapsed.insert("implementer".into());
+
+        let backend = ratatui::backend::TestBackend::new(60, 8);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| render_agent_list(f, f.area(), &state)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let lines: Vec<String> = (0..buffer.area.height)
+            .map(|y| (0..buffer.area.width).map(|x| buffer.cell((x, y)).unwrap().symbol()).collect())
+            .collect();
+        assert!(lines[1].contains("▸ ◆ implementer (1)"));
+        assert!(lines[2].contains("▾ ◆ reviewer (2)"));
+        assert!(lines[3].contains("a0"));
+    }
+
+    #[test]
+    fn empty_list_says_why_there_are_no_agents() {
+        let mut state = AppState::new();
+        state.meta.replay_complete = true;
+
+        let backend = ratatui::backend::TestBackend::new(70, 6);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal.draw(|f| render_agent_list(f, f.area(), &state)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let text: String = (0..buffer.area.height)
+            .map(|y| (0..buffer.area.width).map(|x| buffer.cell((x, y)).unwrap().symbol()).collect::<String>())
+            .collect();
+        assert!(text.contains("No agents yet — no transcript written"), "{text}");
+        assert!(text.contains("loom-tui doctor"), "{text}");
+    }
+
     #[test]
     fn format_token_count_small() {
         assert_eq!(format_token_count(500), "500");
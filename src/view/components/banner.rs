@@ -1,10 +1,122 @@
 use ratatui::{
     layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
     Frame,
 };
 
-use crate::app::state::AppState;
+use crate::app::{AppState, WaveBanner};
+use crate::model::Theme;
+use super::format::format_elapsed;
 
-/// Banner placeholder. Currently renders nothing.
-pub fn render_banner(_frame: &mut Frame, _area: Rect, _state: &AppState) {
+/// Render the transient wave-complete banner. No-op when no banner is active.
+pub fn render_banner(frame: &mut Frame, area: Rect, state: &AppState) {
+    let Some(banner) = &state.ui.wave_banner else {
+        return;
+    };
+
+    let paragraph = Paragraph::new(build_banner_line(banner)).style(
+        Style::default()
+            .fg(Theme::BACKGROUND)
+            .bg(Theme::SUCCESS)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Pure function: "Wave 2 complete in 14m0s — starting Wave 3: 5 tasks  (4 tasks · 7 files · 3 tests passed)"
+fn build_banner_line(banner: &WaveBanner) -> Line<'static> {
+    let c = &banner.completion;
+
+    let mut headline = format!(" ✓ Wave {} complete", c.wave);
+    if let Some(elapsed) = banner.elapsed {
+        headline.push_str(&format!(" in {}", format_elapsed(elapsed.num_seconds().max(0))));
+    }
+    match c.next_wave {
+        Some((number, tasks)) => headline.push_str(&format!(
+            " — starting Wave {}: {} task{}",
+            number,
+            tasks,
+            if tasks == 1 { "" } else { "s" }
+        )),
+        None => headline.push_str(" — all waves done"),
+    }
+
+    let mut summary = vec![format!("{} tasks", c.task_count)];
+    if c.files_modified > 0 {
+        summary.push(format!("{} files", c.files_modified));
+    }
+    if c.tests_passed > 0 {
+        summary.push(format!("{} tests passed", c.tests_passed));
+    }
+
+    Line::from(vec![
+        Span::raw(headline),
+        Span::styled(
+            format!("  ({})", summary.join(" · ")),
+            Style::default().remove_modifier(Modifier::BOLD),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::WaveCompletion;
+    use chrono::Utc;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn banner(next_wave: Option<(u32, usize)>, elapsed: Option<chrono::Duration>) -> WaveBanner {
+        WaveBanner {
+            completion: WaveCompletion {
+                wave: 2,
+                task_count: 4,
+                files_modified: 7,
+                tests_passed: 0,
+                next_wave,
+            },
+            elapsed,
+            expires_at: Utc::now(),
+        }
+    }
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn banner_line_announces_next_wave() {
+        let b = banner(Some((3, 5)), Some(chrono::Duration::minutes(14)));
+        let text = line_text(&build_banner_line(&b));
+
+        assert!(text.contains("Wave 2 complete in 14m0s"), "got: {}", text);
+        assert!(text.contains("starting Wave 3: 5 tasks"));
+        assert!(text.contains("4 tasks · 7 files"));
+        assert!(!text.contains("tests passed"));
+    }
+
+    #[test]
+    fn banner_line_final_wave_without_duration() {
+        let text = line_text(&build_banner_line(&banner(None, None)));
+
+        assert!(text.contains("Wave 2 complete —"));
+        assert!(text.contains("all waves done"));
+    }
+
+    #[test]
+    fn render_banner_noop_without_banner() {
+        let backend = TestBackend::new(60, 1);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let state = AppState::new();
+
+        let result = terminal
+            .draw(|frame| render_banner(frame, frame.area(), &state))
+            .unwrap();
+        let text: String = result.buffer.content().iter().map(|c| c.symbol()).collect();
+
+        assert!(text.trim().is_empty());
+    }
 }
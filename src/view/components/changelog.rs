@@ -0,0 +1,65 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::updates::Release;
+use crate::model::{SessionArchive, Theme};
+
+/// Render the release notes of the newer release found at startup (`:changelog`).
+pub fn render_changelog(frame: &mut Frame, area: Rect, release: &Release) {
+    let popup_area = centered_rect(70, 70, area);
+    frame.render_widget(Clear, popup_area);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("  This is {}. Release notes for {}:", SessionArchive::BUILD_VERSION, release.version),
+            Style::default().fg(Theme::MUTED_TEXT),
+        )),
+        Line::from(""),
+    ];
+    if release.notes.trim().is_empty() {
+        lines.push(Line::from(Span::styled("  No release notes", Style::default().fg(Theme::MUTED_TEXT))));
+    } else {
+        lines.extend(
+            release
+                .notes
+                .lines()
+                .map(|line| Line::from(Span::styled(format!("  {}", line), Style::default().fg(Theme::TEXT)))),
+        );
+    }
+
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::ACTIVE_BORDER))
+            .title(Line::from(Span::styled(
+                format!(" loom-tui {} available (any key to close) ", release.version),
+                Style::default().fg(Theme::ACCENT).add_modifier(Modifier::BOLD),
+            ))),
+    );
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,95 @@
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::command::hint;
+use crate::app::state::{AppState, CommandState};
+use crate::model::theme::Theme;
+
+/// Render the `:` command line at the bottom of the screen, with completion
+/// hints in the border. When closed, shows the last command's outcome instead.
+pub fn render_command_bar(frame: &mut Frame, state: &AppState) {
+    let area = frame.area();
+    let bar_area = Rect {
+        x: area.x,
+        y: area.height.saturating_sub(3),
+        width: area.width,
+        height: 3.min(area.height),
+    };
+
+    let (text, block) = match (&state.ui.command, &state.ui.command_message) {
+        (CommandState::Open { input }, _) => {
+            let text = Line::from(vec![
+                Span::styled(": ", Style::default().fg(Theme::INFO)),
+                Span::styled(input.clone(), Style::default().fg(Theme::TEXT)),
+                Span::styled("█", Style::default().fg(Theme::ACTIVE_BORDER)), // Cursor
+            ]);
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Theme::ACTIVE_BORDER))
+                .title_bottom(Line::from(Span::styled(
+                    format!(" {} ", hint(input)),
+                    Style::default().fg(Theme::MUTED_TEXT),
+                )));
+            (text, block)
+        }
+        (CommandState::Closed, Some(message)) => {
+            let color = if message.is_error { Theme::ERROR } else { Theme::SUCCESS };
+            let text = Line::from(Span::styled(message.text.clone(), Style::default().fg(color)));
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color));
+            (text, block)
+        }
+        (CommandState::Closed, None) => return,
+    };
+
+    frame.render_widget(Clear, bar_area);
+    frame.render_widget(
+        Paragraph::new(text).block(block).alignment(Alignment::Left),
+        bar_area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::state::CommandMessage;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn render_text(state: &AppState) -> String {
+        let backend = TestBackend::new(100, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let result = terminal.draw(|frame| render_command_bar(frame, state)).unwrap();
+        result.buffer.content().iter().map(|c| c.symbol()).collect()
+    }
+
+    #[test]
+    fn shows_input_and_usage_hint() {
+        let mut state = AppState::new();
+        state.ui.command = CommandState::Open { input: "set ".into() };
+
+        let text = render_text(&state);
+        assert!(text.contains(": set"));
+        assert!(text.contains("set tick=<ms>"));
+    }
+
+    #[test]
+    fn shows_message_after_command() {
+        let mut state = AppState::new();
+        state.ui.command_message = Some(CommandMessage { text: "unknown command: zap".into(), is_error: true });
+
+        assert!(render_text(&state).contains("unknown command: zap"));
+    }
+
+    #[test]
+    fn renders_nothing_when_idle() {
+        let state = AppState::new();
+        assert!(render_text(&state).trim().is_empty());
+    }
+}
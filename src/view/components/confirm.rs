@@ -6,56 +6,56 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::state::DeleteConfirmState;
+use crate::app::state::{ConfirmAction, ConfirmState};
 use crate::model::Theme;
 
-/// Render delete confirmation popup overlay.
-pub fn render_delete_confirm(frame: &mut Frame, area: Rect, confirm: &DeleteConfirmState) {
-    let session_ids = match confirm {
-        DeleteConfirmState::Open { session_ids } => session_ids,
-        DeleteConfirmState::Closed => return,
+/// Most session ids listed in a delete dialog.
+const MAX_LISTED: usize = 5;
+
+/// Render the confirm dialog overlay. No-op when closed.
+pub fn render_confirm(frame: &mut Frame, area: Rect, confirm: &ConfirmState) {
+    let ConfirmState::Open { action, yes_focused } = confirm else {
+        return;
     };
+    let (title, question, details) = describe(action);
 
     let popup_area = centered_rect(40, 30, area);
     frame.render_widget(Clear, popup_area);
 
-    let count = session_ids.len();
     let mut lines: Vec<Line> = vec![
         Line::from(""),
         Line::from(Span::styled(
-            format!("Delete {count} session(s)?"),
+            question,
             Style::default()
                 .fg(Theme::WARNING)
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
     ];
-
-    // Show up to 5 session IDs
-    let show_count = session_ids.len().min(5);
-    for id in &session_ids[..show_count] {
-        lines.push(Line::from(Span::styled(
-            format!("  {id}"),
-            Style::default().fg(Theme::TEXT),
-        )));
-    }
-    if session_ids.len() > 5 {
-        lines.push(Line::from(Span::styled(
-            format!("  ...and {} more", session_ids.len() - 5),
-            Style::default().fg(Theme::MUTED_TEXT),
-        )));
+    for detail in details {
+        lines.push(Line::from(Span::styled(detail, Style::default().fg(Theme::TEXT))));
     }
 
+    let button = |label: &'static str, focused: bool| {
+        let style = if focused {
+            Style::default().fg(Theme::BACKGROUND).bg(Theme::WARNING).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Theme::MUTED_TEXT)
+        };
+        Span::styled(label, style)
+    };
     lines.push(Line::from(""));
-    lines.push(Line::from(Span::styled(
-        "y:confirm  n:cancel",
-        Style::default().fg(Theme::MUTED_TEXT),
-    )));
+    lines.push(Line::from(vec![
+        button(" Yes ", *yes_focused),
+        Span::raw("  "),
+        button(" No ", !*yes_focused),
+        Span::styled("   y/n · ←/→ Enter", Style::default().fg(Theme::MUTED_TEXT)),
+    ]));
 
     let paragraph = Paragraph::new(lines).block(
         Block::default()
             .title(Line::from(Span::styled(
-                " Confirm Delete ",
+                format!(" {} ", title),
                 Style::default()
                     .fg(Theme::WARNING)
                     .add_modifier(Modifier::BOLD),
@@ -67,6 +67,23 @@ pub fn render_delete_confirm(frame: &mut Frame, area: Rect, confirm: &DeleteConf
     frame.render_widget(paragraph, popup_area);
 }
 
+/// Pure function: title, question and detail lines of a dialog.
+fn describe(action: &ConfirmAction) -> (&'static str, String, Vec<String>) {
+    match action {
+        ConfirmAction::DeleteSessions(session_ids) => {
+            let mut details: Vec<String> = session_ids.iter().take(MAX_LISTED).map(|id| format!("  {id}")).collect();
+            if session_ids.len() > MAX_LISTED {
+                details.push(format!("  ...and {} more", session_ids.len() - MAX_LISTED));
+            }
+            ("Confirm Delete", format!("Delete {} session(s)?", session_ids.len()), details)
+        }
+        ConfirmAction::Quit { running } => {
+            let agents = if *running == 1 { "agent" } else { "agents" };
+            ("Quit", format!("{running} {agents} still running — quit anyway?"), Vec::new())
+        }
+    }
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::vertical([
         Constraint::Percentage((100 - percent_y) / 2),
@@ -93,13 +110,11 @@ mod tests {
     fn renders_without_panic_when_open() {
         let backend = TestBackend::new(80, 40);
         let mut terminal = Terminal::new(backend).unwrap();
-        let confirm = DeleteConfirmState::Open {
-            session_ids: vec!["s1".into(), "s2".into()],
-        };
+        let confirm = ConfirmState::open(ConfirmAction::DeleteSessions(vec!["s1".into(), "s2".into()]));
 
         terminal
             .draw(|frame| {
-                render_delete_confirm(frame, frame.area(), &confirm);
+                render_confirm(frame, frame.area(), &confirm);
             })
             .unwrap();
     }
@@ -108,11 +123,11 @@ mod tests {
     fn does_nothing_when_closed() {
         let backend = TestBackend::new(80, 40);
         let mut terminal = Terminal::new(backend).unwrap();
-        let confirm = DeleteConfirmState::Closed;
+        let confirm = ConfirmState::Closed;
 
         terminal
             .draw(|frame| {
-                render_delete_confirm(frame, frame.area(), &confirm);
+                render_confirm(frame, frame.area(), &confirm);
             })
             .unwrap();
     }
@@ -122,11 +137,11 @@ mod tests {
         let backend = TestBackend::new(80, 40);
         let mut terminal = Terminal::new(backend).unwrap();
         let ids: Vec<_> = (0..8).map(|i| format!("s{i}").into()).collect();
-        let confirm = DeleteConfirmState::Open { session_ids: ids };
+        let confirm = ConfirmState::open(ConfirmAction::DeleteSessions(ids));
 
         terminal
             .draw(|frame| {
-                render_delete_confirm(frame, frame.area(), &confirm);
+                render_confirm(frame, frame.area(), &confirm);
             })
             .unwrap();
 
@@ -142,4 +157,12 @@ mod tests {
 
         assert!(buffer_str.contains("and 3 more"));
     }
+
+    #[test]
+    fn quit_asks_about_running_agents() {
+        let (title, question, details) = describe(&ConfirmAction::Quit { running: 3 });
+        assert_eq!((title, question.as_str()), ("Quit", "3 agents still running — quit anyway?"));
+        assert!(details.is_empty());
+        assert_eq!(describe(&ConfirmAction::Quit { running: 1 }).1, "1 agent still running — quit anyway?");
+    }
 }
@@ -0,0 +1,108 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::state::DiffPopupState;
+use crate::git::{DiffLine, DiffLineKind};
+use crate::model::Theme;
+
+/// Render the `git diff` popup for a task's modified files (`D` on the Dashboard).
+pub fn render_diff_popup(frame: &mut Frame, area: Rect, popup: &DiffPopupState) {
+    let popup_area = centered_rect(85, 80, area);
+    frame.render_widget(Clear, popup_area);
+
+    let (title, lines, scroll) = match popup {
+        DiffPopupState::Closed => return,
+        DiffPopupState::Loading(request) => (
+            format!(" {} — git diff (Esc to close) ", request.task_id),
+            vec![Line::from(Span::styled("Running git diff…", Style::default().fg(Theme::INFO)))],
+            0,
+        ),
+        DiffPopupState::Open { task_id, diff, scroll } => (
+            format!(
+                " {} — {} files +{} -{} since session start (Esc to close) ",
+                task_id, diff.files, diff.added, diff.removed
+            ),
+            diff.lines.iter().map(styled_line).collect(),
+            *scroll,
+        ),
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Theme::ACTIVE_BORDER))
+                .title(Line::from(Span::styled(
+                    title,
+                    Style::default().fg(Theme::ACCENT).add_modifier(Modifier::BOLD),
+                ))),
+        )
+        .style(Style::default().fg(Theme::TEXT))
+        .scroll((scroll.min(u16::MAX as usize) as u16, 0));
+
+    frame.render_widget(paragraph, popup_area);
+}
+
+/// Pure function: color one diff line by kind.
+fn styled_line(line: &DiffLine) -> Line<'static> {
+    let style = match line.kind {
+        DiffLineKind::FileHeader => Style::default().fg(Theme::ACCENT).add_modifier(Modifier::BOLD),
+        DiffLineKind::Meta => Style::default().fg(Theme::MUTED_TEXT),
+        DiffLineKind::Hunk => Style::default().fg(Theme::INFO),
+        DiffLineKind::Added => Style::default().fg(Theme::SUCCESS),
+        DiffLineKind::Removed => Style::default().fg(Theme::ERROR),
+        DiffLineKind::Context => Style::default().fg(Theme::TEXT),
+    };
+    Line::from(Span::styled(line.text.replace('\t', "    "), style))
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::parse_diff;
+    use crate::model::TaskId;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn shows_totals_and_colors_changes() {
+        let diff = parse_diff("diff --git a/x b/x\n--- a/x\n+++ b/x\n@@ -1 +1 @@\n-old\n+new\n");
+        let popup = DiffPopupState::Open { task_id: TaskId::new("T1"), diff, scroll: 0 };
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal.draw(|frame| render_diff_popup(frame, frame.area(), &popup)).unwrap();
+
+        let buf = terminal.backend().buffer();
+        let rows: Vec<String> = (0..buf.area.height)
+            .map(|y| (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert!(rows.iter().any(|r| r.contains("T1 — 1 files +1 -1")));
+        let (y, row) = rows.iter().enumerate().find(|(_, r)| r.contains("+new")).unwrap();
+        let x = row.chars().position(|c| c == '+').unwrap() as u16;
+        assert_eq!(buf[(x, y as u16)].fg, Theme::SUCCESS);
+    }
+}
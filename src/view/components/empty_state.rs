@@ -0,0 +1,22 @@
+//! Empty panel guidance (see `app::empty_states`).
+
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+
+use crate::app::empty_states::Guidance;
+use crate::model::Theme;
+
+/// Pure function: the headline, then one line per hint, all muted so they
+/// don't read as data.
+pub fn guidance_lines(guidance: &Guidance) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(Span::styled(guidance.headline.clone(), Style::default().fg(Theme::MUTED_TEXT)))];
+    lines.extend(
+        guidance
+            .hints
+            .iter()
+            .map(|hint| Line::from(Span::styled(format!("  · {}", hint), Style::default().fg(Theme::MUTED_TEXT)))),
+    );
+    lines
+}
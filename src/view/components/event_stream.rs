@@ -1,18 +1,37 @@
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
-use crate::app::{AppState, PanelFocus};
-use crate::model::{Theme, TranscriptEventKind};
+use chrono::{DateTime, Utc};
+
+use super::empty_state::guidance_lines;
+use super::filter_bar::filter_chip;
+use super::format::{format_elapsed, TimeFormat};
+use super::scroll::{render_panned, render_wrapped};
+use crate::app::channels::EventChannels;
+use crate::app::empty_states::{guidance, EmptyPanel};
+use crate::app::{event_index, AppState, PanelFocus};
+use crate::model::{EventOrigin, Theme, TranscriptEventKind};
 
 /// Render event stream panel.
 /// Shows scrollable log of recent transcript events with timestamps.
-/// Uses Paragraph with word wrap so long lines don't clip.
+/// Uses Paragraph with word wrap so long lines don't clip; scrolls by wrapped rows.
 pub fn render_event_stream(frame: &mut Frame, area: Rect, state: &AppState) {
+    // Channel tabs (when the config defines channels) sit above the panel
+    let area = if state.ui.event_channels.channels().is_empty() || area.height < 4 {
+        area
+    } else {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        frame.render_widget(Paragraph::new(channel_tabs(&state.ui.event_channels)), rows[0]);
+        rows[1]
+    };
     let lines = build_filtered_event_lines(state, None);
 
     let is_focused = matches!(state.ui.focus, PanelFocus::Right);
@@ -23,27 +42,75 @@ pub fn render_event_stream(frame: &mut Frame, area: Rect, state: &AppState) {
         "Events"
     };
 
-    // Clamp scroll_offset to u16::MAX to prevent silent truncation overflow
-    // Additionally clamp to a reasonable maximum to avoid ratatui internal panics
-    let scroll = state.ui.scroll_offsets.event_stream
-        .min(u16::MAX as usize)
-        .min(10000) as u16;
-
-    let paragraph = Paragraph::new(lines)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(if is_focused {
-                    Theme::ACTIVE_BORDER
-                } else {
-                    Theme::PANEL_BORDER
-                }))
-                .title(title),
-        )
-        .wrap(Wrap { trim: false })
-        .scroll((scroll, 0));
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if is_focused {
+            Theme::ACTIVE_BORDER
+        } else {
+            Theme::PANEL_BORDER
+        }))
+        .title(title);
+    if !state.ui.auto_scroll && state.ui.unseen_events.event_stream > 0 {
+        block = block.title_top(new_events_pill(state.ui.unseen_events.event_stream));
+    }
+    if let Some(chip) = filter_chip(state) {
+        block = block.title(chip);
+    }
+    if let Some(summary) = duplicate_summary(state) {
+        block = block.title_bottom(Line::from(Span::styled(summary, Style::default().fg(Theme::MUTED_TEXT))));
+    }
+
+    let offsets = &state.ui.scroll_offsets;
+    let wrap = !state.ui.truncated_events.is_truncated(&state.ui.view);
+    let (max_offset, max_x) =
+        render_panned(frame, area, lines, block, offsets.event_stream, offsets.event_stream_x, wrap);
+    state.ui.scroll_limits.event_stream_x.set(Some(max_x));
+    state.ui.scroll_limits.event_stream.set(Some(max_offset));
+}
+
+/// Pure function: the channel selector row, `All` then each channel, the shown one highlighted.
+fn channel_tabs(channels: &EventChannels) -> Line<'static> {
+    let selected = channels.selected();
+    let names = std::iter::once((None, "All".to_string()))
+        .chain(channels.channels().iter().enumerate().map(|(idx, channel)| (Some(idx), channel.name.clone())));
+    let mut spans = vec![Span::raw(" ")];
+    for (idx, name) in names {
+        let style = if idx == selected {
+            Style::default().fg(Theme::BACKGROUND).bg(Theme::ACCENT).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Theme::MUTED_TEXT)
+        };
+        spans.push(Span::styled(format!(" {} ", name), style));
+        spans.push(Span::raw(" "));
+    }
+    spans.push(Span::styled("c: channel", Style::default().fg(Theme::MUTED_TEXT)));
+    Line::from(spans)
+}
+
+/// Pure function: cross-source duplicates found so far, by origin of the later copy.
+fn duplicate_summary(state: &AppState) -> Option<String> {
+    let dedup = &state.domain.dedup;
+    if dedup.total_duplicates() == 0 {
+        return None;
+    }
+    let by_origin: Vec<String> = dedup
+        .duplicates()
+        .iter()
+        .map(|(origin, count)| format!("{} {}", origin.label(), count))
+        .collect();
+    let verb = if state.meta.show_duplicates { "shown" } else { "dropped" };
+    Some(format!(" {} duplicates {} ({}) ", dedup.total_duplicates(), verb, by_origin.join(", ")))
+}
 
-    frame.render_widget(paragraph, area);
+/// "▲ N new" marker for a panel scrolled away from arriving events
+/// (newest events render at the top; `g` jumps there and resumes auto-scroll).
+pub fn new_events_pill(count: usize) -> Line<'static> {
+    let noun = if count == 1 { "event" } else { "events" };
+    Line::from(Span::styled(
+        format!(" ▲ {} new {} · g ", count, noun),
+        Style::default().fg(Theme::BACKGROUND).bg(Theme::ACCENT_WARM).add_modifier(Modifier::BOLD),
+    ))
+    .right_aligned()
 }
 
 /// Render filtered event stream for a specific agent.
@@ -63,27 +130,27 @@ pub fn render_agent_event_stream(
         "Activity"
     };
 
-    // Clamp scroll_offset to u16::MAX to prevent silent truncation overflow
-    // Additionally clamp to a reasonable maximum to avoid ratatui internal panics
-    let scroll = scroll_offset
-        .min(u16::MAX as usize)
-        .min(10000) as u16;
-
-    let paragraph = Paragraph::new(lines)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(if is_focused {
-                    Theme::ACTIVE_BORDER
-                } else {
-                    Theme::PANEL_BORDER
-                }))
-                .title(title),
-        )
-        .wrap(Wrap { trim: false })
-        .scroll((scroll, 0));
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if is_focused {
+            Theme::ACTIVE_BORDER
+        } else {
+            Theme::PANEL_BORDER
+        }))
+        .title(title);
+
+    render_wrapped(frame, area, lines, block, scroll_offset);
+}
 
-    frame.render_widget(paragraph, area);
+/// True if the event is the agent's own, or an unattributed event from the agent's session.
+pub(crate) fn event_belongs_to_agent(
+    event: &crate::model::TranscriptEvent,
+    agent_id: &str,
+    agent_session: Option<&crate::model::SessionId>,
+) -> bool {
+    let direct = event.agent_id.as_ref().map(|id| id.as_str()) == Some(agent_id);
+    let shared = event.agent_id.is_none() && agent_session.is_some() && event.session_id.as_ref() == agent_session;
+    direct || shared
 }
 
 /// Pure function: build lines from events, optionally filtered by agent_id.
@@ -108,58 +175,65 @@ fn build_filtered_event_lines(state: &AppState, agent_filter: Option<&str>) -> V
         .filter(|q| !q.is_empty())
         .map(|q| q.to_lowercase());
 
+    // The selected channel also narrows only the Dashboard stream
+    let channel = if agent_filter.is_none() {
+        state.ui.event_channels.current()
+    } else {
+        None
+    };
+
     let filtered: Vec<_> = state
         .domain.events
         .iter()
+        .enumerate()
         .rev()
-        .filter(|e| {
+        .filter(|(position, e)| {
             // First, filter by agent if specified
             let agent_match = match agent_filter {
-                Some(aid) => {
-                    let direct = e.agent_id.as_ref().map(|id| id.as_str()) == Some(aid);
-                    let shared = e.agent_id.is_none()
-                        && agent_session.is_some()
-                        && e.session_id == agent_session;
-                    direct || shared
-                }
+                Some(aid) => event_belongs_to_agent(e, aid, agent_session.as_ref()),
                 None => true,
             };
 
-            if !agent_match {
+            if !agent_match || !state.ui.event_filters.allows(&e.kind) {
+                return false;
+            }
+            if channel.is_some_and(|channel| !channel.matches(e)) {
                 return false;
             }
 
             // Then, filter by search text if specified
             if let Some(ref query_lower) = search_query_lower {
-                event_matches_search_transcript(&e.kind, query_lower, e.agent_id.as_ref())
+                event_index::matches(state, *position, e, query_lower)
             } else {
                 true
             }
         })
+        .map(|(_, e)| e)
         .take(500)
         .collect();
 
     if filtered.is_empty() {
+        if agent_filter.is_none() {
+            return guidance_lines(&guidance(state, EmptyPanel::Events));
+        }
         return vec![Line::from(Span::styled(
             "No events",
             Style::default().fg(Theme::MUTED_TEXT),
         ))];
     }
 
+    let time_format = &state.ui.time_format;
     let mut lines = Vec::new();
-    let mut first = true;
+    let mut newer: Option<DateTime<Utc>> = None;
 
     for event in &filtered {
-        // Separator between events (dim line)
-        if !first {
-            lines.push(Line::from(Span::styled(
-                "────────────────────────────────",
-                Style::default().fg(Theme::SEPARATOR),
-            )));
+        // Separator between events (dim line, or the day when it rolls over)
+        if let Some(newer) = newer {
+            lines.push(event_separator(newer, event.timestamp, time_format));
         }
-        first = false;
+        newer = Some(event.timestamp);
 
-        let timestamp = event.timestamp.format("%H:%M:%S").to_string();
+        let timestamp = time_format.time(event.timestamp);
         let (icon, header, detail, event_color, tool_name) = format_transcript_event_lines(&event.kind);
 
         // Resolve agent display name
@@ -171,14 +245,32 @@ fn build_filtered_event_lines(state: &AppState, agent_filter: Option<&str>) -> V
                 .unwrap_or_else(|| short_id(aid.as_str()))
         });
 
-        // Line 1: timestamp + icon + header
+        // An extra log's lines take its configured color
+        let event_color = match &event.kind {
+            TranscriptEventKind::ExternalLog { source, .. } => state
+                .meta
+                .extra_logs
+                .iter()
+                .find(|log| &log.name == source)
+                .and_then(|log| log.color.as_deref())
+                .and_then(crate::app::severity::color)
+                .unwrap_or(event_color),
+            _ => event_color,
+        };
+
+        // Line 1: timestamp + icon + header, in the severity style of a matching config rule
+        let event_style = Style::default().fg(event_color);
+        let event_style = match state.ui.severity_rules.style_for(event) {
+            Some(severity) => event_style.patch(severity),
+            None => event_style,
+        };
         let mut header_spans = vec![
             Span::styled(
                 format!("{} ", timestamp),
                 Style::default().fg(Theme::MUTED_TEXT),
             ),
-            Span::styled(format!("{} ", icon), Style::default().fg(event_color)),
-            Span::styled(header, Style::default().fg(event_color)),
+            Span::styled(format!("{} ", icon), event_style),
+            Span::styled(header, event_style),
         ];
 
         // Append agent label if present
@@ -189,6 +281,29 @@ fn build_filtered_event_lines(state: &AppState, agent_filter: Option<&str>) -> V
             ));
         }
 
+        // A call unanswered past the long-call limit
+        if let Some(call) = state.open_tool_call(event).filter(|call| call.overdue) {
+            let secs = (state.meta.clock.now() - call.started_at).num_seconds();
+            header_spans.push(Span::styled(
+                format!("  ⚠ running {}", format_elapsed(secs)),
+                Style::default().fg(Theme::WARNING).add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        // A tool the agent's definition does not allow
+        if let Some(def) = crate::app::allowlist::violation(state, event) {
+            header_spans.push(Span::styled(
+                format!("  ⚠ not allowed for {}", def.name),
+                Style::default().fg(Theme::WARNING).add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        // Subtle provenance marker
+        header_spans.push(Span::styled(
+            format!("  {}", event.origin.glyph()),
+            Style::default().fg(Theme::MUTED_TEXT).add_modifier(Modifier::DIM),
+        ));
+
         lines.push(Line::from(header_spans));
 
         // Line 2+: detail if present, with markdown rendering
@@ -225,6 +340,18 @@ fn build_filtered_event_lines(state: &AppState, agent_filter: Option<&str>) -> V
     lines
 }
 
+/// Separator between two consecutive events, newest first: a dim rule, or a
+/// marker naming the older event's day when the two fall on different days.
+pub fn event_separator(newer: DateTime<Utc>, older: DateTime<Utc>, time_format: &TimeFormat) -> Line<'static> {
+    if time_format.date(newer) != time_format.date(older) {
+        return Line::from(Span::styled(
+            format!("──────────── {} ────────────", time_format.day(older)),
+            Style::default().fg(Theme::MUTED_TEXT).add_modifier(Modifier::BOLD),
+        ));
+    }
+    Line::from(Span::styled("────────────────────────────────", Style::default().fg(Theme::SEPARATOR)))
+}
+
 /// Strip JSON escapes and control chars from detail text for clean display.
 /// Converts escaped newlines (\\n) to actual newlines for diff-style content.
 pub fn clean_detail(s: &str) -> String {
@@ -487,7 +614,7 @@ pub fn own_text_lines(text: ratatui::text::Text<'_>) -> Vec<Line<'static>> {
                 .filter(|s| {
                     // Strip tui_markdown's heading prefix spans ("# ", "## ", etc.)
                     let t = s.content.trim_end();
-                    !(t.chars().all(|c| c == '#') && !t.is_empty())
+                    !t.chars().all(|c| c == '#') || t.is_empty()
                 })
                 .map(|s| {
                     let merged = line_style.patch(s.style);
@@ -509,9 +636,24 @@ fn short_id(id: &str) -> String {
 }
 
 /// Check if a TranscriptEvent matches the search query.
-fn event_matches_search_transcript(kind: &TranscriptEventKind, query: &str, agent_id: Option<&crate::model::AgentId>) -> bool {
+pub(crate) fn event_matches_search_transcript(
+    kind: &TranscriptEventKind,
+    query: &str,
+    agent_id: Option<&crate::model::AgentId>,
+    origin: EventOrigin,
+) -> bool {
+    // `origin:main` / `origin:subagent` / `origin:hook` / `origin:injected` keeps one source's events
+    if let Some(origin_query) = query.strip_prefix("origin:") {
+        return origin.label().starts_with(origin_query);
+    }
+
     let (_, header, detail, _, tool_name) = format_transcript_event_lines(kind);
 
+    // `tool:name` restricts the search to tool events with a matching tool name
+    if let Some(tool_query) = query.strip_prefix("tool:") {
+        return tool_name.is_some_and(|tool| tool.to_lowercase().contains(tool_query));
+    }
+
     if header.to_lowercase().contains(query) {
         return true;
     }
@@ -549,7 +691,7 @@ pub fn format_transcript_event_lines(kind: &TranscriptEventKind) -> (&'static st
             };
             ("⚡", tool_name.to_string(), detail, Theme::tool_color(tool_name.as_str()), Some(tool_name.to_string()))
         }
-        TranscriptEventKind::ToolResult { tool_name, result_summary, duration_ms } => {
+        TranscriptEventKind::ToolResult { tool_name, result_summary, duration_ms, is_error } => {
             let duration_text = duration_ms
                 .map(|ms| format!(" ({}ms)", ms))
                 .unwrap_or_default();
@@ -559,8 +701,31 @@ pub fn format_transcript_event_lines(kind: &TranscriptEventKind) -> (&'static st
             } else {
                 Some(result_summary.clone())
             };
+            if *is_error {
+                return ("✗", header, detail, Theme::ERROR, Some(tool_name.to_string()));
+            }
             ("✓", header, detail, Theme::tool_color(tool_name.as_str()), Some(tool_name.to_string()))
         }
+        TranscriptEventKind::ExternalLog { source, line } => {
+            ("▤", format!("{}  {}", source, line), None, Theme::TEXT, None)
+        }
+        TranscriptEventKind::ConfigChanged { file, change } => {
+            ("✎", format!("{} {}", file, change), None, Theme::WARNING, None)
+        }
+        TranscriptEventKind::StatusUpdate { text, task_id } => {
+            let header = match task_id {
+                Some(task) => format!("{} {}", task, crate::app::progress::status_text(text)),
+                None => crate::app::progress::status_text(text).to_string(),
+            };
+            ("◔", header, None, Theme::ACCENT, None)
+        }
+        TranscriptEventKind::ApiError { error_kind, message, status } => {
+            let header = match status {
+                Some(status) => format!("API {} ({})", error_kind.label(), status),
+                None => format!("API {}", error_kind.label()),
+            };
+            ("⛔", header, Some(message.clone()).filter(|m| !m.is_empty()), Theme::ERROR, None)
+        }
         TranscriptEventKind::Unknown { entry_type } => {
             ("?", entry_type.clone(), None, Theme::MUTED_TEXT, None)
         }
@@ -587,6 +752,46 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn render_event_stream_shows_new_events_pill_when_scrolled_away() {
+        let backend = ratatui::backend::TestBackend::new(60, 10);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+
+        let mut state = AppState::new();
+        state.ui.auto_scroll = false;
+        state.ui.unseen_events.event_stream = 12;
+
+        let result = terminal
+            .draw(|frame| render_event_stream(frame, frame.area(), &state))
+            .unwrap();
+        let text: String = result.buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("▲ 12 new events · g"));
+    }
+
+    #[test]
+    fn selected_channel_narrows_the_stream_under_a_tab_row() {
+        use crate::app::settings::{self, Settings};
+        use crate::model::{ToolName, TranscriptEvent};
+
+        let mut state = AppState::new();
+        let settings = Settings { channels: vec!["tests: tool:Bash detail~\"cargo test\"".into()], ..Settings::default() };
+        settings::apply(&mut state, &settings);
+        for input in ["cargo test --workspace", "cargo build"] {
+            state.domain.events.push_back(TranscriptEvent::new(
+                Utc::now(),
+                TranscriptEventKind::ToolUse { tool_name: ToolName::new("Bash"), input_summary: input.into() },
+            ));
+        }
+        state.ui.event_channels.cycle(true);
+
+        let backend = ratatui::backend::TestBackend::new(80, 20);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        let result = terminal.draw(|frame| render_event_stream(frame, frame.area(), &state)).unwrap();
+        let text: String = result.buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(text.starts_with("  All   tests  c: channel"), "{text}");
+        assert!(text.contains("cargo test --workspace") && !text.contains("cargo build"));
+    }
+
     #[test]
     fn build_event_stream_items_shows_no_events_when_empty() {
         let state = AppState::new();
@@ -613,6 +818,44 @@ mod tests {
         assert_eq!(lines.len(), 3);
     }
 
+    #[test]
+    fn severity_rule_styles_the_matching_event_header() {
+        use crate::app::severity::SeverityRules;
+        use crate::model::{ToolName, TranscriptEvent, TranscriptEventKind};
+
+        let mut state = AppState::new();
+        state.ui.severity_rules = SeverityRules::from_config(&["bold red: detail~\"DROP TABLE\"".to_string()]).0;
+        let bash = |command: &str| {
+            TranscriptEvent::new(
+                Utc::now(),
+                TranscriptEventKind::ToolUse { tool_name: ToolName::new("Bash"), input_summary: command.into() },
+            )
+        };
+        state.domain.events = VecDeque::from(vec![bash("ls"), bash("psql -c 'DROP TABLE users'")]);
+
+        let lines = build_filtered_event_lines(&state, None);
+        let headers: Vec<&Line> = lines.iter().filter(|line| line.spans.len() > 2 && line.spans[2].content.contains("Bash")).collect();
+
+        // Newest first: the DROP TABLE call, then ls
+        assert_eq!(headers[0].spans[2].style.fg, Some(Theme::ERROR));
+        assert!(headers[0].spans[2].style.add_modifier.contains(Modifier::BOLD));
+        assert!(!headers[1].spans[2].style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn extra_log_lines_take_the_configured_color() {
+        use crate::watcher::logs::{log_event, ExtraLog};
+
+        let mut state = AppState::new();
+        state.meta.extra_logs =
+            vec![ExtraLog { name: "devserver".into(), path: "dev.log".into(), color: Some("purple".into()) }];
+        state.domain.events = VecDeque::from(vec![log_event("devserver", "GET / 200".into())]);
+
+        let lines = build_filtered_event_lines(&state, None);
+        assert!(lines[0].spans[2].content.contains("devserver  GET / 200"));
+        assert_eq!(lines[0].spans[2].style.fg, Some(Theme::ACCENT_PURPLE));
+    }
+
     #[test]
     fn format_transcript_event_user_message() {
         let (icon, header, _, _, _) = format_transcript_event_lines(&TranscriptEventKind::UserMessage);
@@ -637,6 +880,7 @@ mod tests {
             tool_name: "Bash".into(),
             result_summary: "success".to_string(),
             duration_ms: Some(250),
+            is_error: false,
         });
         assert!(header.contains("Bash"));
         assert!(header.contains("250ms"));
@@ -788,6 +1032,37 @@ mod tests {
         assert!(header_text.contains("Explore"));
     }
 
+    #[test]
+    fn calls_outside_the_agents_allowlist_are_flagged() {
+        use crate::app::agent_defs::AgentDefinition;
+        use crate::model::{Agent, TranscriptEvent, TranscriptEventKind};
+
+        let mut state = AppState::new();
+        let mut agent = Agent::new("a01", Utc::now());
+        agent.agent_type = Some("code-reviewer".into());
+        state.domain.agents.insert("a01".into(), agent);
+        state.domain.agent_definitions = vec![AgentDefinition {
+            name: "code-reviewer".into(),
+            description: None,
+            model: None,
+            tools: Some(vec!["Read".into()]),
+            path: ".claude/agents/code-reviewer.md".into(),
+        }];
+        let call = |tool: &str| {
+            TranscriptEvent::new(Utc::now(), TranscriptEventKind::ToolUse { tool_name: tool.into(), input_summary: String::new() })
+                .with_agent("a01")
+        };
+        state.domain.events = VecDeque::from(vec![call("Read"), call("Bash")]);
+
+        let headers: Vec<String> = build_filtered_event_lines(&state, None)
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .filter(|text: &String| text.contains("Read") || text.contains("Bash"))
+            .collect();
+        assert!(headers[0].contains("Bash") && headers[0].contains("⚠ not allowed for code-reviewer"), "{headers:?}");
+        assert!(!headers[1].contains("not allowed"));
+    }
+
     #[test]
     fn event_matches_search_transcript_tool_use() {
         use crate::model::TranscriptEventKind;
@@ -795,9 +1070,9 @@ mod tests {
             tool_name: "Read".into(),
             input_summary: "my_file.rs".to_string(),
         };
-        assert!(event_matches_search_transcript(&kind, "read", None));
-        assert!(event_matches_search_transcript(&kind, "my_file", None));
-        assert!(!event_matches_search_transcript(&kind, "write", None));
+        assert!(event_matches_search_transcript(&kind, "read", None, EventOrigin::MainTranscript));
+        assert!(event_matches_search_transcript(&kind, "my_file", None, EventOrigin::MainTranscript));
+        assert!(!event_matches_search_transcript(&kind, "write", None, EventOrigin::MainTranscript));
     }
 
     #[test]
@@ -806,8 +1081,8 @@ mod tests {
         let kind = TranscriptEventKind::AssistantMessage {
             content: "Here is the analysis".to_string(),
         };
-        assert!(event_matches_search_transcript(&kind, "analysis", None));
-        assert!(!event_matches_search_transcript(&kind, "other", None));
+        assert!(event_matches_search_transcript(&kind, "analysis", None, EventOrigin::MainTranscript));
+        assert!(!event_matches_search_transcript(&kind, "other", None, EventOrigin::MainTranscript));
     }
 
     #[test]
@@ -815,8 +1090,8 @@ mod tests {
         use crate::model::TranscriptEventKind;
         let kind = TranscriptEventKind::UserMessage;
         // "User message" is the header
-        assert!(event_matches_search_transcript(&kind, "user", None));
-        assert!(event_matches_search_transcript(&kind, "message", None));
+        assert!(event_matches_search_transcript(&kind, "user", None, EventOrigin::MainTranscript));
+        assert!(event_matches_search_transcript(&kind, "message", None, EventOrigin::MainTranscript));
     }
 
     #[test]
@@ -824,9 +1099,59 @@ mod tests {
         use crate::model::TranscriptEventKind;
         let kind = TranscriptEventKind::UserMessage;
         let agent_id = crate::model::AgentId::new("explore-agent-123");
-        assert!(event_matches_search_transcript(&kind, "explore", Some(&agent_id)));
-        assert!(event_matches_search_transcript(&kind, "123", Some(&agent_id)));
-        assert!(!event_matches_search_transcript(&kind, "write", Some(&agent_id)));
+        assert!(event_matches_search_transcript(&kind, "explore", Some(&agent_id), EventOrigin::MainTranscript));
+        assert!(event_matches_search_transcript(&kind, "123", Some(&agent_id), EventOrigin::MainTranscript));
+        assert!(!event_matches_search_transcript(&kind, "write", Some(&agent_id), EventOrigin::MainTranscript));
+    }
+
+    #[test]
+    fn event_matches_search_transcript_tool_qualifier() {
+        use crate::model::TranscriptEventKind;
+        let bash = TranscriptEventKind::ToolUse {
+            tool_name: "Bash".into(),
+            input_summary: "cat README".to_string(),
+        };
+        let read = TranscriptEventKind::ToolUse {
+            tool_name: "Read".into(),
+            input_summary: "bash_helpers.sh".to_string(),
+        };
+        assert!(event_matches_search_transcript(&bash, "tool:bash", None, EventOrigin::MainTranscript));
+        assert!(!event_matches_search_transcript(&read, "tool:bash", None, EventOrigin::MainTranscript));
+        assert!(!event_matches_search_transcript(&TranscriptEventKind::UserMessage, "tool:", None, EventOrigin::MainTranscript));
+    }
+
+    #[test]
+    fn event_separator_marks_day_rollover() {
+        let time_format = TimeFormat { utc: true, hour12: false };
+        let late: DateTime<Utc> = "2026-03-18T00:00:05Z".parse().unwrap();
+        let text = |line: Line<'static>| line.spans.iter().map(|s| s.content.to_string()).collect::<String>();
+
+        let rollover = text(event_separator(late, late - chrono::Duration::seconds(10), &time_format));
+        assert!(rollover.contains("Tue 2026-03-17"), "{}", rollover);
+        let same_day = text(event_separator(late, late - chrono::Duration::seconds(1), &time_format));
+        assert!(same_day.chars().all(|c| c == '─'), "{}", same_day);
+    }
+
+    #[test]
+    fn duplicate_summary_counts_by_origin() {
+        use crate::model::{EventOrigin, TranscriptEvent};
+        let mut state = AppState::new();
+        assert_eq!(duplicate_summary(&state), None);
+
+        let event = |origin| {
+            TranscriptEvent::new(chrono::Utc::now(), TranscriptEventKind::UserMessage).with_origin(origin)
+        };
+        state.domain.dedup.observe(&event(EventOrigin::Hook));
+        state.domain.dedup.observe(&event(EventOrigin::MainTranscript));
+        assert_eq!(duplicate_summary(&state).as_deref(), Some(" 1 duplicates dropped (main 1) "));
+    }
+
+    #[test]
+    fn event_matches_search_transcript_origin_qualifier() {
+        let kind = TranscriptEventKind::UserMessage;
+        assert!(event_matches_search_transcript(&kind, "origin:hook", None, EventOrigin::Hook));
+        assert!(event_matches_search_transcript(&kind, "origin:sub", None, EventOrigin::SubagentTranscript));
+        assert!(!event_matches_search_transcript(&kind, "origin:main", None, EventOrigin::Hook));
     }
 
     #[test]
@@ -836,9 +1161,9 @@ mod tests {
             tool_name: "Read".into(),
             input_summary: "file[1].rs".to_string(),
         };
-        let _ = event_matches_search_transcript(&kind, "a.*[b]", None);
-        let _ = event_matches_search_transcript(&kind, "[1]", None);
-        let _ = event_matches_search_transcript(&kind, "(test)", None);
+        let _ = event_matches_search_transcript(&kind, "a.*[b]", None, EventOrigin::MainTranscript);
+        let _ = event_matches_search_transcript(&kind, "[1]", None, EventOrigin::MainTranscript);
+        let _ = event_matches_search_transcript(&kind, "(test)", None, EventOrigin::MainTranscript);
     }
 
     #[test]
@@ -848,8 +1173,8 @@ mod tests {
             tool_name: "Read".into(),
             input_summary: "日本語.rs".to_string(),
         };
-        assert!(event_matches_search_transcript(&kind, "日本", None));
-        assert!(event_matches_search_transcript(&kind, "本語", None));
-        assert!(!event_matches_search_transcript(&kind, "中文", None));
+        assert!(event_matches_search_transcript(&kind, "日本", None, EventOrigin::MainTranscript));
+        assert!(event_matches_search_transcript(&kind, "本語", None, EventOrigin::MainTranscript));
+        assert!(!event_matches_search_transcript(&kind, "中文", None, EventOrigin::MainTranscript));
     }
 }
@@ -6,9 +6,19 @@ use ratatui::{
     Frame,
 };
 
+use crate::app::filters;
 use crate::app::state::AppState;
 use crate::model::theme::Theme;
 
+/// Chip for the titles of panels the applied filter narrows: ` /auth  x clears `.
+pub fn filter_chip(state: &AppState) -> Option<Line<'static>> {
+    let filter = state.ui.filter.as_deref().filter(|f| !f.is_empty() && !filters::editing(&state.ui))?;
+    Some(Line::from(vec![
+        Span::styled(format!(" /{} ", filter), Style::default().fg(Theme::BACKGROUND).bg(Theme::INFO)),
+        Span::styled(" x clears ", Style::default().fg(Theme::MUTED_TEXT)),
+    ]))
+}
+
 /// Render the filter/search bar overlay.
 /// Displayed at bottom of screen when filter is active.
 /// Shows "/ " prefix with current filter text and cursor.
@@ -119,4 +129,15 @@ mod tests {
         // Check for cursor character
         assert!(buffer_str.contains("█"), "Cursor should be displayed");
     }
+
+    #[test]
+    fn chip_shows_only_the_applied_filter() {
+        let mut state = AppState::new();
+        state.ui.filter = Some("auth".to_string());
+        assert!(filter_chip(&state).is_none(), "no chip while the bar is open");
+
+        filters::apply(&mut state.ui);
+        let chip: String = filter_chip(&state).unwrap().spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(chip, " /auth  x clears ");
+    }
 }
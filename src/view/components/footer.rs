@@ -6,7 +6,7 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::{AppState, ViewState};
+use crate::app::{AppState, TokenDashboardTab, ViewState};
 use crate::model::Theme;
 
 /// Render footer status bar with keybinding hints.
@@ -44,19 +44,39 @@ fn build_footer_text(state: &AppState) -> Line<'static> {
     // Navigation group
     spans.extend(kb("q", ":quit"));
 
+    if state.ui.zoomed {
+        spans.push(sep());
+        spans.extend(kb("z", ":restore"));
+    }
+
     match &state.ui.view {
         ViewState::Dashboard => {
             spans.push(sep());
-            spans.extend(kb("1-4", ":views"));
+            spans.extend(kb("1-6", ":views"));
             spans.push(sep());
             spans.extend(kb("Tab", ":focus "));
+            spans.extend(kb("z", ":zoom "));
             spans.extend(kb("j/k", ":scroll "));
             spans.extend(kb("g/G", ":top/bottom"));
             spans.push(sep());
             spans.extend(kb("Enter", ":drill "));
             spans.extend(kb("p", ":popup "));
+            spans.extend(kb("D", ":diff "));
+            if !state.domain.task_failures.is_empty() {
+                spans.extend(kb("E", ":evidence "));
+            }
             spans.extend(kb("v", ":kanban "));
+            spans.extend(kb("|", ":split "));
+            if state.domain.spec_doc.is_some() {
+                spans.extend(kb("S", ":spec "));
+            }
             spans.extend(kb("/", ":search "));
+            if !state.ui.event_channels.channels().is_empty() {
+                spans.extend(kb("c", ":channel "));
+            }
+            spans.extend(kb("^P", ":go "));
+            spans.extend(kb(":", ":cmd "));
+            spans.extend(kb(",", ":settings "));
             spans.extend(kb("?", ":help"));
         }
         ViewState::AgentDetail => {
@@ -67,6 +87,10 @@ fn build_footer_text(state: &AppState) -> Line<'static> {
             spans.extend(kb("j/k", ":scroll "));
             spans.extend(kb("g/G", ":top/bottom"));
             spans.push(sep());
+            spans.extend(kb("p", ":stats "));
+            spans.extend(kb("T", ":group "));
+            spans.extend(kb("t", ":type"));
+            spans.push(sep());
             spans.extend(kb("?", ":help"));
         }
         ViewState::Sessions => {
@@ -77,7 +101,13 @@ fn build_footer_text(state: &AppState) -> Line<'static> {
             spans.extend(kb("g/G", ":top/bottom "));
             spans.extend(kb("Enter", ":detail "));
             spans.extend(kb("Space", ":mark "));
-            spans.extend(kb("d", ":delete"));
+            spans.extend(kb("P", ":project "));
+            spans.extend(kb("O", ":outcome "));
+            spans.extend(kb("R", ":runs "));
+            spans.extend(kb("[/]", ":day "));
+            spans.extend(kb("</>", ":width "));
+            spans.extend(kb("d", ":delete "));
+            spans.extend(kb("u", ":undo"));
             spans.push(sep());
             spans.extend(kb("?", ":help"));
         }
@@ -95,10 +125,40 @@ fn build_footer_text(state: &AppState) -> Line<'static> {
             spans.push(sep());
             spans.extend(kb("Esc", ":back"));
             spans.push(sep());
-            spans.extend(kb("Tab", ":focus "));
+            if state.ui.token_dashboard_tab == TokenDashboardTab::Cost {
+                spans.extend(kb("c", ":sessions "));
+                spans.extend(kb("w", ":day/week "));
+                spans.extend(kb(":export csv", " for CSV"));
+            } else {
+                spans.extend(kb("Tab", ":focus "));
+                spans.extend(kb("j/k", ":scroll "));
+                spans.extend(kb("g/G", ":top/bottom "));
+                spans.extend(kb("c", ":cost"));
+            }
+            spans.push(sep());
+            spans.extend(kb("?", ":help"));
+        }
+        ViewState::Review => {
+            spans.push(sep());
+            spans.extend(kb("Esc", ":back"));
+            spans.push(sep());
             spans.extend(kb("j/k", ":scroll "));
             spans.extend(kb("g/G", ":top/bottom"));
             spans.push(sep());
+            spans.extend(kb("a", ":approve "));
+            spans.extend(kb("r", ":reject"));
+            spans.push(sep());
+            spans.extend(kb("?", ":help"));
+        }
+        ViewState::Timeline => {
+            spans.push(sep());
+            spans.extend(kb("Esc", ":back"));
+            spans.push(sep());
+            spans.extend(kb("j/k", ":scroll "));
+            spans.extend(kb("g/G", ":top/bottom"));
+            spans.push(sep());
+            spans.extend(kb("/", ":search"));
+            spans.push(sep());
             spans.extend(kb("?", ":help"));
         }
     }
@@ -141,7 +201,7 @@ mod tests {
         let line = build_footer_text(&state);
         let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
 
-        assert!(text.contains("1-4:views"));
+        assert!(text.contains("1-6:views"));
         assert!(text.contains("Tab:focus"));
         assert!(text.contains("j/k:scroll"));
         assert!(text.contains("g/G:top/bottom"));
@@ -1,5 +1,73 @@
 use std::time::Duration;
 
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
+/// How every view shows timestamps: local time zone or UTC, 24- or 12-hour clock.
+/// Set from the settings overlay; views call these instead of `format("%H:%M:%S")`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeFormat {
+    pub utc: bool,
+    pub hour12: bool,
+}
+
+impl Default for TimeFormat {
+    fn default() -> Self {
+        Self { utc: false, hour12: locale_prefers_12h() }
+    }
+}
+
+impl TimeFormat {
+    /// Time of day with seconds: `14:03:07` / `2:03:07 PM`.
+    pub fn time(&self, t: DateTime<Utc>) -> String {
+        self.render(t, "%H:%M:%S", "%-I:%M:%S %p")
+    }
+
+    /// Date and time to the minute: `2026-03-18 14:03` / `2026-03-18 2:03 PM`.
+    pub fn date_time(&self, t: DateTime<Utc>) -> String {
+        self.render(t, "%Y-%m-%d %H:%M", "%Y-%m-%d %-I:%M %p")
+    }
+
+    /// Date and time with seconds, and the zone when it's UTC.
+    pub fn date_time_secs(&self, t: DateTime<Utc>) -> String {
+        let full = self.render(t, "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %-I:%M:%S %p");
+        if self.utc { format!("{} UTC", full) } else { full }
+    }
+
+    /// Compact date and time for tables: `03-18 14:03`.
+    pub fn short_date_time(&self, t: DateTime<Utc>) -> String {
+        self.render(t, "%m-%d %H:%M", "%m-%d %-I:%M %p")
+    }
+
+    /// Day heading for rollover markers: `Wed 2026-03-18`.
+    pub fn day(&self, t: DateTime<Utc>) -> String {
+        self.render(t, "%a %Y-%m-%d", "%a %Y-%m-%d")
+    }
+
+    /// Calendar day `t` falls on in the display zone.
+    pub fn date(&self, t: DateTime<Utc>) -> NaiveDate {
+        if self.utc { t.date_naive() } else { t.with_timezone(&Local).date_naive() }
+    }
+
+    fn render(&self, t: DateTime<Utc>, fmt24: &str, fmt12: &str) -> String {
+        let fmt = if self.hour12 { fmt12 } else { fmt24 };
+        if self.utc {
+            t.format(fmt).to_string()
+        } else {
+            t.with_timezone(&Local).format(fmt).to_string()
+        }
+    }
+}
+
+/// Whether the user's locale (`LC_ALL`, `LC_TIME`, then `LANG`) uses a 12-hour clock.
+pub fn locale_prefers_12h() -> bool {
+    let locale = ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    ["en_US", "en_CA", "en_AU", "en_NZ", "en_PH", "en_IN"].iter().any(|prefix| locale.starts_with(prefix))
+}
+
 /// Format elapsed seconds as human-readable string.
 /// - < 60s: "Xs"
 /// - < 3600s: "XmYs"
@@ -58,6 +126,22 @@ pub fn format_token_count(n: u64) -> String {
     }
 }
 
+/// Format a byte count for compact display: 512B, 48KB, 1.2MB.
+pub fn format_bytes(n: u64) -> String {
+    if n >= 1024 * 1024 {
+        let m = n as f64 / (1024.0 * 1024.0);
+        if m >= 10.0 {
+            format!("{}MB", m as u64)
+        } else {
+            format!("{:.1}MB", m)
+        }
+    } else if n >= 1024 {
+        format!("{}KB", n / 1024)
+    } else {
+        format!("{}B", n)
+    }
+}
+
 /// Format cost in cents as USD string: 0 → "$0.00", 123 → "$1.23", 1234 → "$12.34"
 pub fn format_cost_usd(cents: u64) -> String {
     let dollars = cents / 100;
@@ -69,6 +153,20 @@ pub fn format_cost_usd(cents: u64) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn time_format_switches_clock_and_zone() {
+        let t: DateTime<Utc> = "2026-03-18T14:03:07Z".parse().unwrap();
+        let utc24 = TimeFormat { utc: true, hour12: false };
+        let utc12 = TimeFormat { utc: true, hour12: true };
+        assert_eq!(utc24.time(t), "14:03:07");
+        assert_eq!(utc12.time(t), "2:03:07 PM");
+        assert_eq!(utc24.date_time(t), "2026-03-18 14:03");
+        assert_eq!(utc24.date_time_secs(t), "2026-03-18 14:03:07 UTC");
+        assert_eq!(utc12.short_date_time(t), "03-18 2:03 PM");
+        assert_eq!(utc24.day(t), "Wed 2026-03-18");
+        assert_eq!(utc24.date(t), t.date_naive());
+    }
+
     #[test]
     fn format_elapsed_zero() {
         assert_eq!(format_elapsed(0), "0s");
@@ -147,6 +245,13 @@ mod tests {
         assert_eq!(format_token_count(15_000_000), "15M");
     }
 
+    #[test]
+    fn format_bytes_scales_units() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(48 * 1024 + 100), "48KB");
+        assert_eq!(format_bytes(1_258_291), "1.2MB");
+    }
+
     #[test]
     fn format_cost_usd_zero() {
         assert_eq!(format_cost_usd(0), "$0.00");
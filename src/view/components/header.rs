@@ -6,9 +6,15 @@ use ratatui::{
     Frame,
 };
 
+
+use crate::app::budget::BudgetStatus;
+use crate::app::cost;
 use crate::app::{AppState, ViewState};
-use crate::model::Theme;
-use super::format::format_elapsed;
+use crate::model::{ApiErrorKind, Phase, SessionMeta, SessionStatus, Theme};
+use super::format::{format_cost_usd, format_elapsed};
+
+/// Header space is shared with progress counters; keep the goal short.
+const HEADER_GOAL_MAX_CHARS: usize = 50;
 
 /// Render header bar.
 /// Shows: view indicator, wave, task progress, agents, elapsed time.
@@ -25,10 +31,22 @@ pub fn render_header(frame: &mut Frame, area: Rect, state: &AppState) {
     frame.render_widget(header, area);
 }
 
+/// Render the heads-up summary line (see `app::summary`).
+pub fn render_summary(frame: &mut Frame, area: Rect, summary: &str) {
+    let line = Paragraph::new(format!(" {}", summary))
+        .style(Style::default().fg(Theme::MUTED_TEXT).bg(Theme::HEADER_BG));
+    frame.render_widget(line, area);
+}
+
 /// Pure function: build header text from state.
 fn build_header_text(state: &AppState) -> Line<'static> {
     let active_agents = state.domain.agents.values().filter(|a| a.finished_at.is_none()).count();
-    let elapsed = format_elapsed(state.meta.started_at.elapsed().as_secs() as i64);
+    let mut elapsed_secs = state.meta.clock.instant().saturating_duration_since(state.meta.started_at).as_secs() as i64;
+    if state.ui.low_bandwidth {
+        // A per-second clock would rewrite the header on every tick
+        elapsed_secs -= elapsed_secs % 60;
+    }
+    let elapsed = format_elapsed(elapsed_secs);
 
     let view_indicator = match state.ui.view {
         ViewState::Dashboard => "[1:Dashboard]",
@@ -36,6 +54,8 @@ fn build_header_text(state: &AppState) -> Line<'static> {
         ViewState::Sessions => "[3:Sessions]",
         ViewState::SessionDetail => "[3:Session Detail]",
         ViewState::TokenDashboard => "[4:Tokens]",
+        ViewState::Review => "[5:Review]",
+        ViewState::Timeline => "[6:Timeline]",
     };
 
     let project_name = if state.meta.project_path.is_empty() {
@@ -71,6 +91,9 @@ fn build_header_text(state: &AppState) -> Line<'static> {
                 format!("  {}", progress),
                 Style::default().fg(Theme::SUCCESS),
             ));
+            if let Some(phase) = graph.phase {
+                spans.extend(phase_spans(state, phase));
+            }
         }
         None => {
             spans.push(Span::styled(
@@ -80,6 +103,55 @@ fn build_header_text(state: &AppState) -> Line<'static> {
         }
     }
 
+    if state.meta.watchdog.is_degraded() {
+        spans.push(Span::styled(
+            "  ● watcher degraded",
+            Style::default().fg(Theme::ERROR),
+        ));
+    }
+
+    let bursts = state.domain.error_bursts.bursting(&state.meta.error_burst);
+    if let Some((sid, failures)) = bursts.first() {
+        let more = if bursts.len() > 1 { format!(" +{}", bursts.len() - 1) } else { String::new() };
+        spans.push(Span::styled(
+            format!(
+                "  ⚠ error burst {} ({} failures/{}){}",
+                sid.as_str().chars().take(8).collect::<String>(),
+                failures,
+                state.meta.error_burst.window_label(),
+                more
+            ),
+            Style::default().fg(Theme::ERROR),
+        ));
+    }
+
+    let api_errors = state.domain.api_health.total();
+    if api_errors > 0 {
+        let counts = state.domain.api_health.counts();
+        let kinds: Vec<String> = counts.iter().map(|(kind, n)| format!("{} {}", n, kind.label())).collect();
+        let color = if counts.contains_key(&ApiErrorKind::ContextLength) { Theme::ERROR } else { Theme::WARNING };
+        spans.push(Span::styled(
+            format!("  ⛔ API {}/{}: {}", api_errors, state.domain.api_health.window_label(), kinds.join(", ")),
+            Style::default().fg(color),
+        ));
+    }
+
+    let sampled = state.domain.sampler.sampling();
+    if sampled > 0 {
+        let more = if sampled > 1 { format!(" ({} sessions)", sampled) } else { String::new() };
+        spans.push(Span::styled(
+            format!("  ⇊ sampling {}{}", state.domain.sampler.threshold().label(), more),
+            Style::default().fg(Theme::WARNING),
+        ));
+    }
+
+    if !state.domain.pending_plans.is_empty() {
+        spans.push(Span::styled(
+            "  ⏸ plan awaiting approval",
+            Style::default().fg(Theme::WARNING),
+        ));
+    }
+
     if active_agents > 0 {
         spans.push(Span::styled(
             format!("  {} agents", active_agents),
@@ -92,9 +164,134 @@ fn build_header_text(state: &AppState) -> Line<'static> {
         Style::default().fg(Theme::MUTED_TEXT),
     ));
 
+    let focused = focused_session(state);
+    if let Some(status) = focused.and_then(|meta| budget_status(state, meta)) {
+        let color = if status.is_over() { Theme::ERROR } else { Theme::MUTED_TEXT };
+        spans.push(Span::styled(format!("  ⏱ {}", status.label()), Style::default().fg(color)));
+    }
+
+    spans.extend(spend_spans(state, focused));
+
+    if let Some(release) = &state.meta.newer_release {
+        spans.push(Span::styled(
+            format!("  ↑ v{} available (:changelog)", release.version),
+            Style::default().fg(Theme::INFO).remove_modifier(Modifier::BOLD),
+        ));
+    }
+
+    if state.ui.low_bandwidth {
+        spans.push(Span::styled("  low-bandwidth", Style::default().fg(Theme::MUTED_TEXT)));
+    }
+
+    if let Some(goal) = focused.and_then(|meta| meta.goal_summary(HEADER_GOAL_MAX_CHARS)) {
+        spans.push(Span::styled(
+            format!("  {}", goal),
+            Style::default().fg(Theme::TEXT).remove_modifier(Modifier::BOLD),
+        ));
+    }
+
     Line::from(spans)
 }
 
+/// Pure function: the Plan → Execute → Review stepper, with the time spent
+/// in each phase seen while loom-tui was running.
+fn phase_spans(state: &AppState, current: Phase) -> Vec<Span<'static>> {
+    let entered = &state.domain.phase_started_at;
+    let clock = &*state.meta.clock;
+    let time_in = |step: Phase| {
+        let idx = entered.iter().rposition(|(phase, _)| *phase == step)?;
+        let since = entered[idx].1.elapsed(clock);
+        let mut secs = match entered.get(idx + 1) {
+            // Rounded: the two stamps are read a moment apart
+            Some((_, next)) => ((since - next.elapsed(clock)).num_milliseconds() + 500) / 1000,
+            None => since.num_seconds(),
+        };
+        if state.ui.low_bandwidth && idx + 1 == entered.len() {
+            secs -= secs % 60;
+        }
+        Some(format_elapsed(secs.max(0)))
+    };
+
+    let mut spans = vec![Span::raw("  ")];
+    for (i, step) in Phase::STEPS.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(" → ", Style::default().fg(Theme::MUTED_TEXT)));
+        }
+        let (label, color) = match step.cmp(&current) {
+            std::cmp::Ordering::Less => (format!("✓{}", step.label()), Theme::SUCCESS),
+            std::cmp::Ordering::Equal => (format!("▶{}", step.label()), Theme::ACCENT_WARM),
+            std::cmp::Ordering::Greater => (step.label().to_string(), Theme::MUTED_TEXT),
+        };
+        spans.push(Span::styled(label, Style::default().fg(color)));
+        if let Some(time) = time_in(step).filter(|_| step <= current) {
+            spans.push(Span::styled(
+                format!(" {}", time),
+                Style::default().fg(Theme::MUTED_TEXT).remove_modifier(Modifier::BOLD),
+            ));
+        }
+    }
+    spans
+}
+
+/// Session in focus: the pinned session in Session Detail,
+/// otherwise the most recently started confirmed active session.
+fn focused_session(state: &AppState) -> Option<&SessionMeta> {
+    match (&state.ui.view, &state.ui.selected_session_id) {
+        (ViewState::SessionDetail, Some(id)) => state
+            .domain
+            .active_sessions
+            .get(id)
+            .or_else(|| state.domain.sessions.iter().find(|s| &s.meta.id == id).map(|s| &s.meta)),
+        _ => state
+            .domain
+            .confirmed_active_sessions()
+            .map(|(_, m)| m)
+            .max_by_key(|m| m.timestamp),
+    }
+}
+
+/// Spend against the USD budgets that are set: the focused live session's, then today's.
+fn spend_spans(state: &AppState, focused: Option<&SessionMeta>) -> Vec<Span<'static>> {
+    let budget = &state.meta.cost_budget;
+    let mut parts = Vec::new();
+    if let (Some(limit), Some(meta)) = (budget.session_cents, focused) {
+        let live = cost::live_session_agents(state);
+        if let Some((_, meta, agents)) = live.iter().find(|(sid, _, _)| **sid == meta.id) {
+            parts.push((cost::live_session_cost(meta, agents), limit, ""));
+        }
+    }
+    if let Some(limit) = budget.daily_cents {
+        let spent = state.domain.daily_spend.total_on(state.meta.clock.now().with_timezone(&chrono::Local).date_naive());
+        parts.push((spent, limit, " today"));
+    }
+
+    parts
+        .into_iter()
+        .map(|(spent, limit, suffix)| {
+            let color = if spent >= limit {
+                Theme::ERROR
+            } else if spent * 100 >= limit * 80 {
+                Theme::WARNING
+            } else {
+                Theme::MUTED_TEXT
+            };
+            Span::styled(
+                format!("  {}/{}{}", format_cost_usd(spent), format_cost_usd(limit), suffix),
+                Style::default().fg(color),
+            )
+        })
+        .collect()
+}
+
+/// Countdown against the configured budget, for a session that is still running.
+fn budget_status(state: &AppState, meta: &SessionMeta) -> Option<BudgetStatus> {
+    if meta.status != SessionStatus::Active {
+        return None;
+    }
+    let budget = state.meta.budget.for_session(state.domain.task_graph.is_some())?;
+    Some(BudgetStatus::of(budget, meta.elapsed(&*state.meta.clock)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,6 +362,27 @@ mod tests {
         assert!(text.contains("1 agents"));
     }
 
+    #[test]
+    fn build_header_text_shows_phase_stepper_with_timing() {
+        use crate::model::{Clock, ManualClock, MonoStamp, Phase};
+        use std::sync::Arc;
+
+        let clock = ManualClock::new(chrono::Utc::now());
+        let mut state = AppState::new().with_clock(Arc::new(clock.clone()));
+        let graph = TaskGraph::new(vec![Wave::new(1, vec![Task::new("T1", "a".into(), TaskStatus::Running)])]);
+        state.domain.task_graph = Some(graph.with_phase(Some(Phase::Execute)));
+        let plan = MonoStamp::observe(clock.now(), &clock);
+        clock.advance(chrono::Duration::minutes(3));
+        let execute = MonoStamp::observe(clock.now(), &clock);
+        clock.advance(chrono::Duration::minutes(2));
+        state.domain.phase_started_at = vec![(Phase::Plan, plan), (Phase::Execute, execute)];
+
+        let line = build_header_text(&state);
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+
+        assert!(text.contains("✓Plan 3m0s → ▶Execute 2m0s → Review"), "{text}");
+    }
+
     #[test]
     fn build_header_text_shows_active_agents() {
         use crate::model::Agent;
@@ -234,4 +452,87 @@ mod tests {
         // Should have some elapsed time indicator (0s or 1s)
         assert!(text.contains('s'), "Should contain elapsed seconds");
     }
+
+    #[test]
+    fn build_header_text_shows_active_session_goal() {
+        use crate::model::{SessionId, SessionMeta};
+        use chrono::Utc;
+
+        let mut state = AppState::new();
+        let mut meta = SessionMeta::new("s1", Utc::now(), "/proj".to_string())
+            .with_goal("Refactor the\nbilling module".to_string());
+        meta.confirmed = true;
+        state.domain.active_sessions.insert(SessionId::new("s1"), meta);
+
+        let line = build_header_text(&state);
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(text.contains("Refactor the billing module"), "got: {}", text);
+    }
+
+    #[test]
+    fn build_header_text_counts_down_and_flags_overtime() {
+        use crate::app::budget::DurationBudget;
+        use crate::model::SessionId;
+
+        let mut state = AppState::new();
+        state.meta.budget = DurationBudget { session: Some(chrono::Duration::minutes(45)), orchestration: None };
+        let mut meta = SessionMeta::new("s1", chrono::Utc::now() - chrono::Duration::seconds(33 * 60 + 30), "/proj".to_string());
+        meta.confirmed = true;
+        state.domain.active_sessions.insert(SessionId::new("s1"), meta);
+
+        let text = |state: &AppState| build_header_text(state).spans.iter().map(|s| s.content.to_string()).collect::<String>();
+        assert!(text(&state).contains("⏱ 11m left"), "got: {}", text(&state));
+
+        state.meta.budget.session = Some(chrono::Duration::minutes(20));
+        assert!(text(&state).contains("⏱ over budget by 13m"), "got: {}", text(&state));
+    }
+
+    #[test]
+    fn build_header_text_shows_pinned_archived_session_goal() {
+        use crate::model::{ArchivedSession, SessionId, SessionMeta};
+        use chrono::Utc;
+        use std::path::PathBuf;
+
+        let mut state = AppState::with_view(ViewState::SessionDetail);
+        state.domain.sessions.push(ArchivedSession::new(
+            SessionMeta::new("old", Utc::now(), "/proj".to_string()).with_goal("Write docs".to_string()),
+            PathBuf::new(),
+        ));
+        state.ui.selected_session_id = Some(SessionId::new("old"));
+
+        let line = build_header_text(&state);
+        let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(text.contains("Write docs"));
+    }
+
+    #[test]
+    fn build_header_text_shows_watcher_degraded() {
+        use crate::watcher::health::{Degradation, POLLER};
+
+        let mut state = AppState::new();
+        let text: String = build_header_text(&state).spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(!text.contains("watcher degraded"));
+
+        state.meta.watchdog.degraded = vec![(POLLER, Degradation::Stalled)];
+        let line = build_header_text(&state);
+        let span = line.spans.iter().find(|s| s.content.contains("watcher degraded")).unwrap();
+        assert_eq!(span.style.fg, Some(Theme::ERROR));
+    }
+
+    #[test]
+    fn build_header_text_shows_recent_api_errors_by_kind() {
+        let mut state = AppState::new();
+        let api_span = |state: &AppState| {
+            build_header_text(state).spans.into_iter().find(|s| s.content.contains("API")).map(|s| (s.content.to_string(), s.style.fg))
+        };
+        assert_eq!(api_span(&state), None);
+
+        let now = chrono::Utc::now();
+        state.domain.api_health.record(now, ApiErrorKind::RateLimit, None);
+        state.domain.api_health.record(now, ApiErrorKind::RateLimit, None);
+        assert_eq!(api_span(&state), Some(("  ⛔ API 2/10m: 2 rate limit".to_string(), Some(Theme::WARNING))));
+
+        state.domain.api_health.record(now, ApiErrorKind::ContextLength, None);
+        assert_eq!(api_span(&state), Some(("  ⛔ API 3/10m: 1 context limit, 2 rate limit".to_string(), Some(Theme::ERROR))));
+    }
 }
@@ -45,12 +45,14 @@ fn build_help_text() -> Vec<Line<'static>> {
                 .fg(Theme::INFO)
                 .add_modifier(Modifier::BOLD),
         )),
-        Line::from("  1           - Dashboard view"),
+        Line::from("  1 / |       - Dashboard view / split with followed agent's activity"),
         Line::from("  2           - Agent detail view"),
         Line::from("  3           - Sessions view"),
-        Line::from("  4           - Token cost dashboard"),
+        Line::from("  4 / 5 / 6   - Token cost dashboard / review queue / all-session timeline"),
         Line::from("  Tab         - Switch panel focus"),
-        Line::from("  h / l       - Focus left / right panel"),
+        Line::from("  h / l / z   - Focus left / right panel / zoom it to full screen (z restores)"),
+        Line::from("  Ctrl+p      - Go to session / agent / task (fuzzy)"),
+        Line::from("  :           - Command line (export md|csv|mermaid|dot, delete session, filter, set tick=…, sh)"),
         Line::from(""),
         Line::from(Span::styled(
             "SCROLLING",
@@ -59,9 +61,9 @@ fn build_help_text() -> Vec<Line<'static>> {
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from("  j / k       - Scroll down / up"),
-        Line::from("  Ctrl+d / u  - Page down / up"),
-        Line::from("  g / G       - Jump to top / bottom"),
-        Line::from("  Space       - Toggle auto-scroll (event stream)"),
+        Line::from("  Ctrl+d / u  - Page down / up (Shift+← / → pans events sideways)"),
+        Line::from("  g / G       - Jump to top (newest, resumes auto-scroll) / bottom"),
+        Line::from("  Space / w   - Toggle auto-scroll / wrapped or one-line events"),
         Line::from(""),
         Line::from(Span::styled(
             "ACTIONS",
@@ -71,12 +73,14 @@ fn build_help_text() -> Vec<Line<'static>> {
         )),
         Line::from("  Enter       - Drill down / select"),
         Line::from("  Esc         - Go back / close popup"),
-        Line::from("  /           - Search tasks & events (Esc to clear)"),
-        Line::from("  p           - Preview agent in popup"),
-        Line::from("  v           - Toggle wave/kanban view"),
+        Line::from("  / , x       - Filter this view, kept per view / channel (Enter applies) / clear it"),
+        Line::from("  p           - Agent quick stats popup (Enter in it: full prompt)"),
+        Line::from("  v / S       - Toggle wave/kanban view / spec & plan preview beside the Dashboard"),
         Line::from("  ?           - Toggle help overlay"),
         Line::from("  L           - Tmux layout picker"),
         Line::from("  q           - Quit application"),
+        Line::from("  ,           - Settings (tick rate, stale timeout, event filters)"),
+        Line::from("  Ctrl+z      - Suspend (fg to resume); :sh opens a shell instead"),
         Line::from(""),
         Line::from(Span::styled(
             "VIEW-SPECIFIC",
@@ -88,15 +92,38 @@ fn build_help_text() -> Vec<Line<'static>> {
         Line::from("    v              - Toggle wave/kanban board"),
         Line::from("    Enter on task  - Jump to agent detail (full view)"),
         Line::from("    p on task      - Preview agent in popup"),
+        Line::from("    D / E on task  - git diff of its files / errors behind its failure"),
+        Line::from("    c / C          - Next / previous event channel (config `channels`)"),
+        Line::from(""),
+        Line::from("  Agent Detail:"),
+        Line::from("    j/k (right)    - Move between steps"),
+        Line::from("    Enter (right)  - Expand/collapse step"),
+        Line::from("    o              - Open the step's captured output, else its file in $EDITOR"),
+        Line::from("    J              - Inspect the step's events as a JSON tree (y/Y copy value/path)"),
+        Line::from("    T / t          - Group agents by type / show one type at a time"),
+        Line::from("    Enter (left)   - Fold/unfold the selected type's section"),
         Line::from(""),
         Line::from("  Sessions:"),
         Line::from("    Enter          - Load archived session"),
+        Line::from("    P / R          - Cycle project scope / group by orchestration run"),
+        Line::from("    O              - Show one outcome at a time (success, partial, failed, ...)"),
+        Line::from("    [ ] / { }      - Show one calendar day, step by day / week (Esc: every day)"),
+        Line::from("    ( ) / < >      - Pick a table column / narrow or widen it (= rebalances)"),
         Line::from("    Space          - Mark/unmark session for deletion"),
         Line::from("    d              - Delete marked (or cursor) session"),
+        Line::from("    u              - Undo last delete"),
+        Line::from(""),
+        Line::from("  Session Detail:"),
+        Line::from("    n              - Switch right panel between events / notes"),
+        Line::from("    e / Enter      - Edit notes (Ctrl+S save, Esc discard)"),
         Line::from(""),
         Line::from("  Token Dashboard:"),
         Line::from("    Tab            - Switch panel focus"),
         Line::from("    j/k            - Scroll session table"),
+        Line::from("    c / w          - Cost history tab / chart per day or week"),
+        Line::from(""),
+        Line::from("  Review:"),
+        Line::from("    a / r          - Approve / reject with a note (Enter records)"),
         Line::from(""),
         Line::from(Span::styled(
             "SEARCH",
@@ -134,6 +161,7 @@ mod tests {
     use ratatui::Terminal;
 
     #[test]
+    #[allow(clippy::redundant_closure)]
     fn test_render_help_overlay() {
         let backend = TestBackend::new(120, 40);
         let mut terminal = Terminal::new(backend).unwrap();
@@ -159,6 +187,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::redundant_closure)]
     fn test_help_overlay_has_quit_keybinding() {
         let backend = TestBackend::new(120, 40);
         let mut terminal = Terminal::new(backend).unwrap();
@@ -0,0 +1,150 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::json_tree::{JsonInspector, JsonRow, RowKind};
+use crate::model::Theme;
+
+/// Pure function: one tree row, indented by depth, truncated to `width` columns.
+fn row_line(row: &JsonRow, selected: bool, width: usize) -> Line<'static> {
+    let indent = "  ".repeat(row.depth);
+    let mut spans = vec![Span::raw(indent.clone())];
+    let (marker, value, color) = match &row.kind {
+        RowKind::Container { array, len, folded } => {
+            let marker = if *folded { "▸ " } else { "▾ " };
+            let value = match (array, len) {
+                (true, 1) => "[1 item]".to_string(),
+                (true, n) => format!("[{} items]", n),
+                (false, 1) => "{1 key}".to_string(),
+                (false, n) => format!("{{{} keys}}", n),
+            };
+            (marker, value, Theme::MUTED_TEXT)
+        }
+        RowKind::Scalar(json) => {
+            let color = match json.chars().next() {
+                Some('"') => Theme::SUCCESS,
+                Some('t' | 'f') => Theme::WARNING,
+                Some('n') => Theme::MUTED_TEXT,
+                _ => Theme::INFO,
+            };
+            ("  ", json.clone(), color)
+        }
+    };
+    spans.push(Span::styled(marker, Style::default().fg(Theme::MUTED_TEXT)));
+    if let Some(label) = &row.label {
+        spans.push(Span::styled(label.clone(), Style::default().fg(Theme::ACCENT)));
+        spans.push(Span::styled(": ", Style::default().fg(Theme::MUTED_TEXT)));
+    }
+    let used: usize = spans.iter().map(|s| s.content.chars().count()).sum();
+    let room = width.saturating_sub(used).max(1);
+    let value = if value.chars().count() > room {
+        let kept: String = value.chars().take(room.saturating_sub(1)).collect();
+        format!("{}…", kept)
+    } else {
+        value
+    };
+    spans.push(Span::styled(value, Style::default().fg(color)));
+
+    let line = Line::from(spans);
+    if selected {
+        line.style(Style::default().bg(Theme::SELECTION_BG).add_modifier(Modifier::BOLD))
+    } else {
+        line
+    }
+}
+
+/// Render the JSON tree inspector (`J` on a step of the agent activity tree).
+pub fn render_json_inspector(frame: &mut Frame, area: Rect, inspector: &JsonInspector) {
+    let popup_area = centered_rect(85, 85, area);
+    frame.render_widget(Clear, popup_area);
+
+    let rows = inspector.rows();
+    let cursor = inspector.cursor.min(rows.len().saturating_sub(1));
+    let height = popup_area.height.saturating_sub(2) as usize;
+    let width = popup_area.width.saturating_sub(2) as usize;
+    // Keep the cursor about a third of the way down once the tree is taller than the popup
+    let top = cursor.saturating_sub(height / 3).min(rows.len().saturating_sub(height));
+    let lines: Vec<Line> =
+        rows.iter().enumerate().skip(top).take(height).map(|(idx, row)| row_line(row, idx == cursor, width)).collect();
+
+    let path = rows.get(cursor).map_or(String::new(), |row| {
+        if row.path.is_empty() { ".".to_string() } else { row.path.clone() }
+    });
+    let footer = match &inspector.search {
+        Some(search) => Line::from(vec![
+            Span::styled(" key / ", Style::default().fg(Theme::INFO)),
+            Span::styled(search.clone(), Style::default().fg(Theme::TEXT)),
+            Span::styled("█ ", Style::default().fg(Theme::ACTIVE_BORDER)),
+        ]),
+        None => Line::from(Span::styled(
+            format!(" {} · Enter: fold  /: key  n: next  y/Y: copy value/path ", path),
+            Style::default().fg(Theme::MUTED_TEXT),
+        )),
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::ACTIVE_BORDER))
+            .title(Line::from(Span::styled(
+                format!(" JSON · {} (Esc to close) ", inspector.title),
+                Style::default().fg(Theme::ACCENT).add_modifier(Modifier::BOLD),
+            )))
+            .title_bottom(footer),
+    );
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn shows_folded_containers_and_the_cursor_path() {
+        let mut inspector = JsonInspector::new(
+            "agent-1".into(),
+            serde_json::json!({ "kind": "tool_use", "input": { "command": "cargo test" } }),
+        );
+        inspector.cursor = 1;
+        inspector.toggle();
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| render_json_inspector(frame, frame.area(), &inspector)).unwrap();
+
+        let buf = terminal.backend().buffer();
+        let rows: Vec<String> = (0..buf.area.height)
+            .map(|y| (0..buf.area.width).map(|x| buf.cell((x, y)).unwrap().symbol()).collect())
+            .collect();
+        let screen = rows.join("\n");
+        assert!(screen.contains("▸ input: {1 key}"), "{screen}");
+        assert!(screen.contains("kind: \"tool_use\""));
+        assert!(!screen.contains("command"));
+        assert!(screen.contains(" .input · Enter: fold"));
+    }
+}
@@ -1,29 +1,61 @@
+pub mod accessibility;
+pub mod activity_calendar;
 pub mod agent_list;
 pub mod banner;
-pub mod delete_confirm;
+pub mod changelog;
+pub mod command_bar;
+pub mod confirm;
+pub mod diff_popup;
+pub mod empty_state;
 pub mod event_stream;
 pub mod filter_bar;
 pub mod footer;
 pub mod format;
 pub mod header;
 pub mod help_overlay;
+pub mod json_inspector;
 pub mod kanban;
 pub mod layout_picker;
+pub mod output_pager;
+pub mod palette;
+pub mod panel_grid;
+pub mod plan_gate;
 pub mod popup;
 pub mod prompt_popup;
+pub mod schema_report;
+pub mod scroll;
+pub mod settings;
+pub mod spec_panel;
+pub mod stats_panels;
+pub mod step_tree;
 pub mod syntax;
+pub mod table;
 pub mod task_list;
+pub mod toast;
 pub mod wave_river;
 
 pub use agent_list::{render_agent_list, render_agent_list_generic, render_agent_list_with_main};
 pub use banner::render_banner;
+pub use changelog::render_changelog;
+pub use command_bar::render_command_bar;
+pub use confirm::render_confirm;
+pub use diff_popup::render_diff_popup;
 pub use event_stream::{render_agent_event_stream, render_event_stream};
 pub use filter_bar::render_filter_bar;
 pub use footer::render_footer;
 pub use header::render_header;
 pub use help_overlay::render_help_overlay;
+pub use json_inspector::render_json_inspector;
 pub use kanban::render_kanban_board;
+pub use output_pager::render_output_pager;
+pub use palette::render_palette;
+pub use plan_gate::render_plan_gate;
 pub use popup::render_agent_popup;
 pub use prompt_popup::{extract_references, render_prompt_popup};
+pub use schema_report::render_schema_report;
+pub use settings::render_settings;
+pub use spec_panel::render_spec_panel;
+pub use step_tree::render_step_tree;
 pub use task_list::render_task_list;
+pub use toast::{render_session_toast, render_toasts};
 pub use wave_river::render_wave_river;
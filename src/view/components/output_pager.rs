@@ -0,0 +1,102 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::format::format_bytes;
+use crate::app::tool_outputs::OutputPager;
+use crate::model::Theme;
+
+/// Render the captured tool output pager (`o` on a step with a captured output).
+/// Only the visible lines are built; the rest of the file streams in as it scrolls.
+pub fn render_output_pager(frame: &mut Frame, area: Rect, pager: &OutputPager) {
+    let popup_area = centered_rect(90, 85, area);
+    frame.render_widget(Clear, popup_area);
+
+    let loaded = if pager.eof {
+        String::new()
+    } else {
+        format!(" · {}% loaded", pager.percent_loaded())
+    };
+    let title = format!(
+        " {} output · {}{} · {} (Esc to close) ",
+        pager.tool_name,
+        format_bytes(pager.output.bytes),
+        loaded,
+        pager.output.path.display()
+    );
+
+    let height = popup_area.height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = if pager.lines.is_empty() {
+        let text = if pager.eof { "(empty)" } else { "Reading…" };
+        vec![Line::from(Span::styled(text, Style::default().fg(Theme::MUTED_TEXT)))]
+    } else {
+        pager.lines.iter().skip(pager.scroll).take(height).map(|line| Line::from(line.replace('\t', "    "))).collect()
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Theme::ACTIVE_BORDER))
+                .title(Line::from(Span::styled(
+                    title,
+                    Style::default().fg(Theme::ACCENT).add_modifier(Modifier::BOLD),
+                ))),
+        )
+        .style(Style::default().fg(Theme::TEXT));
+
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::tool_outputs::{OutputChunk, ToolOutput};
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn shows_scrolled_lines_and_load_progress() {
+        let output = ToolOutput { path: "toolu_01.txt".into(), bytes: 100 * 1024 };
+        let mut pager = OutputPager::new("Bash".into(), output);
+        let text: String = (1..=40).map(|n| format!("line {}\n", n)).collect();
+        pager.loading = true;
+        pager.append(0, OutputChunk { next_offset: 50 * 1024, text, eof: false });
+        pager.scroll = 10;
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| render_output_pager(frame, frame.area(), &pager)).unwrap();
+
+        let buf = terminal.backend().buffer();
+        let rows: Vec<String> = (0..buf.area.height)
+            .map(|y| (0..buf.area.width).map(|x| buf.cell((x, y)).unwrap().symbol()).collect())
+            .collect();
+        let screen = rows.join("\n");
+        assert!(screen.contains("Bash output · 100KB · 50% loaded"), "{screen}");
+        assert!(screen.contains("line 11") && !screen.contains("line 10 "));
+    }
+}
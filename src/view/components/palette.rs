@@ -0,0 +1,166 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::palette::{palette_results, PaletteEntry};
+use crate::app::state::{AppState, PaletteState};
+use crate::app::fuzzy::FuzzyMatch;
+use crate::model::Theme;
+
+/// Render the quick-switch palette overlay (Ctrl+P).
+/// Query line on top, ranked results below with matched characters highlighted.
+pub fn render_palette(frame: &mut Frame, area: Rect, state: &AppState) {
+    let PaletteState::Open { query, selected } = &state.ui.palette else {
+        return;
+    };
+
+    let popup_area = centered_rect(70, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let results = palette_results(state, query);
+    // Rows inside the border, minus query line and spacer
+    let list_height = (popup_area.height.saturating_sub(2) as usize).saturating_sub(2).max(1);
+    let first = (selected + 1).saturating_sub(list_height);
+
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(Theme::INFO)),
+            Span::styled(query.clone(), Style::default().fg(Theme::TEXT)),
+            Span::styled("█", Style::default().fg(Theme::ACTIVE_BORDER)),
+        ]),
+        Line::from(""),
+    ];
+
+    if results.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No matches",
+            Style::default().fg(Theme::MUTED_TEXT),
+        )));
+    }
+
+    lines.extend(
+        results
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(list_height)
+            .map(|(i, (entry, matched))| result_line(entry, matched, i == *selected)),
+    );
+
+    let title = format!(" Go to ({}) ", results.len());
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(Line::from(Span::styled(
+                title,
+                Style::default()
+                    .fg(Theme::ACCENT)
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .title_bottom(Line::from(Span::styled(
+                " ↑/↓ move · Enter go · Esc close ",
+                Style::default().fg(Theme::MUTED_TEXT),
+            )))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::ACTIVE_BORDER)),
+    );
+
+    frame.render_widget(paragraph, popup_area);
+}
+
+/// Pure function: one result row — marker, kind tag, then title/detail with
+/// matched characters emphasised.
+fn result_line(entry: &PaletteEntry, matched: &FuzzyMatch, is_selected: bool) -> Line<'static> {
+    let base = if is_selected {
+        Style::default().bg(Theme::SELECTION_BG)
+    } else {
+        Style::default()
+    };
+
+    let mut spans = vec![
+        Span::styled(if is_selected { "▸ " } else { "  " }, base.fg(Theme::ACCENT)),
+        Span::styled(
+            format!("{:<8}", entry.target.kind_label()),
+            base.fg(Theme::AGENT_LABEL),
+        ),
+    ];
+
+    for (i, c) in entry.text.chars().enumerate() {
+        let color = if i < entry.title_len { Theme::TEXT } else { Theme::MUTED_TEXT };
+        let mut style = base.fg(color);
+        if matched.positions.contains(&i) {
+            style = style.fg(Theme::ACCENT_WARM).add_modifier(Modifier::BOLD);
+        }
+        spans.push(Span::styled(c.to_string(), style));
+    }
+
+    Line::from(spans)
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::vertical([
+        Constraint::Percentage((100 - percent_y) / 2),
+        Constraint::Percentage(percent_y),
+        Constraint::Percentage((100 - percent_y) / 2),
+    ])
+    .split(r);
+
+    Layout::horizontal([
+        Constraint::Percentage((100 - percent_x) / 2),
+        Constraint::Percentage(percent_x),
+        Constraint::Percentage((100 - percent_x) / 2),
+    ])
+    .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Task, TaskGraph, TaskStatus, Wave};
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn render_text(state: &AppState) -> String {
+        let backend = TestBackend::new(100, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let result = terminal
+            .draw(|frame| render_palette(frame, frame.area(), state))
+            .unwrap();
+        result.buffer.content().iter().map(|c| c.symbol()).collect()
+    }
+
+    #[test]
+    fn renders_query_and_matching_tasks() {
+        let mut state = AppState::new();
+        state.domain.task_graph = Some(TaskGraph::new(vec![Wave::new(
+            1,
+            vec![
+                Task::new("T1", "Build parser".into(), TaskStatus::Pending),
+                Task::new("T2", "Write docs".into(), TaskStatus::Pending),
+            ],
+        )]));
+        state.ui.palette = PaletteState::Open { query: "pars".into(), selected: 0 };
+
+        let text = render_text(&state);
+        assert!(text.contains("> pars"));
+        assert!(text.contains("Go to (1)"));
+        assert!(text.contains("Build parser"));
+        assert!(!text.contains("Write docs"));
+    }
+
+    #[test]
+    fn shows_no_matches_message() {
+        let mut state = AppState::new();
+        state.ui.palette = PaletteState::Open { query: "zzz".into(), selected: 0 };
+        assert!(render_text(&state).contains("No matches"));
+    }
+
+    #[test]
+    fn does_nothing_when_closed() {
+        let state = AppState::new();
+        assert!(render_text(&state).trim().is_empty());
+    }
+}
@@ -0,0 +1,68 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    Frame,
+};
+
+use crate::app::panels::{DashboardLayout, PanelKind};
+
+/// Pure function: screen area of each panel in `layout`, in reading order.
+/// Row heights and panel widths split `area` by weight.
+pub fn panel_areas(layout: &DashboardLayout, area: Rect) -> Vec<(PanelKind, Rect)> {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(layout.rows.iter().map(|row| Constraint::Fill(row.height)))
+        .split(area);
+
+    layout
+        .rows
+        .iter()
+        .zip(rows.iter())
+        .flat_map(|(row, row_area)| {
+            let cells = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(row.panels.iter().map(|slot| Constraint::Fill(slot.width)))
+                .split(*row_area);
+            row.panels.iter().map(|slot| slot.panel).zip(cells.iter().copied()).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Lay out `layout` in `area` and hand each panel's area to `render_panel`.
+pub fn render_panel_grid(
+    frame: &mut Frame,
+    area: Rect,
+    layout: &DashboardLayout,
+    mut render_panel: impl FnMut(&mut Frame, PanelKind, Rect),
+) {
+    for (panel, panel_area) in panel_areas(layout, area) {
+        render_panel(frame, panel, panel_area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::panels::{PanelRow, PanelSlot};
+
+    #[test]
+    fn areas_split_rows_and_panels_by_weight() {
+        let slot = |panel, width| PanelSlot { panel, width };
+        let layout = DashboardLayout {
+            rows: vec![
+                PanelRow { height: 3, panels: vec![slot(PanelKind::TaskList, 1), slot(PanelKind::EventStream, 2)] },
+                PanelRow { height: 1, panels: vec![slot(PanelKind::Errors, 1)] },
+            ],
+        };
+
+        let areas = panel_areas(&layout, Rect::new(0, 0, 90, 40));
+
+        assert_eq!(
+            areas,
+            [
+                (PanelKind::TaskList, Rect::new(0, 0, 30, 30)),
+                (PanelKind::EventStream, Rect::new(30, 0, 60, 30)),
+                (PanelKind::Errors, Rect::new(0, 30, 90, 10)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::{AppState, PendingPlan};
+use crate::model::{SessionId, Theme};
+use super::format::TimeFormat;
+
+/// Render the "Plan awaiting approval" panel for the newest session blocked on
+/// a plan-mode approval. Renders nothing once every plan has been answered.
+pub fn render_plan_gate(frame: &mut Frame, area: Rect, state: &AppState) {
+    let Some((sid, plan)) = state.newest_pending_plan() else {
+        return;
+    };
+    let others = state.domain.pending_plans.len() - 1;
+
+    let mut title = format!(" ⏸ Plan awaiting approval — session {} ", short_id(sid));
+    if others > 0 {
+        title.push_str(&format!("(+{} more) ", others));
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Thick)
+        .border_style(Style::default().fg(Theme::WARNING))
+        .title(Line::from(Span::styled(
+            title,
+            Style::default().fg(Theme::WARNING).add_modifier(Modifier::BOLD),
+        )));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(build_plan_lines(plan, &state.ui.time_format)).block(block).wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+/// Pure function: who asked and when, then the plan text.
+fn build_plan_lines(plan: &PendingPlan, time_format: &TimeFormat) -> Vec<Line<'static>> {
+    let asker = plan.agent_id.as_ref().map_or("main".to_string(), |id| id.to_string());
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(
+                "{} asked at {} — answer in the session's terminal",
+                asker,
+                time_format.time(plan.requested_at)
+            ),
+            Style::default().fg(Theme::MUTED_TEXT),
+        )),
+        Line::from(""),
+    ];
+    if plan.plan.trim().is_empty() {
+        lines.push(Line::from(Span::styled("(no plan text)", Style::default().fg(Theme::MUTED_TEXT))));
+    }
+    lines.extend(
+        plan.plan
+            .lines()
+            .map(|l| Line::from(Span::styled(l.to_string(), Style::default().fg(Theme::TEXT)))),
+    );
+    lines
+}
+
+/// Shorten a session ID to its first 8 chars.
+fn short_id(id: &SessionId) -> String {
+    id.as_str().chars().take(8).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn shows_newest_plan_with_count_of_others() {
+        let mut state = AppState::new();
+        let now = Utc::now();
+        for (sid, text, age) in [("older-session", "old plan", 60), ("newer-session", "1. Parse\n2. Test", 0)] {
+            state.domain.pending_plans.insert(
+                SessionId::new(sid),
+                PendingPlan { plan: text.into(), requested_at: now - chrono::Duration::seconds(age), agent_id: None },
+            );
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 10)).unwrap();
+        terminal.draw(|frame| render_plan_gate(frame, frame.area(), &state)).unwrap();
+        let buf = terminal.backend().buffer();
+        let text: String = (0..buf.area.height)
+            .map(|y| (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect::<String>() + "\n")
+            .collect();
+
+        assert!(text.contains("Plan awaiting approval — session newer-se (+1 more)"));
+        assert!(text.contains("2. Test"));
+        assert!(!text.contains("old plan"));
+    }
+}
@@ -2,80 +2,177 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 
-use crate::app::AppState;
-use crate::model::Theme;
+use crate::app::cost;
+use crate::app::{AppState, ViewState};
+use crate::model::{Agent, AgentId, Theme, TranscriptEvent, TranscriptEventKind};
+use crate::view::session_detail::get_selected_session_data;
 
 use super::event_stream::render_agent_event_stream;
+use super::format::{format_cost_usd, format_elapsed, format_token_count};
 
-/// Render agent detail popup overlay.
-/// Renders on top of the current view with a centered modal window.
+/// Longest assistant text snippet shown in the popup.
+const SNIPPET_MAX_CHARS: usize = 240;
+
+/// What an agent is doing, read from its events.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentActivity {
+    /// Tool called and not yet answered: name and input summary
+    pub current_tool: Option<(String, String)>,
+    /// Latest assistant text, whitespace collapsed and cut to `SNIPPET_MAX_CHARS`
+    pub last_text: Option<String>,
+}
+
+/// Pure function: current tool and latest assistant text of `agent_id`, from
+/// events given newest first.
+pub fn agent_activity<'a>(events_newest_first: impl Iterator<Item = &'a TranscriptEvent>, agent_id: &AgentId) -> AgentActivity {
+    let mut activity = AgentActivity::default();
+    let mut tool_settled = false;
+    for event in events_newest_first.filter(|e| e.agent_id.as_ref() == Some(agent_id)) {
+        match &event.kind {
+            TranscriptEventKind::ToolUse { tool_name, input_summary } if !tool_settled => {
+                activity.current_tool = Some((tool_name.to_string(), input_summary.clone()));
+                tool_settled = true;
+            }
+            TranscriptEventKind::ToolResult { .. } => tool_settled = true,
+            TranscriptEventKind::AssistantMessage { content } if activity.last_text.is_none() => {
+                let collapsed = content.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !collapsed.is_empty() {
+                    activity.last_text = Some(if collapsed.chars().count() > SNIPPET_MAX_CHARS {
+                        let kept: String = collapsed.chars().take(SNIPPET_MAX_CHARS - 1).collect();
+                        format!("{}…", kept.trim_end())
+                    } else {
+                        collapsed
+                    });
+                }
+            }
+            _ => {}
+        }
+        if tool_settled && activity.last_text.is_some() {
+            break;
+        }
+    }
+    activity
+}
+
+/// Render agent quick stats popup overlay.
+/// Live agents come with their event stream; agents of an archived session
+/// open from SessionDetail show the stats alone.
 pub fn render_agent_popup(frame: &mut Frame, state: &AppState, agent_id: &str) {
-    let agent = match state.domain.agents.get(&crate::model::AgentId::new(agent_id)) {
-        Some(a) => a,
-        None => return, // Agent not found - skip rendering
+    let id = AgentId::new(agent_id);
+    if let Some(agent) = state.domain.agents.get(&id) {
+        // The open-call tracker outlives the event ring buffer and times the call
+        let mut activity = agent_activity(state.domain.events.iter().rev(), &id);
+        activity.current_tool = state.running_tool(&id).map(|tool| (tool.tool_name.to_string(), tool.detail(state.meta.clock.now())));
+        let area = centered_rect(80, 80, frame.area());
+        frame.render_widget(Clear, area);
+        let layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(STATS_HEIGHT), Constraint::Min(5)])
+            .split(area);
+        render_agent_stats(frame, layout[0], state, agent, &activity);
+        render_agent_event_stream(
+            frame,
+            layout[1],
+            state,
+            agent_id,
+            0, // scroll offset (fixed at 0 for popup)
+            true, // is focused
+        );
+        return;
+    }
+
+    if state.ui.view != ViewState::SessionDetail {
+        return; // Agent not found - skip rendering
+    }
+    let Some(data) = get_selected_session_data(state) else {
+        return;
     };
+    let Some(agent) = data.agents.get(&id) else {
+        return;
+    };
+    let activity = agent_activity(data.events.iter_rev(), &id);
+    let outer = centered_rect(80, 80, frame.area());
+    let area = Rect { height: outer.height.min(STATS_HEIGHT), ..outer };
+    frame.render_widget(Clear, area);
+    render_agent_stats(frame, area, state, agent, &activity);
+}
 
-    // Calculate centered popup area (80% width, 80% height)
-    let area = centered_rect(80, 80, frame.area());
+/// Rows of the stats box, borders included.
+const STATS_HEIGHT: u16 = 9;
 
-    // Clear the area behind the popup
-    frame.render_widget(Clear, area);
+fn render_agent_stats(frame: &mut Frame, area: Rect, state: &AppState, agent: &Agent, activity: &AgentActivity) {
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(Theme::MUTED_TEXT));
+    let value = |text: String| Span::styled(text, Style::default().fg(Theme::TEXT));
+    let finished = agent.finished_at.is_some();
+    let elapsed = agent.elapsed(&*state.meta.clock).num_seconds().max(0);
+    let usage = &agent.token_usage;
 
-    // Split popup into header and content
-    let layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(10),   // Event stream
-        ])
-        .split(area);
+    let now_line = match (&activity.current_tool, finished) {
+        (Some((tool, input)), false) => Line::from(vec![
+            label("Now: "),
+            Span::styled(tool.clone(), Style::default().fg(Theme::tool_color(tool)).add_modifier(Modifier::BOLD)),
+            Span::raw(" "),
+            value(input.clone()),
+        ]),
+        (_, false) => Line::from(vec![label("Now: "), Span::styled("thinking", Style::default().fg(Theme::MUTED_TEXT))]),
+        (_, true) => Line::from(vec![label("Now: "), Span::styled("done", Style::default().fg(Theme::MUTED_TEXT))]),
+    };
 
-    // Render header with agent info
-    let header_text = vec![
+    let lines = vec![
         Line::from(vec![
-            Span::styled("Agent: ", Style::default().fg(Theme::MUTED_TEXT)),
-            Span::styled(
-                agent.display_name(),
-                Style::default().fg(Theme::AGENT_LABEL).add_modifier(Modifier::BOLD),
-            ),
+            label("Agent: "),
+            Span::styled(agent.display_name().to_string(), Style::default().fg(Theme::AGENT_LABEL).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("  {}", agent.id), Style::default().fg(Theme::MUTED_TEXT)),
         ]),
         Line::from(vec![
-            Span::styled("Status: ", Style::default().fg(Theme::MUTED_TEXT)),
+            label("Status: "),
             Span::styled(
-                if agent.finished_at.is_some() { "Finished" } else { "Running" },
-                Style::default().fg(if agent.finished_at.is_some() {
-                    Theme::MUTED_TEXT
-                } else {
-                    Theme::SUCCESS
-                }),
+                if finished { "Finished" } else { "Running" },
+                Style::default().fg(if finished { Theme::MUTED_TEXT } else { Theme::SUCCESS }),
             ),
+            Span::styled(format!(" · {}", format_elapsed(elapsed)), Style::default().fg(Theme::MUTED_TEXT)),
+        ]),
+        Line::from(vec![
+            label("Model: "),
+            value(agent.model.clone().unwrap_or_else(|| "inherited".to_string())),
+            label("  Tokens: "),
+            value(format!(
+                "{} in / {} out",
+                format_token_count(usage.input_tokens),
+                format_token_count(usage.output_tokens)
+            )),
+            label("  Cost: "),
+            value(format_cost_usd(cost::agent_cost_cents(agent))),
+        ]),
+        now_line,
+        Line::from(vec![
+            label("Said: "),
+            match &activity.last_text {
+                Some(text) => Span::styled(format!("\u{201c}{}\u{201d}", text), Style::default().fg(Theme::TEXT)),
+                None => Span::styled("—", Style::default().fg(Theme::MUTED_TEXT)),
+            },
         ]),
     ];
 
-    let header = Paragraph::new(header_text)
+    let title = if state.ui.view == ViewState::Dashboard {
+        " Agent Details (Esc to close) "
+    } else {
+        " Agent Details (Enter: prompt · Esc to close) "
+    };
+    let stats = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Theme::ACTIVE_BORDER))
-                .title(" Agent Details (Esc to close) "),
+                .title(title),
         )
         .alignment(Alignment::Left);
-
-    frame.render_widget(header, layout[0]);
-
-    // Render agent event stream
-    render_agent_event_stream(
-        frame,
-        layout[1],
-        state,
-        agent_id,
-        0, // scroll offset (fixed at 0 for popup)
-        true, // is focused
-    );
+    frame.render_widget(stats, area);
 }
 
 /// Helper to create a centered rect using up certain percentage of the available rect `r`.
@@ -98,3 +195,42 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ToolName;
+    use chrono::Utc;
+
+    fn event(agent: &str, kind: TranscriptEventKind) -> TranscriptEvent {
+        TranscriptEvent::new(Utc::now(), kind).with_agent(agent)
+    }
+
+    #[test]
+    fn activity_reads_the_open_tool_call_and_latest_text() {
+        let said = |text: &str| TranscriptEventKind::AssistantMessage { content: text.into() };
+        let tool_use = |input: &str| TranscriptEventKind::ToolUse { tool_name: ToolName::new("Bash"), input_summary: input.into() };
+        let mut events = vec![
+            event("a01", said("Running   the\ntests now")),
+            event("a01", tool_use("cargo test")),
+            event("a02", said("other agent")),
+        ];
+        let id = AgentId::new("a01");
+
+        let activity = agent_activity(events.iter().rev(), &id);
+        assert_eq!(activity.current_tool, Some(("Bash".to_string(), "cargo test".to_string())));
+        assert_eq!(activity.last_text.as_deref(), Some("Running the tests now"));
+
+        // Once answered the call is no longer current
+        events.push(event(
+            "a01",
+            TranscriptEventKind::ToolResult {
+                tool_name: ToolName::new("Bash"),
+                result_summary: "ok".into(),
+                duration_ms: None,
+                is_error: false,
+            },
+        ));
+        assert_eq!(agent_activity(events.iter().rev(), &id).current_tool, None);
+    }
+}
@@ -6,16 +6,20 @@ use ratatui::{
     Frame,
 };
 
+use crate::app::agent_defs::AgentDefinition;
 use crate::model::{AgentMessage, MessageKind, Theme, TokenUsage};
 
 /// Render a centered popup showing an agent's full task_description,
-/// model badge, loaded skills/references, and token usage breakdown.
+/// model badge, loaded skills/references, the definition it was started
+/// from, and token usage breakdown.
+#[allow(clippy::too_many_arguments)]
 pub fn render_prompt_popup(
     frame: &mut Frame,
     area: Rect,
     agent_name: &str,
     model: Option<&str>,
     agent_type: Option<&str>,
+    definition: Option<&AgentDefinition>,
     text: &str,
     messages: &[AgentMessage],
     skills: &[String],
@@ -52,6 +56,10 @@ pub fn render_prompt_popup(
         full_text.push_str(&format!("\n\n--- Skills & References ---\n{}", ref_lines));
     }
 
+    if let Some(def) = definition {
+        full_text.push_str(&definition_section(def, model));
+    }
+
     if !token_usage.is_empty() {
         full_text.push_str(&format!(
             "\n\n--- Token Usage (last turn) ---\n  Input:          {}\n  Cache Create:   {}\n  Cache Read:     {}\n  Context Window: ~{}",
@@ -82,6 +90,29 @@ pub fn render_prompt_popup(
     frame.render_widget(paragraph, popup_area);
 }
 
+/// Pure function: the popup section describing an agent definition. A model
+/// other than the one the agent runs on is called out.
+fn definition_section(def: &AgentDefinition, running_model: Option<&str>) -> String {
+    let file = def.path.file_name().map(|f| f.to_string_lossy().into_owned()).unwrap_or_default();
+    let model = match (def.model.as_deref(), running_model) {
+        (Some(configured), Some(running)) if configured != "inherit" && !running.contains(configured) => {
+            format!("{} (running {})", configured, running)
+        }
+        (Some(configured), _) => configured.to_string(),
+        (None, _) => "inherit".to_string(),
+    };
+    let mut section = format!(
+        "\n\n--- Definition (agents/{}) ---\n  Model:  {}\n  Tools:  {}",
+        file,
+        model,
+        def.tools_label()
+    );
+    if let Some(description) = def.description.as_deref() {
+        section.push_str(&format!("\n  {}", description));
+    }
+    section
+}
+
 /// Extract references (skills/rules/CLAUDE.md files) from agent messages.
 /// Scans Read/Glob/Grep targeting `.claude/skills/`, `.claude/rules/`, `CLAUDE.md`
 /// and any Skill tool calls. Deduplicates and returns sorted.
@@ -97,12 +128,10 @@ pub fn extract_references(messages: &[AgentMessage]) -> Vec<String> {
                 "Skill" => {
                     refs.push(format!("skill:{}", input));
                 }
-                "Read" | "Glob" | "Grep" => {
-                    if is_reference_path(input) {
-                        // Extract just the filename for brevity
-                        let short = input.rsplit('/').next().unwrap_or(input);
-                        refs.push(short.to_string());
-                    }
+                "Read" | "Glob" | "Grep" if is_reference_path(input) => {
+                    // Extract just the filename for brevity
+                    let short = input.rsplit('/').next().unwrap_or(input);
+                    refs.push(short.to_string());
                 }
                 _ => {}
             }
@@ -148,6 +177,23 @@ mod tests {
     use crate::model::{AgentMessage, ToolCall};
     use chrono::Utc;
 
+    #[test]
+    fn definition_section_calls_out_a_different_running_model() {
+        let def = AgentDefinition {
+            name: "code-reviewer".into(),
+            description: Some("Reviews diffs".into()),
+            model: Some("sonnet".into()),
+            tools: Some(vec!["Read".into(), "Grep".into()]),
+            path: ".claude/agents/reviewer.md".into(),
+        };
+        let section = definition_section(&def, Some("opus-4"));
+        assert!(section.contains("--- Definition (agents/reviewer.md) ---"), "{section}");
+        assert!(section.contains("Model:  sonnet (running opus-4)"));
+        assert!(section.contains("Tools:  Read, Grep"));
+        assert!(section.ends_with("Reviews diffs"));
+        assert!(definition_section(&def, Some("sonnet-4-5")).contains("Model:  sonnet\n"));
+    }
+
     #[test]
     fn extract_references_empty_messages() {
         assert!(extract_references(&[]).is_empty());
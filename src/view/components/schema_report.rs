@@ -0,0 +1,66 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::model::Theme;
+use crate::watcher::schema_drift::SchemaDrift;
+
+/// Render the schema drift report (`:schema`): transcript fields loom-tui
+/// does not map, most frequent first.
+pub fn render_schema_report(frame: &mut Frame, area: Rect, drift: &SchemaDrift) {
+    let popup_area = centered_rect(60, 60, area);
+    frame.render_widget(Clear, popup_area);
+
+    let lines: Vec<Line> = if drift.is_empty() {
+        vec![Line::from(Span::styled(
+            "  No unknown fields in the transcripts read so far",
+            Style::default().fg(Theme::MUTED_TEXT),
+        ))]
+    } else {
+        drift
+            .by_frequency()
+            .into_iter()
+            .map(|(field, count)| {
+                Line::from(vec![
+                    Span::styled(format!("  {:>8}  ", count), Style::default().fg(Theme::INFO)),
+                    Span::styled(field.to_string(), Style::default().fg(Theme::TEXT)),
+                ])
+            })
+            .collect()
+    };
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::ACTIVE_BORDER))
+            .title(Line::from(Span::styled(
+                " Schema drift · unknown transcript fields (any key to close) ",
+                Style::default().fg(Theme::ACCENT).add_modifier(Modifier::BOLD),
+            ))),
+    );
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
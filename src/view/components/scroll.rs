@@ -0,0 +1,178 @@
+//! Scrolling for word-wrapped `Paragraph` panels, measured in visual rows.
+//!
+//! Heights come from ratatui's own word wrapper (`Paragraph::line_count`), so
+//! the scroll range matches what is drawn at the panel's current width.
+//!
+//! Wrapping breaks up commands and paths, so event panels can also be drawn
+//! truncated (`w`, one line per event) and panned sideways (Shift+←/→): with a
+//! horizontal offset the lines are drawn unwrapped, shifted left, with the
+//! hidden width on either side in the title.
+
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::model::Theme;
+
+/// Visual rows each line occupies when word-wrapped to `width` columns.
+pub fn wrapped_line_heights(lines: &[Line<'_>], width: u16) -> Vec<usize> {
+    lines
+        .iter()
+        .map(|line| {
+            Paragraph::new(line.clone())
+                .wrap(Wrap { trim: false })
+                .line_count(width)
+                .max(1)
+        })
+        .collect()
+}
+
+/// Total visual rows of `lines` wrapped to `width` columns.
+pub fn wrapped_height(lines: &[Line<'_>], width: u16) -> usize {
+    wrapped_line_heights(lines, width).iter().sum()
+}
+
+/// Render `lines` word-wrapped inside `block`, scrolled down `offset` visual rows.
+/// The offset is clamped so the last row can reach the bottom of the panel but
+/// never scrolls past it.
+///
+/// # Returns
+/// The largest useful offset (0 when everything fits).
+pub fn render_wrapped(
+    frame: &mut Frame,
+    area: Rect,
+    lines: Vec<Line<'static>>,
+    block: Block<'_>,
+    offset: usize,
+) -> usize {
+    let inner = block.inner(area);
+    let max_offset = wrapped_height(&lines, inner.width).saturating_sub(inner.height as usize);
+    let scroll = offset.min(max_offset).min(u16::MAX as usize) as u16;
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+    frame.render_widget(paragraph, area);
+    max_offset
+}
+
+/// Columns `lines` overflow a panel `width` wide: the largest useful horizontal offset.
+pub fn overflow_width(lines: &[Line<'_>], width: u16) -> usize {
+    lines.iter().map(Line::width).max().unwrap_or(0).saturating_sub(width as usize)
+}
+
+/// Pure function: title chip for a panned panel, e.g. "◂ 16 · 42 ▸" for 16
+/// hidden columns on the left and 42 on the right.
+pub fn pan_indicator(x_offset: usize, max_x: usize) -> Line<'static> {
+    Line::from(Span::styled(
+        format!(" ◂ {} · {} ▸ ", x_offset, max_x.saturating_sub(x_offset)),
+        Style::default().fg(Theme::ACCENT),
+    ))
+    .right_aligned()
+}
+
+/// Like `render_wrapped`, but unless `wrap` is set and the horizontal offset is
+/// zero the lines are drawn unwrapped, cut at the panel edge and shifted
+/// `x_offset` columns left.
+///
+/// # Returns
+/// The largest useful vertical and horizontal offsets.
+pub fn render_panned(
+    frame: &mut Frame,
+    area: Rect,
+    lines: Vec<Line<'static>>,
+    block: Block<'_>,
+    offset: usize,
+    x_offset: usize,
+    wrap: bool,
+) -> (usize, usize) {
+    let inner = block.inner(area);
+    let max_x = overflow_width(&lines, inner.width);
+    let x = x_offset.min(max_x);
+    if wrap && x == 0 {
+        return (render_wrapped(frame, area, lines, block, offset), max_x);
+    }
+
+    let max_offset = lines.len().saturating_sub(inner.height as usize);
+    let scroll = offset.min(max_offset).min(u16::MAX as usize) as u16;
+    let block = if max_x > 0 { block.title_top(pan_indicator(x, max_x)) } else { block };
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((scroll, x.min(u16::MAX as usize) as u16));
+    frame.render_widget(paragraph, area);
+    (max_offset, max_x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::widgets::Borders;
+    use ratatui::Terminal;
+
+    #[test]
+    fn heights_follow_word_wrap() {
+        let lines = vec![Line::from("short"), Line::from("one two three four five"), Line::from("")];
+        assert_eq!(wrapped_line_heights(&lines, 10), vec![1, 3, 1]);
+        assert_eq!(wrapped_height(&lines, 100), 3);
+    }
+
+    #[test]
+    fn render_clamps_offset_to_last_page() {
+        let lines: Vec<Line<'static>> = (0..10).map(|i| Line::from(format!("row {i} with some wrapping text"))).collect();
+        let mut terminal = Terminal::new(TestBackend::new(20, 7)).unwrap();
+        let mut max = 0;
+        let result = terminal
+            .draw(|frame| {
+                max = render_wrapped(frame, frame.area(), lines.clone(), Block::default().borders(Borders::ALL), 1000);
+            })
+            .unwrap();
+
+        // 10 lines wrap to 2 rows each at 18 columns; 5 visible rows
+        assert_eq!(max, 15);
+        let text: String = result.buffer.content().iter().map(|c| c.symbol()).collect();
+        assert!(text.contains("row 9"), "bottom line should be visible, not blank space");
+    }
+
+    #[test]
+    fn panned_lines_are_unwrapped_and_shifted() {
+        let lines = vec![Line::from("cargo test --workspace --all-features"), Line::from("ok")];
+        let mut terminal = Terminal::new(TestBackend::new(22, 4)).unwrap();
+        let mut limits = (0, 0);
+        let result = terminal
+            .draw(|frame| {
+                limits = render_panned(frame, frame.area(), lines.clone(), Block::default().borders(Borders::ALL), 0, 6, true);
+            })
+            .unwrap();
+
+        // 37 columns in a 20-column panel: 17 hidden; two rows fit
+        assert_eq!(limits, (0, 17));
+        let buffer = result.buffer;
+        let row = |y: u16| (0..22).map(|x| buffer[(x, y)].symbol()).collect::<String>();
+        assert!(row(0).contains("◂ 6 · 11 ▸"));
+        assert_eq!(row(1), "│test --workspace --a│");
+        assert_eq!(pan_indicator(40, 17).to_string(), " ◂ 40 · 0 ▸ ");
+    }
+
+    #[test]
+    fn truncated_lines_stay_one_row_each() {
+        let lines = vec![Line::from("cargo test --workspace --all-features"), Line::from("ok")];
+        let mut terminal = Terminal::new(TestBackend::new(22, 4)).unwrap();
+        let result = terminal
+            .draw(|frame| {
+                render_panned(frame, frame.area(), lines.clone(), Block::default().borders(Borders::ALL), 0, 0, false);
+            })
+            .unwrap();
+
+        let buffer = result.buffer;
+        let row = |y: u16| (0..22).map(|x| buffer[(x, y)].symbol()).collect::<String>();
+        assert!(row(0).contains("◂ 0 · 17 ▸"));
+        assert_eq!(row(1), "│cargo test --workspa│");
+        assert_eq!(row(2), "│ok                  │");
+    }
+}
@@ -0,0 +1,116 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::settings::{self, SettingField};
+use crate::app::state::{AppState, SettingsState};
+use crate::model::Theme;
+
+/// Render settings overlay. Values shown are the live (previewed) ones;
+/// changed rows are marked against the values at open time.
+pub fn render_settings(frame: &mut Frame, area: Rect, state: &AppState) {
+    let (selected, original) = match &state.ui.settings {
+        SettingsState::Open { selected, original } => (*selected, original),
+        SettingsState::Closed => return,
+    };
+    let current = settings::current(state);
+
+    // Hint and rows plus borders and spacing, so every row fits on short terminals too
+    let height = (SettingField::ALL.len() as u16 + 6).min(area.height);
+    let popup_area = centered_rect(50, height, area);
+    frame.render_widget(Clear, popup_area);
+
+    let mut lines: Vec<Line> = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            " j/k select  h/l change  Enter save  Esc revert",
+            Style::default().fg(Theme::MUTED_TEXT),
+        )),
+        Line::from(""),
+    ];
+
+    for (i, field) in SettingField::ALL.iter().enumerate() {
+        let is_selected = i == selected;
+        let marker = if is_selected { "▸ " } else { "  " };
+        let label_style = if is_selected {
+            Style::default().fg(Theme::ACCENT).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Theme::TEXT)
+        };
+        let changed = field.value(&current) != field.value(original);
+        let value_style = if changed {
+            Style::default().fg(Theme::ACCENT_WARM)
+        } else {
+            Style::default().fg(Theme::TEXT)
+        };
+
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}{:<26}", marker, field.label()), label_style),
+            Span::styled(format!("‹ {} ›", field.value(&current)), value_style),
+            Span::styled(if changed { " *" } else { "" }, Style::default().fg(Theme::ACCENT_WARM)),
+        ]));
+    }
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .title(Line::from(Span::styled(
+                " Settings ",
+                Style::default()
+                    .fg(Theme::ACCENT)
+                    .add_modifier(Modifier::BOLD),
+            )))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::ACTIVE_BORDER)),
+    );
+
+    frame.render_widget(paragraph, popup_area);
+}
+
+fn centered_rect(percent_x: u16, height: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::vertical([Constraint::Fill(1), Constraint::Length(height), Constraint::Fill(1)]).split(r);
+
+    Layout::horizontal([
+        Constraint::Percentage((100 - percent_x) / 2),
+        Constraint::Percentage(percent_x),
+        Constraint::Percentage((100 - percent_x) / 2),
+    ])
+    .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::settings::Settings;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn render_text(state: &AppState) -> String {
+        let backend = TestBackend::new(120, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+        let result = terminal.draw(|frame| render_settings(frame, frame.area(), state)).unwrap();
+        result.buffer.content().iter().map(|c| c.symbol()).collect()
+    }
+
+    #[test]
+    fn lists_fields_and_marks_changes() {
+        let mut state = AppState::new();
+        state.ui.settings = SettingsState::Open { selected: 0, original: Box::new(Settings::default()) };
+        state.meta.tick_rate = std::time::Duration::from_millis(100);
+
+        let text = render_text(&state);
+        assert!(text.contains("Tick rate"));
+        assert!(text.contains("‹ 100ms › *"));
+        assert!(text.contains("‹ 10m ›"));
+        assert!(text.contains("Show tool results"));
+    }
+
+    #[test]
+    fn renders_nothing_when_closed() {
+        let state = AppState::new();
+        assert!(render_text(&state).trim().is_empty());
+    }
+}
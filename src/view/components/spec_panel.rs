@@ -0,0 +1,105 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use super::event_stream::own_text_lines;
+use crate::app::spec_docs::{mentions_task, running_tasks};
+use crate::app::AppState;
+use crate::model::Theme;
+
+/// Pure function: the rendered document, lines about a running task marked
+/// with a bar and highlighted.
+///
+/// # Returns
+/// The lines and the index of the first highlighted one.
+fn spec_lines(state: &AppState) -> (Vec<Line<'static>>, Option<usize>) {
+    let Some(doc) = &state.domain.spec_doc else {
+        return (
+            vec![Line::from(Span::styled(
+                "No spec or plan under .claude/specs or .claude/plans",
+                Style::default().fg(Theme::MUTED_TEXT),
+            ))],
+            None,
+        );
+    };
+    let running = running_tasks(state.domain.task_graph.as_ref());
+    let mut first = None;
+    let lines = own_text_lines(tui_markdown::from_str(&doc.content))
+        .into_iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+            if running.iter().any(|task| mentions_task(&text, task)) {
+                first.get_or_insert(idx);
+                let mut spans = vec![Span::styled("▌", Style::default().fg(Theme::ACCENT_WARM))];
+                spans.extend(line.spans);
+                Line::from(spans).style(Style::default().bg(Theme::SELECTION_BG).add_modifier(Modifier::BOLD))
+            } else {
+                let mut spans = vec![Span::raw(" ")];
+                spans.extend(line.spans);
+                Line::from(spans)
+            }
+        })
+        .collect();
+    (lines, first)
+}
+
+/// Render the spec / plan preview (`S`), scrolled to the first line about a
+/// running task.
+pub fn render_spec_panel(frame: &mut Frame, area: Rect, state: &AppState) {
+    let (lines, first) = spec_lines(state);
+    let height = area.height.saturating_sub(2) as usize;
+    // Keep the running task's line a third of the way down
+    let top = first.map_or(0, |idx| idx.saturating_sub(height / 3));
+
+    let title = match &state.domain.spec_doc {
+        Some(doc) => format!(" Spec · {} ", doc.name),
+        None => " Spec ".to_string(),
+    };
+    let paragraph = Paragraph::new(lines).scroll((top as u16, 0)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Theme::PANEL_BORDER))
+            .title(title),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::spec_docs::SpecDoc;
+    use crate::model::{Task, TaskGraph, TaskStatus, Wave};
+
+    #[test]
+    fn running_task_lines_are_highlighted() {
+        let mut state = AppState::new();
+        state.domain.task_graph = Some(TaskGraph::new(vec![Wave::new(
+            1,
+            vec![
+                Task::new("T1", "Scaffold".into(), TaskStatus::Completed),
+                Task::new("T2", "Models".into(), TaskStatus::Running),
+            ],
+        )]));
+        state.domain.spec_doc = Some(SpecDoc {
+            path: "plan.md".into(),
+            name: "plans/plan.md".into(),
+            content: "# Plan\n\n- T1: scaffold\n- T2: models\n".into(),
+        });
+
+        let (lines, first) = spec_lines(&state);
+        let marked: Vec<String> = lines
+            .iter()
+            .filter(|line| line.spans.first().is_some_and(|s| s.content == "▌"))
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+
+        assert_eq!(marked.len(), 1);
+        assert!(marked[0].contains("T2"), "{marked:?}");
+        assert!(first.is_some());
+    }
+}
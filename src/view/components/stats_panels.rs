@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::columns::TableId;
+use crate::app::pacing;
+use crate::app::AppState;
+use crate::model::{ApiErrorKind, Theme, TranscriptEventKind};
+use super::table::{render_table, Column, TableRow};
+
+/// Tools whose input summary is the file path they touch.
+const READ_TOOLS: &[&str] = &["Read"];
+const EDIT_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write", "NotebookEdit"];
+
+/// Calls and failed results of one tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolRow {
+    pub tool: String,
+    pub calls: usize,
+    pub failures: usize,
+}
+
+/// Pure function: per-tool calls and failures over the live event buffer, most called first.
+pub fn tool_rows(state: &AppState) -> Vec<ToolRow> {
+    let mut tools: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for event in &state.domain.events {
+        match &event.kind {
+            TranscriptEventKind::ToolUse { tool_name, .. } => tools.entry(tool_name.as_str()).or_default().0 += 1,
+            TranscriptEventKind::ToolResult { tool_name, is_error: true, .. } => {
+                tools.entry(tool_name.as_str()).or_default().1 += 1
+            }
+            _ => {}
+        }
+    }
+    let mut rows: Vec<ToolRow> = tools
+        .into_iter()
+        .map(|(tool, (calls, failures))| ToolRow { tool: tool.to_string(), calls, failures })
+        .collect();
+    rows.sort_by_key(|r| std::cmp::Reverse(r.calls));
+    rows
+}
+
+/// Reads and edits of one file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRow {
+    pub path: String,
+    pub reads: usize,
+    pub edits: usize,
+}
+
+/// Pure function: files touched in the live event buffer, most touched first.
+/// Paths under the project root are shown relative to it.
+pub fn hot_files(state: &AppState) -> Vec<FileRow> {
+    let root = state.meta.project_path.trim_end_matches('/');
+    let mut files: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for event in &state.domain.events {
+        let TranscriptEventKind::ToolUse { tool_name, input_summary } = &event.kind else {
+            continue;
+        };
+        let path = input_summary.trim();
+        if path.is_empty() {
+            continue;
+        }
+        let path = path.strip_prefix(root).and_then(|p| p.strip_prefix('/')).filter(|_| !root.is_empty()).unwrap_or(path);
+        if READ_TOOLS.contains(&tool_name.as_str()) {
+            files.entry(path).or_default().0 += 1;
+        } else if EDIT_TOOLS.contains(&tool_name.as_str()) {
+            files.entry(path).or_default().1 += 1;
+        }
+    }
+    let mut rows: Vec<FileRow> = files
+        .into_iter()
+        .map(|(path, (reads, edits))| FileRow { path: path.to_string(), reads, edits })
+        .collect();
+    rows.sort_by_key(|r| std::cmp::Reverse(r.reads + r.edits));
+    rows
+}
+
+fn panel_block(title: &'static str) -> Block<'static> {
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::PANEL_BORDER))
+        .title(title)
+}
+
+const TOOL_COLUMNS: [Column; 3] = [
+    Column::new("Tool", 10, 40, 1),
+    Column::new("Calls", 5, 8, 0).right(),
+    Column::new("Failed", 6, 8, 0).right(),
+];
+
+const FILE_COLUMNS: [Column; 3] = [
+    Column::new("File", 12, 80, 1),
+    Column::new("Reads", 5, 8, 0).right(),
+    Column::new("Edits", 5, 8, 0).right(),
+];
+
+/// Render the tool stats panel: calls and failures per tool.
+pub fn render_tool_stats(frame: &mut Frame, area: Rect, state: &AppState) {
+    let rows: Vec<TableRow> = tool_rows(state)
+        .into_iter()
+        .map(|r| {
+            let failed_style = if r.failures > 0 { Style::default().fg(Theme::ERROR) } else { Style::default().fg(Theme::MUTED_TEXT) };
+            TableRow::new(
+                vec![
+                    Span::styled(r.tool, Style::default().fg(Theme::TEXT)),
+                    Span::styled(r.calls.to_string(), Style::default().fg(Theme::TEXT)),
+                    Span::styled(r.failures.to_string(), failed_style),
+                ],
+                Style::default(),
+            )
+        })
+        .collect();
+    let adjust = state.ui.table_columns.get(TableId::ToolStats);
+    render_table(frame, area, &TOOL_COLUMNS, rows, panel_block("Tools"), adjust, None);
+}
+
+/// Render the hot files panel: files read and edited most often.
+pub fn render_hot_files(frame: &mut Frame, area: Rect, state: &AppState) {
+    let rows: Vec<TableRow> = hot_files(state)
+        .into_iter()
+        .map(|r| {
+            TableRow::new(
+                vec![Span::raw(r.path), Span::raw(r.reads.to_string()), Span::raw(r.edits.to_string())],
+                Style::default().fg(Theme::TEXT),
+            )
+        })
+        .collect();
+    let adjust = state.ui.table_columns.get(TableId::HotFiles);
+    render_table(frame, area, &FILE_COLUMNS, rows, panel_block("Hot Files"), adjust, None);
+}
+
+/// Render the errors panel: recent errors, newest first.
+pub fn render_errors(frame: &mut Frame, area: Rect, state: &AppState) {
+    let lines: Vec<Line> = if state.meta.errors.is_empty() {
+        vec![Line::from(Span::styled("No errors", Style::default().fg(Theme::MUTED_TEXT)))]
+    } else {
+        state
+            .meta
+            .errors
+            .iter()
+            .rev()
+            .map(|e| Line::from(Span::styled(e.clone(), Style::default().fg(Theme::ERROR))))
+            .collect()
+    };
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true }).block(panel_block("Errors"));
+    frame.render_widget(paragraph, area);
+}
+
+/// Render the pacing panel: the advisor's headline over its figures.
+pub fn render_pacing(frame: &mut Frame, area: Rect, state: &AppState) {
+    let advice = pacing::advise(&state.domain.api_health, &state.domain.pacing.throughput, &state.domain.agents, state.meta.clock.now());
+    let color = if advice.throttled {
+        Theme::WARNING
+    } else if state.domain.api_health.counts().contains_key(&ApiErrorKind::ContextLength) {
+        Theme::ERROR
+    } else {
+        Theme::TEXT
+    };
+    let mut lines = vec![Line::from(Span::styled(advice.headline, Style::default().fg(color)))];
+    lines.extend(advice.details.into_iter().map(|d| Line::from(Span::styled(d, Style::default().fg(Theme::MUTED_TEXT)))));
+    let paragraph = Paragraph::new(lines).wrap(Wrap { trim: true }).block(panel_block("Pacing"));
+    frame.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ToolName, TranscriptEvent};
+    use chrono::Utc;
+
+    fn push(state: &mut AppState, kind: TranscriptEventKind) {
+        state.domain.events.push_back(TranscriptEvent::new(Utc::now(), kind));
+    }
+
+    fn tool_use(tool: &str, input: &str) -> TranscriptEventKind {
+        TranscriptEventKind::ToolUse { tool_name: ToolName::new(tool), input_summary: input.into() }
+    }
+
+    #[test]
+    fn tool_rows_count_calls_and_failures() {
+        let mut state = AppState::new();
+        push(&mut state, tool_use("Bash", "ls"));
+        push(&mut state, tool_use("Read", "a.rs"));
+        push(&mut state, tool_use("Bash", "cargo test"));
+        push(
+            &mut state,
+            TranscriptEventKind::ToolResult {
+                tool_name: ToolName::new("Bash"),
+                result_summary: "failed".into(),
+                duration_ms: None,
+                is_error: true,
+            },
+        );
+
+        let rows = tool_rows(&state);
+        assert_eq!(rows[0], ToolRow { tool: "Bash".into(), calls: 2, failures: 1 });
+        assert_eq!(rows[1], ToolRow { tool: "Read".into(), calls: 1, failures: 0 });
+    }
+
+    #[test]
+    fn hot_files_rank_paths_relative_to_the_project() {
+        let mut state = AppState::new();
+        state.meta.project_path = "/work/app".into();
+        push(&mut state, tool_use("Read", "/work/app/src/lib.rs"));
+        push(&mut state, tool_use("Edit", "/work/app/src/lib.rs"));
+        push(&mut state, tool_use("Write", "/tmp/notes.md"));
+        push(&mut state, tool_use("Bash", "/work/app/src/lib.rs"));
+
+        let files = hot_files(&state);
+        assert_eq!(files[0], FileRow { path: "src/lib.rs".into(), reads: 1, edits: 1 });
+        assert_eq!(files[1], FileRow { path: "/tmp/notes.md".into(), reads: 0, edits: 1 });
+        assert_eq!(files.len(), 2);
+    }
+}
@@ -0,0 +1,492 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::event_stream::{
+    clean_detail, event_belongs_to_agent, event_matches_search_transcript, format_transcript_event_lines, new_events_pill,
+    render_detail_lines,
+};
+use super::filter_bar::filter_chip;
+use super::format::{format_bytes, TimeFormat};
+use super::scroll::{overflow_width, pan_indicator, wrapped_height};
+use crate::app::tool_outputs::{step_outputs, ToolOutputIndex};
+use crate::app::{AppState, ViewState};
+use crate::model::{AgentId, SourceRef, Theme, ToolName, TranscriptEvent, TranscriptEventKind};
+
+/// One-line details in the tree are cut here unless the panel is panned sideways.
+const DETAIL_MAX_CHARS: usize = 80;
+
+/// One logical step of an agent: the event that opened it (usually assistant text)
+/// plus the tool calls and results that followed it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step<'a> {
+    pub head: &'a TranscriptEvent,
+    pub tools: Vec<&'a TranscriptEvent>,
+}
+
+impl Step<'_> {
+    /// Identity used to remember expansion across renders (events are append-only).
+    pub fn key(&self) -> DateTime<Utc> {
+        self.head.timestamp
+    }
+
+    /// Tools called in this step with call counts, in first-use order.
+    fn tool_counts(&self) -> Vec<(&ToolName, usize)> {
+        let mut counts: Vec<(&ToolName, usize)> = Vec::new();
+        let calls = std::iter::once(self.head).chain(self.tools.iter().copied());
+        for event in calls {
+            if let TranscriptEventKind::ToolUse { tool_name, .. } = &event.kind {
+                match counts.iter_mut().find(|(name, _)| *name == tool_name) {
+                    Some((_, n)) => *n += 1,
+                    None => counts.push((tool_name, 1)),
+                }
+            }
+        }
+        counts
+    }
+}
+
+fn is_tool_event(event: &TranscriptEvent) -> bool {
+    matches!(
+        event.kind,
+        TranscriptEventKind::ToolUse { .. } | TranscriptEventKind::ToolResult { .. }
+    )
+}
+
+/// Pure function: group chronological events into steps.
+/// Tool events attach to the preceding step; any other event starts a new one.
+pub fn group_steps<'a>(events: impl IntoIterator<Item = &'a TranscriptEvent>) -> Vec<Step<'a>> {
+    let mut steps: Vec<Step<'a>> = Vec::new();
+    for event in events {
+        match steps.last_mut() {
+            Some(step) if is_tool_event(event) => step.tools.push(event),
+            _ => steps.push(Step { head: event, tools: Vec::new() }),
+        }
+    }
+    steps
+}
+
+/// Steps for an agent, oldest first. Uses the same event attribution as the agent event stream.
+/// In AgentDetail the view's filter keeps the steps with a matching event.
+pub fn agent_steps<'a>(state: &'a AppState, agent_id: &str) -> Vec<Step<'a>> {
+    let agent_session = state
+        .domain
+        .agents
+        .get(&AgentId::new(agent_id))
+        .and_then(|a| a.session_id.as_ref());
+    let steps = group_steps(
+        state
+            .domain
+            .events
+            .iter()
+            .filter(|e| event_belongs_to_agent(e, agent_id, agent_session)),
+    );
+    let query = match state.ui.filter.as_deref() {
+        Some(filter) if !filter.is_empty() && state.ui.view == ViewState::AgentDetail => filter.to_lowercase(),
+        _ => return steps,
+    };
+    steps
+        .into_iter()
+        .filter(|step| {
+            std::iter::once(step.head)
+                .chain(step.tools.iter().copied())
+                .any(|e| event_matches_search_transcript(&e.kind, &query, e.agent_id.as_ref(), e.origin))
+        })
+        .collect()
+}
+
+/// Key of the step under the cursor (cursor counts newest first, clamped to the last step).
+pub fn step_key_at(steps: &[Step<'_>], cursor: usize) -> Option<DateTime<Utc>> {
+    let idx = cursor.min(steps.len().checked_sub(1)?);
+    steps.iter().rev().nth(idx).map(Step::key)
+}
+
+/// Render an agent's steps newest first, collapsed to one line unless expanded.
+pub fn render_step_tree(
+    frame: &mut Frame,
+    area: Rect,
+    state: &AppState,
+    agent_id: &str,
+    cursor: usize,
+    is_focused: bool,
+) {
+    let steps = agent_steps(state, agent_id);
+    // Truncated or panned sideways, lines are shown whole; the limit is only known then
+    let x_offset = state.ui.scroll_offsets.agent_events_x;
+    let truncated = state.ui.truncated_events.is_truncated(&state.ui.view);
+    let whole = truncated || x_offset > 0;
+    let cut = if whole { usize::MAX } else { DETAIL_MAX_CHARS };
+    let (lines, cursor_line) = build_step_tree_lines(
+        &steps,
+        &state.ui.expanded_steps,
+        &state.ui.full_texts,
+        cursor,
+        &state.ui.time_format,
+        &state.domain.tool_outputs,
+        cut,
+    );
+
+    // Keep the cursor row about a third of the way down once the list is taller than the panel
+    let inner_width = area.width.saturating_sub(2);
+    let height = area.height.saturating_sub(2) as usize;
+    let max_x = overflow_width(&lines, inner_width);
+    let x = x_offset.min(max_x);
+    state.ui.scroll_limits.agent_events_x.set(whole.then_some(max_x));
+    let unwrapped = truncated || x > 0;
+    let rows_before = if unwrapped { cursor_line } else { wrapped_height(&lines[..cursor_line], inner_width) };
+    let scroll = rows_before
+        .saturating_sub(height / 3)
+        .min(u16::MAX as usize)
+        .min(10000) as u16;
+
+    let title = if state.ui.auto_scroll {
+        "Activity [auto-scroll]"
+    } else {
+        "Activity"
+    };
+
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if is_focused {
+            Theme::ACTIVE_BORDER
+        } else {
+            Theme::PANEL_BORDER
+        }))
+        .title(title);
+    let unseen = match &state.ui.unseen_events.agent {
+        Some((id, n)) if id.as_str() == agent_id && !state.ui.auto_scroll => *n,
+        _ => 0,
+    };
+    if unseen > 0 {
+        block = block.title_top(new_events_pill(unseen));
+    }
+    if state.ui.view == ViewState::AgentDetail {
+        if let Some(chip) = filter_chip(state) {
+            block = block.title(chip);
+        }
+    }
+
+    let paragraph = if unwrapped {
+        if max_x > 0 {
+            block = block.title_top(pan_indicator(x, max_x));
+        }
+        Paragraph::new(lines).block(block).scroll((scroll, x.min(u16::MAX as usize) as u16))
+    } else {
+        Paragraph::new(lines).block(block).wrap(Wrap { trim: false }).scroll((scroll, 0))
+    };
+
+    frame.render_widget(paragraph, area);
+}
+
+/// Pure function: build tree lines (newest step first) and the index of the cursor's line.
+/// Steps with captured tool outputs (`outputs`) show their size; one-line
+/// details are cut at `cut` characters.
+pub fn build_step_tree_lines(
+    steps: &[Step<'_>],
+    expanded: &HashSet<DateTime<Utc>>,
+    full_texts: &HashMap<SourceRef, String>,
+    cursor: usize,
+    time_format: &TimeFormat,
+    outputs: &ToolOutputIndex,
+    cut: usize,
+) -> (Vec<Line<'static>>, usize) {
+    if steps.is_empty() {
+        return (
+            vec![Line::from(Span::styled("No events", Style::default().fg(Theme::MUTED_TEXT)))],
+            0,
+        );
+    }
+
+    let cursor = cursor.min(steps.len() - 1);
+    let mut lines = Vec::new();
+    let mut cursor_line = 0;
+
+    for (idx, step) in steps.iter().rev().enumerate() {
+        let is_open = expanded.contains(&step.key());
+        if idx == cursor {
+            cursor_line = lines.len();
+        }
+        lines.push(step_summary_line(step, full_texts, is_open, idx == cursor, time_format, outputs, cut));
+        if is_open {
+            lines.extend(step_body_lines(step, full_texts, outputs, cut));
+        }
+    }
+
+    (lines, cursor_line)
+}
+
+/// Collapsed form: `▸ 10:00:00 💭 First line of text  Read×3 Edit  output: 48KB`
+fn step_summary_line(
+    step: &Step<'_>,
+    full_texts: &HashMap<SourceRef, String>,
+    is_open: bool,
+    is_selected: bool,
+    time_format: &TimeFormat,
+    outputs: &ToolOutputIndex,
+    cut: usize,
+) -> Line<'static> {
+    let (icon, header, detail, color, _) = format_transcript_event_lines(&full_kind(step.head, full_texts));
+    let headline = detail
+        .map(|d| clean_detail(&d))
+        .and_then(|d| d.lines().next().map(str::to_string))
+        .filter(|d| !d.is_empty())
+        .unwrap_or(header);
+
+    let mut spans = vec![
+        Span::styled(if is_open { "▾ " } else { "▸ " }, Style::default().fg(Theme::MUTED_TEXT)),
+        Span::styled(
+            format!("{} ", time_format.time(step.head.timestamp)),
+            Style::default().fg(Theme::MUTED_TEXT),
+        ),
+        Span::styled(format!("{} ", icon), Style::default().fg(color)),
+        Span::styled(crate::watcher::truncate_str(&headline, cut), Style::default().fg(color)),
+    ];
+
+    let tools: Vec<String> = step
+        .tool_counts()
+        .into_iter()
+        .map(|(name, n)| if n > 1 { format!("{}×{}", name, n) } else { name.to_string() })
+        .collect();
+    if !tools.is_empty() {
+        spans.push(Span::styled(
+            format!("  {}", tools.join(" ")),
+            Style::default().fg(Theme::INFO),
+        ));
+    }
+
+    // `o` opens the newest captured output
+    if let Some((_, output)) = step_outputs(step, outputs).pop() {
+        let hint = if is_selected { " (press o to view)" } else { "" };
+        spans.push(Span::styled(
+            format!("  output: {}{}", format_bytes(output.bytes), hint),
+            Style::default().fg(Theme::ACCENT),
+        ));
+    }
+
+    let line = Line::from(spans);
+    if is_selected {
+        line.style(Style::default().bg(Theme::SELECTION_BG).add_modifier(Modifier::BOLD))
+    } else {
+        line
+    }
+}
+
+/// Expanded form: the head's full text, then one branch line per tool event.
+/// The event's kind with its clipped text replaced by the whole text, once read.
+fn full_kind<'a>(event: &'a TranscriptEvent, full_texts: &HashMap<SourceRef, String>) -> Cow<'a, TranscriptEventKind> {
+    let Some(text) = event.full_text_at.as_ref().and_then(|at| full_texts.get(at)) else {
+        return Cow::Borrowed(&event.kind);
+    };
+    let mut kind = event.kind.clone();
+    match &mut kind {
+        TranscriptEventKind::AssistantMessage { content } => content.clone_from(text),
+        TranscriptEventKind::ToolUse { input_summary, .. } => input_summary.clone_from(text),
+        TranscriptEventKind::ToolResult { result_summary, .. } => result_summary.clone_from(text),
+        _ => {}
+    }
+    Cow::Owned(kind)
+}
+
+fn step_body_lines(
+    step: &Step<'_>,
+    full_texts: &HashMap<SourceRef, String>,
+    outputs: &ToolOutputIndex,
+    cut: usize,
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+
+    if !is_tool_event(step.head) {
+        let (_, _, detail, _, _) = format_transcript_event_lines(&full_kind(step.head, full_texts));
+        if let Some(detail) = detail {
+            let clean = clean_detail(&detail);
+            if !clean.is_empty() {
+                lines.extend(render_detail_lines(&clean, None));
+            }
+        }
+    }
+
+    let tool_events = std::iter::once(step.head)
+        .filter(|e| is_tool_event(e))
+        .chain(step.tools.iter().copied());
+    let count = step.tools.len() + usize::from(is_tool_event(step.head));
+    for (idx, event) in tool_events.enumerate() {
+        let branch = if idx + 1 == count { "  └ " } else { "  ├ " };
+        let (icon, header, detail, color, _) = format_transcript_event_lines(&full_kind(event, full_texts));
+        let mut spans = vec![
+            Span::styled(branch, Style::default().fg(Theme::SEPARATOR)),
+            Span::styled(format!("{} ", icon), Style::default().fg(color)),
+            Span::styled(header, Style::default().fg(color)),
+        ];
+        if let Some(first) = detail.as_deref().map(clean_detail).and_then(|d| d.lines().next().map(str::to_string)) {
+            spans.push(Span::styled(
+                format!("  {}", crate::watcher::truncate_str(&first, cut)),
+                Style::default().fg(Theme::MUTED_TEXT),
+            ));
+        }
+        if let Some(output) = outputs.for_event(event) {
+            spans.push(Span::styled(
+                format!("  output: {}", format_bytes(output.bytes)),
+                Style::default().fg(Theme::ACCENT),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(secs: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 18, 10, 0, secs).unwrap()
+    }
+
+    fn text(secs: u32, content: &str) -> TranscriptEvent {
+        TranscriptEvent::new(at(secs), TranscriptEventKind::AssistantMessage { content: content.into() })
+    }
+
+    fn tool_use(secs: u32, tool: &str) -> TranscriptEvent {
+        TranscriptEvent::new(
+            at(secs),
+            TranscriptEventKind::ToolUse { tool_name: tool.into(), input_summary: format!("{tool} input") },
+        )
+    }
+
+    fn tool_result(secs: u32, tool: &str) -> TranscriptEvent {
+        TranscriptEvent::new(
+            at(secs),
+            TranscriptEventKind::ToolResult {
+                tool_name: tool.into(),
+                result_summary: "ok".into(),
+                duration_ms: Some(5),
+                is_error: false,
+            },
+        )
+    }
+
+    fn line_text(line: &Line<'_>) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn group_steps_attaches_tools_to_preceding_text() {
+        let events = [
+            tool_use(0, "Read"),
+            text(1, "Looking at the parser"),
+            tool_use(2, "Read"),
+            tool_result(3, "Read"),
+            tool_use(4, "Edit"),
+            text(5, "Done"),
+        ];
+
+        let steps = group_steps(&events);
+
+        assert_eq!(steps.len(), 3);
+        // Leading tool call without text gets a step of its own
+        assert_eq!(steps[0].head, &events[0]);
+        assert!(steps[0].tools.is_empty());
+        assert_eq!(steps[1].head, &events[1]);
+        assert_eq!(steps[1].tools.len(), 3);
+        assert!(steps[2].tools.is_empty());
+    }
+
+    #[test]
+    fn collapsed_steps_render_one_line_each_newest_first() {
+        let events = [
+            text(1, "Looking at the parser\nmore detail"),
+            tool_use(2, "Read"),
+            tool_use(3, "Read"),
+            tool_use(4, "Edit"),
+            text(5, "Done"),
+        ];
+        let steps = group_steps(&events);
+
+        let (lines, cursor_line) =
+            build_step_tree_lines(&steps, &HashSet::new(), &HashMap::new(), 1, &TimeFormat::default(), &ToolOutputIndex::default(), DETAIL_MAX_CHARS);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(cursor_line, 1);
+        assert!(line_text(&lines[0]).contains("Done"));
+        let older = line_text(&lines[1]);
+        assert!(older.starts_with("▸ "));
+        assert!(older.contains("Looking at the parser"));
+        assert!(!older.contains("more detail"));
+        assert!(older.contains("Read×2 Edit"), "{older}");
+    }
+
+    #[test]
+    fn expanded_step_shows_text_and_tool_branches() {
+        let events = [text(1, "Plan"), tool_use(2, "Read"), tool_result(3, "Read"), text(4, "Next")];
+        let steps = group_steps(&events);
+        let expanded: HashSet<_> = [steps[0].key()].into_iter().collect();
+
+        // Cursor past the end clamps to the oldest step
+        let (lines, cursor_line) =
+            build_step_tree_lines(&steps, &expanded, &HashMap::new(), 99, &TimeFormat::default(), &ToolOutputIndex::default(), DETAIL_MAX_CHARS);
+        let texts: Vec<String> = lines.iter().map(line_text).collect();
+
+        assert_eq!(cursor_line, 1);
+        assert!(texts[1].starts_with("▾ "));
+        assert!(texts.iter().any(|t| t.starts_with("  ├ ⚡ Read")));
+        assert!(texts.last().unwrap().starts_with("  └ ✓ Read (5ms)"));
+    }
+
+    #[test]
+    fn expanded_step_shows_the_whole_text_once_read() {
+        let source = SourceRef { path: std::path::Path::new("s1.jsonl").into(), offset: 120, block: 0 };
+        let mut head = text(1, "Clipped...");
+        head.full_text_at = Some(source.clone());
+        let events = [head];
+        let steps = group_steps(&events);
+        let expanded: HashSet<_> = [steps[0].key()].into_iter().collect();
+        let render = |full_texts: &HashMap<SourceRef, String>| {
+            let (lines, _) =
+                build_step_tree_lines(&steps, &expanded, full_texts, 0, &TimeFormat::default(), &ToolOutputIndex::default(), DETAIL_MAX_CHARS);
+            lines.iter().map(line_text).collect::<Vec<_>>().join("\n")
+        };
+
+        assert!(render(&HashMap::new()).contains("Clipped..."));
+        let whole = render(&[(source, "Clipped text, all of it".to_string())].into());
+        assert!(whole.contains("all of it") && !whole.contains("Clipped..."), "{whole}");
+    }
+
+    #[test]
+    fn captured_outputs_show_their_size() {
+        use crate::app::tool_outputs::ToolOutput;
+
+        let mut result = tool_result(3, "Bash");
+        result.tool_use_id = Some("toolu_01".into());
+        let events = [text(1, "Run the tests"), tool_use(2, "Bash"), result];
+        let steps = group_steps(&events);
+        let outputs: ToolOutputIndex =
+            [("toolu_01".to_string(), ToolOutput { path: "toolu_01.txt".into(), bytes: 48 * 1024 })].into_iter().collect();
+        let expanded: HashSet<_> = [steps[0].key()].into_iter().collect();
+
+        let (lines, _) = build_step_tree_lines(&steps, &expanded, &HashMap::new(), 0, &TimeFormat::default(), &outputs, DETAIL_MAX_CHARS);
+        let texts: Vec<String> = lines.iter().map(line_text).collect();
+
+        assert!(texts[0].ends_with("output: 48KB (press o to view)"), "{}", texts[0]);
+        assert!(texts.last().unwrap().ends_with("output: 48KB"));
+    }
+
+    #[test]
+    fn step_key_at_counts_newest_first() {
+        let events = [text(1, "a"), text(2, "b")];
+        let steps = group_steps(&events);
+
+        assert_eq!(step_key_at(&steps, 0), Some(at(2)));
+        assert_eq!(step_key_at(&steps, 5), Some(at(1)));
+        assert_eq!(step_key_at(&[], 0), None);
+    }
+}
@@ -0,0 +1,197 @@
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Modifier, Style},
+    text::Span,
+    widgets::{Block, Row, Table},
+    Frame,
+};
+
+use crate::app::columns::ColumnAdjust;
+use crate::model::Theme;
+
+/// Gap between columns, as ratatui draws it.
+const COLUMN_SPACING: u16 = 1;
+
+/// Side of its column a cell is aligned to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    /// Numbers: durations, counts and costs line up on their last digit
+    Right,
+}
+
+/// How a column is sized and aligned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    pub header: &'static str,
+    /// Narrowest the column is squeezed to when the table is too wide
+    pub min: u16,
+    /// Widest the content makes it (the user may widen it further)
+    pub max: u16,
+    /// Share of the spare width it takes (0: none)
+    pub grow: u16,
+    pub align: Align,
+}
+
+impl Column {
+    pub const fn new(header: &'static str, min: u16, max: u16, grow: u16) -> Self {
+        Self { header, min, max, grow, align: Align::Left }
+    }
+
+    /// The same column, right-aligned.
+    pub const fn right(self) -> Self {
+        Self { align: Align::Right, ..self }
+    }
+}
+
+/// A row of cells with its style.
+pub struct TableRow<'a> {
+    pub cells: Vec<Span<'a>>,
+    pub style: Style,
+}
+
+impl<'a> TableRow<'a> {
+    pub fn new(cells: Vec<Span<'a>>, style: Style) -> Self {
+        Self { cells, style }
+    }
+}
+
+/// Pure function: column widths for `available` cells. Each column is as wide
+/// as its header and content up to its `max`, plus the user's offset; if the
+/// table is too wide the widest columns give way first (down to their `min`),
+/// and spare width goes to the growing columns by their share.
+pub fn balance(columns: &[Column], rows: &[TableRow<'_>], available: u16, adjust: Option<&ColumnAdjust>) -> Vec<u16> {
+    let available = available.saturating_sub(COLUMN_SPACING * columns.len().saturating_sub(1) as u16);
+    let mut widths: Vec<u16> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let content = rows.iter().filter_map(|row| row.cells.get(i)).map(|cell| cell.width()).max().unwrap_or(0);
+            let natural = content.max(column.header.chars().count()).min(column.max as usize) as i32;
+            let offset = adjust.and_then(|a| a.offsets.get(i)).copied().unwrap_or(0) as i32;
+            (natural + offset).max(column.min as i32).min(u16::MAX as i32) as u16
+        })
+        .collect();
+
+    let mut total: u32 = widths.iter().map(|&w| w as u32).sum();
+    while total > available as u32 {
+        let widest = (0..columns.len()).filter(|&i| widths[i] > columns[i].min).max_by_key(|&i| (widths[i], i));
+        let Some(i) = widest else { break };
+        widths[i] -= 1;
+        total -= 1;
+    }
+
+    let shares: u32 = columns.iter().map(|c| c.grow as u32).sum();
+    let spare = (available as u32).saturating_sub(total);
+    if shares > 0 && spare > 0 {
+        let mut given = 0;
+        for (i, column) in columns.iter().enumerate() {
+            let share = spare * column.grow as u32 / shares;
+            widths[i] += share as u16;
+            given += share;
+        }
+        if let Some(last) = columns.iter().rposition(|c| c.grow > 0) {
+            widths[last] += (spare - given) as u16;
+        }
+    }
+    widths
+}
+
+/// Pure function: `text` fitted to exactly `width` columns — cut with an
+/// ellipsis when longer, padded on the side away from `align` when shorter.
+pub fn format_cell(text: &str, width: u16, align: Align) -> String {
+    let width = width as usize;
+    let len = text.chars().count();
+    if len > width {
+        return match width {
+            0 => String::new(),
+            _ => text.chars().take(width - 1).chain(std::iter::once('…')).collect(),
+        };
+    }
+    match align {
+        Align::Left => format!("{:<width$}", text),
+        Align::Right => format!("{:>width$}", text),
+    }
+}
+
+/// Render a table with balanced column widths, every cell fitted to its
+/// column by `format_cell`. With `selected` the table has focus and that
+/// column's header is underlined for the resize keys.
+pub fn render_table(
+    frame: &mut Frame,
+    area: Rect,
+    columns: &[Column],
+    rows: Vec<TableRow<'_>>,
+    block: Block<'_>,
+    adjust: Option<&ColumnAdjust>,
+    selected: Option<usize>,
+) {
+    let inner = block.inner(area);
+    let widths = balance(columns, &rows, inner.width, adjust);
+    let header_style = Style::default().fg(Theme::INFO).add_modifier(Modifier::BOLD);
+    let header = Row::new(columns.iter().zip(&widths).enumerate().map(|(i, (column, &width))| {
+        let style = if selected == Some(i) { header_style.add_modifier(Modifier::UNDERLINED) } else { header_style };
+        Span::styled(format_cell(column.header, width, column.align), style)
+    }));
+    let rows = rows.into_iter().map(|row| {
+        let cells = row.cells.into_iter().zip(columns.iter().zip(&widths)).map(|(cell, (column, &width))| {
+            Span::styled(format_cell(&cell.content, width, column.align), cell.style)
+        });
+        Row::new(cells.collect::<Vec<_>>()).style(row.style)
+    });
+    let table = Table::new(rows, widths.iter().copied().map(Constraint::Length))
+        .column_spacing(COLUMN_SPACING)
+        .header(header)
+        .block(block);
+    frame.render_widget(table, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: [Column; 3] = [
+        Column::new("Tool", 6, 30, 1),
+        Column::new("Calls", 5, 6, 0),
+        Column::new("Failed", 6, 6, 0),
+    ];
+
+    fn row(tool: &'static str) -> TableRow<'static> {
+        TableRow::new(vec![Span::raw(tool), Span::raw("12"), Span::raw("0")], Style::default())
+    }
+
+    #[test]
+    fn columns_fit_content_and_spare_width_grows_the_flexible_one() {
+        let rows = [row("Bash"), row("mcp__github__create_pull_request")];
+        // 30 (capped) + 5 + 6 + 2 gaps = 43
+        assert_eq!(balance(&COLUMNS, &rows, 43, None), [30, 5, 6]);
+        assert_eq!(balance(&COLUMNS, &rows, 53, None), [40, 5, 6]);
+        assert_eq!(balance(&COLUMNS, &[row("Read")], 19, None), [6, 5, 6], "never below the minimum");
+    }
+
+    #[test]
+    fn narrow_tables_squeeze_the_widest_columns_first() {
+        let rows = [row("mcp__github__create_pull_request")];
+        assert_eq!(balance(&COLUMNS, &rows, 30, None), [17, 5, 6]);
+        assert_eq!(balance(&COLUMNS, &rows, 5, None), [6, 5, 6], "minimums hold; the rest is clipped");
+    }
+
+    #[test]
+    fn cells_are_padded_or_cut_to_their_width() {
+        assert_eq!(format_cell("Bash", 6, Align::Left), "Bash  ");
+        assert_eq!(format_cell("12", 6, Align::Right), "    12");
+        assert_eq!(format_cell("abcdefghijklmnop", 12, Align::Left), "abcdefghijk…");
+        assert_eq!(format_cell("$1234.56", 5, Align::Right), "$123…");
+        assert_eq!(format_cell("x", 0, Align::Right), "");
+    }
+
+    #[test]
+    fn offsets_widen_or_narrow_a_column() {
+        let rows = [row("Bash")];
+        let adjust = ColumnAdjust { selected: 1, offsets: vec![0, 4] };
+        assert_eq!(balance(&COLUMNS, &rows, 0, Some(&adjust))[1], 5, "squeezed back to its minimum");
+        assert_eq!(balance(&COLUMNS, &rows, 40, Some(&adjust)), [23, 9, 6]);
+        let narrower = ColumnAdjust { selected: 0, offsets: vec![-10] };
+        assert_eq!(balance(&COLUMNS, &rows, 19, Some(&narrower)), [6, 5, 6]);
+    }
+}
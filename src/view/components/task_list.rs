@@ -6,8 +6,12 @@ use ratatui::{
     Frame,
 };
 
+use super::filter_bar::filter_chip;
+use crate::app::failures::TaskFailure;
+use crate::app::retries;
+use crate::app::task_links::LinkConfidence;
 use crate::app::{AppState, PanelFocus};
-use crate::model::{TaskStatus, Theme};
+use crate::model::{ColorMode, TaskStatus, Theme};
 
 /// Render task list panel.
 /// Shows scrollable list of tasks with status indicators.
@@ -17,17 +21,20 @@ pub fn render_task_list(frame: &mut Frame, area: Rect, state: &AppState) {
 
     let is_focused = matches!(state.ui.focus, PanelFocus::Left);
 
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if is_focused {
+            Theme::ACTIVE_BORDER
+        } else {
+            Theme::PANEL_BORDER
+        }))
+        .title("Tasks");
+    if let Some(chip) = filter_chip(state) {
+        block = block.title(chip);
+    }
+
     let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(if is_focused {
-                    Theme::ACTIVE_BORDER
-                } else {
-                    Theme::PANEL_BORDER
-                }))
-                .title("Tasks"),
-        )
+        .block(block)
         .style(Style::default().fg(Theme::TEXT));
 
     frame.render_widget(list, area);
@@ -83,7 +90,10 @@ fn build_task_list_items(state: &AppState) -> Vec<ListItem<'static>> {
                     let flat_idx = task_index + original_idx;
                     let is_selected = state.ui.selected_task_index == Some(flat_idx);
 
-                    let (status_symbol, status_color) = task_status_display(&task.status);
+                    let (mut status_symbol, status_color) = task_status_display(&task.status);
+                    if state.ui.color_mode.uses_labels() {
+                        status_symbol = ColorMode::task_status_label(&task.status);
+                    }
                     let bg = if is_selected { Theme::SELECTION_BG } else { Theme::BACKGROUND };
 
                     let mut spans = vec![
@@ -100,12 +110,37 @@ fn build_task_list_items(state: &AppState) -> Vec<ListItem<'static>> {
                     if let Some(ref agent_id) = task.agent_id {
                         let id_str = agent_id.as_str();
                         let short = &id_str[..id_str.len().min(7)];
+                        // Inferred links carry their confidence marker, weaker ones muted
+                        let (marker, color) = match state.domain.task_links.get(&task.id) {
+                            Some(link) if link.confidence < LinkConfidence::High => (link.confidence.marker(), Theme::MUTED_TEXT),
+                            Some(link) => (link.confidence.marker(), Theme::AGENT_LABEL),
+                            None => ("", Theme::AGENT_LABEL),
+                        };
                         spans.push(Span::styled(
-                            format!("  {}", short),
-                            Style::default().fg(Theme::AGENT_LABEL).bg(bg),
+                            format!("  {}{}", marker, short),
+                            Style::default().fg(color).bg(bg),
                         ));
                     }
 
+                    let attempts = state.domain.task_attempts.get(&task.id);
+                    if attempts.len() > 1 {
+                        let color = if retries::is_looping(attempts) { Theme::WARNING } else { Theme::MUTED_TEXT };
+                        spans.push(Span::styled(format!("  ↻{}", attempts.len()), Style::default().fg(color).bg(bg)));
+                    }
+                    if let Some(failure) = state.domain.task_failures.get(&task.id) {
+                        if matches!(task.status, TaskStatus::Failed { .. }) {
+                            spans.push(Span::styled(format!("  {}", failure_hint(failure)), Style::default().fg(Theme::ERROR).bg(bg)));
+                        }
+                    }
+                    if task.status == TaskStatus::Running {
+                        if let Some(progress) = state.domain.task_progress.get(&task.id) {
+                            spans.push(Span::styled(
+                                format!("  {}", crate::watcher::truncate_str(progress, 40)),
+                                Style::default().fg(Theme::ACCENT).bg(bg),
+                            ));
+                        }
+                    }
+
                     items.push(ListItem::new(Line::from(spans)));
                 }
 
@@ -124,6 +159,16 @@ fn build_task_list_items(state: &AppState) -> Vec<ListItem<'static>> {
     }
 }
 
+/// Pure function: a failed task's reason, retries and evidence count (`E` jumps to it).
+fn failure_hint(failure: &TaskFailure) -> String {
+    let reason = crate::watcher::truncate_str(&failure.reason, 30);
+    let retries = if failure.retry_count > 0 { format!(" · retry {}", failure.retry_count) } else { String::new() };
+    match failure.evidence.len() {
+        0 => format!("{}{}", reason, retries),
+        n => format!("{}{} · E: {} error{}", reason, retries, n, if n == 1 { "" } else { "s" }),
+    }
+}
+
 /// Get display symbol and color for task status.
 fn task_status_display(status: &TaskStatus) -> (&'static str, ratatui::style::Color) {
     match status {
@@ -0,0 +1,128 @@
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::app::toasts::{ToastLevel, ToastQueue, ToastStack};
+use crate::model::Theme;
+use crate::watcher::truncate_str;
+use super::format::format_duration;
+
+/// Toast width, borders included.
+const TOAST_WIDTH: u16 = 44;
+
+/// Render the end-of-session toast in the top-right corner, below the header.
+/// No-op with no toast queued.
+pub fn render_session_toast(frame: &mut Frame, area: Rect, toasts: &ToastQueue) {
+    let Some(toast) = toasts.front() else {
+        return;
+    };
+    let width = TOAST_WIDTH.min(area.width);
+    let height = 5.min(area.height.saturating_sub(1));
+    if width < 10 || height < 3 {
+        return;
+    }
+    let rect = Rect::new(area.x + area.width - width, area.y + 1, width, height);
+    let inner = width.saturating_sub(4) as usize;
+
+    let color = match toast.level() {
+        ToastLevel::Info => Theme::SUCCESS,
+        ToastLevel::Warn => Theme::WARNING,
+        ToastLevel::Error => Theme::ERROR,
+    };
+    let mut title = format!(" Session {} ", toast.outcome());
+    if toasts.waiting() > 0 {
+        title.push_str(&format!("(+{} more) ", toasts.waiting()));
+    }
+    let lines = vec![
+        Line::from(Span::styled(
+            truncate_str(&toast.label, inner),
+            Style::default().fg(Theme::TEXT).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!("{} · {}", toast.session_id, format_duration(toast.duration)),
+            Style::default().fg(Theme::MUTED_TEXT),
+        )),
+        Line::from(Span::styled("view [Enter] / export [e] / dismiss [Esc]", Style::default().fg(Theme::MUTED_TEXT))),
+    ];
+
+    frame.render_widget(Clear, rect);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .title(Span::styled(title, Style::default().fg(color).add_modifier(Modifier::BOLD)))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color)),
+        ),
+        rect,
+    );
+}
+
+/// Render the transient toasts stacked in the bottom-right corner, newest
+/// at the bottom. No-op with none up.
+pub fn render_toasts(frame: &mut Frame, area: Rect, toasts: &ToastStack) {
+    let max_width = (area.width / 2).max(20).min(area.width);
+    for (row, toast) in toasts.iter().rev().enumerate() {
+        let Some(y) = (area.y + area.height).checked_sub(row as u16 + 1).filter(|y| *y >= area.y) else {
+            break;
+        };
+        let (icon, color) = match toast.level {
+            ToastLevel::Info => ("✓", Theme::INFO),
+            ToastLevel::Warn => ("⚠", Theme::WARNING),
+            ToastLevel::Error => ("✗", Theme::ERROR),
+        };
+        let text = format!(" {} {} ", icon, truncate_str(&toast.text, max_width.saturating_sub(4) as usize));
+        let width = (text.chars().count() as u16).min(max_width);
+        let rect = Rect::new(area.x + area.width - width, y, width, 1);
+        frame.render_widget(Clear, rect);
+        frame.render_widget(
+            Paragraph::new(text).style(Style::default().fg(Theme::BACKGROUND).bg(color).add_modifier(Modifier::BOLD)),
+            rect,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::toasts::SessionToast;
+    use crate::model::{SessionMeta, SessionStatus};
+    use chrono::Utc;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn toast_shows_outcome_label_and_actions() {
+        let mut toasts = ToastQueue::default();
+        let mut terminal = Terminal::new(TestBackend::new(100, 12)).unwrap();
+        terminal.draw(|f| render_session_toast(f, f.area(), &toasts)).unwrap();
+        assert!(!format!("{:?}", terminal.backend().buffer()).contains("Session"));
+
+        for id in ["s1", "s2"] {
+            let meta = SessionMeta::new(id, Utc::now(), "/work/app".into()).with_status(SessionStatus::Failed);
+            toasts.push(SessionToast::from_meta(&meta, 1));
+        }
+        terminal.draw(|f| render_session_toast(f, f.area(), &toasts)).unwrap();
+        let text = format!("{:?}", terminal.backend().buffer());
+        assert!(text.contains("Session failed · 1 tool error (+1 more)"));
+        assert!(text.contains("app"));
+        assert!(text.contains("view [Enter] / export [e] / dismiss [Esc]"));
+    }
+
+    #[test]
+    fn toasts_stack_newest_at_the_bottom() {
+        let mut stack = ToastStack::default();
+        stack.push(ToastLevel::Error, "auto-save: disk full".into(), Utc::now());
+        stack.push(ToastLevel::Info, "copied path (6 bytes)".into(), Utc::now());
+        let mut terminal = Terminal::new(TestBackend::new(60, 6)).unwrap();
+        terminal.draw(|f| render_toasts(f, f.area(), &stack)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let row = |y: u16| (0..60).map(|x| buffer[(x, y)].symbol()).collect::<String>();
+        assert!(row(4).ends_with(" ✗ auto-save: disk full "));
+        assert!(row(5).ends_with(" ✓ copied path (6 bytes) "));
+        assert_eq!(buffer[(59, 5)].bg, Theme::INFO);
+    }
+}
@@ -7,7 +7,7 @@ use ratatui::{
 };
 
 use crate::app::AppState;
-use crate::model::{TaskStatus, Theme};
+use crate::model::{ColorMode, TaskStatus, Theme};
 
 /// Render wave river: horizontal swim-lane showing waves and task statuses.
 ///
@@ -78,7 +78,10 @@ fn build_wave_river_text(state: &AppState) -> Vec<Line<'static>> {
                         wave_spans.push(Span::raw("  "));
                     }
 
-                    let (symbol, color) = task_status_symbol(&task.status);
+                    let (mut symbol, color) = task_status_symbol(&task.status);
+                    if state.ui.color_mode.uses_labels() {
+                        symbol = ColorMode::task_status_label(&task.status);
+                    }
                     wave_spans.push(Span::styled(symbol, Style::default().fg(color)));
                 }
 
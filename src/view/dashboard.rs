@@ -6,19 +6,24 @@ use ratatui::{
     Frame,
 };
 
-use crate::app::{AppState, TaskViewMode};
+use crate::app::frame_budget::timed;
+use crate::app::panels::PanelKind;
+use crate::app::{AppState, PanelFocus, TaskViewMode};
 use crate::model::Theme;
 
+use super::agent_detail::render_no_agent_selected;
+use super::components::panel_grid::render_panel_grid;
+use super::components::stats_panels::{render_errors, render_hot_files, render_pacing, render_tool_stats};
 use super::components::{
-    render_event_stream, render_footer, render_kanban_board, render_task_list,
-    render_wave_river,
+    render_agent_list, render_event_stream, render_footer, render_kanban_board, render_plan_gate, render_spec_panel,
+    render_step_tree, render_task_list, render_wave_river,
 };
 
 /// Render dashboard view into the given content area.
 /// Header is rendered globally by the view dispatcher.
 pub fn render_dashboard(frame: &mut Frame, state: &AppState, area: Rect) {
-    // Add search bar if filter is active
-    let has_search = state.ui.filter.is_some();
+    // Add search bar while the filter is typed (an applied one shows as a chip)
+    let has_search = crate::app::filters::editing(&state.ui);
 
     let main_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -50,28 +55,107 @@ pub fn render_dashboard(frame: &mut Frame, state: &AppState, area: Rect) {
         render_search_bar(frame, main_layout[1], state);
     }
 
-    let content_area = main_layout[content_idx];
+    timed(state, "wave river", || render_wave_river(frame, main_layout[0], state));
 
-    // Split content area into two columns
-    let content_columns = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(50), // Task list
-            Constraint::Percentage(50), // Event stream
-        ])
-        .split(content_area);
+    // Content area: the configured panel grid (task list | event stream by default),
+    // with the spec / plan preview beside it when toggled on (`S`)
+    let mut content = main_layout[content_idx];
+    if state.ui.show_spec_panel && !state.ui.dashboard_layout.contains(PanelKind::Spec) {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(62), Constraint::Percentage(38)])
+            .split(content);
+        timed(state, PanelKind::Spec.label(), || render_spec_panel(frame, columns[1], state));
+        content = columns[0];
+    }
+    render_panel_grid(frame, content, &state.ui.dashboard_layout, |frame, panel, area| {
+        timed(state, panel.label(), || render_panel(frame, panel, area, state))
+    });
+
+    render_footer(frame, main_layout[footer_idx], state);
+}
 
-    // Render all components
-    render_wave_river(frame, main_layout[0], state);
+/// Render the focused panel alone for zoom (`z`): the task list on the left,
+/// the event stream on the right.
+pub fn render_focused_panel(frame: &mut Frame, state: &AppState, area: Rect) {
+    let panel = match state.ui.focus {
+        PanelFocus::Left => PanelKind::TaskList,
+        PanelFocus::Right => PanelKind::EventStream,
+    };
+    render_panel(frame, panel, area, state);
+}
 
-    // Render task list OR kanban based on view mode
-    match state.ui.task_view_mode {
-        TaskViewMode::Wave => render_task_list(frame, content_columns[0], state),
-        TaskViewMode::Kanban => render_kanban_board(frame, content_columns[0], state),
+/// Render one dashboard panel into its grid cell.
+fn render_panel(frame: &mut Frame, panel: PanelKind, area: Rect, state: &AppState) {
+    match panel {
+        // Task list OR kanban based on view mode
+        PanelKind::TaskList => match state.ui.task_view_mode {
+            TaskViewMode::Wave => render_task_list(frame, area, state),
+            TaskViewMode::Kanban => render_kanban_board(frame, area, state),
+        },
+        PanelKind::EventStream => {
+            // A session blocked on plan approval takes the top of the panel
+            let area = if state.domain.pending_plans.is_empty() {
+                area
+            } else {
+                let rows = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(45), Constraint::Min(0)])
+                    .split(area);
+                render_plan_gate(frame, rows[0], state);
+                rows[1]
+            };
+            if state.ui.split_view {
+                render_followed_agent(frame, area, state);
+            } else {
+                render_event_stream(frame, area, state);
+            }
+        }
+        PanelKind::AgentList => render_agent_list(frame, area, state),
+        PanelKind::ToolStats => render_tool_stats(frame, area, state),
+        PanelKind::HotFiles => render_hot_files(frame, area, state),
+        PanelKind::Errors => render_errors(frame, area, state),
+        PanelKind::Spec => render_spec_panel(frame, area, state),
+        PanelKind::Pacing => render_pacing(frame, area, state),
     }
+}
 
-    render_event_stream(frame, content_columns[1], state);
-    render_footer(frame, main_layout[footer_idx], state);
+/// Split view right half: which agent is followed, then its activity tree
+/// (cursor = agent_events offset, shared with AgentDetail).
+fn render_followed_agent(frame: &mut Frame, area: Rect, state: &AppState) {
+    let is_focused = matches!(state.ui.focus, PanelFocus::Right);
+    let Some(agent) = state.followed_agent() else {
+        render_no_agent_selected(frame, area, is_focused);
+        return;
+    };
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(area);
+
+    let (status, color) = if agent.finished_at.is_some() {
+        ("finished", Theme::TASK_COMPLETED)
+    } else {
+        ("active", Theme::TASK_RUNNING)
+    };
+    let (icon, kind_color) = state.ui.agent_kinds.style(agent);
+    let label = Line::from(vec![
+        Span::styled(format!(" {} ", icon), Style::default().fg(kind_color)),
+        Span::styled("Following ", Style::default().fg(Theme::MUTED_TEXT)),
+        Span::styled(agent.display_name().to_string(), Style::default().fg(Theme::TEXT).add_modifier(Modifier::BOLD)),
+        Span::styled(format!(" · {}", status), Style::default().fg(color)),
+    ]);
+    frame.render_widget(Paragraph::new(label), rows[0]);
+
+    render_step_tree(
+        frame,
+        rows[1],
+        state,
+        agent.id.as_str(),
+        state.ui.scroll_offsets.agent_events,
+        is_focused,
+    );
 }
 
 /// Render search bar showing current filter text.
@@ -115,6 +199,54 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn split_view_shows_followed_agent_instead_of_event_stream() {
+        use crate::model::{Agent, AgentId};
+
+        let mut state = AppState::new();
+        state.ui.split_view = true;
+        let mut agent = Agent::new("a01", chrono::Utc::now());
+        agent.agent_type = Some("implementer".to_string());
+        state.domain.agents.insert(AgentId::new("a01"), agent);
+        state.recompute_sorted_keys();
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        let result = terminal
+            .draw(|frame| render_dashboard(frame, &state, frame.area()))
+            .unwrap();
+        let text: String = result.buffer.content().iter().map(|c| c.symbol()).collect();
+
+        assert!(text.contains("Following implementer · active"));
+        assert!(text.contains("Activity"));
+        assert!(!text.contains("Events"));
+    }
+
+    #[test]
+    fn configured_layout_replaces_the_default_split() {
+        use crate::app::panels::{DashboardLayout, PanelRow, PanelSlot};
+
+        let mut state = AppState::new();
+        state.meta.errors.push_back("watcher: disk full".into());
+        let slot = |panel| PanelSlot { panel, width: 1 };
+        state.ui.dashboard_layout = DashboardLayout {
+            rows: vec![
+                PanelRow { height: 1, panels: vec![slot(PanelKind::ToolStats), slot(PanelKind::HotFiles)] },
+                PanelRow { height: 1, panels: vec![slot(PanelKind::Errors)] },
+            ],
+        };
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+        let result = terminal
+            .draw(|frame| render_dashboard(frame, &state, frame.area()))
+            .unwrap();
+        let text: String = result.buffer.content().iter().map(|c| c.symbol()).collect();
+
+        assert!(text.contains("Tools"));
+        assert!(text.contains("Hot Files"));
+        assert!(text.contains("watcher: disk full"));
+        assert!(!text.contains("Events"));
+    }
+
     #[test]
     fn render_dashboard_does_not_panic_with_small_terminal() {
         let backend = TestBackend::new(40, 12);
@@ -0,0 +1,241 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    text::Line,
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+use crate::app::state::{AppState, CommandState};
+use crate::model::{TaskStatus, TranscriptEvent, TranscriptEventKind};
+use crate::view::components::scroll::render_wrapped;
+
+/// Most events kept in the log region (older ones have scrolled off anyway).
+const LOG_LIMIT: usize = 500;
+
+/// Longest detail text appended to a log line.
+const DETAIL_LIMIT: usize = 160;
+
+/// Screen-reader-friendly alternate renderer (`--linear`): plain text only, no
+/// box drawing, colors, icons or side-by-side panels. A short status block on
+/// top, then one log line per event, oldest first, pinned to the newest at the
+/// bottom. The last row shows the command line or the latest notice.
+pub fn render_linear(state: &AppState, frame: &mut Frame) {
+    let status = build_status_lines(state);
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(status.len() as u16 + 1), // Status + blank line
+            Constraint::Min(0),                          // Log
+            Constraint::Length(1),                       // Command line / notice
+        ])
+        .split(frame.area());
+
+    frame.render_widget(Paragraph::new(status), layout[0]);
+    render_wrapped(frame, layout[1], build_log_lines(state), Block::default(), usize::MAX);
+
+    let bottom = match (&state.ui.command, &state.ui.command_message) {
+        (CommandState::Open { input }, _) => format!("Command: {}", input),
+        (CommandState::Closed, Some(message)) if message.is_error => format!("Error: {}", message.text),
+        (CommandState::Closed, Some(message)) => format!("Notice: {}", message.text),
+        (CommandState::Closed, None) => String::new(),
+    };
+    frame.render_widget(Paragraph::new(bottom), layout[2]);
+}
+
+/// Pure function: progress summary as whole sentences.
+fn build_status_lines(state: &AppState) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+
+    match &state.domain.task_graph {
+        Some(graph) if graph.total_tasks() > 0 => {
+            let count = |f: fn(&TaskStatus) -> bool| graph.flat_tasks().filter(|t| f(&t.status)).count();
+            lines.push(Line::from(format!(
+                "Wave {} of {}. Tasks: {} completed, {} running, {} failed, {} pending, {} total.",
+                graph.current_wave(),
+                graph.waves.len(),
+                count(|s| matches!(s, TaskStatus::Completed)),
+                count(|s| matches!(s, TaskStatus::Running | TaskStatus::Implemented)),
+                count(|s| matches!(s, TaskStatus::Failed { .. })),
+                count(|s| matches!(s, TaskStatus::Pending)),
+                graph.total_tasks(),
+            )));
+        }
+        _ => lines.push(Line::from("No task graph yet.")),
+    }
+
+    let active: Vec<&str> = state
+        .sorted_agent_keys()
+        .iter()
+        .filter_map(|k| state.domain.agents.get(k))
+        .filter(|a| a.finished_at.is_none())
+        .map(|a| a.display_name())
+        .collect();
+    lines.push(Line::from(if active.is_empty() {
+        "No active agents.".to_string()
+    } else {
+        format!("Active agents: {}.", active.join(", "))
+    }));
+
+    if let Some(ref banner) = state.ui.wave_banner {
+        lines.push(Line::from(format!("Wave {} complete.", banner.completion.wave)));
+    }
+    for (sid, failures) in state.domain.error_bursts.bursting(&state.meta.error_burst) {
+        lines.push(Line::from(format!(
+            "Warning: error burst in session {}, {} tool failures within {}.",
+            sid,
+            failures,
+            state.meta.error_burst.window_label()
+        )));
+    }
+    if let Some((sid, plan)) = state.newest_pending_plan() {
+        let first = plan.plan.lines().find(|l| !l.trim().is_empty()).unwrap_or("").trim();
+        lines.push(Line::from(format!("Plan awaiting approval in session {}: {}", sid, first)));
+    }
+    if state.meta.watchdog.is_degraded() {
+        lines.push(Line::from("Warning: file watcher degraded, updates may be late."));
+    }
+
+    lines
+}
+
+/// Pure function: one plain line per shown event, oldest first.
+fn build_log_lines(state: &AppState) -> Vec<Line<'static>> {
+    let events: Vec<&TranscriptEvent> = state
+        .domain
+        .events
+        .iter()
+        .rev()
+        .filter(|e| state.ui.event_filters.allows(&e.kind))
+        .take(LOG_LIMIT)
+        .collect();
+
+    if events.is_empty() {
+        return vec![Line::from("No events yet.")];
+    }
+
+    events
+        .into_iter()
+        .rev()
+        .map(|event| {
+            let who = event
+                .agent_id
+                .as_ref()
+                .map(|id| {
+                    state
+                        .domain
+                        .agents
+                        .get(id)
+                        .map(|a| a.display_name().to_string())
+                        .unwrap_or_else(|| id.to_string())
+                })
+                .unwrap_or_else(|| "main".to_string());
+            Line::from(format!(
+                "{} {}: {}",
+                state.ui.time_format.time(event.timestamp),
+                who,
+                describe(&event.kind)
+            ))
+        })
+        .collect()
+}
+
+/// Pure function: event as a short phrase, detail collapsed to one line.
+fn describe(kind: &TranscriptEventKind) -> String {
+    let detail = |text: &str| -> String {
+        let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        crate::watcher::truncate_str(&flat, DETAIL_LIMIT)
+    };
+    let with_detail = |head: String, text: &str| {
+        let text = detail(text);
+        if text.is_empty() { head } else { format!("{}: {}", head, text) }
+    };
+
+    match kind {
+        TranscriptEventKind::UserMessage => "user message".to_string(),
+        TranscriptEventKind::AssistantMessage { content } => with_detail("says".to_string(), content),
+        TranscriptEventKind::ToolUse { tool_name, input_summary } => {
+            with_detail(format!("runs {}", tool_name), input_summary)
+        }
+        TranscriptEventKind::ToolResult { tool_name, result_summary, duration_ms, is_error } => {
+            let outcome = if *is_error { "failed" } else { "finished" };
+            let head = match duration_ms {
+                Some(ms) => format!("{} {} in {}ms", tool_name, outcome, ms),
+                None => format!("{} {}", tool_name, outcome),
+            };
+            with_detail(head, result_summary)
+        }
+        TranscriptEventKind::ExternalLog { source, line } => with_detail(format!("{} log", source), line),
+        TranscriptEventKind::ConfigChanged { file, change } => format!("{} {}", file, change),
+        TranscriptEventKind::StatusUpdate { text, .. } => format!("status: {}", crate::app::progress::status_text(text)),
+        TranscriptEventKind::ApiError { error_kind, message, .. } => with_detail(format!("API {}", error_kind.label()), message),
+        TranscriptEventKind::Unknown { entry_type } => format!("{} entry", entry_type),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Agent, AgentId, Task, TaskGraph, Wave};
+    use chrono::Utc;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn render_text(state: &AppState) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(80, 12)).unwrap();
+        let result = terminal.draw(|frame| render_linear(state, frame)).unwrap();
+        let buf = &result.buffer;
+        (0..buf.area.height)
+            .map(|y| (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect::<String>())
+            .collect()
+    }
+
+    #[test]
+    fn renders_plain_status_and_log_without_box_drawing() {
+        let mut state = AppState::new();
+        let mut task = Task::new("T1", "Build".to_string(), TaskStatus::Completed);
+        task.agent_id = Some(AgentId::new("a01"));
+        state.domain.task_graph = Some(TaskGraph::new(vec![Wave::new(1, vec![task])]));
+        let mut agent = Agent::new("a01", Utc::now());
+        agent.agent_type = Some("implementer".to_string());
+        state.domain.agents.insert(AgentId::new("a01"), agent);
+        state.recompute_sorted_keys();
+        let mut event = TranscriptEvent::new(
+            Utc::now(),
+            TranscriptEventKind::ToolUse { tool_name: "Bash".into(), input_summary: "cargo\n test".into() },
+        );
+        event.agent_id = Some(AgentId::new("a01"));
+        state.domain.events.push_back(event);
+
+        let rows = render_text(&state);
+        let text = rows.join("\n");
+        assert!(rows[0].starts_with("Wave 1 of 1. Tasks: 1 completed, 0 running, 0 failed, 0 pending, 1 total."));
+        assert!(rows[1].starts_with("Active agents: implementer."));
+        assert!(text.contains("implementer: runs Bash: cargo test"));
+        assert!(!text.chars().any(|c| ('\u{2500}'..='\u{257F}').contains(&c)), "no box drawing");
+    }
+
+    #[test]
+    fn log_is_oldest_first_and_pinned_to_newest() {
+        let mut state = AppState::new();
+        let start = Utc::now();
+        for i in 0..30 {
+            state.domain.events.push_back(TranscriptEvent::new(
+                start + chrono::Duration::seconds(i),
+                TranscriptEventKind::Unknown { entry_type: format!("e{i}") },
+            ));
+        }
+
+        let rows = render_text(&state);
+        let log: Vec<&String> = rows.iter().filter(|r| r.contains(" entry")).collect();
+        assert!(log.last().unwrap().contains("e29 entry"));
+        assert!(log.first().unwrap().contains(&format!("e{} entry", 30 - log.len())));
+    }
+
+    #[test]
+    fn bottom_row_shows_command_line() {
+        let mut state = AppState::new();
+        state.ui.command = CommandState::Open { input: "set tick=100".into() };
+        let rows = render_text(&state);
+        assert!(rows.last().unwrap().starts_with("Command: set tick=100"));
+    }
+}
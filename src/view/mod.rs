@@ -1,13 +1,21 @@
-use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
+use crate::app::frame_budget::timed;
 use crate::app::state::{AppState, ViewState};
+use crate::model::Theme;
 
 pub mod agent_detail;
 pub mod components;
 pub mod dashboard;
+pub mod linear;
+pub mod review;
 pub mod session_detail;
 pub mod sessions;
+pub mod timeline;
 pub mod token_cost_dashboard;
 
 pub use agent_detail::render_agent_detail;
@@ -17,45 +25,66 @@ pub use sessions::render_sessions;
 pub use token_cost_dashboard::render_token_cost_dashboard;
 
 /// Main view dispatcher.
-/// Renders global header on all views, then routes content area to specific view.
-/// Overlays filter bar and help if active.
+/// Renders global header on all views (plus the heads-up summary while anything
+/// runs and the wave banner while one is active),
+/// then routes content area to specific view (or, zoomed, to its focused panel).
+/// Overlays toasts, filter bar and help if active.
+/// In linear log mode the alternate plain-text renderer replaces all of this,
+/// and after quit, while writes finish, a "finishing writes…" screen does.
 pub fn render(state: &AppState, frame: &mut Frame) {
-    // Global header + content split
+    if state.meta.should_quit && state.meta.in_flight.writes() > 0 {
+        render_finishing_writes(frame, state);
+        return;
+    }
+    if state.ui.linear_log {
+        linear::render_linear(state, frame);
+        return;
+    }
+
+    let banner_height = if state.ui.wave_banner.is_some() { 1 } else { 0 };
+    let summary = crate::app::summary::summarize(state, state.meta.clock.now());
+    let summary_height = if summary.is_some() { 1 } else { 0 };
+
+    // Global header + summary + banner + content split
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // Global header
-            Constraint::Min(0),   // Content area
+            Constraint::Length(1),              // Global header
+            Constraint::Length(summary_height), // Heads-up summary
+            Constraint::Length(banner_height),  // Wave complete banner
+            Constraint::Min(0),                 // Content area
         ])
         .split(frame.area());
 
     // Always render global header
-    components::header::render_header(frame, layout[0], state);
+    timed(state, "header", || components::header::render_header(frame, layout[0], state));
+    if let Some(summary) = summary {
+        components::header::render_summary(frame, layout[1], &summary);
+    }
+    components::render_banner(frame, layout[2], state);
 
     // Route content area to specific view
-    match &state.ui.view {
-        ViewState::Dashboard => {
-            dashboard::render_dashboard(frame, state, layout[1]);
-        }
-        ViewState::AgentDetail => {
-            agent_detail::render_agent_detail(frame, state, layout[1]);
-        }
-        ViewState::Sessions => {
-            sessions::render_sessions(frame, state, layout[1]);
-        }
-        ViewState::SessionDetail => {
-            session_detail::render_session_detail(frame, state, layout[1]);
-        }
-        ViewState::TokenDashboard => {
-            token_cost_dashboard::render_token_cost_dashboard(frame, state, layout[1]);
-        }
+    let content = layout[3];
+    if state.ui.zoomed {
+        render_zoomed(frame, state, content);
+    } else {
+        render_view(frame, state, content);
     }
 
-    // Overlay filter bar if active
-    if state.ui.filter.is_some() {
+    // Overlay the end-of-session toast and transient toasts over the content
+    components::render_session_toast(frame, content, &state.ui.session_toasts);
+    components::render_toasts(frame, content, &state.ui.toasts);
+
+    // Overlay filter bar while the filter is typed
+    if crate::app::filters::editing(&state.ui) {
         components::filter_bar::render_filter_bar(frame, state);
     }
 
+    // Overlay command line (or last command's result) on top of filter bar
+    if state.ui.command.is_open() || state.ui.command_message.is_some() {
+        components::render_command_bar(frame, state);
+    }
+
     // Overlay help if active (on top of filter bar)
     if state.ui.show_help {
         components::help_overlay::render_help_overlay(frame);
@@ -66,13 +95,111 @@ pub fn render(state: &AppState, frame: &mut Frame) {
         components::render_agent_popup(frame, state, agent_id.as_str());
     }
 
+    // Overlay git diff popup if active
+    if state.ui.diff_popup.is_open() {
+        components::render_diff_popup(frame, frame.area(), &state.ui.diff_popup);
+    }
+
+    // Overlay captured tool output pager if active
+    if let Some(ref pager) = state.ui.output_pager {
+        components::render_output_pager(frame, frame.area(), pager);
+    }
+
+    // Overlay JSON inspector if active
+    if let Some(ref inspector) = state.ui.json_inspector {
+        components::render_json_inspector(frame, frame.area(), inspector);
+    }
+
+    // Overlay schema drift report if active
+    if state.ui.show_schema_report {
+        components::render_schema_report(frame, frame.area(), &state.domain.schema_drift);
+    }
+
+    // Overlay release notes if active
+    if let Some(release) = state.meta.newer_release.as_ref().filter(|_| state.ui.show_changelog) {
+        components::render_changelog(frame, frame.area(), release);
+    }
+
     // Overlay layout picker if active
     if state.ui.layout_picker.is_open() {
         components::layout_picker::render_layout_picker(frame, frame.area(), &state.ui.layout_picker);
     }
 
-    // Overlay delete confirm if active
-    if state.ui.delete_confirm.is_open() {
-        components::delete_confirm::render_delete_confirm(frame, frame.area(), &state.ui.delete_confirm);
+    // Overlay confirm dialog if active
+    if state.ui.confirm.is_open() {
+        components::render_confirm(frame, frame.area(), &state.ui.confirm);
+    }
+
+    // Overlay settings if active
+    if state.ui.settings.is_open() {
+        components::render_settings(frame, frame.area(), state);
+    }
+
+    // Overlay quick-switch palette if active
+    if state.ui.palette.is_open() {
+        components::render_palette(frame, frame.area(), state);
+    }
+
+    // High-contrast / no-color remapping of the finished frame
+    components::accessibility::apply_color_mode(frame.buffer_mut(), state.ui.color_mode);
+}
+
+/// Render the wait for background writes after quit.
+fn render_finishing_writes(frame: &mut Frame, state: &AppState) {
+    let area = frame.area();
+    let lines = vec![
+        Line::from("finishing writes…"),
+        Line::from(state.meta.in_flight.writes_label()).style(Style::default().fg(Theme::MUTED_TEXT)),
+    ];
+    let top = area.height.saturating_sub(2) / 2;
+    let rect = Rect::new(area.x, area.y + top, area.width, area.height.min(2));
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), rect);
+}
+
+/// Render the current view into the content area.
+fn render_view(frame: &mut Frame, state: &AppState, content: Rect) {
+    // The Dashboard times each of its panels
+    match &state.ui.view {
+        ViewState::Dashboard => {
+            dashboard::render_dashboard(frame, state, content);
+        }
+        ViewState::AgentDetail => {
+            timed(state, "agent detail", || agent_detail::render_agent_detail(frame, state, content));
+        }
+        ViewState::Sessions => {
+            timed(state, "sessions", || sessions::render_sessions(frame, state, content));
+        }
+        ViewState::SessionDetail => {
+            timed(state, "session detail", || session_detail::render_session_detail(frame, state, content));
+        }
+        ViewState::TokenDashboard => {
+            timed(state, "token dashboard", || token_cost_dashboard::render_token_cost_dashboard(frame, state, content));
+        }
+        ViewState::Review => {
+            timed(state, "review", || review::render_review(frame, state, content));
+        }
+        ViewState::Timeline => {
+            timed(state, "timeline", || timeline::render_timeline(frame, state, content));
+        }
+    }
+}
+
+/// Zoom (`z`): the current view's focused panel fills the content area, above
+/// the footer; `z` again restores the view's layout.
+fn render_zoomed(frame: &mut Frame, state: &AppState, content: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(content);
+
+    match &state.ui.view {
+        ViewState::Dashboard => dashboard::render_focused_panel(frame, state, chunks[0]),
+        ViewState::AgentDetail => agent_detail::render_focused_panel(frame, state, chunks[0]),
+        ViewState::Sessions => sessions::render_focused_panel(frame, state, chunks[0]),
+        ViewState::SessionDetail => session_detail::render_focused_panel(frame, state, chunks[0]),
+        ViewState::TokenDashboard => token_cost_dashboard::render_focused_panel(frame, state, chunks[0]),
+        ViewState::Review => review::render_focused_panel(frame, state, chunks[0]),
+        ViewState::Timeline => timeline::render_focused_panel(frame, state, chunks[0]),
     }
+    components::render_footer(frame, chunks[1], state);
 }
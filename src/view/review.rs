@@ -0,0 +1,308 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::retries::{self, Attempt, AttemptOutcome};
+use crate::app::review::{review_queue, selected_task, ReviewNote, Verdict};
+use crate::app::state::{AppState, PanelFocus};
+use crate::model::{Task, TaskStatus, Theme};
+use super::components::footer::render_footer;
+use super::components::format::TimeFormat;
+
+/// Render the review queue: tasks awaiting a human decision on the left, the
+/// selected task's details and any recorded decision on the right.
+pub fn render_review(frame: &mut Frame, state: &AppState, area: Rect) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Content
+            Constraint::Length(1), // Footer
+        ])
+        .split(area);
+
+    let content = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(layout[0]);
+
+    render_queue(frame, content[0], state);
+    render_task_detail(frame, content[1], state);
+    render_footer(frame, layout[1], state);
+
+    if let Some(ref note) = state.ui.review_note {
+        render_note_input(frame, layout[0], note);
+    }
+}
+
+/// Render the focused panel alone for zoom (`z`): the queue on the left, the
+/// selected task on the right.
+pub fn render_focused_panel(frame: &mut Frame, state: &AppState, area: Rect) {
+    match state.ui.focus {
+        PanelFocus::Left => render_queue(frame, area, state),
+        PanelFocus::Right => render_task_detail(frame, area, state),
+    }
+    if let Some(ref note) = state.ui.review_note {
+        render_note_input(frame, area, note);
+    }
+}
+
+fn render_queue(frame: &mut Frame, area: Rect, state: &AppState) {
+    let queue = review_queue(state);
+    let decided = queue.iter().filter(|t| state.domain.reviews.contains_key(&t.id)).count();
+    let title = format!(" Awaiting review ({} of {} decided) ", decided, queue.len());
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::ACTIVE_BORDER));
+
+    if queue.is_empty() {
+        let text = Line::from(Span::styled(
+            "No implemented tasks awaiting review",
+            Style::default().fg(Theme::MUTED_TEXT).add_modifier(Modifier::ITALIC),
+        ));
+        frame.render_widget(Paragraph::new(text).block(block), area);
+        return;
+    }
+
+    let selected = state.ui.scroll_offsets.review.min(queue.len() - 1);
+    let visible = area.height.saturating_sub(2) as usize;
+    let first = (selected + 1).saturating_sub(visible);
+
+    let lines: Vec<Line> = queue
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible)
+        .map(|(idx, task)| {
+            let (marker, color) = match state.domain.reviews.get(&task.id).map(|d| d.verdict) {
+                Some(Verdict::Approved) => ("✓", Theme::SUCCESS),
+                Some(Verdict::Rejected) => ("✗", Theme::ERROR),
+                None => ("·", Theme::MUTED_TEXT),
+            };
+            let mut line = Line::from(vec![
+                Span::styled(format!(" {} ", marker), Style::default().fg(color)),
+                Span::styled(format!("{:<6} ", task.id), Style::default().fg(Theme::ACCENT)),
+                Span::styled(task.description.clone(), Style::default().fg(Theme::TEXT)),
+            ]);
+            if idx == selected {
+                line = line.style(Style::default().bg(Theme::SELECTION_BG));
+            }
+            line
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_task_detail(frame: &mut Frame, area: Rect, state: &AppState) {
+    let block = Block::default()
+        .title(" Task ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::PANEL_BORDER));
+
+    let Some(task) = selected_task(state) else {
+        frame.render_widget(block, area);
+        return;
+    };
+
+    let mut lines = build_detail_lines(task);
+    let attempts = state.domain.task_attempts.get(&task.id);
+    if attempts.len() > 1 {
+        lines.push(Line::from(""));
+        lines.extend(attempt_lines(attempts, &state.ui.time_format));
+    }
+    lines.push(Line::from(""));
+    match state.domain.reviews.get(&task.id) {
+        Some(decision) => {
+            let color = match decision.verdict {
+                Verdict::Approved => Theme::SUCCESS,
+                Verdict::Rejected => Theme::ERROR,
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    decision.verdict.label(),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!(" at {}", state.ui.time_format.date_time(decision.reviewed_at)),
+                    Style::default().fg(Theme::MUTED_TEXT),
+                ),
+            ]));
+            if !decision.note.is_empty() {
+                lines.push(Line::from(Span::styled(decision.note.clone(), Style::default().fg(Theme::TEXT))));
+            }
+        }
+        None => lines.push(Line::from(Span::styled(
+            "Not reviewed yet — a: approve, r: reject",
+            Style::default().fg(Theme::MUTED_TEXT),
+        ))),
+    }
+
+    frame.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: false }), area);
+}
+
+/// Pure function: task description, agent, status, tests and modified files.
+fn build_detail_lines(task: &Task) -> Vec<Line<'static>> {
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(Theme::MUTED_TEXT));
+    let status = match task.status {
+        TaskStatus::Completed => "completed",
+        _ => "implemented",
+    };
+    let tests = match task.tests_passed {
+        Some(true) => Span::styled("passed", Style::default().fg(Theme::SUCCESS)),
+        Some(false) => Span::styled("failed", Style::default().fg(Theme::ERROR)),
+        None => Span::styled("unknown", Style::default().fg(Theme::MUTED_TEXT)),
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("{} {}", task.id, task.description),
+            Style::default().fg(Theme::TEXT).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![label("Status: "), Span::styled(status, Style::default().fg(Theme::TEXT))]),
+        Line::from(vec![
+            label("Agent:  "),
+            Span::styled(
+                task.agent_id.as_ref().map_or("-".to_string(), |id| id.to_string()),
+                Style::default().fg(Theme::AGENT_LABEL),
+            ),
+        ]),
+        Line::from(vec![label("Tests:  "), tests]),
+        Line::from(""),
+        Line::from(label("Files modified:")),
+    ];
+    if task.files_modified.is_empty() {
+        lines.push(Line::from(label("  none recorded")));
+    }
+    lines.extend(
+        task.files_modified
+            .iter()
+            .map(|f| Line::from(Span::styled(format!("  {}", f), Style::default().fg(Theme::INFO)))),
+    );
+    lines
+}
+
+/// Pure function: the task's attempts, one line each ("attempt 1 failed at
+/// 10:32 — reason"), and a warning when the retries keep failing alike.
+fn attempt_lines(attempts: &[Attempt], time_format: &TimeFormat) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(Span::styled("Attempts:", Style::default().fg(Theme::MUTED_TEXT)))];
+    for (i, attempt) in attempts.iter().enumerate() {
+        let started = time_format.time(attempt.started_at);
+        let ended = attempt.ended_at.map(|at| time_format.time(at)).unwrap_or_default();
+        let (text, color) = match &attempt.outcome {
+            AttemptOutcome::Running => (format!("running since {}", started), Theme::TASK_RUNNING),
+            AttemptOutcome::Done => (format!("went through at {}", ended), Theme::SUCCESS),
+            AttemptOutcome::Failed { reason, .. } => (format!("failed at {} — {}", ended, reason), Theme::ERROR),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  attempt {} ", i + 1), Style::default().fg(Theme::TEXT)),
+            Span::styled(text, Style::default().fg(color)),
+        ]));
+    }
+    if retries::is_looping(attempts) {
+        lines.push(Line::from(Span::styled(
+            "  retries keep failing for the same reason",
+            Style::default().fg(Theme::WARNING).add_modifier(Modifier::BOLD),
+        )));
+    }
+    lines
+}
+
+/// Note input bar over the bottom of the content area.
+fn render_note_input(frame: &mut Frame, area: Rect, note: &ReviewNote) {
+    let bar = Rect {
+        x: area.x,
+        y: area.y + area.height.saturating_sub(3),
+        width: area.width,
+        height: 3.min(area.height),
+    };
+    let color = match note.verdict {
+        Verdict::Approved => Theme::SUCCESS,
+        Verdict::Rejected => Theme::ERROR,
+    };
+    let verb = match note.verdict {
+        Verdict::Approved => "Approve",
+        Verdict::Rejected => "Reject",
+    };
+    let text = Line::from(vec![
+        Span::styled("note: ", Style::default().fg(Theme::INFO)),
+        Span::styled(note.input.clone(), Style::default().fg(Theme::TEXT)),
+        Span::styled("█", Style::default().fg(Theme::ACTIVE_BORDER)), // Cursor
+    ]);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color))
+        .title(format!(" {} {} ", verb, note.task_id))
+        .title_bottom(Line::from(Span::styled(
+            " Enter: record │ Esc: cancel ",
+            Style::default().fg(Theme::MUTED_TEXT),
+        )));
+
+    frame.render_widget(Clear, bar);
+    frame.render_widget(Paragraph::new(text).block(block), bar);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::review::ReviewDecision;
+    use crate::app::state::ViewState;
+    use crate::model::{TaskGraph, TaskId, Wave};
+    use chrono::Utc;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    #[test]
+    fn lists_queue_with_decision_and_note() {
+        let mut state = AppState::new();
+        state.ui.view = ViewState::Review;
+        let mut task = Task::new("T1", "Add parser".into(), TaskStatus::Completed);
+        task.files_modified = vec!["src/parser.rs".into()];
+        state.domain.task_graph = Some(TaskGraph::new(vec![Wave::new(1, vec![
+            task,
+            Task::new("T2", "Add lexer".into(), TaskStatus::Implemented),
+        ])]));
+        state.domain.reviews.insert(TaskId::new("T1"), ReviewDecision {
+            task_id: TaskId::new("T1"),
+            verdict: Verdict::Rejected,
+            note: "needs tests".into(),
+            reviewed_at: Utc::now(),
+        });
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| render_review(frame, &state, frame.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        let text: String = (0..buf.area.height)
+            .map(|y| (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect::<String>() + "\n")
+            .collect();
+
+        assert!(text.contains("1 of 2 decided"));
+        assert!(text.contains("✗ T1"));
+        assert!(text.contains("· T2"));
+        assert!(text.contains("src/parser.rs"));
+        assert!(text.contains("needs tests"));
+    }
+
+    #[test]
+    fn attempts_show_each_outcome_and_a_loop_warning() {
+        let t0 = Utc::now();
+        let failed = |reason: &str| AttemptOutcome::Failed { reason: reason.into(), retry_count: 0 };
+        let attempts = [
+            Attempt { started_at: t0, ended_at: Some(t0), outcome: failed("lint") },
+            Attempt { started_at: t0, ended_at: Some(t0), outcome: failed("lint") },
+            Attempt { started_at: t0, ended_at: None, outcome: AttemptOutcome::Running },
+        ];
+        let text: Vec<String> = attempt_lines(&attempts, &TimeFormat::default()).iter().map(|l| l.to_string()).collect();
+        assert_eq!(text.len(), 4);
+        assert!(text[1].starts_with("  attempt 1 failed at") && text[1].ends_with("— lint"));
+        assert!(text[3].starts_with("  attempt 3 running since"));
+
+        let looping: Vec<String> = attempt_lines(&attempts[..2], &TimeFormat::default()).iter().map(|l| l.to_string()).collect();
+        assert_eq!(looping.last().map(String::as_str), Some("  retries keep failing for the same reason"));
+    }
+}
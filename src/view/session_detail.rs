@@ -1,19 +1,22 @@
 use std::collections::{BTreeMap, VecDeque};
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
+    widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
-use crate::app::state::{AppState, PanelFocus};
-use crate::model::{Agent, AgentId, SessionMeta, SessionStatus, TaskGraph, Theme, TranscriptEvent};
+use crate::app::{agent_defs, anomaly, compat};
+use crate::app::notes::NotesEditor;
+use crate::app::state::{AppState, PanelFocus, SessionDetailTab, UiState, ViewState};
+use crate::model::{Agent, AgentId, Clock, SessionMeta, SessionStatus, TaskGraph, Theme, TranscriptEvent};
 use super::components::agent_list::render_agent_list_with_main;
-use super::components::format::format_duration;
+use super::components::format::{format_duration, TimeFormat};
 use super::components::prompt_popup::render_prompt_popup;
+use super::components::scroll::{render_panned, render_wrapped};
 
 // ============================================================================
 // Data access: unifies active session vs archived session
@@ -25,6 +28,12 @@ pub struct SessionViewData<'a> {
     pub agents: AgentsRef<'a>,
     pub events: EventsRef<'a>,
     pub task_graph: Option<&'a TaskGraph>,
+    /// Older archive events still on disk (streamed in pages as the user scrolls)
+    pub pending_events: usize,
+    /// Postmortem notes (archived sessions only)
+    pub notes: Option<&'a str>,
+    /// loom-tui version that wrote the archive (archived sessions only)
+    pub written_by: Option<&'a str>,
 }
 
 /// Either a borrowed reference or an owned filtered subset of agents.
@@ -45,14 +54,14 @@ impl<'a> AgentsRef<'a> {
         self.len() == 0
     }
 
-    pub fn values(&self) -> Vec<&Agent> {
+    pub fn values(&self) -> Vec<&'a Agent> {
         match self {
             AgentsRef::Borrowed(m) => m.values().collect(),
             AgentsRef::Filtered(m) => m.values().copied().collect(),
         }
     }
 
-    pub fn get(&self, key: &AgentId) -> Option<&Agent> {
+    pub fn get(&self, key: &AgentId) -> Option<&'a Agent> {
         match self {
             AgentsRef::Borrowed(m) => m.get(key),
             AgentsRef::Filtered(m) => m.get(key).copied(),
@@ -134,6 +143,9 @@ pub fn get_selected_session_data(state: &AppState) -> Option<SessionViewData<'_>
             agents: AgentsRef::Filtered(filtered_agents),
             events: EventsRef::Owned(filtered_events),
             task_graph: state.domain.task_graph.as_ref(),
+            pending_events: 0,
+            notes: None,
+            written_by: None,
         });
     }
 
@@ -145,6 +157,9 @@ pub fn get_selected_session_data(state: &AppState) -> Option<SessionViewData<'_>
         agents: AgentsRef::Borrowed(&archive.agents),
         events: EventsRef::Vec(&archive.events),
         task_graph: archive.task_graph.as_ref(),
+        pending_events: session.paging.as_ref().map_or(0, |p| p.remaining()),
+        notes: archive.notes.as_deref(),
+        written_by: archive.written_by.as_deref(),
     })
 }
 
@@ -165,6 +180,13 @@ fn sorted_session_agents<'a>(data: &'a SessionViewData<'a>) -> Vec<&'a Agent> {
     agents
 }
 
+/// The agent highlighted in the SessionDetail agent table; none on Main.
+pub fn selected_session_agent_id(state: &AppState) -> Option<AgentId> {
+    let idx = state.ui.selected_session_agent_index?.checked_sub(1)?;
+    let data = get_selected_session_data(state)?;
+    sorted_session_agents(&data).get(idx).map(|agent| agent.id.clone())
+}
+
 // ============================================================================
 // Renderer
 // ============================================================================
@@ -184,23 +206,31 @@ pub fn render_session_detail(frame: &mut Frame, state: &AppState, area: Rect) {
         }
     };
 
-    // Layout: [header 3] [main_area] [footer 1]
+    // Layout: [header 3] [compat banner 1, if any] [main_area] [footer 1]
+    let banner = compat::check(data.written_by).banner();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),
+            Constraint::Length(u16::from(banner.is_some())),
             Constraint::Min(0),
             Constraint::Length(1),
         ])
         .split(area);
 
-    render_session_header(frame, chunks[0], &data);
+    render_session_header(frame, chunks[0], &data, &*state.meta.clock);
+    if let Some(banner) = banner {
+        frame.render_widget(
+            Paragraph::new(banner).style(Style::default().fg(Theme::BACKGROUND).bg(Theme::WARNING).add_modifier(Modifier::BOLD)),
+            chunks[1],
+        );
+    }
 
     // Split main: [left 30% | right 70%]
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
-        .split(chunks[1]);
+        .split(chunks[2]);
 
     let is_left_focused = matches!(state.ui.focus, PanelFocus::Left);
     let sorted_agents = sorted_session_agents(&data);
@@ -209,33 +239,58 @@ pub fn render_session_detail(frame: &mut Frame, state: &AppState, area: Rect) {
     render_left_panel(frame, main_chunks[0], &data, &sorted_agents, state, is_left_focused);
 
     // Right: per-agent filtered events
-    // Index 0 = Main (show agent_id=None events), index n>=1 = sorted_agents[n-1]
-    let event_filter = match state.ui.selected_session_agent_index {
-        Some(0) => EventFilter::Main,
-        Some(n) => match sorted_agents.get(n - 1) {
-            Some(agent) => EventFilter::Agent(&agent.id),
-            None => EventFilter::All,
-        },
-        None => EventFilter::All,
+    render_events_panel(frame, main_chunks[1], state, &data, &sorted_agents, !is_left_focused);
+
+    render_session_detail_footer(frame, chunks[3]);
+
+    render_agent_prompt(frame, area, state, &sorted_agents);
+}
+
+/// Render the focused panel alone for zoom (`z`): session info and agents on
+/// the left, the events or notes on the right.
+pub fn render_focused_panel(frame: &mut Frame, state: &AppState, area: Rect) {
+    let Some(data) = get_selected_session_data(state) else {
+        render_session_detail(frame, state, area);
+        return;
     };
+    let sorted_agents = sorted_session_agents(&data);
+    match state.ui.focus {
+        PanelFocus::Left => render_left_panel(frame, area, &data, &sorted_agents, state, true),
+        PanelFocus::Right => render_events_panel(frame, area, state, &data, &sorted_agents, true),
+    }
+    render_agent_prompt(frame, area, state, &sorted_agents);
+}
+
+/// Right panel filtered to the selected agent; records its scroll limit.
+fn render_events_panel(
+    frame: &mut Frame,
+    area: Rect,
+    state: &AppState,
+    data: &SessionViewData<'_>,
+    sorted_agents: &[&Agent],
+    is_focused: bool,
+) {
+    let event_filter = selected_event_filter(state, sorted_agents);
+    let max_offset = render_right_panel(frame, area, state, data, &event_filter, is_focused);
+    state.ui.scroll_limits.session_detail_right.set(Some(max_offset));
+}
+
+/// Prompt popup overlay — only for agent selections (not Main).
+fn render_agent_prompt(frame: &mut Frame, area: Rect, state: &AppState, sorted_agents: &[&Agent]) {
     let selected_agent = match state.ui.selected_session_agent_index {
         Some(n) if n >= 1 => sorted_agents.get(n - 1).copied(),
         _ => None,
     };
-    render_right_panel(frame, main_chunks[1], &data, &event_filter, state.ui.scroll_offsets.session_detail_right, !is_left_focused);
-
-    render_session_detail_footer(frame, chunks[2]);
-
-    // Prompt popup overlay — only for agent selections (not Main)
     if state.ui.prompt_popup.is_open() {
         if let Some(agent) = selected_agent {
             let text = agent.task_description.as_deref().unwrap_or("No prompt available");
             render_prompt_popup(
                 frame,
                 area,
-                &agent.display_name(),
+                agent.display_name(),
                 agent.model.as_deref(),
                 agent.agent_type.as_deref(),
+                agent_defs::for_agent(&state.domain.agent_definitions, agent),
                 text,
                 &agent.messages,
                 &agent.skills,
@@ -260,7 +315,7 @@ fn render_no_session(frame: &mut Frame, area: Rect) {
     frame.render_widget(p, area);
 }
 
-fn render_session_header(frame: &mut Frame, area: Rect, data: &SessionViewData<'_>) {
+fn render_session_header(frame: &mut Frame, area: Rect, data: &SessionViewData<'_>, clock: &dyn Clock) {
     let meta = data.meta;
     let status_str = match meta.status {
         SessionStatus::Active => "Active",
@@ -279,8 +334,7 @@ fn render_session_header(frame: &mut Frame, area: Rect, data: &SessionViewData<'
     let duration_str = match meta.duration {
         Some(d) => format_duration(Some(d)),
         None if meta.status == SessionStatus::Active => {
-            let elapsed = Utc::now().signed_duration_since(meta.timestamp);
-            format_duration(elapsed.to_std().ok())
+            format_duration(meta.elapsed(clock).to_std().ok())
         }
         None => format_duration(None),
     };
@@ -324,30 +378,48 @@ fn render_left_panel(
         .constraints([Constraint::Length(7), Constraint::Min(0)])
         .split(area);
 
-    render_session_info(frame, chunks[0], data, is_focused);
+    let clock = &*state.meta.clock;
+    render_session_info(frame, chunks[0], data, &state.ui.time_format, clock, is_focused);
+    let tool_stats = anomaly::tool_stats(data.events.iter());
+    let anomalies: Vec<_> = sorted_agents
+        .iter()
+        .map(|agent| {
+            let tools = tool_stats.get(&agent.id).copied().unwrap_or_default();
+            anomaly::flag(agent, tools, &state.domain.agent_baselines, clock)
+        })
+        .collect();
     render_agent_list_with_main(
         frame,
         chunks[1],
         sorted_agents,
+        &state.ui.agent_kinds,
         state.ui.selected_session_agent_index,
+        &anomalies,
         is_focused,
         data.meta,
+        clock,
     );
 }
 
-fn render_session_info(frame: &mut Frame, area: Rect, data: &SessionViewData<'_>, is_focused: bool) {
+fn render_session_info(
+    frame: &mut Frame,
+    area: Rect,
+    data: &SessionViewData<'_>,
+    time_format: &TimeFormat,
+    clock: &dyn Clock,
+    is_focused: bool,
+) {
     let meta = data.meta;
-    let started = meta.timestamp.format("%Y-%m-%d %H:%M:%S").to_string();
+    let started = time_format.date_time_secs(meta.timestamp);
     // For active sessions, calculate duration from start to now
     let duration_str = match meta.duration {
         Some(d) => format_duration(Some(d)),
         None if meta.status == SessionStatus::Active => {
-            let elapsed = Utc::now().signed_duration_since(meta.timestamp);
-            format_duration(elapsed.to_std().ok())
+            format_duration(meta.elapsed(clock).to_std().ok())
         }
         None => format_duration(None),
     };
-    let event_count = data.events.len();
+    let event_count = data.events.len() + data.pending_events;
     let agent_count = data.agents.len();
 
     let lines = vec![
@@ -395,35 +467,150 @@ enum EventFilter<'a> {
     All,
 }
 
+impl EventFilter<'_> {
+    fn matches(&self, event: &TranscriptEvent) -> bool {
+        match self {
+            EventFilter::Main => event.agent_id.is_none(),
+            EventFilter::Agent(aid) => event.agent_id.as_ref() == Some(*aid),
+            EventFilter::All => true,
+        }
+    }
+}
+
+/// Index 0 = Main (show agent_id=None events), index n>=1 = sorted_agents[n-1]
+fn selected_event_filter<'a>(state: &AppState, sorted_agents: &[&'a Agent]) -> EventFilter<'a> {
+    match state.ui.selected_session_agent_index {
+        Some(0) => EventFilter::Main,
+        Some(n) => match sorted_agents.get(n - 1) {
+            Some(agent) => EventFilter::Agent(&agent.id),
+            None => EventFilter::All,
+        },
+        None => EventFilter::All,
+    }
+}
+
+/// Number of loaded events the right panel currently shows for the selected agent.
+pub fn visible_event_count(state: &AppState) -> usize {
+    let Some(data) = get_selected_session_data(state) else {
+        return 0;
+    };
+    let sorted_agents = sorted_session_agents(&data);
+    let filter = selected_event_filter(state, &sorted_agents);
+    data.events.iter().filter(|e| filter.matches(e)).count()
+}
+
 fn render_right_panel(
     frame: &mut Frame,
     area: Rect,
+    state: &AppState,
     data: &SessionViewData<'_>,
     filter: &EventFilter<'_>,
+    is_focused: bool,
+) -> usize {
+    let scroll_offset = state.ui.scroll_offsets.session_detail_right;
+    state.ui.scroll_limits.session_detail_right_x.set(Some(0));
+    match state.ui.session_detail_tab {
+        SessionDetailTab::Events => {
+            let (max_offset, max_x) = render_events_list(frame, area, data, filter, &state.ui, is_focused);
+            state.ui.scroll_limits.session_detail_right_x.set(Some(max_x));
+            max_offset
+        }
+        SessionDetailTab::Notes => match state.ui.notes_editor.as_ref() {
+            Some(editor) => {
+                render_notes_editor(frame, area, editor);
+                0
+            }
+            None => {
+                let is_live = state.domain.active_sessions.contains_key(&data.meta.id);
+                render_notes(frame, area, data.notes, is_live, scroll_offset, is_focused)
+            }
+        },
+    }
+}
+
+/// Returns the largest useful scroll offset for the rendered notes.
+fn render_notes(
+    frame: &mut Frame,
+    area: Rect,
+    notes: Option<&str>,
+    is_live: bool,
     scroll_offset: usize,
     is_focused: bool,
-) {
-    render_events_list(frame, area, data, filter, scroll_offset, is_focused);
+) -> usize {
+    let block = Block::default()
+        .title(" Notes ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if is_focused {
+            Theme::ACTIVE_BORDER
+        } else {
+            Theme::PANEL_BORDER
+        }));
+
+    let lines: Vec<Line> = match notes {
+        Some(text) => text
+            .lines()
+            .map(|line| {
+                if line.starts_with('#') {
+                    Line::from(Span::styled(line.to_string(), Style::default().fg(Theme::INFO).add_modifier(Modifier::BOLD)))
+                } else {
+                    Line::from(line.to_string())
+                }
+            })
+            .collect(),
+        None if is_live => vec![Line::from(Span::styled(
+            "Notes can be written once the session is archived",
+            Style::default().fg(Theme::MUTED_TEXT),
+        ))],
+        None => vec![Line::from(Span::styled(
+            "No notes yet. Press e to start from the postmortem template.",
+            Style::default().fg(Theme::MUTED_TEXT),
+        ))],
+    };
+
+    render_wrapped(frame, area, lines, block, scroll_offset)
 }
 
+/// Editor text without wrapping, scrolled so the cursor stays visible.
+fn render_notes_editor(frame: &mut Frame, area: Rect, editor: &NotesEditor) {
+    let block = Block::default()
+        .title(" Notes (editing) ")
+        .title_bottom(" Ctrl+S:save | Esc:discard ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::ACTIVE_BORDER));
+    let inner = block.inner(area);
+
+    let (line, column) = editor.cursor_position();
+    let top = line.saturating_sub(inner.height.saturating_sub(1) as usize);
+    let left = column.saturating_sub(inner.width.saturating_sub(1) as usize);
+
+    let p = Paragraph::new(editor.text.as_str())
+        .style(Style::default().fg(Theme::TEXT))
+        .block(block)
+        .scroll((top as u16, left as u16));
+    frame.render_widget(p, area);
+
+    if inner.width > 0 && inner.height > 0 {
+        frame.set_cursor_position((inner.x + (column - left) as u16, inner.y + (line - top) as u16));
+    }
+}
+
+/// Returns the largest useful vertical and horizontal offsets for the rendered events.
 fn render_events_list(
     frame: &mut Frame,
     area: Rect,
     data: &SessionViewData<'_>,
     filter: &EventFilter<'_>,
-    scroll_offset: usize,
+    ui: &UiState,
     is_focused: bool,
-) {
+) -> (usize, usize) {
+    let time_format = &ui.time_format;
     let events: Vec<&TranscriptEvent> = data.events.iter_rev()
-        .filter(|e| match filter {
-            EventFilter::Main => e.agent_id.is_none(),
-            EventFilter::Agent(aid) => e.agent_id.as_ref() == Some(*aid),
-            EventFilter::All => true,
-        })
+        .filter(|e| filter.matches(e))
         .collect();
 
     if events.is_empty() {
-        let p = Paragraph::new("No events")
+        let msg = if data.pending_events > 0 { "Loading events…" } else { "No events" };
+        let p = Paragraph::new(msg)
             .style(Style::default().fg(Theme::MUTED_TEXT))
             .block(
                 Block::default()
@@ -436,22 +623,19 @@ fn render_events_list(
                     })),
             );
         frame.render_widget(p, area);
-        return;
+        return (0, 0);
     }
 
     let mut lines = Vec::new();
-    let mut first = true;
+    let mut newer: Option<DateTime<Utc>> = None;
 
     for event in &events {
-        if !first {
-            lines.push(Line::from(Span::styled(
-                "────────────────────────────────",
-                Style::default().fg(Theme::SEPARATOR),
-            )));
+        if let Some(newer) = newer {
+            lines.push(crate::view::components::event_stream::event_separator(newer, event.timestamp, time_format));
         }
-        first = false;
+        newer = Some(event.timestamp);
 
-        let timestamp = event.timestamp.format("%H:%M:%S").to_string();
+        let timestamp = time_format.time(event.timestamp);
         let (icon, header, detail, event_color, tool_name) =
             crate::view::components::event_stream::format_transcript_event_lines(&event.kind);
 
@@ -499,27 +683,25 @@ fn render_events_list(
         }
     }
 
-    // Clamp scroll_offset to u16::MAX to prevent silent truncation overflow
-    // Additionally clamp to a reasonable maximum to avoid ratatui internal panics
-    let scroll = scroll_offset
-        .min(u16::MAX as usize)
-        .min(10000) as u16;
+    if data.pending_events > 0 {
+        lines.push(Line::from(Span::styled(
+            format!("… {} older events loading", data.pending_events),
+            Style::default().fg(Theme::MUTED_TEXT),
+        )));
+    }
 
-    let p = Paragraph::new(lines)
-        .block(
-            Block::default()
-                .title(" Events ")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(if is_focused {
-                    Theme::ACTIVE_BORDER
-                } else {
-                    Theme::PANEL_BORDER
-                })),
-        )
-        .wrap(Wrap { trim: false })
-        .scroll((scroll, 0));
+    let block = Block::default()
+        .title(" Events ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if is_focused {
+            Theme::ACTIVE_BORDER
+        } else {
+            Theme::PANEL_BORDER
+        }));
 
-    frame.render_widget(p, area);
+    let offsets = &ui.scroll_offsets;
+    let wrap = !ui.truncated_events.is_truncated(&ViewState::SessionDetail);
+    render_panned(frame, area, lines, block, offsets.session_detail_right, offsets.session_detail_right_x, wrap)
 }
 
 fn short_id(id: &str) -> String {
@@ -535,7 +717,9 @@ fn render_session_detail_footer(frame: &mut Frame, area: Rect) {
         Span::styled("j/k", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw(":select/scroll | "),
         Span::styled("p", Style::default().add_modifier(Modifier::BOLD)),
-        Span::raw(":prompt | "),
+        Span::raw(":agent stats | "),
+        Span::styled("n", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(":notes | "),
         Span::styled("?", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw(":help | "),
         Span::styled("q", Style::default().add_modifier(Modifier::BOLD)),
@@ -611,16 +795,19 @@ mod tests {
                 tool_name: "Read".into(),
                 result_summary: "ok".to_string(),
                 duration_ms: Some(100),
+                is_error: false,
             }),
             TranscriptEvent::new(Utc::now(), TranscriptEventKind::ToolResult {
                 tool_name: "Read".into(),
                 result_summary: "ok".to_string(),
                 duration_ms: Some(200),
+                is_error: false,
             }),
             TranscriptEvent::new(Utc::now(), TranscriptEventKind::ToolResult {
                 tool_name: "Bash".into(),
                 result_summary: "ok".to_string(),
                 duration_ms: Some(500),
+                is_error: false,
             }),
         ];
 
@@ -669,6 +856,9 @@ mod tests {
             agents: AgentsRef::Borrowed(&agents),
             events: EventsRef::Vec(&vec![]),
             task_graph: None,
+            pending_events: 0,
+            notes: None,
+            written_by: None,
         };
 
         let sorted = sorted_session_agents(&data);
@@ -1,53 +1,114 @@
 use ratatui::{
-    layout::{Alignment, Constraint, Rect},
-    prelude::Stylize,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Row, Table},
+    widgets::{Block, Borders, Paragraph},
     Frame,
 };
 
-use crate::app::state::AppState;
-use crate::model::{theme::Theme, SessionMeta, SessionStatus};
-use super::components::format::format_duration;
+use crate::app::columns::{self, TableId};
+use crate::app::runs::{self, RunSummary};
+use crate::app::state::{project_name, AppState, PanelFocus};
+use crate::model::{theme::Theme, SessionMeta, SessionOutcome, SessionPreview, SessionStatus};
+use crate::session;
+use super::components::activity_calendar::{render_activity_calendar, CALENDAR_HEIGHT};
+use super::components::event_stream::format_transcript_event_lines;
+use super::components::format::{format_duration, TimeFormat};
+use super::components::table::{render_table, Column, TableRow};
+
+/// Minimum content width before the preview pane is shown beside the table.
+const PREVIEW_MIN_WIDTH: u16 = 100;
+
+/// Width of the preview pane.
+const PREVIEW_WIDTH: u16 = 40;
+
+/// Minimum content height before the activity calendar is shown above the table.
+const CALENDAR_MIN_HEIGHT: u16 = 20;
+
+/// Goal text is cut here in the table; the column clips it further when narrow.
+const GOAL_MAX_CHARS: usize = 80;
+
+/// Session table columns; the ID is cut to 12 characters unless widened.
+const SESSION_COLUMNS: [Column; 9] = [
+    Column::new("", 3, 3, 0),
+    Column::new("Session ID", 8, 12, 0),
+    Column::new("Date", 10, 20, 0),
+    Column::new("Duration", 8, 10, 0).right(),
+    Column::new("Status", 6, 10, 0),
+    Column::new("Agents", 6, 7, 0).right(),
+    Column::new("Tasks", 5, 6, 0).right(),
+    Column::new("Goal", 10, GOAL_MAX_CHARS as u16, 2),
+    Column::new("Project", 8, 40, 1),
+];
 
 /// Render the sessions archive view into the given content area.
 /// Global header is rendered by the view dispatcher.
 pub fn render_sessions(frame: &mut Frame, state: &AppState, area: Rect) {
+    let all_sessions = listed_sessions(state);
 
-    // Combine confirmed active sessions + archived sessions for display
-    let all_sessions: Vec<&SessionMeta> = state.domain.confirmed_active_sessions()
-        .map(|(_, m)| m)
-        .chain(state.domain.sessions.iter().map(|a| &a.meta))
-        .collect();
+    // Empty state: no sessions at all (a day picked on the calendar may just be quiet)
+    if all_sessions.is_empty() && state.ui.session_date.is_none() {
+        render_empty_state(frame, area);
+        return;
+    }
+
+    // Activity calendar above the list when there is height to spare
+    let area = if area.height >= CALENDAR_MIN_HEIGHT {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(CALENDAR_HEIGHT), Constraint::Min(0)])
+            .split(area);
+        render_activity_calendar(frame, chunks[0], state);
+        chunks[1]
+    } else {
+        area
+    };
+
+    // Split off the quick-preview pane when there is room for it
+    let (table_area, preview_area) = if area.width >= PREVIEW_MIN_WIDTH {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(PREVIEW_WIDTH)])
+            .split(area);
+        (chunks[0], Some(chunks[1]))
+    } else {
+        (area, None)
+    };
+
+    render_session_table(frame, table_area, state, &all_sessions);
+    if let Some(preview_area) = preview_area {
+        render_selected_preview(frame, preview_area, state, &all_sessions);
+    }
+}
 
-    // Empty state: no sessions at all
+/// Render the focused panel alone for zoom (`z`): the session table on the left,
+/// the preview on the right.
+pub fn render_focused_panel(frame: &mut Frame, state: &AppState, area: Rect) {
+    let all_sessions = listed_sessions(state);
     if all_sessions.is_empty() {
         render_empty_state(frame, area);
         return;
     }
+    match state.ui.focus {
+        PanelFocus::Left => render_session_table(frame, area, state, &all_sessions),
+        PanelFocus::Right => render_selected_preview(frame, area, state, &all_sessions),
+    }
+}
+
+/// Confirmed active sessions followed by archived sessions, as listed in the table.
+fn listed_sessions(state: &AppState) -> Vec<&SessionMeta> {
+    state.domain.confirmed_active_sessions()
+        .map(|(_, m)| m)
+        .chain(state.domain.sessions.iter().map(|a| &a.meta))
+        .collect()
+}
 
+fn render_session_table(frame: &mut Frame, area: Rect, state: &AppState, all_sessions: &[&SessionMeta]) {
     // Build table rows from session list
     // TODO: use state.ui.scroll_offsets.sessions to scroll the session table
     let active_count = state.domain.confirmed_active_count();
 
-    let header_row = Row::new(vec![
-        "",
-        "Session ID",
-        "Date",
-        "Duration",
-        "Status",
-        "Agents",
-        "Tasks",
-        "Project",
-    ])
-    .style(
-        Style::default()
-            .fg(Theme::INFO)
-            .add_modifier(Modifier::BOLD),
-    );
-
-    let rows: Vec<Row> = all_sessions
+    let session_rows = all_sessions
         .iter()
         .enumerate()
         .map(|(idx, session)| {
@@ -60,16 +121,21 @@ pub fn render_sessions(frame: &mut Frame, state: &AppState, area: Rect) {
                 Style::default().fg(Theme::TEXT)
             };
 
-            let status_color = match session.status {
-                SessionStatus::Active => Theme::TASK_RUNNING,
-                SessionStatus::Completed => Theme::TASK_COMPLETED,
-                SessionStatus::Failed => Theme::TASK_FAILED,
-                SessionStatus::Cancelled => Theme::MUTED_TEXT,
+            // An outcome says more than the status; `Unknown` says less
+            let ended = session
+                .outcome
+                .filter(|o| *o != SessionOutcome::Unknown && session.status != SessionStatus::Active);
+            let status_color = match (&session.status, ended) {
+                (_, Some(ended)) => outcome_color(ended),
+                (SessionStatus::Active, None) => Theme::TASK_RUNNING,
+                (SessionStatus::Completed, None) => Theme::TASK_COMPLETED,
+                (SessionStatus::Failed, None) => Theme::TASK_FAILED,
+                (SessionStatus::Cancelled, None) => Theme::MUTED_TEXT,
             };
 
             // All counts tracked per-session; active sessions get live duration
             let duration = if session.status == SessionStatus::Active {
-                (chrono::Utc::now() - session.timestamp).to_std().ok()
+                session.elapsed(&*state.meta.clock).to_std().ok()
             } else {
                 session.duration
             };
@@ -79,6 +145,8 @@ pub fn render_sessions(frame: &mut Frame, state: &AppState, area: Rect) {
 
             let status_str = if is_loading {
                 "Loading…".to_string()
+            } else if let Some(ended) = ended {
+                outcome_badge(ended).to_string()
             } else {
                 format_status(&session.status)
             };
@@ -91,55 +159,238 @@ pub fn render_sessions(frame: &mut Frame, state: &AppState, area: Rect) {
                 "[ ]".to_string()
             };
 
-            Row::new(vec![
+            let cells = [
                 checkbox,
                 session.id.to_string(),
-                session.timestamp.format("%Y-%m-%d %H:%M").to_string(),
+                state.ui.time_format.date_time(session.timestamp),
                 format_duration(duration),
                 status_str,
                 session.agent_count.to_string(),
                 session.task_count.to_string(),
-                session.project_path.clone(),
-            ])
-            .style(style)
-            .fg(if is_selected {
-                Theme::BACKGROUND
-            } else if is_loading || state.ui.marked_sessions.contains(&session.id) {
-                Theme::WARNING
+                session.goal_summary(GOAL_MAX_CHARS).unwrap_or_else(|| "—".to_string()),
+                project_name(&session.project_path).to_string(),
+            ];
+            TableRow::new(
+                cells.into_iter().map(Span::raw).collect(),
+                style.fg(if is_selected {
+                    Theme::BACKGROUND
+                } else if is_loading || state.ui.marked_sessions.contains(&session.id) {
+                    Theme::WARNING
+                } else {
+                    status_color
+                }),
+            )
+        });
+    let rows: Vec<TableRow> = if state.ui.group_sessions_by_run {
+        grouped_rows(state, all_sessions, session_rows)
+    } else {
+        session_rows.collect()
+    };
+
+    let block = Block::default()
+        .title(format!(
+            " Archived Sessions — {}{}{}{} · {} ",
+            state.ui.project_filter.label(&state.meta.project_path),
+            state.ui.session_date.map(|day| format!(" · {}", day.format("%Y-%m-%d"))).unwrap_or_default(),
+            state.ui.outcome_filter.map(|want| format!(" · {}", want.label())).unwrap_or_default(),
+            if state.ui.group_sessions_by_run { " · by run" } else { "" },
+            archive_count_label(state)
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::PANEL_BORDER));
+    let adjust = state.ui.table_columns.get(TableId::Sessions);
+    let selected = (columns::focused_table(state) == Some(TableId::Sessions)).then(|| adjust.map_or(0, |a| a.selected));
+    render_table(frame, area, &SESSION_COLUMNS, rows, block, adjust, selected);
+}
+
+/// The session rows with a totals row above each run's sessions. Sessions of a
+/// run overlap in time, so they sit together in the start-ordered table; the
+/// rows themselves stay in place so the selection keeps its index.
+fn grouped_rows<'a>(
+    state: &AppState,
+    all_sessions: &[&SessionMeta],
+    session_rows: impl Iterator<Item = TableRow<'a>>,
+) -> Vec<TableRow<'a>> {
+    let runs = runs::summarize(all_sessions, &*state.meta.clock);
+    let mut rows = Vec::new();
+    let mut previous: Option<Option<&String>> = None;
+    for (session, row) in all_sessions.iter().zip(session_rows) {
+        let run = session.loom_plan_id.as_ref();
+        if previous != Some(run) {
+            rows.push(run_header_row(state, run.and_then(|id| runs.get(id).map(|summary| (id, summary)))));
+        }
+        previous = Some(run);
+        rows.push(row);
+    }
+    rows
+}
+
+/// Totals row heading a run's sessions, or a plain marker above sessions in no run.
+fn run_header_row(state: &AppState, run: Option<(&String, &RunSummary)>) -> TableRow<'static> {
+    let style = Style::default().fg(Theme::ACCENT_PURPLE).bg(Theme::SURFACE).add_modifier(Modifier::BOLD);
+    let Some((id, summary)) = run else {
+        return TableRow::new(vec![Span::raw(""), Span::raw("not in a run")], style.fg(Theme::MUTED_TEXT));
+    };
+    let status = if summary.active > 0 { format!("{} active", summary.active) } else { "Done".to_string() };
+    let failed = if summary.failed_tasks > 0 { format!(" · {} failed", summary.failed_tasks) } else { String::new() };
+    let outcomes: Vec<String> = summary.outcomes.iter().map(|(ended, count)| format!("{count} {}", ended.label())).collect();
+    let outcomes = if outcomes.is_empty() { String::new() } else { format!(" ({})", outcomes.join(", ")) };
+    let cells = [
+        "▾".to_string(),
+        id.clone(),
+        state.ui.time_format.date_time(summary.started),
+        format_duration(summary.span().to_std().ok()),
+        status,
+        summary.agents.to_string(),
+        summary.tasks.to_string(),
+        format!("{} sessions{} · {} events{}", summary.sessions, outcomes, summary.events, failed),
+        String::new(),
+    ];
+    TableRow::new(cells.into_iter().map(Span::raw).collect(), style)
+}
+
+fn render_selected_preview(frame: &mut Frame, area: Rect, state: &AppState, all_sessions: &[&SessionMeta]) {
+    let selected = state
+        .ui
+        .selected_session_index
+        .and_then(|idx| all_sessions.get(idx).map(|meta| (idx, *meta)));
+    render_preview(frame, area, state, selected, state.domain.confirmed_active_count());
+}
+
+/// Render the quick-preview pane for the highlighted session.
+/// Active sessions are summarized from live state; archived sessions use the
+/// cached preview (loaded in the background) or in-memory archive data.
+fn render_preview(
+    frame: &mut Frame,
+    area: Rect,
+    state: &AppState,
+    selected: Option<(usize, &SessionMeta)>,
+    active_count: usize,
+) {
+    let block = Block::default()
+        .title(" Preview ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::PANEL_BORDER));
+
+    let Some((idx, meta)) = selected else {
+        frame.render_widget(Paragraph::new("").block(block), area);
+        return;
+    };
+
+    let label = |s: &'static str| Span::styled(s, Style::default().fg(Theme::MUTED_TEXT));
+    let duration = if meta.status == SessionStatus::Active {
+        meta.elapsed(&*state.meta.clock).to_std().ok()
+    } else {
+        meta.duration
+    };
+
+    let mut lines = vec![
+        Line::from(vec![label("Status:   "), Span::raw(format_status(&meta.status))]),
+        Line::from(vec![
+            label("Started:  "),
+            Span::raw(state.ui.time_format.date_time(meta.timestamp)),
+        ]),
+        Line::from(vec![label("Duration: "), Span::raw(format_duration(duration))]),
+        Line::from(vec![
+            label("Branch:   "),
+            Span::raw(meta.git_branch.as_deref().unwrap_or("—").to_string()),
+        ]),
+    ];
+    if let Some(goal) = meta.goal_summary(PREVIEW_WIDTH as usize - 12) {
+        lines.push(Line::from(vec![label("Goal:     "), Span::raw(goal)]));
+    }
+
+    // Live sessions are cheap to summarize; archives come from the cache
+    let computed;
+    let preview = if idx < active_count {
+        let agent_count = state
+            .domain
+            .agents
+            .values()
+            .filter(|a| a.session_id.as_ref() == Some(&meta.id))
+            .count();
+        let events = state
+            .domain
+            .events
+            .iter()
+            .filter(|e| e.session_id.as_ref() == Some(&meta.id));
+        computed = SessionPreview::from_events(events, agent_count);
+        Some(&computed)
+    } else if let Some(cached) = state.session_preview(&meta.id) {
+        Some(cached)
+    } else if let Some(archive) = state.domain.sessions.get(idx - active_count).and_then(|s| s.complete_data()) {
+        computed = session::build_preview(archive);
+        Some(&computed)
+    } else {
+        None
+    };
+
+    match preview {
+        Some(preview) => lines.extend(preview_lines(preview, &state.ui.time_format)),
+        None => {
+            let msg = if state.ui.preview_loading.as_ref() == Some(&meta.id) {
+                "Loading preview…"
+            } else if state.is_preview_failed(&meta.id) {
+                "Preview unavailable"
             } else {
-                status_color
-            })
-        })
-        .collect();
-
-    let widths = [
-        Constraint::Length(3),  // Checkbox
-        Constraint::Length(12), // Session ID
-        Constraint::Length(16), // Date
-        Constraint::Length(10), // Duration
-        Constraint::Length(10), // Status
-        Constraint::Length(7),  // Agents
-        Constraint::Length(6),  // Tasks
-        Constraint::Min(20),    // Project (flexible)
+                ""
+            };
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(msg, Style::default().fg(Theme::MUTED_TEXT))));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(block.title(Line::from(format!(" {} ", meta.id)).right_aligned()))
+        .style(Style::default().fg(Theme::TEXT));
+    frame.render_widget(paragraph, area);
+}
+
+/// Pure function: counts, top tools and recent events for the preview pane.
+fn preview_lines(preview: &SessionPreview, time_format: &TimeFormat) -> Vec<Line<'static>> {
+    let heading = |s: &'static str| {
+        Line::from(Span::styled(
+            s,
+            Style::default().fg(Theme::INFO).add_modifier(Modifier::BOLD),
+        ))
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("Agents: {}  Events: {}", preview.agent_count, preview.event_count),
+            Style::default().fg(Theme::MUTED_TEXT),
+        )),
+        Line::from(""),
+        heading("Tools"),
     ];
 
-    let table = Table::new(rows, widths)
-        .header(header_row)
-        .block(
-            Block::default()
-                .title(" Archived Sessions ")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Theme::PANEL_BORDER)),
-        )
-        .row_highlight_style(
-            Style::default()
-                .bg(Theme::ACTIVE_BORDER)
-                .fg(Theme::BACKGROUND)
-                .add_modifier(Modifier::BOLD),
-        );
+    if preview.tool_counts.is_empty() {
+        lines.push(Line::from(Span::styled("  none", Style::default().fg(Theme::MUTED_TEXT))));
+    }
+    for (tool, count) in preview.tool_counts.iter().take(5) {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<12}", tool.as_str()),
+                Style::default().fg(Theme::tool_color(tool.as_str())),
+            ),
+            Span::raw(format!("{count:>5}")),
+        ]));
+    }
 
-    // Apply scroll offset by skipping rows
-    frame.render_widget(table, area);
+    lines.push(Line::from(""));
+    lines.push(heading("Recent"));
+    for event in &preview.recent_events {
+        let (icon, header, _, color, _) = format_transcript_event_lines(&event.kind);
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {} ", time_format.time(event.timestamp)),
+                Style::default().fg(Theme::MUTED_TEXT),
+            ),
+            Span::styled(format!("{icon} {header}"), Style::default().fg(color)),
+        ]));
+    }
+
+    lines
 }
 
 /// Render empty state when no sessions exist.
@@ -171,6 +422,17 @@ fn render_empty_state(frame: &mut Frame, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
+/// Pure function: archives on disk, and how many are paged in while more remain.
+pub fn archive_count_label(state: &AppState) -> String {
+    let pages = &state.domain.archive_pages;
+    let total = pages.total().max(state.domain.archive_index.len());
+    match pages.remaining() {
+        0 => format!("{} archives", total),
+        remaining if state.ui.loading_metas => format!("{} of {} archives loaded, loading more…", total - remaining, total),
+        remaining => format!("{} of {} archives loaded", total - remaining, total),
+    }
+}
+
 /// Format session status as string.
 fn format_status(status: &SessionStatus) -> String {
     match status {
@@ -182,6 +444,27 @@ fn format_status(status: &SessionStatus) -> String {
     .to_string()
 }
 
+/// Status column text of an ended session with a recorded outcome.
+fn outcome_badge(outcome: SessionOutcome) -> &'static str {
+    match outcome {
+        SessionOutcome::Success => "Success",
+        SessionOutcome::PartialSuccess => "Partial",
+        SessionOutcome::Failed => "Failed",
+        SessionOutcome::Aborted => "Aborted",
+        SessionOutcome::Unknown => "Unknown",
+    }
+}
+
+/// Status column color of an ended session with a recorded outcome.
+fn outcome_color(outcome: SessionOutcome) -> ratatui::style::Color {
+    match outcome {
+        SessionOutcome::Success => Theme::TASK_COMPLETED,
+        SessionOutcome::PartialSuccess => Theme::WARNING,
+        SessionOutcome::Failed => Theme::TASK_FAILED,
+        SessionOutcome::Aborted | SessionOutcome::Unknown => Theme::MUTED_TEXT,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,6 +540,124 @@ mod tests {
         assert!(buffer_str.contains("s2"), "Session s2 should be displayed");
     }
 
+    #[test]
+    fn grouped_mode_heads_each_run_with_its_totals() {
+        let backend = TestBackend::new(200, 16);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut state = AppState::new();
+        state.ui.group_sessions_by_run = true;
+        let session = |id: &str, run: Option<&str>| {
+            let mut meta = SessionMeta::new(id, Utc::now(), "/proj/foo".to_string())
+                .with_status(SessionStatus::Completed)
+                .with_duration(Duration::from_secs(300));
+            meta.loom_plan_id = run.map(str::to_string);
+            ArchivedSession::new(meta, PathBuf::new())
+        };
+        state.domain.sessions = vec![session("p1", Some("auth-3")), session("w1", Some("auth-3")), session("s9", None)];
+
+        terminal
+            .draw(|frame| render_sessions(frame, &state, frame.area()))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..buffer.area.height)
+            .map(|y| (0..buffer.area.width).map(|x| buffer.cell((x, y)).unwrap().symbol()).collect())
+            .collect();
+        let header = rows.iter().position(|row| row.contains("auth-3")).expect("run header");
+        assert!(rows[header].contains("2 sessions"), "{}", rows[header]);
+        assert!(rows[header + 1].contains("p1") && rows[header + 2].contains("w1"));
+        assert!(rows[header + 3].contains("not in a run"));
+        assert!(rows[header + 4].contains("s9"));
+    }
+
+    #[test]
+    fn test_render_sessions_shows_preview_for_highlighted_session() {
+        use crate::model::{SessionPreview, TranscriptEvent, TranscriptEventKind};
+
+        let backend = TestBackend::new(140, 30);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut state = AppState::new();
+        let meta = SessionMeta::new("s1", Utc::now(), "/proj/foo".to_string())
+            .with_status(SessionStatus::Completed);
+        state.domain.sessions = vec![ArchivedSession::new(meta, PathBuf::from("/tmp/s1.json"))];
+        state.ui.selected_session_index = Some(0);
+        let events = vec![TranscriptEvent::new(
+            Utc::now(),
+            TranscriptEventKind::ToolUse {
+                tool_name: "Bash".into(),
+                input_summary: "cargo test".to_string(),
+            },
+        )];
+        state.cache_session_preview("s1".into(), SessionPreview::from_events(&events, 1));
+
+        terminal
+            .draw(|frame| render_sessions(frame, &state, frame.area()))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let buffer_str: String = (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer.cell((x, y)).unwrap().symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        assert!(buffer_str.contains("Preview"), "Preview pane should be displayed");
+        assert!(buffer_str.contains("Recent"), "Recent events heading should be displayed");
+        assert!(buffer_str.contains("Bash"), "Tool summary should list Bash");
+    }
+
+    #[test]
+    fn test_render_sessions_shows_goal_column() {
+        let backend = TestBackend::new(140, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut state = AppState::new();
+        state.domain.sessions = vec![ArchivedSession::new(
+            SessionMeta::new("s1", Utc::now(), "/proj/foo".to_string())
+                .with_status(SessionStatus::Completed)
+                .with_goal("Add OAuth\nlogin".to_string()),
+            PathBuf::new(),
+        )];
+
+        terminal
+            .draw(|frame| render_sessions(frame, &state, frame.area()))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let buffer_str: String = buffer.content().iter().map(|c| c.symbol()).collect();
+
+        assert!(buffer_str.contains("Goal"), "Goal header should be displayed");
+        assert!(buffer_str.contains("Add OAuth login"), "Goal should be shown on one line");
+    }
+
+    #[test]
+    fn test_render_sessions_hides_preview_when_narrow() {
+        let backend = TestBackend::new(80, 24);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        let mut state = AppState::new();
+        state.domain.sessions = vec![ArchivedSession::new(
+            SessionMeta::new("s1", Utc::now(), "/proj".to_string()),
+            PathBuf::new(),
+        )];
+        state.ui.selected_session_index = Some(0);
+
+        terminal
+            .draw(|frame| render_sessions(frame, &state, frame.area()))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let top_row: String = (0..buffer.area.width)
+            .map(|x| buffer.cell((x, 0)).unwrap().symbol())
+            .collect();
+        assert!(!top_row.contains("Preview"));
+    }
+
     #[test]
     fn test_format_status() {
         assert_eq!(format_status(&SessionStatus::Active), "Active");
@@ -0,0 +1,162 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::state::AppState;
+use crate::app::timeline::{session_colors, timeline_events};
+use crate::model::Theme;
+use super::components::event_stream::format_transcript_event_lines;
+use super::components::filter_bar::filter_chip;
+use super::components::footer::render_footer;
+
+/// Shorten a session ID to its first 8 chars.
+fn short_session(id: &str) -> String {
+    id.chars().take(8).collect()
+}
+
+/// Pure function: one entry per active session — its color, short id and
+/// branch (or goal) — wrapped over as many lines as `width` needs.
+fn build_legend_lines(state: &AppState, width: usize) -> Vec<Line<'static>> {
+    let sessions = session_colors(state);
+    if sessions.is_empty() {
+        return vec![Line::from(Span::styled(
+            "No active sessions",
+            Style::default().fg(Theme::MUTED_TEXT).add_modifier(Modifier::ITALIC),
+        ))];
+    }
+    let mut lines = Vec::new();
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut used = 0;
+    for (id, color) in sessions {
+        let meta = &state.domain.active_sessions[id];
+        let label = meta
+            .git_branch
+            .clone()
+            .or_else(|| meta.goal.as_ref().map(|goal| goal.chars().take(24).collect()))
+            .unwrap_or_default();
+        let entry = vec![
+            Span::styled("■ ", Style::default().fg(color)),
+            Span::styled(short_session(id.as_str()), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::styled(format!(" {}   ", label), Style::default().fg(Theme::MUTED_TEXT)),
+        ];
+        let len: usize = entry.iter().map(|s| s.content.chars().count()).sum();
+        if used > 0 && used + len > width {
+            lines.push(Line::from(std::mem::take(&mut spans)));
+            used = 0;
+        }
+        used += len;
+        spans.extend(entry);
+    }
+    lines.push(Line::from(spans));
+    lines
+}
+
+/// Pure function: one line per event, newest first, led by a bar in its
+/// session's color.
+fn build_timeline_lines(state: &AppState) -> Vec<Line<'static>> {
+    let colors = session_colors(state);
+    let events = timeline_events(state);
+    if events.is_empty() {
+        return vec![Line::from(Span::styled("No events", Style::default().fg(Theme::MUTED_TEXT)))];
+    }
+    events
+        .into_iter()
+        .map(|event| {
+            let color = event
+                .session_id
+                .as_ref()
+                .and_then(|id| colors.iter().find(|(sid, _)| *sid == id))
+                .map_or(Theme::MUTED_TEXT, |(_, color)| *color);
+            let session = event.session_id.as_ref().map(|id| short_session(id.as_str())).unwrap_or_default();
+            let (icon, header, _, event_color, _) = format_transcript_event_lines(&event.kind);
+            let mut spans = vec![
+                Span::styled("▌", Style::default().fg(color)),
+                Span::styled(format!("{:<8} ", session), Style::default().fg(color)),
+                Span::styled(format!("{} ", state.ui.time_format.time(event.timestamp)), Style::default().fg(Theme::MUTED_TEXT)),
+                Span::styled(format!("{} ", icon), Style::default().fg(event_color)),
+                Span::styled(header, Style::default().fg(event_color)),
+            ];
+            if let Some(agent) = event.agent_id.as_ref() {
+                let (label, color) = state.domain.agents.get(agent).map_or_else(
+                    || (agent.as_str().chars().take(7).collect(), Theme::AGENT_LABEL),
+                    |a| {
+                        let (icon, color) = state.ui.agent_kinds.style(a);
+                        (format!("{} {}", icon, a.display_name()), color)
+                    },
+                );
+                spans.push(Span::styled(format!("  {}", label), Style::default().fg(color)));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Render the interleaved timeline of all active sessions under its legend.
+pub fn render_timeline(frame: &mut Frame, state: &AppState, area: Rect) {
+    let legend = build_legend_lines(state, area.width.saturating_sub(2) as usize);
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(legend.len() as u16 + 2), // Legend
+            Constraint::Min(0),                          // Timeline
+            Constraint::Length(1),                       // Footer
+        ])
+        .split(area);
+
+    let legend_block = Block::default()
+        .title(" Sessions ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::PANEL_BORDER));
+    frame.render_widget(Paragraph::new(legend).block(legend_block), layout[0]);
+    render_focused_panel(frame, state, layout[1]);
+    render_footer(frame, layout[2], state);
+}
+
+/// Render the timeline alone (also its zoomed panel, `z`).
+pub fn render_focused_panel(frame: &mut Frame, state: &AppState, area: Rect) {
+    let lines = build_timeline_lines(state);
+    let title = format!(" Timeline ({} sessions) ", session_colors(state).len());
+    let mut block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::ACTIVE_BORDER));
+    if let Some(chip) = filter_chip(state) {
+        block = block.title(chip);
+    }
+    let offset = state.ui.scroll_offsets.timeline.min(lines.len().saturating_sub(1));
+    frame.render_widget(Paragraph::new(lines).block(block).scroll((offset as u16, 0)), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{SessionId, SessionMeta, TranscriptEvent, TranscriptEventKind};
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn lines_carry_their_session_color_and_the_legend_wraps() {
+        let mut state = AppState::new();
+        let now = Utc::now();
+        for (id, branch, minutes_ago) in [("3f2a9c1e-0001", "main", 10), ("7be04d55-0002", "feature/auth", 5)] {
+            let mut meta = SessionMeta::new(id, now - Duration::minutes(minutes_ago), "/proj".into());
+            meta.confirmed = true;
+            meta.git_branch = Some(branch.into());
+            state.domain.active_sessions.insert(SessionId::new(id), meta);
+        }
+        state.domain.events.push_back(TranscriptEvent::new(now, TranscriptEventKind::UserMessage).with_session("7be04d55-0002"));
+
+        let lines = build_timeline_lines(&state);
+        assert_eq!(lines[0].spans[0].style.fg, Some(Theme::ACCENT_WARM));
+        assert_eq!(lines[0].spans[1].content, "7be04d55 ");
+
+        assert_eq!(build_legend_lines(&state, 200).len(), 1);
+        let narrow = build_legend_lines(&state, 30);
+        assert_eq!(narrow.len(), 2);
+        let first: String = narrow[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(first.contains("main"), "{first}");
+    }
+}
@@ -5,23 +5,18 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Bar, BarChart, BarGroup, Block, Borders, Paragraph, Row, Table},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Paragraph},
     Frame,
 };
 
-use crate::app::state::AppState;
+use crate::app::cost::{estimate_cost_cents, estimate_session_cost, live_session_agents};
+use crate::app::cost_history::{cost_records, summarize, CostHistory, CostPeriod};
+use crate::app::state::{AppState, PanelFocus};
+use crate::app::TokenDashboardTab;
 use crate::model::{Agent, AgentId, SessionId, Theme};
 use super::components::footer::render_footer;
 use super::components::format::{format_cost_usd, format_token_count};
-
-// ── Pricing (per 1M tokens, in cents) ──────────────────────────────────────
-
-const OPUS_INPUT_PER_M: u64 = 1_500;   // $15
-const OPUS_OUTPUT_PER_M: u64 = 7_500;   // $75
-const SONNET_INPUT_PER_M: u64 = 300;    // $3
-const SONNET_OUTPUT_PER_M: u64 = 1_500;  // $15
-const HAIKU_INPUT_PER_M: u64 = 80;      // $0.80
-const HAIKU_OUTPUT_PER_M: u64 = 400;     // $4
+use super::components::table::{render_table, Column, TableRow};
 
 // ── Aggregation types ──────────────────────────────────────────────────────
 
@@ -53,20 +48,6 @@ struct DashboardData {
 
 // ── Pure functions ─────────────────────────────────────────────────────────
 
-fn estimate_cost_cents(model: &str, input: u64, output: u64) -> u64 {
-    let lower = model.to_lowercase();
-    let (input_rate, output_rate) = if lower.contains("opus") {
-        (OPUS_INPUT_PER_M, OPUS_OUTPUT_PER_M)
-    } else if lower.contains("haiku") {
-        (HAIKU_INPUT_PER_M, HAIKU_OUTPUT_PER_M)
-    } else {
-        // sonnet fallback
-        (SONNET_INPUT_PER_M, SONNET_OUTPUT_PER_M)
-    };
-    // cost = tokens * rate_per_million / 1_000_000
-    (input * input_rate + output * output_rate) / 1_000_000
-}
-
 /// Determine the dominant model from a set of agents (model with most API tokens).
 /// Uses api_tokens() (input+output) to avoid cache_read inflation.
 /// Skips agents with no model set to avoid misattribution.
@@ -89,17 +70,8 @@ fn aggregate(state: &AppState) -> DashboardData {
     let mut sessions = Vec::new();
     let mut model_map: BTreeMap<String, (u64, u64, u64)> = BTreeMap::new(); // model -> (input, output, cost)
 
-    // Active sessions: gather agents by session_id.
-    // Agents created via AgentMetadataUpdated often lack session_id,
-    // so also match unattributed agents to the sole active session.
-    let active_sessions: Vec<_> = state.domain.confirmed_active_sessions().collect();
-    let single_active_sid = if active_sessions.len() == 1 {
-        Some(active_sessions[0].0.clone())
-    } else {
-        None
-    };
-
-    for &(sid, meta) in &active_sessions {
+    // Active sessions with their subagents (see `cost::live_session_agents`)
+    for (sid, meta, session_agents) in live_session_agents(state) {
         // Session-level tokens from main transcript (orchestrator)
         let main_input = meta.token_usage.input_tokens;
         let main_output = meta.token_usage.output_tokens;
@@ -107,18 +79,6 @@ fn aggregate(state: &AppState) -> DashboardData {
             + meta.token_usage.cache_read_input_tokens;
         let main_model = meta.model.clone().unwrap_or_else(|| "unknown".to_string());
 
-        // Subagent tokens
-        let session_agents: BTreeMap<AgentId, Agent> = state
-            .domain
-            .agents
-            .iter()
-            .filter(|(_, a)| {
-                a.session_id.as_ref() == Some(sid)
-                    || (a.session_id.is_none() && single_active_sid.as_ref() == Some(sid))
-            })
-            .map(|(id, a)| (id.clone(), a.clone()))
-            .collect();
-
         let (agent_input, agent_output, agent_cache) = sum_tokens(&session_agents);
 
         let total_input = main_input + agent_input;
@@ -205,7 +165,7 @@ fn aggregate(state: &AppState) -> DashboardData {
     }
 
     // Sort sessions by date descending
-    sessions.sort_by(|a, b| b.date.cmp(&a.date));
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.date));
 
     let by_model: Vec<ModelBreakdown> = model_map
         .into_iter()
@@ -243,17 +203,13 @@ fn sum_tokens(agents: &BTreeMap<AgentId, Agent>) -> (u64, u64, u64) {
     })
 }
 
-/// Estimate cost per-agent (each agent's tokens × its own model's rate).
-fn estimate_session_cost(agents: &BTreeMap<AgentId, Agent>) -> u64 {
-    agents.values().map(|a| {
-        let model = a.model.as_deref().unwrap_or("unknown");
-        estimate_cost_cents(model, a.token_usage.input_tokens, a.token_usage.output_tokens)
-    }).sum()
-}
-
 // ── Render ──────────────────────────────────────────────────────────────────
 
 pub fn render_token_cost_dashboard(frame: &mut Frame, state: &AppState, area: Rect) {
+    if state.ui.token_dashboard_tab == TokenDashboardTab::Cost {
+        render_cost_tab(frame, state, area);
+        return;
+    }
     let data = aggregate(state);
 
     let layout = Layout::default()
@@ -282,6 +238,30 @@ pub fn render_token_cost_dashboard(frame: &mut Frame, state: &AppState, area: Re
     render_footer(frame, layout[2], state);
 }
 
+/// Render the focused panel alone for zoom (`z`): the session table on the left,
+/// the model chart on the right; the cost tab zooms its spend chart.
+pub fn render_focused_panel(frame: &mut Frame, state: &AppState, area: Rect) {
+    if state.ui.token_dashboard_tab == TokenDashboardTab::Cost {
+        let period = state.ui.cost_period;
+        let history = summarize(&cost_records(state), period, &state.ui.time_format);
+        if history.by_period.is_empty() {
+            render_empty_state(frame, area);
+        } else {
+            render_period_chart(frame, area, &history, period);
+        }
+        return;
+    }
+    let data = aggregate(state);
+    if data.sessions.is_empty() {
+        render_empty_state(frame, area);
+        return;
+    }
+    match state.ui.focus {
+        PanelFocus::Left => render_session_table(frame, area, &data, state),
+        PanelFocus::Right => render_model_chart(frame, area, &data),
+    }
+}
+
 fn render_stats_row(frame: &mut Frame, area: Rect, data: &DashboardData) {
     let api_tokens = data.total_input + data.total_output;
     let stats = format!(
@@ -292,8 +272,12 @@ fn render_stats_row(frame: &mut Frame, area: Rect, data: &DashboardData) {
         format_cost_usd(data.total_cost_cents),
     );
 
+    render_title_row(frame, area, "Token Cost Dashboard", stats);
+}
+
+fn render_title_row(frame: &mut Frame, area: Rect, title: &str, stats: String) {
     let paragraph = Paragraph::new(Line::from(vec![
-        Span::styled("Token Cost Dashboard", Style::default().fg(Theme::ACCENT).add_modifier(Modifier::BOLD)),
+        Span::styled(title.to_string(), Style::default().fg(Theme::ACCENT).add_modifier(Modifier::BOLD)),
         Span::styled(
             format!("  {stats}"),
             Style::default().fg(Theme::MUTED_TEXT),
@@ -308,18 +292,25 @@ fn render_stats_row(frame: &mut Frame, area: Rect, data: &DashboardData) {
     frame.render_widget(paragraph, area);
 }
 
-fn render_session_table(frame: &mut Frame, area: Rect, data: &DashboardData, state: &AppState) {
-    let header = Row::new(vec!["Session", "Date", "Model", "Tokens", "Cache", "Cost"])
-        .style(
-            Style::default()
-                .fg(Theme::INFO)
-                .add_modifier(Modifier::BOLD),
-        );
+const SESSION_COLUMNS: [Column; 6] = [
+    Column::new("Session", 8, 12, 0),
+    Column::new("Date", 8, 12, 0),
+    Column::new("Model", 5, 8, 0),
+    Column::new("Tokens", 6, 8, 0).right(),
+    Column::new("Cache", 5, 8, 0).right(),
+    Column::new("Cost", 6, 9, 0).right(),
+];
+
+const BREAKDOWN_COLUMNS: [Column; 3] = [
+    Column::new("Name", 12, 60, 1),
+    Column::new("Cost", 6, 9, 0).right(),
+    Column::new("Share", 5, 6, 0).right(),
+];
 
-    let scroll = state.ui.scroll_offsets.token_dashboard_left;
+fn render_session_table(frame: &mut Frame, area: Rect, data: &DashboardData, state: &AppState) {
     let selected = state.ui.scroll_offsets.token_dashboard_left; // selection = scroll offset for this view
 
-    let rows: Vec<Row> = data
+    let rows: Vec<TableRow> = data
         .sessions
         .iter()
         .enumerate()
@@ -330,55 +321,33 @@ fn render_session_table(frame: &mut Frame, area: Rect, data: &DashboardData, sta
                 Style::default()
                     .bg(Theme::SELECTION_BG)
                     .fg(Theme::TEXT)
+                    .add_modifier(Modifier::BOLD)
             } else {
                 Style::default().fg(Theme::TEXT)
             };
 
-            Row::new(vec![
-                truncate_id(&s.id, 12),
-                s.date.format("%m-%d %H:%M").to_string(),
+            let cells = [
+                s.id.to_string(),
+                state.ui.time_format.short_date_time(s.date),
                 short_model(&s.model),
                 format_token_count(total),
                 format_token_count(s.cache_tokens),
                 format_cost_usd(s.estimated_cost_cents),
-            ])
-            .style(style)
+            ];
+            TableRow::new(cells.into_iter().map(Span::raw).collect(), style)
         })
         .collect();
 
-    let widths = [
-        Constraint::Length(12), // Session ID
-        Constraint::Length(12), // Date
-        Constraint::Length(8),  // Model
-        Constraint::Length(8),  // Tokens
-        Constraint::Length(8),  // Cache
-        Constraint::Length(8),  // Cost
-    ];
-
     let is_focused = matches!(state.ui.focus, crate::app::PanelFocus::Left);
-
-    let table = Table::new(rows, widths)
-        .header(header)
-        .block(
-            Block::default()
-                .title(" Per-Session ")
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(if is_focused {
-                    Theme::ACTIVE_BORDER
-                } else {
-                    Theme::PANEL_BORDER
-                })),
-        )
-        .row_highlight_style(
-            Style::default()
-                .bg(Theme::SELECTION_BG)
-                .add_modifier(Modifier::BOLD),
-        )
-        .highlight_symbol("▸ ");
-
-    // Skip rows for scrolling
-    let _ = scroll; // scroll offset used in row selection above
-    frame.render_widget(table, area);
+    let block = Block::default()
+        .title(" Per-Session ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if is_focused {
+            Theme::ACTIVE_BORDER
+        } else {
+            Theme::PANEL_BORDER
+        }));
+    render_table(frame, area, &SESSION_COLUMNS, rows, block, None, None);
 }
 
 fn render_model_chart(frame: &mut Frame, area: Rect, data: &DashboardData) {
@@ -430,6 +399,98 @@ fn render_model_chart(frame: &mut Frame, area: Rect, data: &DashboardData) {
     frame.render_widget(chart, area);
 }
 
+// ── Cost tab ──────────────────────────────────────────────────────────────
+
+fn render_cost_tab(frame: &mut Frame, state: &AppState, area: Rect) {
+    let records = cost_records(state);
+    let period = state.ui.cost_period;
+    let history = summarize(&records, period, &state.ui.time_format);
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),      // Stats row
+            Constraint::Percentage(55), // Spend per period
+            Constraint::Min(6),         // Breakdowns
+            Constraint::Length(1),      // Footer
+        ])
+        .split(area);
+
+    let sessions = state.domain.sessions.iter().filter(|s| s.data.is_some()).count();
+    let stats = format!(
+        " {} archived sessions │ {} est. │ per {}",
+        sessions,
+        format_cost_usd(history.total_cents),
+        period.label(),
+    );
+    render_title_row(frame, layout[0], "Cost History", stats);
+
+    if history.by_period.is_empty() {
+        render_empty_state(frame, layout[1].union(layout[2]));
+    } else {
+        render_period_chart(frame, layout[1], &history, period);
+        let breakdowns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(layout[2]);
+        render_breakdown(frame, breakdowns[0], " Per Project ", &history.by_project, history.total_cents);
+        render_breakdown(frame, breakdowns[1], " Per Agent Type ", &history.by_agent_type, history.total_cents);
+    }
+
+    render_footer(frame, layout[3], state);
+}
+
+fn render_period_chart(frame: &mut Frame, area: Rect, history: &CostHistory, period: CostPeriod) {
+    let bar_width: u16 = match period {
+        CostPeriod::Day => 6,
+        CostPeriod::Week => 9,
+    };
+    // Most recent periods that fit
+    let fits = (area.width.saturating_sub(2) / (bar_width + 1)).max(1) as usize;
+    let shown = &history.by_period[history.by_period.len().saturating_sub(fits)..];
+
+    let bars: Vec<Bar> = shown
+        .iter()
+        .map(|(start, cents)| {
+            Bar::default()
+                .label(Line::from(period.bucket_label(*start)))
+                .value(*cents)
+                .text_value(format_cost_usd(*cents))
+                .style(Style::default().fg(Theme::ACCENT))
+        })
+        .collect();
+
+    let chart = BarChart::default()
+        .block(
+            Block::default()
+                .title(format!(" Spend per {} ", period.label()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Theme::PANEL_BORDER)),
+        )
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(bar_width)
+        .bar_gap(1)
+        .direction(Direction::Vertical);
+
+    frame.render_widget(chart, area);
+}
+
+fn render_breakdown(frame: &mut Frame, area: Rect, title: &str, totals: &[(String, u64)], total_cents: u64) {
+    let rows: Vec<TableRow> = totals
+        .iter()
+        .map(|(name, cents)| {
+            let share = (*cents * 100).checked_div(total_cents).unwrap_or(0);
+            let cells = [name.clone(), format_cost_usd(*cents), format!("{}%", share)];
+            TableRow::new(cells.into_iter().map(Span::raw).collect(), Style::default().fg(Theme::TEXT))
+        })
+        .collect();
+    let block = Block::default()
+        .title(title.to_string())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::PANEL_BORDER));
+    render_table(frame, area, &BREAKDOWN_COLUMNS, rows, block, None, None);
+}
+
 fn render_empty_state(frame: &mut Frame, area: Rect) {
     let text = vec![
         Line::from(""),
@@ -460,15 +521,6 @@ fn render_empty_state(frame: &mut Frame, area: Rect) {
 
 // ── Helpers ────────────────────────────────────────────────────────────────
 
-fn truncate_id(id: &SessionId, max: usize) -> String {
-    let s = id.to_string();
-    if s.len() <= max {
-        s
-    } else {
-        format!("{}…", &s[..max - 1])
-    }
-}
-
 fn short_model(model: &str) -> String {
     let lower = model.to_lowercase();
     if lower.contains("opus") {
@@ -650,20 +702,6 @@ mod tests {
         assert_eq!(short_model("custom-model"), "custom-model");
     }
 
-    #[test]
-    fn truncate_id_short() {
-        let id = SessionId::new("abc");
-        assert_eq!(truncate_id(&id, 12), "abc");
-    }
-
-    #[test]
-    fn truncate_id_long() {
-        let id = SessionId::new("abcdefghijklmnop");
-        let result = truncate_id(&id, 12);
-        assert_eq!(result.chars().count(), 12);
-        assert!(result.ends_with('…'));
-    }
-
     #[test]
     fn render_does_not_panic_empty() {
         let backend = TestBackend::new(120, 30);
@@ -701,5 +739,11 @@ mod tests {
         terminal
             .draw(|frame| render_token_cost_dashboard(frame, &state, frame.area()))
             .unwrap();
+
+        state.ui.token_dashboard_tab = TokenDashboardTab::Cost;
+        state.ui.cost_period = CostPeriod::Week;
+        terminal
+            .draw(|frame| render_token_cost_dashboard(frame, &state, frame.area()))
+            .unwrap();
     }
 }
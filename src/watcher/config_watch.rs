@@ -0,0 +1,130 @@
+//! Project instruction and settings files, watched for edits mid-run.
+//!
+//! An edited `CLAUDE.md`, `.claude/settings.json` or agent definition explains
+//! a sudden change in how agents behave during a long orchestration, so every
+//! change after the first scan shows up in the event stream as a
+//! `ConfigChanged` event.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+
+use crate::app::agent_defs;
+use crate::model::{TranscriptEvent, TranscriptEventKind};
+
+/// Watched files in the project root.
+const ROOT_FILES: [&str; 2] = ["CLAUDE.md", "CLAUDE.local.md"];
+
+/// Watched files in the `.claude` dir (agent definitions come on top).
+const CLAUDE_DIR_FILES: [&str; 3] = ["CLAUDE.md", "settings.json", "settings.local.json"];
+
+/// Watched files that exist, with their modification times.
+pub type ConfigStamps = BTreeMap<PathBuf, SystemTime>;
+
+/// How a watched file changed between two scans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Created,
+    Edited,
+    Removed,
+}
+
+impl Change {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Created => "created",
+            Self::Edited => "edited",
+            Self::Removed => "removed",
+        }
+    }
+}
+
+/// The watched files under the project of `claude_dir`.
+/// I/O: stats the files and lists the agents directory.
+pub fn stamp(claude_dir: &Path) -> ConfigStamps {
+    let root = claude_dir.parent().unwrap_or(Path::new("."));
+    let files = ROOT_FILES.iter().map(|name| root.join(name)).chain(CLAUDE_DIR_FILES.iter().map(|name| claude_dir.join(name)));
+    let mut stamps: ConfigStamps = files
+        .filter_map(|path| {
+            let modified = path.metadata().ok().filter(|m| m.is_file())?.modified().ok()?;
+            Some((path, modified))
+        })
+        .collect();
+    stamps.extend(agent_defs::stamp(claude_dir));
+    stamps
+}
+
+/// Pure function: files created, edited or removed from `old` to `new`, by path.
+pub fn changes(old: &ConfigStamps, new: &ConfigStamps) -> Vec<(PathBuf, Change)> {
+    let mut changed: Vec<(PathBuf, Change)> = new
+        .iter()
+        .filter_map(|(path, modified)| match old.get(path) {
+            None => Some((path.clone(), Change::Created)),
+            Some(before) if before != modified => Some((path.clone(), Change::Edited)),
+            Some(_) => None,
+        })
+        .chain(old.keys().filter(|path| !new.contains_key(*path)).map(|path| (path.clone(), Change::Removed)))
+        .collect();
+    changed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    changed
+}
+
+/// Pure function: the event for one change, the file named relative to the
+/// project root of `claude_dir`.
+pub fn event(claude_dir: &Path, path: &Path, change: Change, at: DateTime<Utc>) -> TranscriptEvent {
+    let root = claude_dir.parent().unwrap_or(Path::new("."));
+    let file = path.strip_prefix(root).unwrap_or(path).display().to_string();
+    TranscriptEvent::new(at, TranscriptEventKind::ConfigChanged { file, change: change.label().to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn changes_report_created_edited_and_removed_files() {
+        let t0 = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+        let t1 = t0 + Duration::from_secs(5);
+        let old: ConfigStamps = [("/p/CLAUDE.md", t0), ("/p/.claude/settings.json", t0), ("/p/.claude/agents/a.md", t0)]
+            .into_iter()
+            .map(|(p, t)| (PathBuf::from(p), t))
+            .collect();
+        let mut new = old.clone();
+        new.insert(PathBuf::from("/p/CLAUDE.md"), t1);
+        new.remove(Path::new("/p/.claude/agents/a.md"));
+        new.insert(PathBuf::from("/p/.claude/settings.local.json"), t1);
+
+        assert_eq!(
+            changes(&old, &new),
+            vec![
+                (PathBuf::from("/p/.claude/agents/a.md"), Change::Removed),
+                (PathBuf::from("/p/.claude/settings.local.json"), Change::Created),
+                (PathBuf::from("/p/CLAUDE.md"), Change::Edited),
+            ]
+        );
+        assert!(changes(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn stamp_finds_instructions_settings_and_agent_definitions() {
+        let dir = tempfile::tempdir().unwrap();
+        let claude_dir = dir.path().join(".claude");
+        std::fs::create_dir_all(claude_dir.join("agents")).unwrap();
+        std::fs::write(dir.path().join("CLAUDE.md"), "# rules").unwrap();
+        std::fs::write(claude_dir.join("settings.json"), "{}").unwrap();
+        std::fs::write(claude_dir.join("agents/reviewer.md"), "---\nname: reviewer\n---\n").unwrap();
+        std::fs::write(claude_dir.join("notes.txt"), "ignored").unwrap();
+
+        let stamps = stamp(&claude_dir);
+        assert_eq!(stamps.len(), 3);
+
+        let event = event(&claude_dir, &claude_dir.join("settings.json"), Change::Edited, Utc::now());
+        assert_eq!(
+            event.kind,
+            TranscriptEventKind::ConfigChanged { file: ".claude/settings.json".into(), change: "edited".into() }
+        );
+    }
+}
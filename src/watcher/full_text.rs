@@ -0,0 +1,75 @@
+//! Untruncated text of clipped events, read back from their transcripts.
+//!
+//! Events keep at most `TruncationLimits` chars of text in memory. One that
+//! was clipped points at its transcript line (`SourceRef`); expanding its step
+//! reads that one line again and takes the whole block from it.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+
+use serde_json::Value;
+
+use super::parsers::block_full_text;
+use crate::model::SourceRef;
+
+/// Whole text of the block `at` points to.
+///
+/// # Imperative Shell
+/// Reads one line of the transcript.
+///
+/// # Returns
+/// `Ok(None)` when the line there no longer holds such a block (the file was
+/// rewritten since).
+pub fn read_full_text(at: &SourceRef) -> io::Result<Option<String>> {
+    let mut file = File::open(&at.path)?;
+    file.seek(SeekFrom::Start(at.offset))?;
+    let mut line = Vec::new();
+    BufReader::new(file).read_until(b'\n', &mut line)?;
+    let Ok(entry) = serde_json::from_slice::<Value>(&line) else {
+        return Ok(None);
+    };
+    Ok(block_full_text(&entry, at.block))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TranscriptEventKind;
+    use crate::watcher::schema_drift::SchemaDrift;
+    use crate::watcher::{parse_transcript_events_checked, ChunkPosition, TruncationLimits};
+
+    #[test]
+    fn clipped_events_read_back_their_whole_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.jsonl");
+        let long = "x".repeat(50);
+        let lines = [
+            r#"{"type":"user","timestamp":"2026-03-18T10:00:00Z","message":{"content":"hi"}}"#.to_string(),
+            format!(
+                r#"{{"type":"assistant","timestamp":"2026-03-18T10:00:01Z","message":{{"content":[{{"type":"text","text":"short"}},{{"type":"text","text":"{long}"}},{{"type":"tool_use","id":"t1","name":"Bash","input":{{"command":"{long}"}}}}]}}}}"#
+            ),
+        ];
+        let content = format!("{}\n{}\n", lines[0], lines[1]);
+        std::fs::write(&path, &content).unwrap();
+
+        let limits = TruncationLimits { prompt: 10, tool_input: 20, content: 10 };
+        let (events, _) = parse_transcript_events_checked(
+            &content,
+            "s1",
+            &ChunkPosition::file(&path, 0, 1),
+            &limits,
+            &mut SchemaDrift::default(),
+        );
+        assert_eq!(events.len(), 4);
+        assert!(events[0].full_text_at.is_none() && events[1].full_text_at.is_none());
+        assert_eq!(events[2].kind, TranscriptEventKind::AssistantMessage { content: format!("{}...", &long[..10]) });
+
+        let at = events[2].full_text_at.clone().unwrap();
+        assert_eq!((at.offset, at.block), (lines[0].len() as u64 + 1, 1));
+        assert_eq!(read_full_text(&at).unwrap(), Some(long.clone()));
+        assert_eq!(read_full_text(events[3].full_text_at.as_ref().unwrap()).unwrap(), Some(long));
+
+        std::fs::write(&path, "{}\n").unwrap();
+        assert_eq!(read_full_text(&at).unwrap(), None);
+    }
+}
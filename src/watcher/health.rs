@@ -0,0 +1,206 @@
+//! Watcher thread health: heartbeats into a shared map, checked by `update` on Tick.
+//!
+//! Each background poller registers a `Heartbeat` and beats once per loop.
+//! Dropping the heartbeat (normal exit or panic unwind) marks the thread exited.
+//! Re-registering a name supersedes the previous thread, whose `beat` then
+//! returns false so it stops instead of running alongside its replacement.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use chrono::{DateTime, Utc};
+
+/// Name of the transcript polling thread.
+pub const POLLER: &str = "poller";
+
+/// Name of the task graph polling thread.
+pub const TASK_GRAPH_POLLER: &str = "task-graph";
+
+/// Name of the archive dir polling thread.
+pub const ARCHIVE_POLLER: &str = "archive";
+
+/// Name of the extra log polling thread.
+pub const LOG_POLLER: &str = "logs";
+
+/// A thread that has not beaten for this long is considered stalled.
+pub const HEARTBEAT_TIMEOUT: chrono::Duration = chrono::Duration::seconds(10);
+
+/// Last known state of one registered thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThreadStatus {
+    /// Incremented each time the name is (re)registered
+    pub generation: u64,
+    pub last_beat: DateTime<Utc>,
+    pub exited: bool,
+}
+
+/// Why a thread is unhealthy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Degradation {
+    /// Thread returned or panicked
+    Exited,
+    /// Thread is alive but has not beaten within `HEARTBEAT_TIMEOUT`
+    Stalled,
+}
+
+/// Shared map of thread name → status. Cloning shares the map.
+#[derive(Debug, Clone, Default)]
+pub struct HealthMap {
+    inner: Arc<Mutex<BTreeMap<&'static str, ThreadStatus>>>,
+}
+
+impl HealthMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register (or re-register) a thread, superseding any previous holder of `name`.
+    pub fn register(&self, name: &'static str) -> Heartbeat {
+        let mut map = self.lock();
+        let generation = map.get(name).map_or(0, |s| s.generation + 1);
+        map.insert(name, ThreadStatus { generation, last_beat: Utc::now(), exited: false });
+        Heartbeat { map: self.clone(), name, generation }
+    }
+
+    /// Copy of the current statuses.
+    pub fn snapshot(&self) -> BTreeMap<&'static str, ThreadStatus> {
+        self.lock().clone()
+    }
+
+    /// The whole process was stopped (SIGTSTP) and has just been continued: restart
+    /// every live thread's timeout so the pause doesn't read as a stall.
+    pub fn resume(&self) {
+        let now = Utc::now();
+        for status in self.lock().values_mut().filter(|s| !s.exited) {
+            status.last_beat = now;
+        }
+    }
+
+    /// A thread that panicked while holding the lock must not take the watchdog down with it.
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<&'static str, ThreadStatus>> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Handle held by a running thread. Marks the thread exited when dropped.
+#[derive(Debug)]
+pub struct Heartbeat {
+    map: HealthMap,
+    name: &'static str,
+    generation: u64,
+}
+
+impl Heartbeat {
+    /// Record liveness. Returns false if this thread has been superseded and should stop.
+    pub fn beat(&self) -> bool {
+        let mut map = self.map.lock();
+        match map.get_mut(self.name) {
+            Some(status) if status.generation == self.generation => {
+                status.last_beat = Utc::now();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether this thread is still the registered holder of its name.
+    pub fn is_current(&self) -> bool {
+        self.map.lock().get(self.name).is_some_and(|s| s.generation == self.generation)
+    }
+}
+
+impl Drop for Heartbeat {
+    fn drop(&mut self) {
+        let mut map = self.map.lock();
+        if let Some(status) = map.get_mut(self.name).filter(|s| s.generation == self.generation) {
+            status.exited = true;
+        }
+    }
+}
+
+/// Pure function: unhealthy threads in `snapshot` as of `now`.
+pub fn degraded_threads(
+    snapshot: &BTreeMap<&'static str, ThreadStatus>,
+    now: DateTime<Utc>,
+) -> Vec<(&'static str, Degradation)> {
+    snapshot
+        .iter()
+        .filter_map(|(&name, status)| {
+            if status.exited {
+                Some((name, Degradation::Exited))
+            } else if now - status.last_beat >= HEARTBEAT_TIMEOUT {
+                Some((name, Degradation::Stalled))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn beating_thread_is_healthy() {
+        let health = HealthMap::new();
+        let heartbeat = health.register(POLLER);
+        assert!(heartbeat.beat());
+        assert!(degraded_threads(&health.snapshot(), Utc::now()).is_empty());
+    }
+
+    #[test]
+    fn resume_clears_stall_but_not_exit() {
+        let health = HealthMap::new();
+        let _heartbeat = health.register(POLLER);
+        drop(health.register("other"));
+        {
+            let mut map = health.lock();
+            map.get_mut(POLLER).unwrap().last_beat -= HEARTBEAT_TIMEOUT;
+        }
+        health.resume();
+        assert_eq!(degraded_threads(&health.snapshot(), Utc::now()), vec![("other", Degradation::Exited)]);
+    }
+
+    #[test]
+    fn silent_thread_is_stalled() {
+        let health = HealthMap::new();
+        let _heartbeat = health.register(POLLER);
+        let later = Utc::now() + HEARTBEAT_TIMEOUT;
+        assert_eq!(degraded_threads(&health.snapshot(), later), vec![(POLLER, Degradation::Stalled)]);
+    }
+
+    #[test]
+    fn dropped_heartbeat_marks_exited() {
+        let health = HealthMap::new();
+        let heartbeat = health.register(POLLER);
+        drop(heartbeat);
+        assert_eq!(degraded_threads(&health.snapshot(), Utc::now()), vec![(POLLER, Degradation::Exited)]);
+    }
+
+    #[test]
+    fn panicking_thread_marks_exited() {
+        let health = HealthMap::new();
+        let shared = health.clone();
+        let result = std::thread::spawn(move || {
+            let _heartbeat = shared.register(POLLER);
+            panic!("poller crashed");
+        })
+        .join();
+        assert!(result.is_err());
+        assert_eq!(degraded_threads(&health.snapshot(), Utc::now()), vec![(POLLER, Degradation::Exited)]);
+    }
+
+    #[test]
+    fn reregistering_supersedes_old_thread() {
+        let health = HealthMap::new();
+        let old = health.register(POLLER);
+        let new = health.register(POLLER);
+        assert!(!old.beat());
+        assert!(!old.is_current());
+        // Old thread exiting must not flag its replacement
+        drop(old);
+        assert!(new.beat());
+        assert!(degraded_threads(&health.snapshot(), Utc::now()).is_empty());
+    }
+}
@@ -0,0 +1,163 @@
+//! Extra log files tailed into the event stream, one `ExternalLog` event per new line.
+//! Config and tailing rules: `docs/configuration.md`, "Extra logs".
+
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::health::Heartbeat;
+use super::{truncate_str, POLL_INTERVAL};
+use crate::event::AppEvent;
+use crate::model::{EventOrigin, TranscriptEvent, TranscriptEventKind};
+
+/// Longest line kept; the rest is cut.
+pub const MAX_LINE_CHARS: usize = 2000;
+
+/// Most bytes read from one file per poll, so a burst is spread over polls.
+const MAX_READ_BYTES: u64 = 256 * 1024;
+
+/// One extra log from the config.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtraLog {
+    /// Shown on each line and used as its channel name
+    pub name: String,
+    pub path: PathBuf,
+    /// Color of its lines in the event stream (see `app::severity` for names)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+impl ExtraLog {
+    /// Pure function: this log with a relative path resolved against `root`.
+    pub fn resolved(&self, root: &Path) -> Self {
+        Self { path: root.join(&self.path), ..self.clone() }
+    }
+}
+
+/// Read position in one log and the partial line read so far.
+#[derive(Debug, Clone, Default)]
+struct Tail {
+    offset: Option<u64>,
+    partial: String,
+}
+
+impl Tail {
+    /// Read what was appended since the last call and return its complete lines.
+    /// The first call only records the end of the file.
+    /// I/O: stats and reads `path`.
+    fn poll(&mut self, path: &Path) -> Vec<String> {
+        let Ok(len) = path.metadata().map(|m| m.len()) else {
+            return Vec::new();
+        };
+        let offset = match self.offset {
+            None => {
+                self.offset = Some(len);
+                return Vec::new();
+            }
+            // Truncated or replaced: start over
+            Some(offset) if len < offset => {
+                self.partial.clear();
+                0
+            }
+            Some(offset) => offset,
+        };
+        if len == offset {
+            return Vec::new();
+        }
+        let Ok(mut file) = std::fs::File::open(path) else {
+            return Vec::new();
+        };
+        let mut bytes = Vec::new();
+        let read = file
+            .seek(SeekFrom::Start(offset))
+            .and_then(|_| file.take(MAX_READ_BYTES).read_to_end(&mut bytes));
+        let Ok(read) = read else {
+            return Vec::new();
+        };
+        self.offset = Some(offset + read as u64);
+        self.partial.push_str(&String::from_utf8_lossy(&bytes));
+        split_complete_lines(&mut self.partial)
+    }
+}
+
+/// Pure function: take the complete lines off the front of `buffer`, leaving
+/// an unfinished last line in it. Blank lines are dropped; long ones are cut.
+fn split_complete_lines(buffer: &mut String) -> Vec<String> {
+    let Some(end) = buffer.rfind('\n') else {
+        return Vec::new();
+    };
+    let rest = buffer.split_off(end + 1);
+    let complete = std::mem::replace(buffer, rest);
+    complete
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| truncate_str(line, MAX_LINE_CHARS))
+        .collect()
+}
+
+/// Pure function: the event for one line of `log`.
+pub fn log_event(log: &str, line: String) -> TranscriptEvent {
+    let mut event =
+        TranscriptEvent::new(Utc::now(), TranscriptEventKind::ExternalLog { source: log.to_string(), line });
+    event.origin = EventOrigin::ExternalLog;
+    event
+}
+
+/// Poll the extra logs' tails, sending each new line as an event.
+pub(super) fn log_loop(logs: Vec<ExtraLog>, heartbeat: Heartbeat, tx: mpsc::Sender<AppEvent>) {
+    let mut tails = vec![Tail::default(); logs.len()];
+    loop {
+        for (log, tail) in logs.iter().zip(tails.iter_mut()) {
+            for line in tail.poll(&log.path) {
+                if tx.send(AppEvent::TranscriptEventReceived(log_event(&log.name, line))).is_err() {
+                    return;
+                }
+            }
+        }
+        std::thread::sleep(POLL_INTERVAL);
+        if !heartbeat.beat() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn tail_reads_appended_lines_and_restarts_after_truncation() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("dev.log");
+        std::fs::write(&path, "old line\n").unwrap();
+        let mut tail = Tail::default();
+        assert!(tail.poll(&path).is_empty(), "backlog is skipped");
+
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "GET / 200\n\nGET /api").unwrap();
+        assert_eq!(tail.poll(&path), ["GET / 200"]);
+        writeln!(file, " 500").unwrap();
+        assert_eq!(tail.poll(&path), ["GET /api 500"]);
+
+        std::fs::write(&path, "restarted\n").unwrap();
+        assert_eq!(tail.poll(&path), ["restarted"]);
+    }
+
+    #[test]
+    fn long_lines_are_cut_and_carriage_returns_dropped() {
+        let mut buffer = format!("{}\r\nok\r\npartial", "x".repeat(MAX_LINE_CHARS + 10));
+        let lines = split_complete_lines(&mut buffer);
+        assert_eq!(lines[0].chars().count(), MAX_LINE_CHARS + 3);
+        assert_eq!(lines[1], "ok");
+        assert_eq!(buffer, "partial");
+        assert_eq!(log_event("tests", "ok".into()).origin, EventOrigin::ExternalLog);
+
+        let log = ExtraLog { name: "tests".into(), path: "target/test.log".into(), color: None };
+        assert_eq!(log.resolved(Path::new("/work/app")).path, Path::new("/work/app/target/test.log"));
+    }
+}
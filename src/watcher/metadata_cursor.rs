@@ -0,0 +1,123 @@
+//! Incremental metadata parsing of growing transcripts.
+//!
+//! Session and agent metadata (model, token usage, skills, prompt) used to be
+//! parsed from the whole file on every metadata tick, which for a 100MB
+//! transcript means reading and parsing 100MB every ~2s. Each file now keeps a
+//! `MetadataParser` and the byte offset it has parsed up to; a tick reads the
+//! lines appended since, `CHUNK_BYTES` at a time. A file that shrank was
+//! rewritten and is parsed again from the start.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use super::parsers::{MetadataParser, TranscriptMetadata, TruncationLimits};
+
+/// Read buffer size: how much of a file is pulled in at a time.
+const CHUNK_BYTES: usize = 1024 * 1024;
+
+/// A file's parser and how far into the file it has got.
+#[derive(Debug, Clone)]
+struct Cursor {
+    offset: u64,
+    parser: MetadataParser,
+}
+
+/// Metadata parse state per transcript file.
+#[derive(Debug, Clone, Default)]
+pub struct MetadataCursors {
+    files: HashMap<PathBuf, Cursor>,
+    limits: TruncationLimits,
+}
+
+impl MetadataCursors {
+    pub fn new(limits: TruncationLimits) -> Self {
+        Self { files: HashMap::new(), limits }
+    }
+
+    /// Parse the lines appended to `path` since the last call and return the
+    /// metadata of the whole file so far.
+    ///
+    /// # Imperative Shell
+    /// Reads the file from the stored offset. An unterminated last line is
+    /// left for the next call, when its write has finished.
+    pub fn read(&mut self, path: &Path) -> io::Result<TranscriptMetadata> {
+        let mut file = File::open(path)?;
+        let len = file.metadata()?.len();
+        let limits = self.limits;
+        let fresh = || Cursor { offset: 0, parser: MetadataParser::new(limits) };
+        let cursor = self.files.entry(path.to_path_buf()).or_insert_with(fresh);
+        if len < cursor.offset {
+            *cursor = fresh();
+        }
+
+        file.seek(SeekFrom::Start(cursor.offset))?;
+        let mut reader = BufReader::with_capacity(CHUNK_BYTES, file);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if read == 0 || line.last() != Some(&b'\n') {
+                break;
+            }
+            cursor.parser.feed_line(&String::from_utf8_lossy(&line));
+            cursor.offset += read as u64;
+        }
+        Ok(cursor.parser.metadata())
+    }
+
+    /// Drop the state of a file that is no longer watched.
+    pub fn forget(&mut self, path: &Path) {
+        self.files.remove(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn assistant(id: &str, output_tokens: u64) -> String {
+        format!(
+            r#"{{"type":"assistant","message":{{"id":"{id}","model":"claude-sonnet-4-5-20250929","usage":{{"input_tokens":10,"output_tokens":{output_tokens}}}}}}}"#
+        )
+    }
+
+    #[test]
+    fn appended_lines_extend_the_parsed_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.jsonl");
+        let mut file = File::create(&path).unwrap();
+        writeln!(file, r#"{{"type":"user","message":{{"content":"Fix the login bug"}}}}"#).unwrap();
+        writeln!(file, "{}", assistant("m1", 5)).unwrap();
+
+        let mut cursors = MetadataCursors::default();
+        let first = cursors.read(&path).unwrap();
+        assert_eq!(first.task_description.as_deref(), Some("Fix the login bug"));
+        assert_eq!(first.cumulative_usage.output_tokens, 5);
+
+        // A streamed update of m1, a new message, and a line still being written
+        write!(file, "{}\n{}\n{}", assistant("m1", 8), assistant("m2", 20), &assistant("m3", 1)[..20]).unwrap();
+        let second = cursors.read(&path).unwrap();
+        assert_eq!(second.cumulative_usage.output_tokens, 28);
+        assert_eq!(second.token_usage.output_tokens, 20);
+        assert_eq!(second.task_description.as_deref(), Some("Fix the login bug"));
+
+        let full = std::fs::read_to_string(&path).unwrap();
+        let whole = super::super::parsers::parse_transcript_metadata(&full);
+        assert_eq!(second.model, whole.model);
+    }
+
+    #[test]
+    fn a_rewritten_file_is_parsed_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.jsonl");
+        std::fs::write(&path, format!("{}\n{}\n", assistant("m1", 5), assistant("m2", 7))).unwrap();
+        let mut cursors = MetadataCursors::default();
+        assert_eq!(cursors.read(&path).unwrap().cumulative_usage.output_tokens, 12);
+
+        std::fs::write(&path, format!("{}\n", assistant("m9", 3))).unwrap();
+        assert_eq!(cursors.read(&path).unwrap().cumulative_usage.output_tokens, 3);
+    }
+}
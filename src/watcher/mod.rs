@@ -1,18 +1,33 @@
+mod config_watch;
+pub mod full_text;
+pub mod health;
+pub mod logs;
+mod metadata_cursor;
 mod parsers;
+pub mod schema_drift;
+pub mod source;
 mod tail;
 
 pub use parsers::*;
 pub use tail::TailState;
 
+use crate::app::{agent_defs, spec_docs};
+use crate::app::tool_outputs::{self, ToolOutput, ToolOutputIndex};
 use crate::error::WatcherError;
+use health::{HealthMap, Heartbeat};
+use schema_drift::SchemaDrift;
+use logs::ExtraLog;
+use metadata_cursor::MetadataCursors;
+use source::{ArchiveSource, EventSource, LogTailSource, SourceHandle, TaskGraphSource, TranscriptSource};
 use crate::event::AppEvent;
 use crate::model::ids::SessionId;
+use crate::model::EventOrigin;
 use crate::paths::Paths;
 use crate::session;
 use std::collections::BTreeMap;
-use std::path::PathBuf;
-use std::sync::mpsc;
-use std::time::{Duration, SystemTime};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime};
 
 /// Result type for watcher operations
 pub type WatcherResult<T> = Result<T, WatcherError>;
@@ -34,6 +49,9 @@ const POST_RESULT_TIMEOUT: Duration = Duration::from_secs(5);
 /// Agents are marked finished after this idle time without new transcript content.
 const AGENT_IDLE_TIMEOUT: Duration = Duration::from_secs(15);
 
+/// Every polling thread sleeps this long between passes.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
 /// How often we re-scan the transcript directory for new .jsonl files (session discovery).
 /// 10 × 200ms = ~2 seconds, satisfying NFR-001.
 const DIR_RESCAN_INTERVAL: u32 = 10;
@@ -42,6 +60,14 @@ const DIR_RESCAN_INTERVAL: u32 = 10;
 /// 10 × 200ms = ~2 seconds.
 const METADATA_EMIT_INTERVAL: u32 = 10;
 
+/// How often we fingerprint the archive dir for archives added/removed by other
+/// loom-tui instances or sync tools. 5 × 200ms = ~1 second.
+const ARCHIVE_RESCAN_INTERVAL: u32 = 5;
+
+/// The archive dir must be unchanged this long before the session list is re-indexed,
+/// so a burst of writes (sync, bulk delete) triggers a single reload.
+const ARCHIVE_DEBOUNCE: Duration = Duration::from_secs(1);
+
 // ---------------------------------------------------------------------------
 // Internal state per known transcript file
 // ---------------------------------------------------------------------------
@@ -57,50 +83,134 @@ struct FileState {
     session_id: String,
 }
 
+/// Archive files by path with (mtime, size); any difference means the dir changed.
+type ArchiveFingerprint = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+/// Debounced change detection for the archive directory.
+#[derive(Debug, Default)]
+struct ArchiveDirWatch {
+    fingerprint: ArchiveFingerprint,
+    /// When the last unreported change was seen
+    changed_at: Option<Instant>,
+}
+
+impl ArchiveDirWatch {
+    /// Record a fresh fingerprint. Returns true once the dir has settled after a change.
+    fn observe(&mut self, fingerprint: ArchiveFingerprint, now: Instant) -> bool {
+        if fingerprint != self.fingerprint {
+            self.fingerprint = fingerprint;
+            self.changed_at = Some(now);
+            return false;
+        }
+        match self.changed_at {
+            Some(changed) if now.duration_since(changed) >= ARCHIVE_DEBOUNCE => {
+                self.changed_at = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Existence tracking for a watched directory, so deletion (e.g. a cleanup job)
+/// and reappearance are reported instead of the watch silently going quiet.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum DirPresence {
+    /// Not seen yet (a dir that never existed is not "lost")
+    #[default]
+    Unseen,
+    Present,
+    Lost,
+}
+
+/// A change in a watched directory's existence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DirTransition {
+    Lost,
+    Restored,
+}
+
+impl DirPresence {
+    /// Advance with the dir's current existence. Returns the transition to report, if any.
+    fn observe(&mut self, exists: bool) -> Option<DirTransition> {
+        let (next, transition) = match (*self, exists) {
+            (Self::Present, false) => (Self::Lost, Some(DirTransition::Lost)),
+            (Self::Lost, true) => (Self::Present, Some(DirTransition::Restored)),
+            (Self::Lost, false) | (Self::Unseen, false) => (*self, None),
+            (_, true) => (Self::Present, None),
+        };
+        *self = next;
+        transition
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Public API
 // ---------------------------------------------------------------------------
 
-/// Start unified polling loop. Returns channel receiver for AppEvents.
+/// Start the file event sources. Returns channel receiver for AppEvents.
 ///
-/// The loop runs every 200ms and:
-/// 1. (Re)scans transcript_dir for new .jsonl files  -> SessionDiscovered
-/// 2. Checks mtime on known files                     -> SessionCompleted / SessionReactivated
-/// 3. Tails transcript files via TailState            -> TranscriptEventReceived
-/// 4. Scans {session_id}/subagents/ dirs              -> agent discovery + AgentMetadataUpdated
-/// 5. Polls task_graph file mtime                     -> TaskGraphUpdated
+/// Each source polls every 200ms on its own thread (see `source`):
+/// - `ArchiveSource`: fingerprints archive_dir (debounced)  -> SessionMetasLoaded
+/// - `TranscriptSource`: (re)scans transcript_dir for .jsonl -> SessionDiscovered;
+///   checks mtime on known files  -> SessionCompleted / SessionReactivated;
+///   tails transcript files via TailState -> TranscriptEventReceived;
+///   scans {session_id}/subagents/ dirs -> agent discovery + AgentMetadataUpdated
+/// - `TaskGraphSource`: polls task graph file mtime -> TaskGraphUpdated
+/// - `LogTailSource` (with extra logs configured): tails them -> TranscriptEventReceived
 ///
 /// # FR-018 / FR-032 / SC-002
 /// No notify crate, no events.jsonl watcher, no /tmp/loom-tui references.
 pub fn start_watching(paths: &Paths) -> WatcherResult<mpsc::Receiver<AppEvent>> {
-    let (tx, rx) = mpsc::channel();
-
-    // Load archived session metas immediately on startup (lightweight)
-    load_archived_session_metas(&paths.archive_dir, &tx);
+    start_supervised(paths, &[], TruncationLimits::default()).map(|(_, rx)| rx)
+}
 
-    let transcript_dir = paths.transcript_dir.clone();
-    let task_graph_path = paths.task_graph.clone();
+/// Like `start_watching`, but also returns a `SourceHandle` that can restart the
+/// polling threads when their heartbeats (see `health`) report them exited or stalled.
+/// `logs` are tailed too (see `logs`); transcript text is clipped to `limits`.
+pub fn start_supervised(
+    paths: &Paths,
+    logs: &[ExtraLog],
+    limits: TruncationLimits,
+) -> WatcherResult<(SourceHandle, mpsc::Receiver<AppEvent>)> {
+    let (tx, rx) = mpsc::channel();
+    let health = HealthMap::new();
+
+    // Archives first, so the session list is indexed before transcripts replay
+    let sources: [Box<dyn EventSource>; 3] = [
+        Box::new(ArchiveSource::new(paths.archive_dir.clone(), health.clone())),
+        Box::new(TranscriptSource::new(paths.transcript_dir.clone(), health.clone(), limits)),
+        Box::new(TaskGraphSource::new(paths.task_graph.clone(), health.clone())),
+    ];
+    let mut handles: Vec<SourceHandle> = sources.iter().map(|source| source.spawn(tx.clone())).collect();
+    if !logs.is_empty() {
+        handles.push(LogTailSource::new(logs.to_vec(), health.clone()).spawn(tx));
+    }
 
-    std::thread::spawn(move || {
-        polling_loop(transcript_dir, task_graph_path, tx);
-    });
+    Ok((SourceHandle::join(health, handles), rx))
+}
 
-    Ok(rx)
+/// Per-thread supervision state passed into `polling_loop`.
+struct PollerHandles {
+    heartbeat: Heartbeat,
+    /// Starting offsets (from the last checkpoint)
+    tail_state: TailState,
+    checkpoint: Arc<Mutex<TailState>>,
+    limits: TruncationLimits,
 }
 
 // ---------------------------------------------------------------------------
 // Polling loop (imperative shell — all I/O lives here)
 // ---------------------------------------------------------------------------
 
-fn polling_loop(
-    transcript_dir: PathBuf,
-    task_graph_path: PathBuf,
-    tx: mpsc::Sender<AppEvent>,
-) {
-    let mut tail_state = TailState::new();
+/// Transcript polling: session discovery, tailing, agent and session lifecycle.
+fn polling_loop(transcript_dir: PathBuf, handles: PollerHandles, tx: mpsc::Sender<AppEvent>) {
+    let PollerHandles { heartbeat, mut tail_state, checkpoint, limits } = handles;
 
     // key: absolute path to .jsonl file
     let mut known_files: BTreeMap<PathBuf, FileState> = BTreeMap::new();
+    // Metadata parsed so far per file, resumed on each metadata tick
+    let mut metadata_cursors = MetadataCursors::new(limits);
 
     // key: session_id (string), value: whether session is confirmed + last_mtime
     let mut session_confirmed: BTreeMap<String, (bool, SystemTime)> = BTreeMap::new();
@@ -113,12 +223,16 @@ fn polling_loop(
     // Sessions that have seen a "result" entry → use shorter timeout
     let mut session_has_result: std::collections::HashSet<String> = std::collections::HashSet::new();
 
-    let mut task_graph_mtime: Option<SystemTime> = None;
+    let mut transcript_dir_presence = DirPresence::default();
     let mut scan_counter: u32 = 0;
     let mut replay_complete_sent = false;
 
     loop {
-        std::thread::sleep(Duration::from_millis(200));
+        std::thread::sleep(POLL_INTERVAL);
+        // Superseded by a restart: let the replacement take over
+        if !heartbeat.beat() {
+            return;
+        }
         scan_counter = scan_counter.wrapping_add(1);
 
         let do_dir_rescan = scan_counter % DIR_RESCAN_INTERVAL == 1;
@@ -128,6 +242,9 @@ fn polling_loop(
         // 1. Scan transcript directory for new .jsonl files
         // ----------------------------------------------------------------
         if do_dir_rescan {
+            // Transcript dir belongs to Claude Code: wait for it to come back
+            check_watched_dir(&transcript_dir, &mut transcript_dir_presence, false, &tx);
+
             scan_transcript_dir(
                 &transcript_dir,
                 &mut known_files,
@@ -154,9 +271,12 @@ fn polling_loop(
             let current_mtime = match path.metadata().and_then(|m| m.modified()) {
                 Ok(m) => m,
                 Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-                    // File deleted — clean up state to stop polling it
+                    // File deleted — clean up state to stop polling it. A file later
+                    // recreated at the same path is read from the start.
                     known_files.remove(&path);
                     session_confirmed.remove(&session_id);
+                    tail_state.reset(&path);
+                    metadata_cursors.forget(&path);
                     continue;
                 }
                 Err(e) => {
@@ -179,12 +299,12 @@ fn polling_loop(
                     *prev_mtime = current_mtime;
 
                     // Reactivate if previously completed
-                    if completed_sessions.remove(&session_id) {
-                        if tx.send(AppEvent::SessionReactivated {
+                    if completed_sessions.remove(&session_id)
+                        && tx.send(AppEvent::SessionReactivated {
                             session_id: SessionId::new(&session_id),
-                        }).is_err() {
-                            return;
-                        }
+                        }).is_err()
+                    {
+                        return;
                     }
                     let _ = confirmed; // borrowed; confirm state updated by update.rs on UserMessage
                 }
@@ -205,7 +325,25 @@ fn polling_loop(
             };
 
             if !new_content.is_empty() {
-                let events = parsers::parse_transcript_events(&new_content, &session_id);
+                // Line number and byte offset of the chunk's first line, for
+                // diagnostics and reading clipped text back
+                let first_line = tail_state.lines_read(&path) + 1 - new_content.matches('\n').count();
+                let offset = tail_state.get_offset(&path) - new_content.len() as u64;
+                let chunk = ChunkPosition::file(&path, offset, first_line);
+                let mut drift = SchemaDrift::default();
+                let (events, diagnostics) =
+                    parsers::parse_transcript_events_checked(&new_content, &session_id, &chunk, &limits, &mut drift);
+                for diagnostic in diagnostics {
+                    if tx.send(AppEvent::Error {
+                        source: path.display().to_string(),
+                        error: WatcherError::Parse(diagnostic).into(),
+                    }).is_err() {
+                        return;
+                    }
+                }
+                if !drift.is_empty() && tx.send(AppEvent::SchemaDriftObserved(drift)).is_err() {
+                    return;
+                }
 
                 // FR-010/FR-012: mark session confirmed if any UserMessage seen
                 let has_user_message = events
@@ -227,12 +365,12 @@ fn polling_loop(
                     // If result seen, immediately mark agent finished
                     if has_result {
                         let aid = extract_agent_id(&path);
-                        if finished_agents.insert(aid.clone()) {
-                            if tx.send(AppEvent::AgentFinished {
+                        if finished_agents.insert(aid.clone())
+                            && tx.send(AppEvent::AgentFinished {
                                 agent_id: crate::model::AgentId::new(&aid),
-                            }).is_err() {
-                                return;
-                            }
+                            }).is_err()
+                        {
+                            return;
                         }
                     }
                 } else if has_result {
@@ -242,6 +380,11 @@ fn polling_loop(
 
                 for mut event in events {
                     // Mark whether this is a subagent event
+                    event.origin = if is_subagent {
+                        EventOrigin::SubagentTranscript
+                    } else {
+                        EventOrigin::MainTranscript
+                    };
                     if is_subagent {
                         let agent_id = extract_agent_id(&path);
                         if event.agent_id.is_none() {
@@ -278,12 +421,12 @@ fn polling_loop(
 
             // Emit metadata for subagent files on the rescan tick (FR-014)
             if is_subagent && do_metadata_emit {
-                emit_agent_metadata(&path, &tx);
+                emit_agent_metadata(&path, &mut metadata_cursors, &tx);
             }
 
             // Emit session-level metadata from main transcript
             if !is_subagent && do_metadata_emit {
-                emit_session_metadata(&path, &session_id, &tx);
+                emit_session_metadata(&path, &session_id, &mut metadata_cursors, &tx);
             }
         }
 
@@ -320,16 +463,7 @@ fn polling_loop(
         }
 
         // ----------------------------------------------------------------
-        // 5. Poll task graph by mtime (FR-033)
-        // ----------------------------------------------------------------
-        let new_mtime = task_graph_path.metadata().and_then(|m| m.modified()).ok();
-        if new_mtime.is_some() && new_mtime != task_graph_mtime {
-            task_graph_mtime = new_mtime;
-            handle_task_graph_update(&task_graph_path, &tx);
-        }
-
-        // ----------------------------------------------------------------
-        // 6. Signal replay complete AFTER first full scan+tail cycle
+        // 5. Signal replay complete AFTER first full scan+tail cycle
         // ----------------------------------------------------------------
         // This must come AFTER all initial events are queued so the Tick
         // handler in update.rs doesn't expire sessions based on stale
@@ -340,6 +474,118 @@ fn polling_loop(
                 return;
             }
         }
+
+        // ----------------------------------------------------------------
+        // 6. Checkpoint tail offsets for a restarted poller to resume from
+        // ----------------------------------------------------------------
+        if heartbeat.is_current() {
+            *checkpoint.lock().unwrap_or_else(PoisonError::into_inner) = tail_state.clone();
+        }
+    }
+}
+
+/// Poll the task graph file by mtime (FR-033), index the tool outputs
+/// captured next to it (see `app::tool_outputs`), follow the spec / plan
+/// in use (see `app::spec_docs`), reload the agent definitions when they
+/// change (see `app::agent_defs`) and report edits to the project's
+/// instructions and settings (see `config_watch`).
+fn task_graph_loop(task_graph_path: PathBuf, heartbeat: Heartbeat, tx: mpsc::Sender<AppEvent>) {
+    let mut task_graph_mtime: Option<SystemTime> = None;
+    let output_dir = tool_outputs::output_dir(&task_graph_path);
+    let claude_dir = spec_docs::claude_dir(&task_graph_path);
+    let mut outputs = ToolOutputIndex::default();
+    let mut docs: Vec<String> = Vec::new();
+    let mut doc_stamp: Option<(PathBuf, SystemTime)> = None;
+    let mut defs_stamp: Option<Vec<(PathBuf, SystemTime)>> = None;
+    let mut config_stamp: Option<config_watch::ConfigStamps> = None;
+    let mut scan_counter: u32 = 0;
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        if !heartbeat.beat() {
+            return;
+        }
+        let new_mtime = task_graph_path.metadata().and_then(|m| m.modified()).ok();
+        let graph_changed = new_mtime.is_some() && new_mtime != task_graph_mtime;
+        if graph_changed {
+            task_graph_mtime = new_mtime;
+            if let Some(named) = handle_task_graph_update(&task_graph_path, &tx) {
+                docs = named;
+            }
+        }
+
+        scan_counter = scan_counter.wrapping_add(1);
+        if scan_counter % DIR_RESCAN_INTERVAL == 1 {
+            let scanned = index_tool_outputs(&output_dir);
+            if scanned != outputs {
+                outputs = scanned.clone();
+                let _ = tx.send(AppEvent::ToolOutputsIndexed(scanned));
+            }
+        }
+        if graph_changed || scan_counter % DIR_RESCAN_INTERVAL == 1 {
+            let active = spec_docs::find_active(&claude_dir, &docs);
+            if active != doc_stamp {
+                let doc = active.as_ref().and_then(|(path, _)| spec_docs::load(&claude_dir, path).ok());
+                doc_stamp = active;
+                let _ = tx.send(AppEvent::SpecDocLoaded(doc));
+            }
+        }
+        if scan_counter % DIR_RESCAN_INTERVAL == 1 {
+            let files = agent_defs::stamp(&claude_dir);
+            if defs_stamp.as_ref() != Some(&files) {
+                let _ = tx.send(AppEvent::AgentDefinitionsLoaded(agent_defs::load(&files)));
+                defs_stamp = Some(files);
+            }
+
+            // The first scan is the baseline: only later changes happened mid-run
+            let stamps = config_watch::stamp(&claude_dir);
+            if let Some(before) = &config_stamp {
+                for (path, change) in config_watch::changes(before, &stamps) {
+                    let event = config_watch::event(&claude_dir, &path, change, chrono::Utc::now());
+                    let _ = tx.send(AppEvent::TranscriptEventReceived(event));
+                }
+            }
+            config_stamp = Some(stamps);
+        }
+    }
+}
+
+/// Index captured tool outputs by file stem (the tool call id). A missing
+/// directory (no hook capturing outputs) yields an empty index.
+fn index_tool_outputs(dir: &Path) -> ToolOutputIndex {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return ToolOutputIndex::default();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            let path = entry.path();
+            let id = path.file_stem()?.to_str()?.to_string();
+            Some((id, ToolOutput { path, bytes: metadata.len() }))
+        })
+        .collect()
+}
+
+/// Re-index archives once the archive dir settles after a change, recreating
+/// the dir if it is deleted (it is ours, so saves must keep working).
+fn archive_loop(archive_dir: PathBuf, mut archive_watch: ArchiveDirWatch, heartbeat: Heartbeat, tx: mpsc::Sender<AppEvent>) {
+    let mut archive_dir_presence = DirPresence::default();
+    let mut scan_counter: u32 = 0;
+    loop {
+        std::thread::sleep(POLL_INTERVAL);
+        if !heartbeat.beat() {
+            return;
+        }
+        scan_counter = scan_counter.wrapping_add(1);
+
+        if scan_counter % DIR_RESCAN_INTERVAL == 1 {
+            check_watched_dir(&archive_dir, &mut archive_dir_presence, true, &tx);
+        }
+        if scan_counter % ARCHIVE_RESCAN_INTERVAL == 1
+            && archive_watch.observe(archive_dir_fingerprint(&archive_dir), Instant::now())
+        {
+            load_archived_session_metas(&archive_dir, &tx);
+        }
     }
 }
 
@@ -347,6 +593,33 @@ fn polling_loop(
 // Directory scanning (FR-001, FR-002, FR-014)
 // ---------------------------------------------------------------------------
 
+/// Report (and optionally recreate) a watched directory that was deleted while running.
+/// Emits `AppEvent::Info` on each transition so the user knows the watch recovered.
+fn check_watched_dir(dir: &Path, presence: &mut DirPresence, recreate: bool, tx: &mpsc::Sender<AppEvent>) {
+    let message = match presence.observe(dir.is_dir()) {
+        None => return,
+        Some(DirTransition::Lost) if recreate => match std::fs::create_dir_all(dir) {
+            Ok(()) => {
+                presence.observe(true);
+                "was removed; recreated it"
+            }
+            Err(e) => {
+                let _ = tx.send(AppEvent::Error {
+                    source: dir.display().to_string(),
+                    error: WatcherError::Io(e.to_string()).into(),
+                });
+                return;
+            }
+        },
+        Some(DirTransition::Lost) => "was removed; waiting for it to reappear",
+        Some(DirTransition::Restored) => "is back; watching again",
+    };
+    let _ = tx.send(AppEvent::Info {
+        source: dir.display().to_string(),
+        message: message.to_string(),
+    });
+}
+
 /// Scan transcript_dir for top-level .jsonl files and per-session subagent dirs.
 /// Emits SessionDiscovered for newly found sessions.
 fn scan_transcript_dir(
@@ -485,14 +758,16 @@ fn scan_subagents_dir(
 // Helper: emit session-level metadata from main transcript
 // ---------------------------------------------------------------------------
 
-fn emit_session_metadata(path: &PathBuf, session_id: &str, tx: &mpsc::Sender<AppEvent>) {
-    let full_content = match std::fs::read_to_string(path) {
-        Ok(c) => c,
+fn emit_session_metadata(path: &Path, session_id: &str, cursors: &mut MetadataCursors, tx: &mpsc::Sender<AppEvent>) {
+    let metadata = match cursors.read(path) {
+        Ok(metadata) => metadata,
         Err(_) => return,
     };
-
-    let metadata = parsers::parse_transcript_metadata(&full_content);
-    if metadata.model.is_none() && metadata.cumulative_usage.is_empty() {
+    let goal = metadata
+        .task_description
+        .as_deref()
+        .and_then(parsers::goal_from_prompt);
+    if metadata.model.is_none() && metadata.cumulative_usage.is_empty() && goal.is_none() {
         return;
     }
 
@@ -500,16 +775,17 @@ fn emit_session_metadata(path: &PathBuf, session_id: &str, tx: &mpsc::Sender<App
         session_id: SessionId::new(session_id),
         model: metadata.model,
         token_usage: metadata.cumulative_usage,
+        goal,
     });
 }
 
 // ---------------------------------------------------------------------------
-// Helper: emit agent metadata from full file content
+// Helper: emit agent metadata, parsed incrementally
 // ---------------------------------------------------------------------------
 
-fn emit_agent_metadata(path: &PathBuf, tx: &mpsc::Sender<AppEvent>) {
-    let full_content = match std::fs::read_to_string(path) {
-        Ok(c) => c,
+fn emit_agent_metadata(path: &Path, cursors: &mut MetadataCursors, tx: &mpsc::Sender<AppEvent>) {
+    let metadata = match cursors.read(path) {
+        Ok(metadata) => metadata,
         Err(e) => {
             let _ = tx.send(AppEvent::Error {
                 source: path.display().to_string(),
@@ -519,8 +795,12 @@ fn emit_agent_metadata(path: &PathBuf, tx: &mpsc::Sender<AppEvent>) {
         }
     };
 
-    let metadata = parsers::parse_transcript_metadata(&full_content);
-    if metadata.model.is_none() && metadata.token_usage.is_empty() && metadata.skills.is_empty() && metadata.task_description.is_none() {
+    if metadata.model.is_none()
+        && metadata.token_usage.is_empty()
+        && metadata.skills.is_empty()
+        && metadata.task_description.is_none()
+        && metadata.agent_type.is_none()
+    {
         return;
     }
 
@@ -537,17 +817,21 @@ fn emit_agent_metadata(path: &PathBuf, tx: &mpsc::Sender<AppEvent>) {
 // Helper: read + parse task graph
 // ---------------------------------------------------------------------------
 
-fn handle_task_graph_update(path: &PathBuf, tx: &mpsc::Sender<AppEvent>) {
+/// Returns the spec / plan files the graph names, when it parsed.
+fn handle_task_graph_update(path: &PathBuf, tx: &mpsc::Sender<AppEvent>) -> Option<Vec<String>> {
     match std::fs::read_to_string(path) {
         Ok(content) => match parsers::parse_task_graph(&content) {
             Ok(graph) => {
+                let docs = graph.docs.clone();
                 let _ = tx.send(AppEvent::TaskGraphUpdated(graph));
+                Some(docs)
             }
             Err(e) => {
                 let _ = tx.send(AppEvent::Error {
                     source: path.display().to_string(),
                     error: WatcherError::Parse(e).into(),
                 });
+                None
             }
         },
         Err(e) => {
@@ -555,26 +839,65 @@ fn handle_task_graph_update(path: &PathBuf, tx: &mpsc::Sender<AppEvent>) {
                 source: path.display().to_string(),
                 error: WatcherError::Io(e.to_string()).into(),
             });
+            None
         }
     }
 }
 
 // ---------------------------------------------------------------------------
-// Startup: load archived session metas
+// Archive index: load archived session metas (startup + on archive dir changes)
 // ---------------------------------------------------------------------------
 
-fn load_archived_session_metas(archive_dir: &PathBuf, tx: &mpsc::Sender<AppEvent>) {
-    match session::list_session_metas(archive_dir) {
-        Ok((metas, errors)) => {
+/// Fingerprint archives in the archive root and its per-project subdirectories
+/// (dot-directories such as `.trash` are not archives).
+fn archive_dir_fingerprint(archive_dir: &Path) -> ArchiveFingerprint {
+    let Ok(entries) = std::fs::read_dir(archive_dir) else {
+        return ArchiveFingerprint::new();
+    };
+    let mut fingerprint = ArchiveFingerprint::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let hidden = entry.file_name().to_str().is_some_and(|n| n.starts_with('.'));
+        if path.is_dir() && !hidden {
+            if let Ok(sub) = std::fs::read_dir(&path) {
+                fingerprint.extend(sub.flatten().filter_map(|e| archive_stamp_entry(&e)));
+            }
+        } else if let Some((path, stamp)) = archive_stamp_entry(&entry) {
+            fingerprint.insert(path, stamp);
+        }
+    }
+    fingerprint
+}
+
+fn archive_stamp_entry(entry: &std::fs::DirEntry) -> Option<(PathBuf, (SystemTime, u64))> {
+    let path = entry.path();
+    if path.extension().and_then(|s| s.to_str()) != Some("json") {
+        return None;
+    }
+    let metadata = entry.metadata().ok()?;
+    Some((path, (metadata.modified().ok()?, metadata.len())))
+}
+
+/// List the archives and read the first page of metas; the rest are paged in
+/// on demand (see `app::archive_pages`).
+fn load_archived_session_metas(archive_dir: &Path, tx: &mpsc::Sender<AppEvent>) {
+    match session::list_archive_paths(archive_dir) {
+        Ok((files, mut errors)) => {
+            let first_page = &files[..files.len().min(session::META_PAGE_SIZE)];
+            let (mut metas, read_errors) = session::load_session_metas_streamed(first_page, |partial| {
+                let _ = tx.send(AppEvent::SessionMetasLoadedPartial(partial));
+            });
+            metas.sort_by_key(|(_, meta)| std::cmp::Reverse(meta.timestamp));
+            errors.extend(read_errors);
             for error in errors {
                 let _ = tx.send(AppEvent::Error {
                     source: "sessions".to_string(),
                     error: error.into(),
                 });
             }
-            if !metas.is_empty() {
-                let _ = tx.send(AppEvent::SessionMetasLoaded(metas));
-            }
+            let _ = tx.send(AppEvent::ArchiveFilesListed(files));
+            // Sent even when empty so a re-index drops archives removed from disk
+            let _ = tx.send(AppEvent::SessionMetasLoaded(metas));
         }
         Err(e) => {
             let _ = tx.send(AppEvent::Error {
@@ -804,6 +1127,7 @@ mod tests {
     // -----------------------------------------------------------------------
 
     #[test]
+    #[allow(clippy::for_kv_map)]
     fn scan_subagents_discovers_agent_files() {
         let temp = TempDir::new().unwrap();
         let subagents_dir = temp.path().join("subagents");
@@ -927,6 +1251,39 @@ mod tests {
         assert!(received, "TranscriptEventReceived not emitted within 3s");
     }
 
+    #[test]
+    fn restarted_poller_resumes_without_replaying_events() {
+        let temp = TempDir::new().unwrap();
+        let session_path = temp.path().join("sess-restart.jsonl");
+        let line = r#"{"type":"human","timestamp":"2026-03-18T10:00:00Z","message":{"content":[{"type":"text","text":"hello"}]}}"#;
+        fs::write(&session_path, format!("{}\n", line)).unwrap();
+
+        let paths = crate::paths::Paths {
+            task_graph: temp.path().join("task_graph.json"),
+            transcript_dir: temp.path().to_path_buf(),
+            archive_dir: temp.path().join("archives"),
+        };
+        let (watcher, rx) = start_supervised(&paths, &[], TruncationLimits::default()).expect("start_supervised");
+
+        let count_user_messages = |window: Duration| {
+            let deadline = std::time::Instant::now() + window;
+            let mut count = 0;
+            while std::time::Instant::now() < deadline {
+                if let Ok(AppEvent::TranscriptEventReceived(evt)) = rx.recv_timeout(Duration::from_millis(100)) {
+                    if matches!(evt.kind, crate::model::TranscriptEventKind::UserMessage) {
+                        count += 1;
+                    }
+                }
+            }
+            count
+        };
+        assert_eq!(count_user_messages(Duration::from_secs(2)), 1);
+
+        watcher.restart();
+        assert_eq!(count_user_messages(Duration::from_secs(2)), 0, "restart must not replay tailed content");
+        assert!(health::degraded_threads(&watcher.health().snapshot(), chrono::Utc::now()).is_empty());
+    }
+
     #[test]
     fn polling_emits_task_graph_updated_on_file_creation() {
         let temp = TempDir::new().unwrap();
@@ -1292,6 +1649,143 @@ mod tests {
         }
     }
 
+    // -----------------------------------------------------------------------
+    // Unit: archive dir re-indexing
+    // -----------------------------------------------------------------------
+
+    #[test]
+    fn dir_presence_reports_loss_and_restore_only_after_seen() {
+        let mut presence = DirPresence::default();
+        assert_eq!(presence.observe(false), None, "never-seen dir is not lost");
+        assert_eq!(presence.observe(true), None);
+        assert_eq!(presence.observe(true), None);
+        assert_eq!(presence.observe(false), Some(DirTransition::Lost));
+        assert_eq!(presence.observe(false), None);
+        assert_eq!(presence.observe(true), Some(DirTransition::Restored));
+    }
+
+    #[test]
+    fn check_watched_dir_recreates_owned_dir() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("archives");
+        fs::create_dir(&dir).unwrap();
+        let (tx, rx) = mpsc::channel();
+        let mut presence = DirPresence::default();
+
+        check_watched_dir(&dir, &mut presence, true, &tx);
+        assert!(rx.try_recv().is_err());
+
+        fs::remove_dir(&dir).unwrap();
+        check_watched_dir(&dir, &mut presence, true, &tx);
+        assert!(dir.is_dir());
+        match rx.try_recv() {
+            Ok(AppEvent::Info { message, .. }) => assert!(message.contains("recreated")),
+            other => panic!("expected Info, got {other:?}"),
+        }
+        assert_eq!(presence, DirPresence::Present);
+    }
+
+    #[test]
+    fn check_watched_dir_waits_for_foreign_dir() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join("transcripts");
+        fs::create_dir(&dir).unwrap();
+        let (tx, rx) = mpsc::channel();
+        let mut presence = DirPresence::default();
+        check_watched_dir(&dir, &mut presence, false, &tx);
+
+        fs::remove_dir(&dir).unwrap();
+        check_watched_dir(&dir, &mut presence, false, &tx);
+        assert!(!dir.exists(), "transcript dir is not ours to recreate");
+        assert!(matches!(rx.try_recv(), Ok(AppEvent::Info { message, .. }) if message.contains("waiting")));
+
+        fs::create_dir(&dir).unwrap();
+        check_watched_dir(&dir, &mut presence, false, &tx);
+        assert!(matches!(rx.try_recv(), Ok(AppEvent::Info { message, .. }) if message.contains("back")));
+    }
+
+    #[test]
+    fn polling_reads_recreated_transcript_from_start() {
+        let temp = TempDir::new().unwrap();
+        let session_path = temp.path().join("sess-gone.jsonl");
+        let line = r#"{"type":"human","timestamp":"2026-03-18T10:00:00Z","message":{"content":[{"type":"text","text":"hello"}]}}"#;
+        fs::write(&session_path, format!("{}\n", line)).unwrap();
+
+        let paths = crate::paths::Paths {
+            task_graph: temp.path().join("task_graph.json"),
+            transcript_dir: temp.path().to_path_buf(),
+            archive_dir: temp.path().join("archives"),
+        };
+        let rx = start_watching(&paths).expect("start_watching");
+
+        let wait_user_message = |window: Duration| {
+            let deadline = std::time::Instant::now() + window;
+            while std::time::Instant::now() < deadline {
+                if let Ok(AppEvent::TranscriptEventReceived(evt)) = rx.recv_timeout(Duration::from_millis(100)) {
+                    if matches!(evt.kind, crate::model::TranscriptEventKind::UserMessage) {
+                        return true;
+                    }
+                }
+            }
+            false
+        };
+        assert!(wait_user_message(Duration::from_secs(2)));
+
+        // Cleanup removes the file; it is recreated with the same length, so a
+        // stale offset would skip its content entirely
+        fs::remove_file(&session_path).unwrap();
+        std::thread::sleep(Duration::from_millis(600));
+        fs::write(&session_path, format!("{}\n", line)).unwrap();
+        assert!(wait_user_message(Duration::from_secs(4)), "recreated file must be tailed from offset 0");
+    }
+
+    #[test]
+    fn archive_dir_watch_reports_once_after_debounce() {
+        let mut watch = ArchiveDirWatch::default();
+        let start = Instant::now();
+        let mut changed = ArchiveFingerprint::new();
+        changed.insert(PathBuf::from("/a/s1.json"), (SystemTime::UNIX_EPOCH, 10));
+
+        // Change seen, but not settled yet
+        assert!(!watch.observe(changed.clone(), start));
+        assert!(!watch.observe(changed.clone(), start + ARCHIVE_DEBOUNCE / 2));
+        // Quiet for the debounce window -> one reload
+        assert!(watch.observe(changed.clone(), start + ARCHIVE_DEBOUNCE));
+        assert!(!watch.observe(changed, start + ARCHIVE_DEBOUNCE * 3));
+    }
+
+    #[test]
+    fn archive_dir_watch_restarts_debounce_on_further_changes() {
+        let mut watch = ArchiveDirWatch::default();
+        let start = Instant::now();
+        let mut fp = ArchiveFingerprint::new();
+        fp.insert(PathBuf::from("/a/s1.json"), (SystemTime::UNIX_EPOCH, 10));
+        assert!(!watch.observe(fp.clone(), start));
+
+        fp.insert(PathBuf::from("/a/s2.json"), (SystemTime::UNIX_EPOCH, 20));
+        assert!(!watch.observe(fp.clone(), start + ARCHIVE_DEBOUNCE));
+        assert!(watch.observe(fp, start + ARCHIVE_DEBOUNCE * 2));
+    }
+
+    #[test]
+    fn archive_dir_fingerprint_tracks_only_json_archives() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("s1.json"), "{}").unwrap();
+        fs::write(temp.path().join(".deleted"), "s0\n").unwrap();
+
+        let project = temp.path().join("-home-u-app");
+        fs::create_dir_all(project.join(".trash")).unwrap();
+        fs::write(project.join("s2.json"), "{}").unwrap();
+        fs::write(project.join(".trash").join("1-s3.json"), "{}").unwrap();
+
+        let fp = archive_dir_fingerprint(temp.path());
+        assert_eq!(fp.len(), 2);
+        assert!(fp.contains_key(&temp.path().join("s1.json")));
+        assert!(fp.contains_key(&project.join("s2.json")));
+
+        assert!(archive_dir_fingerprint(&temp.path().join("missing")).is_empty());
+    }
+
     // -----------------------------------------------------------------------
     // Unit: content_has_result
     // -----------------------------------------------------------------------
@@ -1,11 +1,14 @@
 use crate::error::ParseError;
-use crate::model::{AgentMessage, Task, TaskGraph, TokenUsage, Wave};
-use crate::model::ids::{AgentId, ToolName};
-use crate::model::transcript_event::{TranscriptEvent, TranscriptEventKind};
+use crate::model::{AgentMessage, Phase, Task, TaskGraph, TokenUsage, Wave};
+use crate::model::ids::{AgentId, TaskId, ToolName};
+use crate::model::transcript_event::{ApiErrorKind, SourceRef, TranscriptEvent, TranscriptEventKind};
+use super::schema_drift::SchemaDrift;
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
 
 /// Safely truncate a string to a maximum character count (not bytes).
 /// Prevents panics from slicing on multibyte UTF-8 character boundaries.
@@ -18,6 +21,67 @@ pub(crate) fn truncate_str(s: &str, max_chars: usize) -> String {
     }
 }
 
+/// `truncate_str`, noting in `clipped` whether anything was cut.
+fn clip(s: &str, max_chars: usize, clipped: &mut bool) -> String {
+    *clipped |= s.chars().nth(max_chars).is_some();
+    truncate_str(s, max_chars)
+}
+
+/// Longest `Task` tool input summary, in chars, whatever the tool input limit.
+const TASK_SUMMARY_CHARS: usize = 2000;
+
+/// How much of a transcript's text is kept in memory, in chars. Clipped
+/// events remember where their entry is (`TranscriptEvent::full_text_at`),
+/// so the whole text can be read back from the file when a step is expanded.
+/// Set in the config file as `"truncation": {"content": 64000}`; missing keys
+/// keep their defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TruncationLimits {
+    /// Task prompt (a transcript's first user message)
+    pub prompt: usize,
+    /// Tool input summaries
+    pub tool_input: usize,
+    /// Assistant text and tool results
+    pub content: usize,
+}
+
+impl Default for TruncationLimits {
+    fn default() -> Self {
+        Self { prompt: 4000, tool_input: 8000, content: 16_000 }
+    }
+}
+
+impl TruncationLimits {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Where a chunk of transcript text was read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkPosition {
+    /// The transcript file; stream input has none, so its clipped events
+    /// keep only what was read
+    pub path: Option<Arc<Path>>,
+    /// Byte offset of the chunk in the file
+    pub offset: u64,
+    /// File line number of the chunk's first line
+    pub first_line: usize,
+}
+
+impl ChunkPosition {
+    /// Chunk starting at `first_line` of input with no file behind it.
+    pub fn stream(first_line: usize) -> Self {
+        Self { path: None, offset: 0, first_line }
+    }
+
+    /// Chunk read from `path` at byte `offset`, line `first_line`.
+    pub fn file(path: &Path, offset: u64, first_line: usize) -> Self {
+        Self { path: Some(Arc::from(path)), offset, first_line }
+    }
+}
+
 /// Parse task graph JSON file into TaskGraph model.
 /// Supports both native TUI format and loom orchestration format.
 ///
@@ -33,6 +97,14 @@ pub fn parse_task_graph(content: &str) -> Result<TaskGraph, ParseError> {
 #[derive(Deserialize)]
 struct LoomFormat {
     tasks: Vec<LoomTask>,
+    #[serde(default)]
+    current_phase: Option<String>,
+    #[serde(default)]
+    plan_file: Option<String>,
+    #[serde(default)]
+    spec_file: Option<String>,
+    #[serde(default, alias = "plan_id")]
+    run_id: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -51,6 +123,8 @@ struct LoomTask {
     files_modified: Vec<String>,
     #[serde(default)]
     tests_passed: Option<bool>,
+    #[serde(default)]
+    depends_on: Vec<String>,
 }
 
 fn default_wave() -> u32 {
@@ -62,7 +136,11 @@ fn parse_loom_format(content: &str) -> Result<TaskGraph, ParseError> {
         serde_json::from_str(content).map_err(|e| ParseError::Json(e.to_string()))?;
 
     let mut wave_map: BTreeMap<u32, Vec<Task>> = BTreeMap::new();
+    let mut dependencies: BTreeMap<TaskId, Vec<TaskId>> = BTreeMap::new();
     for lt in loom.tasks {
+        if !lt.depends_on.is_empty() {
+            dependencies.insert(lt.id.as_str().into(), lt.depends_on.into_iter().map(Into::into).collect());
+        }
         let task = Task {
             id: lt.id.into(),
             description: lt.description,
@@ -80,7 +158,9 @@ fn parse_loom_format(content: &str) -> Result<TaskGraph, ParseError> {
         .map(|(num, tasks)| Wave::new(num, tasks))
         .collect();
 
-    Ok(TaskGraph::new(waves))
+    let phase = loom.current_phase.as_deref().and_then(Phase::parse);
+    let docs = loom.plan_file.into_iter().chain(loom.spec_file).collect();
+    Ok(TaskGraph::new(waves).with_phase(phase).with_docs(docs).with_run_id(loom.run_id).with_dependencies(dependencies))
 }
 
 /// Parse agent transcript JSONL file into vector of messages.
@@ -127,18 +207,52 @@ pub fn parse_transcript(content: &str) -> Result<Vec<AgentMessage>, ParseError>
 /// - `type: "user"` with tool_result content blocks -> ToolResult per block
 /// - `type: "assistant"` with text content blocks -> AssistantMessage per block
 /// - `type: "assistant"` with tool_use content blocks -> ToolUse per block
+/// - `type: "assistant"` with `isApiErrorMessage`, or `type: "system"` with
+///   `subtype: "api_error"` -> ApiError
 /// - Other entry types -> silently skipped (FR-007, NFR-006)
 ///
 /// Malformed JSONL lines are skipped without propagating errors (NFR-005).
 /// `agentId` field is extracted for agent attribution (FR-008).
 /// `session_id` is propagated to all events (FR-008).
 pub fn parse_transcript_events(content: &str, session_id: &str) -> Vec<TranscriptEvent> {
+    parse_transcript_events_checked(
+        content,
+        session_id,
+        &ChunkPosition::stream(1),
+        &TruncationLimits::default(),
+        &mut SchemaDrift::default(),
+    )
+    .0
+}
+
+/// `parse_transcript_events` plus schema diagnostics for lines that were
+/// skipped or only partly understood (`ParseError::Schema`); fields no part of
+/// loom-tui knows are counted into `drift`.
+/// `chunk` says where `content` starts in its file; text longer than `limits`
+/// is clipped, and clipped events of a file point back at their line.
+///
+/// # Functional Core
+/// Pure function.
+pub fn parse_transcript_events_checked(
+    content: &str,
+    session_id: &str,
+    chunk: &ChunkPosition,
+    limits: &TruncationLimits,
+    drift: &mut SchemaDrift,
+) -> (Vec<TranscriptEvent>, Vec<ParseError>) {
     // First pass: build tool_use_id -> tool_name map for ToolResult correlation
     let tool_id_map = build_tool_id_map(content);
 
     let mut events = Vec::new();
-
-    for line in content.lines() {
+    let mut diagnostics = Vec::new();
+
+    let mut line_offset = chunk.offset;
+    for (line_no, line) in (chunk.first_line..).zip(content.split_inclusive('\n')) {
+        let offset = line_offset;
+        line_offset += line.len() as u64;
+        let source_ref = |block: usize| {
+            chunk.path.as_ref().map(|path| SourceRef { path: Arc::clone(path), offset, block })
+        };
         let trimmed = line.trim();
         if trimmed.is_empty() {
             continue;
@@ -147,8 +261,13 @@ pub fn parse_transcript_events(content: &str, session_id: &str) -> Vec<Transcrip
         // NFR-005: skip malformed lines without dropping rest of batch
         let entry: Value = match serde_json::from_str(trimmed) {
             Ok(v) => v,
-            Err(_) => continue,
+            Err(_) => {
+                diagnostics.push(schema_error("entry", line_no, "is not valid JSON", "line skipped"));
+                continue;
+            }
         };
+        diagnostics.extend(validate_transcript_entry(&entry, line_no));
+        drift.record_entry(&entry);
 
         let entry_type = entry.get("type").and_then(|v| v.as_str()).unwrap_or("");
         let timestamp = parse_timestamp(&entry);
@@ -157,8 +276,19 @@ pub fn parse_transcript_events(content: &str, session_id: &str) -> Vec<Transcrip
             .and_then(|v| v.as_str())
             .filter(|s| !s.is_empty())
             .map(AgentId::new);
+        let cwd = entry.get("cwd").and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+        let first_event = events.len();
+
+        if let Some((status, message)) = api_error(&entry) {
+            let mut clipped = false;
+            let message = clip(&message, limits.content, &mut clipped);
+            let error_kind = ApiErrorKind::classify(status, &message);
+            let kind = TranscriptEventKind::ApiError { error_kind, message, status };
+            events.push(build_event(timestamp, kind, session_id, agent_id.clone()));
+        }
 
         match entry_type {
+            "assistant" if is_api_error_message(&entry) => {}
             "human" | "user" => {
                 let content_val = entry.get("message").and_then(|m| m.get("content"));
                 match content_val {
@@ -169,7 +299,7 @@ pub fn parse_transcript_events(content: &str, session_id: &str) -> Vec<Transcrip
                             .any(|b| b.get("type").and_then(|v| v.as_str()) == Some("tool_result"));
 
                         if has_tool_results {
-                            for block in blocks {
+                            for (idx, block) in blocks.iter().enumerate() {
                                 if block.get("type").and_then(|v| v.as_str()) != Some("tool_result") {
                                     continue;
                                 }
@@ -181,17 +311,22 @@ pub fn parse_transcript_events(content: &str, session_id: &str) -> Vec<Transcrip
                                     .get(tool_use_id)
                                     .cloned()
                                     .unwrap_or_else(|| "unknown".to_string());
-                                let result_summary = extract_tool_result_summary(block);
-                                let event = build_event(
+                                let mut clipped = false;
+                                let result_summary = clip(&tool_result_text(block), limits.content, &mut clipped);
+                                let is_error = block.get("is_error").and_then(|v| v.as_bool()).unwrap_or(false);
+                                let mut event = build_event(
                                     timestamp,
                                     TranscriptEventKind::ToolResult {
                                         tool_name: ToolName::new(&tool_name),
                                         result_summary,
                                         duration_ms: None,
+                                        is_error,
                                     },
                                     session_id,
                                     agent_id.clone(),
                                 );
+                                event.tool_use_id = Some(tool_use_id.to_string()).filter(|id| !id.is_empty());
+                                event.full_text_at = source_ref(idx).filter(|_| clipped);
                                 events.push(event);
                             }
                         } else {
@@ -233,21 +368,23 @@ pub fn parse_transcript_events(content: &str, session_id: &str) -> Vec<Transcrip
                     _ => continue,
                 };
 
-                for block in content_blocks {
+                for (idx, block) in content_blocks.iter().enumerate() {
                     let block_type = block.get("type").and_then(|v| v.as_str()).unwrap_or("");
+                    let mut clipped = false;
                     match block_type {
                         "text" => {
                             let text = match block.get("text").and_then(|v| v.as_str()) {
                                 Some(t) if !t.trim().is_empty() => t,
                                 _ => continue,
                             };
-                            let content = truncate_str(text, 16_000);
-                            let event = build_event(
+                            let content = clip(text, limits.content, &mut clipped);
+                            let mut event = build_event(
                                 timestamp,
                                 TranscriptEventKind::AssistantMessage { content },
                                 session_id,
                                 agent_id.clone(),
                             );
+                            event.full_text_at = source_ref(idx).filter(|_| clipped);
                             events.push(event);
                         }
                         "tool_use" => {
@@ -258,8 +395,12 @@ pub fn parse_transcript_events(content: &str, session_id: &str) -> Vec<Transcrip
                                 .unwrap_or("unknown")
                                 .to_string();
                             let input = block.get("input").cloned().unwrap_or(Value::Null);
-                            let input_summary = extract_tool_input_summary(&tool_name, &input);
-                            let event = build_event(
+                            let max_chars = match tool_name.as_str() {
+                                "Task" => limits.tool_input.min(TASK_SUMMARY_CHARS),
+                                _ => limits.tool_input,
+                            };
+                            let input_summary = clip(&tool_input_text(&tool_name, &input), max_chars, &mut clipped);
+                            let mut event = build_event(
                                 timestamp,
                                 TranscriptEventKind::ToolUse {
                                     tool_name: ToolName::new(&tool_name),
@@ -268,6 +409,9 @@ pub fn parse_transcript_events(content: &str, session_id: &str) -> Vec<Transcrip
                                 session_id,
                                 agent_id.clone(),
                             );
+                            event.tool_use_id =
+                                block.get("id").and_then(|v| v.as_str()).filter(|s| !s.is_empty()).map(str::to_string);
+                            event.full_text_at = source_ref(idx).filter(|_| clipped);
                             events.push(event);
                         }
                         // "thinking" and others -> silently skip (NFR-006)
@@ -278,9 +422,133 @@ pub fn parse_transcript_events(content: &str, session_id: &str) -> Vec<Transcrip
             // NFR-006: unknown entry types silently skipped
             _ => {}
         }
+
+        if let Some(cwd) = cwd {
+            for event in &mut events[first_event..] {
+                event.cwd = Some(cwd.to_string());
+            }
+        }
     }
 
-    events
+    (events, diagnostics)
+}
+
+/// Required fields per entry kind. Unknown entry types are not checked (NFR-006).
+///
+/// # Functional Core
+/// Pure function.
+pub fn validate_transcript_entry(entry: &Value, line: usize) -> Vec<ParseError> {
+    let Some(entry_type) = entry.get("type").and_then(|v| v.as_str()) else {
+        return vec![schema_error("entry", line, "missing type", "entry skipped")];
+    };
+    if !matches!(entry_type, "human" | "user" | "assistant") {
+        return Vec::new();
+    }
+
+    let mut problems = Vec::new();
+    if entry.get("timestamp").and_then(|v| v.as_str()).is_none() {
+        problems.push(schema_error(entry_type, line, "missing timestamp", "shown at the time it was read"));
+    }
+    let blocks = match entry.get("message").and_then(|m| m.get("content")) {
+        Some(Value::Array(blocks)) => blocks.as_slice(),
+        Some(_) => &[],
+        None if entry_type == "assistant" => {
+            problems.push(schema_error(entry_type, line, "missing message.content", "entry skipped"));
+            &[]
+        }
+        None => &[],
+    };
+    for block in blocks {
+        let block_type = block.get("type").and_then(|v| v.as_str()).unwrap_or("");
+        let missing = |key: &str| block.get(key).and_then(|v| v.as_str()).is_none_or(str::is_empty);
+        match block_type {
+            "tool_use" if missing("name") => {
+                problems.push(schema_error(entry_type, line, "tool_use block missing name", "tool shown as 'unknown'"));
+            }
+            "tool_result" if missing("tool_use_id") => problems.push(schema_error(
+                entry_type,
+                line,
+                "tool_result block missing tool_use_id",
+                "result not matched to its tool call",
+            )),
+            _ => {}
+        }
+    }
+    problems
+}
+
+fn is_api_error_message(entry: &Value) -> bool {
+    entry.get("isApiErrorMessage").and_then(Value::as_bool) == Some(true)
+}
+
+/// HTTP status and message of an API error entry: an assistant entry standing
+/// in for the failed reply (`API Error: 529 {"type":"error",...}`), or a system
+/// entry reporting a failed attempt (`"error": {"status": 429, ...}`).
+///
+/// # Functional Core
+/// Pure function.
+fn api_error(entry: &Value) -> Option<(Option<u16>, String)> {
+    match entry.get("type").and_then(Value::as_str)? {
+        "assistant" if is_api_error_message(entry) => {
+            let text = match entry.get("message").and_then(|m| m.get("content")) {
+                Some(Value::Array(blocks)) => {
+                    blocks.iter().filter_map(|b| b.get("text").and_then(Value::as_str)).collect::<Vec<_>>().join("\n")
+                }
+                Some(Value::String(s)) => s.clone(),
+                _ => String::new(),
+            };
+            Some(split_api_error_text(&text))
+        }
+        "system" if entry.get("subtype").and_then(Value::as_str) == Some("api_error") => {
+            let error = entry.get("error").unwrap_or(&Value::Null);
+            let status = [error, entry]
+                .iter()
+                .find_map(|v| v.get("status").and_then(Value::as_u64))
+                .and_then(|s| u16::try_from(s).ok());
+            let message = error_message(error)
+                .or_else(|| entry.get("content").and_then(Value::as_str).map(str::to_string))
+                .unwrap_or_default();
+            match status {
+                Some(_) => Some((status, message)),
+                None => Some(split_api_error_text(&message)),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `API Error: 529 {"error":{"message":"Overloaded"}}` as its status and the
+/// message inside the JSON; text in another shape is kept whole.
+fn split_api_error_text(text: &str) -> (Option<u16>, String) {
+    let text = text.trim();
+    let Some(rest) = text.strip_prefix("API Error:").map(str::trim_start) else {
+        return (None, text.to_string());
+    };
+    let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    let status = rest[..digits].parse().ok();
+    let rest = rest[digits..].trim();
+    let message = serde_json::from_str::<Value>(rest)
+        .ok()
+        .and_then(|json| error_message(&json))
+        .unwrap_or_else(|| if rest.is_empty() { text.to_string() } else { rest.to_string() });
+    (status, message)
+}
+
+/// The message of an API error body: `error.message`, `message`, or the text itself.
+fn error_message(error: &Value) -> Option<String> {
+    match error {
+        Value::String(s) => Some(s.clone()),
+        Value::Object(_) => error
+            .get("error")
+            .and_then(error_message)
+            .or_else(|| error.get("message").and_then(Value::as_str).map(str::to_string))
+            .or_else(|| error.get("type").and_then(Value::as_str).map(str::to_string)),
+        _ => None,
+    }
+}
+
+fn schema_error(entry_type: &str, line: usize, problem: &str, consequence: &'static str) -> ParseError {
+    ParseError::Schema { entry_type: entry_type.to_string(), line, problem: problem.to_string(), consequence }
 }
 
 /// Build a TranscriptEvent with session and optional agent attribution.
@@ -343,31 +611,44 @@ fn build_tool_id_map(content: &str) -> HashMap<String, String> {
     map
 }
 
-/// Extract a human-readable summary from a tool_result content block.
-fn extract_tool_result_summary(block: &Value) -> String {
-    match block.get("content") {
-        Some(Value::String(s)) => truncate_str(s, 16_000),
-        Some(Value::Array(items)) => {
-            let text = items
-                .iter()
-                .filter_map(|item| {
-                    if item.get("type").and_then(|v| v.as_str()) == Some("text") {
-                        item.get("text").and_then(|v| v.as_str())
-                    } else {
-                        None
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join(" ");
-            truncate_str(&text, 16_000)
+/// Pure function: whole text of a content block of a transcript entry,
+/// as its event shows it clipped (assistant text, a tool call's input
+/// summary, a tool result). `None` for other blocks.
+pub fn block_full_text(entry: &Value, block: usize) -> Option<String> {
+    let block = entry.get("message")?.get("content")?.as_array()?.get(block)?;
+    match block.get("type").and_then(|v| v.as_str())? {
+        "text" => block.get("text").and_then(|v| v.as_str()).map(str::to_string),
+        "tool_result" => Some(tool_result_text(block)),
+        "tool_use" => {
+            let tool_name = block.get("name").and_then(|v| v.as_str()).filter(|s| !s.is_empty()).unwrap_or("unknown");
+            Some(tool_input_text(tool_name, block.get("input").unwrap_or(&Value::Null)))
         }
+        _ => None,
+    }
+}
+
+/// Human-readable text of a tool_result content block.
+fn tool_result_text(block: &Value) -> String {
+    match block.get("content") {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| {
+                if item.get("type").and_then(|v| v.as_str()) == Some("text") {
+                    item.get("text").and_then(|v| v.as_str())
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
         _ => String::new(),
     }
 }
 
-/// Extract a human-readable summary from tool input JSON.
-fn extract_tool_input_summary(tool_name: &str, input: &Value) -> String {
-    let summary = match tool_name {
+/// Human-readable summary of tool input JSON.
+fn tool_input_text(tool_name: &str, input: &Value) -> String {
+    match tool_name {
         "Read" | "Glob" => input
             .get("file_path")
             .or_else(|| input.get("path"))
@@ -393,12 +674,17 @@ fn extract_tool_input_summary(tool_name: &str, input: &Value) -> String {
             .and_then(|v| v.as_str())
             .unwrap_or("")
             .to_string(),
+        "ExitPlanMode" => input
+            .get("plan")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string(),
         "Task" => input
             .get("description")
             .or_else(|| input.get("prompt"))
             .and_then(|v| v.as_str())
-            .map(|s| truncate_str(s, 2000))
-            .unwrap_or_default(),
+            .unwrap_or("")
+            .to_string(),
         _ => input
             .get("file_path")
             .or_else(|| input.get("command"))
@@ -407,8 +693,7 @@ fn extract_tool_input_summary(tool_name: &str, input: &Value) -> String {
             .and_then(|v| v.as_str())
             .unwrap_or("")
             .to_string(),
-    };
-    truncate_str(&summary, 8000)
+    }
 }
 
 /// Metadata extracted from a Claude Code subagent transcript.
@@ -420,10 +705,15 @@ pub struct TranscriptMetadata {
     /// Sum of all deduplicated messages' usage (cumulative session total)
     pub cumulative_usage: TokenUsage,
     pub skills: Vec<String>,
-    /// The task prompt (first user message content), truncated to 4000 chars.
+    /// The task prompt (first user message content), truncated to `TruncationLimits::prompt` chars.
     pub task_description: Option<String>,
+    /// The subagent's type as its entries name it (see `AGENT_TYPE_KEYS`)
+    pub agent_type: Option<String>,
 }
 
+/// Entry keys naming a subagent's type, first found wins.
+pub const AGENT_TYPE_KEYS: [&str; 4] = ["agentType", "agent_type", "subagentType", "subagent_type"];
+
 /// Parse Claude Code transcript JSONL to extract model, token usage, and skills.
 ///
 /// # Functional Core
@@ -437,27 +727,58 @@ pub struct TranscriptMetadata {
 /// For each JSONL line:
 /// - `type:"assistant"` → extract `.message.model` (keep first), deduplicate usage by message ID
 /// - `type:"user"` → scan content text blocks for `<command-name>X</command-name>` tags
+/// - any type → the first agent type found under `AGENT_TYPE_KEYS`
 pub fn parse_transcript_metadata(content: &str) -> TranscriptMetadata {
-    let mut meta = TranscriptMetadata::default();
+    let mut parser = MetadataParser::default();
+    for line in content.lines() {
+        parser.feed_line(line);
+    }
+    parser.metadata()
+}
+
+/// `parse_transcript_metadata` as a resumable computation: fed a transcript a
+/// line at a time, across as many reads as it takes, it holds what it has
+/// seen so far, so a growing file only has its new lines parsed.
+#[derive(Debug, Clone, Default)]
+pub struct MetadataParser {
+    meta: TranscriptMetadata,
     // Track per-message-ID usage; last write per ID wins (streaming dedup).
     // Preserve insertion order so we can pick the last unique message.
-    let mut msg_order: Vec<String> = Vec::new();
-    let mut msg_usage: HashMap<String, TokenUsage> = HashMap::new();
-    let mut seen_first_user = false;
+    msg_order: Vec<String>,
+    msg_usage: HashMap<String, TokenUsage>,
+    seen_first_user: bool,
+    limits: TruncationLimits,
+}
 
-    for line in content.lines() {
+impl MetadataParser {
+    pub fn new(limits: TruncationLimits) -> Self {
+        Self { limits, ..Self::default() }
+    }
+
+    /// Take in one JSONL line; blank and malformed lines are skipped.
+    pub fn feed_line(&mut self, line: &str) {
+        let meta = &mut self.meta;
         let trimmed = line.trim();
         if trimmed.is_empty() {
-            continue;
+            return;
         }
 
         let entry: Value = match serde_json::from_str(trimmed) {
             Ok(v) => v,
-            Err(_) => continue,
+            Err(_) => return,
         };
 
         let entry_type = entry.get("type").and_then(|v| v.as_str()).unwrap_or("");
 
+        if meta.agent_type.is_none() {
+            meta.agent_type = AGENT_TYPE_KEYS
+                .iter()
+                .find_map(|key| entry.get(*key).and_then(|v| v.as_str()))
+                .map(str::trim)
+                .filter(|t| !t.is_empty())
+                .map(str::to_string);
+        }
+
         match entry_type {
             "assistant" => {
                 // Extract model (keep first non-None)
@@ -499,10 +820,10 @@ pub fn parse_transcript_metadata(content: &str) -> TranscriptMetadata {
                             .unwrap_or(0),
                     };
 
-                    if !msg_usage.contains_key(&msg_id) {
-                        msg_order.push(msg_id.clone());
+                    if !self.msg_usage.contains_key(&msg_id) {
+                        self.msg_order.push(msg_id.clone());
                     }
-                    msg_usage.insert(msg_id, tu);
+                    self.msg_usage.insert(msg_id, tu);
                 }
             }
             "human" | "user" => {
@@ -518,42 +839,71 @@ pub fn parse_transcript_metadata(content: &str) -> TranscriptMetadata {
                             if let Some(text) = block.get("text").and_then(|v| v.as_str()) {
                                 extract_command_names(text, &mut meta.skills);
                                 // Capture first user message as task description
-                                if !seen_first_user {
-                                    meta.task_description = Some(truncate_str(text, 4000));
-                                    seen_first_user = true;
+                                if !self.seen_first_user {
+                                    meta.task_description = Some(truncate_str(text, self.limits.prompt));
+                                    self.seen_first_user = true;
                                 }
                             }
                         }
                     }
                     Some(Value::String(text)) => {
                         extract_command_names(text, &mut meta.skills);
-                        if !seen_first_user {
-                            meta.task_description = Some(truncate_str(text, 4000));
-                            seen_first_user = true;
+                        if !self.seen_first_user {
+                            meta.task_description = Some(truncate_str(text, self.limits.prompt));
+                            self.seen_first_user = true;
                         }
                     }
-                    _ => continue,
+                    _ => {}
                 };
             }
             _ => {}
         }
     }
 
-    // Cumulative: sum all deduplicated messages' usage
-    for tu in msg_usage.values() {
-        meta.cumulative_usage.add(tu);
-    }
+    /// Metadata of the lines fed so far.
+    pub fn metadata(&self) -> TranscriptMetadata {
+        let mut meta = self.meta.clone();
 
-    // Context window: last unique message's usage snapshot
-    if let Some(last_id) = msg_order.last() {
-        if let Some(tu) = msg_usage.remove(last_id) {
-            meta.token_usage = tu;
+        // Cumulative: sum all deduplicated messages' usage
+        for tu in self.msg_usage.values() {
+            meta.cumulative_usage.add(tu);
         }
+
+        // Context window: last unique message's usage snapshot
+        if let Some(tu) = self.msg_order.last().and_then(|last_id| self.msg_usage.get(last_id)) {
+            meta.token_usage = tu.clone();
+        }
+
+        meta.skills.sort();
+        meta.skills.dedup();
+        meta
     }
+}
 
-    meta.skills.sort();
-    meta.skills.dedup();
-    meta
+/// Derive a session goal from the initial user prompt.
+///
+/// # Functional Core
+/// Pure function. Slash-command prompts (`<command-name>/x</command-name>
+/// <command-args>...</command-args>`) become `"/x ..."`; other prompts are
+/// returned trimmed. Returns None for blank prompts.
+pub fn goal_from_prompt(prompt: &str) -> Option<String> {
+    let tag = |name: &str| {
+        let open = format!("<{}>", name);
+        let close = format!("</{}>", name);
+        let start = prompt.find(&open)? + open.len();
+        let end = prompt[start..].find(&close)? + start;
+        Some(prompt[start..end].trim())
+    };
+
+    let goal = match tag("command-name") {
+        Some(command) => match tag("command-args").filter(|args| !args.is_empty()) {
+            Some(args) => format!("{} {}", command, args),
+            None => command.to_string(),
+        },
+        None => prompt.trim().to_string(),
+    };
+
+    (!goal.is_empty()).then_some(goal)
 }
 
 /// Extract `<command-name>X</command-name>` tags from text via string search.
@@ -748,6 +1098,8 @@ not valid json
     fn test_parse_task_graph_loom_format() {
         let json = r#"{
             "current_phase": "execute",
+            "spec_file": ".claude/specs/tui/spec.md",
+            "plan_id": "auth-rework-3",
             "tasks": [
                 {
                     "id": "T1",
@@ -773,7 +1125,8 @@ not valid json
                     "description": "Wire main loop",
                     "agent": "code-implementer-agent",
                     "wave": 2,
-                    "status": "running"
+                    "status": "running",
+                    "depends_on": ["T1", "T2"]
                 }
             ]
         }"#;
@@ -789,11 +1142,18 @@ not valid json
         assert_eq!(graph.waves[0].number, 1);
         assert_eq!(graph.waves[0].tasks.len(), 2);
         assert_eq!(graph.waves[0].tasks[0].agent_id, Some("dotfiles-agent".into()));
+        assert_eq!(graph.phase, Some(Phase::Execute));
+        assert_eq!(graph.docs, [".claude/specs/tui/spec.md"]);
+        assert_eq!(graph.run_id.as_deref(), Some("auth-rework-3"));
 
         // Wave 2 has 1 task
         assert_eq!(graph.waves[1].number, 2);
         assert_eq!(graph.waves[1].tasks.len(), 1);
         assert_eq!(graph.waves[1].tasks[0].id.as_str(), "T3");
+
+        // Only non-empty dependency lists are kept
+        assert_eq!(graph.dependencies.len(), 1);
+        assert_eq!(graph.dependencies[&TaskId::new("T3")], [TaskId::new("T1"), TaskId::new("T2")]);
     }
 
     #[test]
@@ -834,19 +1194,23 @@ not valid json
     }
 
     #[test]
-    fn test_extract_tool_input_summary() {
+    fn test_tool_input_text() {
         assert_eq!(
-            extract_tool_input_summary("Read", &serde_json::json!({"file_path": "/tmp/foo"})),
+            tool_input_text("Read", &serde_json::json!({"file_path": "/tmp/foo"})),
             "/tmp/foo"
         );
         assert_eq!(
-            extract_tool_input_summary("Bash", &serde_json::json!({"command": "ls", "description": "list files"})),
+            tool_input_text("Bash", &serde_json::json!({"command": "ls", "description": "list files"})),
             "list files"
         );
         assert_eq!(
-            extract_tool_input_summary("Edit", &serde_json::json!({"file_path": "/tmp/bar.rs"})),
+            tool_input_text("Edit", &serde_json::json!({"file_path": "/tmp/bar.rs"})),
             "/tmp/bar.rs"
         );
+        assert_eq!(
+            tool_input_text("ExitPlanMode", &serde_json::json!({"plan": "## Plan\n1. Parse"})),
+            "## Plan\n1. Parse"
+        );
     }
 
     #[test]
@@ -1004,6 +1368,40 @@ not valid json
         assert_eq!(meta.token_usage.input_tokens, 10);
     }
 
+    #[test]
+    fn transcript_metadata_agent_type_keeps_first_named() {
+        let jsonl = concat!(
+            "{\"type\":\"user\",\"message\":{\"content\":\"review\"}}\n",
+            "{\"type\":\"user\",\"agentType\":\"loom:code-reviewer\",\"message\":{\"content\":\"x\"}}\n",
+            "{\"type\":\"assistant\",\"subagent_type\":\"Explore\",\"message\":{\"content\":[]}}",
+        );
+        let meta = parse_transcript_metadata(jsonl);
+        assert_eq!(meta.agent_type.as_deref(), Some("loom:code-reviewer"));
+    }
+
+    // ============================================================================
+    // goal_from_prompt tests
+    // ============================================================================
+
+    #[test]
+    fn goal_from_plain_prompt_is_trimmed() {
+        assert_eq!(goal_from_prompt("  fix the flaky test \n").as_deref(), Some("fix the flaky test"));
+    }
+
+    #[test]
+    fn goal_from_slash_command_uses_name_and_args() {
+        let prompt = "<command-message>loom is running…</command-message>\n<command-name>/loom</command-name>\n<command-args>add dark mode</command-args>";
+        assert_eq!(goal_from_prompt(prompt).as_deref(), Some("/loom add dark mode"));
+
+        let bare = "<command-name>/clear</command-name><command-args></command-args>";
+        assert_eq!(goal_from_prompt(bare).as_deref(), Some("/clear"));
+    }
+
+    #[test]
+    fn goal_from_blank_prompt_is_none() {
+        assert_eq!(goal_from_prompt("   "), None);
+    }
+
     // ============================================================================
     // shorten_model_id tests
     // ============================================================================
@@ -1124,6 +1522,39 @@ not valid json
         assert_eq!(events[0].session_id, Some(SessionId::new("s1")));
     }
 
+    // --- ApiError ---
+
+    #[test]
+    fn parse_events_api_error_entries_emit_api_errors() {
+        let lines = [
+            format!(
+                r#"{{"type":"assistant","timestamp":"{ts}","agentId":"a1","isApiErrorMessage":true,"message":{{"model":"<synthetic>","content":[{{"type":"text","text":"API Error: 529 {{\"type\":\"error\",\"error\":{{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}}}"}}]}}}}"#,
+                ts = ts_str()
+            ),
+            format!(
+                r#"{{"type":"assistant","timestamp":"{ts}","isApiErrorMessage":true,"message":{{"content":[{{"type":"text","text":"Prompt is too long"}}]}}}}"#,
+                ts = ts_str()
+            ),
+            format!(
+                r#"{{"type":"system","subtype":"api_error","level":"error","timestamp":"{ts}","error":{{"status":429,"error":{{"type":"rate_limit_error","message":"Number of request tokens has exceeded your per-minute rate limit"}}}},"retryAttempt":1}}"#,
+                ts = ts_str()
+            ),
+            format!(r#"{{"type":"system","subtype":"compact_boundary","timestamp":"{ts}"}}"#, ts = ts_str()),
+        ];
+        let events = parse_transcript_events(&lines.join("\n"), "s1");
+        let kinds: Vec<_> = events.iter().map(|e| e.kind.clone()).collect();
+        assert_eq!(kinds, [
+            TranscriptEventKind::ApiError { error_kind: ApiErrorKind::Overloaded, message: "Overloaded".into(), status: Some(529) },
+            TranscriptEventKind::ApiError { error_kind: ApiErrorKind::ContextLength, message: "Prompt is too long".into(), status: None },
+            TranscriptEventKind::ApiError {
+                error_kind: ApiErrorKind::RateLimit,
+                message: "Number of request tokens has exceeded your per-minute rate limit".into(),
+                status: Some(429),
+            },
+        ]);
+        assert_eq!(events[0].agent_id, Some(AgentId::new("a1")));
+    }
+
     #[test]
     fn parse_events_assistant_empty_text_skipped() {
         let jsonl = make_assistant_entry(r#"[{"type":"text","text":"   "}]"#);
@@ -1182,6 +1613,17 @@ not valid json
         }
     }
 
+    #[test]
+    fn parse_events_carry_entry_cwd() {
+        let jsonl = r#"{"type":"assistant","timestamp":"2026-02-11T10:00:00Z","cwd":"/work/proj","message":{"content":[{"type":"tool_use","id":"t1","name":"Read","input":{"file_path":"src/lib.rs"}},{"type":"text","text":"ok"}]}}
+{"type":"user","timestamp":"2026-02-11T10:00:01Z","message":{"content":"hi"}}"#;
+        let events = parse_transcript_events(jsonl, "s1");
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].cwd.as_deref(), Some("/work/proj"));
+        assert_eq!(events[1].cwd.as_deref(), Some("/work/proj"));
+        assert_eq!(events[2].cwd, None);
+    }
+
     #[test]
     fn parse_events_multiple_tool_use_blocks() {
         let jsonl = make_assistant_entry(
@@ -1215,6 +1657,8 @@ not valid json
             }
             _ => panic!("expected ToolResult, got {:?}", events[1].kind),
         }
+        // Both ends carry the call id (captured outputs are keyed by it)
+        assert!(events.iter().all(|e| e.tool_use_id.as_deref() == Some("toolu_01")));
     }
 
     #[test]
@@ -1233,6 +1677,19 @@ not valid json
         }
     }
 
+    #[test]
+    fn parse_events_tool_result_carries_is_error() {
+        let jsonl = make_user_entry(
+            r#"[{"type":"tool_result","tool_use_id":"t1","content":"exit 1","is_error":true},{"type":"tool_result","tool_use_id":"t2","content":"ok"}]"#,
+        );
+        let events = parse_transcript_events(&jsonl, "s1");
+        let flags: Vec<bool> = events
+            .iter()
+            .map(|e| matches!(e.kind, TranscriptEventKind::ToolResult { is_error: true, .. }))
+            .collect();
+        assert_eq!(flags, [true, false]);
+    }
+
     // --- agent_id extraction (FR-008) ---
 
     #[test]
@@ -1270,6 +1727,47 @@ not valid json
         }
     }
 
+    // --- schema diagnostics ---
+
+    #[test]
+    fn checked_parse_reports_skipped_lines_with_file_line_numbers() {
+        let jsonl = format!(
+            "{}\nnot json\n{}\n{}",
+            make_user_entry(r#""hello""#),
+            r#"{"type":"assistant","timestamp":"2026-03-18T10:00:00Z"}"#,
+            r#"{"type":"progress"}"#
+        );
+        let (events, diagnostics) = parse_transcript_events_checked(&jsonl, "s1", &ChunkPosition::stream(83), &TruncationLimits::default(), &mut SchemaDrift::default());
+        assert_eq!(events.len(), 1);
+        let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+        assert_eq!(messages, vec![
+            "entry at line 84 is not valid JSON — line skipped",
+            "assistant at line 85 missing message.content — entry skipped",
+        ]);
+    }
+
+    #[test]
+    fn validate_flags_missing_fields_per_kind() {
+        let entry: Value = serde_json::from_str(
+            r#"{"type":"user","message":{"content":[{"type":"tool_result","content":"ok"}]}}"#,
+        )
+        .unwrap();
+        let problems: Vec<String> = validate_transcript_entry(&entry, 7).iter().map(|d| d.to_string()).collect();
+        assert_eq!(problems, vec![
+            "user at line 7 missing timestamp — shown at the time it was read",
+            "user at line 7 tool_result block missing tool_use_id — result not matched to its tool call",
+        ]);
+
+        let entry: Value = serde_json::from_str(
+            r#"{"type":"assistant","timestamp":"2026-03-18T10:00:00Z","message":{"content":[{"type":"tool_use","input":{}}]}}"#,
+        )
+        .unwrap();
+        assert_eq!(validate_transcript_entry(&entry, 1).len(), 1);
+
+        let valid: Value = serde_json::from_str(&make_user_entry(r#""hi""#)).unwrap();
+        assert!(validate_transcript_entry(&valid, 1).is_empty());
+    }
+
     // --- resilience (NFR-005, NFR-006, FR-007) ---
 
     #[test]
@@ -1347,6 +1845,7 @@ not valid json
     // --- timestamp parsing ---
 
     #[test]
+    #[allow(clippy::useless_format)]
     fn parse_events_timestamp_parsed_from_entry() {
         let jsonl = format!(
             r#"{{"type":"user","timestamp":"2026-03-18T15:30:00Z","message":{{"role":"user","content":"hi"}}}}"#
@@ -0,0 +1,248 @@
+//! Schema drift: transcript data loom-tui does not know about.
+//!
+//! Every transcript entry the parser reads is checked against the fields it
+//! maps or knowingly ignores. Anything else — a new top-level key, a new
+//! `message` key, a new entry or content block type — is counted, so a
+//! Claude Code release that starts emitting data the TUI drops gets noticed.
+//! The counts show in the `:schema` report and `loom-tui doctor`.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+/// Entry types the parser maps, or knowingly skips.
+const ENTRY_TYPES: &[&str] =
+    &["user", "human", "assistant", "summary", "system", "result", "file-history-snapshot", "queue-operation", "progress"];
+
+/// Top-level entry keys that are mapped or knowingly ignored.
+const ENTRY_KEYS: &[&str] = &[
+    "type",
+    "timestamp",
+    "message",
+    "agentId",
+    "cwd",
+    "sessionId",
+    "session_id",
+    "uuid",
+    "parentUuid",
+    "logicalParentUuid",
+    "leafUuid",
+    "isSidechain",
+    "isMeta",
+    "isCompactSummary",
+    "isApiErrorMessage",
+    "isVisibleInTranscriptOnly",
+    "userType",
+    "version",
+    "gitBranch",
+    "slug",
+    "requestId",
+    "toolUseResult",
+    "toolUseID",
+    "thinkingMetadata",
+    "todos",
+    "summary",
+    "subtype",
+    "level",
+    "content",
+    "compactMetadata",
+    "snapshot",
+    "messageId",
+    "isSnapshotUpdate",
+    "operation",
+    "data",
+    "parentToolUseID",
+    // Orchestration run ids added by loom hooks (see `source::RUN_ID_KEYS`)
+    "runId",
+    "run_id",
+    // Subagent types (see `parsers::AGENT_TYPE_KEYS`)
+    "agentType",
+    "agent_type",
+    "subagentType",
+    "subagent_type",
+];
+
+/// Keys of `message` in user / assistant entries.
+const MESSAGE_KEYS: &[&str] =
+    &["role", "content", "id", "type", "model", "stop_reason", "stop_sequence", "usage", "container", "context_management"];
+
+/// Content block types and the keys each is known to carry.
+const BLOCKS: &[(&str, &[&str])] = &[
+    ("text", &["type", "text", "citations"]),
+    ("tool_use", &["type", "id", "name", "input", "caller"]),
+    ("tool_result", &["type", "tool_use_id", "content", "is_error"]),
+    ("thinking", &["type", "thinking", "signature"]),
+    ("redacted_thinking", &["type", "data"]),
+    ("image", &["type", "source"]),
+    ("document", &["type", "source", "title", "context", "citations"]),
+];
+
+/// Pure function: the fields of one transcript entry loom-tui does not know:
+/// `entry.<key>`, `message.<key>`, `type=<entry type>`, `block=<block type>`
+/// and `block.<type>.<key>`.
+pub fn unmapped_fields(entry: &Value) -> Vec<String> {
+    let Value::Object(map) = entry else {
+        return Vec::new();
+    };
+    let mut fields: Vec<String> =
+        map.keys().filter(|key| !ENTRY_KEYS.contains(&key.as_str())).map(|key| format!("entry.{}", key)).collect();
+    let entry_type = map.get("type").and_then(Value::as_str).unwrap_or("");
+    if !entry_type.is_empty() && !ENTRY_TYPES.contains(&entry_type) {
+        fields.push(format!("type={}", entry_type));
+    }
+    if !matches!(entry_type, "user" | "human" | "assistant") {
+        return fields;
+    }
+    let Some(Value::Object(message)) = map.get("message") else {
+        return fields;
+    };
+    fields.extend(
+        message.keys().filter(|key| !MESSAGE_KEYS.contains(&key.as_str())).map(|key| format!("message.{}", key)),
+    );
+    let blocks = message.get("content").and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default();
+    for block in blocks {
+        let block_type = block.get("type").and_then(Value::as_str).unwrap_or("");
+        match BLOCKS.iter().find(|(name, _)| *name == block_type) {
+            Some((name, keys)) => {
+                let keys = block.as_object().into_iter().flat_map(|b| b.keys()).filter(|key| !keys.contains(&key.as_str()));
+                fields.extend(keys.map(|key| format!("block.{}.{}", name, key)));
+            }
+            None if !block_type.is_empty() => fields.push(format!("block={}", block_type)),
+            None => {}
+        }
+    }
+    fields
+}
+
+/// Unknown fields seen so far, with how many entries carried each.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaDrift {
+    fields: BTreeMap<String, u64>,
+}
+
+impl SchemaDrift {
+    /// Count the unknown fields of one transcript entry.
+    pub fn record_entry(&mut self, entry: &Value) {
+        for field in unmapped_fields(entry) {
+            *self.fields.entry(field).or_insert(0) += 1;
+        }
+    }
+
+    /// Add another tally to this one.
+    ///
+    /// # Returns
+    /// Fields seen for the first time.
+    pub fn merge(&mut self, other: SchemaDrift) -> Vec<String> {
+        let mut new = Vec::new();
+        for (field, count) in other.fields {
+            let seen = self.fields.entry(field.clone()).or_insert(0);
+            if *seen == 0 {
+                new.push(field);
+            }
+            *seen += count;
+        }
+        new
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Fields by how often they were seen, most frequent first.
+    pub fn by_frequency(&self) -> Vec<(&str, u64)> {
+        let mut fields: Vec<(&str, u64)> = self.fields.iter().map(|(field, count)| (field.as_str(), *count)).collect();
+        fields.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        fields
+    }
+
+    /// Plain-text report for `loom-tui doctor`.
+    pub fn render(&self) -> String {
+        if self.is_empty() {
+            return "  no unknown fields\n".to_string();
+        }
+        self.by_frequency().iter().map(|(field, count)| format!("  {:>8}  {}\n", count, field)).collect()
+    }
+}
+
+/// Most recently modified transcripts scanned by `scan_transcripts`.
+pub const SCAN_MAX_FILES: usize = 50;
+
+/// Tally the unknown fields of the newest `SCAN_MAX_FILES` transcripts under
+/// `dir` (subagent transcripts included).
+/// I/O: lists `dir` and reads the transcripts.
+///
+/// # Returns
+/// The tally and the number of files read.
+pub fn scan_transcripts(dir: &Path) -> (SchemaDrift, usize) {
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else { continue };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "jsonl") {
+                let modified = entry.metadata().and_then(|m| m.modified()).unwrap_or(std::time::UNIX_EPOCH);
+                files.push((modified, path));
+            }
+        }
+    }
+    files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    files.truncate(SCAN_MAX_FILES);
+
+    let mut drift = SchemaDrift::default();
+    let mut read = 0;
+    for (_, path) in &files {
+        let Ok(content) = std::fs::read_to_string(path) else { continue };
+        read += 1;
+        for entry in content.lines().filter_map(|line| serde_json::from_str::<Value>(line.trim()).ok()) {
+            drift.record_entry(&entry);
+        }
+    }
+    (drift, read)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn known_fields_pass_and_new_ones_are_named() {
+        let entry = json!({
+            "type": "assistant",
+            "uuid": "u1",
+            "timestamp": "2026-03-18T10:00:00Z",
+            "effortLevel": "high",
+            "message": {
+                "role": "assistant",
+                "model": "m",
+                "serviceTier": "priority",
+                "content": [
+                    { "type": "text", "text": "hi" },
+                    { "type": "tool_use", "id": "t1", "name": "Bash", "input": {}, "timeoutHint": 5 },
+                    { "type": "server_tool_use", "id": "s1" },
+                ],
+            },
+        });
+        assert_eq!(
+            unmapped_fields(&entry),
+            ["entry.effortLevel", "message.serviceTier", "block.tool_use.timeoutHint", "block=server_tool_use"]
+        );
+        assert_eq!(unmapped_fields(&json!({ "type": "checkpoint", "uuid": "u2" })), ["type=checkpoint"]);
+    }
+
+    #[test]
+    fn merge_reports_first_sightings_and_ranks_by_count() {
+        let mut total = SchemaDrift::default();
+        let mut batch = SchemaDrift::default();
+        batch.record_entry(&json!({ "type": "user", "a": 1 }));
+        batch.record_entry(&json!({ "type": "user", "a": 1, "b": 2 }));
+        assert_eq!(total.merge(batch.clone()), ["entry.a", "entry.b"]);
+        assert!(total.merge(batch).is_empty());
+        assert_eq!(total.by_frequency(), [("entry.a", 4), ("entry.b", 2)]);
+        assert_eq!(total.render(), "         4  entry.a\n         2  entry.b\n");
+    }
+}
@@ -0,0 +1,564 @@
+//! Pluggable event sources: where `AppEvent`s come from.
+//! Which sources exist and how they attribute lines: `docs/design.md`, "Event sources".
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex, PoisonError};
+
+use serde_json::Value;
+
+use super::health::{self, HealthMap};
+use super::logs::{log_loop, ExtraLog};
+use super::schema_drift::SchemaDrift;
+use super::{
+    archive_dir_fingerprint, archive_loop, load_archived_session_metas, parsers, polling_loop, start_supervised,
+    task_graph_loop, ArchiveDirWatch, ChunkPosition, PollerHandles, TailState, TruncationLimits, WatcherResult,
+};
+use crate::error::WatcherError;
+use crate::event::AppEvent;
+use crate::model::{EventOrigin, SessionId};
+use crate::paths::Paths;
+
+/// Session for stream lines that carry no `sessionId`.
+const FALLBACK_SESSION: &str = "stream";
+
+/// Keys of a stream line naming the orchestration run of its session.
+pub const RUN_ID_KEYS: [&str; 2] = ["runId", "run_id"];
+
+/// An input that produces `AppEvent`s for the main loop.
+pub trait EventSource {
+    /// Start the source's thread(s), sending events on `tx`.
+    fn spawn(&self, tx: mpsc::Sender<AppEvent>) -> SourceHandle;
+}
+
+/// What the main loop keeps of a running source: its heartbeats for the
+/// watchdog in `update`, and how to respawn its threads.
+pub struct SourceHandle {
+    health: HealthMap,
+    restarts: Vec<Box<dyn Fn()>>,
+}
+
+impl SourceHandle {
+    /// Handle for threads that register no heartbeats (stream readers block on
+    /// input) and cannot be restarted.
+    pub fn unsupervised() -> Self {
+        Self { health: HealthMap::new(), restarts: Vec::new() }
+    }
+
+    /// Handle for threads beating into `health`; `restart` spawns a fresh set.
+    pub fn supervised(health: HealthMap, restart: impl Fn() + 'static) -> Self {
+        Self { health, restarts: vec![Box::new(restart)] }
+    }
+
+    /// One handle for sources started together and sharing `health`.
+    pub fn join(health: HealthMap, handles: impl IntoIterator<Item = SourceHandle>) -> Self {
+        let restarts = handles.into_iter().flat_map(|handle| handle.restarts).collect();
+        Self { health, restarts }
+    }
+
+    /// Shared heartbeat map for the watchdog check in `update`.
+    pub fn health(&self) -> HealthMap {
+        self.health.clone()
+    }
+
+    /// Spawn fresh threads. Stalled predecessors stop at their next beat.
+    pub fn restart(&self) {
+        for restart in &self.restarts {
+            restart();
+        }
+    }
+}
+
+/// Start `source` now and again on every restart.
+fn supervise<S: Clone + 'static>(
+    source: &S,
+    health: &HealthMap,
+    tx: mpsc::Sender<AppEvent>,
+    start: fn(&S, &mpsc::Sender<AppEvent>),
+) -> SourceHandle {
+    start(source, &tx);
+    let source = source.clone();
+    SourceHandle::supervised(health.clone(), move || start(&source, &tx))
+}
+
+/// Polls the transcript dir: session discovery, tailing, agent and session lifecycle.
+#[derive(Debug, Clone)]
+pub struct TranscriptSource {
+    transcript_dir: PathBuf,
+    health: HealthMap,
+    /// Tail offsets as of the poller's last completed pass, so a restarted
+    /// poller resumes where the old one stopped instead of replaying transcripts
+    tail_checkpoint: Arc<Mutex<TailState>>,
+    limits: TruncationLimits,
+}
+
+impl TranscriptSource {
+    pub fn new(transcript_dir: PathBuf, health: HealthMap, limits: TruncationLimits) -> Self {
+        Self { transcript_dir, health, tail_checkpoint: Arc::new(Mutex::new(TailState::new())), limits }
+    }
+
+    fn start(&self, tx: &mpsc::Sender<AppEvent>) {
+        // Register before spawning so the watchdog never sees a gap
+        let heartbeat = self.health.register(health::POLLER);
+        let tail_state = self
+            .tail_checkpoint
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let transcript_dir = self.transcript_dir.clone();
+        let checkpoint = Arc::clone(&self.tail_checkpoint);
+        let limits = self.limits;
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            polling_loop(transcript_dir, PollerHandles { heartbeat, tail_state, checkpoint, limits }, tx);
+        });
+    }
+}
+
+impl EventSource for TranscriptSource {
+    fn spawn(&self, tx: mpsc::Sender<AppEvent>) -> SourceHandle {
+        supervise(self, &self.health, tx, Self::start)
+    }
+}
+
+/// Polls the task graph file's mtime.
+#[derive(Debug, Clone)]
+pub struct TaskGraphSource {
+    path: PathBuf,
+    health: HealthMap,
+}
+
+impl TaskGraphSource {
+    pub fn new(path: PathBuf, health: HealthMap) -> Self {
+        Self { path, health }
+    }
+
+    fn start(&self, tx: &mpsc::Sender<AppEvent>) {
+        let heartbeat = self.health.register(health::TASK_GRAPH_POLLER);
+        let path = self.path.clone();
+        let tx = tx.clone();
+        std::thread::spawn(move || task_graph_loop(path, heartbeat, tx));
+    }
+}
+
+impl EventSource for TaskGraphSource {
+    fn spawn(&self, tx: mpsc::Sender<AppEvent>) -> SourceHandle {
+        supervise(self, &self.health, tx, Self::start)
+    }
+}
+
+/// Indexes archived sessions on startup and whenever the archive dir changes.
+#[derive(Debug, Clone)]
+pub struct ArchiveSource {
+    archive_dir: PathBuf,
+    health: HealthMap,
+}
+
+impl ArchiveSource {
+    pub fn new(archive_dir: PathBuf, health: HealthMap) -> Self {
+        Self { archive_dir, health }
+    }
+
+    fn start(&self, tx: &mpsc::Sender<AppEvent>) {
+        let heartbeat = self.health.register(health::ARCHIVE_POLLER);
+        let archive_watch = ArchiveDirWatch {
+            fingerprint: archive_dir_fingerprint(&self.archive_dir),
+            changed_at: None,
+        };
+        let archive_dir = self.archive_dir.clone();
+        let tx = tx.clone();
+        std::thread::spawn(move || archive_loop(archive_dir, archive_watch, heartbeat, tx));
+    }
+}
+
+impl EventSource for ArchiveSource {
+    fn spawn(&self, tx: mpsc::Sender<AppEvent>) -> SourceHandle {
+        // Archived session metas load immediately (lightweight); the thread
+        // re-indexes whenever the archive dir changes afterwards
+        load_archived_session_metas(&self.archive_dir, &tx);
+        supervise(self, &self.health, tx, Self::start)
+    }
+}
+
+/// Tails the config's extra logs (see `logs`) on one thread.
+#[derive(Debug, Clone)]
+pub struct LogTailSource {
+    logs: Vec<ExtraLog>,
+    health: HealthMap,
+}
+
+impl LogTailSource {
+    pub fn new(logs: Vec<ExtraLog>, health: HealthMap) -> Self {
+        Self { logs, health }
+    }
+
+    fn start(&self, tx: &mpsc::Sender<AppEvent>) {
+        let heartbeat = self.health.register(health::LOG_POLLER);
+        let logs = self.logs.clone();
+        let tx = tx.clone();
+        std::thread::spawn(move || log_loop(logs, heartbeat, tx));
+    }
+}
+
+impl EventSource for LogTailSource {
+    fn spawn(&self, tx: mpsc::Sender<AppEvent>) -> SourceHandle {
+        supervise(self, &self.health, tx, Self::start)
+    }
+}
+
+/// Which event source to start, from the command line.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SourceKind {
+    /// Poll the project's files (default)
+    #[default]
+    File,
+    /// Transcript JSONL piped into standard input (`--stdin`)
+    Stdin,
+    /// Transcript JSONL written to a Unix socket at this path (`--socket=PATH`)
+    Socket(PathBuf),
+}
+
+impl SourceKind {
+    /// Pure function: source selected by CLI flags; the last source flag wins.
+    pub fn from_flags(flags: &[String]) -> Self {
+        flags.iter().fold(Self::File, |kind, flag| match flag.as_str() {
+            "--stdin" => Self::Stdin,
+            _ => match flag.strip_prefix("--socket=") {
+                Some(path) if !path.is_empty() => Self::Socket(PathBuf::from(path)),
+                _ => kind,
+            },
+        })
+    }
+}
+
+/// Start the selected source, plus a `LogTailSource` when `logs` are configured.
+/// Transcript text is clipped to `limits`.
+/// Returns its handle with the channel its events arrive on.
+pub fn start_source(
+    kind: &SourceKind,
+    paths: &Paths,
+    logs: &[ExtraLog],
+    limits: TruncationLimits,
+) -> WatcherResult<(SourceHandle, mpsc::Receiver<AppEvent>)> {
+    let stream: Box<dyn EventSource> = match kind {
+        SourceKind::File => return start_supervised(paths, logs, limits),
+        SourceKind::Stdin => Box::new(StdinSource { limits }),
+        SourceKind::Socket(path) => Box::new(SocketSource::bind(path.clone())?.with_limits(limits)),
+    };
+    let (tx, rx) = mpsc::channel();
+    // A stream has no backlog to replay: stale-session cleanup can start now
+    let _ = tx.send(AppEvent::ReplayComplete);
+    let health = HealthMap::new();
+    let mut handles = vec![stream.spawn(tx.clone())];
+    if !logs.is_empty() {
+        handles.push(LogTailSource::new(logs.to_vec(), health.clone()).spawn(tx));
+    }
+    Ok((SourceHandle::join(health, handles), rx))
+}
+
+/// Reads transcript JSONL from standard input until EOF.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdinSource {
+    pub limits: TruncationLimits,
+}
+
+impl EventSource for StdinSource {
+    fn spawn(&self, tx: mpsc::Sender<AppEvent>) -> SourceHandle {
+        let mut decoder = StreamDecoder::new("stdin").with_limits(self.limits);
+        std::thread::spawn(move || {
+            read_stream(std::io::stdin().lock(), &mut decoder, &tx);
+            let _ = tx.send(AppEvent::Info {
+                source: "stdin".into(),
+                message: "stdin closed; no further events".into(),
+            });
+        });
+        // Standard input cannot be reopened once closed
+        SourceHandle::unsupervised()
+    }
+}
+
+/// Listens on a Unix socket; every connection streams transcript JSONL.
+/// The socket file is left for the caller to remove on exit.
+#[derive(Debug)]
+pub struct SocketSource {
+    path: PathBuf,
+    limits: TruncationLimits,
+    #[cfg(unix)]
+    listener: std::os::unix::net::UnixListener,
+}
+
+impl SocketSource {
+    /// Bind the socket, replacing a stale socket file.
+    #[cfg(unix)]
+    pub fn bind(path: PathBuf) -> WatcherResult<Self> {
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+        let listener = std::os::unix::net::UnixListener::bind(&path)?;
+        Ok(Self { path, limits: TruncationLimits::default(), listener })
+    }
+
+    #[cfg(not(unix))]
+    pub fn bind(path: PathBuf) -> WatcherResult<Self> {
+        Err(WatcherError::Io(format!("{}: sockets are only supported on Unix", path.display())))
+    }
+
+    pub fn with_limits(mut self, limits: TruncationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+}
+
+impl EventSource for SocketSource {
+    /// Accept connections on a background thread, one reader thread each.
+    #[cfg(unix)]
+    fn spawn(&self, tx: mpsc::Sender<AppEvent>) -> SourceHandle {
+        let source = self.path.display().to_string();
+        let limits = self.limits;
+        let listener = match self.listener.try_clone() {
+            Ok(listener) => listener,
+            Err(e) => {
+                let _ = tx.send(AppEvent::Error { source, error: WatcherError::Io(e.to_string()).into() });
+                return SourceHandle::unsupervised();
+            }
+        };
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let tx = tx.clone();
+                        let source = source.clone();
+                        std::thread::spawn(move || {
+                            let mut decoder = StreamDecoder::new(&source).with_limits(limits);
+                            read_stream(BufReader::new(stream), &mut decoder, &tx);
+                        });
+                    }
+                    Err(e) => {
+                        if tx.send(AppEvent::Error {
+                            source: source.clone(),
+                            error: WatcherError::Io(e.to_string()).into(),
+                        }).is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+        // The listener only stops when the app does
+        SourceHandle::unsupervised()
+    }
+
+    #[cfg(not(unix))]
+    fn spawn(&self, _tx: mpsc::Sender<AppEvent>) -> SourceHandle {
+        SourceHandle::unsupervised()
+    }
+}
+
+/// Decode lines from `input` until EOF, a read error, or the receiver hangs up.
+fn read_stream(input: impl BufRead, decoder: &mut StreamDecoder, tx: &mpsc::Sender<AppEvent>) {
+    for line in input.lines() {
+        let events = match line {
+            Ok(line) => decoder.decode(&line),
+            Err(e) => vec![AppEvent::Error {
+                source: decoder.origin.clone(),
+                error: WatcherError::Io(e.to_string()).into(),
+            }],
+        };
+        for event in events {
+            if tx.send(event).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Turns stream lines into `AppEvent`s, remembering which sessions were
+/// announced and which run each was last tagged with.
+#[derive(Debug, Clone)]
+pub struct StreamDecoder {
+    /// Where the lines come from (`stdin`, the socket path), for error sources
+    origin: String,
+    sessions: BTreeSet<String>,
+    runs: BTreeMap<String, String>,
+    lines: usize,
+    limits: TruncationLimits,
+}
+
+impl StreamDecoder {
+    pub fn new(origin: &str) -> Self {
+        Self {
+            origin: origin.to_string(),
+            sessions: BTreeSet::new(),
+            runs: BTreeMap::new(),
+            lines: 0,
+            limits: TruncationLimits::default(),
+        }
+    }
+
+    pub fn with_limits(mut self, limits: TruncationLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Decode one JSONL line: `SessionDiscovered` for a new session and
+    /// `SessionRunTagged` for a new run id, then its transcript events, plus
+    /// diagnostics for lines that didn't parse.
+    ///
+    /// # Functional Core
+    /// Pure apart from the decoder's own bookkeeping.
+    pub fn decode(&mut self, line: &str) -> Vec<AppEvent> {
+        self.lines += 1;
+        if line.trim().is_empty() {
+            return Vec::new();
+        }
+        let entry = serde_json::from_str::<Value>(line).ok();
+        let session_id = entry
+            .as_ref()
+            .and_then(|entry| entry.get("sessionId").and_then(Value::as_str))
+            .filter(|sid| !sid.is_empty())
+            .unwrap_or(FALLBACK_SESSION)
+            .to_string();
+
+        let mut out = Vec::new();
+        // Only a line that parsed announces its session
+        if entry.is_some() && self.sessions.insert(session_id.clone()) {
+            out.push(AppEvent::SessionDiscovered {
+                session_id: SessionId::new(&session_id),
+                transcript_path: PathBuf::from(&self.origin),
+            });
+        }
+        let run_id = entry
+            .as_ref()
+            .and_then(|entry| RUN_ID_KEYS.iter().find_map(|key| entry.get(key).and_then(Value::as_str)))
+            .filter(|run| !run.is_empty());
+        if let Some(run_id) = run_id {
+            if self.runs.get(&session_id).is_none_or(|known| known != run_id) {
+                self.runs.insert(session_id.clone(), run_id.to_string());
+                out.push(AppEvent::SessionRunTagged { session_id: SessionId::new(&session_id), run_id: run_id.to_string() });
+            }
+        }
+        let mut drift = SchemaDrift::default();
+        let (events, diagnostics) = parsers::parse_transcript_events_checked(
+            line,
+            &session_id,
+            &ChunkPosition::stream(self.lines),
+            &self.limits,
+            &mut drift,
+        );
+        out.extend(diagnostics.into_iter().map(|diagnostic| AppEvent::Error {
+            source: self.origin.clone(),
+            error: WatcherError::Parse(diagnostic).into(),
+        }));
+        if !drift.is_empty() {
+            out.push(AppEvent::SchemaDriftObserved(drift));
+        }
+        out.extend(
+            events
+                .into_iter()
+                .map(|event| AppEvent::TranscriptEventReceived(event.with_origin(EventOrigin::Hook))),
+        );
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TranscriptEventKind;
+
+    #[test]
+    fn source_flags_pick_the_last_source() {
+        let flags = |f: &[&str]| f.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(SourceKind::from_flags(&flags(&["--ssh"])), SourceKind::File);
+        assert_eq!(SourceKind::from_flags(&flags(&["--stdin", "--linear"])), SourceKind::Stdin);
+        assert_eq!(
+            SourceKind::from_flags(&flags(&["--stdin", "--socket=/tmp/loom.sock"])),
+            SourceKind::Socket(PathBuf::from("/tmp/loom.sock"))
+        );
+        assert_eq!(SourceKind::from_flags(&flags(&["--socket="])), SourceKind::File);
+    }
+
+    #[test]
+    fn stream_lines_announce_sessions_once_and_carry_events() {
+        let input = concat!(
+            r#"{"type":"user","sessionId":"s1","timestamp":"2026-03-18T10:00:00Z","message":{"content":"hi"}}"#, "\n",
+            "\n",
+            r#"{"type":"user","sessionId":"s1","timestamp":"2026-03-18T10:00:01Z","message":{"content":"again"}}"#, "\n",
+            "not json\n",
+        );
+        let (tx, rx) = mpsc::channel();
+        read_stream(input.as_bytes(), &mut StreamDecoder::new("stdin"), &tx);
+        drop(tx);
+        let events: Vec<AppEvent> = rx.iter().collect();
+
+        let discovered = events.iter().filter(|e| matches!(e, AppEvent::SessionDiscovered { .. })).count();
+        assert_eq!(discovered, 1);
+        let messages: Vec<_> = events
+            .iter()
+            .filter_map(|e| match e {
+                AppEvent::TranscriptEventReceived(ev) => Some(ev),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(messages.len(), 2);
+        assert!(messages.iter().all(|ev| ev.kind == TranscriptEventKind::UserMessage));
+        assert!(messages.iter().all(|ev| ev.session_id == Some(SessionId::new("s1"))));
+        assert!(messages.iter().all(|ev| ev.origin == EventOrigin::Hook));
+        assert!(matches!(events.last(), Some(AppEvent::Error { source, .. }) if source == "stdin"));
+    }
+
+    #[test]
+    fn hook_payload_run_ids_tag_their_session_once() {
+        let mut decoder = StreamDecoder::new("stdin");
+        let line = |run: &str| format!(r#"{{"type":"user","sessionId":"w1","runId":"{run}","message":{{"content":"hi"}}}}"#);
+        let tags = |events: Vec<AppEvent>| {
+            events
+                .into_iter()
+                .filter_map(|e| match e {
+                    AppEvent::SessionRunTagged { run_id, .. } => Some(run_id),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(tags(decoder.decode(&line("auth-3"))), ["auth-3"]);
+        assert!(tags(decoder.decode(&line("auth-3"))).is_empty());
+        assert_eq!(tags(decoder.decode(&line("auth-4"))), ["auth-4"]);
+    }
+
+    #[test]
+    fn file_source_runs_on_its_own_and_restarts_under_one_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("task_graph.json");
+        std::fs::write(&path, r#"{"waves":[{"number":1,"tasks":[{"id":"T1","description":"t","status":"pending"}]}],"total_tasks":1,"completed_tasks":0}"#)
+            .unwrap();
+        let (tx, rx) = mpsc::channel();
+        let handle = TaskGraphSource::new(path, HealthMap::new()).spawn(tx);
+
+        let event = rx.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+        assert!(matches!(event, AppEvent::TaskGraphUpdated(_)), "{event:?}");
+
+        handle.restart();
+        let threads = handle.health().snapshot();
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[health::TASK_GRAPH_POLLER].generation, 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn socket_source_reads_connections() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("loom.sock");
+        let (tx, rx) = mpsc::channel();
+        let _handle = SocketSource::bind(path.clone()).unwrap().spawn(tx);
+        let mut conn = std::os::unix::net::UnixStream::connect(&path).unwrap();
+        conn.write_all(br#"{"type":"user","sessionId":"s9","message":{"content":"hi"}}"#).unwrap();
+        conn.write_all(b"\n").unwrap();
+
+        let first = rx.recv_timeout(std::time::Duration::from_secs(5)).unwrap();
+        assert!(matches!(first, AppEvent::SessionDiscovered { ref session_id, .. } if session_id.as_str() == "s9"));
+    }
+}
@@ -13,6 +13,8 @@ use std::path::{Path, PathBuf};
 pub struct TailState {
     /// Map from file path to last read byte offset
     offsets: HashMap<PathBuf, u64>,
+    /// Complete lines consumed per file (for diagnostics line numbers)
+    lines: HashMap<PathBuf, usize>,
 }
 
 impl TailState {
@@ -20,6 +22,7 @@ impl TailState {
     pub fn new() -> Self {
         Self {
             offsets: HashMap::new(),
+            lines: HashMap::new(),
         }
     }
 
@@ -28,6 +31,11 @@ impl TailState {
         self.offsets.get(path).copied().unwrap_or(0)
     }
 
+    /// Complete lines read from a file so far (0 if never read)
+    pub fn lines_read(&self, path: &Path) -> usize {
+        self.lines.get(path).copied().unwrap_or(0)
+    }
+
     /// Update offset for a file
     pub fn set_offset(&mut self, path: PathBuf, offset: u64) {
         self.offsets.insert(path, offset);
@@ -56,6 +64,7 @@ impl TailState {
         let read_offset = if file_len < current_offset {
             // Reset offset and re-read from start
             self.set_offset(path.to_path_buf(), 0);
+            self.lines.remove(path);
             0
         } else {
             current_offset
@@ -89,6 +98,7 @@ impl TailState {
 
         if advance > 0 {
             self.set_offset(path.to_path_buf(), read_offset + advance as u64);
+            *self.lines.entry(path.to_path_buf()).or_default() += new_content.matches('\n').count();
         }
 
         Ok(new_content)
@@ -97,11 +107,13 @@ impl TailState {
     /// Reset offset for a file to 0 (force full re-read next time)
     pub fn reset(&mut self, path: &Path) {
         self.offsets.remove(path);
+        self.lines.remove(path);
     }
 
     /// Clear all tracked offsets
     pub fn clear(&mut self) {
         self.offsets.clear();
+        self.lines.clear();
     }
 }
 
@@ -334,6 +346,26 @@ mod tests {
         assert!(new_offset < original_offset, "Offset should decrease after truncation");
     }
 
+    #[test]
+    fn test_lines_read_counts_complete_lines_and_resets_on_truncation() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "Line 1").unwrap();
+        write!(file, "Line 2\npartial").unwrap();
+
+        let mut state = TailState::new();
+        state.read_new_lines(file.path()).unwrap();
+        assert_eq!(state.lines_read(file.path()), 2);
+
+        let path = file.path().to_path_buf();
+        drop(file);
+        let mut file = File::create(&path).unwrap();
+        writeln!(file, "Short").unwrap();
+        drop(file);
+
+        state.read_new_lines(&path).unwrap();
+        assert_eq!(state.lines_read(&path), 1);
+    }
+
     #[test]
     fn test_normal_append_after_truncation_detection() {
         let mut file = NamedTempFile::new().unwrap();
@@ -0,0 +1,113 @@
+//! Render harness for view tests: draw a view into a `TestBackend` and query
+//! the resulting cells (text, colors, panel borders) instead of eyeballing it.
+
+use loom_tui::app::AppState;
+use loom_tui::model::Theme;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::Color;
+use ratatui::{Frame, Terminal};
+
+/// Symbols a panel border is drawn with.
+const BORDER_SYMBOLS: [&str; 10] = ["─", "│", "┌", "┐", "└", "┘", "╭", "╮", "╰", "╯"];
+
+/// A rendered frame.
+pub struct Screen {
+    buffer: Buffer,
+}
+
+impl Screen {
+    /// The whole UI for `state`, as the main loop draws it.
+    pub fn render(state: &AppState, width: u16, height: u16) -> Self {
+        Self::draw(width, height, |frame| loom_tui::view::render(state, frame))
+    }
+
+    /// Whatever `draw` puts into a `width` x `height` frame.
+    pub fn draw(width: u16, height: u16, draw: impl FnOnce(&mut Frame)) -> Self {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(draw).unwrap();
+        Self { buffer: terminal.backend().buffer().clone() }
+    }
+
+    /// Row `y` as text, one symbol per cell.
+    pub fn row(&self, y: u16) -> String {
+        (0..self.buffer.area.width).map(|x| self.buffer[(x, y)].symbol()).collect()
+    }
+
+    /// All rows, newline separated.
+    pub fn text(&self) -> String {
+        (0..self.buffer.area.height).map(|y| self.row(y)).collect::<Vec<_>>().join("\n")
+    }
+
+    pub fn contains(&self, needle: &str) -> bool {
+        (0..self.buffer.area.height).any(|y| self.row(y).contains(needle))
+    }
+
+    /// `len` cells of text starting at (`x`, `y`).
+    pub fn text_at(&self, x: u16, y: u16, len: u16) -> String {
+        (x..x.saturating_add(len).min(self.buffer.area.width)).map(|x| self.buffer[(x, y)].symbol()).collect()
+    }
+
+    /// Cell position of the first occurrence of `needle`, top to bottom.
+    pub fn find(&self, needle: &str) -> Option<Position> {
+        self.find_all(needle).into_iter().next()
+    }
+
+    /// Cell positions of every occurrence of `needle`, top to bottom.
+    pub fn find_all(&self, needle: &str) -> Vec<Position> {
+        let symbols: Vec<String> = needle.chars().map(String::from).collect();
+        let width = self.buffer.area.width as usize;
+        (0..self.buffer.area.height)
+            .flat_map(|y| {
+                let row: Vec<&str> = (0..self.buffer.area.width).map(|x| self.buffer[(x, y)].symbol()).collect();
+                (0..(width + 1).saturating_sub(symbols.len()))
+                    .filter(|&x| symbols.iter().zip(&row[x..]).all(|(want, got)| want == got))
+                    .map(|x| Position::new(x as u16, y))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Position of the panel title `title`: text set into a top or bottom
+    /// border, so a header or list row with the same words doesn't count.
+    pub fn find_title(&self, title: &str) -> Option<Position> {
+        self.find_all(title).into_iter().find(|at| {
+            (0..at.x).rev().map(|x| self.buffer[(x, at.y)].symbol()).find(|s| *s != " ").is_some_and(|s| BORDER_SYMBOLS.contains(&s))
+        })
+    }
+
+    /// Foreground color of the cell at (`x`, `y`).
+    pub fn fg(&self, x: u16, y: u16) -> Color {
+        self.buffer[(x, y)].fg
+    }
+
+    /// The one foreground color of every non-blank cell in `area`, or `None`
+    /// when they differ (or all are blank).
+    pub fn region_fg(&self, area: Rect) -> Option<Color> {
+        let mut colors = area.positions().map(|p| &self.buffer[p]).filter(|c| c.symbol() != " ").map(|c| c.fg);
+        let first = colors.next()?;
+        colors.all(|c| c == first).then_some(first)
+    }
+
+    /// Border color of the panel titled `title`: the first border cell left of
+    /// the title on its row.
+    pub fn border_color(&self, title: &str) -> Option<Color> {
+        let at = self.find_title(title)?;
+        (0..at.x)
+            .rev()
+            .map(|x| &self.buffer[(x, at.y)])
+            .find(|cell| BORDER_SYMBOLS.contains(&cell.symbol()))
+            .map(|cell| cell.fg)
+    }
+
+    /// The panel titled `title` has the focus border.
+    pub fn is_focused(&self, title: &str) -> bool {
+        self.border_color(title) == Some(Theme::ACTIVE_BORDER)
+    }
+
+    /// Column `x` as text, top to bottom.
+    pub fn column(&self, x: u16) -> String {
+        (0..self.buffer.area.height).map(|y| self.buffer[(x, y)].symbol()).collect()
+    }
+}
@@ -4,6 +4,7 @@ use std::collections::BTreeMap;
 use std::time::Duration;
 
 #[test]
+#[allow(clippy::redundant_closure)]
 fn parse_active_task_graph_fixture() {
     let json = std::fs::read_to_string("tests/fixtures/active_task_graph.json")
         .expect("Failed to read fixture");
@@ -1,5 +1,6 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use loom_tui::app::{handle_key, AppState, PanelFocus, ViewState};
+use loom_tui::app::cost_history::CostPeriod;
+use loom_tui::app::{handle_key, AppState, CommandState, PaletteState, PanelFocus, SessionDetailTab, Suspend, TokenDashboardTab, ViewState};
 use loom_tui::model::{Agent, AgentId, ArchivedSession, SessionArchive, SessionId, SessionMeta, Task, TaskId, TaskGraph, TaskStatus, Wave};
 use std::path::PathBuf;
 use chrono::Utc;
@@ -16,6 +17,31 @@ fn quit_signal_set_by_q_key() {
     assert!(state.meta.should_quit);
 }
 
+#[test]
+fn quit_with_running_agents_asks_first() {
+    use loom_tui::app::{ConfirmAction, ConfirmState};
+    let mut state = AppState::new();
+    for id in ["a01", "a02"] {
+        state.domain.agents.insert(AgentId::new(id), Agent::new(id, Utc::now()));
+    }
+
+    handle_key(&mut state, key(KeyCode::Char('q')));
+    assert!(!state.meta.should_quit);
+    assert_eq!(state.ui.confirm, ConfirmState::open(ConfirmAction::Quit { running: 2 }));
+
+    // Focus is trapped; Enter on the default No closes the dialog
+    handle_key(&mut state, key(KeyCode::Char('3')));
+    assert_eq!(state.ui.view, ViewState::Dashboard);
+    handle_key(&mut state, key(KeyCode::Enter));
+    assert!(!state.ui.confirm.is_open());
+    assert!(!state.meta.should_quit);
+
+    handle_key(&mut state, key(KeyCode::Char('q')));
+    handle_key(&mut state, key(KeyCode::Tab));
+    handle_key(&mut state, key(KeyCode::Enter));
+    assert!(state.meta.should_quit);
+}
+
 #[test]
 fn number_key_1_switches_to_dashboard() {
     let mut state = AppState::new();
@@ -295,6 +321,64 @@ fn enter_on_dashboard_drills_into_agent_detail() {
     assert_eq!(state.ui.selected_agent_index, Some(0));
 }
 
+#[test]
+fn pipe_toggles_split_view_and_switches_to_dashboard() {
+    let mut state = AppState::new();
+    state.ui.view = ViewState::AgentDetail;
+
+    handle_key(&mut state, key(KeyCode::Char('|')));
+    assert!(matches!(state.ui.view, ViewState::Dashboard));
+    assert!(state.ui.split_view);
+
+    handle_key(&mut state, key(KeyCode::Char('|')));
+    assert!(!state.ui.split_view);
+}
+
+#[test]
+fn shift_s_toggles_spec_panel_and_switches_to_dashboard() {
+    let mut state = AppState::new();
+    state.ui.view = ViewState::Sessions;
+
+    handle_key(&mut state, key(KeyCode::Char('S')));
+    assert!(matches!(state.ui.view, ViewState::Dashboard));
+    assert!(state.ui.show_spec_panel);
+
+    handle_key(&mut state, key(KeyCode::Char('S')));
+    assert!(!state.ui.show_spec_panel);
+}
+
+#[test]
+fn enter_in_split_view_follows_task_agent_without_leaving_dashboard() {
+    let mut state = AppState::new();
+    state.ui.split_view = true;
+    let mut task = Task::new("T1".to_string(), "Test".to_string(), TaskStatus::Running);
+    task.agent_id = Some(AgentId::new("a04"));
+    state.domain.task_graph = Some(TaskGraph::new(vec![Wave::new(1, vec![task])]));
+    state.ui.selected_task_index = Some(0);
+    let now = Utc::now();
+    state.domain.agents.insert(AgentId::new("a04"), Agent::new("a04", now));
+    state.domain.agents.insert(AgentId::new("a05"), Agent::new("a05", now + chrono::Duration::seconds(1)));
+    state.recompute_sorted_keys();
+    state.ui.scroll_offsets.agent_events = 3;
+
+    handle_key(&mut state, key(KeyCode::Enter));
+    assert!(matches!(state.ui.view, ViewState::Dashboard));
+    assert_eq!(state.followed_agent().map(|a| a.id.as_str()), Some("a04"));
+    assert_eq!(state.ui.scroll_offsets.agent_events, 0);
+}
+
+#[test]
+fn split_view_right_panel_scrolls_agent_activity() {
+    let mut state = AppState::new();
+    state.ui.split_view = true;
+    state.ui.focus = PanelFocus::Right;
+
+    handle_key(&mut state, key(KeyCode::Char('j')));
+    assert_eq!(state.ui.scroll_offsets.agent_events, 1);
+    assert_eq!(state.ui.scroll_offsets.event_stream, 0);
+    assert!(!state.ui.auto_scroll);
+}
+
 #[test]
 fn enter_on_dashboard_noop_if_no_agent() {
     let mut state = AppState::new();
@@ -596,3 +680,836 @@ fn filter_mode_prevents_navigation() {
     assert_eq!(state.ui.filter.as_deref(), Some("test3"));
     assert!(matches!(state.ui.view, ViewState::Dashboard));
 }
+
+// ---------------------------------------------------------------------------
+// Quick-switch palette (Ctrl+P)
+// ---------------------------------------------------------------------------
+
+fn ctrl(c: char) -> KeyEvent {
+    KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+}
+
+fn type_query(state: &mut AppState, query: &str) {
+    for c in query.chars() {
+        handle_key(state, key(KeyCode::Char(c)));
+    }
+}
+
+#[test]
+fn ctrl_p_opens_palette_and_captures_typing() {
+    let mut state = AppState::new();
+    handle_key(&mut state, ctrl('p'));
+    assert!(state.ui.palette.is_open());
+
+    // '3' is typed into the query instead of switching views
+    type_query(&mut state, "3q");
+    assert_eq!(state.ui.palette, PaletteState::Open { query: "3q".into(), selected: 0 });
+    assert!(matches!(state.ui.view, ViewState::Dashboard));
+    assert!(!state.meta.should_quit);
+
+    handle_key(&mut state, key(KeyCode::Esc));
+    assert!(!state.ui.palette.is_open());
+}
+
+#[test]
+fn palette_jumps_to_task_on_dashboard() {
+    let mut state = AppState::with_view(ViewState::Sessions);
+    state.domain.task_graph = Some(TaskGraph::new(vec![Wave::new(
+        1,
+        vec![
+            Task::new("T1".to_string(), "Set up CI".to_string(), TaskStatus::Completed),
+            Task::new("T2".to_string(), "Write migration".to_string(), TaskStatus::Running),
+        ],
+    )]));
+
+    handle_key(&mut state, ctrl('p'));
+    type_query(&mut state, "migration");
+    handle_key(&mut state, key(KeyCode::Enter));
+
+    assert!(!state.ui.palette.is_open());
+    assert!(matches!(state.ui.view, ViewState::Dashboard));
+    assert_eq!(state.ui.selected_task_index, Some(1));
+}
+
+#[test]
+fn palette_jumps_to_agent_detail() {
+    let mut state = AppState::new();
+    let now = Utc::now();
+    state.domain.agents.insert(AgentId::new("a01"), Agent::new("a01", now));
+    state.domain.agents.insert(AgentId::new("a02"), Agent::new("a02", now + chrono::Duration::seconds(1)));
+    state.recompute_sorted_keys();
+
+    handle_key(&mut state, ctrl('p'));
+    type_query(&mut state, "a01");
+    handle_key(&mut state, key(KeyCode::Enter));
+
+    assert!(matches!(state.ui.view, ViewState::AgentDetail));
+    let selected = state.ui.selected_agent_index.unwrap();
+    assert_eq!(state.sorted_agent_keys()[selected], AgentId::new("a01"));
+}
+
+#[test]
+fn palette_jump_to_unloaded_archive_requests_load() {
+    let mut state = AppState::new();
+    state.domain.sessions = vec![
+        ArchivedSession::new(SessionMeta::new("older", Utc::now(), "/p".into()), PathBuf::from("/tmp/older.json")),
+        ArchivedSession::new(SessionMeta::new("target", Utc::now(), "/p".into()), PathBuf::from("/tmp/target.json")),
+    ];
+
+    handle_key(&mut state, ctrl('p'));
+    type_query(&mut state, "target");
+    handle_key(&mut state, key(KeyCode::Enter));
+
+    assert!(matches!(state.ui.view, ViewState::Sessions));
+    assert_eq!(state.ui.selected_session_index, Some(1));
+    assert_eq!(state.ui.loading_session, Some(SessionId::new("target")));
+}
+
+#[test]
+fn palette_arrow_keys_move_selection_within_results() {
+    let mut state = AppState::new();
+    state.domain.task_graph = Some(TaskGraph::new(vec![Wave::new(
+        1,
+        vec![
+            Task::new("T1".to_string(), "one".to_string(), TaskStatus::Pending),
+            Task::new("T2".to_string(), "two".to_string(), TaskStatus::Pending),
+        ],
+    )]));
+
+    handle_key(&mut state, ctrl('p'));
+    for _ in 0..5 {
+        handle_key(&mut state, key(KeyCode::Down));
+    }
+    assert_eq!(state.ui.palette, PaletteState::Open { query: String::new(), selected: 1 });
+
+    handle_key(&mut state, ctrl('p'));
+    assert_eq!(state.ui.palette, PaletteState::Open { query: String::new(), selected: 0 });
+
+    handle_key(&mut state, key(KeyCode::Down));
+    handle_key(&mut state, key(KeyCode::Enter));
+    assert_eq!(state.ui.selected_task_index, Some(1));
+}
+
+// Command line tests
+
+fn type_str(state: &mut AppState, text: &str) {
+    for c in text.chars() {
+        handle_key(state, key(KeyCode::Char(c)));
+    }
+}
+
+#[test]
+fn colon_opens_command_line() {
+    let mut state = AppState::new();
+    handle_key(&mut state, key(KeyCode::Char(':')));
+    assert_eq!(state.ui.command, CommandState::Open { input: String::new() });
+
+    // Keys are captured while open
+    handle_key(&mut state, key(KeyCode::Char('q')));
+    assert!(!state.meta.should_quit);
+    assert_eq!(state.ui.command, CommandState::Open { input: "q".into() });
+}
+
+#[test]
+fn command_set_tick_updates_tick_rate() {
+    let mut state = AppState::new();
+    handle_key(&mut state, key(KeyCode::Char(':')));
+    type_str(&mut state, "set tick=100ms");
+    handle_key(&mut state, key(KeyCode::Enter));
+
+    assert!(!state.ui.command.is_open());
+    assert_eq!(state.meta.tick_rate, std::time::Duration::from_millis(100));
+    let message = state.ui.command_message.as_ref().unwrap();
+    assert!(!message.is_error);
+}
+
+#[test]
+fn unknown_command_reports_error() {
+    let mut state = AppState::new();
+    handle_key(&mut state, key(KeyCode::Char(':')));
+    type_str(&mut state, "frobnicate");
+    handle_key(&mut state, key(KeyCode::Enter));
+
+    let message = state.ui.command_message.as_ref().unwrap();
+    assert!(message.is_error);
+    assert!(message.text.contains("frobnicate"));
+
+    // Message clears on next key
+    handle_key(&mut state, key(KeyCode::Char('j')));
+    assert!(state.ui.command_message.is_none());
+}
+
+#[test]
+fn backspace_on_empty_command_line_closes_it() {
+    let mut state = AppState::new();
+    handle_key(&mut state, key(KeyCode::Char(':')));
+    type_str(&mut state, "e");
+    handle_key(&mut state, key(KeyCode::Backspace));
+    assert_eq!(state.ui.command, CommandState::Open { input: String::new() });
+    handle_key(&mut state, key(KeyCode::Backspace));
+    assert!(!state.ui.command.is_open());
+}
+
+#[test]
+fn tab_completes_command_name() {
+    let mut state = AppState::new();
+    handle_key(&mut state, key(KeyCode::Char(':')));
+    type_str(&mut state, "exp");
+    handle_key(&mut state, key(KeyCode::Tab));
+    let CommandState::Open { input } = &state.ui.command else {
+        panic!("command line should stay open");
+    };
+    assert!(input.starts_with("export"));
+}
+
+#[test]
+fn filter_command_sets_event_filter() {
+    let mut state = AppState::new();
+    handle_key(&mut state, key(KeyCode::Char(':')));
+    type_str(&mut state, "filter tool:Bash");
+    handle_key(&mut state, key(KeyCode::Enter));
+    assert_eq!(state.ui.filter.as_deref(), Some("tool:Bash"));
+}
+
+// Undo tests
+
+#[test]
+fn delete_then_undo_restores_session_and_file() {
+    let temp = tempfile::tempdir().unwrap();
+    let path = temp.path().join("old.json");
+    std::fs::write(&path, "{}").unwrap();
+
+    let mut state = AppState::new();
+    state.meta.archive_dir = Some(temp.path().to_path_buf());
+    state.domain.sessions.push(ArchivedSession::new(
+        SessionMeta::new("old", Utc::now(), "/proj".into()),
+        path.clone(),
+    ));
+    state.ui.view = ViewState::Sessions;
+    state.ui.selected_session_index = Some(0);
+
+    handle_key(&mut state, key(KeyCode::Char('d')));
+    handle_key(&mut state, key(KeyCode::Char('y')));
+    assert!(state.domain.sessions.is_empty());
+    assert!(!path.exists());
+    assert!(state.ui.command_message.as_ref().unwrap().text.contains("u to undo"));
+
+    handle_key(&mut state, key(KeyCode::Char('u')));
+    assert_eq!(state.domain.sessions.len(), 1);
+    assert!(path.exists());
+    assert!(!state.domain.deleted_session_ids.contains(&SessionId::new("old")));
+    assert_eq!(state.ui.command_message.as_ref().unwrap().text, "restored session old");
+    assert!(state.meta.errors.is_empty(), "{:?}", state.meta.errors);
+
+    // Nothing left to undo
+    handle_key(&mut state, key(KeyCode::Char('u')));
+    assert_eq!(state.ui.command_message.as_ref().unwrap().text, "nothing to undo");
+}
+
+// Project scope tests
+
+#[test]
+fn shift_p_cycles_sessions_project_scope() {
+    use loom_tui::app::{update, ProjectFilter};
+    use loom_tui::event::AppEvent;
+
+    let mut state = AppState::new().with_project_path("/home/u/app".to_string());
+    state.ui.view = ViewState::Sessions;
+    let now = Utc::now();
+    update(&mut state, AppEvent::SessionMetasLoaded(vec![
+        (PathBuf::from("/a/s1.json"), SessionMeta::new("s1", now, "/home/u/app".into())),
+        (PathBuf::from("/b/s2.json"), SessionMeta::new("s2", now, "/home/u/other".into())),
+    ]));
+    assert_eq!(state.domain.sessions.len(), 1);
+
+    handle_key(&mut state, key(KeyCode::Char('P')));
+    assert_eq!(state.ui.project_filter, ProjectFilter::Project("/home/u/other".into()));
+    assert_eq!(state.domain.sessions[0].meta.id, SessionId::new("s2"));
+
+    handle_key(&mut state, key(KeyCode::Char('P')));
+    assert_eq!(state.ui.project_filter, ProjectFilter::All);
+    assert_eq!(state.domain.sessions.len(), 2);
+
+    handle_key(&mut state, key(KeyCode::Char('P')));
+    assert_eq!(state.ui.project_filter, ProjectFilter::Current);
+    assert_eq!(state.domain.sessions.len(), 1);
+}
+
+#[test]
+fn calendar_day_keys_narrow_sessions_to_one_day() {
+    use loom_tui::app::{update, ProjectFilter};
+    use loom_tui::event::AppEvent;
+
+    let mut state = AppState::with_view(ViewState::Sessions);
+    state.ui.project_filter = ProjectFilter::All;
+    state.ui.time_format.utc = true;
+    let now = Utc::now();
+    let yesterday = now - chrono::Duration::days(1);
+    update(&mut state, AppEvent::SessionMetasLoaded(vec![
+        (PathBuf::from("/a/s1.json"), SessionMeta::new("s1", now, "/p".into())),
+        (PathBuf::from("/a/s2.json"), SessionMeta::new("s2", yesterday, "/p".into())),
+        (PathBuf::from("/a/s3.json"), SessionMeta::new("s3", yesterday, "/p".into())),
+    ]));
+    assert_eq!(state.session_calendar().count(yesterday.date_naive()), 2);
+
+    // First step lands on the newest day with sessions
+    handle_key(&mut state, key(KeyCode::Char('[')));
+    assert_eq!(state.ui.session_date, Some(now.date_naive()));
+    assert_eq!(state.domain.sessions.len(), 1);
+
+    handle_key(&mut state, key(KeyCode::Char('[')));
+    let ids: Vec<_> = state.domain.sessions.iter().map(|s| s.meta.id.as_str()).collect();
+    assert_eq!(ids, ["s2", "s3"]);
+    assert_eq!(state.ui.command_message.as_ref().unwrap().text.split(' ').next(), Some("2"));
+
+    // Esc shows every day again instead of leaving the view
+    handle_key(&mut state, key(KeyCode::Esc));
+    assert_eq!(state.ui.view, ViewState::Sessions);
+    assert!(state.ui.session_date.is_none());
+    assert_eq!(state.domain.sessions.len(), 3);
+
+    // Stepping forward past today clears the day too
+    handle_key(&mut state, key(KeyCode::Char('[')));
+    handle_key(&mut state, key(KeyCode::Char(']')));
+    assert!(state.ui.session_date.is_none());
+}
+
+#[test]
+fn outcome_key_narrows_sessions_to_one_outcome_at_a_time() {
+    use loom_tui::app::{update, ProjectFilter};
+    use loom_tui::event::AppEvent;
+    use loom_tui::model::{SessionOutcome, SessionStatus};
+
+    let mut state = AppState::with_view(ViewState::Sessions);
+    state.ui.project_filter = ProjectFilter::All;
+    let now = Utc::now();
+    let ended = |id: &str, outcome: Option<SessionOutcome>| {
+        let meta = SessionMeta::new(id, now, "/p".into()).with_status(SessionStatus::Completed);
+        (PathBuf::from(format!("/a/{id}.json")), outcome.map_or(meta.clone(), |o| meta.with_outcome(o)))
+    };
+    update(&mut state, AppEvent::SessionMetasLoaded(vec![
+        ended("s1", Some(SessionOutcome::Success)),
+        ended("s2", Some(SessionOutcome::Failed)),
+        ended("s3", Some(SessionOutcome::Success)),
+        ended("old", None),
+    ]));
+    state.ui.marked_sessions.insert(SessionId::new("s2"));
+
+    handle_key(&mut state, key(KeyCode::Char('O')));
+    assert_eq!(state.ui.outcome_filter, Some(SessionOutcome::Success));
+    assert_eq!(state.domain.sessions.len(), 2);
+    assert!(state.ui.marked_sessions.is_empty());
+
+    handle_key(&mut state, key(KeyCode::Char('O')));
+    handle_key(&mut state, key(KeyCode::Char('O')));
+    assert_eq!(state.ui.outcome_filter, Some(SessionOutcome::Failed));
+    assert_eq!(state.domain.sessions[0].meta.id, SessionId::new("s2"));
+
+    // Archives from before outcomes were recorded count as unknown
+    handle_key(&mut state, key(KeyCode::Char('O')));
+    handle_key(&mut state, key(KeyCode::Char('O')));
+    assert_eq!(state.ui.outcome_filter, Some(SessionOutcome::Unknown));
+    assert_eq!(state.domain.sessions[0].meta.id, SessionId::new("old"));
+
+    handle_key(&mut state, key(KeyCode::Char('O')));
+    assert_eq!(state.ui.outcome_filter, None);
+    assert_eq!(state.domain.sessions.len(), 4);
+}
+
+// Settings overlay tests
+
+#[test]
+fn settings_edits_preview_live_and_esc_reverts() {
+    use std::time::Duration;
+
+    let mut state = AppState::new();
+    handle_key(&mut state, key(KeyCode::Char(',')));
+    assert!(state.ui.settings.is_open());
+
+    handle_key(&mut state, key(KeyCode::Char('l')));
+    assert_eq!(state.meta.tick_rate, Duration::from_millis(300));
+
+    handle_key(&mut state, key(KeyCode::Char('j')));
+    handle_key(&mut state, key(KeyCode::Char('j')));
+    handle_key(&mut state, key(KeyCode::Char(' ')));
+    assert!(!state.ui.event_filters.tool_results);
+
+    handle_key(&mut state, key(KeyCode::Esc));
+    assert!(!state.ui.settings.is_open());
+    assert_eq!(state.meta.tick_rate, AppState::new().meta.tick_rate);
+    assert!(state.ui.event_filters.tool_results);
+}
+
+#[test]
+fn settings_enter_keeps_and_saves() {
+    let dir = tempfile::tempdir().unwrap();
+    let config_path = dir.path().join("config.json");
+    let mut state = AppState::new();
+    state.meta.config_path = Some(config_path.clone());
+
+    handle_key(&mut state, key(KeyCode::Char(',')));
+    handle_key(&mut state, key(KeyCode::Char('j')));
+    handle_key(&mut state, key(KeyCode::Char('h')));
+    handle_key(&mut state, key(KeyCode::Enter));
+
+    assert!(!state.ui.settings.is_open());
+    assert_eq!(state.meta.stale_timeout, chrono::Duration::minutes(9));
+    assert!(state.ui.command_message.as_ref().unwrap().text.starts_with("settings saved"));
+    let saved = loom_tui::app::settings::load_settings(&config_path).unwrap();
+    assert_eq!(saved.stale_timeout_mins, 9);
+}
+
+fn archived_session_detail(dir: &std::path::Path) -> AppState {
+    let mut state = AppState::new();
+    let meta = SessionMeta::new("s1", Utc::now(), "/proj".to_string());
+    let path = dir.join("s1.json");
+    loom_tui::session::save_session(&path, &SessionArchive::new(meta.clone())).unwrap();
+    state.domain.sessions = vec![ArchivedSession::new(meta.clone(), path).with_data(SessionArchive::new(meta))];
+    state.ui.view = ViewState::SessionDetail;
+    state.ui.selected_session_id = Some(SessionId::new("s1"));
+    state
+}
+
+#[test]
+fn notes_tab_edit_and_save_round_trip() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut state = archived_session_detail(dir.path());
+
+    handle_key(&mut state, key(KeyCode::Char('n')));
+    assert_eq!(state.ui.session_detail_tab, SessionDetailTab::Notes);
+    handle_key(&mut state, key(KeyCode::Char('e')));
+    assert!(state.ui.notes_editor.is_some());
+
+    // Typing goes into the editor, not the key map
+    for c in "\nq n".chars() {
+        handle_key(&mut state, key(if c == '\n' { KeyCode::Enter } else { KeyCode::Char(c) }));
+    }
+    assert!(!state.meta.should_quit);
+    handle_key(&mut state, KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+
+    assert!(state.ui.notes_editor.is_none());
+    assert!(!state.ui.command_message.as_ref().unwrap().is_error);
+    let saved = loom_tui::session::load_session(&dir.path().join("s1.json")).unwrap();
+    assert!(saved.notes.unwrap().ends_with("## Follow-ups\n\n\nq n\n"));
+}
+
+#[test]
+fn notes_editor_esc_discards() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut state = archived_session_detail(dir.path());
+    state.ui.session_detail_tab = SessionDetailTab::Notes;
+    state.ui.focus = PanelFocus::Right;
+
+    handle_key(&mut state, key(KeyCode::Enter));
+    handle_key(&mut state, key(KeyCode::Char('x')));
+    handle_key(&mut state, key(KeyCode::Esc));
+
+    assert!(state.ui.notes_editor.is_none());
+    assert!(matches!(state.ui.view, ViewState::SessionDetail));
+    assert!(state.domain.sessions[0].data.as_ref().unwrap().notes.is_none());
+}
+
+#[test]
+fn ctrl_z_requests_suspend_without_touching_state() {
+    let mut state = AppState::new();
+    state.ui.view = ViewState::Sessions;
+    handle_key(&mut state, KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL));
+    assert_eq!(state.meta.suspend, Some(Suspend::Stop));
+    assert!(matches!(state.ui.view, ViewState::Sessions));
+    assert!(!state.meta.should_quit);
+}
+
+#[test]
+fn z_toggles_zoom_in_every_view() {
+    let mut state = AppState::new();
+    handle_key(&mut state, key(KeyCode::Char('z')));
+    assert!(state.ui.zoomed);
+
+    // Zoom survives switching views; focus still picks the panel
+    handle_key(&mut state, key(KeyCode::Char('5')));
+    handle_key(&mut state, key(KeyCode::Tab));
+    assert!(state.ui.zoomed);
+    assert_eq!(state.ui.focus, PanelFocus::Right);
+
+    handle_key(&mut state, key(KeyCode::Char('z')));
+    assert!(!state.ui.zoomed);
+    assert_eq!(state.meta.suspend, None);
+}
+
+#[test]
+fn o_requests_editor_for_selected_step_file() {
+    use loom_tui::model::{ToolName, TranscriptEvent, TranscriptEventKind};
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("lib.rs"), "fn main() {}\n").unwrap();
+
+    let mut state = AppState::new();
+    state.domain.agents.insert(AgentId::new("a01"), Agent::new("a01", Utc::now()));
+    state.recompute_sorted_keys();
+    state.ui.view = ViewState::AgentDetail;
+    state.ui.selected_agent_index = Some(0);
+    let read = TranscriptEvent::new(
+        Utc::now(),
+        TranscriptEventKind::ToolUse { tool_name: ToolName::new("Read"), input_summary: "lib.rs".into() },
+    )
+    .with_agent("a01")
+    .with_cwd(dir.path().display().to_string());
+    state.domain.events.push_back(read);
+
+    handle_key(&mut state, key(KeyCode::Char('o')));
+    match state.meta.suspend {
+        Some(Suspend::Editor(ref file)) => assert_eq!(file.path, dir.path().join("lib.rs")),
+        ref other => panic!("expected editor request, got {other:?}"),
+    }
+
+    // Nothing to open outside agent activity
+    state.meta.suspend = None;
+    state.ui.view = ViewState::Sessions;
+    handle_key(&mut state, key(KeyCode::Char('o')));
+    assert!(state.meta.suspend.is_none());
+    assert!(state.ui.command_message.as_ref().unwrap().is_error);
+}
+
+#[test]
+fn o_opens_captured_tool_output_in_pager_before_the_file() {
+    use loom_tui::app::tool_outputs::{ToolOutput, ToolOutputIndex};
+    use loom_tui::model::{ToolName, TranscriptEvent, TranscriptEventKind};
+
+    let mut state = AppState::new();
+    state.domain.agents.insert(AgentId::new("a01"), Agent::new("a01", Utc::now()));
+    state.recompute_sorted_keys();
+    state.ui.view = ViewState::AgentDetail;
+    state.ui.selected_agent_index = Some(0);
+    let now = Utc::now();
+    let read = TranscriptEvent::new(
+        now,
+        TranscriptEventKind::ToolUse { tool_name: ToolName::new("Read"), input_summary: "lib.rs".into() },
+    )
+    .with_agent("a01");
+    let mut result = TranscriptEvent::new(
+        now,
+        TranscriptEventKind::ToolResult {
+            tool_name: ToolName::new("Read"),
+            result_summary: "1\tfn main() {}".into(),
+            duration_ms: None,
+            is_error: false,
+        },
+    )
+    .with_agent("a01");
+    result.tool_use_id = Some("toolu_01".into());
+    state.domain.events.extend([read, result]);
+    let output = ToolOutput { path: "/state/tool-outputs/toolu_01.txt".into(), bytes: 48 * 1024 };
+    state.domain.tool_outputs = ToolOutputIndex::from_iter([("toolu_01".to_string(), output.clone())]);
+
+    handle_key(&mut state, key(KeyCode::Char('o')));
+    assert!(state.meta.suspend.is_none());
+    let pager = state.ui.output_pager.as_ref().expect("pager open");
+    assert_eq!((pager.tool_name.as_str(), &pager.output), ("Read", &output));
+
+    // Keys scroll the pager, not the view underneath; o closes it
+    handle_key(&mut state, key(KeyCode::Char('3')));
+    assert_eq!(state.ui.view, ViewState::AgentDetail);
+    handle_key(&mut state, key(KeyCode::Char('o')));
+    assert!(state.ui.output_pager.is_none());
+}
+
+#[test]
+fn shift_d_requests_diff_of_task_files_and_esc_closes() {
+    use loom_tui::app::DiffPopupState;
+    let mut state = AppState::new().with_project_path("/repo".to_string());
+    let mut task = Task::new("T1", "Build".to_string(), TaskStatus::Completed);
+    task.files_modified = vec!["src/a.rs".into()];
+    state.domain.task_graph = Some(TaskGraph::new(vec![Wave::new(1, vec![task])]));
+    let mut meta = SessionMeta::new("s1", Utc::now(), "/repo".to_string());
+    meta.confirmed = true;
+    let start = meta.timestamp;
+    state.domain.active_sessions.insert(SessionId::new("s1"), meta);
+    state.ui.selected_task_index = Some(0);
+
+    handle_key(&mut state, key(KeyCode::Char('D')));
+    match &state.ui.diff_popup {
+        DiffPopupState::Loading(request) => {
+            assert_eq!(request.paths, vec!["src/a.rs".to_string()]);
+            assert_eq!(request.since, start);
+            assert_eq!(request.repo, PathBuf::from("/repo"));
+        }
+        other => panic!("expected loading popup, got {other:?}"),
+    }
+
+    handle_key(&mut state, key(KeyCode::Esc));
+    assert_eq!(state.ui.diff_popup, DiffPopupState::Closed);
+    assert!(matches!(state.ui.view, ViewState::Dashboard));
+}
+
+#[test]
+fn review_view_records_rejection_with_note() {
+    use loom_tui::app::review::{load_reviews, Verdict};
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("reviews.jsonl");
+    let mut state = AppState::new();
+    state.meta.reviews_path = Some(path.clone());
+    state.domain.task_graph = Some(TaskGraph::new(vec![Wave::new(1, vec![
+        Task::new("T1", "Build".to_string(), TaskStatus::Completed),
+        Task::new("T2", "Test".to_string(), TaskStatus::Implemented),
+    ])]));
+
+    handle_key(&mut state, key(KeyCode::Char('5')));
+    assert!(matches!(state.ui.view, ViewState::Review));
+    handle_key(&mut state, key(KeyCode::Char('j')));
+    handle_key(&mut state, key(KeyCode::Char('r')));
+    for c in "no tests".chars() {
+        handle_key(&mut state, key(KeyCode::Char(c)));
+    }
+    // Typing in the note doesn't trigger view keys
+    assert!(matches!(state.ui.view, ViewState::Review));
+    handle_key(&mut state, key(KeyCode::Enter));
+
+    assert!(state.ui.review_note.is_none());
+    assert!(!state.ui.command_message.as_ref().unwrap().is_error);
+    let saved = load_reviews(&path).unwrap();
+    let decision = &saved[&TaskId::new("T2")];
+    assert_eq!((decision.verdict, decision.note.as_str()), (Verdict::Rejected, "no tests"));
+    assert_eq!(state.domain.reviews[&TaskId::new("T2")], *decision);
+
+    // Esc cancels a note without writing
+    handle_key(&mut state, key(KeyCode::Char('a')));
+    handle_key(&mut state, key(KeyCode::Esc));
+    assert!(state.ui.review_note.is_none());
+    assert_eq!(load_reviews(&path).unwrap().len(), 1);
+    handle_key(&mut state, key(KeyCode::Esc));
+    assert!(matches!(state.ui.view, ViewState::Dashboard));
+}
+
+#[test]
+fn token_dashboard_c_switches_to_cost_tab_and_w_toggles_period() {
+    let mut state = AppState::with_view(ViewState::TokenDashboard);
+
+    // `w` only applies on the cost tab
+    handle_key(&mut state, key(KeyCode::Char('w')));
+    assert_eq!(state.ui.cost_period, CostPeriod::Day);
+
+    handle_key(&mut state, key(KeyCode::Char('c')));
+    assert_eq!(state.ui.token_dashboard_tab, TokenDashboardTab::Cost);
+    handle_key(&mut state, key(KeyCode::Char('w')));
+    assert_eq!(state.ui.cost_period, CostPeriod::Week);
+
+    handle_key(&mut state, key(KeyCode::Char('c')));
+    assert_eq!(state.ui.token_dashboard_tab, TokenDashboardTab::Sessions);
+}
+
+#[test]
+fn agent_list_groups_by_type_and_folds_sections() {
+    let mut state = AppState::new();
+    state.ui.view = ViewState::AgentDetail;
+    state.ui.focus = PanelFocus::Left;
+    let start = Utc::now();
+    for (i, agent_type) in ["reviewer", "implementer", "reviewer"].iter().enumerate() {
+        let id = format!("a0{}", i);
+        let mut agent = Agent::new(id.as_str(), start + chrono::Duration::seconds(i as i64));
+        agent.agent_type = Some(agent_type.to_string());
+        state.domain.agents.insert(AgentId::new(&id), agent);
+    }
+    state.recompute_sorted_keys();
+    state.ui.selected_agent_index = Some(0);
+
+    // Rows: implementer header, a01, reviewer header, a02, a00; a02 stays selected
+    handle_key(&mut state, key(KeyCode::Char('T')));
+    assert!(state.ui.agent_grouping.grouped);
+    assert_eq!(state.ui.selected_agent_index, Some(3));
+
+    // Enter on a header folds its section
+    handle_key(&mut state, key(KeyCode::Char('k')));
+    handle_key(&mut state, key(KeyCode::Enter));
+    assert!(state.ui.agent_grouping.collapsed.contains("reviewer"));
+    handle_key(&mut state, key(KeyCode::Char('G')));
+    assert_eq!(state.ui.selected_agent_index, Some(2));
+
+    // t narrows to one type at a time, then back to all
+    handle_key(&mut state, key(KeyCode::Char('t')));
+    assert_eq!(state.ui.agent_grouping.type_filter.as_deref(), Some("implementer"));
+    handle_key(&mut state, key(KeyCode::Char('t')));
+    handle_key(&mut state, key(KeyCode::Char('t')));
+    assert_eq!(state.ui.agent_grouping.type_filter, None);
+}
+
+#[test]
+fn c_steps_the_dashboard_event_channel_and_wraps_to_all() {
+    use loom_tui::app::settings::{self, Settings};
+    let mut state = AppState::new();
+    let channels = vec!["tests: tool:Bash".to_string(), "writes: kind:pretool tool:Edit|Write".to_string()];
+    settings::apply(&mut state, &Settings { channels, ..Settings::default() });
+    state.ui.scroll_offsets.event_stream = 7;
+
+    handle_key(&mut state, key(KeyCode::Char('c')));
+    assert_eq!(state.ui.event_channels.current().map(|c| c.name.as_str()), Some("tests"));
+    assert_eq!(state.ui.scroll_offsets.event_stream, 0);
+    handle_key(&mut state, key(KeyCode::Char('c')));
+    handle_key(&mut state, key(KeyCode::Char('c')));
+    assert!(state.ui.event_channels.current().is_none());
+    handle_key(&mut state, key(KeyCode::Char('C')));
+    assert_eq!(state.ui.event_channels.current().map(|c| c.name.as_str()), Some("writes"));
+}
+
+#[test]
+fn applied_filter_survives_a_trip_to_agent_detail() {
+    let mut state = AppState::new();
+    handle_key(&mut state, key(KeyCode::Char('/')));
+    for c in "auth".chars() {
+        handle_key(&mut state, key(KeyCode::Char(c)));
+    }
+    handle_key(&mut state, key(KeyCode::Enter));
+
+    // Keys navigate again; AgentDetail starts without a filter
+    handle_key(&mut state, key(KeyCode::Char('2')));
+    assert!(matches!(state.ui.view, ViewState::AgentDetail));
+    assert!(state.ui.filter.is_none());
+
+    handle_key(&mut state, key(KeyCode::Char('1')));
+    assert_eq!(state.ui.filter.as_deref(), Some("auth"));
+    handle_key(&mut state, key(KeyCode::Char('x')));
+    assert!(state.ui.filter.is_none());
+}
+
+#[test]
+fn j_inspects_the_selected_step_as_json_and_copies_a_path() {
+    use loom_tui::model::{ToolName, TranscriptEvent, TranscriptEventKind};
+
+    let mut state = AppState::new();
+    state.domain.agents.insert(AgentId::new("a01"), Agent::new("a01", Utc::now()));
+    state.recompute_sorted_keys();
+    state.ui.view = ViewState::AgentDetail;
+    state.ui.selected_agent_index = Some(0);
+    let event = TranscriptEvent::new(
+        Utc::now(),
+        TranscriptEventKind::ToolUse { tool_name: ToolName::new("Bash"), input_summary: "cargo test".into() },
+    )
+    .with_agent("a01");
+    state.domain.events.push_back(event);
+
+    handle_key(&mut state, key(KeyCode::Char('J')));
+    assert!(state.ui.json_inspector.is_some());
+
+    // `/` searches keys inside the inspector; Y copies the match's path
+    handle_key(&mut state, key(KeyCode::Char('/')));
+    for c in "input".chars() {
+        handle_key(&mut state, key(KeyCode::Char(c)));
+    }
+    handle_key(&mut state, key(KeyCode::Enter));
+    assert!(state.ui.filter.is_none(), "the view's filter is untouched");
+    handle_key(&mut state, key(KeyCode::Char('Y')));
+    assert_eq!(state.meta.clipboard.as_deref(), Some(".input_summary"));
+
+    handle_key(&mut state, key(KeyCode::Esc));
+    assert!(state.ui.json_inspector.is_none());
+}
+
+#[test]
+fn timeline_view_scrolls_within_the_interleaved_events() {
+    use loom_tui::model::{TranscriptEvent, TranscriptEventKind};
+    let mut state = AppState::new();
+    for id in ["s1", "s2"] {
+        let mut meta = SessionMeta::new(id, Utc::now(), "/proj".to_string());
+        meta.confirmed = true;
+        state.domain.active_sessions.insert(SessionId::new(id), meta);
+    }
+    for session in ["s1", "s2", "s1"] {
+        state.domain.events.push_back(TranscriptEvent::new(Utc::now(), TranscriptEventKind::UserMessage).with_session(session));
+    }
+
+    handle_key(&mut state, key(KeyCode::Char('6')));
+    assert!(matches!(state.ui.view, ViewState::Timeline));
+    for _ in 0..5 {
+        handle_key(&mut state, key(KeyCode::Char('j')));
+    }
+    assert_eq!(state.ui.scroll_offsets.timeline, 2);
+    handle_key(&mut state, key(KeyCode::Char('g')));
+    assert_eq!(state.ui.scroll_offsets.timeline, 0);
+    handle_key(&mut state, key(KeyCode::Esc));
+    assert!(matches!(state.ui.view, ViewState::Dashboard));
+}
+
+#[test]
+fn column_keys_resize_the_focused_table_only() {
+    use loom_tui::app::columns::TableId;
+    let mut state = AppState::new();
+    for c in [')', '>', '>'] {
+        handle_key(&mut state, key(KeyCode::Char(c)));
+    }
+    assert!(state.ui.table_columns.get(TableId::Sessions).is_none(), "the Dashboard has no focused table");
+
+    state.ui.view = ViewState::Sessions;
+    state.ui.focus = PanelFocus::Left;
+    for c in [')', '>', '>', '(', '(', '<'] {
+        handle_key(&mut state, key(KeyCode::Char(c)));
+    }
+    let adjust = state.ui.table_columns.get(TableId::Sessions).unwrap();
+    assert_eq!((adjust.selected, adjust.offsets.as_slice()), (8, [0, 4, 0, 0, 0, 0, 0, 0, -2].as_slice()));
+
+    handle_key(&mut state, key(KeyCode::Char('=')));
+    assert!(state.ui.table_columns.get(TableId::Sessions).unwrap().offsets.is_empty());
+}
+
+#[test]
+fn shift_arrows_pan_the_focused_event_panel_within_its_widest_line() {
+    let shift = |code| KeyEvent::new(code, KeyModifiers::SHIFT);
+    let mut state = AppState::new();
+    state.ui.focus = PanelFocus::Right;
+    state.ui.scroll_limits.event_stream_x.set(Some(20));
+
+    for _ in 0..4 {
+        handle_key(&mut state, shift(KeyCode::Right));
+    }
+    assert_eq!(state.ui.scroll_offsets.event_stream_x, 20, "stops at the widest line");
+    handle_key(&mut state, shift(KeyCode::Left));
+    assert_eq!(state.ui.scroll_offsets.event_stream_x, 12);
+
+    state.ui.focus = PanelFocus::Left;
+    handle_key(&mut state, shift(KeyCode::Right));
+    assert_eq!(state.ui.scroll_offsets.event_stream_x, 12, "the task list does not pan");
+}
+
+#[test]
+fn w_toggles_wrapping_of_the_current_views_events_only() {
+    let mut state = AppState::new();
+    handle_key(&mut state, key(KeyCode::Char('w')));
+    assert!(state.ui.truncated_events.dashboard);
+    assert!(!state.ui.truncated_events.agent_detail);
+
+    state.ui.view = ViewState::SessionDetail;
+    handle_key(&mut state, key(KeyCode::Char('w')));
+    handle_key(&mut state, key(KeyCode::Char('w')));
+    assert!(!state.ui.truncated_events.session_detail);
+    assert!(state.ui.truncated_events.is_truncated(&ViewState::Dashboard), "kept per view");
+}
+
+#[test]
+fn evidence_key_jumps_to_the_failed_tasks_errors() {
+    use loom_tui::app::update;
+    use loom_tui::event::AppEvent;
+    use loom_tui::model::{ToolName, TranscriptEvent, TranscriptEventKind};
+    let mut state = AppState::new();
+    state.domain.agents.insert(AgentId::new("a01"), Agent::new("a01", Utc::now()));
+    state.recompute_sorted_keys();
+    let now = Utc::now();
+    let result = |secs: i64, is_error: bool| {
+        let kind = TranscriptEventKind::ToolResult {
+            tool_name: ToolName::new("Bash"),
+            result_summary: "exit 1".into(),
+            duration_ms: None,
+            is_error,
+        };
+        AppEvent::TranscriptEventReceived(TranscriptEvent::new(now - chrono::Duration::seconds(secs), kind).with_agent("a01"))
+    };
+    update(&mut state, result(30, true));
+    update(&mut state, result(20, false));
+
+    let mut task = Task::new("T1", "Parser".into(), TaskStatus::Failed { reason: "tests failed".into(), retry_count: 0 });
+    task.agent_id = Some("a01".into());
+    update(&mut state, AppEvent::TaskGraphUpdated(TaskGraph::new(vec![Wave { number: 1, tasks: vec![task] }])));
+    assert_eq!(state.domain.task_failures[&TaskId::new("T1")].evidence.len(), 1);
+
+    state.ui.selected_task_index = Some(0);
+    handle_key(&mut state, key(KeyCode::Char('E')));
+    assert_eq!(state.ui.view, ViewState::AgentDetail);
+    assert_eq!(state.ui.focus, PanelFocus::Right);
+    assert_eq!(state.ui.expanded_steps.len(), 1, "the step holding the error is expanded");
+}
@@ -1,12 +1,13 @@
 use chrono::Utc;
 use loom_tui::app::AppState;
-use loom_tui::model::{SessionArchive, SessionMeta, SessionStatus, TaskGraph, TranscriptEvent};
+use loom_tui::model::{Clock, ManualClock, SessionArchive, SessionMeta, SessionStatus, TaskGraph, TranscriptEvent};
+use loom_tui::error::SessionError;
 use loom_tui::session::{
-    auto_save_tick, build_archive, delete_session, generate_filename, list_session_metas,
-    list_sessions, load_session, save_session,
+    append_overflow, archive_stamp, auto_save_tick, build_archive, delete_session, generate_filename,
+    list_session_metas, list_sessions, load_session, lock_path, save_session,
+    save_session_guarded,
 };
 use std::collections::VecDeque;
-use std::time::{Duration, Instant};
 use tempfile::TempDir;
 
 // ============================================================================
@@ -49,6 +50,84 @@ fn save_creates_parent_directory() {
     assert!(nested_path.parent().unwrap().exists());
 }
 
+#[test]
+fn save_fails_while_another_instance_holds_the_lock() {
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.path().join("s1.json");
+    let archive = SessionArchive::new(SessionMeta::new("s1", Utc::now(), "/proj".to_string()));
+
+    // Simulate another instance mid-write
+    let other = std::fs::File::create(lock_path(&path)).unwrap();
+    other.lock().unwrap();
+
+    let result = save_session(&path, &archive);
+    assert!(matches!(result, Err(SessionError::Locked { .. })), "got {result:?}");
+    assert!(!path.exists());
+
+    other.unlock().unwrap();
+    save_session(&path, &archive).unwrap();
+    assert!(path.exists());
+}
+
+#[test]
+fn guarded_save_detects_foreign_write_and_last_writer_wins() {
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.path().join("s1.json");
+    let ours = SessionArchive::new(SessionMeta::new("s1", Utc::now(), "/ours".to_string()));
+
+    let first = save_session_guarded(&path, &ours, None).unwrap();
+    assert!(!first.clobbered);
+
+    // Our own stamp: no conflict
+    let second = save_session_guarded(&path, &ours, first.stamp).unwrap();
+    assert!(!second.clobbered);
+
+    // Another instance rewrites the archive (different size → different stamp)
+    let theirs = SessionArchive::new(SessionMeta::new("s1", Utc::now(), "/someone/else".to_string()));
+    save_session(&path, &theirs).unwrap();
+    assert_ne!(archive_stamp(&path), second.stamp);
+
+    let third = save_session_guarded(&path, &ours, second.stamp).unwrap();
+    assert!(third.clobbered);
+    assert_eq!(load_session(&path).unwrap(), ours);
+
+    // No temp files left behind
+    let leftovers: Vec<_> = std::fs::read_dir(tmpdir.path())
+        .unwrap()
+        .flatten()
+        .filter(|e| e.path().to_string_lossy().ends_with(".tmp"))
+        .collect();
+    assert!(leftovers.is_empty());
+}
+
+#[test]
+fn delete_session_removes_lock_record() {
+    let tmpdir = TempDir::new().unwrap();
+    let path = tmpdir.path().join("s1.json");
+    save_session(&path, &SessionArchive::new(SessionMeta::new("s1", Utc::now(), "/p".to_string()))).unwrap();
+    assert!(lock_path(&path).exists());
+
+    delete_session(&path).unwrap();
+    assert!(!path.exists());
+    assert!(!lock_path(&path).exists());
+}
+
+#[test]
+fn append_overflow_writes_one_jsonl_log_per_session() {
+    use loom_tui::model::TranscriptEventKind;
+    let tmpdir = TempDir::new().unwrap();
+    let dir = tmpdir.path().join("overflow");
+    let event = |sid: &str| TranscriptEvent::new(Utc::now(), TranscriptEventKind::UserMessage).with_session(sid);
+
+    append_overflow(&dir, &[event("s1"), event("s2")]).unwrap();
+    append_overflow(&dir, &[event("s1")]).unwrap();
+
+    let s1 = std::fs::read_to_string(dir.join("s1.jsonl")).unwrap();
+    assert_eq!(s1.lines().count(), 2);
+    assert!(s1.lines().all(|line| serde_json::from_str::<TranscriptEvent>(line).is_ok()));
+    assert_eq!(std::fs::read_to_string(dir.join("s2.jsonl")).unwrap().lines().count(), 1);
+}
+
 #[test]
 fn load_nonexistent_file_returns_error() {
     let tmpdir = TempDir::new().unwrap();
@@ -202,11 +281,13 @@ fn auto_save_tick_saves_when_interval_elapsed() {
     let meta = SessionMeta::new("s1", Utc::now(), "/proj".to_string());
     let archive = SessionArchive::new(meta);
 
-    // Simulate last save 31 seconds ago
-    let last_save = Instant::now() - Duration::from_secs(31);
+    // Last save 31 seconds ago
+    let clock = ManualClock::new(Utc::now());
+    let last_save = clock.instant();
+    clock.advance(chrono::Duration::seconds(31));
 
     // Auto-save should trigger
-    let result = auto_save_tick(&path, &archive, last_save, 30).unwrap();
+    let result = auto_save_tick(&path, &archive, last_save, 30, &clock).unwrap();
     assert!(result.is_some());
     assert!(path.exists());
 }
@@ -219,11 +300,13 @@ fn auto_save_tick_does_not_save_before_interval() {
     let meta = SessionMeta::new("s1", Utc::now(), "/proj".to_string());
     let archive = SessionArchive::new(meta);
 
-    // Simulate last save 5 seconds ago
-    let last_save = Instant::now() - Duration::from_secs(5);
+    // Last save 5 seconds ago
+    let clock = ManualClock::new(Utc::now());
+    let last_save = clock.instant();
+    clock.advance(chrono::Duration::seconds(5));
 
     // Auto-save should not trigger
-    let result = auto_save_tick(&path, &archive, last_save, 30).unwrap();
+    let result = auto_save_tick(&path, &archive, last_save, 30, &clock).unwrap();
     assert!(result.is_none());
     assert!(!path.exists());
 }
@@ -366,10 +449,12 @@ fn auto_save_tick_returns_error_on_save_failure() {
     let meta = SessionMeta::new("s1", Utc::now(), "/proj".to_string());
     let archive = SessionArchive::new(meta);
 
-    // Simulate last save 31 seconds ago (interval elapsed)
-    let last_save = Instant::now() - Duration::from_secs(31);
+    // Last save 31 seconds ago (interval elapsed)
+    let clock = ManualClock::new(Utc::now());
+    let last_save = clock.instant();
+    clock.advance(chrono::Duration::seconds(31));
 
     // Auto-save should return error
-    let result = auto_save_tick(path, &archive, last_save, 30);
+    let result = auto_save_tick(path, &archive, last_save, 30, &clock);
     assert!(result.is_err());
 }
@@ -438,6 +438,7 @@ fn multiple_updates_compose_correctly() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn update_preserves_unmodified_fields() {
     let mut state = AppState::new();
     state.ui.view = ViewState::Sessions;
@@ -453,6 +454,20 @@ fn update_preserves_unmodified_fields() {
     assert_eq!(state.ui.show_help, true);
 }
 
+#[test]
+fn schema_drift_logs_each_unknown_field_once() {
+    let mut state = AppState::new();
+    let mut drift = loom_tui::watcher::schema_drift::SchemaDrift::default();
+    drift.record_entry(&serde_json::json!({ "type": "user", "effortLevel": "high" }));
+
+    update(&mut state, AppEvent::SchemaDriftObserved(drift.clone()));
+    update(&mut state, AppEvent::SchemaDriftObserved(drift));
+
+    assert_eq!(state.domain.schema_drift.by_frequency(), [("entry.effortLevel", 2)]);
+    assert_eq!(state.meta.errors.len(), 1);
+    assert!(state.meta.errors[0].contains("entry.effortLevel"));
+}
+
 #[test]
 fn property_event_buffer_never_exceeds_10000() {
     let mut state = AppState::new();
@@ -523,3 +538,75 @@ fn property_update_never_panics() {
         update(&mut state, event); // Should never panic
     }
 }
+
+#[test]
+fn sessions_join_the_running_orchestration_and_hooks_can_name_theirs() {
+    let mut state = AppState::new();
+    let graph = TaskGraph::new(vec![Wave::new(1, vec![Task::new("T1", "Build".to_string(), TaskStatus::Running)])])
+        .with_run_id(Some("auth-3".to_string()));
+    update(&mut state, AppEvent::TaskGraphUpdated(graph));
+
+    for id in ["worker", "tagged"] {
+        state.domain.active_sessions.insert(SessionId::new(id), SessionMeta::new(id, Utc::now(), "/proj".to_string()));
+    }
+    update(
+        &mut state,
+        AppEvent::SessionRunTagged { session_id: SessionId::new("tagged"), run_id: "billing-1".to_string() },
+    );
+    for id in ["worker", "tagged"] {
+        let event = TranscriptEvent::new(Utc::now(), TranscriptEventKind::UserMessage).with_session(id);
+        update(&mut state, AppEvent::TranscriptEventReceived(event));
+    }
+
+    let run = |id: &str| state.domain.active_sessions[&SessionId::new(id)].loom_plan_id.clone();
+    assert_eq!(run("worker").as_deref(), Some("auth-3"));
+    assert_eq!(run("tagged").as_deref(), Some("billing-1"));
+}
+
+#[test]
+fn finished_write_is_uncounted_and_its_error_raised() {
+    use loom_tui::app::in_flight::IoKind;
+    let mut state = AppState::new();
+    state.meta.in_flight.start(IoKind::Save);
+
+    update(&mut state, AppEvent::WriteFinished { kind: IoKind::Save, error: Some("auto-save: disk full".into()) });
+    assert_eq!(state.meta.in_flight.writes(), 0);
+    assert_eq!(state.meta.errors.back().map(String::as_str), Some("auto-save: disk full"));
+    assert_eq!(state.ui.toasts.iter().count(), 1);
+}
+
+#[test]
+fn only_a_newer_release_is_announced() {
+    use loom_tui::app::updates::Release;
+    let mut state = AppState::new();
+    let release = |version: &str| Release { version: version.to_string(), notes: "- faster parser".to_string() };
+
+    update(&mut state, AppEvent::ReleaseChecked(Ok(release(SessionArchive::BUILD_VERSION))));
+    update(&mut state, AppEvent::ReleaseChecked(Err("curl: offline".to_string())));
+    assert!(state.meta.newer_release.is_none());
+    assert!(state.meta.errors.is_empty());
+
+    update(&mut state, AppEvent::ReleaseChecked(Ok(release("99.0.0"))));
+    assert_eq!(state.meta.newer_release.as_ref().map(|r| r.version.as_str()), Some("99.0.0"));
+}
+
+#[test]
+fn status_updates_become_the_agents_task_progress() {
+    let mut state = AppState::new();
+    let mut task = Task::new("T1", "Parser".into(), TaskStatus::Running);
+    task.agent_id = Some("a01".into());
+    update(&mut state, AppEvent::TaskGraphUpdated(TaskGraph::new(vec![Wave { number: 1, tasks: vec![task] }])));
+
+    let status = |text: &str, task_id: Option<&str>| {
+        let kind = TranscriptEventKind::StatusUpdate { text: text.into(), task_id: task_id.map(Into::into) };
+        AppEvent::TranscriptEventReceived(TranscriptEvent::new(Utc::now(), kind).with_agent("a01"))
+    };
+    update(&mut state, status("loom-status: building parser 40%", None));
+    assert_eq!(state.domain.task_progress.get(&"T1".into()).map(String::as_str), Some("building parser 40%"));
+
+    update(&mut state, status("T2 waiting on T1", Some("T2")));
+    assert_eq!(state.domain.task_progress.len(), 2, "an update naming its task goes there");
+
+    update(&mut state, status("loom-status:", None));
+    assert!(!state.domain.task_progress.contains_key(&"T1".into()));
+}
@@ -1,6 +1,11 @@
+mod common;
+
+use common::Screen;
 use loom_tui::app::{AppState, PanelFocus, ViewState};
-use loom_tui::model::{Task, TaskGraph, TaskStatus, TranscriptEvent, TranscriptEventKind, Wave};
+use loom_tui::model::{ColorMode, Task, TaskGraph, TaskStatus, Theme, TranscriptEvent, TranscriptEventKind, Wave};
 use ratatui::backend::TestBackend;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
 use ratatui::Terminal;
 
 use chrono::Utc;
@@ -73,6 +78,7 @@ fn dashboard_renders_without_panic_with_events() {
                 tool_name: "Read".into(),
                 result_summary: "success".to_string(),
                 duration_ms: Some(150),
+                is_error: false,
             },
         ),
     ];
@@ -602,6 +608,19 @@ fn view_render_with_both_overlays() {
         .unwrap();
 }
 
+#[test]
+fn zoomed_view_renders_only_the_focused_panel() {
+    let mut state = AppState::new();
+    state.ui.zoomed = true;
+    state.ui.focus = PanelFocus::Right;
+
+    let screen = Screen::render(&state, 120, 40);
+
+    assert!(screen.contains("Events"), "Focused event stream should fill the content area");
+    assert!(!screen.contains("Tasks"), "Unfocused task list should be hidden");
+    assert!(screen.contains("z:restore"));
+}
+
 // Scroll offset clamping tests (FR-C2)
 
 #[test]
@@ -655,3 +674,113 @@ fn event_stream_handles_large_scroll_offset() {
         })
         .unwrap();
 }
+
+#[test]
+fn quit_with_writes_in_flight_shows_finishing_screen() {
+    use loom_tui::app::in_flight::IoKind;
+    let mut state = AppState::new();
+    state.meta.in_flight.start(IoKind::Save);
+    state.meta.should_quit = true;
+
+    let screen = Screen::render(&state, 60, 10);
+    assert!(screen.contains("finishing writes…"));
+    assert!(screen.contains("1 save"));
+}
+
+// Render harness: focus indicators, empty states, overflow
+
+fn long_task_state() -> AppState {
+    let mut state = AppState::new();
+    let description = format!("Migrate the session archive format {}", "and keep old archives readable ".repeat(10));
+    state.domain.task_graph = Some(TaskGraph::new(vec![Wave::new(
+        1,
+        vec![Task::new("T1", description, TaskStatus::Running), Task::new("T2", "Short".into(), TaskStatus::Pending)],
+    )]));
+    state
+}
+
+#[test]
+fn focus_border_follows_panel_focus_on_the_dashboard() {
+    let mut state = AppState::new();
+    let screen = Screen::render(&state, 120, 40);
+    assert!(screen.is_focused("Tasks"));
+    assert!(!screen.is_focused("Events"));
+    assert_eq!(screen.border_color("Events"), Some(Theme::PANEL_BORDER));
+
+    state.ui.focus = PanelFocus::Right;
+    let screen = Screen::render(&state, 120, 40);
+    assert!(!screen.is_focused("Tasks"));
+    assert!(screen.is_focused("Events"));
+}
+
+#[test]
+fn focus_border_follows_panel_focus_in_agent_detail() {
+    let mut state = AppState::with_view(ViewState::AgentDetail);
+    state.ui.focus = PanelFocus::Left;
+    let screen = Screen::render(&state, 120, 40);
+    assert!(screen.is_focused("Agents") && !screen.is_focused("Activity"));
+
+    state.ui.focus = PanelFocus::Right;
+    let screen = Screen::render(&state, 120, 40);
+    assert!(!screen.is_focused("Agents") && screen.is_focused("Activity"));
+}
+
+#[test]
+fn without_color_focus_is_labelled_in_the_focused_border() {
+    let mut state = AppState::new();
+    state.ui.color_mode = ColorMode::NoColor;
+    let screen = Screen::render(&state, 120, 40);
+
+    // Focus can't be told by border color: the label marks the task list
+    let label = screen.find("FOCUSED").expect("focus label");
+    let events = screen.find_title("Events").unwrap();
+    assert_eq!(label.y, events.y);
+    assert!(label.x < events.x, "label belongs to the left panel");
+    assert_eq!(screen.region_fg(Rect::new(0, 0, 120, 40)), Some(Color::Reset));
+}
+
+#[test]
+fn empty_views_say_what_they_are_waiting_for() {
+    let cases = [
+        (ViewState::Dashboard, ["No waves — waiting for task graph", "No tasks — waiting for task graph", "No events"].as_slice()),
+        (ViewState::AgentDetail, &["No agents", "Select an agent to view activity"]),
+        (ViewState::Sessions, &["No archived sessions", "Sessions will appear here after completion"]),
+        (ViewState::SessionDetail, &["No session selected"]),
+    ];
+    for (view, messages) in cases {
+        let screen = Screen::render(&AppState::with_view(view.clone()), 120, 40);
+        for message in messages {
+            assert!(screen.contains(message), "{view:?} should show {message:?}:\n{}", screen.text());
+        }
+    }
+
+    // Placeholders are muted, so they don't read as data
+    let screen = Screen::render(&AppState::with_view(ViewState::AgentDetail), 120, 40);
+    let placeholder = screen.find("No agents").unwrap();
+    assert_eq!(screen.fg(placeholder.x, placeholder.y), Theme::MUTED_TEXT);
+}
+
+#[test]
+fn long_task_descriptions_are_cut_inside_their_panel() {
+    let screen = Screen::render(&long_task_state(), 120, 40);
+    let row = screen.find("T1 Migrate").expect("task row");
+    let tasks = screen.find_title("Tasks").unwrap();
+
+    // The row ends in an ellipsis before the border, and the next task keeps its own row
+    let panel_right = (tasks.x..120).find(|&x| screen.text_at(x, tasks.y, 1) == "┐").unwrap();
+    let cut = screen.text_at(row.x, row.y, panel_right - row.x);
+    assert!(cut.trim_end().ends_with("..."), "{cut:?}");
+    assert_eq!(screen.text_at(panel_right, row.y, 1), "│");
+    assert_eq!(screen.find("T2 Short").map(|p| p.y), Some(row.y + 1));
+}
+
+#[test]
+fn narrow_terminals_keep_panel_borders_closed() {
+    for (width, height) in [(80, 24), (60, 20), (40, 12)] {
+        let screen = Screen::render(&long_task_state(), width, height);
+        let tasks = screen.find_title("Tasks").expect("task list title");
+        let left_edge = screen.column(tasks.x - 1);
+        let body: String = left_edge.chars().skip(tasks.y as usize + 1).take_while(|&c| c != '└').collect();
+        assert!(!body.is_empty() && body.chars().all(|c| c == '│'), "{width}x{height}:\n{}", screen.text());
+    }
+}